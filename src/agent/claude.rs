@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde_json::json;
@@ -8,13 +9,18 @@ use tokio::sync::mpsc;
 
 use crate::agent::error::AgentError;
 use crate::agent::events::{
-    AgentEvent, AssistantMessageEvent, ControlRequestEvent, ErrorEvent, SessionInitEvent,
-    TokenUsage, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
+    AgentEvent, AssistantMessageEvent, ControlRequestEvent, ErrorEvent, PermissionDecidedEvent,
+    SessionInitEvent, TokenUsage, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent,
+    TurnFailedEvent,
 };
+use crate::agent::permissions::{PermissionDecision, PermissionGate, PermissionRequest};
 use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
 use crate::agent::stream::{ClaudeRawEvent, JsonlStreamParser};
 
+/// Tools pre-approved in print mode, where permission prompts can't be answered
+const PRINT_MODE_ALLOWED_TOOLS: &[&str] = &["Read", "Edit", "Write", "Bash", "Glob", "Grep"];
+
 pub struct ClaudeCodeRunner {
     binary_path: PathBuf,
 }
@@ -54,18 +60,16 @@ impl ClaudeCodeRunner {
                               // --verbose
         if use_stream_input {
             cmd.arg("--permission-prompt-tool").arg("stdio");
+        } else if config.permissions.is_allow_all() {
+            // Print mode can't be asked, so pre-approve the common tools
+            cmd.arg("--allowedTools")
+                .arg(PRINT_MODE_ALLOWED_TOOLS.join(","));
         }
 
         // Permission mode (Build vs Plan)
         cmd.arg("--permission-mode")
             .arg(config.agent_mode.as_permission_mode());
 
-        // Allowed tools
-        if !config.allowed_tools.is_empty() {
-            cmd.arg("--allowedTools")
-                .arg(config.allowed_tools.join(","));
-        }
-
         // Resume session if provided
         if let Some(session_id) = &config.resume_session {
             cmd.arg("--resume").arg(session_id.as_str());
@@ -245,11 +249,19 @@ impl ClaudeCodeRunner {
                 // Use default values if usage is not provided
                 let usage = res
                     .usage
-                    .map(|u| TokenUsage {
-                        input_tokens: u.input_tokens.unwrap_or(0),
-                        output_tokens: u.output_tokens.unwrap_or(0),
-                        cached_tokens: 0,
-                        total_tokens: u.input_tokens.unwrap_or(0) + u.output_tokens.unwrap_or(0),
+                    .map(|u| {
+                        // Claude reports cache reads/writes separately from input_tokens
+                        let cache_read = u.cache_read_input_tokens.unwrap_or(0);
+                        let cache_write = u.cache_creation_input_tokens.unwrap_or(0);
+                        let input_tokens = u.input_tokens.unwrap_or(0) + cache_read + cache_write;
+                        let output_tokens = u.output_tokens.unwrap_or(0);
+                        TokenUsage {
+                            input_tokens,
+                            output_tokens,
+                            cached_tokens: cache_read,
+                            cache_write_tokens: cache_write,
+                            total_tokens: input_tokens + output_tokens,
+                        }
                     })
                     .unwrap_or_default();
 
@@ -311,6 +323,29 @@ impl ClaudeCodeRunner {
         Ok(format!("{json}\n"))
     }
 
+    /// Response to a `can_use_tool` control request
+    fn build_permission_payload(
+        allow: bool,
+        input: &serde_json::Value,
+        tool_use_id: Option<&str>,
+    ) -> serde_json::Value {
+        let mut payload = serde_json::Map::new();
+        if allow {
+            payload.insert("behavior".to_string(), json!("allow"));
+            payload.insert("updatedInput".to_string(), input.clone());
+        } else {
+            payload.insert("behavior".to_string(), json!("deny"));
+            payload.insert(
+                "message".to_string(),
+                json!("Denied by the conduit permission policy"),
+            );
+        }
+        if let Some(tool_use_id) = tool_use_id {
+            payload.insert("toolUseID".to_string(), json!(tool_use_id));
+        }
+        serde_json::Value::Object(payload)
+    }
+
     fn is_interactive_tool(tool_name: &str) -> bool {
         matches!(tool_name, "AskUserQuestion" | "ExitPlanMode")
     }
@@ -338,6 +373,10 @@ impl AgentRunner for ClaudeCodeRunner {
             .is_some_and(|format| format == "stream-json");
         let stdin_payload = config.stdin_payload.clone();
         let mut input_tx: Option<mpsc::Sender<AgentInput>> = None;
+        let permissions: Arc<PermissionGate<(serde_json::Value, Option<String>)>> = Arc::new(
+            PermissionGate::new(config.permissions.clone(), config.working_dir.clone()),
+        );
+        let permissions_for_input = permissions.clone();
 
         if let Some(stdin) = child.stdin.take() {
             if use_stream_input {
@@ -386,6 +425,31 @@ impl AgentRunner for ClaudeCodeRunner {
                                     "Ignored OpenCode question response sent to Claude input channel"
                                 );
                             }
+                            AgentInput::PermissionResponse { request_id, reply } => {
+                                let Some((input, tool_use_id)) =
+                                    permissions_for_input.resolve(&request_id, reply)
+                                else {
+                                    tracing::warn!(
+                                        request_id,
+                                        "Permission response for unknown Claude request"
+                                    );
+                                    continue;
+                                };
+                                let payload = Self::build_permission_payload(
+                                    reply.is_allowed(),
+                                    &input,
+                                    tool_use_id.as_deref(),
+                                );
+                                let Ok(line) =
+                                    Self::build_control_response_jsonl(&request_id, payload)
+                                else {
+                                    continue;
+                                };
+                                if let Err(err) = stdin.write_all(line.as_bytes()).await {
+                                    tracing::error!("Failed to write to Claude stdin: {}", err);
+                                    break;
+                                }
+                            }
                         }
                     }
                     if let Err(err) = stdin.shutdown().await {
@@ -455,6 +519,7 @@ impl AgentRunner for ClaudeCodeRunner {
                                     tool_name: tool_name.clone(),
                                     tool_use_id: tool_use_id.clone(),
                                     input: input.clone(),
+                                    permission: None,
                                 });
                                 if tx.send(event).await.is_err() {
                                     break 'outer;
@@ -465,25 +530,60 @@ impl AgentRunner for ClaudeCodeRunner {
                                         "Control request for interactive tool received without stdin channel"
                                     );
                                 }
-                            } else if let Some(ref tx) = control_tx {
-                                let mut response_payload = serde_json::Map::new();
-                                response_payload.insert("behavior".to_string(), json!("allow"));
-                                response_payload.insert("updatedInput".to_string(), input.clone());
-                                if let Some(tool_use_id) = tool_use_id.as_ref() {
-                                    response_payload
-                                        .insert("toolUseID".to_string(), json!(tool_use_id));
-                                }
-                                if let Ok(response) = Self::build_control_response_jsonl(
-                                    &request.request_id,
-                                    serde_json::Value::Object(response_payload),
-                                ) {
-                                    if let Err(err) =
-                                        tx.send(AgentInput::ClaudeJsonl(response)).await
-                                    {
-                                        tracing::warn!(
-                                            "Failed to respond to control request: {}",
-                                            err
+                            } else {
+                                let permission =
+                                    PermissionRequest::from_tool_input(tool_name, input);
+                                let decision = permissions.evaluate(&permission);
+                                let response_payload = match decision {
+                                    PermissionDecision::Allow | PermissionDecision::Deny => {
+                                        let event =
+                                            AgentEvent::PermissionDecided(PermissionDecidedEvent {
+                                                request_id: request.request_id.clone(),
+                                                permission,
+                                                decision,
+                                            });
+                                        if tx.send(event).await.is_err() {
+                                            break 'outer;
+                                        }
+                                        Self::build_permission_payload(
+                                            decision == PermissionDecision::Allow,
+                                            input,
+                                            tool_use_id.as_deref(),
+                                        )
+                                    }
+                                    PermissionDecision::Ask => {
+                                        permissions.park(
+                                            request.request_id.clone(),
+                                            permission.clone(),
+                                            (input.clone(), tool_use_id.clone()),
                                         );
+                                        let event =
+                                            AgentEvent::ControlRequest(ControlRequestEvent {
+                                                request_id: request.request_id.clone(),
+                                                tool_name: tool_name.clone(),
+                                                tool_use_id: tool_use_id.clone(),
+                                                input: input.clone(),
+                                                permission: Some(permission),
+                                            });
+                                        if tx.send(event).await.is_err() {
+                                            break 'outer;
+                                        }
+                                        continue 'outer;
+                                    }
+                                };
+                                if let Some(ref tx) = control_tx {
+                                    if let Ok(response) = Self::build_control_response_jsonl(
+                                        &request.request_id,
+                                        response_payload,
+                                    ) {
+                                        if let Err(err) =
+                                            tx.send(AgentInput::ClaudeJsonl(response)).await
+                                        {
+                                            tracing::warn!(
+                                                "Failed to respond to control request: {}",
+                                                err
+                                            );
+                                        }
                                     }
                                 }
                             }
@@ -693,6 +793,8 @@ mod tests {
                 usage: Some(ClaudeUsage {
                     input_tokens: Some(100),
                     output_tokens: Some(50),
+                    cache_read_input_tokens: None,
+                    cache_creation_input_tokens: None,
                 }),
             }),
             text: None,
@@ -725,6 +827,8 @@ mod tests {
             usage: Some(ClaudeUsage {
                 input_tokens: Some(0),
                 output_tokens: Some(0),
+                cache_read_input_tokens: None,
+                cache_creation_input_tokens: None,
             }),
         });
 
@@ -741,6 +845,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_convert_result_usage_includes_cache_tokens() {
+        let raw = ClaudeRawEvent::Result(ClaudeResultEvent {
+            result: Some("done".to_string()),
+            output: None,
+            is_error: Some(false),
+            error: None,
+            session_id: Some("test-session".to_string()),
+            usage: Some(ClaudeUsage {
+                input_tokens: Some(10),
+                output_tokens: Some(200),
+                cache_read_input_tokens: Some(5_000),
+                cache_creation_input_tokens: Some(1_000),
+            }),
+        });
+
+        let events = ClaudeCodeRunner::convert_event(raw);
+        match &events[0] {
+            AgentEvent::TurnCompleted(completed) => {
+                assert_eq!(completed.usage.input_tokens, 6_010);
+                assert_eq!(completed.usage.cached_tokens, 5_000);
+                assert_eq!(completed.usage.cache_write_tokens, 1_000);
+                assert_eq!(completed.usage.total_tokens, 6_210);
+            }
+            other => panic!("Expected TurnCompleted, got {:?}", other),
+        }
+    }
+
     /// Test the full auth failure sequence conversion
     /// This simulates what happens when Claude CLI returns an auth error
     #[test]
@@ -775,6 +907,8 @@ mod tests {
                 usage: Some(ClaudeUsage {
                     input_tokens: Some(0),
                     output_tokens: Some(0),
+                    cache_read_input_tokens: None,
+                    cache_creation_input_tokens: None,
                 }),
             }),
         ];
@@ -27,9 +27,14 @@ use tokio::sync::{mpsc, oneshot, Mutex};
 
 use crate::agent::error::AgentError;
 use crate::agent::events::{
-    AgentEvent, AssistantMessageEvent, CommandOutputEvent, ContextCompactionEvent, ErrorEvent,
-    FileChangedEvent, FileOperation, ReasoningEvent, SessionInitEvent, TokenUsage, TokenUsageEvent,
-    ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
+    AgentEvent, AssistantMessageEvent, CommandOutputEvent, ContextCompactionEvent,
+    ControlRequestEvent, ErrorEvent, FileChangedEvent, FileOperation, PermissionDecidedEvent,
+    ReasoningEvent, SessionInitEvent, TokenUsage, TokenUsageEvent, ToolCompletedEvent,
+    ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
+};
+use crate::agent::permissions::{
+    PermissionDecision, PermissionGate, PermissionPolicy, PermissionRequest,
+    SandboxMode as PermissionSandbox,
 };
 use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
@@ -107,13 +112,28 @@ impl JsonRpcPeer {
 struct CodexEventState {
     exec_command_by_id: HashMap<String, String>,
     exec_output_by_id: HashMap<String, String>,
-    last_usage: Option<TokenUsage>,
+    /// Usage accumulated from each model request in the current turn
+    turn_usage: TokenUsage,
     last_total_tokens: Option<i64>,
     pending_compaction: bool,
     message_stream_source: Option<MessageStreamSource>,
     reasoning_stream_source: Option<ReasoningStreamSource>,
 }
 
+/// Approval request from the app-server waiting for a decision
+enum CodexApproval {
+    Patch(RequestId),
+    Exec(RequestId),
+}
+
+impl CodexApproval {
+    /// Key used as the control request ID
+    fn key(&self) -> String {
+        let (Self::Patch(id) | Self::Exec(id)) = self;
+        serde_json::to_string(id).unwrap_or_default()
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum MessageStreamSource {
     LegacyDelta,
@@ -159,30 +179,113 @@ impl CodexCliRunner {
         }
     }
 
-    fn approval_policy() -> AskForApproval {
-        match std::env::var("CODEX_APPROVAL_POLICY")
-            .unwrap_or_else(|_| "never".to_string())
-            .to_lowercase()
-            .as_str()
-        {
-            "untrusted" => AskForApproval::UnlessTrusted,
-            "on-failure" => AskForApproval::OnFailure,
-            "on-request" => AskForApproval::OnRequest,
-            "never" => AskForApproval::Never,
-            _ => AskForApproval::Never,
+    /// Only let Codex run without asking when the policy would allow everything anyway
+    fn approval_policy(permissions: &PermissionPolicy) -> AskForApproval {
+        if permissions.is_allow_all() {
+            AskForApproval::Never
+        } else {
+            AskForApproval::UnlessTrusted
         }
     }
 
-    fn sandbox_mode() -> SandboxMode {
-        match std::env::var("CODEX_SANDBOX_MODE")
-            .unwrap_or_else(|_| "danger-full-access".to_string())
-            .to_lowercase()
-            .as_str()
-        {
-            "read-only" => SandboxMode::ReadOnly,
-            "workspace-write" => SandboxMode::WorkspaceWrite,
-            "danger-full-access" => SandboxMode::DangerFullAccess,
-            _ => SandboxMode::DangerFullAccess,
+    fn sandbox_mode(sandbox: PermissionSandbox) -> SandboxMode {
+        match sandbox {
+            PermissionSandbox::ReadOnly => SandboxMode::ReadOnly,
+            PermissionSandbox::WorkspaceWrite => SandboxMode::WorkspaceWrite,
+            PermissionSandbox::DangerFullAccess => SandboxMode::DangerFullAccess,
+        }
+    }
+
+    /// Normalize an approval request's params for the permission policy,
+    /// returning the request and the Codex call ID
+    fn permission_request(
+        method: &str,
+        params: Option<&Value>,
+    ) -> (PermissionRequest, Option<String>) {
+        let params = params.cloned().unwrap_or(Value::Null);
+        let call_id = params
+            .get("callId")
+            .or_else(|| params.get("call_id"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let request = if method.to_ascii_lowercase().contains("patch") {
+            let mut request = PermissionRequest::new("Edit");
+            if let Some(Value::Object(changes)) = params
+                .get("fileChanges")
+                .or_else(|| params.get("file_changes"))
+            {
+                for path in changes.keys() {
+                    request = request.with_path(path.clone());
+                }
+            }
+            request
+        } else {
+            PermissionRequest::from_tool_input("Bash", &params)
+        };
+        (request, call_id)
+    }
+
+    /// Answer an approval request from the policy, or ask the user
+    async fn handle_approval(
+        peer: &JsonRpcPeer,
+        permissions: &PermissionGate<CodexApproval>,
+        event_tx: &mpsc::Sender<AgentEvent>,
+        approval: CodexApproval,
+        permission: PermissionRequest,
+        call_id: Option<String>,
+    ) {
+        let decision = permissions.evaluate(&permission);
+        match decision {
+            PermissionDecision::Allow | PermissionDecision::Deny => {
+                let event = AgentEvent::PermissionDecided(PermissionDecidedEvent {
+                    request_id: approval.key(),
+                    permission,
+                    decision,
+                });
+                if let Err(err) = event_tx.send(event).await {
+                    tracing::debug!(error = ?err, "Failed to send Codex permission decision");
+                }
+                Self::send_approval(peer, approval, decision == PermissionDecision::Allow).await
+            }
+            PermissionDecision::Ask => {
+                let request_id = approval.key();
+                let event = AgentEvent::ControlRequest(ControlRequestEvent {
+                    request_id: request_id.clone(),
+                    tool_name: permission.tool.clone(),
+                    tool_use_id: call_id,
+                    input: serde_json::to_value(&permission).unwrap_or(Value::Null),
+                    permission: Some(permission.clone()),
+                });
+                permissions.park(request_id, permission, approval);
+                if let Err(err) = event_tx.send(event).await {
+                    tracing::debug!(error = ?err, "Failed to send Codex permission request");
+                }
+            }
+        }
+    }
+
+    async fn send_approval(peer: &JsonRpcPeer, approval: CodexApproval, approved: bool) {
+        let decision = if approved {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::Denied
+        };
+        let (id, result) = match approval {
+            CodexApproval::Patch(id) => (
+                id,
+                serde_json::to_value(ApplyPatchApprovalResponse { decision }),
+            ),
+            CodexApproval::Exec(id) => (
+                id,
+                serde_json::to_value(ExecCommandApprovalResponse { decision }),
+            ),
+        };
+        let response = JSONRPCResponse {
+            id,
+            result: result.unwrap_or(Value::Null),
+        };
+        if let Err(err) = peer.send(&response).await {
+            tracing::warn!(error = %err, "Failed to send Codex approval response");
         }
     }
 
@@ -262,7 +365,7 @@ impl CodexCliRunner {
                 vec![AgentEvent::TurnStarted]
             }
             EventMsg::TurnComplete(_) => {
-                let usage = state.last_usage.clone().unwrap_or_default();
+                let usage = std::mem::take(&mut state.turn_usage);
                 state.message_stream_source = None;
                 state.reasoning_stream_source = None;
                 vec![AgentEvent::TurnCompleted(TurnCompletedEvent { usage })]
@@ -507,6 +610,7 @@ impl CodexCliRunner {
                         input_tokens: total.input_tokens,
                         output_tokens: total.output_tokens,
                         cached_tokens: total.cached_input_tokens,
+                        cache_write_tokens: 0,
                         total_tokens: total.total_tokens,
                     };
                     let context_window = info.model_context_window;
@@ -519,7 +623,15 @@ impl CodexCliRunner {
                     });
                     let previous_total = state.last_total_tokens;
                     state.last_total_tokens = Some(usage.total_tokens);
-                    state.last_usage = Some(usage.clone());
+                    // total_token_usage is cumulative for the thread; bill per request.
+                    // A repeated count (e.g. a rate-limit update) carries no new request.
+                    if previous_total != Some(usage.total_tokens) {
+                        let last = &info.last_token_usage;
+                        state.turn_usage.input_tokens += last.input_tokens;
+                        state.turn_usage.output_tokens += last.output_tokens;
+                        state.turn_usage.cached_tokens += last.cached_input_tokens;
+                        state.turn_usage.total_tokens += last.total_tokens;
+                    }
 
                     if let Some(prev) = previous_total {
                         if state.pending_compaction && prev > 0 {
@@ -636,6 +748,10 @@ impl AgentRunner for CodexCliRunner {
         let stderr = child.stderr.take();
 
         let peer = JsonRpcPeer::new(stdin);
+        let permissions = Arc::new(PermissionGate::new(
+            config.permissions.clone(),
+            config.working_dir.clone(),
+        ));
 
         let (tx, rx) = mpsc::channel::<AgentEvent>(256);
         let tx_for_monitor = tx.clone();
@@ -643,6 +759,7 @@ impl AgentRunner for CodexCliRunner {
 
         // Spawn JSON-RPC read loop
         let reader_peer = peer.clone();
+        let reader_permissions = permissions.clone();
         tokio::spawn(async move {
             let mut reader = BufReader::new(stdout);
             let mut buffer = String::new();
@@ -692,48 +809,29 @@ impl AgentRunner for CodexCliRunner {
                                 }
                             }
                             Ok(JSONRPCMessage::Request(request)) => {
+                                let (permission, call_id) = Self::permission_request(
+                                    &request.method,
+                                    request.params.as_ref(),
+                                );
                                 if let Ok(server_req) = ServerRequest::try_from(request) {
-                                    match server_req {
+                                    let approval = match server_req {
                                         ServerRequest::ApplyPatchApproval {
                                             request_id, ..
-                                        } => {
-                                            let response = JSONRPCResponse {
-                                                id: request_id,
-                                                result: serde_json::to_value(
-                                                    ApplyPatchApprovalResponse {
-                                                        decision: ReviewDecision::Approved,
-                                                    },
-                                                )
-                                                .unwrap_or(Value::Null),
-                                            };
-                                            if let Err(err) = reader_peer.send(&response).await {
-                                                tracing::warn!(
-                                                    error = %err,
-                                                    "Failed to send patch approval response"
-                                                );
-                                            }
-                                        }
+                                        } => CodexApproval::Patch(request_id),
                                         ServerRequest::ExecCommandApproval {
                                             request_id, ..
-                                        } => {
-                                            let response = JSONRPCResponse {
-                                                id: request_id,
-                                                result: serde_json::to_value(
-                                                    ExecCommandApprovalResponse {
-                                                        decision: ReviewDecision::Approved,
-                                                    },
-                                                )
-                                                .unwrap_or(Value::Null),
-                                            };
-                                            if let Err(err) = reader_peer.send(&response).await {
-                                                tracing::warn!(
-                                                    error = %err,
-                                                    "Failed to send exec approval response"
-                                                );
-                                            }
-                                        }
-                                        _ => {}
-                                    }
+                                        } => CodexApproval::Exec(request_id),
+                                        _ => continue,
+                                    };
+                                    Self::handle_approval(
+                                        &reader_peer,
+                                        &reader_permissions,
+                                        &tx_for_events,
+                                        approval,
+                                        permission,
+                                        call_id,
+                                    )
+                                    .await;
                                 }
                             }
                             Ok(JSONRPCMessage::Error(err)) => {
@@ -799,8 +897,8 @@ impl AgentRunner for CodexCliRunner {
                         model_provider: None,
                         profile: None,
                         cwd: Some(config.working_dir.to_string_lossy().to_string()),
-                        approval_policy: Some(Self::approval_policy()),
-                        sandbox: Some(Self::sandbox_mode()),
+                        approval_policy: Some(Self::approval_policy(&config.permissions)),
+                        sandbox: Some(Self::sandbox_mode(config.permissions.sandbox)),
                         config: None,
                         base_instructions: None,
                         developer_instructions: None,
@@ -821,8 +919,8 @@ impl AgentRunner for CodexCliRunner {
                     model: config.model.clone(),
                     profile: None,
                     cwd: Some(config.working_dir.to_string_lossy().to_string()),
-                    approval_policy: Some(Self::approval_policy()),
-                    sandbox: Some(Self::sandbox_mode()),
+                    approval_policy: Some(Self::approval_policy(&config.permissions)),
+                    sandbox: Some(Self::sandbox_mode(config.permissions.sandbox)),
                     config: None,
                     base_instructions: None,
                     include_apply_patch_tool: None,
@@ -887,6 +985,18 @@ impl AgentRunner for CodexCliRunner {
                             "Ignored OpenCode question response sent to Codex input channel"
                         );
                     }
+                    AgentInput::PermissionResponse { request_id, reply } => {
+                        match permissions.resolve(&request_id, reply) {
+                            Some(approval) => {
+                                Self::send_approval(&input_peer, approval, reply.is_allowed())
+                                    .await;
+                            }
+                            None => tracing::warn!(
+                                request_id,
+                                "Permission response for unknown Codex request"
+                            ),
+                        }
+                    }
                 }
             }
         });
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use crate::agent::permissions::{PermissionDecision, PermissionRequest};
 use crate::agent::session::SessionId;
 
 /// Unified event type emitted by all agents
@@ -33,6 +34,9 @@ pub enum AgentEvent {
     /// Control request (permission prompt) from agent runtime
     ControlRequest(ControlRequestEvent),
 
+    /// Tool call allowed or denied by the permission policy without asking
+    PermissionDecided(PermissionDecidedEvent),
+
     /// File operation
     FileChanged(FileChangedEvent),
 
@@ -65,6 +69,7 @@ impl AgentEvent {
             AgentEvent::ToolStarted(_) => "ToolStarted",
             AgentEvent::ToolCompleted(_) => "ToolCompleted",
             AgentEvent::ControlRequest(_) => "ControlRequest",
+            AgentEvent::PermissionDecided(_) => "PermissionDecided",
             AgentEvent::FileChanged(_) => "FileChanged",
             AgentEvent::CommandOutput(_) => "CommandOutput",
             AgentEvent::TokenUsage(_) => "TokenUsage",
@@ -123,6 +128,18 @@ pub struct ControlRequestEvent {
     pub tool_name: String,
     pub tool_use_id: Option<String>,
     pub input: serde_json::Value,
+    /// Set when the permission policy asks the user to allow the tool call;
+    /// answer with `AgentInput::PermissionResponse`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission: Option<PermissionRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionDecidedEvent {
+    pub request_id: String,
+    pub permission: PermissionRequest,
+    /// Allow or Deny
+    pub decision: PermissionDecision,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -149,9 +166,14 @@ pub struct CommandOutputEvent {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TokenUsage {
+    /// Prompt tokens, including cache reads and writes
     pub input_tokens: i64,
     pub output_tokens: i64,
+    /// Prompt tokens served from the cache
     pub cached_tokens: i64,
+    /// Prompt tokens written to the cache
+    #[serde(default)]
+    pub cache_write_tokens: i64,
     pub total_tokens: i64,
 }
 
@@ -4,6 +4,7 @@ use std::env;
 use std::path::PathBuf;
 use std::process::Stdio;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use agent_client_protocol as acp;
@@ -13,14 +14,17 @@ use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
 use tokio_util::io::ReaderStream;
 
 use crate::agent::error::AgentError;
 use crate::agent::events::{
-    AgentEvent, AssistantMessageEvent, ErrorEvent, ReasoningEvent, SessionInitEvent,
-    ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent,
+    AgentEvent, AssistantMessageEvent, ControlRequestEvent, ErrorEvent, PermissionDecidedEvent,
+    ReasoningEvent, SessionInitEvent, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent,
+};
+use crate::agent::permissions::{
+    PermissionDecision, PermissionGate, PermissionReply, PermissionRequest,
 };
 use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
@@ -46,21 +50,80 @@ struct ModelCache {
     models: Vec<String>,
 }
 
+/// Permission state shared with the input task; parked requests wait on a oneshot
+type GeminiPermissions = PermissionGate<oneshot::Sender<PermissionReply>>;
+
 struct GeminiAcpClient {
     event_tx: mpsc::Sender<AgentEvent>,
     saw_message: Rc<Cell<bool>>,
     tool_titles: RefCell<HashMap<String, String>>,
+    permissions: Arc<GeminiPermissions>,
 }
 
 impl GeminiAcpClient {
-    fn new(event_tx: mpsc::Sender<AgentEvent>, saw_message: Rc<Cell<bool>>) -> Self {
+    fn new(
+        event_tx: mpsc::Sender<AgentEvent>,
+        saw_message: Rc<Cell<bool>>,
+        permissions: Arc<GeminiPermissions>,
+    ) -> Self {
         Self {
             event_tx,
             saw_message,
             tool_titles: RefCell::new(HashMap::new()),
+            permissions,
         }
     }
 
+    /// Normalize an ACP tool call for the permission policy
+    fn permission_request(tool_call: &acp::ToolCallUpdate) -> PermissionRequest {
+        let input = tool_call
+            .fields
+            .raw_input
+            .clone()
+            .unwrap_or(serde_json::Value::Null);
+        let tool = match tool_call.fields.kind {
+            Some(acp::ToolKind::Execute) => "Bash",
+            Some(acp::ToolKind::Edit | acp::ToolKind::Delete | acp::ToolKind::Move) => "Edit",
+            Some(acp::ToolKind::Read) => "Read",
+            Some(acp::ToolKind::Fetch) => "WebFetch",
+            _ => tool_call.fields.title.as_deref().unwrap_or("tool"),
+        };
+        let mut request = PermissionRequest::from_tool_input(tool, &input);
+        if request.paths.is_empty() {
+            if let Some(locations) = &tool_call.fields.locations {
+                for location in locations {
+                    request = request.with_path(location.path.display().to_string());
+                }
+            }
+        }
+        request
+    }
+
+    /// Ask the user through a control request and wait for the answer
+    async fn ask_permission(
+        &self,
+        tool_call: &acp::ToolCallUpdate,
+        permission: PermissionRequest,
+    ) -> Option<PermissionReply> {
+        let request_id = tool_call.tool_call_id.0.to_string();
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.permissions
+            .park(request_id.clone(), permission.clone(), reply_tx);
+        self.send_event(AgentEvent::ControlRequest(ControlRequestEvent {
+            request_id: request_id.clone(),
+            tool_name: permission.tool.clone(),
+            tool_use_id: Some(request_id),
+            input: tool_call
+                .fields
+                .raw_input
+                .clone()
+                .unwrap_or(serde_json::Value::Null),
+            permission: Some(permission),
+        }))
+        .await;
+        reply_rx.await.ok()
+    }
+
     async fn send_event(&self, event: AgentEvent) {
         if self.event_tx.send(event).await.is_err() {
             tracing::debug!("Failed to send Gemini agent event");
@@ -110,16 +173,44 @@ impl acp::Client for GeminiAcpClient {
         &self,
         args: acp::RequestPermissionRequest,
     ) -> Result<acp::RequestPermissionResponse, acp::Error> {
-        let chosen = args
-            .options
-            .iter()
-            .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowAlways))
-            .or_else(|| {
-                args.options
-                    .iter()
-                    .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowOnce))
-            })
-            .or_else(|| args.options.first());
+        let permission = Self::permission_request(&args.tool_call);
+        let decision = self.permissions.evaluate(&permission);
+        let allowed = match decision {
+            PermissionDecision::Allow | PermissionDecision::Deny => {
+                self.send_event(AgentEvent::PermissionDecided(PermissionDecidedEvent {
+                    request_id: args.tool_call.tool_call_id.0.to_string(),
+                    permission,
+                    decision,
+                }))
+                .await;
+                Some(decision == PermissionDecision::Allow)
+            }
+            PermissionDecision::Ask => self
+                .ask_permission(&args.tool_call, permission)
+                .await
+                .map(|reply| reply.is_allowed()),
+        };
+
+        // "Always" answers are remembered by the policy, so prefer one-shot options
+        let chosen = match allowed {
+            Some(true) => args
+                .options
+                .iter()
+                .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowOnce))
+                .or_else(|| {
+                    args.options
+                        .iter()
+                        .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowAlways))
+                })
+                .or_else(|| args.options.first()),
+            Some(false) => args.options.iter().find(|o| {
+                matches!(
+                    o.kind,
+                    acp::PermissionOptionKind::RejectOnce | acp::PermissionOptionKind::RejectAlways
+                )
+            }),
+            None => None,
+        };
 
         let outcome = if let Some(option) = chosen {
             acp::RequestPermissionOutcome::Selected(acp::SelectedPermissionOutcome::new(
@@ -487,6 +578,35 @@ impl AgentRunner for GeminiCliRunner {
         let (tx, rx) = mpsc::channel::<AgentEvent>(256);
         let tx_for_monitor = tx.clone();
 
+        let permissions: Arc<GeminiPermissions> = Arc::new(PermissionGate::new(
+            config.permissions.clone(),
+            config.working_dir.clone(),
+        ));
+        let permissions_for_session = permissions.clone();
+        let (input_tx, mut input_rx) = mpsc::channel::<AgentInput>(16);
+        // The input task must not hold an event sender, or the event stream
+        // would outlive the process
+        tokio::spawn(async move {
+            while let Some(input) = input_rx.recv().await {
+                match input {
+                    AgentInput::PermissionResponse { request_id, reply } => {
+                        match permissions.resolve(&request_id, reply) {
+                            Some(reply_tx) => {
+                                if reply_tx.send(reply).is_err() {
+                                    tracing::debug!("Gemini permission request already closed");
+                                }
+                            }
+                            None => tracing::warn!(
+                                request_id,
+                                "Permission response for unknown Gemini request"
+                            ),
+                        }
+                    }
+                    _ => tracing::warn!("Gemini CLI runner does not support interactive input"),
+                }
+            }
+        });
+
         let (mut to_acp_writer, acp_incoming_reader) = tokio::io::duplex(64 * 1024);
         let (acp_out_writer, acp_out_reader) = tokio::io::duplex(64 * 1024);
 
@@ -536,8 +656,11 @@ impl AgentRunner for GeminiCliRunner {
                 local
                     .run_until(async move {
                         let saw_message = Rc::new(Cell::new(false));
-                        let client =
-                            GeminiAcpClient::new(tx_for_session.clone(), saw_message.clone());
+                        let client = GeminiAcpClient::new(
+                            tx_for_session.clone(),
+                            saw_message.clone(),
+                            permissions_for_session,
+                        );
                         let (conn, io_fut) =
                             acp::ClientSideConnection::new(client, outgoing, incoming, |fut| {
                                 tokio::task::spawn_local(fut);
@@ -772,17 +895,22 @@ impl AgentRunner for GeminiCliRunner {
             }
         });
 
-        Ok(AgentHandle::new(rx, pid, None))
+        Ok(AgentHandle::new(rx, pid, Some(input_tx)))
     }
 
-    async fn send_input(
-        &self,
-        _handle: &AgentHandle,
-        _input: AgentInput,
-    ) -> Result<(), AgentError> {
-        Err(AgentError::NotSupported(
-            "Gemini CLI runner does not support interactive input".into(),
-        ))
+    async fn send_input(&self, handle: &AgentHandle, input: AgentInput) -> Result<(), AgentError> {
+        if !matches!(input, AgentInput::PermissionResponse { .. }) {
+            return Err(AgentError::NotSupported(
+                "Gemini CLI runner does not support interactive input".into(),
+            ));
+        }
+        let Some(ref input_tx) = handle.input_tx else {
+            return Err(AgentError::ChannelClosed);
+        };
+        input_tx
+            .send(input)
+            .await
+            .map_err(|_| AgentError::ChannelClosed)
     }
 
     async fn stop(&self, handle: &AgentHandle) -> Result<(), AgentError> {
@@ -4,6 +4,9 @@
 //! - Claude Code: ~/.claude/projects/{project-path}/{session-id}.jsonl
 //! - Codex CLI: ~/.codex/sessions/YYYY/MM/DD/rollout-*-{session-id}.jsonl
 //! - Gemini CLI: not supported yet
+//!
+//! These readers are the fallback for sessions without a Conduit event journal
+//! (see `journal`).
 
 use std::collections::HashMap;
 use std::fs::{self, File};
@@ -15,6 +18,7 @@ use serde::Deserialize;
 use serde_json::Value;
 
 use super::display::MessageDisplay;
+use super::runner::AgentType;
 #[cfg(test)]
 use crate::ui::components::MessageRole;
 use crate::ui::components::{ChatMessage, TurnSummary};
@@ -276,7 +280,7 @@ fn is_claude_user_prompt(entry: &Value) -> bool {
     has_text || (!has_tool_result && !blocks.is_empty())
 }
 
-fn build_turn_summary(
+pub(super) fn build_turn_summary(
     started_at: Option<DateTime<Utc>>,
     ended_at: Option<DateTime<Utc>>,
     usage: Option<(u64, u64)>,
@@ -787,6 +791,24 @@ pub fn load_claude_history_with_debug(
     Ok((messages, debug_entries, session_file))
 }
 
+/// Where an agent keeps a session's history: the session file for Claude and
+/// Codex, the message directory for OpenCode.
+///
+/// Its modification time and size change as the session grows.
+pub fn agent_history_path(agent_type: AgentType, session_id: &str) -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    match agent_type {
+        AgentType::Claude => {
+            find_claude_session_file(&home.join(".claude/projects"), session_id).ok()
+        }
+        AgentType::Codex => find_codex_session_file(&home.join(".codex/sessions"), session_id).ok(),
+        AgentType::Gemini => None,
+        AgentType::Opencode => find_opencode_storage_for_session(session_id)
+            .ok()
+            .map(|(storage_dir, _)| storage_dir.join("message").join(session_id)),
+    }
+}
+
 /// Find Claude session file by searching project directories
 fn find_claude_session_file(
     projects_dir: &PathBuf,
@@ -0,0 +1,378 @@
+//! Session event journal replay
+//!
+//! Rebuilds chat history from the events Conduit recorded in its own
+//! `session_events` table. This is the preferred history source for every
+//! agent; the vendor-specific readers in `history` are only used when a
+//! session has no (complete) journal.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::display::MessageDisplay;
+use super::events::{AgentEvent, ContextWindowState};
+use super::history::{build_turn_summary, HistoryDebugEntry};
+use crate::data::{SessionEvent, SessionEventPayload, SessionEventStore};
+use crate::ui::components::ChatMessage;
+
+/// Load and replay a session's journal.
+///
+/// Returns `None` when the journal is empty, incomplete, or can't be read.
+pub fn load_journal_history(
+    store: &SessionEventStore,
+    session_id: Uuid,
+) -> Option<(Vec<ChatMessage>, Vec<HistoryDebugEntry>)> {
+    match store.get_by_session(session_id) {
+        Ok(events) => replay_session_events(&events),
+        Err(e) => {
+            tracing::warn!(%session_id, error = %e, "Failed to load session journal");
+            None
+        }
+    }
+}
+
+/// Replay journaled events into chat messages.
+///
+/// Returns `None` when there is nothing to replay, or when the journal
+/// starts part-way through an agent session that predates journaling (the
+/// caller should fall back to the vendor history readers in that case).
+pub fn replay_session_events(
+    events: &[SessionEvent],
+) -> Option<(Vec<ChatMessage>, Vec<HistoryDebugEntry>)> {
+    let first = events.first()?;
+    if matches!(
+        first.payload,
+        SessionEventPayload::UserPrompt { resumed: true, .. }
+    ) {
+        return None;
+    }
+
+    let mut replay = JournalReplay::default();
+    let mut debug_entries = Vec::with_capacity(events.len());
+    for (line_number, event) in events.iter().enumerate() {
+        replay.apply(event);
+        debug_entries.push(HistoryDebugEntry {
+            line_number,
+            entry_type: event.payload.event_type().to_string(),
+            status: "INCLUDE".to_string(),
+            reason: "journal".to_string(),
+            raw_json: serde_json::to_value(&event.payload).unwrap_or_default(),
+        });
+    }
+    replay.flush_text();
+
+    Some((replay.messages, debug_entries))
+}
+
+/// Pending streamed text, mirroring how `ChatView` buffers live output
+enum PendingText {
+    Assistant(String),
+    Reasoning(String),
+}
+
+#[derive(Default)]
+struct JournalReplay {
+    messages: Vec<ChatMessage>,
+    pending: Option<PendingText>,
+    /// Indices of tool messages in `messages`, paired with their tool IDs
+    tool_ids: Vec<(usize, String)>,
+    /// Start of the current turn (prompt submission or TurnStarted)
+    turn_started_at: Option<DateTime<Utc>>,
+    /// Current turn was triggered by a hidden prompt (e.g. a fork seed)
+    hidden_turn: bool,
+}
+
+impl JournalReplay {
+    fn apply(&mut self, event: &SessionEvent) {
+        match &event.payload {
+            SessionEventPayload::UserPrompt { text, hidden, .. } => {
+                self.flush_text();
+                self.hidden_turn = *hidden;
+                self.turn_started_at = Some(event.created_at);
+                if !hidden {
+                    self.push(MessageDisplay::User {
+                        content: text.clone(),
+                    });
+                }
+            }
+            SessionEventPayload::Agent { event: agent_event } => {
+                self.apply_agent_event(agent_event, event.created_at);
+            }
+        }
+    }
+
+    fn apply_agent_event(&mut self, event: &AgentEvent, created_at: DateTime<Utc>) {
+        match event {
+            AgentEvent::TurnStarted => {
+                if self.turn_started_at.is_none() {
+                    self.turn_started_at = Some(created_at);
+                }
+            }
+            AgentEvent::AssistantMessage(msg) => {
+                if self.hidden_turn {
+                    return;
+                }
+                match &mut self.pending {
+                    Some(PendingText::Assistant(text)) => text.push_str(&msg.text),
+                    _ => {
+                        self.flush_text();
+                        self.pending = Some(PendingText::Assistant(msg.text.clone()));
+                    }
+                }
+                if msg.is_final {
+                    self.flush_text();
+                }
+            }
+            AgentEvent::AssistantReasoning(reasoning) => match &mut self.pending {
+                Some(PendingText::Reasoning(text)) => text.push_str(&reasoning.text),
+                _ => {
+                    self.flush_text();
+                    self.pending = Some(PendingText::Reasoning(reasoning.text.clone()));
+                }
+            },
+            AgentEvent::ToolStarted(tool) => {
+                if tool.tool_name == "AskUserQuestion" || tool.tool_name == "ExitPlanMode" {
+                    return;
+                }
+                let args = if tool.arguments.is_null() {
+                    String::new()
+                } else {
+                    serde_json::to_string(&tool.arguments).unwrap_or_default()
+                };
+                self.push(MessageDisplay::Tool {
+                    name: MessageDisplay::tool_display_name_owned(&tool.tool_name),
+                    args,
+                    output: "Running...".to_string(),
+                    exit_code: None,
+                    file_size: None,
+                });
+                self.tool_ids
+                    .push((self.messages.len() - 1, tool.tool_id.clone()));
+            }
+            AgentEvent::ToolCompleted(tool) => {
+                let output = if tool.success {
+                    tool.result
+                        .clone()
+                        .unwrap_or_else(|| "Completed".to_string())
+                } else {
+                    format!("Error: {}", tool.error.clone().unwrap_or_default())
+                };
+                let index = self
+                    .tool_ids
+                    .iter()
+                    .rev()
+                    .find(|(_, id)| *id == tool.tool_id)
+                    .map(|(index, _)| *index)
+                    .or_else(|| self.tool_ids.last().map(|(index, _)| *index));
+                if let Some(index) = index {
+                    self.messages[index].content = output;
+                }
+            }
+            AgentEvent::CommandOutput(cmd) => {
+                if let Some((index, _)) = self.tool_ids.last() {
+                    let msg = &mut self.messages[*index];
+                    msg.content = cmd.output.clone();
+                    msg.exit_code = cmd.exit_code;
+                }
+            }
+            AgentEvent::TurnCompleted(completed) => {
+                self.flush_text();
+                if !self.hidden_turn {
+                    let usage = (
+                        completed.usage.input_tokens.max(0) as u64,
+                        completed.usage.output_tokens.max(0) as u64,
+                    );
+                    if let Some(summary) =
+                        build_turn_summary(self.turn_started_at, Some(created_at), Some(usage))
+                    {
+                        self.messages.push(ChatMessage::turn_summary(summary));
+                    }
+                }
+                self.end_turn();
+            }
+            AgentEvent::TurnFailed(failed) => {
+                self.push(MessageDisplay::Error {
+                    content: failed.error.clone(),
+                });
+                self.end_turn();
+            }
+            AgentEvent::Error(err) => {
+                self.push(MessageDisplay::Error {
+                    content: err.message.clone(),
+                });
+                if err.is_fatal {
+                    self.end_turn();
+                }
+            }
+            AgentEvent::ContextCompaction(compaction) => {
+                self.push(MessageDisplay::System {
+                    content: format!(
+                        "🔄 Context compacted: {} → {} tokens (reason: {})",
+                        ContextWindowState::format_tokens(compaction.tokens_before),
+                        ContextWindowState::format_tokens(compaction.tokens_after),
+                        compaction.reason
+                    ),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn push(&mut self, display: MessageDisplay) {
+        self.flush_text();
+        self.messages.push(display.to_chat_message());
+    }
+
+    fn flush_text(&mut self) {
+        let display = match self.pending.take() {
+            Some(PendingText::Assistant(content)) => MessageDisplay::Assistant {
+                content,
+                is_streaming: false,
+            },
+            Some(PendingText::Reasoning(content)) => MessageDisplay::Reasoning { content },
+            None => return,
+        };
+        self.messages.push(display.to_chat_message());
+    }
+
+    fn end_turn(&mut self) {
+        self.flush_text();
+        self.hidden_turn = false;
+        self.turn_started_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::events::{
+        AssistantMessageEvent, CommandOutputEvent, ReasoningEvent, TokenUsage, ToolStartedEvent,
+        TurnCompletedEvent,
+    };
+    use crate::ui::components::MessageRole;
+
+    fn event(id: i64, payload: SessionEventPayload) -> SessionEvent {
+        SessionEvent {
+            id,
+            session_id: Uuid::nil(),
+            payload,
+            created_at: DateTime::from_timestamp(1_700_000_000 + id * 5, 0).unwrap(),
+        }
+    }
+
+    fn prompt(id: i64, text: &str, hidden: bool, resumed: bool) -> SessionEvent {
+        event(
+            id,
+            SessionEventPayload::UserPrompt {
+                text: text.to_string(),
+                hidden,
+                resumed,
+            },
+        )
+    }
+
+    fn agent(id: i64, event_: AgentEvent) -> SessionEvent {
+        event(id, SessionEventPayload::Agent { event: event_ })
+    }
+
+    fn assistant(id: i64, text: &str, is_final: bool) -> SessionEvent {
+        agent(
+            id,
+            AgentEvent::AssistantMessage(AssistantMessageEvent {
+                text: text.to_string(),
+                is_final,
+            }),
+        )
+    }
+
+    fn completed(id: i64) -> SessionEvent {
+        agent(
+            id,
+            AgentEvent::TurnCompleted(TurnCompletedEvent {
+                usage: TokenUsage {
+                    input_tokens: 100,
+                    output_tokens: 20,
+                    ..Default::default()
+                },
+            }),
+        )
+    }
+
+    #[test]
+    fn test_replay_empty_or_resumed_journal_falls_back() {
+        assert!(replay_session_events(&[]).is_none());
+        assert!(replay_session_events(&[prompt(1, "hi", false, true)]).is_none());
+    }
+
+    #[test]
+    fn test_replay_builds_transcript() {
+        let events = vec![
+            prompt(1, "list files", false, false),
+            agent(2, AgentEvent::TurnStarted),
+            agent(
+                3,
+                AgentEvent::AssistantReasoning(ReasoningEvent {
+                    text: "thinking".to_string(),
+                }),
+            ),
+            assistant(4, "Let me ", false),
+            assistant(5, "check.", false),
+            agent(
+                6,
+                AgentEvent::ToolStarted(ToolStartedEvent {
+                    tool_name: "shell".to_string(),
+                    tool_id: "t1".to_string(),
+                    arguments: serde_json::json!({"command": "ls"}),
+                }),
+            ),
+            agent(
+                7,
+                AgentEvent::CommandOutput(CommandOutputEvent {
+                    command: "ls".to_string(),
+                    output: "README.md".to_string(),
+                    exit_code: Some(0),
+                    is_streaming: false,
+                }),
+            ),
+            assistant(8, "Done.", true),
+            completed(9),
+        ];
+
+        let (messages, debug) = replay_session_events(&events).unwrap();
+        assert_eq!(debug.len(), events.len());
+        let roles: Vec<_> = messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                MessageRole::User,
+                MessageRole::Reasoning,
+                MessageRole::Assistant,
+                MessageRole::Tool,
+                MessageRole::Assistant,
+                MessageRole::Summary,
+            ]
+        );
+        assert_eq!(messages[2].content, "Let me check.");
+        assert_eq!(messages[3].tool_name.as_deref(), Some("Bash"));
+        assert_eq!(messages[3].content, "README.md");
+        assert_eq!(messages[3].exit_code, Some(0));
+        assert!(messages[5].summary.is_some());
+    }
+
+    #[test]
+    fn test_replay_hides_hidden_turns() {
+        let events = vec![
+            prompt(1, "", true, false),
+            assistant(2, "ack", true),
+            completed(3),
+            prompt(4, "real question", false, false),
+            assistant(5, "answer", true),
+            completed(6),
+        ];
+
+        let (messages, _) = replay_session_events(&events).unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].content, "real question");
+        assert_eq!(messages[1].content, "answer");
+        assert_eq!(messages[2].role, MessageRole::Summary);
+    }
+}
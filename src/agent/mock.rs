@@ -330,6 +330,7 @@ impl MockEventBuilder {
                     input_tokens,
                     output_tokens,
                     cached_tokens: 0,
+                    cache_write_tokens: 0,
                     total_tokens: input_tokens + output_tokens,
                 },
             }));
@@ -359,6 +360,7 @@ impl MockEventBuilder {
 mod tests {
     use super::*;
     use crate::agent::events::{AssistantMessageEvent, SessionInitEvent};
+    use crate::agent::permissions::{PermissionDecision, PermissionPolicy};
     use crate::agent::session::SessionId;
 
     #[tokio::test]
@@ -420,7 +422,10 @@ mod tests {
 
         let config = AgentStartConfig::new("Test prompt", PathBuf::from("/workspace"))
             .with_model("opus")
-            .with_tools(vec!["Bash".to_string(), "Read".to_string()]);
+            .with_permissions(PermissionPolicy {
+                default: PermissionDecision::Ask,
+                ..PermissionPolicy::default()
+            });
 
         runner.start(config).await.unwrap();
 
@@ -428,7 +433,7 @@ mod tests {
         assert_eq!(captured.len(), 1);
         assert_eq!(captured[0].prompt, "Test prompt");
         assert_eq!(captured[0].model, Some("opus".to_string()));
-        assert_eq!(captured[0].allowed_tools, vec!["Bash", "Read"]);
+        assert_eq!(captured[0].permissions.default, PermissionDecision::Ask);
     }
 
     #[tokio::test]
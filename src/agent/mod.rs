@@ -5,9 +5,11 @@ pub mod error;
 pub mod events;
 pub mod gemini;
 pub mod history;
+pub mod journal;
 pub mod mock;
 pub mod models;
 pub mod opencode;
+pub mod permissions;
 pub mod runner;
 pub mod session;
 pub mod stream;
@@ -19,12 +21,17 @@ pub use error::AgentError;
 pub use events::*;
 pub use gemini::GeminiCliRunner;
 pub use history::{
-    load_claude_history_with_debug, load_codex_history_with_debug,
+    agent_history_path, load_claude_history_with_debug, load_codex_history_with_debug,
     load_opencode_history_for_dir_with_debug, load_opencode_history_with_debug, HistoryDebugEntry,
     HistoryError,
 };
+pub use journal::{load_journal_history, replay_session_events};
 pub use mock::{MockAgentRunner, MockConfig, MockEventBuilder, MockStartError};
-pub use models::{ModelInfo, ModelRegistry};
+pub use models::{ModelInfo, ModelPricing, ModelRegistry};
 pub use opencode::OpencodeRunner;
+pub use permissions::{
+    canonical_tool_name, PermissionDecision, PermissionGate, PermissionPolicy, PermissionReply,
+    PermissionRequest, PermissionRule, SandboxMode,
+};
 pub use runner::{AgentHandle, AgentInput, AgentMode, AgentRunner, AgentStartConfig, AgentType};
 pub use session::{SessionId, SessionMetadata, SessionStatus};
@@ -2,11 +2,50 @@
 
 use std::sync::{OnceLock, RwLock};
 
+use serde::Serialize;
 use tracing::error;
 
+use crate::agent::events::TokenUsage;
 use crate::agent::opencode::load_opencode_models;
 use crate::agent::AgentType;
 
+/// Token pricing for a model, in USD per million tokens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ModelPricing {
+    /// Uncached input tokens
+    pub input: f64,
+    /// Output tokens (including reasoning)
+    pub output: f64,
+    /// Input tokens served from the prompt cache
+    pub cache_read: f64,
+    /// Input tokens written to the prompt cache
+    pub cache_write: f64,
+}
+
+impl ModelPricing {
+    pub const fn new(input: f64, output: f64, cache_read: f64, cache_write: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_read,
+            cache_write,
+        }
+    }
+
+    /// Cost in USD of the given token usage
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        let cache_read = usage.cached_tokens.max(0);
+        let cache_write = usage.cache_write_tokens.max(0);
+        let uncached_input = (usage.input_tokens - cache_read - cache_write).max(0);
+        let per_token = |tokens: i64, price: f64| tokens as f64 / 1_000_000.0 * price;
+
+        per_token(uncached_input, self.input)
+            + per_token(cache_read, self.cache_read)
+            + per_token(cache_write, self.cache_write)
+            + per_token(usage.output_tokens.max(0), self.output)
+    }
+}
+
 /// Information about a model
 #[derive(Debug, Clone)]
 pub struct ModelInfo {
@@ -24,6 +63,8 @@ pub struct ModelInfo {
     pub agent_type: AgentType,
     /// Maximum context window in tokens
     pub context_window: i64,
+    /// List pricing (None when unknown, e.g. OpenCode provider models)
+    pub pricing: Option<ModelPricing>,
 }
 
 impl ModelInfo {
@@ -43,6 +84,7 @@ impl ModelInfo {
             is_default: false,
             agent_type,
             context_window,
+            pricing: None,
         }
     }
 
@@ -50,6 +92,11 @@ impl ModelInfo {
         self.is_default = true;
         self
     }
+
+    pub fn with_pricing(mut self, pricing: ModelPricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
 }
 
 /// Registry of available models for each agent type
@@ -181,6 +228,7 @@ impl ModelRegistry {
                 "Most powerful, best for complex reasoning",
                 Self::CLAUDE_CONTEXT_WINDOW,
             )
+            .with_pricing(ModelPricing::new(5.0, 25.0, 0.5, 6.25))
             .as_default(),
             ModelInfo::new(
                 AgentType::Claude,
@@ -189,7 +237,8 @@ impl ModelRegistry {
                 "sonnet",
                 "Fast and capable, best for most tasks",
                 Self::CLAUDE_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(3.0, 15.0, 0.3, 3.75)),
             ModelInfo::new(
                 AgentType::Claude,
                 "haiku",
@@ -197,7 +246,8 @@ impl ModelRegistry {
                 "haiku",
                 "Fastest, great for simple tasks",
                 Self::CLAUDE_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(1.0, 5.0, 0.1, 1.25)),
         ]
     }
 
@@ -212,6 +262,7 @@ impl ModelRegistry {
                 "Latest Codex model",
                 Self::CODEX_CONTEXT_WINDOW,
             )
+            .with_pricing(ModelPricing::new(1.75, 14.0, 0.175, 0.0))
             .as_default(),
             ModelInfo::new(
                 AgentType::Codex,
@@ -220,7 +271,8 @@ impl ModelRegistry {
                 "gpt-5.2",
                 "Fast and efficient",
                 Self::CODEX_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(1.75, 14.0, 0.175, 0.0)),
             ModelInfo::new(
                 AgentType::Codex,
                 "gpt-5.1-codex-max",
@@ -228,7 +280,8 @@ impl ModelRegistry {
                 "gpt-5.1-codex-max",
                 "Maximum capability",
                 Self::CODEX_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(1.25, 10.0, 0.125, 0.0)),
         ]
     }
 
@@ -243,6 +296,7 @@ impl ModelRegistry {
                 "Highest quality Gemini model",
                 Self::GEMINI_CONTEXT_WINDOW,
             )
+            .with_pricing(ModelPricing::new(1.25, 10.0, 0.125, 0.0))
             .as_default(),
             ModelInfo::new(
                 AgentType::Gemini,
@@ -251,7 +305,8 @@ impl ModelRegistry {
                 "gemini-2.5-flash",
                 "Fast and capable Gemini model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(0.3, 2.5, 0.03, 0.0)),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-2.5-flash-lite",
@@ -259,7 +314,8 @@ impl ModelRegistry {
                 "gemini-2.5-flash-lite",
                 "Lowest-latency Gemini model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(0.1, 0.4, 0.01, 0.0)),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-3-pro-preview",
@@ -267,7 +323,8 @@ impl ModelRegistry {
                 "gemini-3-pro-preview",
                 "Preview Gemini 3 model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(2.0, 12.0, 0.2, 0.0)),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-3-flash-preview",
@@ -275,7 +332,8 @@ impl ModelRegistry {
                 "gemini-3-flash-preview",
                 "Preview Gemini 3 flash model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(0.5, 3.0, 0.05, 0.0)),
         ]
     }
 
@@ -363,6 +421,11 @@ impl ModelRegistry {
             .unwrap_or_else(|| Self::default_context_window(agent_type))
     }
 
+    /// Get list pricing for a specific model (None when unknown)
+    pub fn pricing(agent_type: AgentType, model_id: &str) -> Option<ModelPricing> {
+        Self::find_model(agent_type, model_id).and_then(|m| m.pricing)
+    }
+
     /// Default context window when model not found
     pub fn default_context_window(agent_type: AgentType) -> i64 {
         match agent_type {
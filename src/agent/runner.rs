@@ -5,6 +5,7 @@ use tokio::sync::mpsc;
 
 use crate::agent::error::AgentError;
 use crate::agent::events::AgentEvent;
+use crate::agent::permissions::{PermissionPolicy, PermissionReply};
 use crate::agent::session::SessionId;
 
 /// Agent type identifier
@@ -116,7 +117,8 @@ impl std::fmt::Display for AgentType {
 pub struct AgentStartConfig {
     pub prompt: String,
     pub working_dir: PathBuf,
-    pub allowed_tools: Vec<String>,
+    /// Tool permission policy, already scoped to the session's repository/workspace
+    pub permissions: PermissionPolicy,
     pub resume_session: Option<SessionId>,
     pub timeout_ms: Option<u64>,
     pub additional_args: Vec<String>,
@@ -137,7 +139,7 @@ impl AgentStartConfig {
         Self {
             prompt: prompt.into(),
             working_dir,
-            allowed_tools: vec![],
+            permissions: PermissionPolicy::default(),
             resume_session: None,
             timeout_ms: None,
             additional_args: vec![],
@@ -149,8 +151,8 @@ impl AgentStartConfig {
         }
     }
 
-    pub fn with_tools(mut self, tools: Vec<String>) -> Self {
-        self.allowed_tools = tools;
+    pub fn with_permissions(mut self, permissions: PermissionPolicy) -> Self {
+        self.permissions = permissions;
         self
     }
 
@@ -206,6 +208,11 @@ pub enum AgentInput {
         request_id: String,
         answers: Option<Vec<Vec<String>>>,
     },
+    /// Answer to a tool permission request the policy left to the user.
+    PermissionResponse {
+        request_id: String,
+        reply: PermissionReply,
+    },
 }
 
 /// Handle to a running agent process
@@ -315,6 +315,8 @@ pub struct ClaudeResultEvent {
 pub struct ClaudeUsage {
     pub input_tokens: Option<i64>,
     pub output_tokens: Option<i64>,
+    pub cache_read_input_tokens: Option<i64>,
+    pub cache_creation_input_tokens: Option<i64>,
 }
 
 // ============================================================================
@@ -29,13 +29,22 @@ pub fn default_keybindings() -> KeybindingConfig {
     bind(&mut config.global, "C-n", Action::NewProject);
     bind(&mut config.global, "C-p", Action::OpenCommandPalette);
     bind(&mut config.global, "C-M-p", Action::OpenPr); // Ctrl+Alt+P for PR
+    bind(&mut config.global, "C-M-c", Action::OpenCommitDialog); // Ctrl+Alt+C for commit
+    bind(&mut config.global, "C-M-r", Action::OpenReviewComments); // Ctrl+Alt+R for review
+    bind(&mut config.global, "C-M-f", Action::FixCi); // Ctrl+Alt+F for fix CI
+    bind(&mut config.global, "C-M-s", Action::SyncWithBase); // Ctrl+Alt+S for sync
     bind(&mut config.global, "M-S-f", Action::ForkSession); // Alt+Shift+F for fork
+    bind(&mut config.global, "M-S-h", Action::HandoffSession); // Alt+Shift+H for handoff
     bind(&mut config.global, "C-z", Action::Suspend);
 
     // Note: Ctrl+C is handled specially in app.rs for double-press detection
     bind(&mut config.global, "C-g", Action::ToggleViewMode);
     bind(&mut config.global, "C-o", Action::ShowModelSelector);
     bind(&mut config.global, "M-i", Action::OpenSessionImport);
+    bind(&mut config.global, "M-s", Action::OpenSessionSearch);
+    bind(&mut config.global, "M-u", Action::ShowUsageStats);
+    bind(&mut config.global, "M-S-g", Action::ShowDiff); // Alt+Shift+G for workspace diff
+    bind(&mut config.global, "M-z", Action::RewindTurn);
 
     // Readline shortcuts (work globally in input modes)
     bind(&mut config.global, "C-a", Action::MoveCursorStart);
@@ -183,6 +192,7 @@ pub fn default_keybindings() -> KeybindingConfig {
     );
     bind(chat, "M-<Up>", Action::ScrollPrevUserMessage);
     bind(chat, "M-<Down>", Action::ScrollNextUserMessage);
+    bind(chat, "M-/", Action::FindInChat);
 
     // Tab toggles Plan/Build mode
     chat.insert(
@@ -230,6 +240,7 @@ pub fn default_keybindings() -> KeybindingConfig {
     );
     bind(scrolling, "g", Action::ScrollToTop);
     bind(scrolling, "G", Action::ScrollToBottom);
+    bind(scrolling, "/", Action::FindInChat);
     scrolling.insert(
         KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
         Action::Cancel,
@@ -648,6 +659,277 @@ pub fn default_keybindings() -> KeybindingConfig {
         Action::Backspace,
     );
 
+    // ========== Session Search ==========
+    let session_search = config.context.entry(KeyContext::SessionSearch).or_default();
+
+    session_search.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::SelectPrev,
+    );
+    session_search.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::SelectNext,
+    );
+    bind(session_search, "C-j", Action::SelectNext);
+    bind(session_search, "C-k", Action::SelectPrev);
+    bind(session_search, "C-n", Action::SelectNext);
+    bind(session_search, "C-p", Action::SelectPrev);
+    session_search.insert(
+        KeyCombo::new(KeyCode::PageDown, KeyModifiers::NONE),
+        Action::SelectPageDown,
+    );
+    session_search.insert(
+        KeyCombo::new(KeyCode::PageUp, KeyModifiers::NONE),
+        Action::SelectPageUp,
+    );
+    // Swallow tab switching while the dialog is open
+    session_search.insert(
+        KeyCombo::new(KeyCode::Tab, KeyModifiers::NONE),
+        Action::SelectNext,
+    );
+    session_search.insert(
+        KeyCombo::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+        Action::SelectPrev,
+    );
+    session_search.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::OpenSearchResult,
+    );
+    session_search.insert(
+        KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
+        Action::Cancel,
+    );
+    session_search.insert(
+        KeyCombo::new(KeyCode::Backspace, KeyModifiers::NONE),
+        Action::Backspace,
+    );
+
+    // ========== Chat Find ==========
+    let chat_find = config.context.entry(KeyContext::ChatFind).or_default();
+
+    chat_find.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::FindNext,
+    );
+    chat_find.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::FindNext,
+    );
+    chat_find.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::FindPrev,
+    );
+    bind(chat_find, "C-n", Action::FindNext);
+    bind(chat_find, "C-p", Action::FindPrev);
+    chat_find.insert(
+        KeyCombo::new(KeyCode::PageUp, KeyModifiers::NONE),
+        Action::ScrollPageUp,
+    );
+    chat_find.insert(
+        KeyCombo::new(KeyCode::PageDown, KeyModifiers::NONE),
+        Action::ScrollPageDown,
+    );
+    chat_find.insert(
+        KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
+        Action::Cancel,
+    );
+    chat_find.insert(
+        KeyCombo::new(KeyCode::Backspace, KeyModifiers::NONE),
+        Action::Backspace,
+    );
+
+    // ========== Usage Stats ==========
+    let usage_stats = config.context.entry(KeyContext::UsageStats).or_default();
+
+    usage_stats.insert(
+        KeyCombo::new(KeyCode::Tab, KeyModifiers::NONE),
+        Action::NextStatsView,
+    );
+    usage_stats.insert(
+        KeyCombo::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+        Action::PrevStatsView,
+    );
+    usage_stats.insert(
+        KeyCombo::new(KeyCode::Right, KeyModifiers::NONE),
+        Action::NextStatsView,
+    );
+    usage_stats.insert(
+        KeyCombo::new(KeyCode::Left, KeyModifiers::NONE),
+        Action::PrevStatsView,
+    );
+    bind(usage_stats, "r", Action::CycleStatsRange);
+    usage_stats.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::SelectPrev,
+    );
+    usage_stats.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::SelectNext,
+    );
+    bind(usage_stats, "k", Action::SelectPrev);
+    bind(usage_stats, "j", Action::SelectNext);
+    usage_stats.insert(
+        KeyCombo::new(KeyCode::PageUp, KeyModifiers::NONE),
+        Action::SelectPageUp,
+    );
+    usage_stats.insert(
+        KeyCombo::new(KeyCode::PageDown, KeyModifiers::NONE),
+        Action::SelectPageDown,
+    );
+    usage_stats.insert(
+        KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
+        Action::Cancel,
+    );
+    bind(usage_stats, "q", Action::Cancel);
+
+    // ========== Diff Viewer ==========
+    let diff = config.context.entry(KeyContext::DiffViewer).or_default();
+
+    diff.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::ScrollUp(1),
+    );
+    diff.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::ScrollDown(1),
+    );
+    bind(diff, "k", Action::ScrollUp(1));
+    bind(diff, "j", Action::ScrollDown(1));
+    diff.insert(
+        KeyCombo::new(KeyCode::PageUp, KeyModifiers::NONE),
+        Action::ScrollPageUp,
+    );
+    diff.insert(
+        KeyCombo::new(KeyCode::PageDown, KeyModifiers::NONE),
+        Action::ScrollPageDown,
+    );
+    bind(diff, "C-u", Action::ScrollPageUp);
+    bind(diff, "C-d", Action::ScrollPageDown);
+    bind(diff, "g", Action::ScrollToTop);
+    bind(diff, "G", Action::ScrollToBottom);
+    bind(diff, "n", Action::DiffNextFile);
+    bind(diff, "p", Action::DiffPrevFile);
+    bind(diff, "]", Action::DiffNextHunk);
+    bind(diff, "[", Action::DiffPrevHunk);
+    bind(diff, "s", Action::DiffStageHunk);
+    bind(diff, "x", Action::DiffRevertHunk);
+    bind(diff, "t", Action::DiffToggleScope);
+    bind(diff, "v", Action::DiffToggleLayout);
+    bind(diff, "b", Action::DiffToggleBase);
+    bind(diff, "r", Action::DiffReload);
+
+    // ========== Race Viewer ==========
+    let race = config.context.entry(KeyContext::RaceViewer).or_default();
+
+    race.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::ScrollUp(1),
+    );
+    race.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::ScrollDown(1),
+    );
+    bind(race, "k", Action::ScrollUp(1));
+    bind(race, "j", Action::ScrollDown(1));
+    race.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::RaceOpenContender,
+    );
+    bind(race, "w", Action::RacePickWinner);
+    bind(race, "t", Action::RaceRunTests);
+    bind(race, "r", Action::RaceReload);
+
+    // ========== Commit Dialog ==========
+    let commit = config.context.entry(KeyContext::CommitDialog).or_default();
+
+    commit.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::Confirm,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::SHIFT),
+        Action::InsertNewline,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::ALT),
+        Action::InsertNewline,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
+        Action::Cancel,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Tab, KeyModifiers::NONE),
+        Action::ToggleCommitPush,
+    );
+    bind(commit, "C-r", Action::RegenerateCommitMessage);
+    commit.insert(
+        KeyCombo::new(KeyCode::Backspace, KeyModifiers::NONE),
+        Action::Backspace,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Delete, KeyModifiers::NONE),
+        Action::Delete,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Left, KeyModifiers::NONE),
+        Action::MoveCursorLeft,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Right, KeyModifiers::NONE),
+        Action::MoveCursorRight,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::MoveCursorUp,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::MoveCursorDown,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::Home, KeyModifiers::NONE),
+        Action::MoveCursorStart,
+    );
+    commit.insert(
+        KeyCombo::new(KeyCode::End, KeyModifiers::NONE),
+        Action::MoveCursorEnd,
+    );
+
+    // ========== Review Comments ==========
+    let review = config
+        .context
+        .entry(KeyContext::ReviewComments)
+        .or_default();
+
+    review.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::SelectPrev,
+    );
+    review.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::SelectNext,
+    );
+    bind(review, "k", Action::SelectPrev);
+    bind(review, "j", Action::SelectNext);
+    review.insert(
+        KeyCombo::new(KeyCode::Char(' '), KeyModifiers::NONE),
+        Action::ToggleReviewCommentSelected,
+    );
+    bind(review, "a", Action::SelectAllReviewComments);
+    bind(review, "m", Action::ToggleReviewCommentAddressed);
+    bind(review, "h", Action::ToggleAddressedReviewComments);
+    bind(review, "r", Action::ReloadReviewComments);
+    review.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::Confirm,
+    );
+    review.insert(
+        KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
+        Action::Cancel,
+    );
+    bind(review, "q", Action::Cancel);
+
     // ========== Command Palette ==========
     let palette = config
         .context
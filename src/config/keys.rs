@@ -118,6 +118,20 @@ pub enum KeyContext {
     HelpDialog,
     /// Session import picker
     SessionImport,
+    /// Session search dialog
+    SessionSearch,
+    /// Find in the current chat transcript
+    ChatFind,
+    /// Usage statistics dialog
+    UsageStats,
+    /// Workspace diff tab
+    DiffViewer,
+    /// Race comparison tab
+    RaceViewer,
+    /// Commit dialog
+    CommitDialog,
+    /// PR review comments dialog
+    ReviewComments,
     /// Command palette
     CommandPalette,
     /// Theme picker
@@ -142,6 +156,13 @@ impl KeyContext {
             KeyContext::Command,
             KeyContext::HelpDialog,
             KeyContext::SessionImport,
+            KeyContext::SessionSearch,
+            KeyContext::ChatFind,
+            KeyContext::UsageStats,
+            KeyContext::DiffViewer,
+            KeyContext::RaceViewer,
+            KeyContext::CommitDialog,
+            KeyContext::ReviewComments,
             KeyContext::CommandPalette,
             KeyContext::ThemePicker,
             KeyContext::QueueEditing,
@@ -170,10 +191,15 @@ impl KeyContext {
             InputMode::Command => return KeyContext::Command,
             InputMode::ShowingHelp => return KeyContext::HelpDialog,
             InputMode::ImportingSession => return KeyContext::SessionImport,
+            InputMode::SearchingSessions => return KeyContext::SessionSearch,
+            InputMode::FindingInChat => return KeyContext::ChatFind,
+            InputMode::ViewingUsageStats => return KeyContext::UsageStats,
             InputMode::CommandPalette | InputMode::SlashMenu => return KeyContext::CommandPalette,
             InputMode::MissingTool => return KeyContext::Dialog,
             InputMode::SelectingTheme => return KeyContext::ThemePicker,
             InputMode::QueueEditing => return KeyContext::QueueEditing,
+            InputMode::Committing => return KeyContext::CommitDialog,
+            InputMode::ReviewingComments => return KeyContext::ReviewComments,
             // Non-modal modes - continue to check view mode
             InputMode::Normal | InputMode::Scrolling | InputMode::SidebarNavigation => {}
         }
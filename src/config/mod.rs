@@ -5,6 +5,8 @@ mod settings;
 pub use default_keys::default_keybindings;
 pub use keys::{parse_key_notation, KeyCombo, KeyContext, KeyParseError, KeybindingConfig};
 pub use settings::{
-    parse_action, save_default_model, save_theme_config, save_tool_path, Config, QueueDelivery,
-    QueueMode, SteerBehavior, SteerFallback, COMMAND_NAMES, EXAMPLE_CONFIG,
+    parse_action, save_default_model, save_permission_rule, save_theme_config, save_tool_path,
+    BudgetConfig, CheckpointConfig, CiConfig, Config, DaemonConfig, DesktopNotification, HookSet,
+    HooksConfig, NotificationConfig, QueueDelivery, QueueMode, RaceConfig, SteerBehavior,
+    SteerFallback, WebConfig, WebhookConfig, COMMAND_NAMES, EXAMPLE_CONFIG,
 };
@@ -3,10 +3,13 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
-use toml_edit::{DocumentMut, Item, Table};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table};
 
-use crate::agent::{AgentType, ModelRegistry};
-use crate::git::WorkspaceMode;
+use crate::agent::{
+    AgentType, ModelPricing, ModelRegistry, PermissionDecision, PermissionPolicy, PermissionRule,
+    SandboxMode, TokenUsage,
+};
+use crate::git::{SyncStrategy, WorkspaceMode};
 use crate::ui::action::Action;
 use crate::util::paths::config_path;
 use crate::util::tools::{Tool, ToolPaths};
@@ -32,12 +35,28 @@ pub struct Config {
     pub show_token_usage: bool,
     /// Show estimated cost in status bar
     pub show_cost: bool,
-    /// Default allowed tools for Claude
-    pub claude_allowed_tools: Vec<String>,
-    /// Claude model pricing (input tokens per $1M)
-    pub claude_input_cost_per_million: f64,
-    /// Claude model pricing (output tokens per $1M)
-    pub claude_output_cost_per_million: f64,
+    /// Tool permission rules for all agents
+    pub permissions: PermissionPolicy,
+    /// Per-model pricing overrides (agent -> model ID -> pricing)
+    pub pricing: HashMap<AgentType, HashMap<String, ModelPricing>>,
+    /// Spend limits
+    pub budget: BudgetConfig,
+    /// Per-turn workspace checkpoints
+    pub checkpoints: CheckpointConfig,
+    /// Multi-agent races
+    pub race: RaceConfig,
+    /// Fixing failing CI checks
+    pub ci: CiConfig,
+    /// Workspace lifecycle hooks
+    pub hooks: HooksConfig,
+    /// Web server access and TLS
+    pub web: WebConfig,
+    /// Attaching the TUI to a running `conduit serve`
+    pub daemon: DaemonConfig,
+    /// Outbound webhooks for `conduit serve` activity
+    pub webhooks: Vec<WebhookConfig>,
+    /// Notifications when an agent needs attention
+    pub notifications: NotificationConfig,
     /// Keybinding configuration
     pub keybindings: KeybindingConfig,
     /// Configured paths for external tools (git, gh, claude, codex, gemini)
@@ -156,6 +175,7 @@ pub struct WorkspacesConfig {
     pub default_mode: WorkspaceMode,
     pub archive_delete_branch: bool,
     pub archive_remote_prompt: bool,
+    pub sync_strategy: SyncStrategy,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -163,6 +183,280 @@ pub struct TomlWorkspacesConfig {
     pub mode: Option<WorkspaceMode>,
     pub archive_delete_branch: Option<bool>,
     pub archive_remote_prompt: Option<bool>,
+    pub sync_strategy: Option<SyncStrategy>,
+}
+
+/// Spend limits in USD. Soft limits warn before a turn; hard limits block it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BudgetConfig {
+    pub daily_soft_limit: Option<f64>,
+    pub daily_hard_limit: Option<f64>,
+    pub session_soft_limit: Option<f64>,
+    pub session_hard_limit: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlBudgetConfig {
+    pub daily_soft_limit: Option<f64>,
+    pub daily_hard_limit: Option<f64>,
+    pub session_soft_limit: Option<f64>,
+    pub session_hard_limit: Option<f64>,
+}
+
+/// Per-turn workspace checkpoints
+#[derive(Debug, Clone, Copy)]
+pub struct CheckpointConfig {
+    /// Snapshot the workspace whenever an agent turn starts
+    pub enabled: bool,
+    /// Rewinding also removes the undone turns from the transcript
+    pub truncate_transcript: bool,
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            truncate_transcript: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlCheckpointConfig {
+    pub enabled: Option<bool>,
+    pub truncate_transcript: Option<bool>,
+}
+
+/// Multi-agent races
+#[derive(Debug, Clone, Default)]
+pub struct RaceConfig {
+    /// Contenders as "agent" or "agent:model" (empty = every installed agent)
+    pub contenders: Vec<String>,
+    /// Shell command run in each contender's workspace once its turn ends
+    pub test_command: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlRaceConfig {
+    pub contenders: Option<Vec<String>>,
+    pub test_command: Option<String>,
+}
+
+/// Fixing failing CI checks
+#[derive(Debug, Clone, Copy)]
+pub struct CiConfig {
+    /// Send failing checks to the agent when a PR's checks start failing
+    pub auto_fix: bool,
+    /// Automatic fix attempts per workspace before giving up
+    pub auto_fix_limit: u32,
+}
+
+impl Default for CiConfig {
+    fn default() -> Self {
+        Self {
+            auto_fix: false,
+            auto_fix_limit: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlCiConfig {
+    pub auto_fix: Option<bool>,
+    pub auto_fix_limit: Option<u32>,
+}
+
+/// Web server access and TLS (`conduit serve`)
+#[derive(Debug, Clone)]
+pub struct WebConfig {
+    /// Require an API token for the REST API and WebSocket
+    pub auth: bool,
+    /// PEM certificate chain to serve HTTPS with
+    pub tls_cert: Option<PathBuf>,
+    /// PEM private key for `tls_cert`
+    pub tls_key: Option<PathBuf>,
+    /// Origins allowed to call the API besides the server's own
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            auth: true,
+            tls_cert: None,
+            tls_key: None,
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlWebConfig {
+    pub auth: Option<bool>,
+    pub tls_cert: Option<PathBuf>,
+    pub tls_key: Option<PathBuf>,
+    pub allowed_origins: Option<Vec<String>>,
+}
+
+/// Attaching the TUI to a running `conduit serve` (`conduit --attach`)
+#[derive(Debug, Clone, Default)]
+pub struct DaemonConfig {
+    /// Always attach, without `--attach`
+    pub attach: bool,
+    /// WebSocket URL of a remote daemon; the local one is found automatically
+    pub url: Option<String>,
+    /// API token for `url`
+    pub token: Option<String>,
+    /// Certificate to trust for a `wss://` daemon with a self-signed certificate
+    pub ca_cert: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlDaemonConfig {
+    pub attach: Option<bool>,
+    pub url: Option<String>,
+    pub token: Option<String>,
+    pub ca_cert: Option<PathBuf>,
+}
+
+/// An endpoint that `conduit serve` POSTs activity events to (`[[webhooks]]`)
+#[derive(Debug, Clone, Default)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Key for the `X-Conduit-Signature` HMAC-SHA256 of each body
+    pub secret: Option<String>,
+    /// Event types to send (empty = all)
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlWebhookConfig {
+    pub url: String,
+    pub secret: Option<String>,
+    pub events: Option<Vec<String>>,
+}
+
+/// Escape sequence used for desktop notifications
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DesktopNotification {
+    #[default]
+    Off,
+    /// `OSC 9` (iTerm2, WezTerm, Windows Terminal, kitty, ghostty)
+    Osc9,
+    /// `OSC 777` (rxvt-unicode, foot, VTE-based terminals)
+    Osc777,
+}
+
+/// Notifications when an agent needs attention (`[notifications]`)
+#[derive(Debug, Clone)]
+pub struct NotificationConfig {
+    /// Ring the terminal bell
+    pub bell: bool,
+    pub desktop: DesktopNotification,
+    /// Shell command run for each notification
+    pub command: Option<String>,
+    pub turn_complete: bool,
+    pub turn_failed: bool,
+    /// A permission prompt or question from the agent is waiting
+    pub input_required: bool,
+    /// Completed turns shorter than this do not notify
+    pub min_turn_secs: u64,
+    /// Start muted; `:quiet` toggles it
+    pub quiet: bool,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            bell: false,
+            desktop: DesktopNotification::Off,
+            command: None,
+            turn_complete: true,
+            turn_failed: true,
+            input_required: true,
+            min_turn_secs: 10,
+            quiet: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlNotificationConfig {
+    pub bell: Option<bool>,
+    pub desktop: Option<DesktopNotification>,
+    pub command: Option<String>,
+    pub turn_complete: Option<bool>,
+    pub turn_failed: Option<bool>,
+    pub input_required: Option<bool>,
+    pub min_turn_secs: Option<u64>,
+    pub quiet: Option<bool>,
+}
+
+/// Hook commands and copy rules as written in `[hooks]`, `[hooks.repos.<name>]`
+/// or a repository's `.conduit.toml`. Unset fields fall through to the layer below.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HookSet {
+    /// Commands run in a new workspace
+    pub on_create: Option<Vec<String>>,
+    /// Commands run in a workspace before it is removed
+    pub before_archive: Option<Vec<String>>,
+    /// Commands run in the repository after a workspace is removed
+    pub after_archive: Option<Vec<String>>,
+    /// Repository-relative paths copied into new workspaces
+    pub copy: Option<Vec<String>>,
+}
+
+/// Workspace lifecycle hooks
+#[derive(Debug, Clone)]
+pub struct HooksConfig {
+    /// Hooks for every repository
+    pub defaults: HookSet,
+    /// Per-repository overrides, keyed by repository name
+    pub repos: HashMap<String, HookSet>,
+    /// Read hooks from a repository's `.conduit.toml`. Off by default since
+    /// those hooks are commands chosen by whoever controls the repository
+    pub repo_config: bool,
+    /// Time limit for each hook command
+    pub timeout_secs: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            defaults: HookSet::default(),
+            repos: HashMap::new(),
+            repo_config: false,
+            timeout_secs: 600,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlHooksConfig {
+    #[serde(flatten)]
+    pub defaults: HookSet,
+    pub repos: Option<HashMap<String, HookSet>>,
+    pub repo_config: Option<bool>,
+    pub timeout_secs: Option<u64>,
+}
+
+/// TOML representation of the [permissions] section
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlPermissionsConfig {
+    pub default: Option<PermissionDecision>,
+    pub sandbox: Option<SandboxMode>,
+    pub rules: Option<Vec<PermissionRule>>,
+}
+
+/// TOML representation of a model price override (USD per million tokens).
+/// Unset fields fall back to the built-in price for the model.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlModelPricing {
+    pub input: Option<f64>,
+    pub output: Option<f64>,
+    pub cache_read: Option<f64>,
+    pub cache_write: Option<f64>,
 }
 
 /// TOML representation of default model
@@ -181,17 +475,17 @@ impl Default for Config {
             max_tabs: 10,
             show_token_usage: true,
             show_cost: true,
-            claude_allowed_tools: vec![
-                "Read".into(),
-                "Edit".into(),
-                "Write".into(),
-                "Bash".into(),
-                "Glob".into(),
-                "Grep".into(),
-            ],
-            // Claude Sonnet 3.5 pricing
-            claude_input_cost_per_million: 3.0,
-            claude_output_cost_per_million: 15.0,
+            permissions: PermissionPolicy::default(),
+            pricing: HashMap::new(),
+            budget: BudgetConfig::default(),
+            checkpoints: CheckpointConfig::default(),
+            race: RaceConfig::default(),
+            ci: CiConfig::default(),
+            hooks: HooksConfig::default(),
+            web: WebConfig::default(),
+            daemon: DaemonConfig::default(),
+            webhooks: Vec::new(),
+            notifications: NotificationConfig::default(),
             keybindings: default_keybindings(),
             tool_paths: ToolPaths::default(),
             theme_name: None,
@@ -221,6 +515,7 @@ impl Default for Config {
                 default_mode: WorkspaceMode::Worktree,
                 archive_delete_branch: true,
                 archive_remote_prompt: true,
+                sync_strategy: SyncStrategy::Merge,
             },
         }
     }
@@ -296,6 +591,28 @@ pub struct TomlConfig {
     pub web_status: Option<TomlWebStatusConfig>,
     /// Workspace defaults
     pub workspaces: Option<TomlWorkspacesConfig>,
+    /// Model pricing overrides ([pricing.<agent>.<model>])
+    pub pricing: Option<HashMap<String, HashMap<String, TomlModelPricing>>>,
+    /// Spend limits
+    pub budget: Option<TomlBudgetConfig>,
+    /// Per-turn workspace checkpoints
+    pub checkpoints: Option<TomlCheckpointConfig>,
+    /// Multi-agent races
+    pub race: Option<TomlRaceConfig>,
+    /// Fixing failing CI checks
+    pub ci: Option<TomlCiConfig>,
+    /// Workspace lifecycle hooks
+    pub hooks: Option<TomlHooksConfig>,
+    /// Web server access and TLS
+    pub web: Option<TomlWebConfig>,
+    /// Attaching the TUI to a running `conduit serve`
+    pub daemon: Option<TomlDaemonConfig>,
+    /// Outbound webhooks
+    pub webhooks: Option<Vec<TomlWebhookConfig>>,
+    /// Notifications when an agent needs attention
+    pub notifications: Option<TomlNotificationConfig>,
+    /// Tool permission rules
+    pub permissions: Option<TomlPermissionsConfig>,
 }
 
 impl TomlKeybindings {
@@ -388,13 +705,22 @@ pub fn parse_action(name: &str) -> Option<Action> {
         "toggle_sidebar" => Some(Action::ToggleSidebar),
         "new_project" => Some(Action::NewProject),
         "open_pr" => Some(Action::OpenPr),
+        "open_commit_dialog" | "commit" => Some(Action::OpenCommitDialog),
+        "open_review_comments" | "review" => Some(Action::OpenReviewComments),
+        "fix_ci" => Some(Action::FixCi),
+        "sync_with_base" | "sync" => Some(Action::SyncWithBase),
+        "continue_sync" | "sync_continue" => Some(Action::ContinueSync),
+        "abort_sync" | "sync_abort" => Some(Action::AbortSync),
+        "toggle_quiet_mode" | "quiet" => Some(Action::ToggleQuietMode),
         "fork_session" => Some(Action::ForkSession),
+        "handoff_session" | "handoff" => Some(Action::HandoffSession),
         "interrupt_agent" => Some(Action::InterruptAgent),
         "toggle_view_mode" => Some(Action::ToggleViewMode),
         "show_model_selector" => Some(Action::ShowModelSelector),
         "show_theme_picker" => Some(Action::ShowThemePicker),
         "toggle_metrics" => Some(Action::ToggleMetrics),
         "dump_debug_state" => Some(Action::DumpDebugState),
+        "export_transcript" => Some(Action::ExportTranscript),
         "suspend" => Some(Action::Suspend),
         "copy_selection" => Some(Action::CopySelection),
 
@@ -412,6 +738,9 @@ pub fn parse_action(name: &str) -> Option<Action> {
         "scroll_to_bottom" => Some(Action::ScrollToBottom),
         "prev_user_message" => Some(Action::ScrollPrevUserMessage),
         "next_user_message" => Some(Action::ScrollNextUserMessage),
+        "find_in_chat" | "find" => Some(Action::FindInChat),
+        "find_next" => Some(Action::FindNext),
+        "find_prev" => Some(Action::FindPrev),
 
         // Input editing
         "insert_newline" => Some(Action::InsertNewline),
@@ -479,6 +808,49 @@ pub fn parse_action(name: &str) -> Option<Action> {
         "import_session" => Some(Action::ImportSession),
         "cycle_import_filter" => Some(Action::CycleImportFilter),
 
+        // Session search
+        "open_session_search" | "search" => Some(Action::OpenSessionSearch),
+        "open_search_result" => Some(Action::OpenSearchResult),
+
+        // Usage stats
+        "show_usage_stats" | "stats" => Some(Action::ShowUsageStats),
+        "next_stats_view" => Some(Action::NextStatsView),
+        "prev_stats_view" => Some(Action::PrevStatsView),
+        "cycle_stats_range" => Some(Action::CycleStatsRange),
+
+        "rewind_turn" | "rewind" => Some(Action::RewindTurn),
+
+        // Diff viewer
+        "show_diff" | "diff" => Some(Action::ShowDiff),
+        "diff_next_file" => Some(Action::DiffNextFile),
+        "diff_prev_file" => Some(Action::DiffPrevFile),
+        "diff_toggle_layout" => Some(Action::DiffToggleLayout),
+        "diff_toggle_base" => Some(Action::DiffToggleBase),
+        "diff_reload" => Some(Action::DiffReload),
+        "diff_next_hunk" => Some(Action::DiffNextHunk),
+        "diff_prev_hunk" => Some(Action::DiffPrevHunk),
+        "diff_stage_hunk" => Some(Action::DiffStageHunk),
+        "diff_revert_hunk" => Some(Action::DiffRevertHunk),
+        "diff_toggle_scope" => Some(Action::DiffToggleScope),
+
+        // Race
+        "start_race" | "race" => Some(Action::StartRace),
+        "race_open_contender" => Some(Action::RaceOpenContender),
+        "race_pick_winner" => Some(Action::RacePickWinner),
+        "race_run_tests" => Some(Action::RaceRunTests),
+        "race_reload" => Some(Action::RaceReload),
+
+        // Commit dialog
+        "toggle_commit_push" => Some(Action::ToggleCommitPush),
+        "regenerate_commit_message" => Some(Action::RegenerateCommitMessage),
+
+        // Review comments
+        "toggle_review_comment_selected" => Some(Action::ToggleReviewCommentSelected),
+        "select_all_review_comments" => Some(Action::SelectAllReviewComments),
+        "toggle_review_comment_addressed" => Some(Action::ToggleReviewCommentAddressed),
+        "toggle_addressed_review_comments" => Some(Action::ToggleAddressedReviewComments),
+        "reload_review_comments" => Some(Action::ReloadReviewComments),
+
         // Command mode
         "show_help" => Some(Action::ShowHelp),
         "execute_command" => Some(Action::ExecuteCommand),
@@ -498,13 +870,25 @@ pub const COMMAND_NAMES: &[&str] = &[
     "toggle_sidebar",
     "new_project",
     "open_pr",
+    "commit",
+    "review",
+    "fix_ci",
+    "sync",
+    "sync_continue",
+    "sync_abort",
+    "quiet",
     "fork_session",
+    "handoff_session",
     "interrupt_agent",
     "toggle_view_mode",
     "show_model_selector",
     "show_theme_picker",
     "toggle_metrics",
     "dump_debug_state",
+    "export_transcript",
+    "show_diff",
+    "rewind",
+    "race",
     "suspend",
     "copy_selection",
     // Tab management
@@ -520,6 +904,10 @@ pub const COMMAND_NAMES: &[&str] = &[
     "scroll_to_bottom",
     "prev_user_message",
     "next_user_message",
+    "find_in_chat",
+    "find",
+    "find_next",
+    "find_prev",
     // Input editing
     "insert_newline",
     "backspace",
@@ -580,6 +968,16 @@ pub const COMMAND_NAMES: &[&str] = &[
     "import",
     "import_session",
     "cycle_import_filter",
+    // Session search
+    "open_session_search",
+    "search",
+    "open_search_result",
+    // Usage stats
+    "show_usage_stats",
+    "stats",
+    "next_stats_view",
+    "prev_stats_view",
+    "cycle_stats_range",
     // Command mode
     "show_help",
     // Command palette
@@ -704,6 +1102,121 @@ impl Config {
                         if let Some(remote_prompt) = workspaces.archive_remote_prompt {
                             config.workspaces.archive_remote_prompt = remote_prompt;
                         }
+                        if let Some(strategy) = workspaces.sync_strategy {
+                            config.workspaces.sync_strategy = strategy;
+                        }
+                    }
+                    // Load model pricing overrides
+                    if let Some(pricing) = toml_config.pricing {
+                        config.pricing = resolve_pricing_overrides(pricing);
+                    }
+                    // Load spend limits
+                    if let Some(budget) = toml_config.budget {
+                        config.budget = BudgetConfig {
+                            daily_soft_limit: budget.daily_soft_limit,
+                            daily_hard_limit: budget.daily_hard_limit,
+                            session_soft_limit: budget.session_soft_limit,
+                            session_hard_limit: budget.session_hard_limit,
+                        };
+                    }
+                    // Load checkpoint settings
+                    if let Some(checkpoints) = toml_config.checkpoints {
+                        if let Some(enabled) = checkpoints.enabled {
+                            config.checkpoints.enabled = enabled;
+                        }
+                        if let Some(truncate) = checkpoints.truncate_transcript {
+                            config.checkpoints.truncate_transcript = truncate;
+                        }
+                    }
+                    // Load race settings
+                    if let Some(race) = toml_config.race {
+                        config.race = RaceConfig {
+                            contenders: race.contenders.unwrap_or_default(),
+                            test_command: race
+                                .test_command
+                                .filter(|command| !command.trim().is_empty()),
+                        };
+                    }
+                    // Load CI settings
+                    if let Some(ci) = toml_config.ci {
+                        if let Some(auto_fix) = ci.auto_fix {
+                            config.ci.auto_fix = auto_fix;
+                        }
+                        if let Some(limit) = ci.auto_fix_limit {
+                            config.ci.auto_fix_limit = limit;
+                        }
+                    }
+                    if let Some(hooks) = toml_config.hooks {
+                        config.hooks.defaults = hooks.defaults;
+                        if let Some(repos) = hooks.repos {
+                            config.hooks.repos = repos;
+                        }
+                        if let Some(repo_config) = hooks.repo_config {
+                            config.hooks.repo_config = repo_config;
+                        }
+                        if let Some(timeout_secs) = hooks.timeout_secs {
+                            config.hooks.timeout_secs = timeout_secs.max(1);
+                        }
+                    }
+                    if let Some(web) = toml_config.web {
+                        if let Some(auth) = web.auth {
+                            config.web.auth = auth;
+                        }
+                        config.web.tls_cert = web.tls_cert;
+                        config.web.tls_key = web.tls_key;
+                        if let Some(origins) = web.allowed_origins {
+                            config.web.allowed_origins = origins;
+                        }
+                    }
+                    // Load daemon attachment
+                    if let Some(daemon) = toml_config.daemon {
+                        config.daemon = DaemonConfig {
+                            attach: daemon.attach.unwrap_or(false),
+                            url: daemon.url,
+                            token: daemon.token,
+                            ca_cert: daemon.ca_cert,
+                        };
+                    }
+                    if let Some(webhooks) = toml_config.webhooks {
+                        config.webhooks = webhooks
+                            .into_iter()
+                            .filter(|webhook| !webhook.url.trim().is_empty())
+                            .map(|webhook| WebhookConfig {
+                                url: webhook.url,
+                                secret: webhook.secret.filter(|secret| !secret.is_empty()),
+                                events: webhook.events.unwrap_or_default(),
+                            })
+                            .collect();
+                    }
+                    // Load notification settings
+                    if let Some(notifications) = toml_config.notifications {
+                        let defaults = NotificationConfig::default();
+                        config.notifications = NotificationConfig {
+                            bell: notifications.bell.unwrap_or(defaults.bell),
+                            desktop: notifications.desktop.unwrap_or(defaults.desktop),
+                            command: notifications
+                                .command
+                                .filter(|command| !command.trim().is_empty()),
+                            turn_complete: notifications
+                                .turn_complete
+                                .unwrap_or(defaults.turn_complete),
+                            turn_failed: notifications.turn_failed.unwrap_or(defaults.turn_failed),
+                            input_required: notifications
+                                .input_required
+                                .unwrap_or(defaults.input_required),
+                            min_turn_secs: notifications
+                                .min_turn_secs
+                                .unwrap_or(defaults.min_turn_secs),
+                            quiet: notifications.quiet.unwrap_or(defaults.quiet),
+                        };
+                    }
+                    // Load tool permission rules
+                    if let Some(permissions) = toml_config.permissions {
+                        config.permissions = PermissionPolicy {
+                            default: permissions.default.unwrap_or(PermissionDecision::Allow),
+                            sandbox: permissions.sandbox.unwrap_or_default(),
+                            rules: permissions.rules.unwrap_or_default(),
+                        };
                     }
                 }
             }
@@ -759,13 +1272,65 @@ impl Config {
         self.default_model = Some(model_id);
     }
 
-    /// Calculate cost for given token usage
-    pub fn calculate_cost(&self, input_tokens: i64, output_tokens: i64) -> f64 {
-        let input_cost = (input_tokens as f64 / 1_000_000.0) * self.claude_input_cost_per_million;
-        let output_cost =
-            (output_tokens as f64 / 1_000_000.0) * self.claude_output_cost_per_million;
-        input_cost + output_cost
+    /// Get pricing for a model (config override, then built-in list price).
+    ///
+    /// `None` for the model means the agent's default model.
+    pub fn model_pricing(
+        &self,
+        agent_type: AgentType,
+        model: Option<&str>,
+    ) -> Option<ModelPricing> {
+        let model_id = match model {
+            Some(id) => ModelRegistry::find_model(agent_type, id)
+                .map(|m| m.id)
+                .unwrap_or_else(|| id.to_string()),
+            None => ModelRegistry::default_model(agent_type),
+        };
+        self.pricing
+            .get(&agent_type)
+            .and_then(|models| models.get(&model_id))
+            .copied()
+            .or_else(|| ModelRegistry::pricing(agent_type, &model_id))
     }
+
+    /// Calculate cost in USD for token usage on a model (0 when pricing is unknown)
+    pub fn calculate_cost(
+        &self,
+        agent_type: AgentType,
+        model: Option<&str>,
+        usage: &TokenUsage,
+    ) -> f64 {
+        self.model_pricing(agent_type, model)
+            .map(|pricing| pricing.cost(usage))
+            .unwrap_or(0.0)
+    }
+}
+
+/// Merge `[pricing.<agent>.<model>]` tables over the built-in model prices
+fn resolve_pricing_overrides(
+    tables: HashMap<String, HashMap<String, TomlModelPricing>>,
+) -> HashMap<AgentType, HashMap<String, ModelPricing>> {
+    let mut resolved: HashMap<AgentType, HashMap<String, ModelPricing>> = HashMap::new();
+    for (agent, models) in tables {
+        let agent_type = AgentType::parse(&agent);
+        for (model_id, overrides) in models {
+            let (model_id, base) = match ModelRegistry::find_model(agent_type, &model_id) {
+                Some(model) => (model.id, model.pricing.unwrap_or_default()),
+                None => (model_id, ModelPricing::default()),
+            };
+            let pricing = ModelPricing {
+                input: overrides.input.unwrap_or(base.input),
+                output: overrides.output.unwrap_or(base.output),
+                cache_read: overrides.cache_read.unwrap_or(base.cache_read),
+                cache_write: overrides.cache_write.unwrap_or(base.cache_write),
+            };
+            resolved
+                .entry(agent_type)
+                .or_default()
+                .insert(model_id, pricing);
+        }
+    }
+    resolved
 }
 
 /// Save a tool path to the config file
@@ -901,3 +1466,64 @@ pub fn save_default_model(agent_type: AgentType, model_id: &str) -> std::io::Res
 
     Ok(())
 }
+
+/// Append a rule to the [[permissions.rules]] list in the config file.
+pub fn save_permission_rule(rule: &PermissionRule) -> std::io::Result<()> {
+    let config_file = config_path();
+
+    // Read existing config or start with empty document
+    let contents = if config_file.exists() {
+        fs::read_to_string(&config_file)?
+    } else {
+        String::new()
+    };
+
+    // Parse as TOML document
+    let mut doc: DocumentMut = contents
+        .parse()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+    // Ensure [permissions] section exists without emitting an empty header
+    if !doc.contains_key("permissions") {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        doc["permissions"] = Item::Table(table);
+    }
+    let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+    let permissions = doc["permissions"]
+        .as_table_mut()
+        .ok_or_else(|| invalid("[permissions] is not a table"))?;
+    if !permissions.contains_key("rules") {
+        permissions.insert("rules", Item::ArrayOfTables(ArrayOfTables::new()));
+    }
+    let rules = permissions["rules"]
+        .as_array_of_tables_mut()
+        .ok_or_else(|| invalid("permissions.rules is not an array of tables"))?;
+
+    let mut entry = Table::new();
+    entry.insert("decision", toml_edit::value(rule.decision.as_str()));
+    let patterns = [
+        ("tool", &rule.tool),
+        ("command", &rule.command),
+        ("path", &rule.path),
+        ("repository", &rule.repository),
+        ("workspace", &rule.workspace),
+    ];
+    for (key, pattern) in patterns {
+        if let Some(pattern) = pattern {
+            entry.insert(key, toml_edit::value(pattern.as_str()));
+        }
+    }
+    rules.push(entry);
+
+    // Ensure parent directory exists
+    if let Some(parent) = config_file.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    fs::write(&config_file, doc.to_string())?;
+
+    Ok(())
+}
@@ -6,8 +6,11 @@ use crate::agent::{
     ClaudeCodeRunner, CodexCliRunner, GeminiCliRunner, ModelRegistry, OpencodeRunner,
 };
 use crate::config::Config;
+use crate::core::SessionJournal;
 use crate::data::{
-    AppStateStore, Database, ForkSeedStore, RepositoryStore, SessionTabStore, WorkspaceStore,
+    AppStateStore, AuditStore, CheckpointStore, Database, ForkSeedStore, RaceStore,
+    RepositoryStore, ReviewCommentStore, SearchIndexStore, SessionEventStore, SessionTabStore,
+    UsageStore, WorkspaceStore,
 };
 use crate::git::WorkspaceRepoManager;
 use crate::util::{Tool, ToolAvailability};
@@ -36,6 +39,22 @@ pub struct ConduitCore {
     session_tab_store: Option<SessionTabStore>,
     /// Fork seed DAO (for persisting fork metadata)
     fork_seed_store: Option<ForkSeedStore>,
+    /// Session event DAO (for the per-session event journal)
+    session_event_store: Option<SessionEventStore>,
+    /// Background writer for the session event journal
+    session_journal: Option<SessionJournal>,
+    /// Full-text search index DAO
+    search_index_store: Option<SearchIndexStore>,
+    /// Turn usage and cost DAO
+    usage_store: Option<UsageStore>,
+    /// Audit log DAO
+    audit_store: Option<AuditStore>,
+    /// Per-turn checkpoint DAO
+    checkpoint_store: Option<CheckpointStore>,
+    /// Multi-agent race DAO
+    race_store: Option<RaceStore>,
+    /// Addressed PR review comment DAO
+    review_comment_store: Option<ReviewCommentStore>,
     /// Claude Code runner
     claude_runner: Arc<ClaudeCodeRunner>,
     /// Codex CLI runner
@@ -59,6 +78,13 @@ impl ConduitCore {
             app_state_store,
             session_tab_store,
             fork_seed_store,
+            session_event_store,
+            search_index_store,
+            usage_store,
+            audit_store,
+            checkpoint_store,
+            race_store,
+            review_comment_store,
         ) = match Database::open_default() {
             Ok(db) => {
                 let repo_store = RepositoryStore::new(db.connection());
@@ -66,6 +92,13 @@ impl ConduitCore {
                 let app_state_store = AppStateStore::new(db.connection());
                 let session_tab_store = SessionTabStore::new(db.connection());
                 let fork_seed_store = ForkSeedStore::new(db.connection());
+                let session_event_store = SessionEventStore::new(db.connection());
+                let search_index_store = SearchIndexStore::new(db.connection());
+                let usage_store = UsageStore::new(db.connection());
+                let audit_store = AuditStore::new(db.connection());
+                let checkpoint_store = CheckpointStore::new(db.connection());
+                let race_store = RaceStore::new(db.connection());
+                let review_comment_store = ReviewCommentStore::new(db.connection());
                 (
                     Some(db),
                     Some(repo_store),
@@ -73,14 +106,28 @@ impl ConduitCore {
                     Some(app_state_store),
                     Some(session_tab_store),
                     Some(fork_seed_store),
+                    Some(session_event_store),
+                    Some(search_index_store),
+                    Some(usage_store),
+                    Some(audit_store),
+                    Some(checkpoint_store),
+                    Some(race_store),
+                    Some(review_comment_store),
                 )
             }
             Err(e) => {
                 tracing::warn!(error = %e, "Failed to open database");
-                (None, None, None, None, None, None)
+                (
+                    None, None, None, None, None, None, None, None, None, None, None, None, None,
+                )
             }
         };
 
+        let session_journal = session_event_store
+            .clone()
+            .zip(audit_store.clone())
+            .map(|(store, audit_store)| SessionJournal::spawn(store, audit_store));
+
         // Migrate old worktrees folder to workspaces (one-time migration)
         crate::util::migrate_worktrees_to_workspaces();
 
@@ -124,6 +171,14 @@ impl ConduitCore {
             app_state_store,
             session_tab_store,
             fork_seed_store,
+            session_event_store,
+            session_journal,
+            search_index_store,
+            usage_store,
+            audit_store,
+            checkpoint_store,
+            race_store,
+            review_comment_store,
             claude_runner,
             codex_runner,
             gemini_runner,
@@ -192,6 +247,71 @@ impl ConduitCore {
         self.fork_seed_store.clone()
     }
 
+    /// Get the session event store.
+    pub fn session_event_store(&self) -> Option<&SessionEventStore> {
+        self.session_event_store.as_ref()
+    }
+
+    /// Get a clone of the session event store.
+    pub fn session_event_store_clone(&self) -> Option<SessionEventStore> {
+        self.session_event_store.clone()
+    }
+
+    /// Get the session event journal writer.
+    pub fn session_journal(&self) -> Option<&SessionJournal> {
+        self.session_journal.as_ref()
+    }
+
+    /// Get the search index store.
+    pub fn search_index_store(&self) -> Option<&SearchIndexStore> {
+        self.search_index_store.as_ref()
+    }
+
+    /// Get a clone of the search index store.
+    pub fn search_index_store_clone(&self) -> Option<SearchIndexStore> {
+        self.search_index_store.clone()
+    }
+
+    /// Get the usage store.
+    pub fn usage_store(&self) -> Option<&UsageStore> {
+        self.usage_store.as_ref()
+    }
+
+    /// Get a clone of the usage store.
+    pub fn usage_store_clone(&self) -> Option<UsageStore> {
+        self.usage_store.clone()
+    }
+
+    /// Get the checkpoint store.
+    pub fn checkpoint_store(&self) -> Option<&CheckpointStore> {
+        self.checkpoint_store.as_ref()
+    }
+
+    /// Get a clone of the checkpoint store.
+    pub fn checkpoint_store_clone(&self) -> Option<CheckpointStore> {
+        self.checkpoint_store.clone()
+    }
+
+    /// Get the race store.
+    pub fn race_store(&self) -> Option<&RaceStore> {
+        self.race_store.as_ref()
+    }
+
+    /// Get a clone of the race store.
+    pub fn race_store_clone(&self) -> Option<RaceStore> {
+        self.race_store.clone()
+    }
+
+    /// Get the addressed review comment store.
+    pub fn review_comment_store(&self) -> Option<&ReviewCommentStore> {
+        self.review_comment_store.as_ref()
+    }
+
+    /// Get the audit log store.
+    pub fn audit_store(&self) -> Option<&AuditStore> {
+        self.audit_store.as_ref()
+    }
+
     /// Get the Claude runner.
     pub fn claude_runner(&self) -> &Arc<ClaudeCodeRunner> {
         &self.claude_runner
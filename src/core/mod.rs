@@ -2,6 +2,7 @@
 //!
 //! This module provides the foundational components used by both the TUI and web interfaces:
 //! - Database access and DAO stores
+//! - Session event journal
 //! - Agent runners (Claude, Codex, Gemini)
 //! - Configuration and tool availability
 //! - Worktree management
@@ -10,6 +11,13 @@ mod conduit_core;
 pub mod dto;
 mod repo_settings;
 pub mod services;
+mod session_journal;
+mod workspace_hooks;
 
 pub use conduit_core::ConduitCore;
 pub use repo_settings::{resolve_repo_workspace_settings, RepoWorkspaceSettings};
+pub use session_journal::{touched_paths, SessionJournal};
+pub use workspace_hooks::{
+    resolve_workspace_hooks, run_workspace_hooks, HookContext, HookEvent, HookFailure, HookReport,
+    WorkspaceHooks, REPO_CONFIG_FILE,
+};
@@ -1,12 +1,13 @@
 use crate::config::Config;
 use crate::data::Repository;
-use crate::git::WorkspaceMode;
+use crate::git::{SyncStrategy, WorkspaceMode};
 
 #[derive(Debug, Clone, Copy)]
 pub struct RepoWorkspaceSettings {
     pub mode: WorkspaceMode,
     pub archive_delete_branch: bool,
     pub archive_remote_prompt: bool,
+    pub sync_strategy: SyncStrategy,
 }
 
 pub fn resolve_repo_workspace_settings(
@@ -19,5 +20,6 @@ pub fn resolve_repo_workspace_settings(
             .archive_delete_branch_or(config.workspaces.archive_delete_branch),
         archive_remote_prompt: repo
             .archive_remote_prompt_or(config.workspaces.archive_remote_prompt),
+        sync_strategy: repo.sync_strategy_or(config.workspaces.sync_strategy),
     }
 }
@@ -0,0 +1,114 @@
+//! Querying and exporting the audit log of tool executions and permission decisions.
+
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+
+use crate::core::services::error::ServiceError;
+use crate::core::services::RunService;
+use crate::core::ConduitCore;
+use crate::data::{AuditEntry, AuditKind, AuditQuery};
+
+/// Filters accepted from the CLI and the HTTP API, before resolution.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub session_id: Option<uuid::Uuid>,
+    /// Workspace name or ID
+    pub workspace: Option<String>,
+    /// `tool_started`, `tool_completed`, `command`, `file_changed` or `permission`
+    pub kind: Option<String>,
+    /// RFC 3339 timestamp or `YYYY-MM-DD` (local midnight)
+    pub since: Option<String>,
+    pub limit: Option<usize>,
+}
+
+pub struct AuditService;
+
+impl AuditService {
+    /// Audit entries matching `filter`, oldest first.
+    pub fn list(core: &ConduitCore, filter: &AuditFilter) -> Result<Vec<AuditEntry>, ServiceError> {
+        let store = core
+            .audit_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        let query = AuditQuery {
+            session_id: filter.session_id,
+            // IDs are used as-is so entries of deleted workspaces stay reachable
+            workspace_id: match filter.workspace.as_deref() {
+                Some(workspace) => match uuid::Uuid::parse_str(workspace) {
+                    Ok(id) => Some(id),
+                    Err(_) => Some(RunService::find_workspace(core, workspace)?.id),
+                },
+                None => None,
+            },
+            kind: filter.kind.as_deref().map(parse_kind).transpose()?,
+            since: filter.since.as_deref().map(parse_since).transpose()?,
+            limit: filter.limit,
+        };
+        store
+            .list(&query)
+            .map_err(|e| ServiceError::Internal(format!("Failed to load audit log: {}", e)))
+    }
+
+    /// Render entries as JSON Lines, one entry per line.
+    pub fn to_jsonl(entries: &[AuditEntry]) -> Result<String, ServiceError> {
+        let mut out = String::new();
+        for entry in entries {
+            let line = serde_json::to_string(entry).map_err(|e| {
+                ServiceError::Internal(format!("Failed to serialize audit entry: {}", e))
+            })?;
+            out.push_str(&line);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+fn parse_kind(kind: &str) -> Result<AuditKind, ServiceError> {
+    AuditKind::parse(kind).ok_or_else(|| {
+        ServiceError::InvalidInput(format!(
+            "Unknown audit kind '{}' (expected tool_started, tool_completed, command, \
+             file_changed or permission)",
+            kind
+        ))
+    })
+}
+
+/// Parse an RFC 3339 timestamp, or a date meaning local midnight.
+pub(crate) fn parse_since(since: &str) -> Result<DateTime<Utc>, ServiceError> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(since) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(since, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+        .map(|midnight| midnight.with_timezone(&Utc))
+        .ok_or_else(|| {
+            ServiceError::InvalidInput(format!(
+                "Invalid time '{}' (expected YYYY-MM-DD or an RFC 3339 timestamp)",
+                since
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since() {
+        let timestamp = parse_since("2024-05-01T12:30:00Z").unwrap();
+        assert_eq!(timestamp.to_rfc3339(), "2024-05-01T12:30:00+00:00");
+        let midnight = parse_since("2024-05-01").unwrap();
+        assert_eq!(
+            midnight.with_timezone(&Local).date_naive(),
+            NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()
+        );
+        assert!(matches!(
+            parse_since("yesterday"),
+            Err(ServiceError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            parse_kind("tool"),
+            Err(ServiceError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,217 @@
+//! Per-turn workspace checkpoints and rewinding a session to before a turn.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::services::error::ServiceError;
+use crate::core::services::SessionService;
+use crate::core::{ConduitCore, SessionJournal};
+use crate::data::{Checkpoint, CheckpointStore, SessionEventStore};
+use crate::git::CheckpointManager;
+
+pub struct CheckpointService;
+
+/// A rewind resolved against the core, ready to run on a blocking thread.
+pub struct Rewind {
+    store: CheckpointStore,
+    checkpoint: Checkpoint,
+    working_dir: PathBuf,
+    journal: Option<SessionJournal>,
+    events: Option<SessionEventStore>,
+}
+
+impl Rewind {
+    /// Restore the workspace to the checkpoint.
+    ///
+    /// With `truncate_transcript`, the rewound turns are also removed from the
+    /// session's journal, along with their checkpoints. The agent's own
+    /// conversation history is not changed.
+    pub fn run(self, truncate_transcript: bool) -> Result<Checkpoint, ServiceError> {
+        let Self {
+            store,
+            checkpoint,
+            working_dir,
+            journal,
+            events,
+        } = self;
+        let session_id = checkpoint.session_id;
+
+        CheckpointManager::restore(&working_dir, &checkpoint.commit_sha)
+            .map_err(|e| ServiceError::Internal(format!("Failed to restore checkpoint: {}", e)))?;
+
+        if truncate_transcript {
+            if let Some(journal) = journal {
+                journal.flush();
+            }
+            if let Some(events) = events {
+                events
+                    .truncate_turns_from(session_id, checkpoint.created_at)
+                    .map_err(|e| {
+                        ServiceError::Internal(format!("Failed to truncate transcript: {}", e))
+                    })?;
+            }
+            let dropped = store
+                .list_by_session(session_id)
+                .map_err(|e| ServiceError::Internal(format!("Failed to load checkpoints: {}", e)))?
+                .into_iter()
+                .filter(|other| other.turn >= checkpoint.turn);
+            for other in dropped {
+                if let Err(e) = CheckpointManager::delete(&working_dir, &other.ref_name) {
+                    tracing::warn!(error = %e, ref_name = %other.ref_name, "Failed to delete checkpoint ref");
+                }
+            }
+            store
+                .delete_from_turn(session_id, checkpoint.turn)
+                .map_err(|e| {
+                    ServiceError::Internal(format!("Failed to delete checkpoints: {}", e))
+                })?;
+        }
+
+        Ok(checkpoint)
+    }
+}
+
+/// A checkpoint to take before a session's next turn, detached from the core.
+///
+/// Await [`TurnCheckpoint::record`] before handing the agent its prompt;
+/// snapshotting any later can capture the turn's first edits.
+#[derive(Clone)]
+pub struct TurnCheckpoint {
+    store: CheckpointStore,
+    session_id: Uuid,
+    workspace_id: Option<Uuid>,
+    working_dir: PathBuf,
+}
+
+impl TurnCheckpoint {
+    /// `None` when checkpoints are disabled or there is no database.
+    pub fn for_session(
+        core: &ConduitCore,
+        session_id: Uuid,
+        workspace_id: Option<Uuid>,
+        working_dir: PathBuf,
+    ) -> Option<Self> {
+        if !core.config().checkpoints.enabled {
+            return None;
+        }
+        Some(Self {
+            store: core.checkpoint_store_clone()?,
+            session_id,
+            workspace_id,
+            working_dir,
+        })
+    }
+
+    /// Snapshot the workspace on a blocking thread. Failures are logged, since
+    /// a missing checkpoint should not hold up the turn.
+    pub async fn record(self) {
+        let session_id = self.session_id;
+        let started_at = Utc::now();
+        let result = tokio::task::spawn_blocking(move || {
+            CheckpointService::record_turn(
+                &self.store,
+                self.session_id,
+                self.workspace_id,
+                &self.working_dir,
+                started_at,
+            )
+        })
+        .await;
+        match result {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => tracing::debug!(%session_id, error = %e, "Failed to checkpoint turn"),
+            Err(e) => tracing::debug!(%session_id, error = %e, "Checkpoint task failed"),
+        }
+    }
+}
+
+impl CheckpointService {
+    /// Snapshot a workspace before a session's next turn.
+    ///
+    /// Takes an explicit store so it can run on a blocking thread without the core.
+    pub fn record_turn(
+        store: &CheckpointStore,
+        session_id: Uuid,
+        workspace_id: Option<Uuid>,
+        working_dir: &Path,
+        started_at: DateTime<Utc>,
+    ) -> Result<Checkpoint, ServiceError> {
+        let turn = store
+            .next_turn(session_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to number checkpoint: {}", e)))?;
+        let ref_name = CheckpointManager::ref_name(session_id, turn);
+        let message = format!("Conduit checkpoint before turn {}", turn);
+        let commit_sha = CheckpointManager::create(working_dir, &ref_name, &message)
+            .map_err(|e| ServiceError::Internal(format!("Failed to create checkpoint: {}", e)))?;
+
+        let mut checkpoint = Checkpoint {
+            id: 0,
+            session_id,
+            workspace_id,
+            turn,
+            commit_sha,
+            ref_name,
+            created_at: started_at,
+        };
+        checkpoint.id = store
+            .insert(&checkpoint)
+            .map_err(|e| ServiceError::Internal(format!("Failed to save checkpoint: {}", e)))?;
+        Ok(checkpoint)
+    }
+
+    /// A session's checkpoints, oldest turn first.
+    pub fn list(core: &ConduitCore, session_id: Uuid) -> Result<Vec<Checkpoint>, ServiceError> {
+        SessionService::get_session(core, session_id)?;
+        Self::store(core)?
+            .list_by_session(session_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to load checkpoints: {}", e)))
+    }
+
+    /// Prepare to restore a session's workspace to before `turn` (default: the
+    /// latest turn), without touching the workspace yet.
+    pub fn prepare_rewind(
+        core: &ConduitCore,
+        session_id: Uuid,
+        turn: Option<u32>,
+    ) -> Result<Rewind, ServiceError> {
+        let store = Self::store(core)?;
+        let checkpoint = store
+            .get(session_id, turn)
+            .map_err(|e| ServiceError::Internal(format!("Failed to load checkpoint: {}", e)))?
+            .ok_or_else(|| match turn {
+                Some(turn) => ServiceError::NotFound(format!("No checkpoint for turn {}", turn)),
+                None => ServiceError::NotFound("Session has no checkpoints".to_string()),
+            })?;
+        Ok(Rewind {
+            working_dir: Self::working_dir(core, session_id)?,
+            store: store.clone(),
+            checkpoint,
+            journal: core.session_journal().cloned(),
+            events: core.session_event_store_clone(),
+        })
+    }
+
+    fn store(core: &ConduitCore) -> Result<&CheckpointStore, ServiceError> {
+        core.checkpoint_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))
+    }
+
+    /// Workspace directory of a session
+    fn working_dir(core: &ConduitCore, session_id: Uuid) -> Result<PathBuf, ServiceError> {
+        let session = SessionService::get_session(core, session_id)?;
+        let workspace_id = session.workspace_id.ok_or_else(|| {
+            ServiceError::InvalidInput("Session has no workspace to rewind".to_string())
+        })?;
+        let workspace = core
+            .workspace_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?
+            .get_by_id(workspace_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get workspace: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Workspace {} not found", workspace_id))
+            })?;
+        Ok(workspace.path)
+    }
+}
@@ -0,0 +1,36 @@
+//! Sending failing CI checks to a workspace's agent.
+
+use std::path::Path;
+
+use crate::core::services::error::ServiceError;
+use crate::git::{CiFailures, CiManager};
+
+pub struct CiService;
+
+impl CiService {
+    /// Fetch the failing checks on the PR for the workspace's branch.
+    ///
+    /// Runs `gh`, so call it off the async runtime.
+    pub fn failures(working_dir: &Path) -> Result<CiFailures, ServiceError> {
+        CiManager::failing_checks(working_dir).map_err(|error| {
+            if error.is_setup_error() {
+                ServiceError::InvalidInput(error.to_string())
+            } else {
+                ServiceError::Internal(format!("Failed to fetch CI checks: {}", error))
+            }
+        })
+    }
+
+    /// Prompt asking the agent to fix the failing checks
+    pub fn fix_prompt(failures: &CiFailures, branch: Option<&str>) -> Result<String, ServiceError> {
+        if failures.checks.is_empty() {
+            let message = if failures.pending > 0 {
+                format!("No failing CI checks ({} still running)", failures.pending)
+            } else {
+                "No failing CI checks".to_string()
+            };
+            return Err(ServiceError::InvalidInput(message));
+        }
+        Ok(CiManager::build_fix_prompt(failures, branch))
+    }
+}
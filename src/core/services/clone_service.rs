@@ -0,0 +1,195 @@
+//! Cloning remote repositories so they can host workspaces.
+//!
+//! Clones go under the projects base directory chosen during onboarding, or
+//! under `~/.conduit/repos` when none is set. Once cloned, a repository is an
+//! ordinary local repository that remembers its URL.
+
+use std::path::{Component, Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::core::services::error::ServiceError;
+use crate::data::{AppStateStore, Repository, RepositoryStore};
+use crate::git::{CloneManager, CloneProgress, WorktreeError};
+use crate::util::repos_dir;
+
+/// App state key of the projects base directory
+const PROJECTS_BASE_DIR_KEY: &str = "projects_base_dir";
+
+pub struct CloneService;
+
+impl CloneService {
+    /// Name for a repository cloned from `url`, unless one is given
+    pub fn repo_name(url: &str, name: Option<&str>) -> Result<String, ServiceError> {
+        let name = match name.map(str::trim).filter(|name| !name.is_empty()) {
+            Some(name) => name.to_string(),
+            None => CloneManager::repo_name(url).ok_or_else(|| {
+                ServiceError::InvalidInput(format!("Cannot derive a name from '{}'", url))
+            })?,
+        };
+        let mut components = Path::new(&name).components();
+        let is_single_component =
+            matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none();
+        if !is_single_component {
+            return Err(ServiceError::InvalidInput(format!(
+                "Invalid repository name: {}",
+                name
+            )));
+        }
+        Ok(name)
+    }
+
+    /// Directory a repository named `name` is cloned into
+    pub fn destination(app_state: Option<&AppStateStore>, name: &str) -> PathBuf {
+        let base_dir = app_state
+            .and_then(|store| store.get(PROJECTS_BASE_DIR_KEY).ok().flatten())
+            .map(|dir| expand_home(&dir))
+            .unwrap_or_else(repos_dir);
+        base_dir.join(name)
+    }
+
+    /// Clone `url` and register it as a repository.
+    ///
+    /// Blocks until the clone finishes. A destination already registered for
+    /// the same URL is returned as is; one registered for another repository
+    /// (e.g. `a/utils` when cloning `b/utils`) is refused so a different name
+    /// can be picked.
+    pub fn clone_repository(
+        repo_store: &RepositoryStore,
+        url: &str,
+        name: &str,
+        dest: &Path,
+        on_progress: &mut dyn FnMut(CloneProgress),
+    ) -> Result<Repository, ServiceError> {
+        if let Some(existing) = repo_store
+            .get_by_path(dest)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get repository: {}", e)))?
+        {
+            let same_url = existing
+                .repository_url
+                .as_deref()
+                .is_some_and(|existing_url| same_remote(existing_url, url));
+            if !same_url {
+                return Err(ServiceError::InvalidInput(format!(
+                    "{} already holds repository '{}'{}; choose another name",
+                    dest.display(),
+                    existing.name,
+                    existing
+                        .repository_url
+                        .as_deref()
+                        .map(|url| format!(" from {}", url))
+                        .unwrap_or_default()
+                )));
+            }
+            return Ok(existing);
+        }
+
+        CloneManager::clone(url, dest, on_progress).map_err(map_clone_error)?;
+
+        let mut repo = Repository::from_local_path(name, dest.to_path_buf());
+        repo.repository_url = Some(url.to_string());
+        repo_store
+            .create(&repo)
+            .map_err(|e| ServiceError::Internal(format!("Failed to create repository: {}", e)))?;
+        Ok(repo)
+    }
+
+    /// Clone a repository that so far only has a URL, setting its base path.
+    ///
+    /// Blocks until the clone finishes.
+    pub fn clone_existing(
+        repo_store: &RepositoryStore,
+        repo_id: Uuid,
+        dest: &Path,
+        on_progress: &mut dyn FnMut(CloneProgress),
+    ) -> Result<Repository, ServiceError> {
+        let mut repo = repo_store
+            .get_by_id(repo_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get repository: {}", e)))?
+            .ok_or_else(|| ServiceError::NotFound(format!("Repository {} not found", repo_id)))?;
+        if repo.base_path.is_some() {
+            return Err(ServiceError::InvalidInput(
+                "Repository already has a local checkout".to_string(),
+            ));
+        }
+        let url = repo.repository_url.clone().ok_or_else(|| {
+            ServiceError::InvalidInput("Repository has no URL to clone".to_string())
+        })?;
+
+        CloneManager::clone(&url, dest, on_progress).map_err(map_clone_error)?;
+
+        repo.base_path = Some(dest.to_path_buf());
+        repo_store
+            .update(&repo)
+            .map_err(|e| ServiceError::Internal(format!("Failed to update repository: {}", e)))?;
+        Ok(repo)
+    }
+}
+
+/// Whether two clone URLs name the same remote, ignoring a trailing `/` or `.git`
+fn same_remote(a: &str, b: &str) -> bool {
+    let normalize = |url: &str| {
+        let url = url.trim().trim_end_matches('/');
+        url.strip_suffix(".git").unwrap_or(url).to_string()
+    };
+    normalize(a) == normalize(b)
+}
+
+fn expand_home(dir: &str) -> PathBuf {
+    if let Some(rest) = dir.strip_prefix('~') {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest.trim_start_matches('/'));
+        }
+    }
+    PathBuf::from(dir)
+}
+
+fn map_clone_error(error: WorktreeError) -> ServiceError {
+    match error {
+        WorktreeError::AlreadyExists(path) => ServiceError::InvalidInput(format!(
+            "{} already exists; add it as a local repository instead",
+            path.display()
+        )),
+        WorktreeError::CommandFailed(message) => {
+            ServiceError::InvalidInput(format!("Clone failed: {}", message))
+        }
+        error => ServiceError::Internal(format!("Clone failed: {}", error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_clone_refuses_destination_of_another_repository() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = RepositoryStore::new(db.connection());
+        let dest = dir.path().join("utils");
+        let mut existing = Repository::from_local_path("utils", dest.clone());
+        existing.repository_url = Some("https://github.com/a/utils".to_string());
+        store.create(&existing).unwrap();
+
+        let result = CloneService::clone_repository(
+            &store,
+            "https://github.com/b/utils",
+            "utils",
+            &dest,
+            &mut |_| {},
+        );
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+
+        let same = CloneService::clone_repository(
+            &store,
+            "https://github.com/a/utils.git",
+            "utils",
+            &dest,
+            &mut |_| {},
+        )
+        .unwrap();
+        assert_eq!(same.id, existing.id);
+    }
+}
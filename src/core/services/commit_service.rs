@@ -0,0 +1,109 @@
+//! Committing (and optionally pushing) a workspace's changes, with
+//! AI-generated commit messages.
+
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::core::services::error::ServiceError;
+use crate::core::ConduitCore;
+use crate::git::{ChangedFile, CommitManager};
+use crate::util::{generate_commit_message, GeneratedCommitMessage, ToolAvailability};
+
+/// Result of committing a workspace
+#[derive(Debug, Clone)]
+pub struct CommitOutcome {
+    pub commit_sha: String,
+    /// The message as committed (trimmed)
+    pub message: String,
+    pub pushed: bool,
+    /// Why the push failed; the commit itself succeeded
+    pub push_error: Option<String>,
+}
+
+pub struct CommitService;
+
+impl CommitService {
+    /// Path of an active workspace
+    pub fn working_dir(core: &ConduitCore, workspace_id: Uuid) -> Result<PathBuf, ServiceError> {
+        let store = core
+            .workspace_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        let workspace = store
+            .get_by_id(workspace_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get workspace: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Workspace {} not found", workspace_id))
+            })?;
+        if workspace.archived_at.is_some() {
+            return Err(ServiceError::InvalidInput(
+                "Workspace is archived".to_string(),
+            ));
+        }
+        Ok(workspace.path)
+    }
+
+    /// Files a commit would include, sorted by path
+    pub fn changed_files(working_dir: &Path) -> Result<Vec<ChangedFile>, ServiceError> {
+        CommitManager::changed_files(working_dir)
+            .map_err(|e| ServiceError::Internal(format!("Failed to read git status: {}", e)))
+    }
+
+    /// Generate a commit message from the workspace's pending changes.
+    pub async fn generate_message(
+        tools: &ToolAvailability,
+        working_dir: &Path,
+    ) -> Result<GeneratedCommitMessage, ServiceError> {
+        let dir = working_dir.to_path_buf();
+        let diff = tokio::task::spawn_blocking(move || CommitManager::pending_diff(&dir))
+            .await
+            .map_err(|e| ServiceError::Internal(format!("Diff task failed: {}", e)))?
+            .map_err(|e| ServiceError::Internal(format!("Failed to read diff: {}", e)))?;
+        if diff.trim().is_empty() {
+            return Err(ServiceError::InvalidInput("Nothing to commit".to_string()));
+        }
+
+        generate_commit_message(tools, &diff, working_dir)
+            .await
+            .map_err(|e| {
+                ServiceError::Internal(format!("Failed to generate commit message: {}", e))
+            })
+    }
+
+    /// Commit pending changes, then push when `push` is set.
+    ///
+    /// Only staged changes are committed when anything is staged. A failed
+    /// push is reported in the outcome rather than as an error.
+    pub fn commit(
+        working_dir: &Path,
+        message: &str,
+        push: bool,
+    ) -> Result<CommitOutcome, ServiceError> {
+        let message = message.trim();
+        if message.is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "Commit message cannot be empty".to_string(),
+            ));
+        }
+        if Self::changed_files(working_dir)?.is_empty() {
+            return Err(ServiceError::InvalidInput("Nothing to commit".to_string()));
+        }
+
+        let commit_sha = CommitManager::commit(working_dir, message)
+            .map_err(|e| ServiceError::Internal(format!("Failed to commit: {}", e)))?;
+
+        let push_error = if push {
+            CommitManager::push(working_dir)
+                .err()
+                .map(|e| e.to_string())
+        } else {
+            None
+        };
+        Ok(CommitOutcome {
+            commit_sha,
+            message: message.to_string(),
+            pushed: push && push_error.is_none(),
+            push_error,
+        })
+    }
+}
@@ -1,5 +1,9 @@
-use crate::agent::{AgentType, ModelRegistry};
-use crate::config::save_default_model;
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::agent::{AgentType, ModelRegistry, PermissionPolicy, PermissionRequest, PermissionRule};
+use crate::config::{save_default_model, save_permission_rule};
 use crate::core::services::error::ServiceError;
 use crate::core::ConduitCore;
 
@@ -33,4 +37,68 @@ impl ConfigService {
 
         Ok(())
     }
+
+    /// Permission policy for a session, scoped to its workspace and repository.
+    pub fn permission_policy(core: &ConduitCore, workspace_id: Option<Uuid>) -> PermissionPolicy {
+        let (repository, workspace) = Self::permission_scope(core, workspace_id);
+        core.config()
+            .permissions
+            .for_scope(repository.as_deref(), workspace.as_deref())
+    }
+
+    /// Remember an "always allow" answer for the session's repository and save it
+    /// to the config file.
+    ///
+    /// Paths are stored relative to `working_dir`, so the rule covers the same
+    /// file in every workspace of the repository and nothing else.
+    pub fn remember_permission_rule(
+        core: &mut ConduitCore,
+        workspace_id: Option<Uuid>,
+        working_dir: Option<&Path>,
+        request: &PermissionRequest,
+    ) -> Result<PermissionRule, ServiceError> {
+        let request = match working_dir {
+            Some(root) => request.relative_to(root),
+            None => request.clone(),
+        };
+        let (repository, _) = Self::permission_scope(core, workspace_id);
+        let rule = PermissionRule::always_allow(&request)
+            .ok_or_else(|| {
+                ServiceError::InvalidInput(format!(
+                    "Not remembered: no single rule covers {}",
+                    request.summary()
+                ))
+            })?
+            .in_repository(repository);
+
+        if core.config().permissions.rules.contains(&rule) {
+            return Ok(rule);
+        }
+        core.config_mut().permissions.add_rule(rule.clone());
+
+        save_permission_rule(&rule).map_err(|err| {
+            ServiceError::Internal(format!("Failed to save permission rule: {err}"))
+        })?;
+
+        Ok(rule)
+    }
+
+    /// Repository and workspace names used to scope permission rules.
+    fn permission_scope(
+        core: &ConduitCore,
+        workspace_id: Option<Uuid>,
+    ) -> (Option<String>, Option<String>) {
+        let workspace = match (workspace_id, core.workspace_store()) {
+            (Some(id), Some(store)) => store.get_by_id(id).ok().flatten(),
+            _ => None,
+        };
+        let Some(workspace) = workspace else {
+            return (None, None);
+        };
+        let repository = core
+            .repo_store()
+            .and_then(|store| store.get_by_id(workspace.repository_id).ok().flatten())
+            .map(|repository| repository.name);
+        (repository, Some(workspace.name))
+    }
 }
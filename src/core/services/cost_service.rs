@@ -0,0 +1,255 @@
+//! Turn cost accounting and spend limits.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::agent::{AgentType, TokenUsage};
+use crate::config::BudgetConfig;
+use crate::core::services::error::ServiceError;
+use crate::core::ConduitCore;
+use crate::data::{UsageRecord, UsageStore, UsageTotals};
+
+/// Result of checking spend against the configured budget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetStatus {
+    WithinBudget,
+    /// A soft limit was reached; new turns are allowed but the user should be warned
+    SoftLimitReached(String),
+    /// A hard limit was reached; new turns must not be started
+    HardLimitReached(String),
+}
+
+pub struct CostService;
+
+impl CostService {
+    /// Record the usage of a completed turn, returning its estimated cost in USD.
+    ///
+    /// Turns that report no tokens (e.g. failed auth) are not recorded.
+    pub fn record_turn(
+        core: &ConduitCore,
+        session_id: Uuid,
+        workspace_id: Option<Uuid>,
+        agent_type: AgentType,
+        model: Option<&str>,
+        usage: &TokenUsage,
+        duration: Option<Duration>,
+    ) -> Result<f64, ServiceError> {
+        let cost_usd = core.config().calculate_cost(agent_type, model, usage);
+        if usage.input_tokens == 0 && usage.output_tokens == 0 {
+            return Ok(cost_usd);
+        }
+
+        let store = Self::store(core)?;
+        let repository_id = match (workspace_id, core.workspace_store()) {
+            (Some(workspace_id), Some(workspace_store)) => workspace_store
+                .get_by_id(workspace_id)
+                .map_err(|e| ServiceError::Internal(format!("Failed to load workspace: {}", e)))?
+                .map(|workspace| workspace.repository_id),
+            _ => None,
+        };
+
+        store
+            .record(&UsageRecord {
+                session_id,
+                workspace_id,
+                repository_id,
+                agent_type,
+                model: model.map(str::to_string),
+                input_tokens: usage.input_tokens,
+                output_tokens: usage.output_tokens,
+                cached_tokens: usage.cached_tokens,
+                cache_write_tokens: usage.cache_write_tokens,
+                cost_usd,
+                duration_ms: duration.map(|duration| duration.as_millis() as i64),
+                recorded_at: Utc::now(),
+            })
+            .map_err(|e| ServiceError::Internal(format!("Failed to record usage: {}", e)))?;
+
+        Ok(cost_usd)
+    }
+
+    /// Usage totals for a session.
+    pub fn session_totals(
+        core: &ConduitCore,
+        session_id: Uuid,
+    ) -> Result<UsageTotals, ServiceError> {
+        Self::store(core)?
+            .totals_for_session(session_id)
+            .map_err(Self::query_error)
+    }
+
+    /// Usage totals for a workspace.
+    pub fn workspace_totals(
+        core: &ConduitCore,
+        workspace_id: Uuid,
+    ) -> Result<UsageTotals, ServiceError> {
+        Self::store(core)?
+            .totals_for_workspace(workspace_id)
+            .map_err(Self::query_error)
+    }
+
+    /// Usage totals for a repository.
+    pub fn repository_totals(
+        core: &ConduitCore,
+        repository_id: Uuid,
+    ) -> Result<UsageTotals, ServiceError> {
+        Self::store(core)?
+            .totals_for_repository(repository_id)
+            .map_err(Self::query_error)
+    }
+
+    /// Usage totals since local midnight.
+    pub fn today_totals(core: &ConduitCore) -> Result<UsageTotals, ServiceError> {
+        Self::store(core)?
+            .totals_since(start_of_local_day(Local::now()))
+            .map_err(Self::query_error)
+    }
+
+    /// Check today's and the session's spend against the configured limits.
+    pub fn check_budget(
+        core: &ConduitCore,
+        session_id: Uuid,
+    ) -> Result<BudgetStatus, ServiceError> {
+        let budget = core.config().budget;
+        let has_daily = budget.daily_soft_limit.is_some() || budget.daily_hard_limit.is_some();
+        let has_session =
+            budget.session_soft_limit.is_some() || budget.session_hard_limit.is_some();
+        if !has_daily && !has_session {
+            return Ok(BudgetStatus::WithinBudget);
+        }
+
+        let daily_spend = if has_daily {
+            Self::today_totals(core)?.cost_usd
+        } else {
+            0.0
+        };
+        let session_spend = if has_session {
+            Self::session_totals(core, session_id)?.cost_usd
+        } else {
+            0.0
+        };
+        Ok(evaluate_budget(&budget, daily_spend, session_spend))
+    }
+
+    fn store(core: &ConduitCore) -> Result<&UsageStore, ServiceError> {
+        core.usage_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))
+    }
+
+    fn query_error(e: rusqlite::Error) -> ServiceError {
+        ServiceError::Internal(format!("Failed to load usage: {}", e))
+    }
+}
+
+/// Compare spend against limits. Hard limits take precedence over soft ones.
+pub fn evaluate_budget(
+    budget: &BudgetConfig,
+    daily_spend: f64,
+    session_spend: f64,
+) -> BudgetStatus {
+    let checks = [
+        ("Daily", daily_spend, budget.daily_hard_limit, true),
+        ("Session", session_spend, budget.session_hard_limit, true),
+        ("Daily", daily_spend, budget.daily_soft_limit, false),
+        ("Session", session_spend, budget.session_soft_limit, false),
+    ];
+    for (scope, spend, limit, hard) in checks {
+        let Some(limit) = limit else {
+            continue;
+        };
+        if spend < limit {
+            continue;
+        }
+        return if hard {
+            BudgetStatus::HardLimitReached(format!(
+                "{} spend ${:.2} reached the ${:.2} limit; new turns are blocked",
+                scope, spend, limit
+            ))
+        } else {
+            BudgetStatus::SoftLimitReached(format!(
+                "{} spend ${:.2} reached the ${:.2} warning limit",
+                scope, spend, limit
+            ))
+        };
+    }
+    BudgetStatus::WithinBudget
+}
+
+/// Local midnight of the given day, as UTC
+pub(crate) fn start_of_local_day(now: DateTime<Local>) -> DateTime<Utc> {
+    now.date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+        .map(|midnight| midnight.with_timezone(&Utc))
+        .unwrap_or_else(|| now.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::agent::ModelPricing;
+    use crate::config::Config;
+
+    #[test]
+    fn test_calculate_cost_uses_model_and_cache_pricing() {
+        let usage = TokenUsage {
+            input_tokens: 1_500_000,
+            output_tokens: 100_000,
+            cached_tokens: 1_000_000,
+            cache_write_tokens: 0,
+            total_tokens: 1_600_000,
+        };
+        let mut config = Config::default();
+
+        // Sonnet: 0.5M uncached at $3, 1M cache reads at $0.30, 0.1M output at $15
+        let sonnet = config.calculate_cost(AgentType::Claude, Some("sonnet"), &usage);
+        assert!((sonnet - 3.3).abs() < 1e-9);
+        assert!(config.calculate_cost(AgentType::Claude, Some("haiku"), &usage) < sonnet);
+        assert_eq!(
+            config.calculate_cost(AgentType::Opencode, Some("some/model"), &usage),
+            0.0
+        );
+
+        config
+            .pricing
+            .entry(AgentType::Claude)
+            .or_default()
+            .insert("sonnet".to_string(), ModelPricing::new(1.0, 1.0, 0.0, 0.0));
+        let overridden = config.calculate_cost(AgentType::Claude, Some("sonnet"), &usage);
+        assert!((overridden - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_evaluate_budget_without_limits() {
+        let budget = BudgetConfig::default();
+        assert_eq!(
+            evaluate_budget(&budget, 1_000.0, 1_000.0),
+            BudgetStatus::WithinBudget
+        );
+    }
+
+    #[test]
+    fn test_evaluate_budget_hard_limit_wins() {
+        let budget = BudgetConfig {
+            daily_soft_limit: Some(5.0),
+            daily_hard_limit: None,
+            session_soft_limit: None,
+            session_hard_limit: Some(2.0),
+        };
+        assert_eq!(
+            evaluate_budget(&budget, 1.0, 1.0),
+            BudgetStatus::WithinBudget
+        );
+        assert!(matches!(
+            evaluate_budget(&budget, 6.0, 1.0),
+            BudgetStatus::SoftLimitReached(message) if message.starts_with("Daily")
+        ));
+        assert!(matches!(
+            evaluate_budget(&budget, 6.0, 2.0),
+            BudgetStatus::HardLimitReached(message) if message.starts_with("Session")
+        ));
+    }
+}
@@ -0,0 +1,535 @@
+//! Session transcript export (Markdown, HTML, JSON).
+
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::agent::AgentType;
+use crate::core::services::error::ServiceError;
+use crate::core::services::session_service::SessionService;
+use crate::core::ConduitCore;
+use crate::ui::app_prompt::FORK_SEED_PREFIX;
+use crate::ui::components::{ChatMessage, MessageRole, TurnSummary};
+
+/// Output format for a transcript export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::Markdown,
+        ExportFormat::Html,
+        ExportFormat::Json,
+    ];
+
+    /// File extension (without the dot).
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    /// MIME type for HTTP responses.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+            ExportFormat::Html => "text/html; charset=utf-8",
+            ExportFormat::Json => "application/json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = ServiceError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(ExportFormat::Markdown),
+            "html" | "htm" => Ok(ExportFormat::Html),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(ServiceError::InvalidInput(format!(
+                "Unknown export format '{}' (expected markdown, html, or json)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Session details shown in the transcript header.
+#[derive(Debug, Clone)]
+pub struct TranscriptInfo {
+    pub session_id: Uuid,
+    pub title: Option<String>,
+    pub agent_type: AgentType,
+    pub model: Option<String>,
+    pub exported_at: DateTime<Utc>,
+}
+
+impl TranscriptInfo {
+    fn heading(&self) -> String {
+        self.title
+            .clone()
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| format!("{} session", self.agent_type.display_name()))
+    }
+
+    /// Suggested file name, e.g. `conduit-fix-login-bug-1a2b3c4d.md`.
+    pub fn file_name(&self, format: ExportFormat) -> String {
+        let slug: String = self
+            .heading()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        let slug = slug
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        let short_id = &self.session_id.simple().to_string()[..8];
+        format!("conduit-{}-{}.{}", slug, short_id, format.extension())
+    }
+}
+
+pub struct ExportService;
+
+impl ExportService {
+    /// Load a stored session's history and render it in the requested format.
+    pub fn export_session(
+        core: &ConduitCore,
+        session_id: Uuid,
+        format: ExportFormat,
+    ) -> Result<(TranscriptInfo, String), ServiceError> {
+        let session = SessionService::get_session(core, session_id)?;
+        let messages = SessionService::load_history(core, &session);
+        let info = TranscriptInfo {
+            session_id: session.id,
+            title: session.title.clone(),
+            agent_type: session.agent_type,
+            model: session.model.clone(),
+            exported_at: Utc::now(),
+        };
+        let content = Self::render(&info, &messages, format);
+        Ok((info, content))
+    }
+
+    /// Render chat messages in the requested format.
+    pub fn render(info: &TranscriptInfo, messages: &[ChatMessage], format: ExportFormat) -> String {
+        let messages: Vec<&ChatMessage> = messages
+            .iter()
+            .filter(|msg| {
+                !(msg.role == MessageRole::User
+                    && msg.content.trim_start().starts_with(FORK_SEED_PREFIX))
+            })
+            .collect();
+        match format {
+            ExportFormat::Markdown => render_markdown(info, &messages),
+            ExportFormat::Html => render_html(info, &messages),
+            ExportFormat::Json => render_json(info, &messages),
+        }
+    }
+}
+
+fn role_name(role: MessageRole) -> &'static str {
+    match role {
+        MessageRole::User => "user",
+        MessageRole::Assistant => "assistant",
+        MessageRole::Reasoning => "reasoning",
+        MessageRole::Tool => "tool",
+        MessageRole::System => "system",
+        MessageRole::Error => "error",
+        MessageRole::Summary => "summary",
+    }
+}
+
+fn summary_line(msg: &ChatMessage) -> Option<String> {
+    let summary = msg.summary.as_ref()?;
+    let mut parts = Vec::new();
+    if summary.duration_secs > 0 {
+        parts.push(summary.format_duration());
+    }
+    if summary.input_tokens > 0 || summary.output_tokens > 0 {
+        parts.push(format!(
+            "{} in / {} out tokens",
+            TurnSummary::format_tokens(summary.input_tokens),
+            TurnSummary::format_tokens(summary.output_tokens)
+        ));
+    }
+    if !summary.files_changed.is_empty() {
+        let files = summary
+            .files_changed
+            .iter()
+            .map(|f| format!("{} (+{} -{})", f.filename, f.additions, f.deletions))
+            .collect::<Vec<_>>()
+            .join(", ");
+        parts.push(format!("files: {}", files));
+    }
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+/// Pick a backtick fence longer than any run inside `content`.
+fn code_fence(content: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in content.chars() {
+        if c == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn render_markdown(info: &TranscriptInfo, messages: &[&ChatMessage]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", info.heading());
+    let _ = writeln!(out, "- **Session:** `{}`", info.session_id);
+    let _ = writeln!(out, "- **Agent:** {}", info.agent_type.display_name());
+    if let Some(model) = info.model.as_deref() {
+        let _ = writeln!(out, "- **Model:** {}", model);
+    }
+    let _ = writeln!(
+        out,
+        "- **Exported:** {}",
+        info.exported_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+
+    for msg in messages {
+        // Turn summaries trail the turn they belong to
+        if msg.role == MessageRole::Summary {
+            if let Some(line) = summary_line(msg) {
+                let _ = writeln!(out, "\n_Turn complete: {}_", line);
+            }
+            continue;
+        }
+        out.push_str("\n---\n\n");
+        match msg.role {
+            MessageRole::User => {
+                let _ = writeln!(out, "### User\n\n{}", msg.content.trim_end());
+            }
+            MessageRole::Assistant => {
+                let _ = writeln!(out, "### Assistant\n\n{}", msg.content.trim_end());
+            }
+            MessageRole::Reasoning => {
+                let _ = writeln!(
+                    out,
+                    "<details>\n<summary>Reasoning</summary>\n\n{}\n\n</details>",
+                    msg.content.trim_end()
+                );
+            }
+            MessageRole::Tool => {
+                let name = msg.tool_name.as_deref().unwrap_or("Tool");
+                let _ = writeln!(out, "### Tool: {}\n", name);
+                if let Some(args) = msg.tool_args.as_deref().filter(|a| !a.is_empty()) {
+                    let fence = code_fence(args);
+                    let _ = writeln!(out, "{fence}\n{args}\n{fence}\n");
+                }
+                let output = msg.content.trim_end();
+                if !output.is_empty() {
+                    let fence = code_fence(output);
+                    let _ = writeln!(out, "{fence}text\n{output}\n{fence}");
+                }
+                if let Some(code) = msg.exit_code {
+                    let _ = writeln!(out, "\n_Exit code: {}_", code);
+                }
+            }
+            MessageRole::System => {
+                let _ = writeln!(out, "> **System:** {}", msg.content.trim_end());
+            }
+            MessageRole::Error => {
+                let _ = writeln!(out, "> **Error:** {}", msg.content.trim_end());
+            }
+            MessageRole::Summary => {}
+        }
+    }
+
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const HTML_STYLE: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:56rem;margin:2rem auto;padding:0 1rem;color:#1f2328;background:#fff;line-height:1.5}\
+header{border-bottom:1px solid #d0d7de;margin-bottom:1.5rem}\
+header dl{display:grid;grid-template-columns:max-content 1fr;gap:.25rem 1rem;font-size:.9rem}\
+header dt{font-weight:600}header dd{margin:0}\
+.msg{margin:1rem 0;padding:.75rem 1rem;border-radius:6px;border:1px solid #d0d7de}\
+.role{font-size:.75rem;font-weight:600;text-transform:uppercase;letter-spacing:.05em;color:#59636e;margin-bottom:.25rem}\
+.text{white-space:pre-wrap;word-wrap:break-word}\
+.user{background:#ddf4ff;border-color:#54aeff}\
+.reasoning{color:#59636e;font-style:italic}\
+.system{background:#f6f8fa}\
+.error{background:#ffebe9;border-color:#ff8182}\
+.summary{border:none;padding:0 1rem;font-size:.85rem;color:#59636e}\
+pre{background:#f6f8fa;padding:.5rem;border-radius:4px;overflow-x:auto;white-space:pre-wrap;margin:.25rem 0}\
+.args{color:#59636e}\
+.exit{font-size:.85rem}.exit.fail{color:#cf222e}\
+@media(prefers-color-scheme:dark){body{background:#0d1117;color:#e6edf3}.msg{border-color:#30363d}.user{background:#0c2d6b;border-color:#1f6feb}.system,pre{background:#161b22}.error{background:#3d1214;border-color:#f85149}}";
+
+fn render_html(info: &TranscriptInfo, messages: &[&ChatMessage]) -> String {
+    let heading = escape_html(&info.heading());
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{heading}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n<header>\n\
+         <h1>{heading}</h1>\n<dl>\n<dt>Session</dt><dd><code>{}</code></dd>\n\
+         <dt>Agent</dt><dd>{}</dd>\n",
+        info.session_id,
+        escape_html(info.agent_type.display_name()),
+    );
+    if let Some(model) = info.model.as_deref() {
+        let _ = writeln!(out, "<dt>Model</dt><dd>{}</dd>", escape_html(model));
+    }
+    let _ = writeln!(
+        out,
+        "<dt>Exported</dt><dd>{}</dd>\n</dl>\n</header>\n<main>",
+        info.exported_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+
+    for msg in messages {
+        let role = role_name(msg.role);
+        match msg.role {
+            MessageRole::Reasoning => {
+                let _ = writeln!(
+                    out,
+                    "<details class=\"msg reasoning\"><summary class=\"role\">Reasoning</summary>\
+                     <div class=\"text\">{}</div></details>",
+                    escape_html(msg.content.trim_end())
+                );
+            }
+            MessageRole::Tool => {
+                let name = msg.tool_name.as_deref().unwrap_or("Tool");
+                let _ = write!(
+                    out,
+                    "<section class=\"msg tool\"><div class=\"role\">Tool: {}</div>",
+                    escape_html(name)
+                );
+                if let Some(args) = msg.tool_args.as_deref().filter(|a| !a.is_empty()) {
+                    let _ = write!(out, "<pre class=\"args\">{}</pre>", escape_html(args));
+                }
+                if !msg.content.trim().is_empty() {
+                    let _ = write!(out, "<pre>{}</pre>", escape_html(msg.content.trim_end()));
+                }
+                if let Some(code) = msg.exit_code {
+                    let class = if code == 0 { "exit" } else { "exit fail" };
+                    let _ = write!(out, "<div class=\"{class}\">Exit code: {code}</div>");
+                }
+                out.push_str("</section>\n");
+            }
+            MessageRole::Summary => {
+                if let Some(line) = summary_line(msg) {
+                    let _ = writeln!(
+                        out,
+                        "<div class=\"msg summary\">Turn complete: {}</div>",
+                        escape_html(&line)
+                    );
+                }
+            }
+            _ => {
+                let label = match msg.role {
+                    MessageRole::User => "User",
+                    MessageRole::Assistant => "Assistant",
+                    MessageRole::System => "System",
+                    _ => "Error",
+                };
+                let _ = writeln!(
+                    out,
+                    "<section class=\"msg {role}\"><div class=\"role\">{label}</div>\
+                     <div class=\"text\">{}</div></section>",
+                    escape_html(msg.content.trim_end())
+                );
+            }
+        }
+    }
+
+    out.push_str("</main>\n</body>\n</html>\n");
+    out
+}
+
+#[derive(Serialize)]
+struct JsonTranscript<'a> {
+    session_id: Uuid,
+    title: Option<&'a str>,
+    agent_type: &'a str,
+    model: Option<&'a str>,
+    exported_at: DateTime<Utc>,
+    messages: Vec<JsonMessage<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonMessage<'a> {
+    role: &'static str,
+    content: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_args: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<JsonSummary<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonSummary<'a> {
+    duration_secs: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    files_changed: Vec<JsonFileChange<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFileChange<'a> {
+    filename: &'a str,
+    additions: usize,
+    deletions: usize,
+}
+
+fn render_json(info: &TranscriptInfo, messages: &[&ChatMessage]) -> String {
+    let transcript = JsonTranscript {
+        session_id: info.session_id,
+        title: info.title.as_deref(),
+        agent_type: info.agent_type.as_str(),
+        model: info.model.as_deref(),
+        exported_at: info.exported_at,
+        messages: messages
+            .iter()
+            .map(|msg| JsonMessage {
+                role: role_name(msg.role),
+                content: &msg.content,
+                tool_name: msg.tool_name.as_deref(),
+                tool_args: msg.tool_args.as_deref(),
+                exit_code: msg.exit_code,
+                summary: msg.summary.as_ref().map(|s| JsonSummary {
+                    duration_secs: s.duration_secs,
+                    input_tokens: s.input_tokens,
+                    output_tokens: s.output_tokens,
+                    files_changed: s
+                        .files_changed
+                        .iter()
+                        .map(|f| JsonFileChange {
+                            filename: &f.filename,
+                            additions: f.additions,
+                            deletions: f.deletions,
+                        })
+                        .collect(),
+                }),
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&transcript).unwrap_or_else(|_| "{}".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> TranscriptInfo {
+        TranscriptInfo {
+            session_id: Uuid::nil(),
+            title: Some("Fix <login> bug".to_string()),
+            agent_type: AgentType::Claude,
+            model: Some("sonnet".to_string()),
+            exported_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        }
+    }
+
+    fn messages() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::user("[CONDUIT_FORK_SEED] hidden context"),
+            ChatMessage::user("Why does login fail?"),
+            ChatMessage::assistant("Let me check."),
+            ChatMessage::tool_with_exit(
+                "Bash",
+                r#"{"command":"cargo test"}"#,
+                "```\nfailed",
+                Some(1),
+            ),
+            ChatMessage::turn_summary(TurnSummary::new().with_tokens(1500, 20).with_duration(75)),
+        ]
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            "md".parse::<ExportFormat>().unwrap(),
+            ExportFormat::Markdown
+        );
+        assert_eq!("HTML".parse::<ExportFormat>().unwrap(), ExportFormat::Html);
+        assert!("pdf".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let md = ExportService::render(&info(), &messages(), ExportFormat::Markdown);
+        assert!(md.starts_with("# Fix <login> bug\n"));
+        assert!(!md.contains("CONDUIT_FORK_SEED"));
+        assert!(md.contains("### User\n\nWhy does login fail?"));
+        assert!(md.contains("### Tool: Bash"));
+        // Output containing a fence gets a longer fence
+        assert!(md.contains("````text\n```\nfailed\n````"));
+        assert!(md.contains("_Exit code: 1_"));
+        assert!(md.contains("_Turn complete: 1m 15s · 1.5k in / 20 out tokens_"));
+    }
+
+    #[test]
+    fn test_render_html_escapes_content() {
+        let html = ExportService::render(&info(), &messages(), ExportFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Fix &lt;login&gt; bug</title>"));
+        assert!(html.contains("{&quot;command&quot;:&quot;cargo test&quot;}"));
+        assert!(html.contains("exit fail"));
+        assert!(!html.contains("<login>"));
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json = ExportService::render(&info(), &messages(), ExportFormat::Json);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["agent_type"], "claude");
+        let messages = value["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[2]["tool_name"], "Bash");
+        assert_eq!(messages[2]["exit_code"], 1);
+        assert_eq!(messages[3]["summary"]["input_tokens"], 1500);
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            info().file_name(ExportFormat::Html),
+            "conduit-fix-login-bug-00000000.html"
+        );
+    }
+}
@@ -1,9 +1,35 @@
+pub mod audit_service;
+pub mod checkpoint_service;
+pub mod ci_service;
+pub mod clone_service;
+pub mod commit_service;
 pub mod config_service;
+pub mod cost_service;
 pub mod error;
+pub mod export_service;
 pub mod model_service;
+pub mod race_service;
+pub mod review_service;
+pub mod run_service;
+pub mod search_service;
 pub mod session_service;
+pub mod stats_service;
+pub mod sync_service;
 
+pub use audit_service::{AuditFilter, AuditService};
+pub use checkpoint_service::{CheckpointService, Rewind, TurnCheckpoint};
+pub use ci_service::CiService;
+pub use clone_service::CloneService;
+pub use commit_service::{CommitOutcome, CommitService};
 pub use config_service::ConfigService;
+pub use cost_service::{BudgetStatus, CostService};
 pub use error::ServiceError;
+pub use export_service::{ExportFormat, ExportService, TranscriptInfo};
 pub use model_service::ModelService;
+pub use race_service::{CreateRaceParams, RaceContender, RaceService, RaceStanding};
+pub use review_service::{PendingReview, ReviewService};
+pub use run_service::{RunOutcome, RunParams, RunService};
+pub use search_service::{IndexRefreshStats, SearchIndexer, SearchService};
 pub use session_service::{CreateSessionParams, SessionService, UpdateSessionParams};
+pub use stats_service::{StatsService, UsageRow, UsageStats};
+pub use sync_service::SyncService;
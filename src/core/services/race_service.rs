@@ -0,0 +1,412 @@
+//! Multi-agent races: one prompt, several agents, each in its own workspace.
+
+use std::path::Path;
+use std::process::Command;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::agent::{AgentType, ModelRegistry};
+use crate::config::Config;
+use crate::core::services::error::ServiceError;
+use crate::core::ConduitCore;
+use crate::core::{
+    resolve_repo_workspace_settings, resolve_workspace_hooks, run_workspace_hooks, HookContext,
+    HookEvent,
+};
+use crate::data::{
+    Race, RaceEntry, RaceStore, RaceTestResult, RepositoryStore, UsageStore, UsageTotals,
+    Workspace, WorkspaceStore,
+};
+use crate::git::{GitDiffStats, WorkspaceDiff, WorkspaceRepoManager};
+use crate::util::{generate_branch_name, generate_workspace_name, get_git_username, Tool};
+
+/// Lines of test output kept per contender
+const TEST_OUTPUT_LINES: usize = 40;
+
+/// An agent (and optionally a model) taking part in a race
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceContender {
+    pub agent_type: AgentType,
+    pub model: Option<String>,
+}
+
+impl RaceContender {
+    /// Parse "agent" or "agent:model"
+    pub fn parse(spec: &str) -> Result<Self, ServiceError> {
+        let (agent, model) = match spec.trim().split_once(':') {
+            Some((agent, model)) => (agent.trim(), Some(model.trim().to_string())),
+            None => (spec.trim(), None),
+        };
+        let agent_type = match agent.to_lowercase().as_str() {
+            "claude" => AgentType::Claude,
+            "codex" => AgentType::Codex,
+            "gemini" => AgentType::Gemini,
+            "opencode" => AgentType::Opencode,
+            _ => {
+                return Err(ServiceError::InvalidInput(format!(
+                    "Unknown agent '{}' in race contender '{}'",
+                    agent, spec
+                )))
+            }
+        };
+        if let Some(model) = model.as_deref() {
+            if ModelRegistry::find_model(agent_type, model).is_none() {
+                return Err(ServiceError::InvalidInput(format!(
+                    "Unknown {} model '{}'",
+                    agent_type.display_name(),
+                    model
+                )));
+            }
+        }
+        Ok(Self {
+            agent_type,
+            model: model.filter(|model| !model.is_empty()),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateRaceParams {
+    /// Workspace whose current branch the contenders start from
+    pub workspace_id: Uuid,
+    pub prompt: String,
+    pub contenders: Vec<RaceContender>,
+}
+
+/// How a contender is doing, for the comparison view
+#[derive(Debug, Clone)]
+pub struct RaceStanding {
+    pub entry: RaceEntry,
+    pub workspace_name: String,
+    pub branch: String,
+    pub archived: bool,
+    /// Changes against the race's base commit (None once the workspace is gone)
+    pub diff_stats: Option<GitDiffStats>,
+    pub usage: UsageTotals,
+}
+
+pub struct RaceService;
+
+impl RaceService {
+    /// Contenders from `[race] contenders`, or every installed agent with its default model.
+    pub fn configured_contenders(core: &ConduitCore) -> Result<Vec<RaceContender>, ServiceError> {
+        let config = core.config();
+        let contenders = if config.race.contenders.is_empty() {
+            [
+                (AgentType::Claude, Tool::Claude),
+                (AgentType::Codex, Tool::Codex),
+                (AgentType::Gemini, Tool::Gemini),
+                (AgentType::Opencode, Tool::Opencode),
+            ]
+            .into_iter()
+            .filter(|(_, tool)| core.tools().is_available(*tool))
+            .map(|(agent_type, _)| RaceContender {
+                agent_type,
+                model: None,
+            })
+            .collect()
+        } else {
+            config
+                .race
+                .contenders
+                .iter()
+                .map(|spec| RaceContender::parse(spec))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        Ok(contenders)
+    }
+
+    /// Create one workspace per contender from the source workspace's current branch.
+    ///
+    /// Takes explicit stores so it can run on a blocking thread without the core.
+    /// Sessions are left to the caller, which starts each agent with the prompt.
+    pub fn create(
+        race_store: &RaceStore,
+        workspace_store: &WorkspaceStore,
+        repo_store: &RepositoryStore,
+        worktree_manager: &WorkspaceRepoManager,
+        config: &Config,
+        params: CreateRaceParams,
+    ) -> Result<Race, ServiceError> {
+        if params.prompt.trim().is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "A race needs a prompt".to_string(),
+            ));
+        }
+        if params.contenders.len() < 2 {
+            return Err(ServiceError::InvalidInput(
+                "A race needs at least two contenders".to_string(),
+            ));
+        }
+
+        let source = workspace_store
+            .get_by_id(params.workspace_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get workspace: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Workspace {} not found", params.workspace_id))
+            })?;
+        let repo = repo_store
+            .get_by_id(source.repository_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get repository: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Repository {} not found", source.repository_id))
+            })?;
+        let base_path = repo
+            .base_path
+            .clone()
+            .ok_or_else(|| ServiceError::InvalidInput("Repository has no base path".to_string()))?;
+        let settings = resolve_repo_workspace_settings(config, &repo);
+
+        let base_branch = worktree_manager
+            .get_current_branch(&source.path)
+            .unwrap_or_else(|_| source.branch.clone());
+        let base_commit = worktree_manager
+            .get_branch_sha(settings.mode, &base_path, &source.path, &base_branch)
+            .map_err(|e| {
+                ServiceError::Internal(format!("Failed to resolve '{}': {}", base_branch, e))
+            })?;
+
+        let hooks = resolve_workspace_hooks(config, &repo);
+        let username = get_git_username();
+        let mut created: Vec<Workspace> = Vec::new();
+        let mut entries = Vec::new();
+        for contender in params.contenders {
+            let result = (|| {
+                let existing_names = workspace_store
+                    .get_all_names_by_repository(repo.id)
+                    .map_err(|e| format!("Failed to get workspace names: {}", e))?;
+                let workspace_name = generate_workspace_name(&existing_names);
+                let branch_name = generate_branch_name(&username, &workspace_name);
+                let path = worktree_manager
+                    .create_workspace_from_branch(
+                        settings.mode,
+                        &base_path,
+                        &base_branch,
+                        &branch_name,
+                        &workspace_name,
+                    )
+                    .map_err(|e| format!("Failed to create workspace: {}", e))?;
+                let workspace = Workspace::new(repo.id, &workspace_name, &branch_name, path);
+                if let Err(e) = workspace_store.create(&workspace) {
+                    discard_workspace(worktree_manager, settings.mode, &base_path, &workspace);
+                    return Err(format!("Failed to save workspace to database: {}", e));
+                }
+                if hooks.has(HookEvent::OnCreate) {
+                    let context = HookContext {
+                        workspace_name: &workspace.name,
+                        branch: &workspace.branch,
+                        workspace_path: &workspace.path,
+                        repo_path: &base_path,
+                    };
+                    let report =
+                        run_workspace_hooks(&hooks, HookEvent::OnCreate, context, &mut |_| {});
+                    if let Some(summary) = report.failure_summary() {
+                        discard_workspace(worktree_manager, settings.mode, &base_path, &workspace);
+                        if let Err(e) = workspace_store.delete(workspace.id) {
+                            tracing::error!(error = %e, workspace_id = %workspace.id, "Failed to delete race workspace");
+                        }
+                        return Err(summary);
+                    }
+                }
+                Ok(workspace)
+            })();
+
+            let workspace = match result {
+                Ok(workspace) => workspace,
+                Err(message) => {
+                    for workspace in &created {
+                        discard_workspace(worktree_manager, settings.mode, &base_path, workspace);
+                        if let Err(e) = workspace_store.delete(workspace.id) {
+                            tracing::error!(error = %e, workspace_id = %workspace.id, "Failed to delete race workspace");
+                        }
+                    }
+                    return Err(ServiceError::Internal(message));
+                }
+            };
+            entries.push(RaceEntry {
+                workspace_id: workspace.id,
+                agent_type: contender.agent_type,
+                model: Some(
+                    contender
+                        .model
+                        .unwrap_or_else(|| config.default_model_for(contender.agent_type)),
+                ),
+                test_result: None,
+            });
+            created.push(workspace);
+        }
+
+        let race = Race {
+            id: Uuid::new_v4(),
+            repository_id: repo.id,
+            prompt: params.prompt,
+            base_branch,
+            base_commit,
+            winner_workspace_id: None,
+            entries,
+            created_at: Utc::now(),
+        };
+        race_store
+            .create(&race)
+            .map_err(|e| ServiceError::Internal(format!("Failed to save race: {}", e)))?;
+        Ok(race)
+    }
+
+    /// Load a race.
+    pub fn get(race_store: &RaceStore, race_id: Uuid) -> Result<Race, ServiceError> {
+        race_store
+            .get(race_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to load race: {}", e)))?
+            .ok_or_else(|| ServiceError::NotFound(format!("Race {} not found", race_id)))
+    }
+
+    /// Diff stats, usage and test results of every contender.
+    pub fn standings(
+        workspace_store: &WorkspaceStore,
+        usage_store: Option<&UsageStore>,
+        race: &Race,
+    ) -> Result<Vec<RaceStanding>, ServiceError> {
+        race.entries
+            .iter()
+            .map(|entry| {
+                let workspace = workspace_store.get_by_id(entry.workspace_id).map_err(|e| {
+                    ServiceError::Internal(format!("Failed to get workspace: {}", e))
+                })?;
+                let usage = match usage_store {
+                    Some(store) => store
+                        .totals_for_workspace(entry.workspace_id)
+                        .map_err(|e| {
+                            ServiceError::Internal(format!("Failed to load usage: {}", e))
+                        })?,
+                    None => UsageTotals::default(),
+                };
+                let (workspace_name, branch, archived, diff_stats) = match workspace {
+                    Some(workspace) => {
+                        let diff_stats = (workspace.archived_at.is_none()
+                            && workspace.path.exists())
+                        .then(|| WorkspaceDiff::load_since(&workspace.path, &race.base_commit))
+                        .and_then(Result::ok)
+                        .map(|diff| GitDiffStats {
+                            additions: diff.additions(),
+                            deletions: diff.deletions(),
+                            files_changed: diff.files.len(),
+                        });
+                        (
+                            workspace.name.clone(),
+                            workspace.branch.clone(),
+                            workspace.archived_at.is_some(),
+                            diff_stats,
+                        )
+                    }
+                    None => (String::new(), String::new(), true, None),
+                };
+                Ok(RaceStanding {
+                    entry: entry.clone(),
+                    workspace_name,
+                    branch,
+                    archived,
+                    diff_stats,
+                    usage,
+                })
+            })
+            .collect()
+    }
+
+    /// Run the test command in a contender's workspace and record the outcome.
+    pub fn run_tests(
+        race_store: &RaceStore,
+        race_id: Uuid,
+        workspace_id: Uuid,
+        working_dir: &Path,
+        command: &str,
+    ) -> Result<RaceTestResult, ServiceError> {
+        let output = Command::new("sh")
+            .args(["-c", command])
+            .current_dir(working_dir)
+            .output()
+            .map_err(|e| ServiceError::Internal(format!("Failed to run '{}': {}", command, e)))?;
+
+        let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+        combined.push_str(&String::from_utf8_lossy(&output.stderr));
+        let lines: Vec<&str> = combined.lines().collect();
+        let result = RaceTestResult {
+            passed: output.status.success(),
+            output: lines[lines.len().saturating_sub(TEST_OUTPUT_LINES)..].join("\n"),
+        };
+        race_store
+            .set_test_result(race_id, workspace_id, &result)
+            .map_err(|e| ServiceError::Internal(format!("Failed to save test result: {}", e)))?;
+        Ok(result)
+    }
+
+    /// Record the winner, returning the other contenders' workspaces to archive.
+    pub fn pick_winner(
+        race_store: &RaceStore,
+        race: &Race,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Uuid>, ServiceError> {
+        if race.entry(workspace_id).is_none() {
+            return Err(ServiceError::InvalidInput(format!(
+                "Workspace {} is not part of this race",
+                workspace_id
+            )));
+        }
+        race_store
+            .set_winner(race.id, workspace_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to save winner: {}", e)))?;
+        Ok(race
+            .entries
+            .iter()
+            .map(|entry| entry.workspace_id)
+            .filter(|id| *id != workspace_id)
+            .collect())
+    }
+}
+
+/// Remove a workspace directory and branch that did not make it into a race
+fn discard_workspace(
+    worktree_manager: &WorkspaceRepoManager,
+    mode: crate::git::WorkspaceMode,
+    base_path: &Path,
+    workspace: &Workspace,
+) {
+    if let Err(e) = worktree_manager.remove_workspace(mode, base_path, &workspace.path) {
+        tracing::error!(
+            error = %e,
+            workspace_path = %workspace.path.display(),
+            "Failed to clean up race workspace"
+        );
+    }
+    if let Err(e) =
+        worktree_manager.delete_branch(mode, base_path, &workspace.path, &workspace.branch)
+    {
+        tracing::error!(
+            error = %e,
+            branch = %workspace.branch,
+            "Failed to delete race branch"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_contender() {
+        assert_eq!(
+            RaceContender::parse("codex").unwrap(),
+            RaceContender {
+                agent_type: AgentType::Codex,
+                model: None,
+            }
+        );
+        let model = ModelRegistry::default_model(AgentType::Claude);
+        let contender = RaceContender::parse(&format!(" Claude:{} ", model)).unwrap();
+        assert_eq!(contender.agent_type, AgentType::Claude);
+        assert_eq!(contender.model, Some(model));
+        assert!(RaceContender::parse("cursor").is_err());
+        assert!(RaceContender::parse("claude:not-a-model").is_err());
+    }
+}
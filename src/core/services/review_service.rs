@@ -0,0 +1,101 @@
+//! Feeding PR review comments back to a workspace's agent.
+//!
+//! Unresolved comments come from GitHub; which of them were already sent to
+//! the agent is tracked locally per workspace.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::core::services::error::ServiceError;
+use crate::data::ReviewCommentStore;
+use crate::git::{GhError, PrReviewComments, ReviewComment, ReviewManager};
+
+/// Unresolved review comments of a workspace's PR
+#[derive(Debug, Clone, Default)]
+pub struct PendingReview {
+    pub pr_number: u32,
+    pub pr_url: String,
+    pub comments: Vec<ReviewComment>,
+    /// IDs of comments already marked as addressed
+    pub addressed: HashSet<String>,
+}
+
+impl PendingReview {
+    pub fn is_addressed(&self, comment: &ReviewComment) -> bool {
+        self.addressed.contains(&comment.id)
+    }
+}
+
+pub struct ReviewService;
+
+impl ReviewService {
+    /// Fetch the unresolved comments on the PR for the workspace's branch.
+    ///
+    /// Runs `gh`, so call it off the async runtime.
+    pub fn pending_review(
+        store: Option<&ReviewCommentStore>,
+        workspace_id: Uuid,
+        working_dir: &Path,
+    ) -> Result<PendingReview, ServiceError> {
+        let PrReviewComments {
+            pr_number,
+            pr_url,
+            comments,
+        } = ReviewManager::unresolved_comments(working_dir).map_err(map_review_error)?;
+        let addressed = match store {
+            Some(store) => store.addressed_ids(workspace_id).map_err(|e| {
+                ServiceError::Internal(format!("Failed to load addressed comments: {}", e))
+            })?,
+            None => HashSet::new(),
+        };
+        Ok(PendingReview {
+            pr_number,
+            pr_url,
+            comments,
+            addressed,
+        })
+    }
+
+    /// Prompt for the given comments, in the order they appear on the PR
+    pub fn build_prompt(
+        review: &PendingReview,
+        comment_ids: &[String],
+    ) -> Result<String, ServiceError> {
+        let selected: Vec<ReviewComment> = review
+            .comments
+            .iter()
+            .filter(|comment| comment_ids.contains(&comment.id))
+            .cloned()
+            .collect();
+        if selected.is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "No review comments selected".to_string(),
+            ));
+        }
+        Ok(ReviewManager::build_prompt(review.pr_number, &selected))
+    }
+
+    /// Mark comments as addressed, or clear the mark
+    pub fn set_addressed(
+        store: Option<&ReviewCommentStore>,
+        workspace_id: Uuid,
+        comment_ids: &[String],
+        addressed: bool,
+    ) -> Result<(), ServiceError> {
+        let store =
+            store.ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        store
+            .set_addressed(workspace_id, comment_ids, addressed)
+            .map_err(|e| ServiceError::Internal(format!("Failed to update comments: {}", e)))
+    }
+}
+
+fn map_review_error(error: GhError) -> ServiceError {
+    if error.is_setup_error() {
+        ServiceError::InvalidInput(error.to_string())
+    } else {
+        ServiceError::Internal(format!("Failed to fetch review comments: {}", error))
+    }
+}
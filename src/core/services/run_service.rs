@@ -0,0 +1,753 @@
+//! Headless agent runs (used by `conduit run`).
+//!
+//! Drives an `AgentRunner` through one or more turns without a terminal UI while
+//! keeping the same workspace/session bookkeeping as the TUI and web interfaces.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde_json::json;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::agent::events::{AgentEvent, ControlRequestEvent};
+use crate::agent::permissions::{PermissionPolicy, PermissionReply};
+use crate::agent::runner::{AgentInput, AgentRunner, AgentStartConfig, AgentType};
+use crate::agent::session::SessionId;
+use crate::core::services::config_service::ConfigService;
+use crate::core::services::cost_service::{BudgetStatus, CostService};
+use crate::core::services::error::ServiceError;
+use crate::core::services::session_service::{
+    CreateSessionParams, SessionService, UpdateSessionParams,
+};
+use crate::core::ConduitCore;
+use crate::data::{AuditActor, SessionTab, Workspace};
+
+/// Interactive Claude tools that need a human answer and are denied in headless runs.
+const INTERACTIVE_TOOLS: &[&str] = &["AskUserQuestion", "ExitPlanMode"];
+
+#[derive(Debug, Clone)]
+pub struct RunParams {
+    /// Agent to use for a new session (defaults to the configured default agent)
+    pub agent_type: Option<AgentType>,
+    /// Model override (validated against the model registry)
+    pub model: Option<String>,
+    /// Workspace name or ID to run in
+    pub workspace: Option<String>,
+    /// Existing session to continue instead of creating a new one
+    pub session_id: Option<Uuid>,
+    /// Fallback working directory when no workspace is given
+    pub working_dir: PathBuf,
+    /// One prompt per turn; later prompts are sent as follow-ups
+    pub prompts: Vec<String>,
+}
+
+/// A validated run, ready to be executed.
+pub struct PreparedRun {
+    pub session: SessionTab,
+    pub working_dir: PathBuf,
+    prompts: Vec<String>,
+    runner: Arc<dyn AgentRunner>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunOutcome {
+    pub session_id: Uuid,
+    pub agent_session_id: Option<String>,
+    pub turns_completed: usize,
+    /// Error message of the turn that failed, if any
+    pub failure: Option<String>,
+}
+
+impl RunOutcome {
+    pub fn is_success(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Settings for a single agent conversation driven by [`drive_turns`].
+#[derive(Debug, Clone)]
+pub struct TurnDriverConfig {
+    pub agent_type: AgentType,
+    pub working_dir: PathBuf,
+    pub model: Option<String>,
+    pub resume_session_id: Option<String>,
+    pub permissions: PermissionPolicy,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TurnDriverResult {
+    pub agent_session_id: Option<String>,
+    pub turns_completed: usize,
+    pub failure: Option<String>,
+}
+
+enum TurnEnd {
+    Completed,
+    Failed(String),
+    /// Event stream closed before the turn finished
+    Exited(Option<String>),
+}
+
+pub struct RunService;
+
+impl RunService {
+    /// Resolve the workspace and session for a run, creating the session if needed.
+    pub fn prepare(core: &ConduitCore, params: RunParams) -> Result<PreparedRun, ServiceError> {
+        if params.prompts.iter().all(|prompt| prompt.trim().is_empty()) {
+            return Err(ServiceError::InvalidInput(
+                "At least one non-empty prompt is required".to_string(),
+            ));
+        }
+
+        let workspace = match params.workspace.as_deref() {
+            Some(name_or_id) => Some(Self::find_workspace(core, name_or_id)?),
+            None => None,
+        };
+
+        let session = match params.session_id {
+            Some(session_id) => {
+                let session = SessionService::get_session(core, session_id)?;
+                if let Some(workspace) = workspace.as_ref() {
+                    if session.workspace_id != Some(workspace.id) {
+                        return Err(ServiceError::InvalidInput(format!(
+                            "Session {} does not belong to workspace '{}'",
+                            session_id, workspace.name
+                        )));
+                    }
+                }
+                if params.agent_type.is_some() || params.model.is_some() {
+                    SessionService::update_session(
+                        core,
+                        session_id,
+                        UpdateSessionParams {
+                            model: params.model.clone(),
+                            agent_type: params.agent_type,
+                            agent_mode: None,
+                        },
+                    )?
+                } else {
+                    session
+                }
+            }
+            None => {
+                let workspace_id = match workspace.as_ref() {
+                    Some(workspace) => Some(workspace.id),
+                    None => core
+                        .workspace_store()
+                        .and_then(|store| store.get_by_path(&params.working_dir).ok().flatten())
+                        .map(|workspace| workspace.id),
+                };
+                SessionService::create_session(
+                    core,
+                    CreateSessionParams {
+                        workspace_id,
+                        agent_type: params.agent_type.unwrap_or(core.config().default_agent),
+                        model: params.model.clone(),
+                    },
+                )?
+            }
+        };
+
+        let working_dir = match (workspace, session.workspace_id) {
+            (Some(workspace), _) => workspace.path,
+            (None, Some(workspace_id)) => core
+                .workspace_store()
+                .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?
+                .get_by_id(workspace_id)
+                .map_err(|e| ServiceError::Internal(format!("Failed to get workspace: {}", e)))?
+                .map(|workspace| workspace.path)
+                .unwrap_or(params.working_dir),
+            (None, None) => params.working_dir,
+        };
+
+        if !working_dir.exists() {
+            return Err(ServiceError::InvalidInput(format!(
+                "Working directory does not exist: {}",
+                working_dir.display()
+            )));
+        }
+
+        if session.model_invalid || session.model.is_none() {
+            return Err(ServiceError::InvalidInput(
+                "Session has no valid model; pass --model to select one".to_string(),
+            ));
+        }
+
+        let runner: Arc<dyn AgentRunner> = match session.agent_type {
+            AgentType::Claude => core.claude_runner().clone(),
+            AgentType::Codex => core.codex_runner().clone(),
+            AgentType::Gemini => core.gemini_runner().clone(),
+            AgentType::Opencode => core.opencode_runner().clone(),
+        };
+        if !runner.is_available() {
+            return Err(ServiceError::InvalidInput(format!(
+                "{} is not available",
+                session.agent_type.display_name()
+            )));
+        }
+
+        let prompts = params
+            .prompts
+            .into_iter()
+            .filter(|prompt| !prompt.trim().is_empty())
+            .collect();
+
+        Ok(PreparedRun {
+            session,
+            working_dir,
+            prompts,
+            runner,
+        })
+    }
+
+    /// Run every prompt of a prepared run, forwarding agent events to `on_event`.
+    ///
+    /// The session tab is kept up to date as the agent reports its session ID, so a
+    /// headless run can later be resumed from the TUI, the web UI, or another run.
+    pub async fn execute<F>(
+        core: &ConduitCore,
+        run: PreparedRun,
+        mut on_event: F,
+    ) -> Result<RunOutcome, ServiceError>
+    where
+        F: FnMut(&AgentEvent),
+    {
+        let PreparedRun {
+            session,
+            working_dir,
+            prompts,
+            runner,
+        } = run;
+        let session_id = session.id;
+
+        match CostService::check_budget(core, session_id)? {
+            BudgetStatus::WithinBudget => {}
+            BudgetStatus::SoftLimitReached(message) => {
+                tracing::warn!(%session_id, "{}", message);
+            }
+            BudgetStatus::HardLimitReached(message) => {
+                return Err(ServiceError::InvalidInput(message));
+            }
+        }
+
+        for prompt in &prompts {
+            SessionService::append_input_history(core, session_id, prompt)?;
+        }
+
+        let config = TurnDriverConfig {
+            agent_type: session.agent_type,
+            working_dir,
+            model: session.model.clone(),
+            resume_session_id: session.agent_session_id.clone(),
+            permissions: ConfigService::permission_policy(core, session.workspace_id),
+        };
+
+        let store = core.session_tab_store_clone();
+        let journal = core.session_journal();
+        let mut resumed = session.agent_session_id.is_some();
+        let mut record_prompt = |prompt: &str| {
+            if let Some(journal) = journal {
+                journal.record_prompt(session_id, prompt, false, resumed);
+            }
+            resumed = true;
+        };
+        let mut persisted_agent_session_id = session.agent_session_id.clone();
+        let mut turn_started = Instant::now();
+        let mut observe = |event: &AgentEvent| {
+            if let Some(journal) = journal {
+                journal.record_event(session_id, event);
+                if let AgentEvent::ControlRequest(ControlRequestEvent {
+                    request_id,
+                    permission: Some(permission),
+                    ..
+                }) = event
+                {
+                    // Denied by `await_turn_end`, since nobody can answer
+                    journal.record_permission(
+                        session_id,
+                        request_id,
+                        permission,
+                        PermissionReply::Deny.as_str(),
+                        AuditActor::Cli,
+                    );
+                }
+            }
+            match event {
+                AgentEvent::SessionInit(init)
+                    if persisted_agent_session_id.as_deref() != Some(init.session_id.as_str()) =>
+                {
+                    if let Err(error) = persist_agent_session_id(
+                        store.as_ref(),
+                        session_id,
+                        init.session_id.as_str(),
+                    ) {
+                        tracing::warn!(%session_id, error = %error, "Failed to persist agent session id");
+                    } else {
+                        persisted_agent_session_id = Some(init.session_id.as_str().to_string());
+                    }
+                }
+                AgentEvent::TurnStarted => turn_started = Instant::now(),
+                AgentEvent::TurnCompleted(completed) => {
+                    if let Err(error) = CostService::record_turn(
+                        core,
+                        session_id,
+                        session.workspace_id,
+                        session.agent_type,
+                        session.model.as_deref(),
+                        &completed.usage,
+                        Some(turn_started.elapsed()),
+                    ) {
+                        tracing::warn!(%session_id, error = %error, "Failed to record turn cost");
+                    }
+                    turn_started = Instant::now();
+                }
+                AgentEvent::Error(err) if err.code.as_deref() == Some("model_not_found") => {
+                    if let Err(error) = SessionService::invalidate_session_model(core, session_id) {
+                        tracing::warn!(%session_id, error = %error, "Failed to invalidate session model");
+                    }
+                }
+                _ => {}
+            }
+            on_event(event);
+        };
+
+        let result = drive_turns(
+            runner.as_ref(),
+            config,
+            &prompts,
+            &mut record_prompt,
+            &mut observe,
+        )
+        .await;
+        if let Some(journal) = journal {
+            journal.flush();
+        }
+
+        if let Some(agent_session_id) = result.agent_session_id.as_deref() {
+            if persisted_agent_session_id.as_deref() != Some(agent_session_id) {
+                persist_agent_session_id(store.as_ref(), session_id, agent_session_id)
+                    .map_err(ServiceError::Internal)?;
+            }
+        }
+
+        Ok(RunOutcome {
+            session_id,
+            agent_session_id: result.agent_session_id,
+            turns_completed: result.turns_completed,
+            failure: result.failure,
+        })
+    }
+
+    pub(crate) fn find_workspace(
+        core: &ConduitCore,
+        name_or_id: &str,
+    ) -> Result<Workspace, ServiceError> {
+        let store = core
+            .workspace_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+
+        if let Ok(id) = Uuid::parse_str(name_or_id) {
+            return store
+                .get_by_id(id)
+                .map_err(|e| ServiceError::Internal(format!("Failed to get workspace: {}", e)))?
+                .filter(|workspace| workspace.archived_at.is_none())
+                .ok_or_else(|| ServiceError::NotFound(format!("Workspace {} not found", id)));
+        }
+
+        let mut matches: Vec<Workspace> = store
+            .get_all()
+            .map_err(|e| ServiceError::Internal(format!("Failed to list workspaces: {}", e)))?
+            .into_iter()
+            .filter(|workspace| workspace.name == name_or_id)
+            .collect();
+
+        match matches.len() {
+            0 => Err(ServiceError::NotFound(format!(
+                "Workspace '{}' not found",
+                name_or_id
+            ))),
+            1 => Ok(matches.remove(0)),
+            _ => Err(ServiceError::InvalidInput(format!(
+                "Workspace name '{}' is ambiguous; pass the workspace ID instead",
+                name_or_id
+            ))),
+        }
+    }
+}
+
+/// Drive `runner` through one turn per prompt.
+///
+/// Agents that keep a process alive between turns (Claude stream-json, Codex,
+/// OpenCode) receive follow-ups over their input channel; the rest are restarted
+/// with the agent session ID so they resume the same conversation. `on_prompt`
+/// is called just before each prompt is handed to the agent.
+pub async fn drive_turns(
+    runner: &dyn AgentRunner,
+    config: TurnDriverConfig,
+    prompts: &[String],
+    on_prompt: &mut dyn FnMut(&str),
+    on_event: &mut dyn FnMut(&AgentEvent),
+) -> TurnDriverResult {
+    let mut result = TurnDriverResult {
+        agent_session_id: config.resume_session_id.clone(),
+        ..Default::default()
+    };
+    let mut handle = None;
+    let mut input_tx: Option<mpsc::Sender<AgentInput>> = None;
+    let follow_ups = accepts_follow_ups(config.agent_type);
+
+    for prompt in prompts {
+        if let Some(tx) = input_tx.as_ref().filter(|_| follow_ups) {
+            let input = match follow_up_input(config.agent_type, prompt, config.model.clone()) {
+                Ok(input) => input,
+                Err(error) => {
+                    result.failure = Some(error);
+                    break;
+                }
+            };
+            on_prompt(prompt);
+            if tx.send(input).await.is_err() {
+                result.failure = Some("Agent input channel closed".to_string());
+                break;
+            }
+        } else {
+            if let Some(mut previous) = handle.take() {
+                drain_events(&mut previous, &mut result, on_event).await;
+            }
+            let start_config = match build_start_config(&config, prompt, &result) {
+                Ok(start_config) => start_config,
+                Err(error) => {
+                    result.failure = Some(error);
+                    break;
+                }
+            };
+            on_prompt(prompt);
+            let mut started = match runner.start(start_config).await {
+                Ok(started) => started,
+                Err(error) => {
+                    result.failure = Some(format!("Failed to start agent: {}", error));
+                    break;
+                }
+            };
+            if let Some(agent_session_id) = started.session_id.as_ref() {
+                result.agent_session_id = Some(agent_session_id.as_str().to_string());
+            }
+            input_tx = started.take_input_sender();
+            handle = Some(started);
+        }
+
+        let Some(active) = handle.as_mut() else {
+            break;
+        };
+        match await_turn_end(active, input_tx.as_ref(), &mut result, on_event).await {
+            TurnEnd::Completed => result.turns_completed += 1,
+            TurnEnd::Failed(error) => {
+                result.failure = Some(error);
+                break;
+            }
+            TurnEnd::Exited(error) => {
+                result.failure = Some(
+                    error.unwrap_or_else(|| "Agent exited before the turn completed".to_string()),
+                );
+                handle = None;
+                input_tx = None;
+                break;
+            }
+        }
+    }
+
+    if let Some(mut active) = handle {
+        if input_tx.take().is_some() && follow_ups {
+            // Long-lived agent processes wait for more input; shut them down.
+            if let Err(error) = runner.stop(&active).await {
+                tracing::debug!(error = %error, "Failed to stop agent after headless run");
+            }
+        } else {
+            drain_events(&mut active, &mut result, on_event).await;
+        }
+    }
+
+    result
+}
+
+async fn await_turn_end(
+    handle: &mut crate::agent::AgentHandle,
+    input_tx: Option<&mpsc::Sender<AgentInput>>,
+    result: &mut TurnDriverResult,
+    on_event: &mut dyn FnMut(&AgentEvent),
+) -> TurnEnd {
+    let mut fatal_error = None;
+    while let Some(event) = handle.events.recv().await {
+        track_event(&event, result);
+        on_event(&event);
+        match event {
+            AgentEvent::TurnCompleted(_) => return TurnEnd::Completed,
+            AgentEvent::TurnFailed(failed) => return TurnEnd::Failed(failed.error),
+            AgentEvent::Error(err) if err.is_fatal => fatal_error = Some(err.message),
+            AgentEvent::ControlRequest(request) if request.permission.is_some() => {
+                // Nobody can answer in a headless run
+                if let Some(input_tx) = input_tx {
+                    let input = AgentInput::PermissionResponse {
+                        request_id: request.request_id.clone(),
+                        reply: PermissionReply::Deny,
+                    };
+                    if input_tx.send(input).await.is_err() {
+                        tracing::debug!("Failed to deny permission request in headless run");
+                    }
+                }
+            }
+            AgentEvent::ControlRequest(request)
+                if INTERACTIVE_TOOLS.contains(&request.tool_name.as_str()) =>
+            {
+                deny_control_request(input_tx, &request).await;
+            }
+            _ => {}
+        }
+    }
+    TurnEnd::Exited(fatal_error)
+}
+
+async fn drain_events(
+    handle: &mut crate::agent::AgentHandle,
+    result: &mut TurnDriverResult,
+    on_event: &mut dyn FnMut(&AgentEvent),
+) {
+    while let Some(event) = handle.events.recv().await {
+        track_event(&event, result);
+        on_event(&event);
+    }
+}
+
+fn track_event(event: &AgentEvent, result: &mut TurnDriverResult) {
+    if let AgentEvent::SessionInit(init) = event {
+        result.agent_session_id = Some(init.session_id.as_str().to_string());
+    }
+}
+
+fn build_start_config(
+    config: &TurnDriverConfig,
+    prompt: &str,
+    result: &TurnDriverResult,
+) -> Result<AgentStartConfig, String> {
+    let prompt_for_agent = if config.agent_type == AgentType::Claude {
+        String::new()
+    } else {
+        prompt.to_string()
+    };
+    let mut start_config = AgentStartConfig::new(prompt_for_agent, config.working_dir.clone())
+        .with_permissions(config.permissions.clone());
+    if let Some(model) = config.model.clone() {
+        start_config = start_config.with_model(model);
+    }
+    if config.agent_type == AgentType::Claude {
+        start_config = start_config
+            .with_input_format("stream-json")
+            .with_stdin_payload(build_claude_prompt_jsonl(prompt)?);
+    }
+    if let Some(agent_session_id) = result.agent_session_id.clone() {
+        start_config = start_config.with_resume(SessionId::from_string(agent_session_id));
+    }
+    Ok(start_config)
+}
+
+/// Whether the agent takes follow-up prompts over its input channel. Gemini
+/// only accepts permission answers there.
+fn accepts_follow_ups(agent_type: AgentType) -> bool {
+    !matches!(agent_type, AgentType::Gemini)
+}
+
+fn follow_up_input(
+    agent_type: AgentType,
+    prompt: &str,
+    model: Option<String>,
+) -> Result<AgentInput, String> {
+    match agent_type {
+        AgentType::Claude => Ok(AgentInput::ClaudeJsonl(build_claude_prompt_jsonl(prompt)?)),
+        AgentType::Codex | AgentType::Gemini | AgentType::Opencode => Ok(AgentInput::CodexPrompt {
+            text: prompt.to_string(),
+            images: Vec::new(),
+            model,
+        }),
+    }
+}
+
+fn build_claude_prompt_jsonl(prompt: &str) -> Result<String, String> {
+    let payload = json!({
+        "type": "user",
+        "message": {
+            "role": "user",
+            "content": [{ "type": "text", "text": prompt }],
+        }
+    });
+    let json = serde_json::to_string(&payload)
+        .map_err(|e| format!("Failed to serialize Claude JSONL payload: {}", e))?;
+    Ok(format!("{json}\n"))
+}
+
+async fn deny_control_request(
+    input_tx: Option<&mpsc::Sender<AgentInput>>,
+    request: &crate::agent::events::ControlRequestEvent,
+) {
+    let Some(input_tx) = input_tx else {
+        return;
+    };
+    let mut response = serde_json::Map::new();
+    response.insert("behavior".to_string(), json!("deny"));
+    response.insert(
+        "message".to_string(),
+        json!(format!(
+            "{} is not available in a headless run; continue without asking the user.",
+            request.tool_name
+        )),
+    );
+    if let Some(tool_use_id) = request.tool_use_id.as_ref() {
+        response.insert("toolUseID".to_string(), json!(tool_use_id));
+    }
+    let payload = json!({
+        "type": "control_response",
+        "response": {
+            "subtype": "success",
+            "request_id": request.request_id,
+            "response": serde_json::Value::Object(response),
+        }
+    });
+    if let Err(err) = input_tx
+        .send(AgentInput::ClaudeJsonl(format!("{payload}\n")))
+        .await
+    {
+        tracing::warn!("Failed to deny control request: {}", err);
+    }
+}
+
+fn persist_agent_session_id(
+    store: Option<&crate::data::SessionTabStore>,
+    session_id: Uuid,
+    agent_session_id: &str,
+) -> Result<(), String> {
+    let store = store.ok_or_else(|| "Database not available".to_string())?;
+    let mut tab = store
+        .get_by_id(session_id)
+        .map_err(|e| format!("Failed to get session {}: {}", session_id, e))?
+        .ok_or_else(|| format!("Session {} not found in database", session_id))?;
+
+    if tab.agent_session_id.as_deref() == Some(agent_session_id) {
+        return Ok(());
+    }
+
+    tab.agent_session_id = Some(agent_session_id.to_string());
+    store
+        .update(&tab)
+        .map_err(|e| format!("Failed to update session {}: {}", session_id, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{MockAgentRunner, MockConfig, MockEventBuilder};
+
+    fn driver_config(agent_type: AgentType) -> TurnDriverConfig {
+        TurnDriverConfig {
+            agent_type,
+            working_dir: PathBuf::from("/tmp"),
+            model: Some("mock-model".to_string()),
+            resume_session_id: None,
+            permissions: PermissionPolicy::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drive_turns_restarts_with_resume_between_turns() {
+        let events = MockEventBuilder::new("agent-123")
+            .session_init(Some("mock-model"))
+            .turn_started()
+            .assistant_message("done", true)
+            .turn_completed(10, 5)
+            .build();
+        let runner = MockAgentRunner::new(AgentType::Gemini)
+            .with_config(MockConfig::default().with_events(events));
+        let prompts = vec!["first".to_string(), "second".to_string()];
+
+        let mut sent = Vec::new();
+        let mut seen = Vec::new();
+        let result = drive_turns(
+            &runner,
+            driver_config(AgentType::Gemini),
+            &prompts,
+            &mut |prompt| sent.push(prompt.to_string()),
+            &mut |event| seen.push(event.event_type_name()),
+        )
+        .await;
+
+        assert_eq!(sent, prompts);
+        assert_eq!(result.turns_completed, 2);
+        assert!(result.failure.is_none());
+        assert_eq!(result.agent_session_id.as_deref(), Some("agent-123"));
+        assert_eq!(
+            seen.iter().filter(|name| **name == "TurnCompleted").count(),
+            2
+        );
+
+        let config = runner.last_config().expect("second start config");
+        assert_eq!(config.prompt, "second");
+        assert_eq!(
+            config.resume_session.as_ref().map(|id| id.as_str()),
+            Some("agent-123")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_drive_turns_stops_on_turn_failed() {
+        let mut events = MockEventBuilder::new("agent-456")
+            .session_init(None)
+            .turn_started()
+            .build();
+        events.push(AgentEvent::TurnFailed(crate::agent::TurnFailedEvent {
+            error: "boom".to_string(),
+        }));
+        let runner = MockAgentRunner::new(AgentType::Codex)
+            .with_config(MockConfig::default().with_events(events));
+        let prompts = vec!["first".to_string(), "second".to_string()];
+
+        let result = drive_turns(
+            &runner,
+            driver_config(AgentType::Codex),
+            &prompts,
+            &mut |_| {},
+            &mut |_| {},
+        )
+        .await;
+
+        assert_eq!(result.turns_completed, 0);
+        assert_eq!(result.failure.as_deref(), Some("boom"));
+    }
+
+    #[tokio::test]
+    async fn test_drive_turns_reports_exit_before_completion() {
+        let events = MockEventBuilder::new("agent-789")
+            .session_init(None)
+            .error("process crashed", true)
+            .build();
+        let runner = MockAgentRunner::new(AgentType::Claude)
+            .with_config(MockConfig::default().with_events(events));
+
+        let result = drive_turns(
+            &runner,
+            driver_config(AgentType::Claude),
+            &["hello".to_string()],
+            &mut |_| {},
+            &mut |_| {},
+        )
+        .await;
+
+        assert_eq!(result.failure.as_deref(), Some("process crashed"));
+        let config = runner.last_config().expect("start config");
+        assert!(config.prompt.is_empty());
+        assert_eq!(config.input_format.as_deref(), Some("stream-json"));
+        assert!(config
+            .stdin_payload
+            .as_deref()
+            .is_some_and(|payload| payload.contains("hello")));
+    }
+}
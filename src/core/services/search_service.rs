@@ -0,0 +1,488 @@
+//! Full-text search across Conduit sessions and imported agent histories.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::agent::agent_history_path;
+use crate::core::services::error::ServiceError;
+use crate::core::services::session_service::{load_agent_history, SessionService};
+use crate::core::ConduitCore;
+use crate::data::{
+    fts_query, AppStateStore, IndexedMessage, SearchDocument, SearchHit, SearchIndexStore,
+    SearchSource, SessionEventStore, SessionTab, SessionTabStore, WorkspaceStore,
+};
+use crate::session::{discover_all_sessions, get_file_mtime, ExternalSession};
+use crate::ui::app_prompt::FORK_SEED_PREFIX;
+use crate::ui::components::{ChatMessage, MessageRole};
+
+/// App state key holding the time of the last full index refresh.
+const REFRESHED_AT_KEY: &str = "search_index_refreshed_at";
+
+/// Default number of hits returned by a search.
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+/// Upper bound on the number of hits returned by a search.
+const MAX_SEARCH_LIMIT: usize = 500;
+
+/// Maximum title length stored for an indexed session.
+const TITLE_MAX_CHARS: usize = 80;
+
+/// Counts reported by an index refresh.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct IndexRefreshStats {
+    /// Sessions (re)indexed because their content changed
+    pub indexed: usize,
+    /// Sessions skipped because they were already up to date
+    pub unchanged: usize,
+    /// Sessions removed because they no longer exist
+    pub removed: usize,
+}
+
+pub struct SearchService;
+
+impl SearchService {
+    /// Build an indexer from the core's stores.
+    ///
+    /// The indexer owns cloned stores so it can run on a blocking thread.
+    pub fn indexer(core: &ConduitCore) -> Result<SearchIndexer, ServiceError> {
+        let search_store = core
+            .search_index_store_clone()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        Ok(SearchIndexer {
+            search_store,
+            session_tab_store: core.session_tab_store_clone(),
+            session_event_store: core.session_event_store_clone(),
+            workspace_store: core.workspace_store_clone(),
+            app_state_store: core.app_state_store_clone(),
+        })
+    }
+
+    /// Search the index, best matches first.
+    pub fn search(
+        core: &ConduitCore,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, ServiceError> {
+        let store = core
+            .search_index_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        Self::search_store(store, query, limit)
+    }
+
+    /// Search using an explicit store (for callers that don't hold the core).
+    pub fn search_store(
+        store: &SearchIndexStore,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, ServiceError> {
+        let fts = fts_query(query)
+            .ok_or_else(|| ServiceError::InvalidInput("Search query is empty".to_string()))?;
+        store
+            .search(&fts, limit.clamp(1, MAX_SEARCH_LIMIT))
+            .map_err(|e| ServiceError::Internal(format!("Search failed: {}", e)))
+    }
+}
+
+/// Keeps the search index in sync with Conduit sessions and external histories.
+#[derive(Clone)]
+pub struct SearchIndexer {
+    search_store: SearchIndexStore,
+    session_tab_store: Option<SessionTabStore>,
+    session_event_store: Option<SessionEventStore>,
+    workspace_store: Option<WorkspaceStore>,
+    app_state_store: Option<AppStateStore>,
+}
+
+impl SearchIndexer {
+    /// Index every Conduit session and every discovered external session.
+    ///
+    /// Sessions whose content hasn't changed since they were last indexed are
+    /// skipped, so repeated refreshes are cheap.
+    pub fn refresh(&self) -> Result<IndexRefreshStats, ServiceError> {
+        let mut stats = IndexRefreshStats::default();
+        let agent_session_ids = self.refresh_conduit_sessions(&mut stats)?;
+        self.refresh_external_sessions(discover_all_sessions(), &agent_session_ids, &mut stats)?;
+
+        if let Some(store) = self.app_state_store.as_ref() {
+            if let Err(e) = store.set(REFRESHED_AT_KEY, &Utc::now().to_rfc3339()) {
+                tracing::debug!(error = %e, "Failed to record search index refresh time");
+            }
+        }
+        tracing::debug!(?stats, "Search index refreshed");
+        Ok(stats)
+    }
+
+    /// Refresh the index unless it was refreshed within `max_age`.
+    ///
+    /// Returns `None` when the index was fresh enough.
+    pub fn refresh_if_stale(
+        &self,
+        max_age: Duration,
+    ) -> Result<Option<IndexRefreshStats>, ServiceError> {
+        let refreshed_at = self
+            .app_state_store
+            .as_ref()
+            .and_then(|store| store.get(REFRESHED_AT_KEY).ok().flatten())
+            .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        match refreshed_at {
+            Some(at) if Utc::now().signed_duration_since(at) < max_age => Ok(None),
+            _ => self.refresh().map(Some),
+        }
+    }
+
+    /// Index Conduit session tabs (open and closed).
+    ///
+    /// Returns the agent session IDs of the indexed tabs so the matching
+    /// external histories aren't indexed twice.
+    pub fn refresh_conduit_sessions(
+        &self,
+        stats: &mut IndexRefreshStats,
+    ) -> Result<HashSet<String>, ServiceError> {
+        let Some(tab_store) = self.session_tab_store.as_ref() else {
+            return Ok(HashSet::new());
+        };
+        let sessions = tab_store
+            .get_all_including_closed()
+            .map_err(|e| ServiceError::Internal(format!("Failed to list sessions: {}", e)))?;
+
+        let mut keys = HashSet::new();
+        let mut agent_session_ids = HashSet::new();
+        for session in &sessions {
+            keys.insert(session.id.to_string());
+            if let Some(agent_session_id) = session.agent_session_id.as_ref() {
+                agent_session_ids.insert(agent_session_id.clone());
+            }
+
+            let fingerprint = self.conduit_fingerprint(session);
+            if self.is_unchanged(SearchSource::Conduit, &session.id.to_string(), &fingerprint) {
+                stats.unchanged += 1;
+                continue;
+            }
+
+            let messages =
+                SessionService::load_history_with_store(self.session_event_store.as_ref(), session);
+            let document = SearchDocument {
+                source: SearchSource::Conduit,
+                source_key: session.id.to_string(),
+                agent_type: session.agent_type,
+                title: session
+                    .title
+                    .as_deref()
+                    .filter(|title| !title.trim().is_empty())
+                    .map(truncate_title)
+                    .unwrap_or_else(|| fallback_title(&messages)),
+                project: self.workspace_path(session),
+                file_path: None,
+                fingerprint,
+            };
+            self.store_document(&document, &messages)?;
+            stats.indexed += 1;
+        }
+
+        stats.removed += self
+            .search_store
+            .retain_documents(SearchSource::Conduit, &keys)
+            .map_err(|e| ServiceError::Internal(format!("Failed to prune search index: {}", e)))?;
+
+        Ok(agent_session_ids)
+    }
+
+    /// Index sessions discovered in external agent histories.
+    ///
+    /// Sessions whose ID is in `skip_ids` (already indexed as Conduit tabs)
+    /// are left out.
+    pub fn refresh_external_sessions(
+        &self,
+        sessions: Vec<ExternalSession>,
+        skip_ids: &HashSet<String>,
+        stats: &mut IndexRefreshStats,
+    ) -> Result<(), ServiceError> {
+        let mut keys = HashSet::new();
+        for session in sessions {
+            let agent_session_id = external_session_id(&session);
+            if skip_ids.contains(&session.id) || skip_ids.contains(&agent_session_id) {
+                continue;
+            }
+
+            let key = session.file_path.to_string_lossy().to_string();
+            keys.insert(key.clone());
+
+            let fingerprint = format!(
+                "{}:{}",
+                get_file_mtime(&session.file_path).unwrap_or(0),
+                session.message_count
+            );
+            if self.is_unchanged(SearchSource::External, &key, &fingerprint) {
+                stats.unchanged += 1;
+                continue;
+            }
+
+            let messages = load_agent_history(session.agent_type, &agent_session_id);
+            let document = SearchDocument {
+                source: SearchSource::External,
+                source_key: key,
+                agent_type: session.agent_type,
+                title: session.truncated_display(TITLE_MAX_CHARS),
+                project: session.project.clone(),
+                file_path: Some(session.file_path.clone()),
+                fingerprint,
+            };
+            self.store_document(&document, &messages)?;
+            stats.indexed += 1;
+        }
+
+        stats.removed += self
+            .search_store
+            .retain_documents(SearchSource::External, &keys)
+            .map_err(|e| ServiceError::Internal(format!("Failed to prune search index: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn store_document(
+        &self,
+        document: &SearchDocument,
+        messages: &[ChatMessage],
+    ) -> Result<(), ServiceError> {
+        self.search_store
+            .replace_document(document, &indexed_messages(messages))
+            .map_err(|e| ServiceError::Internal(format!("Failed to index session: {}", e)))
+    }
+
+    fn is_unchanged(&self, source: SearchSource, key: &str, fingerprint: &str) -> bool {
+        matches!(
+            self.search_store.fingerprint(source, key),
+            Ok(Some(existing)) if existing == fingerprint
+        )
+    }
+
+    /// Fingerprint a Conduit tab by its latest journal entry and agent session.
+    ///
+    /// Tabs without a journal are indexed from the agent's history file, so
+    /// its modification time and size stand in for the journal entry.
+    fn conduit_fingerprint(&self, session: &SessionTab) -> String {
+        let last_event_id = self
+            .session_event_store
+            .as_ref()
+            .and_then(|store| store.last_event_id(session.id).ok().flatten());
+        let history_version = match last_event_id {
+            Some(id) => id.to_string(),
+            None => session
+                .agent_session_id
+                .as_deref()
+                .and_then(|agent_session_id| {
+                    agent_history_path(session.agent_type, agent_session_id)
+                })
+                .map(|path| {
+                    let size = std::fs::metadata(&path).map_or(0, |meta| meta.len());
+                    format!("{}+{}", get_file_mtime(&path).unwrap_or(0), size)
+                })
+                .unwrap_or_else(|| "0".to_string()),
+        };
+        format!(
+            "{}:{}:{}:{}",
+            history_version,
+            session.agent_session_id.as_deref().unwrap_or_default(),
+            session.title.as_deref().unwrap_or_default(),
+            session
+                .pending_user_message
+                .as_deref()
+                .unwrap_or_default()
+                .len()
+        )
+    }
+
+    fn workspace_path(&self, session: &SessionTab) -> Option<String> {
+        let workspace_id = session.workspace_id?;
+        let workspace = self
+            .workspace_store
+            .as_ref()?
+            .get_by_id(workspace_id)
+            .ok()
+            .flatten()?;
+        Some(workspace.path.to_string_lossy().to_string())
+    }
+}
+
+/// The agent session ID used to load an external session's history.
+///
+/// Matches how the import picker resumes sessions (the session file's stem).
+pub fn external_session_id(session: &ExternalSession) -> String {
+    session
+        .file_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(|stem| stem.to_string())
+        .unwrap_or_else(|| session.id.clone())
+}
+
+/// Select the searchable messages of a chat history.
+///
+/// Message indices refer to positions in `messages`, so a hit can be mapped
+/// back to the message shown in a restored tab.
+fn indexed_messages(messages: &[ChatMessage]) -> Vec<IndexedMessage> {
+    messages
+        .iter()
+        .enumerate()
+        .filter_map(|(message_index, msg)| {
+            let (role, content) = match msg.role {
+                MessageRole::User => {
+                    if msg.content.trim_start().starts_with(FORK_SEED_PREFIX) {
+                        return None;
+                    }
+                    ("user", msg.content.clone())
+                }
+                MessageRole::Assistant => ("assistant", msg.content.clone()),
+                MessageRole::Tool => {
+                    let content = [
+                        msg.tool_name.as_deref(),
+                        msg.tool_args.as_deref(),
+                        Some(msg.content.as_str()),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                    ("tool", content)
+                }
+                _ => return None,
+            };
+            if content.trim().is_empty() {
+                return None;
+            }
+            Some(IndexedMessage {
+                message_index,
+                role,
+                content,
+            })
+        })
+        .collect()
+}
+
+fn fallback_title(messages: &[ChatMessage]) -> String {
+    messages
+        .iter()
+        .find(|msg| {
+            msg.role == MessageRole::User && !msg.content.trim_start().starts_with(FORK_SEED_PREFIX)
+        })
+        .map(|msg| truncate_title(&msg.content))
+        .unwrap_or_else(|| "Untitled session".to_string())
+}
+
+fn truncate_title(title: &str) -> String {
+    let line = title.lines().next().unwrap_or_default().trim();
+    if line.chars().count() <= TITLE_MAX_CHARS {
+        return line.to_string();
+    }
+    let truncated: String = line.chars().take(TITLE_MAX_CHARS - 3).collect();
+    format!("{}...", truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::events::{AgentEvent, AssistantMessageEvent};
+    use crate::agent::AgentType;
+    use crate::data::{Database, SessionEventPayload};
+    use tempfile::tempdir;
+
+    fn indexer(db: &Database) -> SearchIndexer {
+        SearchIndexer {
+            search_store: SearchIndexStore::new(db.connection()),
+            session_tab_store: Some(SessionTabStore::new(db.connection())),
+            session_event_store: Some(SessionEventStore::new(db.connection())),
+            workspace_store: Some(WorkspaceStore::new(db.connection())),
+            app_state_store: Some(AppStateStore::new(db.connection())),
+        }
+    }
+
+    #[test]
+    fn test_indexed_messages_keeps_searchable_roles() {
+        let mut tool = ChatMessage::user("README.md");
+        tool.role = MessageRole::Tool;
+        tool.tool_name = Some("Bash".to_string());
+        tool.tool_args = Some("ls".to_string());
+        let mut reasoning = ChatMessage::user("thinking");
+        reasoning.role = MessageRole::Reasoning;
+
+        let messages = vec![
+            ChatMessage::user(format!("{}\nseed", FORK_SEED_PREFIX)),
+            ChatMessage::user("list files"),
+            reasoning,
+            tool,
+        ];
+        let indexed = indexed_messages(&messages);
+
+        assert_eq!(indexed.len(), 2);
+        assert_eq!(indexed[0].message_index, 1);
+        assert_eq!(indexed[0].role, "user");
+        assert_eq!(indexed[1].message_index, 3);
+        assert_eq!(indexed[1].content, "Bash\nls\nREADME.md");
+    }
+
+    #[test]
+    fn test_refresh_conduit_sessions_indexes_journal_once() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let indexer = indexer(&db);
+
+        let tab = SessionTab::new(
+            0,
+            AgentType::Codex,
+            None,
+            Some("abc".to_string()),
+            None,
+            None,
+        );
+        SessionTabStore::new(db.connection()).create(&tab).unwrap();
+        let events = SessionEventStore::new(db.connection());
+        events
+            .append(
+                tab.id,
+                &SessionEventPayload::UserPrompt {
+                    text: "fix the migration bug".to_string(),
+                    hidden: false,
+                    resumed: false,
+                },
+            )
+            .unwrap();
+        events
+            .append(
+                tab.id,
+                &SessionEventPayload::Agent {
+                    event: AgentEvent::AssistantMessage(AssistantMessageEvent {
+                        text: "Patched the migrations".to_string(),
+                        is_final: true,
+                    }),
+                },
+            )
+            .unwrap();
+
+        let mut stats = IndexRefreshStats::default();
+        let ids = indexer.refresh_conduit_sessions(&mut stats).unwrap();
+        assert!(ids.contains("abc"));
+        assert_eq!(stats.indexed, 1);
+
+        let hits = SearchService::search_store(&indexer.search_store, "migrat", 10).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits
+            .iter()
+            .all(|hit| hit.document.source == SearchSource::Conduit
+                && hit.document.source_key == tab.id.to_string()
+                && hit.document.title == "fix the migration bug"));
+
+        let mut stats = IndexRefreshStats::default();
+        indexer.refresh_conduit_sessions(&mut stats).unwrap();
+        assert_eq!(stats.unchanged, 1);
+        assert_eq!(stats.indexed, 0);
+
+        assert!(matches!(
+            SearchService::search_store(&indexer.search_store, "  ", 10),
+            Err(ServiceError::InvalidInput(_))
+        ));
+    }
+}
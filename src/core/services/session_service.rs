@@ -2,12 +2,17 @@ use chrono::Utc;
 use rusqlite::{params, Error as SqliteError, ErrorCode, Result as SqliteResult};
 use uuid::Uuid;
 
-use crate::agent::{AgentMode, AgentType, ModelRegistry};
+use crate::agent::{
+    load_claude_history_with_debug, load_codex_history_with_debug, load_journal_history,
+    load_opencode_history_with_debug, AgentMode, AgentType, ModelRegistry,
+};
 use crate::core::services::error::ServiceError;
 use crate::core::ConduitCore;
 use crate::data::{
-    QueuedImageAttachment, QueuedMessage, QueuedMessageMode, SessionTab, SessionTabStore,
+    QueuedImageAttachment, QueuedMessage, QueuedMessageMode, SessionEventStore, SessionTab,
+    SessionTabStore,
 };
+use crate::ui::components::{ChatMessage, MessageRole};
 
 const INPUT_HISTORY_MAX: usize = 1000;
 
@@ -548,6 +553,80 @@ impl SessionService {
 
         Ok(session.input_history)
     }
+
+    /// Load the chat history for a session.
+    ///
+    /// Uses the session event journal when available and falls back to the
+    /// agent's own session files. A pending user message that never reached
+    /// the agent is appended so it isn't lost.
+    pub fn load_history(core: &ConduitCore, session: &SessionTab) -> Vec<ChatMessage> {
+        Self::load_history_with_store(core.session_event_store(), session)
+    }
+
+    /// Load the chat history for a session using an explicit journal store.
+    ///
+    /// Used by background work that can't borrow the [`ConduitCore`].
+    pub fn load_history_with_store(
+        event_store: Option<&SessionEventStore>,
+        session: &SessionTab,
+    ) -> Vec<ChatMessage> {
+        let journal = event_store.and_then(|store| load_journal_history(store, session.id));
+        let mut messages = match journal {
+            Some((messages, _)) => messages,
+            None => load_vendor_history(session),
+        };
+
+        if let Some(pending) = session.pending_user_message.as_ref() {
+            let already_in_history = messages
+                .iter()
+                .rev()
+                .find(|m| m.role == MessageRole::User)
+                .map(|m| m.content.as_str() == pending.as_str())
+                .unwrap_or(false);
+
+            if !already_in_history {
+                messages.push(ChatMessage::user(pending.clone()));
+            }
+        }
+
+        messages
+    }
+}
+
+/// Load history from the agent's own session files.
+fn load_vendor_history(session: &SessionTab) -> Vec<ChatMessage> {
+    match session.agent_session_id.as_deref() {
+        Some(agent_session_id) => load_agent_history(session.agent_type, agent_session_id),
+        None => Vec::new(),
+    }
+}
+
+/// Load history for an agent session ID from the agent's own session files.
+pub(crate) fn load_agent_history(
+    agent_type: AgentType,
+    agent_session_id: &str,
+) -> Vec<ChatMessage> {
+    match agent_type {
+        AgentType::Claude => load_claude_history_with_debug(agent_session_id)
+            .map(|(messages, _, _)| messages)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load Claude history: {}", e);
+                Vec::new()
+            }),
+        AgentType::Codex => load_codex_history_with_debug(agent_session_id)
+            .map(|(messages, _, _)| messages)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load Codex history: {}", e);
+                Vec::new()
+            }),
+        AgentType::Gemini => Vec::new(),
+        AgentType::Opencode => load_opencode_history_with_debug(agent_session_id)
+            .map(|(messages, _, _)| messages)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load OpenCode history: {}", e);
+                Vec::new()
+            }),
+    }
 }
 
 fn is_unique_violation(err: &SqliteError) -> bool {
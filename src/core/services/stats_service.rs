@@ -0,0 +1,207 @@
+//! Usage analytics: recorded turn usage aggregated per day, agent, model, repository and workspace.
+
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::Serialize;
+
+use crate::agent::ModelRegistry;
+use crate::core::services::cost_service::start_of_local_day;
+use crate::core::services::error::ServiceError;
+use crate::core::ConduitCore;
+use crate::data::{UsageBucket, UsageDimension, UsageStore, UsageTotals};
+
+/// Longest range that can be requested, in days.
+pub const MAX_STATS_DAYS: u32 = 3650;
+
+/// One row of a usage breakdown.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageRow {
+    /// Grouping key: local date, agent type, model ID, or repository/workspace ID
+    pub key: Option<String>,
+    /// Human-readable label for the row
+    pub label: String,
+    pub totals: UsageTotals,
+}
+
+/// Usage totals and breakdowns over a time range.
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageStats {
+    /// Number of local days covered, including today (None = all time)
+    pub days: Option<u32>,
+    /// Start of the range (None = all time)
+    pub since: Option<DateTime<Utc>>,
+    pub totals: UsageTotals,
+    pub by_day: Vec<UsageRow>,
+    pub by_agent: Vec<UsageRow>,
+    pub by_model: Vec<UsageRow>,
+    pub by_repository: Vec<UsageRow>,
+    pub by_workspace: Vec<UsageRow>,
+}
+
+impl UsageStats {
+    /// Rows for a breakdown dimension.
+    pub fn breakdown(&self, dimension: UsageDimension) -> &[UsageRow] {
+        match dimension {
+            UsageDimension::Day => &self.by_day,
+            UsageDimension::Agent => &self.by_agent,
+            UsageDimension::Model => &self.by_model,
+            UsageDimension::Repository => &self.by_repository,
+            UsageDimension::Workspace => &self.by_workspace,
+        }
+    }
+}
+
+pub struct StatsService;
+
+impl StatsService {
+    /// Aggregate recorded usage over the last `days` local days (None = all time).
+    pub fn usage_stats(core: &ConduitCore, days: Option<u32>) -> Result<UsageStats, ServiceError> {
+        let store = core
+            .usage_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        Self::usage_stats_store(store, days)
+    }
+
+    /// Aggregate using an explicit store (for callers that don't hold the core).
+    pub fn usage_stats_store(
+        store: &UsageStore,
+        days: Option<u32>,
+    ) -> Result<UsageStats, ServiceError> {
+        if let Some(days) = days {
+            if days == 0 || days > MAX_STATS_DAYS {
+                return Err(ServiceError::InvalidInput(format!(
+                    "days must be between 1 and {}",
+                    MAX_STATS_DAYS
+                )));
+            }
+        }
+        let since =
+            days.map(|days| start_of_local_day(Local::now()) - Duration::days(i64::from(days) - 1));
+
+        let totals = match since {
+            Some(since) => store.totals_since(since),
+            None => store.totals(),
+        }
+        .map_err(query_error)?;
+        let breakdown = |dimension: UsageDimension| -> Result<Vec<UsageRow>, ServiceError> {
+            Ok(store
+                .breakdown(dimension, since)
+                .map_err(query_error)?
+                .into_iter()
+                .map(|bucket| UsageRow {
+                    label: bucket_label(dimension, &bucket),
+                    key: bucket.key,
+                    totals: bucket.totals,
+                })
+                .collect())
+        };
+
+        Ok(UsageStats {
+            days,
+            since,
+            totals,
+            by_day: breakdown(UsageDimension::Day)?,
+            by_agent: breakdown(UsageDimension::Agent)?,
+            by_model: breakdown(UsageDimension::Model)?,
+            by_repository: breakdown(UsageDimension::Repository)?,
+            by_workspace: breakdown(UsageDimension::Workspace)?,
+        })
+    }
+}
+
+fn query_error(e: rusqlite::Error) -> ServiceError {
+    ServiceError::Internal(format!("Failed to load usage stats: {}", e))
+}
+
+/// Display label for a bucket, resolving agent and model names.
+fn bucket_label(dimension: UsageDimension, bucket: &UsageBucket) -> String {
+    match dimension {
+        UsageDimension::Day => bucket.key.clone().unwrap_or_else(|| "Unknown".to_string()),
+        UsageDimension::Agent => bucket
+            .agent_type
+            .map(|agent_type| agent_type.display_name().to_string())
+            .unwrap_or_else(|| "Unknown".to_string()),
+        UsageDimension::Model => {
+            let model = match (bucket.agent_type, bucket.key.as_deref()) {
+                (Some(agent_type), Some(model)) => ModelRegistry::find_model(agent_type, model)
+                    .map(|info| info.display_name)
+                    .unwrap_or_else(|| model.to_string()),
+                (_, Some(model)) => model.to_string(),
+                (_, None) => "default model".to_string(),
+            };
+            match bucket.agent_type {
+                Some(agent_type) => format!("{} · {}", agent_type.display_name(), model),
+                None => model,
+            }
+        }
+        UsageDimension::Repository | UsageDimension::Workspace => {
+            match (&bucket.name, &bucket.key) {
+                (Some(name), _) => name.clone(),
+                (None, Some(_)) => "(deleted)".to_string(),
+                (None, None) if dimension == UsageDimension::Repository => "No project".to_string(),
+                (None, None) => "No workspace".to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::agent::AgentType;
+
+    fn bucket(key: Option<&str>, agent_type: Option<AgentType>, name: Option<&str>) -> UsageBucket {
+        UsageBucket {
+            key: key.map(str::to_string),
+            agent_type,
+            name: name.map(str::to_string),
+            totals: UsageTotals::default(),
+        }
+    }
+
+    #[test]
+    fn test_bucket_labels() {
+        let sonnet = bucket(Some("sonnet"), Some(AgentType::Claude), None);
+        let label = bucket_label(UsageDimension::Model, &sonnet);
+        assert!(label.starts_with("Claude Code · "));
+        assert_ne!(label, "Claude Code · sonnet");
+        assert_eq!(
+            bucket_label(
+                UsageDimension::Model,
+                &bucket(None, Some(AgentType::Codex), None)
+            ),
+            "Codex CLI · default model"
+        );
+        assert_eq!(
+            bucket_label(
+                UsageDimension::Repository,
+                &bucket(Some("id"), None, Some("conduit"))
+            ),
+            "conduit"
+        );
+        assert_eq!(
+            bucket_label(UsageDimension::Workspace, &bucket(Some("id"), None, None)),
+            "(deleted)"
+        );
+        assert_eq!(
+            bucket_label(UsageDimension::Repository, &bucket(None, None, None)),
+            "No project"
+        );
+    }
+
+    #[test]
+    fn test_usage_stats_rejects_invalid_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = crate::data::Database::open(dir.path().join("test.db")).unwrap();
+        let store = UsageStore::new(db.connection());
+
+        assert!(matches!(
+            StatsService::usage_stats_store(&store, Some(0)),
+            Err(ServiceError::InvalidInput(_))
+        ));
+        let stats = StatsService::usage_stats_store(&store, Some(7)).unwrap();
+        assert_eq!(stats.totals, UsageTotals::default());
+        assert!(stats.since.is_some());
+        assert!(stats.by_model.is_empty());
+    }
+}
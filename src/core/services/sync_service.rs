@@ -0,0 +1,76 @@
+//! Syncing workspaces with their base branch.
+//!
+//! Whether a workspace merges or rebases comes from its repository's
+//! settings, falling back to `[workspaces] sync_strategy`.
+
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::core::resolve_repo_workspace_settings;
+use crate::core::services::error::ServiceError;
+use crate::core::ConduitCore;
+use crate::git::{
+    SyncConflicts, SyncManager, SyncOutcome, SyncStatus, SyncStrategy, WorktreeError,
+};
+
+pub struct SyncService;
+
+impl SyncService {
+    /// Sync strategy configured for the workspace's repository
+    pub fn strategy(core: &ConduitCore, workspace_id: Uuid) -> Result<SyncStrategy, ServiceError> {
+        let workspace = core
+            .workspace_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?
+            .get_by_id(workspace_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get workspace: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Workspace {} not found", workspace_id))
+            })?;
+        let repo = core
+            .repo_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?
+            .get_by_id(workspace.repository_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get repository: {}", e)))?;
+        Ok(match repo {
+            Some(repo) => resolve_repo_workspace_settings(core.config(), &repo).sync_strategy,
+            None => core.config().workspaces.sync_strategy,
+        })
+    }
+
+    /// Ahead/behind counts and any sync in progress. Runs git.
+    pub fn status(working_dir: &Path) -> Result<SyncStatus, ServiceError> {
+        SyncManager::status(working_dir).map_err(map_sync_error)
+    }
+
+    /// Merge in or rebase onto the base branch. Runs git.
+    pub fn sync(working_dir: &Path, strategy: SyncStrategy) -> Result<SyncOutcome, ServiceError> {
+        SyncManager::sync(working_dir, strategy).map_err(map_sync_error)
+    }
+
+    /// Continue a sync once its conflicts are resolved. Runs git.
+    pub fn continue_sync(working_dir: &Path) -> Result<SyncOutcome, ServiceError> {
+        SyncManager::continue_sync(working_dir).map_err(map_sync_error)
+    }
+
+    /// Abort the sync in progress. Runs git.
+    pub fn abort(working_dir: &Path) -> Result<SyncStrategy, ServiceError> {
+        SyncManager::abort(working_dir).map_err(map_sync_error)
+    }
+
+    /// Prompt asking the agent to resolve a stopped sync's conflicts
+    pub fn conflict_prompt(conflicts: &SyncConflicts) -> String {
+        SyncManager::build_conflict_prompt(conflicts)
+    }
+}
+
+/// Git refusing or stopping is the caller's to fix; anything else is ours
+fn map_sync_error(error: WorktreeError) -> ServiceError {
+    match error {
+        WorktreeError::CommandFailed(message) => ServiceError::InvalidInput(message),
+        WorktreeError::NotFound(path) => {
+            ServiceError::NotFound(format!("Workspace path {} not found", path.display()))
+        }
+        other => ServiceError::Internal(format!("Failed to sync workspace: {}", other)),
+    }
+}
@@ -0,0 +1,446 @@
+//! Background writer for the per-session event journal.
+//!
+//! Agent events arrive at streaming rates (one per text delta), so they are
+//! handed to a dedicated thread that batches them into SQLite transactions
+//! instead of blocking the UI or the WebSocket forwarding tasks.
+//!
+//! The same thread appends tool executions, file changes and permission
+//! decisions to the audit log. Audit entries the database keeps rejecting are
+//! appended to a JSON Lines fallback file instead, and the failure is kept for
+//! the UI to report.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::agent::{canonical_tool_name, AgentEvent, PermissionRequest};
+use crate::data::{
+    AuditActor, AuditEntry, AuditStore, SessionEvent, SessionEventPayload, SessionEventStore,
+};
+
+/// Maximum number of events written in a single transaction.
+const MAX_BATCH: usize = 256;
+
+/// Attempts at writing an audit batch before it goes to the fallback file
+const AUDIT_WRITE_ATTEMPTS: u32 = 3;
+
+/// Pause before retrying an audit batch, multiplied by the attempt number
+const AUDIT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+enum JournalCommand {
+    Append(Uuid, SessionEventPayload, DateTime<Utc>),
+    Audit(AuditEntry),
+    Flush(mpsc::Sender<()>),
+}
+
+/// Cloneable handle for appending to the session event journal.
+#[derive(Clone)]
+pub struct SessionJournal {
+    tx: mpsc::Sender<JournalCommand>,
+    audit_failure: Arc<Mutex<Option<String>>>,
+}
+
+impl SessionJournal {
+    /// Spawn the writer thread for the given stores.
+    pub fn spawn(store: SessionEventStore, audit_store: AuditStore) -> Self {
+        Self::spawn_with_fallback(store, audit_store, crate::util::audit_fallback_path())
+    }
+
+    fn spawn_with_fallback(
+        store: SessionEventStore,
+        audit_store: AuditStore,
+        fallback_path: PathBuf,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let audit_failure = Arc::new(Mutex::new(None));
+        let audit = AuditWriter {
+            store: audit_store,
+            fallback_path,
+            failure: audit_failure.clone(),
+        };
+        let spawned = thread::Builder::new()
+            .name("conduit-session-journal".to_string())
+            .spawn(move || run_writer(store, audit, rx));
+        if let Err(e) = spawned {
+            tracing::warn!(error = %e, "Failed to spawn session journal writer");
+        }
+        Self { tx, audit_failure }
+    }
+
+    /// The latest audit log write failure, if any, without clearing it.
+    pub fn audit_failure(&self) -> Option<String> {
+        self.audit_failure.lock().clone()
+    }
+
+    /// The latest audit log write failure not yet reported to the user.
+    pub fn take_audit_failure(&self) -> Option<String> {
+        self.audit_failure.lock().take()
+    }
+
+    /// Record a prompt submitted by the user.
+    ///
+    /// `resumed` marks prompts sent to an agent session that existed before
+    /// journaling started, so replay knows the journal may be incomplete.
+    pub fn record_prompt(&self, session_id: Uuid, text: &str, hidden: bool, resumed: bool) {
+        self.send(
+            session_id,
+            SessionEventPayload::UserPrompt {
+                text: text.to_string(),
+                hidden,
+                resumed,
+            },
+        );
+    }
+
+    /// Record an event received from the agent, auditing tool executions,
+    /// file changes and policy decisions.
+    pub fn record_event(&self, session_id: Uuid, event: &AgentEvent) {
+        self.send(
+            session_id,
+            SessionEventPayload::Agent {
+                event: event.clone(),
+            },
+        );
+        if let Some(entry) = AuditEntry::from_event(session_id, event) {
+            self.audit(entry);
+        }
+    }
+
+    /// Audit a user's (or headless run's) answer to a permission prompt.
+    pub fn record_permission(
+        &self,
+        session_id: Uuid,
+        request_id: &str,
+        request: &PermissionRequest,
+        decision: &str,
+        actor: AuditActor,
+    ) {
+        self.audit(AuditEntry::permission(
+            session_id, request_id, request, decision, actor,
+        ));
+    }
+
+    /// Block until all previously recorded events have been written.
+    pub fn flush(&self) {
+        let (ack_tx, ack_rx) = mpsc::channel();
+        if self.tx.send(JournalCommand::Flush(ack_tx)).is_ok() {
+            let _ = ack_rx.recv();
+        }
+    }
+
+    fn audit(&self, entry: AuditEntry) {
+        if self.tx.send(JournalCommand::Audit(entry)).is_err() {
+            tracing::debug!("Session journal writer is not running");
+        }
+    }
+
+    fn send(&self, session_id: Uuid, payload: SessionEventPayload) {
+        if self
+            .tx
+            .send(JournalCommand::Append(session_id, payload, Utc::now()))
+            .is_err()
+        {
+            tracing::debug!(%session_id, "Session journal writer is not running");
+        }
+    }
+}
+
+/// Files written by the agent in `events`, in first-touched order.
+///
+/// Uses the runners' file change events and the paths of Edit/Write tool
+/// calls, since not every agent reports file changes separately.
+pub fn touched_paths(events: &[SessionEvent]) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::new();
+    for event in events {
+        let SessionEventPayload::Agent { event } = &event.payload else {
+            continue;
+        };
+        let touched = match event {
+            AgentEvent::FileChanged(changed) => vec![changed.path.clone()],
+            AgentEvent::ToolStarted(started)
+                if matches!(
+                    canonical_tool_name(&started.tool_name).as_str(),
+                    "Edit" | "Write"
+                ) =>
+            {
+                PermissionRequest::from_tool_input(&started.tool_name, &started.arguments).paths
+            }
+            _ => continue,
+        };
+        for path in touched {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// Writes audit batches, falling back to a file when the database fails.
+struct AuditWriter {
+    store: AuditStore,
+    fallback_path: PathBuf,
+    failure: Arc<Mutex<Option<String>>>,
+}
+
+impl AuditWriter {
+    fn write(&self, entries: &[AuditEntry]) {
+        let mut attempt = 1;
+        let error = loop {
+            match self.store.append_batch(entries) {
+                Ok(()) => return,
+                Err(e) if attempt < AUDIT_WRITE_ATTEMPTS => {
+                    tracing::debug!(error = %e, attempt, "Retrying audit log write");
+                    thread::sleep(AUDIT_RETRY_DELAY * attempt);
+                    attempt += 1;
+                }
+                Err(e) => break e,
+            }
+        };
+
+        let message = match self.write_fallback(entries) {
+            Ok(()) => format!(
+                "Failed to write {} audit entries to the database ({}); saved them to {}",
+                entries.len(),
+                error,
+                self.fallback_path.display()
+            ),
+            Err(fallback_error) => format!(
+                "Failed to write {} audit entries to the database ({}) or to {} ({}); they were lost",
+                entries.len(),
+                error,
+                self.fallback_path.display(),
+                fallback_error
+            ),
+        };
+        tracing::error!("{}", message);
+        *self.failure.lock() = Some(message);
+    }
+
+    fn write_fallback(&self, entries: &[AuditEntry]) -> std::io::Result<()> {
+        if let Some(parent) = self.fallback_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.fallback_path)?
+            .write_all(lines.as_bytes())
+    }
+}
+
+fn run_writer(store: SessionEventStore, audit: AuditWriter, rx: mpsc::Receiver<JournalCommand>) {
+    let mut batch = Vec::new();
+    let mut audit_batch = Vec::new();
+    let mut acks = Vec::new();
+
+    while let Ok(first) = rx.recv() {
+        let mut next = Some(first);
+        while let Some(command) = next.take() {
+            match command {
+                JournalCommand::Append(session_id, payload, created_at) => {
+                    batch.push((session_id, payload, created_at));
+                }
+                JournalCommand::Audit(entry) => audit_batch.push(entry),
+                JournalCommand::Flush(ack) => acks.push(ack),
+            }
+            if batch.len() < MAX_BATCH && audit_batch.len() < MAX_BATCH {
+                next = rx.try_recv().ok();
+            }
+        }
+
+        if !batch.is_empty() {
+            if let Err(e) = store.append_batch(&batch) {
+                tracing::warn!(error = %e, count = batch.len(), "Failed to write session events");
+            }
+            batch.clear();
+        }
+        if !audit_batch.is_empty() {
+            audit.write(&audit_batch);
+            audit_batch.clear();
+        }
+        for ack in acks.drain(..) {
+            let _ = ack.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::events::{
+        AssistantMessageEvent, FileChangedEvent, FileOperation, ToolStartedEvent,
+    };
+    use crate::agent::PermissionReply;
+    use crate::data::{AuditKind, Database};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_flush_persists_recorded_events_in_order() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = SessionEventStore::new(db.connection());
+        let journal = SessionJournal::spawn(store.clone(), AuditStore::new(db.connection()));
+        let session_id = Uuid::new_v4();
+
+        journal.record_prompt(session_id, "hello", false, false);
+        for i in 0..(MAX_BATCH + 10) {
+            journal.record_event(
+                session_id,
+                &AgentEvent::AssistantMessage(AssistantMessageEvent {
+                    text: i.to_string(),
+                    is_final: false,
+                }),
+            );
+        }
+        journal.flush();
+
+        let events = store.get_by_session(session_id).unwrap();
+        assert_eq!(events.len(), MAX_BATCH + 11);
+        assert!(matches!(
+            events[0].payload,
+            SessionEventPayload::UserPrompt { .. }
+        ));
+        assert!(matches!(
+            &events.last().unwrap().payload,
+            SessionEventPayload::Agent { event: AgentEvent::AssistantMessage(msg) }
+                if msg.text == (MAX_BATCH + 9).to_string()
+        ));
+    }
+
+    #[test]
+    fn test_audits_tool_events_and_permission_decisions() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let audit_store = AuditStore::new(db.connection());
+        let journal =
+            SessionJournal::spawn(SessionEventStore::new(db.connection()), audit_store.clone());
+        let session_id = Uuid::new_v4();
+
+        journal.record_event(
+            session_id,
+            &AgentEvent::AssistantMessage(AssistantMessageEvent {
+                text: "working".to_string(),
+                is_final: false,
+            }),
+        );
+        journal.record_event(
+            session_id,
+            &AgentEvent::FileChanged(FileChangedEvent {
+                path: "src/lib.rs".to_string(),
+                operation: FileOperation::Update,
+            }),
+        );
+        journal.record_permission(
+            session_id,
+            "req-1",
+            &PermissionRequest::new("Bash").with_command("cargo test"),
+            PermissionReply::AlwaysAllow.as_str(),
+            AuditActor::Tui,
+        );
+        journal.flush();
+
+        let entries = audit_store
+            .list(&crate::data::AuditQuery::default())
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, AuditKind::FileChanged);
+        assert_eq!(entries[0].outcome.as_deref(), Some("update"));
+        assert_eq!(entries[1].kind, AuditKind::Permission);
+        assert_eq!(entries[1].outcome.as_deref(), Some("always_allow"));
+        assert_eq!(entries[1].actor, Some(AuditActor::Tui));
+    }
+
+    #[test]
+    fn test_failed_audit_writes_go_to_fallback_file() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        db.connection()
+            .lock()
+            .unwrap()
+            .execute("DROP TABLE audit_log", [])
+            .unwrap();
+        let fallback_path = dir.path().join("audit_fallback.jsonl");
+        let journal = SessionJournal::spawn_with_fallback(
+            SessionEventStore::new(db.connection()),
+            AuditStore::new(db.connection()),
+            fallback_path.clone(),
+        );
+        let session_id = Uuid::new_v4();
+
+        journal.record_event(
+            session_id,
+            &AgentEvent::FileChanged(FileChangedEvent {
+                path: "src/lib.rs".to_string(),
+                operation: FileOperation::Update,
+            }),
+        );
+        journal.flush();
+
+        let saved = std::fs::read_to_string(&fallback_path).unwrap();
+        let entry: AuditEntry = serde_json::from_str(saved.lines().next().unwrap()).unwrap();
+        assert_eq!(entry.kind, AuditKind::FileChanged);
+        assert_eq!(entry.session_id, session_id);
+
+        let failure = journal.take_audit_failure().unwrap();
+        assert!(failure.contains("saved them to"));
+        assert!(journal.take_audit_failure().is_none());
+    }
+
+    #[test]
+    fn test_touched_paths_of_last_turn() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = SessionEventStore::new(db.connection());
+        let journal = SessionJournal::spawn(store.clone(), AuditStore::new(db.connection()));
+        let session_id = Uuid::new_v4();
+        let file_changed = |path: &str| {
+            AgentEvent::FileChanged(FileChangedEvent {
+                path: path.to_string(),
+                operation: FileOperation::Update,
+            })
+        };
+
+        journal.record_prompt(session_id, "first", false, false);
+        journal.record_event(session_id, &file_changed("old.rs"));
+        journal.record_prompt(session_id, "second", false, false);
+        journal.record_event(
+            session_id,
+            &AgentEvent::ToolStarted(ToolStartedEvent {
+                tool_name: "Write".to_string(),
+                tool_id: "t1".to_string(),
+                arguments: serde_json::json!({"file_path": "/repo/new.rs"}),
+            }),
+        );
+        journal.record_event(
+            session_id,
+            &AgentEvent::ToolStarted(ToolStartedEvent {
+                tool_name: "Read".to_string(),
+                tool_id: "t2".to_string(),
+                arguments: serde_json::json!({"file_path": "/repo/read.rs"}),
+            }),
+        );
+        journal.record_event(session_id, &file_changed("src/lib.rs"));
+        journal.record_event(session_id, &file_changed("src/lib.rs"));
+        journal.flush();
+
+        let events = store.get_last_turn(session_id).unwrap();
+        assert!(matches!(
+            &events[0].payload,
+            SessionEventPayload::UserPrompt { text, .. } if text == "second"
+        ));
+        assert_eq!(touched_paths(&events), vec!["/repo/new.rs", "src/lib.rs"]);
+    }
+}
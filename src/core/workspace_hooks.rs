@@ -0,0 +1,505 @@
+//! Workspace lifecycle hooks.
+//!
+//! Hooks are shell commands run when a workspace is created (`on_create`),
+//! before its checkout is removed (`before_archive`) and after it is gone
+//! (`after_archive`), plus files copied from the repository into new
+//! workspaces. They come from the config's `[hooks]` section, its
+//! `[hooks.repos.<name>]` overrides and the repository's `.conduit.toml`, each
+//! layer replacing the fields it sets.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Component, Path};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, HookSet};
+use crate::data::Repository;
+
+/// Repository-local config file read for hooks
+pub const REPO_CONFIG_FILE: &str = ".conduit.toml";
+
+/// Output lines kept in a hook report
+const OUTPUT_TAIL_LINES: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    OnCreate,
+    BeforeArchive,
+    AfterArchive,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::OnCreate => "on_create",
+            HookEvent::BeforeArchive => "before_archive",
+            HookEvent::AfterArchive => "after_archive",
+        }
+    }
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Resolved hooks for one repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceHooks {
+    pub on_create: Vec<String>,
+    pub before_archive: Vec<String>,
+    pub after_archive: Vec<String>,
+    /// Repository-relative paths copied into new workspaces
+    pub copy: Vec<String>,
+    /// Time limit for each command
+    pub timeout: Duration,
+}
+
+impl Default for WorkspaceHooks {
+    fn default() -> Self {
+        Self {
+            on_create: Vec::new(),
+            before_archive: Vec::new(),
+            after_archive: Vec::new(),
+            copy: Vec::new(),
+            timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+impl WorkspaceHooks {
+    pub fn commands(&self, event: HookEvent) -> &[String] {
+        match event {
+            HookEvent::OnCreate => &self.on_create,
+            HookEvent::BeforeArchive => &self.before_archive,
+            HookEvent::AfterArchive => &self.after_archive,
+        }
+    }
+
+    /// Whether anything runs for the event
+    pub fn has(&self, event: HookEvent) -> bool {
+        !self.commands(event).is_empty() || (event == HookEvent::OnCreate && !self.copy.is_empty())
+    }
+
+    fn apply(&mut self, set: &HookSet) {
+        if let Some(on_create) = &set.on_create {
+            self.on_create = on_create.clone();
+        }
+        if let Some(before_archive) = &set.before_archive {
+            self.before_archive = before_archive.clone();
+        }
+        if let Some(after_archive) = &set.after_archive {
+            self.after_archive = after_archive.clone();
+        }
+        if let Some(copy) = &set.copy {
+            self.copy = copy.clone();
+        }
+    }
+}
+
+/// The workspace a hook runs for
+#[derive(Debug, Clone, Copy)]
+pub struct HookContext<'a> {
+    pub workspace_name: &'a str,
+    pub branch: &'a str,
+    pub workspace_path: &'a Path,
+    pub repo_path: &'a Path,
+}
+
+/// The step that stopped a hook
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HookFailure {
+    /// Command or copy rule that failed
+    pub step: String,
+    pub message: String,
+}
+
+/// Outcome of running one event's hooks
+#[derive(Debug, Clone, Serialize)]
+pub struct HookReport {
+    pub event: HookEvent,
+    /// Last lines of output
+    pub output: Vec<String>,
+    pub failure: Option<HookFailure>,
+}
+
+impl HookReport {
+    pub fn succeeded(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    /// One-line description of the failure
+    pub fn failure_summary(&self) -> Option<String> {
+        self.failure
+            .as_ref()
+            .map(|failure| format!("{} hook `{}` {}", self.event, failure.step, failure.message))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RepoConfigFile {
+    hooks: Option<HookSet>,
+}
+
+/// Resolve a repository's hooks from the config and its `.conduit.toml`.
+pub fn resolve_workspace_hooks(config: &Config, repo: &Repository) -> WorkspaceHooks {
+    let mut hooks = WorkspaceHooks {
+        timeout: Duration::from_secs(config.hooks.timeout_secs),
+        ..WorkspaceHooks::default()
+    };
+    hooks.apply(&config.hooks.defaults);
+    if let Some(set) = config.hooks.repos.get(&repo.name) {
+        hooks.apply(set);
+    }
+    if config.hooks.repo_config {
+        if let Some(set) = repo.base_path.as_deref().and_then(read_repo_hooks) {
+            hooks.apply(&set);
+        }
+    }
+    hooks
+}
+
+fn read_repo_hooks(repo_path: &Path) -> Option<HookSet> {
+    let path = repo_path.join(REPO_CONFIG_FILE);
+    let contents = std::fs::read_to_string(&path).ok()?;
+    match toml::from_str::<RepoConfigFile>(&contents) {
+        Ok(file) => file.hooks,
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "Ignoring invalid repository hooks");
+            None
+        }
+    }
+}
+
+/// Run an event's hooks, passing each output line to `on_line` as it arrives.
+///
+/// `on_create` first copies the configured files, then every event runs its
+/// commands in order and stops at the first failure. Blocks until done.
+pub fn run_workspace_hooks(
+    hooks: &WorkspaceHooks,
+    event: HookEvent,
+    context: HookContext<'_>,
+    on_line: &mut dyn FnMut(&str),
+) -> HookReport {
+    let mut output = VecDeque::new();
+    let mut emit = |line: &str| {
+        on_line(line);
+        if output.len() == OUTPUT_TAIL_LINES {
+            output.pop_front();
+        }
+        output.push_back(line.to_string());
+    };
+
+    let mut failure = None;
+    if event == HookEvent::OnCreate {
+        for rule in &hooks.copy {
+            match copy_into_workspace(rule, context.repo_path, context.workspace_path) {
+                Ok(true) => emit(&format!("Copied {}", rule)),
+                Ok(false) => emit(&format!("Skipped {} (missing or already present)", rule)),
+                Err(message) => {
+                    failure = Some(HookFailure {
+                        step: format!("copy {}", rule),
+                        message,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+
+    // The workspace is gone once it has been archived
+    let working_dir = match event {
+        HookEvent::AfterArchive => context.repo_path,
+        HookEvent::OnCreate | HookEvent::BeforeArchive => context.workspace_path,
+    };
+    if failure.is_none() {
+        for command in hooks.commands(event) {
+            emit(&format!("$ {}", command));
+            if let Err(message) = run_command(
+                command,
+                event,
+                context,
+                working_dir,
+                hooks.timeout,
+                &mut emit,
+            ) {
+                emit(&message);
+                failure = Some(HookFailure {
+                    step: command.clone(),
+                    message,
+                });
+                break;
+            }
+        }
+    }
+
+    HookReport {
+        event,
+        output: output.into(),
+        failure,
+    }
+}
+
+/// Copy a repository path into the workspace unless it is missing or already there.
+fn copy_into_workspace(
+    rule: &str,
+    repo_path: &Path,
+    workspace_path: &Path,
+) -> Result<bool, String> {
+    let relative = Path::new(rule);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err("must be a path inside the repository".to_string());
+    }
+    let source = repo_path.join(relative);
+    let target = workspace_path.join(relative);
+    if !source.exists() || target.exists() {
+        return Ok(false);
+    }
+    copy_path(&source, &target).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+fn copy_path(source: &Path, target: &Path) -> std::io::Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if source.is_dir() {
+        std::fs::create_dir_all(target)?;
+        for entry in std::fs::read_dir(source)? {
+            let entry = entry?;
+            copy_path(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        std::fs::copy(source, target).map(|_| ())
+    }
+}
+
+fn run_command(
+    command: &str,
+    event: HookEvent,
+    context: HookContext<'_>,
+    working_dir: &Path,
+    timeout: Duration,
+    emit: &mut dyn FnMut(&str),
+) -> Result<(), String> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut child = Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .current_dir(working_dir)
+        .env("CONDUIT_HOOK", event.as_str())
+        .env("CONDUIT_WORKSPACE_NAME", context.workspace_name)
+        .env("CONDUIT_WORKSPACE_PATH", context.workspace_path)
+        .env("CONDUIT_BRANCH", context.branch)
+        .env("CONDUIT_REPO_PATH", context.repo_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to start: {}", e))?;
+
+    let (line_tx, line_rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, line_tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, line_tx);
+    }
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match line_rx.recv_timeout(remaining) {
+            Ok(line) => emit(&line),
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if let Err(e) = child.kill() {
+                    tracing::debug!(error = %e, command, "Failed to kill timed out hook");
+                }
+                let _ = child.wait();
+                return Err(format!("timed out after {}s", timeout.as_secs()));
+            }
+        }
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("failed to wait for command: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(match status.code() {
+            Some(code) => format!("exited with status {}", code),
+            None => "was terminated by a signal".to_string(),
+        })
+    }
+}
+
+fn forward_lines(stream: impl Read + Send + 'static, line_tx: mpsc::Sender<String>) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buf);
+                    if line_tx.send(line.trim_end().to_string()).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn repository(name: &str, base_path: &Path) -> Repository {
+        Repository::from_local_path(name, base_path.to_path_buf())
+    }
+
+    fn commands(list: &[&str]) -> Option<Vec<String>> {
+        Some(list.iter().map(|command| command.to_string()).collect())
+    }
+
+    #[test]
+    fn test_resolve_layers_config_and_repo_file() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(REPO_CONFIG_FILE),
+            "[hooks]\non_create = [\"make setup\"]\n",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        // Repository hooks only run once enabled
+        let hooks = resolve_workspace_hooks(&config, &repository("app", dir.path()));
+        assert!(hooks.on_create.is_empty());
+
+        config.hooks.repo_config = true;
+        config.hooks.defaults = HookSet {
+            on_create: commands(&["npm ci"]),
+            after_archive: commands(&["echo done"]),
+            ..HookSet::default()
+        };
+        config.hooks.repos.insert(
+            "app".to_string(),
+            HookSet {
+                before_archive: commands(&["npm run clean"]),
+                copy: commands(&[".env"]),
+                ..HookSet::default()
+            },
+        );
+
+        let hooks = resolve_workspace_hooks(&config, &repository("app", dir.path()));
+        assert_eq!(hooks.on_create, vec!["make setup"]);
+        assert_eq!(hooks.before_archive, vec!["npm run clean"]);
+        assert_eq!(hooks.after_archive, vec!["echo done"]);
+        assert_eq!(hooks.copy, vec![".env"]);
+
+        config.hooks.repo_config = false;
+        let hooks = resolve_workspace_hooks(&config, &repository("other", dir.path()));
+        assert_eq!(hooks.on_create, vec!["npm ci"]);
+        assert!(hooks.before_archive.is_empty());
+    }
+
+    #[test]
+    fn test_on_create_copies_files_and_streams_output() {
+        let dir = tempdir().unwrap();
+        let repo_path = dir.path().join("repo");
+        let workspace_path = dir.path().join("workspace");
+        std::fs::create_dir_all(repo_path.join("config")).unwrap();
+        std::fs::create_dir_all(&workspace_path).unwrap();
+        std::fs::write(repo_path.join(".env"), "SECRET=1").unwrap();
+        std::fs::write(repo_path.join("config/local.yml"), "debug: true").unwrap();
+
+        let hooks = WorkspaceHooks {
+            on_create: vec!["echo \"setting up $CONDUIT_WORKSPACE_NAME\"".to_string()],
+            copy: vec![
+                ".env".to_string(),
+                "config".to_string(),
+                "missing".to_string(),
+            ],
+            ..WorkspaceHooks::default()
+        };
+        let context = HookContext {
+            workspace_name: "brave-otter",
+            branch: "me/brave-otter",
+            workspace_path: &workspace_path,
+            repo_path: &repo_path,
+        };
+        let mut streamed = Vec::new();
+        let report = run_workspace_hooks(&hooks, HookEvent::OnCreate, context, &mut |line| {
+            streamed.push(line.to_string())
+        });
+
+        assert!(report.succeeded());
+        assert_eq!(
+            std::fs::read_to_string(workspace_path.join(".env")).unwrap(),
+            "SECRET=1"
+        );
+        assert!(workspace_path.join("config/local.yml").exists());
+        assert!(streamed.contains(&"setting up brave-otter".to_string()));
+        assert_eq!(report.output, streamed);
+    }
+
+    #[test]
+    fn test_failure_stops_remaining_commands() {
+        let dir = tempdir().unwrap();
+        let hooks = WorkspaceHooks {
+            before_archive: vec![
+                "echo first".to_string(),
+                "exit 3".to_string(),
+                "touch should-not-exist".to_string(),
+            ],
+            ..WorkspaceHooks::default()
+        };
+        let context = HookContext {
+            workspace_name: "ws",
+            branch: "ws",
+            workspace_path: dir.path(),
+            repo_path: dir.path(),
+        };
+        let report = run_workspace_hooks(&hooks, HookEvent::BeforeArchive, context, &mut |_| {});
+
+        assert_eq!(
+            report.failure,
+            Some(HookFailure {
+                step: "exit 3".to_string(),
+                message: "exited with status 3".to_string(),
+            })
+        );
+        assert!(!dir.path().join("should-not-exist").exists());
+        assert_eq!(
+            report.failure_summary().unwrap(),
+            "before_archive hook `exit 3` exited with status 3"
+        );
+    }
+
+    #[test]
+    fn test_copy_rejects_paths_outside_repository() {
+        let dir = tempdir().unwrap();
+        let result = copy_into_workspace("../secrets", dir.path(), dir.path());
+        assert!(result.is_err());
+    }
+}
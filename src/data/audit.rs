@@ -0,0 +1,240 @@
+//! Audit log data access object
+//!
+//! The `audit_log` table is append-only: triggers reject updates and deletes,
+//! so entries outlive the sessions and workspaces they describe.
+
+use super::models::{AuditActor, AuditEntry, AuditKind, AuditQuery};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult, Row};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for the audit log
+#[derive(Clone)]
+pub struct AuditStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl AuditStore {
+    /// Create a new AuditStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Append entries in a single transaction (preserving order).
+    ///
+    /// Entries without a workspace take the workspace of their session tab.
+    pub fn append_batch(&self, entries: &[AuditEntry]) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for entry in entries {
+            Self::insert_with_conn(&tx, entry)?;
+        }
+        tx.commit()
+    }
+
+    /// Entries matching `query`, oldest first. With a limit, the most recent entries are kept.
+    pub fn list(&self, query: &AuditQuery) -> SqliteResult<Vec<AuditEntry>> {
+        let limit = query
+            .limit
+            .map(|limit| i64::try_from(limit).unwrap_or(i64::MAX))
+            .unwrap_or(-1);
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM (
+                SELECT id, session_id, workspace_id, kind, tool, tool_id, target, outcome, actor,
+                    details, recorded_at
+                FROM audit_log
+                WHERE (?1 IS NULL OR session_id = ?1)
+                    AND (?2 IS NULL OR workspace_id = ?2)
+                    AND (?3 IS NULL OR kind = ?3)
+                    AND (?4 IS NULL OR recorded_at >= ?4)
+                ORDER BY id DESC
+                LIMIT ?5
+             ) ORDER BY id",
+        )?;
+        let entries = stmt
+            .query_map(
+                params![
+                    query.session_id.map(|id| id.to_string()),
+                    query.workspace_id.map(|id| id.to_string()),
+                    query.kind.map(|kind| kind.as_str()),
+                    query.since.map(|since| since.to_rfc3339()),
+                    limit,
+                ],
+                Self::row_to_entry,
+            )?
+            .filter_map(|r| r.ok().flatten())
+            .collect();
+        Ok(entries)
+    }
+
+    fn insert_with_conn(conn: &Connection, entry: &AuditEntry) -> SqliteResult<()> {
+        let details = serde_json::to_string(&entry.details)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO audit_log (session_id, workspace_id, kind, tool, tool_id, target, outcome,
+                actor, details, recorded_at)
+             VALUES (?1, COALESCE(?2, (SELECT workspace_id FROM session_tabs WHERE id = ?1)),
+                ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                entry.session_id.to_string(),
+                entry.workspace_id.map(|id| id.to_string()),
+                entry.kind.as_str(),
+                entry.tool,
+                entry.tool_id,
+                entry.target,
+                entry.outcome,
+                entry.actor.map(|actor| actor.as_str()),
+                details,
+                entry.recorded_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Convert a database row to an AuditEntry.
+    ///
+    /// Rows with an unknown kind (e.g. written by a newer version) are skipped.
+    fn row_to_entry(row: &Row) -> SqliteResult<Option<AuditEntry>> {
+        let kind: String = row.get(3)?;
+        let Some(kind) = AuditKind::parse(&kind) else {
+            return Ok(None);
+        };
+        let session_id: String = row.get(1)?;
+        let workspace_id: Option<String> = row.get(2)?;
+        let actor: Option<String> = row.get(8)?;
+        let details: String = row.get(9)?;
+        let recorded_at: String = row.get(10)?;
+
+        Ok(Some(AuditEntry {
+            id: row.get(0)?,
+            session_id: Uuid::parse_str(&session_id).unwrap_or_else(|_| Uuid::nil()),
+            workspace_id: workspace_id.and_then(|id| Uuid::parse_str(&id).ok()),
+            kind,
+            tool: row.get(4)?,
+            tool_id: row.get(5)?,
+            target: row.get(6)?,
+            outcome: row.get(7)?,
+            actor: actor.as_deref().and_then(AuditActor::parse),
+            details: serde_json::from_str(&details).unwrap_or_default(),
+            recorded_at: DateTime::parse_from_rfc3339(&recorded_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::events::{CommandOutputEvent, ToolStartedEvent};
+    use crate::agent::{AgentEvent, AgentType, PermissionRequest};
+    use crate::data::{
+        Database, Repository, RepositoryStore, SessionTab, SessionTabStore, Workspace,
+        WorkspaceStore,
+    };
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn setup_db() -> (tempfile::TempDir, Database, AuditStore) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = AuditStore::new(db.connection());
+        (dir, db, store)
+    }
+
+    #[test]
+    fn test_append_and_filter_entries() {
+        let (_dir, db, store) = setup_db();
+        let repo = Repository::from_local_path("conduit", "/tmp/conduit".into());
+        RepositoryStore::new(db.connection()).create(&repo).unwrap();
+        let workspace = Workspace::new(repo.id, "audit", "audit", "/tmp/conduit-audit".into());
+        WorkspaceStore::new(db.connection())
+            .create(&workspace)
+            .unwrap();
+        let workspace_id = workspace.id;
+        let tab = SessionTab::new(0, AgentType::Claude, Some(workspace_id), None, None, None);
+        SessionTabStore::new(db.connection()).create(&tab).unwrap();
+        let other_session = Uuid::new_v4();
+
+        let started = AuditEntry::from_event(
+            tab.id,
+            &AgentEvent::ToolStarted(ToolStartedEvent {
+                tool_name: "Bash".to_string(),
+                tool_id: "tool-1".to_string(),
+                arguments: json!({ "command": "cargo test" }),
+            }),
+        )
+        .unwrap();
+        let command = AuditEntry::from_event(
+            other_session,
+            &AgentEvent::CommandOutput(CommandOutputEvent {
+                command: "ls".to_string(),
+                output: "x".repeat(AuditEntry::MAX_OUTPUT_LEN * 2),
+                exit_code: Some(2),
+                is_streaming: false,
+            }),
+        )
+        .unwrap();
+        let permission = AuditEntry::permission(
+            tab.id,
+            "req-1",
+            &PermissionRequest::new("Bash").with_command("rm -rf target"),
+            "deny",
+            AuditActor::Web,
+        );
+        store.append_batch(&[started, command, permission]).unwrap();
+
+        let all = store.list(&AuditQuery::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].kind, AuditKind::ToolStarted);
+        assert_eq!(all[0].target.as_deref(), Some("cargo test"));
+        assert_eq!(all[0].workspace_id, Some(workspace_id));
+        assert_eq!(all[1].workspace_id, None);
+        assert_eq!(all[1].outcome.as_deref(), Some("2"));
+        assert!(all[1].details["output"].as_str().unwrap().len() < AuditEntry::MAX_OUTPUT_LEN + 32);
+        assert_eq!(all[2].actor, Some(AuditActor::Web));
+
+        let by_workspace = store
+            .list(&AuditQuery {
+                workspace_id: Some(workspace_id),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_workspace.len(), 2);
+
+        let latest = store
+            .list(&AuditQuery {
+                session_id: Some(tab.id),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].kind, AuditKind::Permission);
+        assert_eq!(latest[0].outcome.as_deref(), Some("deny"));
+    }
+
+    #[test]
+    fn test_entries_cannot_be_modified() {
+        let (_dir, db, store) = setup_db();
+        let entry = AuditEntry::permission(
+            Uuid::new_v4(),
+            "req-1",
+            &PermissionRequest::new("Edit").with_path("src/main.rs"),
+            "allow_once",
+            AuditActor::Tui,
+        );
+        store.append_batch(&[entry]).unwrap();
+
+        let conn = db.connection();
+        let conn = conn.lock().unwrap();
+        assert!(conn
+            .execute("UPDATE audit_log SET outcome = 'deny'", [])
+            .is_err());
+        assert!(conn.execute("DELETE FROM audit_log", []).is_err());
+        drop(conn);
+        assert_eq!(store.list(&AuditQuery::default()).unwrap().len(), 1);
+    }
+}
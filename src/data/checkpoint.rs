@@ -0,0 +1,149 @@
+//! Checkpoint data access object
+
+use super::models::Checkpoint;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult, Row};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for per-turn workspace checkpoints
+#[derive(Clone)]
+pub struct CheckpointStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl CheckpointStore {
+    /// Create a new CheckpointStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Turn number the next checkpoint of a session gets
+    pub fn next_turn(&self, session_id: Uuid) -> SqliteResult<u32> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT COALESCE(MAX(turn), 0) + 1 FROM checkpoints WHERE session_id = ?1",
+            params![session_id.to_string()],
+            |row| row.get(0),
+        )
+    }
+
+    /// Insert a checkpoint, returning its row ID
+    pub fn insert(&self, checkpoint: &Checkpoint) -> SqliteResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO checkpoints (session_id, workspace_id, turn, commit_sha, ref_name,
+                created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                checkpoint.session_id.to_string(),
+                checkpoint.workspace_id.map(|id| id.to_string()),
+                checkpoint.turn,
+                checkpoint.commit_sha,
+                checkpoint.ref_name,
+                checkpoint.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Checkpoints of a session, oldest turn first
+    pub fn list_by_session(&self, session_id: Uuid) -> SqliteResult<Vec<Checkpoint>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, workspace_id, turn, commit_sha, ref_name, created_at
+             FROM checkpoints WHERE session_id = ?1 ORDER BY turn",
+        )?;
+        let checkpoints = stmt
+            .query_map(params![session_id.to_string()], Self::row_to_checkpoint)?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(checkpoints)
+    }
+
+    /// The checkpoint taken before `turn` (or before the latest turn)
+    pub fn get(&self, session_id: Uuid, turn: Option<u32>) -> SqliteResult<Option<Checkpoint>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT id, session_id, workspace_id, turn, commit_sha, ref_name, created_at
+             FROM checkpoints
+             WHERE session_id = ?1 AND (?2 IS NULL OR turn = ?2)
+             ORDER BY turn DESC LIMIT 1",
+            params![session_id.to_string(), turn],
+            Self::row_to_checkpoint,
+        )
+        .optional()
+    }
+
+    /// Delete a session's checkpoints from `turn` on
+    pub fn delete_from_turn(&self, session_id: Uuid, turn: u32) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM checkpoints WHERE session_id = ?1 AND turn >= ?2",
+            params![session_id.to_string(), turn],
+        )?;
+        Ok(())
+    }
+
+    fn row_to_checkpoint(row: &Row) -> SqliteResult<Checkpoint> {
+        let session_id: String = row.get(1)?;
+        let workspace_id: Option<String> = row.get(2)?;
+        let created_at: String = row.get(6)?;
+        Ok(Checkpoint {
+            id: row.get(0)?,
+            session_id: Uuid::parse_str(&session_id).unwrap_or_else(|_| Uuid::nil()),
+            workspace_id: workspace_id.and_then(|id| Uuid::parse_str(&id).ok()),
+            turn: row.get(3)?,
+            commit_sha: row.get(4)?,
+            ref_name: row.get(5)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    fn checkpoint(session_id: Uuid, turn: u32) -> Checkpoint {
+        Checkpoint {
+            id: 0,
+            session_id,
+            workspace_id: None,
+            turn,
+            commit_sha: format!("sha{}", turn),
+            ref_name: format!("refs/conduit/checkpoints/{}/{}", session_id, turn),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_turns_get_and_delete() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = CheckpointStore::new(db.connection());
+        let session_id = Uuid::new_v4();
+
+        assert_eq!(store.next_turn(session_id).unwrap(), 1);
+        for turn in 1..=3 {
+            store.insert(&checkpoint(session_id, turn)).unwrap();
+        }
+        store.insert(&checkpoint(Uuid::new_v4(), 1)).unwrap();
+        assert_eq!(store.next_turn(session_id).unwrap(), 4);
+
+        assert_eq!(store.get(session_id, None).unwrap().unwrap().turn, 3);
+        assert_eq!(
+            store.get(session_id, Some(2)).unwrap().unwrap().commit_sha,
+            "sha2"
+        );
+        assert!(store.get(session_id, Some(9)).unwrap().is_none());
+
+        store.delete_from_turn(session_id, 2).unwrap();
+        let remaining = store.list_by_session(session_id).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].turn, 1);
+    }
+}
@@ -23,8 +23,8 @@ impl ForkSeedStore {
     pub fn create(&self, seed: &ForkSeed) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "INSERT INTO fork_seeds (id, agent_type, parent_session_id, parent_workspace_id, created_at, seed_prompt_hash, seed_prompt_path, token_estimate, context_window, seed_ack_filtered)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO fork_seeds (id, agent_type, parent_session_id, parent_workspace_id, created_at, seed_prompt_hash, seed_prompt_path, token_estimate, context_window, seed_ack_filtered, parent_agent_type)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 seed.id.to_string(),
                 seed.agent_type.as_str(),
@@ -36,6 +36,7 @@ impl ForkSeedStore {
                 seed.token_estimate,
                 seed.context_window,
                 if seed.seed_ack_filtered { 1 } else { 0 },
+                seed.parent_agent_type.map(|agent_type| agent_type.as_str()),
             ],
         )?;
         Ok(())
@@ -45,7 +46,7 @@ impl ForkSeedStore {
     pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<ForkSeed>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, agent_type, parent_session_id, parent_workspace_id, created_at, seed_prompt_hash, seed_prompt_path, token_estimate, context_window, seed_ack_filtered
+            "SELECT id, agent_type, parent_session_id, parent_workspace_id, created_at, seed_prompt_hash, seed_prompt_path, token_estimate, context_window, seed_ack_filtered, parent_agent_type
              FROM fork_seeds WHERE id = ?1",
         )?;
 
@@ -74,6 +75,7 @@ impl ForkSeedStore {
         let parent_workspace_id_str: Option<String> = row.get(3)?;
         let created_at_str: String = row.get(4)?;
         let seed_ack_filtered: i64 = row.get(9)?;
+        let parent_agent_type_str: Option<String> = row.get(10)?;
 
         Ok(ForkSeed {
             id: Uuid::parse_str(&id_str).unwrap_or_else(|e| {
@@ -81,6 +83,7 @@ impl ForkSeedStore {
                 Uuid::new_v4()
             }),
             agent_type: AgentType::parse(&agent_type_str),
+            parent_agent_type: parent_agent_type_str.as_deref().map(AgentType::parse),
             parent_session_id: row.get(2)?,
             parent_workspace_id: parent_workspace_id_str.and_then(|s| {
                 Uuid::parse_str(&s)
@@ -200,6 +200,211 @@ pub const MIGRATIONS: &[Migration] = &[
         name: "add_session_tabs_model_invalid",
         sql: "ALTER TABLE session_tabs ADD COLUMN model_invalid INTEGER NOT NULL DEFAULT 0;",
     },
+    // ============================================================
+    // Session event journal (v20)
+    // ============================================================
+    Migration {
+        version: 20,
+        name: "create_session_events_table",
+        sql: r#"
+            CREATE TABLE IF NOT EXISTS session_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_session_events_session
+                ON session_events(session_id, id);
+        "#,
+    },
+    // ============================================================
+    // Full-text search index (v21)
+    // ============================================================
+    Migration {
+        version: 21,
+        name: "create_search_index",
+        sql: r#"
+            CREATE TABLE IF NOT EXISTS search_documents (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                source TEXT NOT NULL,
+                source_key TEXT NOT NULL,
+                agent_type TEXT NOT NULL,
+                title TEXT NOT NULL,
+                project TEXT,
+                file_path TEXT,
+                fingerprint TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                UNIQUE(source, source_key)
+            );
+            CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+                content,
+                role UNINDEXED,
+                message_index UNINDEXED,
+                document_id UNINDEXED,
+                tokenize = 'unicode61 remove_diacritics 2'
+            );
+        "#,
+    },
+    // ============================================================
+    // Per-turn usage and cost (v22)
+    // ============================================================
+    Migration {
+        version: 22,
+        name: "create_usage_records_table",
+        sql: r#"
+            CREATE TABLE IF NOT EXISTS usage_records (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                workspace_id TEXT,
+                repository_id TEXT,
+                agent_type TEXT NOT NULL,
+                model TEXT,
+                input_tokens INTEGER NOT NULL,
+                output_tokens INTEGER NOT NULL,
+                cached_tokens INTEGER NOT NULL,
+                cache_write_tokens INTEGER NOT NULL,
+                cost_usd REAL NOT NULL,
+                recorded_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_usage_records_session
+                ON usage_records(session_id);
+            CREATE INDEX IF NOT EXISTS idx_usage_records_recorded_at
+                ON usage_records(recorded_at);
+        "#,
+    },
+    Migration {
+        version: 23,
+        name: "add_usage_records_duration",
+        sql: "ALTER TABLE usage_records ADD COLUMN duration_ms INTEGER;",
+    },
+    // ============================================================
+    // Append-only audit log (v24)
+    // ============================================================
+    Migration {
+        version: 24,
+        name: "create_audit_log_table",
+        sql: r#"
+            CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                workspace_id TEXT,
+                kind TEXT NOT NULL,
+                tool TEXT,
+                tool_id TEXT,
+                target TEXT,
+                outcome TEXT,
+                actor TEXT,
+                details TEXT NOT NULL,
+                recorded_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_audit_log_session
+                ON audit_log(session_id);
+            CREATE INDEX IF NOT EXISTS idx_audit_log_workspace
+                ON audit_log(workspace_id);
+            CREATE INDEX IF NOT EXISTS idx_audit_log_recorded_at
+                ON audit_log(recorded_at);
+            CREATE TRIGGER IF NOT EXISTS audit_log_no_update
+                BEFORE UPDATE ON audit_log
+                BEGIN SELECT RAISE(ABORT, 'audit log is append-only'); END;
+            CREATE TRIGGER IF NOT EXISTS audit_log_no_delete
+                BEFORE DELETE ON audit_log
+                BEGIN SELECT RAISE(ABORT, 'audit log is append-only'); END;
+        "#,
+    },
+    Migration {
+        version: 25,
+        name: "add_fork_seeds_parent_agent_type",
+        sql: "ALTER TABLE fork_seeds ADD COLUMN parent_agent_type TEXT;",
+    },
+    // ============================================================
+    // Per-turn workspace checkpoints (v26)
+    // ============================================================
+    Migration {
+        version: 26,
+        name: "create_checkpoints_table",
+        sql: r#"
+            CREATE TABLE IF NOT EXISTS checkpoints (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                workspace_id TEXT,
+                turn INTEGER NOT NULL,
+                commit_sha TEXT NOT NULL,
+                ref_name TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                UNIQUE(session_id, turn)
+            );
+            CREATE INDEX IF NOT EXISTS idx_checkpoints_session
+                ON checkpoints(session_id);
+        "#,
+    },
+    // ============================================================
+    // Multi-agent races (v27)
+    // ============================================================
+    Migration {
+        version: 27,
+        name: "create_races_tables",
+        sql: r#"
+            CREATE TABLE IF NOT EXISTS races (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                prompt TEXT NOT NULL,
+                base_branch TEXT NOT NULL,
+                base_commit TEXT NOT NULL,
+                winner_workspace_id TEXT,
+                created_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS race_entries (
+                race_id TEXT NOT NULL REFERENCES races(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL,
+                workspace_id TEXT NOT NULL,
+                agent_type TEXT NOT NULL,
+                model TEXT,
+                test_passed INTEGER,
+                test_output TEXT,
+                PRIMARY KEY (race_id, position)
+            );
+            CREATE INDEX IF NOT EXISTS idx_race_entries_workspace
+                ON race_entries(workspace_id);
+        "#,
+    },
+    // ============================================================
+    // Addressed PR review comments (v28)
+    // ============================================================
+    Migration {
+        version: 28,
+        name: "create_addressed_review_comments_table",
+        sql: r#"
+            CREATE TABLE IF NOT EXISTS addressed_review_comments (
+                workspace_id TEXT NOT NULL,
+                comment_id TEXT NOT NULL,
+                addressed_at TEXT NOT NULL,
+                PRIMARY KEY (workspace_id, comment_id)
+            );
+        "#,
+    },
+    // ============================================================
+    // Per-repository base branch sync strategy (v29)
+    // ============================================================
+    Migration {
+        version: 29,
+        name: "add_repositories_sync_strategy",
+        sql: "ALTER TABLE repositories ADD COLUMN sync_strategy TEXT;",
+    },
+    // ============================================================
+    // Drop journaled events with their session (v30)
+    // ============================================================
+    Migration {
+        version: 30,
+        name: "create_session_events_cleanup_trigger",
+        sql: r#"
+            CREATE TRIGGER IF NOT EXISTS session_tabs_delete_events
+            AFTER DELETE ON session_tabs
+            BEGIN
+                DELETE FROM session_events WHERE session_id = OLD.id;
+            END;
+        "#,
+    },
 ];
 
 /// Create the schema_migrations table if it doesn't exist.
@@ -293,6 +498,16 @@ fn bootstrap_existing_database(conn: &Connection) -> rusqlite::Result<()> {
             17 => column_exists(conn, "repositories", "workspace_mode")?,
             18 => index_exists(conn, "idx_session_tabs_open_workspace")?,
             19 => column_exists(conn, "session_tabs", "model_invalid")?,
+            20 => table_exists(conn, "session_events")?,
+            21 => table_exists(conn, "search_documents")?,
+            22 => table_exists(conn, "usage_records")?,
+            23 => column_exists(conn, "usage_records", "duration_ms")?,
+            24 => table_exists(conn, "audit_log")?,
+            25 => column_exists(conn, "fork_seeds", "parent_agent_type")?,
+            26 => table_exists(conn, "checkpoints")?,
+            27 => table_exists(conn, "races")? && table_exists(conn, "race_entries")?,
+            28 => table_exists(conn, "addressed_review_comments")?,
+            29 => column_exists(conn, "repositories", "sync_strategy")?,
             _ => false,
         };
 
@@ -388,7 +603,6 @@ pub fn run_migrations(conn: &mut Connection) -> rusqlite::Result<()> {
     Ok(())
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -434,6 +648,12 @@ mod tests {
         assert!(table_exists(&conn, "workspaces").unwrap());
         assert!(table_exists(&conn, "session_tabs").unwrap());
         assert!(table_exists(&conn, "fork_seeds").unwrap());
+        assert!(table_exists(&conn, "session_events").unwrap());
+        assert!(table_exists(&conn, "search_documents").unwrap());
+        assert!(table_exists(&conn, "search_index").unwrap());
+        assert!(table_exists(&conn, "checkpoints").unwrap());
+        assert!(table_exists(&conn, "races").unwrap());
+        assert!(table_exists(&conn, "race_entries").unwrap());
         assert!(table_exists(&conn, "schema_migrations").unwrap());
     }
 
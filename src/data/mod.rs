@@ -3,21 +3,39 @@
 //! This module provides SQLite-based storage for repositories and workspaces.
 
 mod app_state;
+mod audit;
+mod checkpoint;
 mod database;
 mod fork_seed;
 mod migrations;
 mod models;
+mod race;
 mod repository;
+mod review_comment;
+mod search_index;
+mod session_event;
 mod session_tab;
+mod usage;
 mod workspace;
 
 pub use app_state::AppStateStore;
+pub use audit::AuditStore;
+pub use checkpoint::CheckpointStore;
 pub use database::Database;
 pub use fork_seed::ForkSeedStore;
 pub use models::{
-    ForkSeed, QueuedImageAttachment, QueuedMessage, QueuedMessageMode, Repository, SessionTab,
-    Workspace,
+    AuditActor, AuditEntry, AuditKind, AuditQuery, Checkpoint, ForkSeed, QueuedImageAttachment,
+    QueuedMessage, QueuedMessageMode, Race, RaceEntry, RaceTestResult, Repository, SearchDocument,
+    SearchHit, SearchSource, SessionEvent, SessionEventPayload, SessionTab, UsageBucket,
+    UsageDimension, UsageRecord, UsageTotals, Workspace,
 };
+pub use race::RaceStore;
 pub use repository::RepositoryStore;
+pub use review_comment::ReviewCommentStore;
+pub use search_index::{
+    fts_query, IndexedMessage, SearchIndexStore, SEARCH_MATCH_END, SEARCH_MATCH_START,
+};
+pub use session_event::SessionEventStore;
 pub use session_tab::SessionTabStore;
+pub use usage::UsageStore;
 pub use workspace::WorkspaceStore;
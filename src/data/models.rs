@@ -5,8 +5,8 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use uuid::Uuid;
 
-use crate::agent::AgentType;
-use crate::git::WorkspaceMode;
+use crate::agent::{AgentEvent, AgentType, PermissionRequest};
+use crate::git::{SyncStrategy, WorkspaceMode};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -56,6 +56,8 @@ pub struct Repository {
     pub archive_delete_branch: Option<bool>,
     /// Whether to prompt for remote deletion on archive (None = config default)
     pub archive_remote_prompt: Option<bool>,
+    /// Merge or rebase when syncing with the base branch (None = config default)
+    pub sync_strategy: Option<SyncStrategy>,
     /// When the repository was added
     pub created_at: DateTime<Utc>,
     /// Last time the repository was modified
@@ -74,6 +76,7 @@ impl Repository {
             workspace_mode: None,
             archive_delete_branch: None,
             archive_remote_prompt: None,
+            sync_strategy: None,
             created_at: now,
             updated_at: now,
         }
@@ -90,6 +93,7 @@ impl Repository {
             workspace_mode: None,
             archive_delete_branch: None,
             archive_remote_prompt: None,
+            sync_strategy: None,
             created_at: now,
             updated_at: now,
         }
@@ -106,6 +110,10 @@ impl Repository {
     pub fn archive_remote_prompt_or(&self, default: bool) -> bool {
         self.archive_remote_prompt.unwrap_or(default)
     }
+
+    pub fn sync_strategy_or(&self, default: SyncStrategy) -> SyncStrategy {
+        self.sync_strategy.unwrap_or(default)
+    }
 }
 
 /// Represents a workspace (git worktree) within a repository
@@ -255,8 +263,10 @@ impl SessionTab {
 pub struct ForkSeed {
     /// Unique identifier
     pub id: Uuid,
-    /// Agent type (Claude, Codex, or Gemini)
+    /// Agent type of the forked session (Claude, Codex, or Gemini)
     pub agent_type: AgentType,
+    /// Agent type of the parent session, when it differs (a cross-agent handoff)
+    pub parent_agent_type: Option<AgentType>,
     /// Parent agent session ID
     pub parent_session_id: Option<String>,
     /// Parent workspace ID
@@ -288,6 +298,7 @@ impl ForkSeed {
         Self {
             id: Uuid::new_v4(),
             agent_type,
+            parent_agent_type: None,
             parent_session_id,
             parent_workspace_id,
             created_at: Utc::now(),
@@ -298,4 +309,496 @@ impl ForkSeed {
             seed_ack_filtered: true,
         }
     }
+
+    /// Record the parent's agent type if the session is handed off to another agent.
+    pub fn with_parent_agent_type(mut self, parent_agent_type: AgentType) -> Self {
+        self.parent_agent_type =
+            (parent_agent_type != self.agent_type).then_some(parent_agent_type);
+        self
+    }
+}
+
+/// Payload of a single entry in the per-session event journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SessionEventPayload {
+    /// Prompt submitted by the user (hidden prompts such as fork seeds omit the text)
+    UserPrompt {
+        text: String,
+        #[serde(default)]
+        hidden: bool,
+        /// Whether the agent conversation already had history before this prompt
+        #[serde(default)]
+        resumed: bool,
+    },
+    /// Event received from the agent runner
+    Agent { event: AgentEvent },
+}
+
+impl SessionEventPayload {
+    /// Short type name stored alongside the payload (for filtering and debugging)
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            SessionEventPayload::UserPrompt { .. } => "UserPrompt",
+            SessionEventPayload::Agent { event } => event.event_type_name(),
+        }
+    }
+}
+
+/// A journaled session event
+#[derive(Debug, Clone)]
+pub struct SessionEvent {
+    /// Monotonic row ID (orders events across the whole journal)
+    pub id: i64,
+    /// Session tab the event belongs to
+    pub session_id: Uuid,
+    /// Event payload
+    pub payload: SessionEventPayload,
+    /// When the event was recorded
+    pub created_at: DateTime<Utc>,
+}
+
+/// Snapshot of a workspace taken when an agent turn started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub id: i64,
+    /// Session tab the turn ran in
+    pub session_id: Uuid,
+    /// Workspace of the session (if any)
+    pub workspace_id: Option<Uuid>,
+    /// 1-based turn number within the session
+    pub turn: u32,
+    /// Commit holding the working tree as it was before the turn
+    pub commit_sha: String,
+    /// Ref keeping the commit alive
+    pub ref_name: String,
+    /// When the turn started
+    pub created_at: DateTime<Utc>,
+}
+
+/// One prompt given to several agents, each in its own workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Race {
+    pub id: Uuid,
+    pub repository_id: Uuid,
+    pub prompt: String,
+    /// Branch the contenders' workspaces were created from
+    pub base_branch: String,
+    /// Commit of `base_branch` when the race started; contenders are diffed against it
+    pub base_commit: String,
+    /// Workspace picked as the winner (the others get archived)
+    pub winner_workspace_id: Option<Uuid>,
+    pub entries: Vec<RaceEntry>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Race {
+    /// Look up a contender by workspace
+    pub fn entry(&self, workspace_id: Uuid) -> Option<&RaceEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.workspace_id == workspace_id)
+    }
+}
+
+/// A single contender of a race
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaceEntry {
+    pub workspace_id: Uuid,
+    pub agent_type: AgentType,
+    pub model: Option<String>,
+    /// Outcome of the race's test command in this workspace, once run
+    pub test_result: Option<RaceTestResult>,
+}
+
+/// Outcome of running a race's test command in a contender's workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RaceTestResult {
+    pub passed: bool,
+    /// Tail of the combined stdout and stderr
+    pub output: String,
+}
+
+/// Where an indexed search document came from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSource {
+    /// A Conduit session tab (keyed by session tab ID)
+    Conduit,
+    /// A session discovered in an external agent's history (keyed by file path)
+    External,
+}
+
+impl SearchSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchSource::Conduit => "conduit",
+            SearchSource::External => "external",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "external" => SearchSource::External,
+            _ => SearchSource::Conduit,
+        }
+    }
+}
+
+/// A session whose messages are stored in the full-text search index
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SearchDocument {
+    /// Where the session came from
+    pub source: SearchSource,
+    /// Session tab ID (Conduit) or session file path (external)
+    pub source_key: String,
+    /// Agent that produced the session
+    pub agent_type: AgentType,
+    /// Display title (session title or first prompt)
+    pub title: String,
+    /// Project / working directory, if known
+    pub project: Option<String>,
+    /// Session file path (external sessions only)
+    pub file_path: Option<PathBuf>,
+    /// Opaque value that changes whenever the session's content changes
+    pub fingerprint: String,
+}
+
+/// A single matching message returned by a full-text search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// Session containing the match
+    pub document: SearchDocument,
+    /// Index of the matching message in the session's chat history
+    pub message_index: usize,
+    /// Role of the matching message (user, assistant, tool)
+    pub role: String,
+    /// Excerpt around the match, with matched terms wrapped in `«` and `»`
+    pub snippet: String,
+    /// When the session was last indexed
+    pub indexed_at: DateTime<Utc>,
+}
+
+/// Token usage and cost of a single completed agent turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// Session tab the turn ran in
+    pub session_id: Uuid,
+    /// Workspace of the session (if any)
+    pub workspace_id: Option<Uuid>,
+    /// Repository of the workspace (if any)
+    pub repository_id: Option<Uuid>,
+    /// Agent that ran the turn
+    pub agent_type: AgentType,
+    /// Model used for the turn (None = agent default)
+    pub model: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cached_tokens: i64,
+    pub cache_write_tokens: i64,
+    /// Estimated cost in USD
+    pub cost_usd: f64,
+    /// Wall-clock duration of the turn (None if it was not measured)
+    pub duration_ms: Option<i64>,
+    /// When the turn completed
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Aggregated usage over a set of turns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub turns: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cached_tokens: i64,
+    pub cache_write_tokens: i64,
+    pub cost_usd: f64,
+    /// Summed duration of the turns that were timed
+    pub duration_ms: i64,
+    /// Number of turns with a measured duration
+    pub timed_turns: i64,
+}
+
+impl UsageTotals {
+    /// Mean duration of the timed turns
+    pub fn average_duration_ms(&self) -> Option<i64> {
+        (self.timed_turns > 0).then(|| self.duration_ms / self.timed_turns)
+    }
+}
+
+/// Dimension that usage can be grouped by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageDimension {
+    /// Local calendar day
+    Day,
+    Agent,
+    Model,
+    Repository,
+    Workspace,
+}
+
+impl UsageDimension {
+    /// All dimensions, in display order
+    pub const ALL: [UsageDimension; 5] = [
+        UsageDimension::Day,
+        UsageDimension::Agent,
+        UsageDimension::Model,
+        UsageDimension::Repository,
+        UsageDimension::Workspace,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UsageDimension::Day => "Day",
+            UsageDimension::Agent => "Agent",
+            UsageDimension::Model => "Model",
+            UsageDimension::Repository => "Repository",
+            UsageDimension::Workspace => "Workspace",
+        }
+    }
+}
+
+/// Usage totals for one value of a [`UsageDimension`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageBucket {
+    /// Grouping key (date, agent type, model, or repository/workspace ID).
+    /// None groups turns without a model, repository or workspace.
+    pub key: Option<String>,
+    /// Agent of the bucket (agent and model breakdowns only)
+    pub agent_type: Option<AgentType>,
+    /// Repository or workspace name, if it still exists
+    pub name: Option<String>,
+    pub totals: UsageTotals,
+}
+
+/// Kind of action recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    ToolStarted,
+    ToolCompleted,
+    /// Finished shell command, with its exit code
+    Command,
+    FileChanged,
+    /// Permission approval or denial
+    Permission,
+}
+
+impl AuditKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditKind::ToolStarted => "tool_started",
+            AuditKind::ToolCompleted => "tool_completed",
+            AuditKind::Command => "command",
+            AuditKind::FileChanged => "file_changed",
+            AuditKind::Permission => "permission",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "tool_started" => Some(AuditKind::ToolStarted),
+            "tool_completed" => Some(AuditKind::ToolCompleted),
+            "command" => Some(AuditKind::Command),
+            "file_changed" => Some(AuditKind::FileChanged),
+            "permission" => Some(AuditKind::Permission),
+            _ => None,
+        }
+    }
+}
+
+/// Who made a permission decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditActor {
+    /// A configured permission rule, without asking
+    Policy,
+    /// A user in the terminal UI
+    Tui,
+    /// A user of a web client
+    Web,
+    /// A headless `conduit run`, which denies anything it would have to ask about
+    Cli,
+}
+
+impl AuditActor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditActor::Policy => "policy",
+            AuditActor::Tui => "tui",
+            AuditActor::Web => "web",
+            AuditActor::Cli => "cli",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "policy" => Some(AuditActor::Policy),
+            "tui" => Some(AuditActor::Tui),
+            "web" => Some(AuditActor::Web),
+            "cli" => Some(AuditActor::Cli),
+            _ => None,
+        }
+    }
+}
+
+/// An entry in the append-only audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Row ID (0 until stored)
+    pub id: i64,
+    pub session_id: Uuid,
+    /// Workspace of the session when the entry was recorded
+    pub workspace_id: Option<Uuid>,
+    pub kind: AuditKind,
+    /// Tool name (tool started and permission entries)
+    pub tool: Option<String>,
+    /// Tool call or permission request ID
+    pub tool_id: Option<String>,
+    /// Command line or file path(s) the action concerned
+    pub target: Option<String>,
+    /// `success`/`failure`, exit code, file operation, or permission decision
+    pub outcome: Option<String>,
+    /// Who decided (permission entries only)
+    pub actor: Option<AuditActor>,
+    /// Event payload, with long outputs truncated
+    pub details: serde_json::Value,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    /// Longest tool result or command output kept in `details`, in bytes
+    pub const MAX_OUTPUT_LEN: usize = 4096;
+
+    fn new(session_id: Uuid, kind: AuditKind, details: serde_json::Value) -> Self {
+        Self {
+            id: 0,
+            session_id,
+            workspace_id: None,
+            kind,
+            tool: None,
+            tool_id: None,
+            target: None,
+            outcome: None,
+            actor: None,
+            details,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    /// Entry for an auditable agent event (None for streaming text, usage, etc.)
+    pub fn from_event(session_id: Uuid, event: &AgentEvent) -> Option<Self> {
+        use serde_json::json;
+
+        let entry = match event {
+            AgentEvent::ToolStarted(started) => {
+                let request =
+                    PermissionRequest::from_tool_input(&started.tool_name, &started.arguments);
+                Self {
+                    tool: Some(started.tool_name.clone()),
+                    tool_id: Some(started.tool_id.clone()),
+                    target: request.target(),
+                    ..Self::new(
+                        session_id,
+                        AuditKind::ToolStarted,
+                        json!({ "arguments": started.arguments }),
+                    )
+                }
+            }
+            AgentEvent::ToolCompleted(completed) => Self {
+                tool_id: Some(completed.tool_id.clone()),
+                outcome: Some(
+                    if completed.success {
+                        "success"
+                    } else {
+                        "failure"
+                    }
+                    .to_string(),
+                ),
+                ..Self::new(
+                    session_id,
+                    AuditKind::ToolCompleted,
+                    json!({
+                        "result": completed.result.as_deref().map(truncate_output),
+                        "error": completed.error,
+                    }),
+                )
+            },
+            AgentEvent::CommandOutput(output) if !output.is_streaming => Self {
+                target: Some(output.command.clone()),
+                outcome: output.exit_code.map(|code| code.to_string()),
+                ..Self::new(
+                    session_id,
+                    AuditKind::Command,
+                    json!({
+                        "output": truncate_output(&output.output),
+                        "exit_code": output.exit_code,
+                    }),
+                )
+            },
+            AgentEvent::FileChanged(changed) => {
+                let operation = serde_json::to_value(&changed.operation).unwrap_or_default();
+                Self {
+                    target: Some(changed.path.clone()),
+                    outcome: operation.as_str().map(str::to_string),
+                    ..Self::new(session_id, AuditKind::FileChanged, json!({}))
+                }
+            }
+            AgentEvent::PermissionDecided(decided) => Self::permission(
+                session_id,
+                &decided.request_id,
+                &decided.permission,
+                decided.decision.as_str(),
+                AuditActor::Policy,
+            ),
+            _ => return None,
+        };
+        Some(entry)
+    }
+
+    /// Entry for a permission decision (`allow`, `deny`, `allow_once`, `always_allow`)
+    pub fn permission(
+        session_id: Uuid,
+        request_id: &str,
+        request: &PermissionRequest,
+        decision: &str,
+        actor: AuditActor,
+    ) -> Self {
+        Self {
+            tool: Some(request.tool.clone()),
+            tool_id: Some(request_id.to_string()),
+            target: request.target(),
+            outcome: Some(decision.to_string()),
+            actor: Some(actor),
+            ..Self::new(
+                session_id,
+                AuditKind::Permission,
+                serde_json::to_value(request).unwrap_or_default(),
+            )
+        }
+    }
+}
+
+fn truncate_output(output: &str) -> String {
+    if output.len() <= AuditEntry::MAX_OUTPUT_LEN {
+        return output.to_string();
+    }
+    let mut end = AuditEntry::MAX_OUTPUT_LEN;
+    while !output.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}… [truncated]", &output[..end])
+}
+
+/// Filters for listing audit entries
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub session_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub kind: Option<AuditKind>,
+    /// Only entries recorded at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Maximum number of (most recent) entries (None = all)
+    pub limit: Option<usize>,
 }
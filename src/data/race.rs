@@ -0,0 +1,237 @@
+//! Race data access object
+
+use super::models::{Race, RaceEntry, RaceTestResult};
+use crate::agent::AgentType;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult, Row};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for multi-agent races
+#[derive(Clone)]
+pub struct RaceStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl RaceStore {
+    /// Create a new RaceStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Insert a race and its entries
+    pub fn create(&self, race: &Race) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO races (id, repository_id, prompt, base_branch, base_commit,
+                winner_workspace_id, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                race.id.to_string(),
+                race.repository_id.to_string(),
+                race.prompt,
+                race.base_branch,
+                race.base_commit,
+                race.winner_workspace_id.map(|id| id.to_string()),
+                race.created_at.to_rfc3339(),
+            ],
+        )?;
+        for (position, entry) in race.entries.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO race_entries (race_id, position, workspace_id, agent_type, model,
+                    test_passed, test_output)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    race.id.to_string(),
+                    position as i64,
+                    entry.workspace_id.to_string(),
+                    entry.agent_type.as_str(),
+                    entry.model,
+                    entry.test_result.as_ref().map(|result| result.passed),
+                    entry.test_result.as_ref().map(|result| &result.output),
+                ],
+            )?;
+        }
+        tx.commit()
+    }
+
+    /// Get a race with its entries
+    pub fn get(&self, id: Uuid) -> SqliteResult<Option<Race>> {
+        let conn = self.conn.lock().unwrap();
+        let race = conn
+            .query_row(
+                "SELECT id, repository_id, prompt, base_branch, base_commit, winner_workspace_id,
+                    created_at
+                 FROM races WHERE id = ?1",
+                params![id.to_string()],
+                Self::row_to_race,
+            )
+            .optional()?;
+        let Some(mut race) = race else {
+            return Ok(None);
+        };
+
+        let mut stmt = conn.prepare(
+            "SELECT workspace_id, agent_type, model, test_passed, test_output
+             FROM race_entries WHERE race_id = ?1 ORDER BY position",
+        )?;
+        race.entries = stmt
+            .query_map(params![id.to_string()], Self::row_to_entry)?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(Some(race))
+    }
+
+    /// Find the race a workspace is a contender in
+    pub fn find_by_workspace(&self, workspace_id: Uuid) -> SqliteResult<Option<Uuid>> {
+        let conn = self.conn.lock().unwrap();
+        let race_id: Option<String> = conn
+            .query_row(
+                "SELECT race_id FROM race_entries WHERE workspace_id = ?1",
+                params![workspace_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(race_id.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    /// Record the test outcome of a contender
+    pub fn set_test_result(
+        &self,
+        race_id: Uuid,
+        workspace_id: Uuid,
+        result: &RaceTestResult,
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE race_entries SET test_passed = ?3, test_output = ?4
+             WHERE race_id = ?1 AND workspace_id = ?2",
+            params![
+                race_id.to_string(),
+                workspace_id.to_string(),
+                result.passed,
+                result.output,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Record the winning workspace of a race
+    pub fn set_winner(&self, race_id: Uuid, workspace_id: Uuid) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE races SET winner_workspace_id = ?2 WHERE id = ?1",
+            params![race_id.to_string(), workspace_id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    fn row_to_race(row: &Row) -> SqliteResult<Race> {
+        let id: String = row.get(0)?;
+        let repository_id: String = row.get(1)?;
+        let winner_workspace_id: Option<String> = row.get(5)?;
+        let created_at: String = row.get(6)?;
+        Ok(Race {
+            id: Uuid::parse_str(&id).unwrap_or_else(|_| Uuid::nil()),
+            repository_id: Uuid::parse_str(&repository_id).unwrap_or_else(|_| Uuid::nil()),
+            prompt: row.get(2)?,
+            base_branch: row.get(3)?,
+            base_commit: row.get(4)?,
+            winner_workspace_id: winner_workspace_id.and_then(|id| Uuid::parse_str(&id).ok()),
+            entries: Vec::new(),
+            created_at: DateTime::parse_from_rfc3339(&created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+
+    fn row_to_entry(row: &Row) -> SqliteResult<RaceEntry> {
+        let workspace_id: String = row.get(0)?;
+        let agent_type: String = row.get(1)?;
+        let test_passed: Option<bool> = row.get(3)?;
+        let test_output: Option<String> = row.get(4)?;
+        Ok(RaceEntry {
+            workspace_id: Uuid::parse_str(&workspace_id).unwrap_or_else(|_| Uuid::nil()),
+            agent_type: AgentType::parse(&agent_type),
+            model: row.get(2)?,
+            test_result: test_passed.map(|passed| RaceTestResult {
+                passed,
+                output: test_output.unwrap_or_default(),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_create_get_and_update_race() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = RaceStore::new(db.connection());
+
+        let claude_workspace = Uuid::new_v4();
+        let codex_workspace = Uuid::new_v4();
+        let race = Race {
+            id: Uuid::new_v4(),
+            repository_id: Uuid::new_v4(),
+            prompt: "Fix the flaky test".to_string(),
+            base_branch: "main".to_string(),
+            base_commit: "abc123".to_string(),
+            winner_workspace_id: None,
+            entries: vec![
+                RaceEntry {
+                    workspace_id: claude_workspace,
+                    agent_type: AgentType::Claude,
+                    model: Some("opus".to_string()),
+                    test_result: None,
+                },
+                RaceEntry {
+                    workspace_id: codex_workspace,
+                    agent_type: AgentType::Codex,
+                    model: None,
+                    test_result: None,
+                },
+            ],
+            created_at: Utc::now(),
+        };
+        store.create(&race).unwrap();
+
+        let loaded = store.get(race.id).unwrap().unwrap();
+        assert_eq!(loaded.prompt, "Fix the flaky test");
+        assert_eq!(loaded.entries.len(), 2);
+        assert_eq!(loaded.entries[0].agent_type, AgentType::Claude);
+        assert_eq!(loaded.entries[0].model.as_deref(), Some("opus"));
+        assert_eq!(loaded.entries[1].agent_type, AgentType::Codex);
+        assert_eq!(
+            store.find_by_workspace(codex_workspace).unwrap(),
+            Some(race.id)
+        );
+        assert!(store.find_by_workspace(Uuid::new_v4()).unwrap().is_none());
+
+        let result = RaceTestResult {
+            passed: false,
+            output: "1 failed".to_string(),
+        };
+        store
+            .set_test_result(race.id, codex_workspace, &result)
+            .unwrap();
+        store.set_winner(race.id, claude_workspace).unwrap();
+
+        let loaded = store.get(race.id).unwrap().unwrap();
+        assert_eq!(loaded.winner_workspace_id, Some(claude_workspace));
+        assert!(loaded
+            .entry(claude_workspace)
+            .unwrap()
+            .test_result
+            .is_none());
+        assert_eq!(
+            loaded.entry(codex_workspace).unwrap().test_result,
+            Some(result)
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! Repository data access object
 
 use super::models::Repository;
-use crate::git::WorkspaceMode;
+use crate::git::{SyncStrategy, WorkspaceMode};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqliteResult};
 use std::path::{Path, PathBuf};
@@ -25,8 +25,8 @@ impl RepositoryStore {
     pub fn create(&self, repo: &Repository) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "INSERT INTO repositories (id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            "INSERT INTO repositories (id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at, sync_strategy)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 repo.id.to_string(),
                 repo.name,
@@ -39,6 +39,7 @@ impl RepositoryStore {
                 repo.archive_remote_prompt.map(|value| value as i32),
                 repo.created_at.to_rfc3339(),
                 repo.updated_at.to_rfc3339(),
+                repo.sync_strategy.map(|strategy| strategy.as_str().to_string()),
             ],
         )?;
         Ok(())
@@ -48,7 +49,7 @@ impl RepositoryStore {
     pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<Repository>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at
+            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at, sync_strategy
              FROM repositories WHERE id = ?1",
         )?;
 
@@ -64,7 +65,7 @@ impl RepositoryStore {
     pub fn get_all(&self) -> SqliteResult<Vec<Repository>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at
+            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at, sync_strategy
              FROM repositories ORDER BY name",
         )?;
 
@@ -79,7 +80,7 @@ impl RepositoryStore {
     pub fn update(&self, repo: &Repository) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "UPDATE repositories SET name = ?2, base_path = ?3, repository_url = ?4, workspace_mode = ?5, archive_delete_branch = ?6, archive_remote_prompt = ?7, updated_at = ?8
+            "UPDATE repositories SET name = ?2, base_path = ?3, repository_url = ?4, workspace_mode = ?5, archive_delete_branch = ?6, archive_remote_prompt = ?7, updated_at = ?8, sync_strategy = ?9
              WHERE id = ?1",
             params![
                 repo.id.to_string(),
@@ -90,6 +91,7 @@ impl RepositoryStore {
                 repo.archive_delete_branch.map(|value| value as i32),
                 repo.archive_remote_prompt.map(|value| value as i32),
                 Utc::now().to_rfc3339(),
+                repo.sync_strategy.map(|strategy| strategy.as_str().to_string()),
             ],
         )?;
         Ok(())
@@ -122,7 +124,7 @@ impl RepositoryStore {
         let conn = self.conn.lock().unwrap();
         let path_str = path.to_string_lossy().to_string();
         let mut stmt = conn.prepare(
-            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at
+            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at, sync_strategy
              FROM repositories WHERE base_path = ?1",
         )?;
 
@@ -143,6 +145,7 @@ impl RepositoryStore {
         let archive_remote_prompt_raw: Option<i32> = row.get(6)?;
         let created_at_str: String = row.get(7)?;
         let updated_at_str: String = row.get(8)?;
+        let sync_strategy_raw: Option<String> = row.get(9)?;
 
         let workspace_mode = match workspace_mode_raw {
             None => None,
@@ -170,6 +173,8 @@ impl RepositoryStore {
             workspace_mode,
             archive_delete_branch: archive_delete_branch_raw.map(|value| value != 0),
             archive_remote_prompt: archive_remote_prompt_raw.map(|value| value != 0),
+            // Unknown values fall back to the config default
+            sync_strategy: sync_strategy_raw.and_then(|value| SyncStrategy::from_str(&value).ok()),
             created_at: DateTime::parse_from_rfc3339(&created_at_str)
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
@@ -186,6 +191,7 @@ impl RepositoryStore {
         workspace_mode: Option<WorkspaceMode>,
         archive_delete_branch: Option<bool>,
         archive_remote_prompt: Option<bool>,
+        sync_strategy: Option<SyncStrategy>,
     ) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
@@ -193,6 +199,7 @@ impl RepositoryStore {
              SET workspace_mode = COALESCE(?2, workspace_mode),
                  archive_delete_branch = COALESCE(?3, archive_delete_branch),
                  archive_remote_prompt = COALESCE(?4, archive_remote_prompt),
+                 sync_strategy = COALESCE(?6, sync_strategy),
                  updated_at = ?5
              WHERE id = ?1",
             params![
@@ -201,6 +208,7 @@ impl RepositoryStore {
                 archive_delete_branch.map(|value| value as i32),
                 archive_remote_prompt.map(|value| value as i32),
                 Utc::now().to_rfc3339(),
+                sync_strategy.map(|strategy| strategy.as_str().to_string()),
             ],
         )?;
         Ok(())
@@ -259,4 +267,21 @@ mod tests {
         dao.delete(repo.id).unwrap();
         assert!(dao.get_by_id(repo.id).unwrap().is_none());
     }
+
+    #[test]
+    fn test_update_settings_keeps_unset_fields() {
+        let (_dir, _db, dao) = setup_db();
+        let repo = Repository::from_local_path("settings", PathBuf::from("/tmp/settings"));
+        dao.create(&repo).unwrap();
+
+        dao.update_settings(repo.id, None, Some(false), None, Some(SyncStrategy::Rebase))
+            .unwrap();
+        dao.update_settings(repo.id, None, None, Some(true), None)
+            .unwrap();
+
+        let updated = dao.get_by_id(repo.id).unwrap().unwrap();
+        assert_eq!(updated.sync_strategy, Some(SyncStrategy::Rebase));
+        assert_eq!(updated.archive_delete_branch, Some(false));
+        assert_eq!(updated.archive_remote_prompt, Some(true));
+    }
 }
@@ -0,0 +1,88 @@
+//! Addressed review comment data access object
+
+use chrono::Utc;
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for PR review comments already sent to an agent
+#[derive(Clone)]
+pub struct ReviewCommentStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ReviewCommentStore {
+    /// Create a new ReviewCommentStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// IDs of the workspace's comments marked as addressed
+    pub fn addressed_ids(&self, workspace_id: Uuid) -> SqliteResult<HashSet<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT comment_id FROM addressed_review_comments WHERE workspace_id = ?1")?;
+        let ids = stmt
+            .query_map(params![workspace_id.to_string()], |row| row.get(0))?
+            .collect::<SqliteResult<HashSet<String>>>()?;
+        Ok(ids)
+    }
+
+    /// Mark comments as addressed (or not)
+    pub fn set_addressed(
+        &self,
+        workspace_id: Uuid,
+        comment_ids: &[String],
+        addressed: bool,
+    ) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        for comment_id in comment_ids {
+            if addressed {
+                tx.execute(
+                    "INSERT OR IGNORE INTO addressed_review_comments
+                        (workspace_id, comment_id, addressed_at)
+                     VALUES (?1, ?2, ?3)",
+                    params![workspace_id.to_string(), comment_id, now],
+                )?;
+            } else {
+                tx.execute(
+                    "DELETE FROM addressed_review_comments
+                     WHERE workspace_id = ?1 AND comment_id = ?2",
+                    params![workspace_id.to_string(), comment_id],
+                )?;
+            }
+        }
+        tx.commit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_set_addressed_per_workspace() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = ReviewCommentStore::new(db.connection());
+        let workspace_id = Uuid::new_v4();
+        let ids = vec!["C1".to_string(), "C2".to_string()];
+
+        store.set_addressed(workspace_id, &ids, true).unwrap();
+        // Marking twice is a no-op
+        store.set_addressed(workspace_id, &ids[..1], true).unwrap();
+        store
+            .set_addressed(Uuid::new_v4(), &["C3".to_string()], true)
+            .unwrap();
+        assert_eq!(store.addressed_ids(workspace_id).unwrap().len(), 2);
+
+        store.set_addressed(workspace_id, &ids[..1], false).unwrap();
+        let addressed = store.addressed_ids(workspace_id).unwrap();
+        assert_eq!(addressed, HashSet::from(["C2".to_string()]));
+    }
+}
@@ -0,0 +1,404 @@
+//! Full-text search index data access object
+//!
+//! Messages are stored in the `search_index` FTS5 table, one row per chat
+//! message, and grouped into `search_documents` (one per indexed session).
+
+use super::models::{SearchDocument, SearchHit, SearchSource};
+use crate::agent::AgentType;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Marker inserted before each matched term in search snippets
+pub const SEARCH_MATCH_START: &str = "«";
+/// Marker inserted after each matched term in search snippets
+pub const SEARCH_MATCH_END: &str = "»";
+
+/// Approximate number of tokens included in a search snippet
+const SNIPPET_TOKENS: i64 = 16;
+
+/// A message to be stored in the search index
+#[derive(Debug, Clone)]
+pub struct IndexedMessage {
+    /// Index of the message in the session's chat history
+    pub message_index: usize,
+    /// Role name (user, assistant, tool)
+    pub role: &'static str,
+    /// Searchable text
+    pub content: String,
+}
+
+/// Data access object for the full-text search index
+#[derive(Clone)]
+pub struct SearchIndexStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SearchIndexStore {
+    /// Create a new SearchIndexStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Get the fingerprint of an indexed session (None if it isn't indexed)
+    pub fn fingerprint(
+        &self,
+        source: SearchSource,
+        source_key: &str,
+    ) -> SqliteResult<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT fingerprint FROM search_documents WHERE source = ?1 AND source_key = ?2",
+            params![source.as_str(), source_key],
+            |row| row.get(0),
+        )
+        .optional()
+    }
+
+    /// Replace the indexed messages of a session in a single transaction
+    pub fn replace_document(
+        &self,
+        document: &SearchDocument,
+        messages: &[IndexedMessage],
+    ) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO search_documents (source, source_key, agent_type, title, project, file_path, fingerprint, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(source, source_key) DO UPDATE SET
+               agent_type = excluded.agent_type,
+               title = excluded.title,
+               project = excluded.project,
+               file_path = excluded.file_path,
+               fingerprint = excluded.fingerprint,
+               updated_at = excluded.updated_at",
+            params![
+                document.source.as_str(),
+                document.source_key,
+                document.agent_type.as_str(),
+                document.title,
+                document.project,
+                document
+                    .file_path
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().to_string()),
+                document.fingerprint,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        let document_id: i64 = tx.query_row(
+            "SELECT id FROM search_documents WHERE source = ?1 AND source_key = ?2",
+            params![document.source.as_str(), document.source_key],
+            |row| row.get(0),
+        )?;
+
+        tx.execute(
+            "DELETE FROM search_index WHERE document_id = ?1",
+            params![document_id],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO search_index (content, role, message_index, document_id)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for message in messages {
+                stmt.execute(params![
+                    message.content,
+                    message.role,
+                    message.message_index as i64,
+                    document_id,
+                ])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Remove a session from the index
+    pub fn delete_document(&self, source: SearchSource, source_key: &str) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        Self::delete_document_with_conn(&tx, source, source_key)?;
+        tx.commit()
+    }
+
+    /// Remove every session of `source` whose key is not in `keep`.
+    ///
+    /// Returns the number of sessions removed.
+    pub fn retain_documents(
+        &self,
+        source: SearchSource,
+        keep: &HashSet<String>,
+    ) -> SqliteResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let stale: Vec<String> = {
+            let mut stmt =
+                tx.prepare("SELECT source_key FROM search_documents WHERE source = ?1")?;
+            let keys = stmt
+                .query_map(params![source.as_str()], |row| row.get::<_, String>(0))?
+                .collect::<SqliteResult<Vec<_>>>()?;
+            keys.into_iter().filter(|key| !keep.contains(key)).collect()
+        };
+        for key in &stale {
+            Self::delete_document_with_conn(&tx, source, key)?;
+        }
+        tx.commit()?;
+        Ok(stale.len())
+    }
+
+    /// Run a full-text query, best matches first.
+    ///
+    /// `query` must already be in FTS5 syntax (see [`fts_query`]).
+    pub fn search(&self, query: &str, limit: usize) -> SqliteResult<Vec<SearchHit>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT d.source, d.source_key, d.agent_type, d.title, d.project, d.file_path,
+                    d.fingerprint, d.updated_at, search_index.message_index, search_index.role,
+                    snippet(search_index, 0, ?3, ?4, '…', ?5)
+             FROM search_index
+             JOIN search_documents d ON d.id = search_index.document_id
+             WHERE search_index MATCH ?1
+             ORDER BY rank
+             LIMIT ?2",
+        )?;
+
+        let hits = stmt
+            .query_map(
+                params![
+                    query,
+                    limit as i64,
+                    SEARCH_MATCH_START,
+                    SEARCH_MATCH_END,
+                    SNIPPET_TOKENS
+                ],
+                Self::row_to_hit,
+            )?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(hits)
+    }
+
+    /// Count indexed sessions
+    pub fn document_count(&self) -> SqliteResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT COUNT(*) FROM search_documents", [], |row| {
+            row.get(0)
+        })
+    }
+
+    fn delete_document_with_conn(
+        conn: &Connection,
+        source: SearchSource,
+        source_key: &str,
+    ) -> SqliteResult<()> {
+        let document_id: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM search_documents WHERE source = ?1 AND source_key = ?2",
+                params![source.as_str(), source_key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(document_id) = document_id {
+            conn.execute(
+                "DELETE FROM search_index WHERE document_id = ?1",
+                params![document_id],
+            )?;
+            conn.execute(
+                "DELETE FROM search_documents WHERE id = ?1",
+                params![document_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Convert a database row to a SearchHit
+    fn row_to_hit(row: &rusqlite::Row) -> SqliteResult<SearchHit> {
+        let source: String = row.get(0)?;
+        let agent_type: String = row.get(2)?;
+        let file_path: Option<String> = row.get(5)?;
+        let updated_at: String = row.get(7)?;
+        let message_index: i64 = row.get(8)?;
+
+        Ok(SearchHit {
+            document: SearchDocument {
+                source: SearchSource::parse(&source),
+                source_key: row.get(1)?,
+                agent_type: AgentType::parse(&agent_type),
+                title: row.get(3)?,
+                project: row.get(4)?,
+                file_path: file_path.map(PathBuf::from),
+                fingerprint: row.get(6)?,
+            },
+            message_index: message_index.max(0) as usize,
+            role: row.get(9)?,
+            snippet: row.get(10)?,
+            indexed_at: DateTime::parse_from_rfc3339(&updated_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
+
+/// Convert free-form user input into an FTS5 query.
+///
+/// Every whitespace-separated term must match (terms are quoted so FTS5
+/// operators and punctuation are treated literally), and the last term is a
+/// prefix match so results update while typing. Returns `None` for blank input.
+pub fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"", term))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    fn setup_db() -> (tempfile::TempDir, Database, SearchIndexStore) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = SearchIndexStore::new(db.connection());
+        (dir, db, store)
+    }
+
+    fn document(source: SearchSource, key: &str, fingerprint: &str) -> SearchDocument {
+        SearchDocument {
+            source,
+            source_key: key.to_string(),
+            agent_type: AgentType::Codex,
+            title: format!("Session {}", key),
+            project: Some("/tmp/project".to_string()),
+            file_path: None,
+            fingerprint: fingerprint.to_string(),
+        }
+    }
+
+    fn message(message_index: usize, role: &'static str, content: &str) -> IndexedMessage {
+        IndexedMessage {
+            message_index,
+            role,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_fts_query_quotes_terms_and_prefixes_last() {
+        assert_eq!(fts_query("   "), None);
+        assert_eq!(
+            fts_query("fix \"migration\" bu").as_deref(),
+            Some("\"fix\" \"migration\" \"bu\"*")
+        );
+        assert_eq!(
+            fts_query("NOT-a(query)").as_deref(),
+            Some("\"NOT-a(query)\"*")
+        );
+    }
+
+    #[test]
+    fn test_replace_and_search() {
+        let (_dir, _db, store) = setup_db();
+        let doc = document(SearchSource::Conduit, "a", "1");
+        store
+            .replace_document(
+                &doc,
+                &[
+                    message(0, "user", "please fix the migration bug"),
+                    message(1, "assistant", "Fixed the migrations in database.rs"),
+                    message(2, "tool", "cargo test: ok"),
+                ],
+            )
+            .unwrap();
+        store
+            .replace_document(
+                &document(SearchSource::External, "/b.jsonl", "1"),
+                &[message(0, "user", "unrelated question")],
+            )
+            .unwrap();
+
+        let hits = store.search(&fts_query("migration").unwrap(), 10).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|hit| hit.document == doc));
+        let user_hit = hits.iter().find(|hit| hit.message_index == 0).unwrap();
+        assert_eq!(user_hit.role, "user");
+        assert!(user_hit.snippet.contains("«migration»"));
+
+        assert_eq!(
+            store
+                .fingerprint(SearchSource::Conduit, "a")
+                .unwrap()
+                .as_deref(),
+            Some("1")
+        );
+        assert_eq!(store.fingerprint(SearchSource::Conduit, "b").unwrap(), None);
+    }
+
+    #[test]
+    fn test_replace_drops_previous_messages() {
+        let (_dir, _db, store) = setup_db();
+        store
+            .replace_document(
+                &document(SearchSource::Conduit, "a", "1"),
+                &[message(0, "user", "old content")],
+            )
+            .unwrap();
+        store
+            .replace_document(
+                &document(SearchSource::Conduit, "a", "2"),
+                &[message(0, "user", "new content")],
+            )
+            .unwrap();
+
+        assert!(store.search("\"old\"", 10).unwrap().is_empty());
+        assert_eq!(store.search("\"new\"", 10).unwrap().len(), 1);
+        assert_eq!(store.document_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_retain_documents_removes_stale_sessions() {
+        let (_dir, _db, store) = setup_db();
+        for key in ["a", "b"] {
+            store
+                .replace_document(
+                    &document(SearchSource::External, key, "1"),
+                    &[message(0, "user", "shared term")],
+                )
+                .unwrap();
+        }
+        store
+            .replace_document(
+                &document(SearchSource::Conduit, "c", "1"),
+                &[message(0, "user", "shared term")],
+            )
+            .unwrap();
+
+        let keep: HashSet<String> = ["a".to_string()].into_iter().collect();
+        assert_eq!(
+            store
+                .retain_documents(SearchSource::External, &keep)
+                .unwrap(),
+            1
+        );
+        let hits = store.search("\"shared\"", 10).unwrap();
+        let mut keys: Vec<_> = hits
+            .iter()
+            .map(|h| h.document.source_key.as_str())
+            .collect();
+        keys.sort();
+        assert_eq!(keys, vec!["a", "c"]);
+
+        store.delete_document(SearchSource::Conduit, "c").unwrap();
+        assert_eq!(store.document_count().unwrap(), 1);
+    }
+}
@@ -0,0 +1,371 @@
+//! Session event journal data access object
+
+use super::models::{SessionEvent, SessionEventPayload};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for the per-session event journal
+#[derive(Clone)]
+pub struct SessionEventStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SessionEventStore {
+    /// Create a new SessionEventStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Append a single event to a session's journal, returning its row ID
+    pub fn append(&self, session_id: Uuid, payload: &SessionEventPayload) -> SqliteResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        Self::insert_with_conn(&conn, session_id, payload, Utc::now())?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Append several events in a single transaction (preserving order)
+    pub fn append_batch(
+        &self,
+        events: &[(Uuid, SessionEventPayload, DateTime<Utc>)],
+    ) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for (session_id, payload, created_at) in events {
+            Self::insert_with_conn(&tx, *session_id, payload, *created_at)?;
+        }
+        tx.commit()
+    }
+
+    /// Get all journaled events for a session in recording order
+    pub fn get_by_session(&self, session_id: Uuid) -> SqliteResult<Vec<SessionEvent>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, payload, created_at
+             FROM session_events WHERE session_id = ?1 ORDER BY id",
+        )?;
+
+        let events = stmt
+            .query_map(params![session_id.to_string()], Self::row_to_event)?
+            .filter_map(|r| r.ok().flatten())
+            .collect();
+
+        Ok(events)
+    }
+
+    /// Get the events of a session's most recent turn: the last user prompt and
+    /// everything recorded after it
+    pub fn get_last_turn(&self, session_id: Uuid) -> SqliteResult<Vec<SessionEvent>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, payload, created_at
+             FROM session_events
+             WHERE session_id = ?1
+               AND id >= COALESCE(
+                   (SELECT MAX(id) FROM session_events
+                    WHERE session_id = ?1 AND event_type = 'UserPrompt'),
+                   0)
+             ORDER BY id",
+        )?;
+
+        let events = stmt
+            .query_map(params![session_id.to_string()], Self::row_to_event)?
+            .filter_map(|r| r.ok().flatten())
+            .collect();
+
+        Ok(events)
+    }
+
+    /// Count journaled events for a session
+    pub fn count_by_session(&self, session_id: Uuid) -> SqliteResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT COUNT(*) FROM session_events WHERE session_id = ?1",
+            params![session_id.to_string()],
+            |row| row.get(0),
+        )
+    }
+
+    /// Get the ID of the most recent journaled event for a session
+    pub fn last_event_id(&self, session_id: Uuid) -> SqliteResult<Option<i64>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT MAX(id) FROM session_events WHERE session_id = ?1",
+            params![session_id.to_string()],
+            |row| row.get(0),
+        )
+    }
+
+    /// Delete the events of the turn that started at `started_at` and every
+    /// later one, returning how many were removed.
+    ///
+    /// The turn begins at its user prompt, which is recorded before the agent
+    /// reports the turn as started.
+    pub fn truncate_turns_from(
+        &self,
+        session_id: Uuid,
+        started_at: DateTime<Utc>,
+    ) -> SqliteResult<usize> {
+        let events = self.get_by_session(session_id)?;
+        let cutoff = events
+            .iter()
+            .rev()
+            .find(|event| {
+                matches!(event.payload, SessionEventPayload::UserPrompt { .. })
+                    && event.created_at <= started_at
+            })
+            .or_else(|| events.iter().find(|event| event.created_at >= started_at))
+            .map(|event| event.id);
+        let Some(cutoff) = cutoff else {
+            return Ok(0);
+        };
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM session_events WHERE session_id = ?1 AND id >= ?2",
+            params![session_id.to_string(), cutoff],
+        )
+    }
+
+    /// Delete all journaled events for a session
+    pub fn delete_by_session(&self, session_id: Uuid) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM session_events WHERE session_id = ?1",
+            params![session_id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Delete the journaled events of every session in a workspace, once it
+    /// is archived, returning how many were removed
+    pub fn delete_by_workspace(&self, workspace_id: Uuid) -> SqliteResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM session_events WHERE session_id IN
+                (SELECT id FROM session_tabs WHERE workspace_id = ?1)",
+            params![workspace_id.to_string()],
+        )
+    }
+
+    fn insert_with_conn(
+        conn: &Connection,
+        session_id: Uuid,
+        payload: &SessionEventPayload,
+        created_at: DateTime<Utc>,
+    ) -> SqliteResult<()> {
+        let payload_json = serde_json::to_string(payload)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO session_events (session_id, event_type, payload, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                session_id.to_string(),
+                payload.event_type(),
+                payload_json,
+                created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Convert a database row to a SessionEvent.
+    ///
+    /// Rows whose payload can't be parsed (e.g. written by a newer version) are skipped.
+    fn row_to_event(row: &rusqlite::Row) -> SqliteResult<Option<SessionEvent>> {
+        let id: i64 = row.get(0)?;
+        let session_id_str: String = row.get(1)?;
+        let payload_str: String = row.get(2)?;
+        let created_at_str: String = row.get(3)?;
+
+        let payload = match serde_json::from_str::<SessionEventPayload>(&payload_str) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!(id, error = %e, "Skipping unparseable session event");
+                return Ok(None);
+            }
+        };
+
+        Ok(Some(SessionEvent {
+            id,
+            session_id: Uuid::parse_str(&session_id_str).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Invalid UUID in session_events table: {}, error: {}",
+                    session_id_str,
+                    e
+                );
+                Uuid::nil()
+            }),
+            payload,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|e| {
+                    tracing::warn!(
+                        "Invalid DateTime in session_events table: {}, error: {}",
+                        created_at_str,
+                        e
+                    );
+                    Utc::now()
+                }),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::events::{AgentEvent, AssistantMessageEvent};
+    use crate::agent::AgentType;
+    use crate::data::{
+        Database, Repository, RepositoryStore, SessionTab, SessionTabStore, Workspace,
+        WorkspaceStore,
+    };
+    use tempfile::tempdir;
+
+    fn setup_db() -> (tempfile::TempDir, Database, SessionEventStore) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = SessionEventStore::new(db.connection());
+        (dir, db, store)
+    }
+
+    fn assistant(text: &str) -> SessionEventPayload {
+        SessionEventPayload::Agent {
+            event: AgentEvent::AssistantMessage(AssistantMessageEvent {
+                text: text.to_string(),
+                is_final: true,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_append_and_get_in_order() {
+        let (_dir, _db, store) = setup_db();
+        let session_id = Uuid::new_v4();
+        let other_session = Uuid::new_v4();
+
+        store
+            .append(
+                session_id,
+                &SessionEventPayload::UserPrompt {
+                    text: "hello".to_string(),
+                    hidden: false,
+                    resumed: false,
+                },
+            )
+            .unwrap();
+        store.append(other_session, &assistant("other")).unwrap();
+        store.append(session_id, &assistant("hi there")).unwrap();
+
+        let events = store.get_by_session(session_id).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events[0].id < events[1].id);
+        assert!(matches!(
+            &events[0].payload,
+            SessionEventPayload::UserPrompt { text, .. } if text == "hello"
+        ));
+        assert!(matches!(
+            &events[1].payload,
+            SessionEventPayload::Agent { event: AgentEvent::AssistantMessage(msg) }
+                if msg.text == "hi there"
+        ));
+        assert_eq!(store.count_by_session(other_session).unwrap(), 1);
+        assert_eq!(store.last_event_id(session_id).unwrap(), Some(events[1].id));
+        assert_eq!(store.last_event_id(Uuid::new_v4()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_append_batch_and_delete() {
+        let (_dir, _db, store) = setup_db();
+        let session_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        store
+            .append_batch(&[
+                (session_id, assistant("one"), now),
+                (session_id, assistant("two"), now),
+            ])
+            .unwrap();
+        assert_eq!(store.count_by_session(session_id).unwrap(), 2);
+
+        store.delete_by_session(session_id).unwrap();
+        assert!(store.get_by_session(session_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_events_go_with_their_session_and_workspace() {
+        let (_dir, db, store) = setup_db();
+        let repo = Repository::from_local_path("conduit", "/tmp/conduit".into());
+        RepositoryStore::new(db.connection()).create(&repo).unwrap();
+        let workspace = Workspace::new(repo.id, "journal", "journal", "/tmp/journal".into());
+        WorkspaceStore::new(db.connection())
+            .create(&workspace)
+            .unwrap();
+        let tabs = SessionTabStore::new(db.connection());
+        let in_workspace =
+            SessionTab::new(0, AgentType::Claude, Some(workspace.id), None, None, None);
+        let deleted = SessionTab::new(1, AgentType::Codex, None, None, None, None);
+        let kept = SessionTab::new(2, AgentType::Codex, None, None, None, None);
+        for tab in [&in_workspace, &deleted, &kept] {
+            tabs.create(tab).unwrap();
+            store.append(tab.id, &assistant("hi")).unwrap();
+        }
+
+        assert_eq!(store.delete_by_workspace(workspace.id).unwrap(), 1);
+        assert_eq!(store.count_by_session(in_workspace.id).unwrap(), 0);
+
+        tabs.delete(deleted.id).unwrap();
+        assert_eq!(store.count_by_session(deleted.id).unwrap(), 0);
+        assert_eq!(store.count_by_session(kept.id).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_truncate_turns_from() {
+        let (_dir, _db, store) = setup_db();
+        let session_id = Uuid::new_v4();
+        let start = Utc::now();
+        let prompt = |text: &str| SessionEventPayload::UserPrompt {
+            text: text.to_string(),
+            hidden: false,
+            resumed: false,
+        };
+        let at = |seconds: i64| start + chrono::Duration::seconds(seconds);
+
+        store
+            .append_batch(&[
+                (session_id, prompt("first"), at(0)),
+                (session_id, assistant("one"), at(2)),
+                (session_id, prompt("second"), at(10)),
+                (session_id, assistant("two"), at(12)),
+            ])
+            .unwrap();
+
+        // The second turn started shortly after its prompt was recorded
+        assert_eq!(store.truncate_turns_from(session_id, at(11)).unwrap(), 2);
+        let events = store.get_by_session(session_id).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[1].payload,
+            SessionEventPayload::Agent { .. }
+        ));
+        assert_eq!(store.truncate_turns_from(session_id, at(-5)).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_unparseable_rows_are_skipped() {
+        let (_dir, db, store) = setup_db();
+        let session_id = Uuid::new_v4();
+        store.append(session_id, &assistant("ok")).unwrap();
+        db.with_connection(|conn| {
+            conn.execute(
+                "INSERT INTO session_events (session_id, event_type, payload, created_at)
+                 VALUES (?1, 'Future', '{\"kind\":\"future\"}', ?2)",
+                params![session_id.to_string(), Utc::now().to_rfc3339()],
+            )
+        })
+        .unwrap();
+
+        let events = store.get_by_session(session_id).unwrap();
+        assert_eq!(events.len(), 1);
+    }
+}
@@ -163,6 +163,26 @@ impl SessionTabStore {
         Ok(tabs)
     }
 
+    /// Get all session tabs (open and closed) that don't belong to archived workspaces
+    pub fn get_all_including_closed(&self) -> SqliteResult<Vec<SessionTab>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT st.id, st.tab_index, st.is_open, st.workspace_id, st.agent_type, st.agent_mode, st.agent_session_id, st.model, st.model_invalid, st.pr_number, st.created_at, st.pending_user_message, st.queued_messages, st.input_history, st.fork_seed_id, st.title, st.title_generated
+             FROM session_tabs st
+             LEFT JOIN workspaces w ON st.workspace_id = w.id
+             WHERE st.workspace_id IS NULL
+                OR (w.id IS NOT NULL AND w.archived_at IS NULL)
+             ORDER BY st.created_at DESC",
+        )?;
+
+        let tabs = stmt
+            .query_map([], Self::row_to_session_tab)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(tabs)
+    }
+
     /// Get a session tab by ID
     pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<SessionTab>> {
         let conn = self.conn.lock().unwrap();
@@ -489,6 +509,20 @@ mod tests {
         assert_eq!(all[1].tab_index, 1);
     }
 
+    #[test]
+    fn test_get_all_including_closed() {
+        let (_dir, _db, dao) = setup_db();
+
+        let open = SessionTab::new(0, AgentType::Claude, None, None, None, None);
+        let closed = SessionTab::new(1, AgentType::Codex, None, None, None, None);
+        dao.create(&open).unwrap();
+        dao.create(&closed).unwrap();
+        dao.set_open(closed.id, false).unwrap();
+
+        assert_eq!(dao.get_all().unwrap().len(), 1);
+        assert_eq!(dao.get_all_including_closed().unwrap().len(), 2);
+    }
+
     #[test]
     fn test_clear_all() {
         let (_dir, _db, dao) = setup_db();
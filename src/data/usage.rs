@@ -0,0 +1,298 @@
+//! Per-turn usage and cost data access object
+//!
+//! Each completed agent turn is stored with the workspace and repository it
+//! ran in, so totals survive session and workspace deletion.
+
+use super::models::{UsageBucket, UsageDimension, UsageRecord, UsageTotals};
+use crate::agent::AgentType;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult, Row, ToSql};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Aggregate columns shared by the totals and breakdown queries
+const TOTALS_COLUMNS: &str = "COUNT(*), COALESCE(SUM(u.input_tokens), 0),
+    COALESCE(SUM(u.output_tokens), 0), COALESCE(SUM(u.cached_tokens), 0),
+    COALESCE(SUM(u.cache_write_tokens), 0), COALESCE(SUM(u.cost_usd), 0.0),
+    COALESCE(SUM(u.duration_ms), 0), COUNT(u.duration_ms)";
+
+/// Data access object for recorded turn usage
+#[derive(Clone)]
+pub struct UsageStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl UsageStore {
+    /// Create a new UsageStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Record a completed turn, returning its row ID
+    pub fn record(&self, record: &UsageRecord) -> SqliteResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO usage_records (session_id, workspace_id, repository_id, agent_type, model,
+                input_tokens, output_tokens, cached_tokens, cache_write_tokens, cost_usd, duration_ms,
+                recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                record.session_id.to_string(),
+                record.workspace_id.map(|id| id.to_string()),
+                record.repository_id.map(|id| id.to_string()),
+                record.agent_type.as_str(),
+                record.model,
+                record.input_tokens,
+                record.output_tokens,
+                record.cached_tokens,
+                record.cache_write_tokens,
+                record.cost_usd,
+                record.duration_ms,
+                record.recorded_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Totals for a single session
+    pub fn totals_for_session(&self, session_id: Uuid) -> SqliteResult<UsageTotals> {
+        self.totals_where("session_id = ?1", &[&session_id.to_string()])
+    }
+
+    /// Totals for all sessions that ran in a workspace
+    pub fn totals_for_workspace(&self, workspace_id: Uuid) -> SqliteResult<UsageTotals> {
+        self.totals_where("workspace_id = ?1", &[&workspace_id.to_string()])
+    }
+
+    /// Totals for all workspaces of a repository
+    pub fn totals_for_repository(&self, repository_id: Uuid) -> SqliteResult<UsageTotals> {
+        self.totals_where("repository_id = ?1", &[&repository_id.to_string()])
+    }
+
+    /// Totals for all turns completed at or after `since`
+    pub fn totals_since(&self, since: DateTime<Utc>) -> SqliteResult<UsageTotals> {
+        self.totals_where("recorded_at >= ?1", &[&since.to_rfc3339()])
+    }
+
+    /// Totals for every recorded turn
+    pub fn totals(&self) -> SqliteResult<UsageTotals> {
+        self.totals_where("1 = 1", &[])
+    }
+
+    /// Totals grouped by `dimension`, optionally limited to turns completed at or after `since`.
+    ///
+    /// Days are returned oldest first; other dimensions by descending cost.
+    pub fn breakdown(
+        &self,
+        dimension: UsageDimension,
+        since: Option<DateTime<Utc>>,
+    ) -> SqliteResult<Vec<UsageBucket>> {
+        // (key, agent type, name, join, group by)
+        let (key, agent, name, join, group) = match dimension {
+            UsageDimension::Day => ("date(u.recorded_at, 'localtime')", "NULL", "NULL", "", "1"),
+            UsageDimension::Agent => ("u.agent_type", "u.agent_type", "NULL", "", "1"),
+            UsageDimension::Model => ("u.model", "u.agent_type", "NULL", "", "1, 2"),
+            UsageDimension::Repository => (
+                "u.repository_id",
+                "NULL",
+                "MAX(r.name)",
+                "LEFT JOIN repositories r ON r.id = u.repository_id",
+                "1",
+            ),
+            UsageDimension::Workspace => (
+                "u.workspace_id",
+                "NULL",
+                "MAX(w.name)",
+                "LEFT JOIN workspaces w ON w.id = u.workspace_id",
+                "1",
+            ),
+        };
+        // Columns 9 and 4 are cost and turn count
+        let order = match dimension {
+            UsageDimension::Day => "1 ASC",
+            _ => "9 DESC, 4 DESC",
+        };
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {key}, {agent}, {name}, {TOTALS_COLUMNS}
+             FROM usage_records u {join}
+             WHERE ?1 IS NULL OR u.recorded_at >= ?1
+             GROUP BY {group}
+             ORDER BY {order}"
+        ))?;
+        let buckets = stmt
+            .query_map([since.map(|since| since.to_rfc3339())], |row| {
+                let agent_type: Option<String> = row.get(1)?;
+                Ok(UsageBucket {
+                    key: row.get(0)?,
+                    agent_type: agent_type.as_deref().map(AgentType::parse),
+                    name: row.get(2)?,
+                    totals: Self::totals_from_row(row, 3)?,
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(buckets)
+    }
+
+    fn totals_where(&self, condition: &str, params: &[&dyn ToSql]) -> SqliteResult<UsageTotals> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM usage_records u WHERE {}",
+                TOTALS_COLUMNS, condition
+            ),
+            params,
+            |row| Self::totals_from_row(row, 0),
+        )
+    }
+
+    fn totals_from_row(row: &Row<'_>, offset: usize) -> SqliteResult<UsageTotals> {
+        Ok(UsageTotals {
+            turns: row.get(offset)?,
+            input_tokens: row.get(offset + 1)?,
+            output_tokens: row.get(offset + 2)?,
+            cached_tokens: row.get(offset + 3)?,
+            cache_write_tokens: row.get(offset + 4)?,
+            cost_usd: row.get(offset + 5)?,
+            duration_ms: row.get(offset + 6)?,
+            timed_turns: row.get(offset + 7)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    fn setup_db() -> (tempfile::TempDir, Database, UsageStore) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = UsageStore::new(db.connection());
+        (dir, db, store)
+    }
+
+    fn record(
+        session_id: Uuid,
+        workspace_id: Option<Uuid>,
+        repository_id: Option<Uuid>,
+        cost_usd: f64,
+        recorded_at: DateTime<Utc>,
+    ) -> UsageRecord {
+        UsageRecord {
+            session_id,
+            workspace_id,
+            repository_id,
+            agent_type: AgentType::Claude,
+            model: Some("sonnet".to_string()),
+            input_tokens: 1_000,
+            output_tokens: 100,
+            cached_tokens: 400,
+            cache_write_tokens: 0,
+            cost_usd,
+            duration_ms: Some(2_000),
+            recorded_at,
+        }
+    }
+
+    #[test]
+    fn test_totals_by_session_workspace_and_repository() {
+        let (_dir, _db, store) = setup_db();
+        let repo = Uuid::new_v4();
+        let workspace = Uuid::new_v4();
+        let session = Uuid::new_v4();
+        let other_session = Uuid::new_v4();
+        let now = Utc::now();
+
+        store
+            .record(&record(session, Some(workspace), Some(repo), 0.25, now))
+            .unwrap();
+        store
+            .record(&record(session, Some(workspace), Some(repo), 0.5, now))
+            .unwrap();
+        store
+            .record(&record(other_session, None, None, 1.0, now))
+            .unwrap();
+
+        let totals = store.totals_for_session(session).unwrap();
+        assert_eq!(totals.turns, 2);
+        assert_eq!(totals.input_tokens, 2_000);
+        assert_eq!(totals.cached_tokens, 800);
+        assert!((totals.cost_usd - 0.75).abs() < 1e-9);
+
+        assert_eq!(store.totals_for_workspace(workspace).unwrap().turns, 2);
+        assert_eq!(store.totals_for_repository(repo).unwrap().turns, 2);
+        assert_eq!(
+            store.totals_for_session(Uuid::new_v4()).unwrap(),
+            UsageTotals::default()
+        );
+    }
+
+    #[test]
+    fn test_totals_since() {
+        let (_dir, _db, store) = setup_db();
+        let session = Uuid::new_v4();
+        let now = Utc::now();
+
+        store
+            .record(&record(session, None, None, 2.0, now - Duration::days(2)))
+            .unwrap();
+        store
+            .record(&record(session, None, None, 0.5, now))
+            .unwrap();
+
+        let totals = store.totals_since(now - Duration::hours(1)).unwrap();
+        assert_eq!(totals.turns, 1);
+        assert!((totals.cost_usd - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_breakdown_groups_and_orders_buckets() {
+        let (_dir, db, store) = setup_db();
+        let repo_store = crate::data::RepositoryStore::new(db.connection());
+        let repo = crate::data::Repository::from_local_path("conduit", "/tmp/conduit".into());
+        repo_store.create(&repo).unwrap();
+        let session = Uuid::new_v4();
+        let now = Utc::now();
+
+        store
+            .record(&record(session, None, Some(repo.id), 0.5, now))
+            .unwrap();
+        store
+            .record(&record(session, None, Some(repo.id), 0.25, now))
+            .unwrap();
+        let mut untimed = record(session, None, None, 1.0, now - Duration::days(3));
+        untimed.agent_type = AgentType::Codex;
+        untimed.model = None;
+        untimed.duration_ms = None;
+        store.record(&untimed).unwrap();
+
+        let by_repo = store.breakdown(UsageDimension::Repository, None).unwrap();
+        assert_eq!(by_repo.len(), 2);
+        assert_eq!(by_repo[0].key, None);
+        assert_eq!(by_repo[1].key, Some(repo.id.to_string()));
+        assert_eq!(by_repo[1].name.as_deref(), Some("conduit"));
+        assert_eq!(by_repo[1].totals.turns, 2);
+        assert_eq!(by_repo[1].totals.average_duration_ms(), Some(2_000));
+        assert_eq!(by_repo[0].totals.average_duration_ms(), None);
+
+        let by_agent = store.breakdown(UsageDimension::Agent, None).unwrap();
+        assert_eq!(by_agent[0].agent_type, Some(AgentType::Codex));
+        assert_eq!(by_agent[1].agent_type, Some(AgentType::Claude));
+
+        let by_day = store.breakdown(UsageDimension::Day, None).unwrap();
+        assert_eq!(by_day.len(), 2);
+        assert!(by_day[0].key < by_day[1].key);
+
+        let recent = store
+            .breakdown(UsageDimension::Model, Some(now - Duration::hours(1)))
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].key.as_deref(), Some("sonnet"));
+        assert_eq!(recent[0].agent_type, Some(AgentType::Claude));
+        assert_eq!(store.totals().unwrap().turns, 3);
+    }
+}
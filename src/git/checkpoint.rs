@@ -0,0 +1,236 @@
+//! Worktree checkpoints
+//!
+//! A checkpoint is a commit of the whole working tree (tracked and untracked
+//! files, ignored files excluded) written through a scratch index, so taking
+//! one never touches the user's index, stash or branch. Checkpoint commits are
+//! kept reachable by refs under `refs/conduit/checkpoints/`.
+
+use std::path::Path;
+
+use uuid::Uuid;
+
+use super::worktree::WorktreeError;
+use super::{git, git_with};
+
+/// Namespace for checkpoint refs
+pub const CHECKPOINT_REF_PREFIX: &str = "refs/conduit/checkpoints";
+
+/// Snapshots and restores workspace state
+pub struct CheckpointManager;
+
+impl CheckpointManager {
+    /// Ref name for a session's checkpoint before `turn`
+    pub fn ref_name(session_id: Uuid, turn: u32) -> String {
+        format!("{}/{}/{}", CHECKPOINT_REF_PREFIX, session_id, turn)
+    }
+
+    /// Snapshot the working tree into a commit kept alive by `ref_name`.
+    ///
+    /// The commit's parent is the current HEAD (if any). Returns the commit SHA.
+    pub fn create(
+        working_dir: &Path,
+        ref_name: &str,
+        message: &str,
+    ) -> Result<String, WorktreeError> {
+        let head = rev_parse(working_dir, "HEAD");
+        let tree = snapshot_tree(working_dir, head.as_deref())?;
+
+        let mut args = vec!["commit-tree", tree.as_str(), "-m", message];
+        if let Some(head) = head.as_deref() {
+            args.extend(["-p", head]);
+        }
+        let commit = git(working_dir, &args)?.trim().to_string();
+        git(working_dir, &["update-ref", ref_name, &commit])?;
+        Ok(commit)
+    }
+
+    /// Restore the working tree to a checkpoint.
+    ///
+    /// Files created since the checkpoint are removed and changed or deleted
+    /// files are rewritten. HEAD and the index are reset to the commit the
+    /// checkpoint was taken on, so commits made since are undone as well
+    /// (staged changes come back as unstaged). Ignored files are left alone.
+    pub fn restore(working_dir: &Path, commit: &str) -> Result<(), WorktreeError> {
+        let target_tree = format!("{}^{{tree}}", commit);
+        if rev_parse(working_dir, &target_tree).is_none() {
+            return Err(WorktreeError::CommandFailed(format!(
+                "Checkpoint {} no longer exists",
+                commit
+            )));
+        }
+        let parent = rev_parse(working_dir, &format!("{}^", commit));
+        let head = rev_parse(working_dir, "HEAD");
+        let current_tree = snapshot_tree(working_dir, head.as_deref())?;
+
+        // Remove files that did not exist at the checkpoint
+        let added = git(
+            working_dir,
+            &[
+                "diff-tree",
+                "-r",
+                "-z",
+                "--name-only",
+                "--no-renames",
+                "--diff-filter=A",
+                &target_tree,
+                &current_tree,
+            ],
+        )?;
+        for path in added.split('\0').filter(|path| !path.is_empty()) {
+            remove_with_empty_parents(working_dir, Path::new(path))?;
+        }
+
+        // Write the checkpoint's files through a scratch index
+        let scratch = tempfile::tempdir()?;
+        let index = scratch.path().join("index");
+        git_with(working_dir, Some(&index), &[], &["read-tree", &target_tree])?;
+        git_with(
+            working_dir,
+            Some(&index),
+            &[],
+            &["checkout-index", "-a", "-f"],
+        )?;
+
+        match parent {
+            Some(parent) => git(working_dir, &["reset", "-q", "--mixed", &parent])?,
+            None => git(working_dir, &["read-tree", "--empty"])?,
+        };
+        Ok(())
+    }
+
+    /// Delete a checkpoint ref (missing refs are not an error)
+    pub fn delete(working_dir: &Path, ref_name: &str) -> Result<(), WorktreeError> {
+        if rev_parse(working_dir, ref_name).is_none() {
+            return Ok(());
+        }
+        git(working_dir, &["update-ref", "-d", ref_name]).map(|_| ())
+    }
+}
+
+/// Write the working tree (minus ignored files) as a tree object.
+///
+/// Starts from a copy of the real index so unchanged files are not rehashed.
+fn snapshot_tree(working_dir: &Path, head: Option<&str>) -> Result<String, WorktreeError> {
+    let scratch = tempfile::tempdir()?;
+    let index = scratch.path().join("index");
+    let real_index = git(working_dir, &["rev-parse", "--git-path", "index"])?;
+    let real_index = working_dir.join(real_index.trim());
+    if real_index.exists() {
+        std::fs::copy(&real_index, &index)?;
+    } else if let Some(head) = head {
+        git_with(working_dir, Some(&index), &[], &["read-tree", head])?;
+    }
+    git_with(working_dir, Some(&index), &[], &["add", "-A", "--", "."])?;
+    Ok(git_with(working_dir, Some(&index), &[], &["write-tree"])?
+        .trim()
+        .to_string())
+}
+
+/// Remove a file, then any directories it leaves empty (up to `working_dir`).
+fn remove_with_empty_parents(working_dir: &Path, path: &Path) -> Result<(), WorktreeError> {
+    match std::fs::remove_file(working_dir.join(path)) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    }
+    let mut parent = path.parent();
+    while let Some(dir) = parent.filter(|dir| !dir.as_os_str().is_empty()) {
+        if std::fs::remove_dir(working_dir.join(dir)).is_err() {
+            break;
+        }
+        parent = dir.parent();
+    }
+    Ok(())
+}
+
+fn rev_parse(working_dir: &Path, rev: &str) -> Option<String> {
+    git(working_dir, &["rev-parse", "--verify", "--quiet", rev])
+        .ok()
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn git_in(path: &Path, args: &[&str]) -> String {
+        git(path, args).unwrap()
+    }
+
+    #[test]
+    fn test_create_and_restore_checkpoint() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        git_in(path, &["init"]);
+        git_in(path, &["config", "user.email", "test@test.com"]);
+        git_in(path, &["config", "user.name", "Test"]);
+        std::fs::write(path.join(".gitignore"), "build/\n").unwrap();
+        std::fs::write(path.join("kept.txt"), "kept\n").unwrap();
+        std::fs::write(path.join("removed.txt"), "removed\n").unwrap();
+        git_in(path, &["add", "."]);
+        git_in(path, &["commit", "-m", "Initial commit"]);
+        let initial_head = git_in(path, &["rev-parse", "HEAD"]);
+
+        // Uncommitted and untracked work at the checkpoint is preserved
+        std::fs::write(path.join("kept.txt"), "kept\nedited\n").unwrap();
+        std::fs::write(path.join("notes.txt"), "notes\n").unwrap();
+        git_in(path, &["add", "kept.txt"]);
+
+        let session_id = Uuid::new_v4();
+        let ref_name = CheckpointManager::ref_name(session_id, 1);
+        let commit = CheckpointManager::create(path, &ref_name, "checkpoint").unwrap();
+        assert_eq!(git_in(path, &["rev-parse", &ref_name]).trim(), commit);
+        // Taking a checkpoint leaves the index alone
+        assert_eq!(
+            git_in(path, &["diff", "--cached", "--name-only"]),
+            "kept.txt\n"
+        );
+
+        // The agent wrecks the workspace and commits
+        std::fs::write(path.join("kept.txt"), "garbage\n").unwrap();
+        std::fs::remove_file(path.join("removed.txt")).unwrap();
+        std::fs::create_dir_all(path.join("src/nested")).unwrap();
+        std::fs::write(path.join("src/nested/new.rs"), "fn main() {}\n").unwrap();
+        std::fs::create_dir_all(path.join("build")).unwrap();
+        std::fs::write(path.join("build/out.o"), "binary").unwrap();
+        git_in(path, &["add", "-A"]);
+        git_in(path, &["commit", "-m", "Agent commit"]);
+
+        CheckpointManager::restore(path, &commit).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path.join("kept.txt")).unwrap(),
+            "kept\nedited\n"
+        );
+        assert!(path.join("removed.txt").exists());
+        assert!(path.join("notes.txt").exists());
+        assert!(!path.join("src").exists());
+        // Ignored files are not touched
+        assert!(path.join("build/out.o").exists());
+        assert_eq!(git_in(path, &["rev-parse", "HEAD"]), initial_head);
+
+        CheckpointManager::delete(path, &ref_name).unwrap();
+        assert!(rev_parse(path, &ref_name).is_none());
+        CheckpointManager::delete(path, &ref_name).unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_in_repository_without_commits() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        git_in(path, &["init"]);
+        git_in(path, &["config", "user.email", "test@test.com"]);
+        git_in(path, &["config", "user.name", "Test"]);
+        std::fs::write(path.join("a.txt"), "a\n").unwrap();
+
+        let ref_name = CheckpointManager::ref_name(Uuid::new_v4(), 1);
+        let commit = CheckpointManager::create(path, &ref_name, "checkpoint").unwrap();
+        std::fs::write(path.join("a.txt"), "changed\n").unwrap();
+        std::fs::write(path.join("b.txt"), "b\n").unwrap();
+
+        CheckpointManager::restore(path, &commit).unwrap();
+        assert_eq!(std::fs::read_to_string(path.join("a.txt")).unwrap(), "a\n");
+        assert!(!path.join("b.txt").exists());
+    }
+}
@@ -0,0 +1,359 @@
+//! Failing CI checks
+//!
+//! Collects the failing checks of a workspace's PR through the GitHub CLI,
+//! along with the tail of their GitHub Actions logs, and turns them into a
+//! prompt asking the agent to fix the build.
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use super::gh::{current_pr, require_gh, run_gh, GhError};
+
+/// Lines of log kept per failing check
+const LOG_TAIL_LINES: usize = 80;
+/// Characters of log kept per failing check
+const LOG_TAIL_CHARS: usize = 6000;
+/// Error lines pulled out of each log for the summary
+const MAX_ERROR_LINES: usize = 15;
+
+/// `<timestamp> ` prefix on each GitHub Actions log line
+static TIMESTAMP_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?Z ?").expect("Invalid regex pattern")
+});
+
+/// ANSI color and cursor escape sequences
+static ANSI_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").expect("Invalid regex pattern"));
+
+/// Job ID in a GitHub Actions check link
+static JOB_LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"/actions/runs/\d+/job/(\d+)").expect("Invalid regex pattern"));
+
+/// A check that failed on the PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedCheck {
+    pub name: String,
+    /// Workflow the check belongs to, for GitHub Actions checks
+    pub workflow: Option<String>,
+    pub description: Option<String>,
+    /// Details page of the check
+    pub link: Option<String>,
+    /// Tail of the failed steps' log, when it could be fetched
+    pub log: Option<String>,
+    /// Lines of the log that look like errors
+    pub error_lines: Vec<String>,
+}
+
+/// Failing checks of a pull request
+#[derive(Debug, Clone, Default)]
+pub struct CiFailures {
+    pub pr_number: u32,
+    pub pr_url: String,
+    pub checks: Vec<FailedCheck>,
+    /// Checks still running
+    pub pending: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhCheck {
+    name: String,
+    #[serde(default)]
+    bucket: String,
+    #[serde(default)]
+    link: String,
+    #[serde(default)]
+    workflow: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// Reads failing CI checks through `gh`
+pub struct CiManager;
+
+impl CiManager {
+    /// Failing checks on the PR for the current branch, with their logs
+    pub fn failing_checks(working_dir: &Path) -> Result<CiFailures, GhError> {
+        require_gh()?;
+        let pr = current_pr(working_dir)?;
+
+        // `gh pr checks` exits non-zero when checks fail or are pending, so
+        // its stdout is parsed regardless of the exit status
+        let output = run_gh(
+            working_dir,
+            &[
+                "pr",
+                "checks",
+                "--json",
+                "name,bucket,link,workflow,description",
+            ],
+        )?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (mut checks, pending) = if stdout.trim().is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if output.status.success() || stderr.contains("no checks reported") {
+                (Vec::new(), 0)
+            } else {
+                return Err(GhError::CommandFailed(stderr.trim().to_string()));
+            }
+        } else {
+            parse_checks(&stdout)?
+        };
+
+        for check in &mut checks {
+            let Some(job_id) = check.link.as_deref().and_then(job_id_from_link) else {
+                continue;
+            };
+            // Logs are best effort: the check is still reported without them
+            let Ok(output) = run_gh(
+                working_dir,
+                &["run", "view", "--job", &job_id, "--log-failed"],
+            ) else {
+                continue;
+            };
+            if !output.status.success() {
+                tracing::debug!(
+                    check = %check.name,
+                    stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+                    "Failed to fetch check log"
+                );
+                continue;
+            }
+            let log = clean_log(&String::from_utf8_lossy(&output.stdout));
+            check.error_lines = extract_error_lines(&log);
+            let tail = tail_log(&log);
+            if !tail.is_empty() {
+                check.log = Some(tail);
+            }
+        }
+
+        Ok(CiFailures {
+            pr_number: pr.number,
+            pr_url: pr.url,
+            checks,
+            pending,
+        })
+    }
+
+    /// Prompt asking the agent to fix the failing checks
+    pub fn build_fix_prompt(failures: &CiFailures, branch: Option<&str>) -> String {
+        let target = match branch {
+            Some(branch) => format!("PR #{} (branch `{}`)", failures.pr_number, branch),
+            None => format!("PR #{}", failures.pr_number),
+        };
+        let mut prompt = format!(
+            "CI is failing on {}. Investigate and fix the failures below.\n",
+            target
+        );
+        for check in &failures.checks {
+            let name = match &check.workflow {
+                Some(workflow) => format!("{} / {}", workflow, check.name),
+                None => check.name.clone(),
+            };
+            prompt.push_str(&format!("\n## {}\n", name));
+            if let Some(description) = &check.description {
+                prompt.push_str(&format!("{}\n", description));
+            }
+            if !check.error_lines.is_empty() {
+                prompt.push_str("\nErrors:\n");
+                for line in &check.error_lines {
+                    prompt.push_str(&format!("- {}\n", line));
+                }
+            }
+            match &check.log {
+                Some(log) => prompt.push_str(&format!("\nEnd of the log:\n```\n{}\n```\n", log)),
+                None => {
+                    if let Some(link) = &check.link {
+                        prompt.push_str(&format!("\nNo log available; see {}\n", link));
+                    }
+                }
+            }
+        }
+        prompt.push_str(
+            "\nReproduce each failure locally where you can, fix the cause rather than the \
+             symptom, and run the relevant checks before finishing. Do not disable or skip \
+             tests to make CI pass. Finish with a short summary of what was wrong and what \
+             you changed.",
+        );
+        prompt
+    }
+}
+
+/// Failing checks and the number of pending ones from `gh pr checks --json`
+fn parse_checks(json: &str) -> Result<(Vec<FailedCheck>, usize), GhError> {
+    let checks: Vec<GhCheck> =
+        serde_json::from_str(json).map_err(|e| GhError::ParseError(e.to_string()))?;
+    let pending = checks.iter().filter(|c| c.bucket == "pending").count();
+    let failed = checks
+        .into_iter()
+        .filter(|c| c.bucket == "fail")
+        .map(|c| FailedCheck {
+            name: c.name,
+            workflow: non_empty(c.workflow),
+            description: non_empty(c.description),
+            link: non_empty(c.link),
+            log: None,
+            error_lines: Vec::new(),
+        })
+        .collect();
+    Ok((failed, pending))
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn job_id_from_link(link: &str) -> Option<String> {
+    JOB_LINK_REGEX
+        .captures(link)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Strip the `job\tstep\t` columns, timestamps and escape codes that
+/// `gh run view --log-failed` puts on each line
+fn clean_log(raw: &str) -> String {
+    raw.lines()
+        .map(|line| {
+            let message = line.splitn(3, '\t').nth(2).unwrap_or(line);
+            let message = message.trim_start_matches('\u{feff}');
+            let message = TIMESTAMP_REGEX.replace(message, "");
+            ANSI_REGEX.replace_all(&message, "").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Last lines of the log, capped in size
+fn tail_log(log: &str) -> String {
+    let lines: Vec<&str> = log.lines().collect();
+    let start = lines.len().saturating_sub(LOG_TAIL_LINES);
+    let mut tail = lines[start..].join("\n");
+    if tail.len() > LOG_TAIL_CHARS {
+        let mut cut = tail.len() - LOG_TAIL_CHARS;
+        while !tail.is_char_boundary(cut) {
+            cut += 1;
+        }
+        // Start on a line boundary so the first line isn't half a message
+        let cut = tail[cut..].find('\n').map_or(cut, |i| cut + i + 1);
+        tail = tail[cut..].to_string();
+    }
+    tail.trim().to_string()
+}
+
+/// Lines that look like compiler errors, test failures or panics
+fn extract_error_lines(log: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in log.lines() {
+        let trimmed = line.trim();
+        let lower = trimmed.to_lowercase();
+        let is_error = lower.starts_with("error")
+            || lower.contains("error:")
+            || lower.contains("##[error]")
+            || lower.contains("panicked at")
+            || lower.contains("assertion")
+            || lower.contains(" failed")
+            || lower.starts_with("failed");
+        if !is_error {
+            continue;
+        }
+        let line = trimmed.trim_start_matches("##[error]").trim().to_string();
+        if line.is_empty() || lines.contains(&line) {
+            continue;
+        }
+        lines.push(line);
+        if lines.len() == MAX_ERROR_LINES {
+            break;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKS: &str = r#"[
+      {"name": "test", "bucket": "fail", "workflow": "CI", "description": "",
+       "link": "https://github.com/o/r/actions/runs/123/job/456"},
+      {"name": "lint", "bucket": "pass", "workflow": "CI", "description": "",
+       "link": "https://github.com/o/r/actions/runs/123/job/457"},
+      {"name": "deploy", "bucket": "pending", "workflow": "", "description": "", "link": ""},
+      {"name": "ci/external", "bucket": "fail", "workflow": "",
+       "description": "Build failed", "link": "https://ci.example.com/build/9"}
+    ]"#;
+
+    #[test]
+    fn test_parse_checks_keeps_failures() {
+        let (checks, pending) = parse_checks(CHECKS).unwrap();
+        assert_eq!(pending, 1);
+        let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["test", "ci/external"]);
+        assert_eq!(checks[0].workflow.as_deref(), Some("CI"));
+        assert_eq!(checks[0].description, None);
+        assert_eq!(checks[1].description.as_deref(), Some("Build failed"));
+
+        assert_eq!(
+            job_id_from_link(checks[0].link.as_deref().unwrap()).as_deref(),
+            Some("456")
+        );
+        assert_eq!(job_id_from_link(checks[1].link.as_deref().unwrap()), None);
+    }
+
+    #[test]
+    fn test_clean_log_and_error_lines() {
+        let raw = "test\tRun tests\t\u{feff}2024-05-01T10:00:00.1234567Z running 2 tests\n\
+                   test\tRun tests\t2024-05-01T10:00:01.0000000Z \x1b[31merror[E0425]\x1b[0m: cannot find value `x`\n\
+                   test\tRun tests\t2024-05-01T10:00:02.0000000Z thread 'main' panicked at src/lib.rs:3:5\n\
+                   test\tRun tests\t2024-05-01T10:00:03.0000000Z ##[error]Process completed with exit code 101.\n\
+                   test\tRun tests\t2024-05-01T10:00:03.0000000Z ##[error]Process completed with exit code 101.";
+        let log = clean_log(raw);
+        assert!(log.starts_with("running 2 tests\nerror[E0425]: cannot find value `x`"));
+
+        let errors = extract_error_lines(&log);
+        assert_eq!(
+            errors,
+            vec![
+                "error[E0425]: cannot find value `x`",
+                "thread 'main' panicked at src/lib.rs:3:5",
+                "Process completed with exit code 101.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tail_log_caps_size() {
+        let log = (0..200)
+            .map(|i| format!("line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let tail = tail_log(&log);
+        assert_eq!(tail.lines().count(), LOG_TAIL_LINES);
+        assert!(tail.ends_with("line 199"));
+
+        let long = "x".repeat(100) + "\n" + &"y".repeat(LOG_TAIL_CHARS);
+        assert_eq!(tail_log(&long), "y".repeat(LOG_TAIL_CHARS));
+    }
+
+    #[test]
+    fn test_build_fix_prompt() {
+        let (mut checks, pending) = parse_checks(CHECKS).unwrap();
+        checks[0].error_lines = vec!["error: boom".to_string()];
+        checks[0].log = Some("error: boom\nexit 1".to_string());
+        let failures = CiFailures {
+            pr_number: 7,
+            pr_url: "https://github.com/o/r/pull/7".to_string(),
+            checks,
+            pending,
+        };
+
+        let prompt = CiManager::build_fix_prompt(&failures, Some("feature"));
+        assert!(prompt.contains("PR #7 (branch `feature`)"));
+        assert!(prompt.contains("## CI / test\n\nErrors:\n- error: boom\n"));
+        assert!(prompt.contains("```\nerror: boom\nexit 1\n```"));
+        assert!(prompt.contains("## ci/external\nBuild failed\n"));
+        assert!(prompt.contains("No log available; see https://ci.example.com/build/9"));
+    }
+}
@@ -0,0 +1,249 @@
+//! Cloning remote repositories.
+
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde::Serialize;
+
+use super::worktree::WorktreeError;
+
+/// One step of `git clone --progress`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CloneProgress {
+    /// e.g. "Receiving objects"
+    pub phase: String,
+    pub percent: Option<u8>,
+}
+
+impl CloneProgress {
+    /// Parse a progress line such as `Receiving objects:  45% (450/1000), 1.2 MiB`
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim().trim_start_matches("remote:").trim();
+        let (phase, rest) = line.split_once(':')?;
+        // Errors ("fatal: ...") also look like "label: text"
+        if phase.is_empty() || !(rest.contains('%') || rest.trim_end().ends_with("done.")) {
+            return None;
+        }
+        let percent = rest
+            .trim_start()
+            .split_once('%')
+            .and_then(|(number, _)| number.trim().parse::<u8>().ok());
+        Some(Self {
+            phase: phase.to_string(),
+            percent,
+        })
+    }
+}
+
+impl std::fmt::Display for CloneProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.percent {
+            Some(percent) => write!(f, "{} {}%", self.phase, percent),
+            None => f.write_str(&self.phase),
+        }
+    }
+}
+
+pub struct CloneManager;
+
+impl CloneManager {
+    /// Whether the input looks like a remote URL rather than a local path
+    pub fn is_remote_url(input: &str) -> bool {
+        let input = input.trim();
+        if ["https://", "http://", "ssh://", "git://", "file://"]
+            .iter()
+            .any(|scheme| input.starts_with(scheme))
+        {
+            return true;
+        }
+        // scp-like syntax: user@host:path
+        match input.split_once(':') {
+            Some((host, path)) => host.contains('@') && !host.contains('/') && !path.is_empty(),
+            None => false,
+        }
+    }
+
+    /// Repository name from the last path segment of a URL
+    pub fn repo_name(url: &str) -> Option<String> {
+        let trimmed = url.trim().trim_end_matches('/');
+        let last = trimmed.rsplit(['/', ':']).next()?;
+        let name = last.strip_suffix(".git").unwrap_or(last);
+        if name.is_empty() || name == "." || name == ".." {
+            None
+        } else {
+            Some(name.to_string())
+        }
+    }
+
+    /// Clone `url` into `dest`, reporting progress as git prints it.
+    ///
+    /// Blocks until the clone finishes; a failed clone leaves nothing behind.
+    pub fn clone(
+        url: &str,
+        dest: &Path,
+        on_progress: &mut dyn FnMut(CloneProgress),
+    ) -> Result<(), WorktreeError> {
+        if dest.exists() {
+            return Err(WorktreeError::AlreadyExists(dest.to_path_buf()));
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut child = Command::new("git")
+            .args(["clone", "--progress", "--", url])
+            .arg(dest)
+            // Fail instead of waiting for credentials nobody can type
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Progress updates end in '\r'; everything else in '\n'
+        let Some(mut stderr) = child.stderr.take() else {
+            return Err(WorktreeError::CommandFailed(
+                "git clone output unavailable".to_string(),
+            ));
+        };
+        let mut messages = Vec::new();
+        let mut current = Vec::new();
+        let mut last = None;
+        let mut buf = [0u8; 4096];
+        loop {
+            let read = match stderr.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => read,
+            };
+            for &byte in &buf[..read] {
+                if byte != b'\r' && byte != b'\n' {
+                    current.push(byte);
+                    continue;
+                }
+                let line = String::from_utf8_lossy(&current).to_string();
+                current.clear();
+                match CloneProgress::parse(&line) {
+                    Some(progress) if last.as_ref() != Some(&progress) => {
+                        on_progress(progress.clone());
+                        last = Some(progress);
+                    }
+                    Some(_) => {}
+                    None if !line.trim().is_empty() => messages.push(line),
+                    None => {}
+                }
+            }
+        }
+        if !current.is_empty() {
+            messages.push(String::from_utf8_lossy(&current).to_string());
+        }
+
+        let status = child.wait()?;
+        if status.success() {
+            return Ok(());
+        }
+        if dest.exists() {
+            if let Err(e) = std::fs::remove_dir_all(dest) {
+                tracing::warn!(error = %e, path = %dest.display(), "Failed to remove failed clone");
+            }
+        }
+        let message = messages
+            .iter()
+            .filter(|line| !line.starts_with("Cloning into"))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(WorktreeError::CommandFailed(if message.is_empty() {
+            format!("git clone exited with {}", status)
+        } else {
+            message
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_is_remote_url() {
+        assert!(CloneManager::is_remote_url(
+            "https://github.com/acme/app.git"
+        ));
+        assert!(CloneManager::is_remote_url("git@github.com:acme/app.git"));
+        assert!(CloneManager::is_remote_url("ssh://git@host/acme/app"));
+        assert!(!CloneManager::is_remote_url("~/code/app"));
+        assert!(!CloneManager::is_remote_url("/home/me/app"));
+        assert!(!CloneManager::is_remote_url("C:\\code\\app"));
+    }
+
+    #[test]
+    fn test_repo_name() {
+        assert_eq!(
+            CloneManager::repo_name("https://github.com/acme/app.git").as_deref(),
+            Some("app")
+        );
+        assert_eq!(
+            CloneManager::repo_name("git@github.com:acme/tool/").as_deref(),
+            Some("tool")
+        );
+        assert_eq!(CloneManager::repo_name("https://host/.."), None);
+    }
+
+    #[test]
+    fn test_parse_progress() {
+        assert_eq!(
+            CloneProgress::parse("Receiving objects:  45% (450/1000), 1.20 MiB | 1.1 MiB/s"),
+            Some(CloneProgress {
+                phase: "Receiving objects".to_string(),
+                percent: Some(45),
+            })
+        );
+        assert_eq!(
+            CloneProgress::parse("remote: Counting objects: 100% (12/12), done."),
+            Some(CloneProgress {
+                phase: "Counting objects".to_string(),
+                percent: Some(100),
+            })
+        );
+        assert_eq!(CloneProgress::parse("Cloning into 'app'..."), None);
+        assert_eq!(
+            CloneProgress::parse("fatal: repository 'x' does not exist"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_clone_local_repository() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source");
+        std::fs::create_dir(&source).unwrap();
+        for args in [
+            vec!["init"],
+            vec!["config", "user.email", "test@test.com"],
+            vec!["config", "user.name", "Test"],
+            vec!["commit", "--allow-empty", "-m", "Initial commit"],
+        ] {
+            let status = Command::new("git")
+                .args(&args)
+                .current_dir(&source)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+
+        let url = format!("file://{}", source.display());
+        let dest = dir.path().join("repos/source");
+        CloneManager::clone(&url, &dest, &mut |_| {}).unwrap();
+        assert!(dest.join(".git").exists());
+
+        let err = CloneManager::clone(&url, &dest, &mut |_| {}).unwrap_err();
+        assert!(matches!(err, WorktreeError::AlreadyExists(_)));
+
+        let missing = format!("file://{}", dir.path().join("missing").display());
+        let failed = dir.path().join("repos/missing");
+        assert!(CloneManager::clone(&missing, &failed, &mut |_| {}).is_err());
+        assert!(!failed.exists());
+    }
+}
@@ -0,0 +1,195 @@
+//! Committing workspace changes
+//!
+//! Lists what a commit would contain, collects the diff used to generate a
+//! commit message, and commits (and optionally pushes) a workspace. When
+//! nothing is staged, a commit takes every change in the working tree.
+
+use std::path::Path;
+
+use super::git;
+use super::worktree::WorktreeError;
+
+/// A changed path from `git status`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    /// Path relative to the repository root (the new path for renames)
+    pub path: String,
+    /// Status in the index (`' '` when unchanged, `'?'` when untracked)
+    pub index_status: char,
+    /// Status in the working tree (`' '` when unchanged, `'?'` when untracked)
+    pub worktree_status: char,
+}
+
+impl ChangedFile {
+    pub fn is_untracked(&self) -> bool {
+        self.index_status == '?'
+    }
+
+    /// Whether the file has changes in the index
+    pub fn is_staged(&self) -> bool {
+        !self.is_untracked() && self.index_status != ' '
+    }
+
+    /// Whether the file has changes not yet in the index
+    pub fn is_unstaged(&self) -> bool {
+        self.is_untracked() || self.worktree_status != ' '
+    }
+}
+
+/// Commits and pushes workspace changes
+pub struct CommitManager;
+
+impl CommitManager {
+    /// Changed files in the working tree, sorted by path
+    pub fn changed_files(working_dir: &Path) -> Result<Vec<ChangedFile>, WorktreeError> {
+        let output = git(
+            working_dir,
+            &["status", "--porcelain=v1", "-z", "--untracked-files=all"],
+        )?;
+        let mut files = parse_porcelain_status(&output);
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Diff of what the next commit would contain, for message generation.
+    ///
+    /// This is the staged diff when anything is staged; otherwise every
+    /// tracked change plus the names of untracked files.
+    pub fn pending_diff(working_dir: &Path) -> Result<String, WorktreeError> {
+        let files = Self::changed_files(working_dir)?;
+        if files.iter().any(ChangedFile::is_staged) {
+            return git(working_dir, &["diff", "--cached", "--stat", "--patch"]);
+        }
+
+        let mut diff = if has_head(working_dir) {
+            git(working_dir, &["diff", "HEAD", "--stat", "--patch"])?
+        } else {
+            String::new()
+        };
+        for file in files.iter().filter(|file| file.is_untracked()) {
+            diff.push_str(&format!("New file: {}\n", file.path));
+        }
+        Ok(diff)
+    }
+
+    /// Commit pending changes and return the new commit's SHA.
+    ///
+    /// Only staged changes are committed when anything is staged; otherwise
+    /// all changes (including untracked files) are staged first.
+    pub fn commit(working_dir: &Path, message: &str) -> Result<String, WorktreeError> {
+        let files = Self::changed_files(working_dir)?;
+        if files.is_empty() {
+            return Err(WorktreeError::CommandFailed(
+                "Nothing to commit".to_string(),
+            ));
+        }
+        if !files.iter().any(ChangedFile::is_staged) {
+            git(working_dir, &["add", "-A", "--", "."])?;
+        }
+        git(working_dir, &["commit", "-q", "-m", message])?;
+        Ok(git(working_dir, &["rev-parse", "HEAD"])?.trim().to_string())
+    }
+
+    /// Push the current branch to `origin`, setting its upstream
+    pub fn push(working_dir: &Path) -> Result<(), WorktreeError> {
+        git(working_dir, &["push", "-q", "-u", "origin", "HEAD"]).map(|_| ())
+    }
+}
+
+/// Parse `git status --porcelain=v1 -z` output
+fn parse_porcelain_status(output: &str) -> Vec<ChangedFile> {
+    let mut files = Vec::new();
+    let mut fields = output.split('\0').filter(|field| !field.is_empty());
+    while let Some(field) = fields.next() {
+        let mut chars = field.chars();
+        let (Some(index_status), Some(worktree_status)) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        let path = field.get(3..).unwrap_or_default().to_string();
+        // Renames and copies are followed by the original path
+        if matches!(index_status, 'R' | 'C') || matches!(worktree_status, 'R' | 'C') {
+            fields.next();
+        }
+        files.push(ChangedFile {
+            path,
+            index_status,
+            worktree_status,
+        });
+    }
+    files
+}
+
+fn has_head(working_dir: &Path) -> bool {
+    git(working_dir, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn git_in(path: &Path, args: &[&str]) -> String {
+        git(path, args).unwrap()
+    }
+
+    fn init_repo(path: &Path) {
+        git_in(path, &["init"]);
+        git_in(path, &["config", "user.email", "test@test.com"]);
+        git_in(path, &["config", "user.name", "Test"]);
+        std::fs::write(path.join("a.txt"), "a\n").unwrap();
+        std::fs::write(path.join("b.txt"), "b\n").unwrap();
+        git_in(path, &["add", "."]);
+        git_in(path, &["commit", "-m", "Initial commit"]);
+    }
+
+    #[test]
+    fn test_parse_porcelain_status() {
+        let output = " M a.txt\0R  new.txt\0old.txt\0?? dir/c.txt\0AM d.txt\0";
+        let files = parse_porcelain_status(output);
+        assert_eq!(files.len(), 4);
+        assert_eq!(files[0].path, "a.txt");
+        assert!(!files[0].is_staged() && files[0].is_unstaged());
+        assert_eq!(files[1].path, "new.txt");
+        assert!(files[1].is_staged() && !files[1].is_unstaged());
+        assert_eq!(files[2].path, "dir/c.txt");
+        assert!(files[2].is_untracked() && !files[2].is_staged());
+        assert!(files[3].is_staged() && files[3].is_unstaged());
+    }
+
+    #[test]
+    fn test_commit_all_changes_when_nothing_staged() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        init_repo(path);
+        std::fs::write(path.join("a.txt"), "a\nedited\n").unwrap();
+        std::fs::write(path.join("new.txt"), "new\n").unwrap();
+
+        let diff = CommitManager::pending_diff(path).unwrap();
+        assert!(diff.contains("+edited"));
+        assert!(diff.contains("New file: new.txt"));
+
+        let sha = CommitManager::commit(path, "Edit a and add new").unwrap();
+        assert_eq!(git_in(path, &["rev-parse", "HEAD"]).trim(), sha);
+        assert!(CommitManager::changed_files(path).unwrap().is_empty());
+        assert!(CommitManager::commit(path, "Empty").is_err());
+    }
+
+    #[test]
+    fn test_commit_only_staged_changes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        init_repo(path);
+        std::fs::write(path.join("a.txt"), "a\nstaged\n").unwrap();
+        std::fs::write(path.join("b.txt"), "b\nunstaged\n").unwrap();
+        git_in(path, &["add", "a.txt"]);
+
+        let diff = CommitManager::pending_diff(path).unwrap();
+        assert!(diff.contains("+staged"));
+        assert!(!diff.contains("+unstaged"));
+
+        CommitManager::commit(path, "Stage a").unwrap();
+        let remaining = CommitManager::changed_files(path).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].path, "b.txt");
+    }
+}
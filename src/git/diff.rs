@@ -0,0 +1,955 @@
+//! Structured workspace diffs (files, hunks and lines) parsed from `git diff`
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::git;
+use super::pr::PrManager;
+use super::worktree::WorktreeError;
+
+/// Object ID of git's empty tree, used to diff repositories without commits
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Untracked files larger than this are listed without contents
+const MAX_UNTRACKED_FILE_SIZE: u64 = 1024 * 1024;
+
+/// What the working tree is compared against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffBase {
+    /// Uncommitted changes (staged and unstaged) against HEAD
+    #[default]
+    Head,
+    /// Everything since the branch left the repository's default branch
+    BaseBranch,
+}
+
+impl DiffBase {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "head" => Some(DiffBase::Head),
+            "base" | "base_branch" => Some(DiffBase::BaseBranch),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiffBase::Head => "head",
+            DiffBase::BaseBranch => "base_branch",
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            DiffBase::Head => DiffBase::BaseBranch,
+            DiffBase::BaseBranch => DiffBase::Head,
+        }
+    }
+}
+
+/// What to do with a single hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HunkAction {
+    /// Keep the change and add it to the index
+    Stage,
+    /// Discard the change from the working tree (and the index)
+    Revert,
+}
+
+impl HunkAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "stage" | "keep" | "accept" => Some(HunkAction::Stage),
+            "revert" | "discard" => Some(HunkAction::Revert),
+            _ => None,
+        }
+    }
+}
+
+/// How a file changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChangeKind {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Untracked,
+}
+
+impl FileChangeKind {
+    /// Single-letter marker, as in `git status --short`
+    pub fn marker(&self) -> char {
+        match self {
+            FileChangeKind::Added => 'A',
+            FileChangeKind::Modified => 'M',
+            FileChangeKind::Deleted => 'D',
+            FileChangeKind::Renamed => 'R',
+            FileChangeKind::Untracked => '?',
+        }
+    }
+}
+
+/// Kind of a line within a hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// A single line of a hunk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// Line number in the old file (None for added lines)
+    pub old_line: Option<usize>,
+    /// Line number in the new file (None for removed lines)
+    pub new_line: Option<usize>,
+    pub content: String,
+    /// The line is the last in its file and has no trailing newline
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub no_newline: bool,
+}
+
+/// A contiguous block of changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    /// Text after the `@@ … @@` range (usually the enclosing function)
+    pub section: String,
+    pub lines: Vec<DiffLine>,
+    /// The same change is already in the index (diffs against HEAD only)
+    pub staged: bool,
+    /// [`DiffHunk::content_hash`], for clients to prove which hunk they mean
+    pub hash: String,
+}
+
+impl DiffHunk {
+    /// The `@@ -a,b +c,d @@ section` header line
+    pub fn header(&self) -> String {
+        let mut header = format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_lines, self.new_start, self.new_lines
+        );
+        if !self.section.is_empty() {
+            header.push(' ');
+            header.push_str(&self.section);
+        }
+        header
+    }
+
+    /// Short hash of the header and every line, so a different hunk that
+    /// happens to cover the same ranges does not pass for this one
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.header().as_bytes());
+        for line in &self.lines {
+            let prefix: &[u8] = match line.kind {
+                DiffLineKind::Context => b"\n ",
+                DiffLineKind::Added => b"\n+",
+                DiffLineKind::Removed => b"\n-",
+            };
+            hasher.update(prefix);
+            hasher.update(line.content.as_bytes());
+            if line.no_newline {
+                hasher.update(b"\n\\");
+            }
+        }
+        let mut hash = format!("{:x}", hasher.finalize());
+        hash.truncate(16);
+        hash
+    }
+
+    /// Pair removed and added lines for side-by-side display.
+    ///
+    /// Context lines appear on both sides; a run of removals followed by a run of
+    /// additions is laid out row by row, padding the shorter side.
+    pub fn side_by_side(&self) -> Vec<(Option<&DiffLine>, Option<&DiffLine>)> {
+        let mut rows = Vec::new();
+        let mut removed: Vec<&DiffLine> = Vec::new();
+        let mut added: Vec<&DiffLine> = Vec::new();
+
+        fn flush<'a>(
+            rows: &mut Vec<(Option<&'a DiffLine>, Option<&'a DiffLine>)>,
+            removed: &mut Vec<&'a DiffLine>,
+            added: &mut Vec<&'a DiffLine>,
+        ) {
+            let len = removed.len().max(added.len());
+            for i in 0..len {
+                rows.push((removed.get(i).copied(), added.get(i).copied()));
+            }
+            removed.clear();
+            added.clear();
+        }
+
+        for line in &self.lines {
+            match line.kind {
+                DiffLineKind::Context => {
+                    flush(&mut rows, &mut removed, &mut added);
+                    rows.push((Some(line), Some(line)));
+                }
+                DiffLineKind::Removed => {
+                    if !added.is_empty() {
+                        flush(&mut rows, &mut removed, &mut added);
+                    }
+                    removed.push(line);
+                }
+                DiffLineKind::Added => added.push(line),
+            }
+        }
+        flush(&mut rows, &mut removed, &mut added);
+        rows
+    }
+
+    /// Whether `other` makes the same change at the same place
+    fn same_change(&self, other: &DiffHunk) -> bool {
+        self.old_start == other.old_start
+            && self.old_lines == other.old_lines
+            && self.lines.len() == other.lines.len()
+            && self
+                .lines
+                .iter()
+                .zip(&other.lines)
+                .all(|(a, b)| a.kind == b.kind && a.content == b.content)
+    }
+}
+
+/// Changes to a single file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileDiff {
+    /// Path relative to the workspace root (the new path for renames)
+    pub path: String,
+    /// Previous path, for renames
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    pub kind: FileChangeKind,
+    /// Binary (or oversized) file; no hunks are available
+    pub binary: bool,
+    pub additions: usize,
+    pub deletions: usize,
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl FileDiff {
+    fn new(path: String, kind: FileChangeKind) -> Self {
+        Self {
+            path,
+            old_path: None,
+            kind,
+            binary: false,
+            additions: 0,
+            deletions: 0,
+            hunks: Vec::new(),
+        }
+    }
+
+    /// File extension, used to pick syntax coloring
+    pub fn extension(&self) -> Option<&str> {
+        Path::new(&self.path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+    }
+
+    /// A patch containing only the given hunk, suitable for `git apply`
+    pub fn hunk_patch(&self, index: usize) -> Option<String> {
+        let hunk = self.hunks.get(index)?;
+        let (old, new) = match self.kind {
+            FileChangeKind::Added | FileChangeKind::Untracked => {
+                ("/dev/null".to_string(), format!("b/{}", self.path))
+            }
+            FileChangeKind::Deleted => (format!("a/{}", self.path), "/dev/null".to_string()),
+            // Hunks of a renamed file only touch its contents at the new path
+            FileChangeKind::Modified | FileChangeKind::Renamed => {
+                (format!("a/{}", self.path), format!("b/{}", self.path))
+            }
+        };
+
+        let mut patch = format!("--- {}\n+++ {}\n{}\n", old, new, hunk.header());
+        for line in &hunk.lines {
+            let prefix = match line.kind {
+                DiffLineKind::Context => ' ',
+                DiffLineKind::Added => '+',
+                DiffLineKind::Removed => '-',
+            };
+            patch.push(prefix);
+            patch.push_str(&line.content);
+            patch.push('\n');
+            if line.no_newline {
+                patch.push_str("\\ No newline at end of file\n");
+            }
+        }
+        Some(patch)
+    }
+}
+
+/// All changes in a workspace against a base revision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkspaceDiff {
+    pub base: DiffBase,
+    /// Revision the working tree is compared against
+    pub base_ref: String,
+    /// Base branch name, when diffing against the base branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    pub files: Vec<FileDiff>,
+}
+
+impl WorkspaceDiff {
+    /// Diff the working tree (including untracked files) against `base`.
+    pub fn load(working_dir: &Path, base: DiffBase) -> Result<Self, WorktreeError> {
+        let (base_ref, base_branch) = match base {
+            DiffBase::Head => {
+                let head = if rev_exists(working_dir, "HEAD") {
+                    "HEAD".to_string()
+                } else {
+                    EMPTY_TREE.to_string()
+                };
+                (head, None)
+            }
+            DiffBase::BaseBranch => {
+                let branch = PrManager::get_default_branch(working_dir);
+                let merge_base = [format!("origin/{}", branch), branch.clone()]
+                    .iter()
+                    .find_map(|candidate| merge_base(working_dir, candidate))
+                    .ok_or_else(|| {
+                        WorktreeError::CommandFailed(format!(
+                            "No common ancestor with base branch '{}'",
+                            branch
+                        ))
+                    })?;
+                (merge_base, Some(branch))
+            }
+        };
+        Self::load_from(working_dir, base, base_ref, base_branch)
+    }
+
+    /// Diff the working tree (including untracked files) against a fixed commit.
+    pub fn load_since(working_dir: &Path, commit: &str) -> Result<Self, WorktreeError> {
+        Self::load_from(working_dir, DiffBase::BaseBranch, commit.to_string(), None)
+    }
+
+    fn load_from(
+        working_dir: &Path,
+        base: DiffBase,
+        base_ref: String,
+        base_branch: Option<String>,
+    ) -> Result<Self, WorktreeError> {
+        let output = git(
+            working_dir,
+            &[
+                "diff",
+                "--no-color",
+                "--no-ext-diff",
+                "--find-renames",
+                &base_ref,
+            ],
+        )?;
+        let mut files = parse_unified_diff(&output);
+        if base == DiffBase::Head {
+            let staged = git(
+                working_dir,
+                &[
+                    "diff",
+                    "--cached",
+                    "--no-color",
+                    "--no-ext-diff",
+                    "--find-renames",
+                    &base_ref,
+                ],
+            )?;
+            mark_staged_hunks(&mut files, &parse_unified_diff(&staged));
+        }
+        files.extend(untracked_files(working_dir)?);
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        for hunk in files.iter_mut().flat_map(|file| file.hunks.iter_mut()) {
+            hunk.hash = hunk.content_hash();
+        }
+
+        Ok(Self {
+            base,
+            base_ref,
+            base_branch,
+            files,
+        })
+    }
+
+    pub fn additions(&self) -> usize {
+        self.files.iter().map(|file| file.additions).sum()
+    }
+
+    pub fn deletions(&self) -> usize {
+        self.files.iter().map(|file| file.deletions).sum()
+    }
+
+    /// Look up a changed file by its workspace-relative path
+    pub fn file(&self, path: &str) -> Option<&FileDiff> {
+        self.files.iter().find(|file| file.path == path)
+    }
+
+    /// Keep only files in `paths` (absolute or relative to `working_dir`).
+    pub fn retain_paths(&mut self, working_dir: &Path, paths: &[String]) {
+        let relative: Vec<PathBuf> = paths
+            .iter()
+            .filter_map(|path| relative_path(working_dir, path))
+            .collect();
+        self.files.retain(|file| {
+            relative.iter().any(|path| {
+                Path::new(&file.path) == path
+                    || file
+                        .old_path
+                        .as_deref()
+                        .is_some_and(|old| Path::new(old) == path)
+            })
+        });
+    }
+
+    /// Stage or revert one hunk of `path`.
+    ///
+    /// Only diffs against HEAD can be changed hunk by hunk; against the base
+    /// branch the hunks may already be committed.
+    pub fn apply_hunk(
+        &self,
+        working_dir: &Path,
+        path: &str,
+        index: usize,
+        action: HunkAction,
+    ) -> Result<(), WorktreeError> {
+        if self.base != DiffBase::Head {
+            return Err(WorktreeError::CommandFailed(
+                "Hunks can only be staged or reverted in a diff against HEAD".to_string(),
+            ));
+        }
+        let file = self
+            .file(path)
+            .ok_or_else(|| WorktreeError::HunkNotFound(path.to_string()))?;
+        let hunk = file
+            .hunks
+            .get(index)
+            .ok_or_else(|| WorktreeError::HunkNotFound(format!("{} #{}", path, index + 1)))?;
+
+        // Untracked files are a single all-added hunk
+        if file.kind == FileChangeKind::Untracked {
+            return match action {
+                HunkAction::Stage => git(working_dir, &["add", "--", path]).map(|_| ()),
+                HunkAction::Revert => Ok(std::fs::remove_file(working_dir.join(path))?),
+            };
+        }
+
+        let patch = file
+            .hunk_patch(index)
+            .ok_or_else(|| WorktreeError::HunkNotFound(format!("{} #{}", path, index + 1)))?;
+        match action {
+            HunkAction::Stage if hunk.staged => Ok(()),
+            HunkAction::Stage => git_apply(working_dir, &["--cached"], &patch),
+            HunkAction::Revert => {
+                if hunk.staged {
+                    git_apply(working_dir, &["--cached", "--reverse"], &patch)?;
+                }
+                git_apply(working_dir, &["--reverse"], &patch)
+            }
+        }
+    }
+}
+
+/// Mark hunks whose change is already in the index.
+fn mark_staged_hunks(files: &mut [FileDiff], staged: &[FileDiff]) {
+    for file in files {
+        let Some(staged_file) = staged.iter().find(|s| s.path == file.path) else {
+            continue;
+        };
+        for hunk in &mut file.hunks {
+            hunk.staged = staged_file.hunks.iter().any(|s| s.same_change(hunk));
+        }
+    }
+}
+
+/// Make an agent-reported path relative to the workspace root.
+fn relative_path(working_dir: &Path, path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    if path.is_relative() {
+        return Some(path.strip_prefix("./").unwrap_or(path).to_path_buf());
+    }
+    if let Ok(relative) = path.strip_prefix(working_dir) {
+        return Some(relative.to_path_buf());
+    }
+    // Agents may report canonical paths (e.g. /private/tmp on macOS)
+    let canonical_dir = working_dir.canonicalize().ok()?;
+    path.strip_prefix(&canonical_dir)
+        .ok()
+        .map(Path::to_path_buf)
+}
+
+/// Feed a patch to `git apply` on stdin.
+fn git_apply(working_dir: &Path, args: &[&str], patch: &str) -> Result<(), WorktreeError> {
+    let mut child = Command::new("git")
+        .args(["apply", "--whitespace=nowarn"])
+        .args(args)
+        .arg("-")
+        .current_dir(working_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(patch.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(WorktreeError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `rev` names a commit in the repository
+fn rev_exists(working_dir: &Path, rev: &str) -> bool {
+    git(working_dir, &["rev-parse", "--verify", "--quiet", rev]).is_ok()
+}
+
+fn merge_base(working_dir: &Path, rev: &str) -> Option<String> {
+    git(working_dir, &["merge-base", "HEAD", rev])
+        .ok()
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+}
+
+/// Untracked (not ignored) files, shown as fully added.
+fn untracked_files(working_dir: &Path) -> Result<Vec<FileDiff>, WorktreeError> {
+    let output = git(
+        working_dir,
+        &["ls-files", "--others", "--exclude-standard", "-z"],
+    )?;
+    let mut files = Vec::new();
+    for path in output.split('\0').filter(|path| !path.is_empty()) {
+        let mut file = FileDiff::new(path.to_string(), FileChangeKind::Untracked);
+        let full_path = working_dir.join(path);
+        let too_large = std::fs::metadata(&full_path)
+            .map(|meta| meta.len() > MAX_UNTRACKED_FILE_SIZE)
+            .unwrap_or(true);
+        let content = if too_large {
+            None
+        } else {
+            std::fs::read(&full_path)
+                .ok()
+                .filter(|bytes| !bytes.contains(&0))
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+        };
+        match content {
+            Some(content) => {
+                // Split on `\n` only: CRLF files keep their `\r` so patches still apply
+                let mut lines: Vec<DiffLine> = content
+                    .split_terminator('\n')
+                    .enumerate()
+                    .map(|(idx, line)| DiffLine {
+                        kind: DiffLineKind::Added,
+                        old_line: None,
+                        new_line: Some(idx + 1),
+                        content: line.to_string(),
+                        no_newline: false,
+                    })
+                    .collect();
+                if let Some(last) = lines.last_mut() {
+                    last.no_newline = !content.ends_with('\n');
+                }
+                file.additions = lines.len();
+                if !lines.is_empty() {
+                    file.hunks.push(DiffHunk {
+                        old_start: 0,
+                        old_lines: 0,
+                        new_start: 1,
+                        new_lines: lines.len(),
+                        section: String::new(),
+                        lines,
+                        staged: false,
+                        hash: String::new(),
+                    });
+                }
+            }
+            None => file.binary = true,
+        }
+        files.push(file);
+    }
+    Ok(files)
+}
+
+/// Parse `git diff` output into per-file hunks.
+pub fn parse_unified_diff(output: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+    let mut old_line = 0;
+    let mut new_line = 0;
+
+    // Split on `\n` only: content lines of CRLF files keep their `\r`, or the
+    // patches rebuilt from them no longer apply
+    for raw_line in output.split_terminator('\n') {
+        let line = raw_line.strip_suffix('\r').unwrap_or(raw_line);
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            let path = parse_git_header_path(rest).unwrap_or_default();
+            files.push(FileDiff::new(path, FileChangeKind::Modified));
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        let in_hunk = !file.hunks.is_empty();
+        if in_hunk {
+            if let Some(hunk) = file.hunks.last_mut() {
+                let (kind, content) = match raw_line.as_bytes().first() {
+                    Some(b' ') => (Some(DiffLineKind::Context), &raw_line[1..]),
+                    Some(b'+') => (Some(DiffLineKind::Added), &raw_line[1..]),
+                    Some(b'-') => (Some(DiffLineKind::Removed), &raw_line[1..]),
+                    // Blank context lines can lose their leading space
+                    None => (Some(DiffLineKind::Context), ""),
+                    _ => (None, line),
+                };
+                if let Some(kind) = kind {
+                    let (old, new) = match kind {
+                        DiffLineKind::Context => {
+                            old_line += 1;
+                            new_line += 1;
+                            (Some(old_line - 1), Some(new_line - 1))
+                        }
+                        DiffLineKind::Added => {
+                            file.additions += 1;
+                            new_line += 1;
+                            (None, Some(new_line - 1))
+                        }
+                        DiffLineKind::Removed => {
+                            file.deletions += 1;
+                            old_line += 1;
+                            (Some(old_line - 1), None)
+                        }
+                    };
+                    hunk.lines.push(DiffLine {
+                        kind,
+                        old_line: old,
+                        new_line: new,
+                        content: content.to_string(),
+                        no_newline: false,
+                    });
+                    continue;
+                }
+                if line.starts_with("\\ ") {
+                    if let Some(last) = hunk.lines.last_mut() {
+                        last.no_newline = true;
+                    }
+                    continue;
+                }
+            }
+        }
+
+        if let Some(hunk) = parse_hunk_header(line) {
+            old_line = hunk.old_start;
+            new_line = hunk.new_start;
+            file.hunks.push(hunk);
+        } else if line.starts_with("new file mode") {
+            file.kind = FileChangeKind::Added;
+        } else if line.starts_with("deleted file mode") {
+            file.kind = FileChangeKind::Deleted;
+        } else if let Some(from) = line.strip_prefix("rename from ") {
+            file.kind = FileChangeKind::Renamed;
+            file.old_path = Some(from.to_string());
+        } else if let Some(to) = line.strip_prefix("rename to ") {
+            file.path = to.to_string();
+        } else if line.starts_with("Binary files ") || line == "GIT binary patch" {
+            file.binary = true;
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            file.path = path.to_string();
+        } else if let Some(path) = line.strip_prefix("--- a/") {
+            if file.kind == FileChangeKind::Deleted {
+                file.path = path.to_string();
+            }
+        }
+    }
+
+    files
+}
+
+/// Extract the new path from `a/<old> b/<new>`.
+fn parse_git_header_path(rest: &str) -> Option<String> {
+    // Paths are unquoted (core.quotepath=off) and, without renames, identical on both sides,
+    // so splitting in the middle handles paths containing " b/".
+    let rest = rest.strip_prefix("a/")?;
+    let half = rest.len().checked_sub(3)? / 2;
+    if rest.is_char_boundary(half) && rest[half..].starts_with(" b/") {
+        let (old, new) = (&rest[..half], &rest[half + 3..]);
+        if old == new {
+            return Some(new.to_string());
+        }
+    }
+    rest.rsplit_once(" b/").map(|(_, new)| new.to_string())
+}
+
+/// Parse `@@ -a,b +c,d @@ section`.
+fn parse_hunk_header(line: &str) -> Option<DiffHunk> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, section) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_lines) = parse_range(old)?;
+    let (new_start, new_lines) = parse_range(new)?;
+    Some(DiffHunk {
+        old_start,
+        old_lines,
+        new_start,
+        new_lines,
+        section: section.trim().to_string(),
+        lines: Vec::new(),
+        staged: false,
+        hash: String::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const SAMPLE: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 3b18e51..a3c9d1f 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,4 +1,5 @@ mod parser;
+ use std::io;
+-fn old() {}
++fn new() {}
++fn extra() {}
+
+ fn keep() {}
+diff --git a/notes.txt b/notes.txt
+deleted file mode 100644
+index 9daeafb..0000000
+--- a/notes.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-todo
+\\ No newline at end of file
+diff --git a/old name.rs b/new name.rs
+similarity index 100%
+rename from old name.rs
+rename to new name.rs
+diff --git a/logo.png b/logo.png
+new file mode 100644
+index 0000000..e69de29
+Binary files /dev/null and b/logo.png differ
+";
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let files = parse_unified_diff(SAMPLE);
+        assert_eq!(files.len(), 4);
+
+        let lib = &files[0];
+        assert_eq!(lib.path, "src/lib.rs");
+        assert_eq!(lib.kind, FileChangeKind::Modified);
+        assert_eq!((lib.additions, lib.deletions), (2, 1));
+        let hunk = &lib.hunks[0];
+        assert_eq!(hunk.header(), "@@ -1,4 +1,5 @@ mod parser;");
+        assert_eq!(hunk.lines.len(), 6);
+        assert_eq!(hunk.lines[1].kind, DiffLineKind::Removed);
+        assert_eq!(hunk.lines[1].old_line, Some(2));
+        assert_eq!(hunk.lines[3].new_line, Some(3));
+        // Blank context line without its leading space
+        assert_eq!(hunk.lines[4].kind, DiffLineKind::Context);
+        assert_eq!(hunk.lines[5].old_line, Some(4));
+        assert_eq!(hunk.lines[5].new_line, Some(5));
+
+        let notes = &files[1];
+        assert_eq!(notes.kind, FileChangeKind::Deleted);
+        assert_eq!(notes.path, "notes.txt");
+        assert!(notes.hunks[0].lines[0].no_newline);
+
+        let renamed = &files[2];
+        assert_eq!(renamed.kind, FileChangeKind::Renamed);
+        assert_eq!(renamed.path, "new name.rs");
+        assert_eq!(renamed.old_path.as_deref(), Some("old name.rs"));
+
+        let logo = &files[3];
+        assert_eq!(logo.kind, FileChangeKind::Added);
+        assert!(logo.binary);
+        assert!(logo.hunks.is_empty());
+    }
+
+    #[test]
+    fn test_content_hash_covers_lines() {
+        let same_ranges = SAMPLE.replace("+fn extra() {}", "+fn other() {}");
+        let original = &parse_unified_diff(SAMPLE)[0].hunks[0];
+        let changed = &parse_unified_diff(&same_ranges)[0].hunks[0];
+        assert_eq!(original.header(), changed.header());
+        assert_ne!(original.content_hash(), changed.content_hash());
+        assert_eq!(original.content_hash(), original.clone().content_hash());
+        assert_eq!(original.content_hash().len(), 16);
+    }
+
+    #[test]
+    fn test_side_by_side_pairs_changes() {
+        let files = parse_unified_diff(SAMPLE);
+        let rows = files[0].hunks[0].side_by_side();
+        // context, (old -> new), (pad -> extra), blank, context
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[1].0.unwrap().content, "fn old() {}");
+        assert_eq!(rows[1].1.unwrap().content, "fn new() {}");
+        assert!(rows[2].0.is_none());
+        assert_eq!(rows[2].1.unwrap().content, "fn extra() {}");
+    }
+
+    fn git_in(path: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    fn init_repo(path: &Path, files: &[(&str, &str)]) {
+        git_in(path, &["init"]);
+        git_in(path, &["config", "user.email", "test@test.com"]);
+        git_in(path, &["config", "user.name", "Test"]);
+        for (name, content) in files {
+            std::fs::write(path.join(name), content).unwrap();
+        }
+        git_in(path, &["add", "."]);
+        git_in(path, &["commit", "-m", "Initial commit"]);
+    }
+
+    #[test]
+    fn test_load_includes_untracked_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        init_repo(path, &[("README.md", "# Test\n")]);
+
+        std::fs::write(path.join("README.md"), "# Test\nMore\n").unwrap();
+        std::fs::write(path.join("new.txt"), "one\ntwo").unwrap();
+
+        let diff = WorkspaceDiff::load(path, DiffBase::Head).unwrap();
+        assert_eq!(diff.base_ref, "HEAD");
+        let paths: Vec<_> = diff.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["README.md", "new.txt"]);
+        assert_eq!(diff.files[1].kind, FileChangeKind::Untracked);
+        assert_eq!(diff.files[1].hunks[0].lines.len(), 2);
+        assert!(diff.files[1].hunks[0].lines[1].no_newline);
+        assert_eq!((diff.additions(), diff.deletions()), (3, 0));
+    }
+
+    #[test]
+    fn test_stage_and_revert_single_hunks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let original: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        init_repo(path, &[("a.txt", &original)]);
+
+        let changed = original
+            .replace("line 2\n", "line two\n")
+            .replace("line 19\n", "line nineteen\n");
+        std::fs::write(path.join("a.txt"), &changed).unwrap();
+        std::fs::write(path.join("scratch.txt"), "tmp\n").unwrap();
+
+        let diff = WorkspaceDiff::load(path, DiffBase::Head).unwrap();
+        assert_eq!(diff.file("a.txt").unwrap().hunks.len(), 2);
+
+        // Keep the first hunk
+        diff.apply_hunk(path, "a.txt", 0, HunkAction::Stage)
+            .unwrap();
+        let diff = WorkspaceDiff::load(path, DiffBase::Head).unwrap();
+        let hunks = &diff.file("a.txt").unwrap().hunks;
+        assert!(hunks[0].staged);
+        assert!(!hunks[1].staged);
+        assert!(git_in(path, &["diff", "--cached"]).contains("+line two"));
+
+        // Discard the second hunk from the working tree
+        diff.apply_hunk(path, "a.txt", 1, HunkAction::Revert)
+            .unwrap();
+        let content = std::fs::read_to_string(path.join("a.txt")).unwrap();
+        assert!(content.contains("line two\n"));
+        assert!(content.contains("line 19\n"));
+
+        // Discarding a staged hunk also removes it from the index
+        let diff = WorkspaceDiff::load(path, DiffBase::Head).unwrap();
+        diff.apply_hunk(path, "a.txt", 0, HunkAction::Revert)
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path.join("a.txt")).unwrap(),
+            original
+        );
+        assert!(git_in(path, &["diff", "--cached"]).is_empty());
+
+        // Untracked files are reverted by deleting them
+        let diff = WorkspaceDiff::load(path, DiffBase::Head).unwrap();
+        diff.apply_hunk(path, "scratch.txt", 0, HunkAction::Revert)
+            .unwrap();
+        assert!(!path.join("scratch.txt").exists());
+        assert!(matches!(
+            diff.apply_hunk(path, "a.txt", 5, HunkAction::Stage),
+            Err(WorktreeError::HunkNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_stage_and_revert_crlf_hunks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let original: String = (1..=20).map(|i| format!("line {}\r\n", i)).collect();
+        init_repo(path, &[("crlf.txt", &original)]);
+
+        let changed = original
+            .replace("line 2\r\n", "line two\r\n")
+            .replace("line 19\r\n", "line nineteen\r\n");
+        std::fs::write(path.join("crlf.txt"), &changed).unwrap();
+
+        let diff = WorkspaceDiff::load(path, DiffBase::Head).unwrap();
+        let hunks = &diff.file("crlf.txt").unwrap().hunks;
+        assert_eq!(hunks.len(), 2);
+        assert!(hunks[0]
+            .lines
+            .iter()
+            .all(|line| line.content.ends_with('\r')));
+
+        diff.apply_hunk(path, "crlf.txt", 0, HunkAction::Stage)
+            .unwrap();
+        assert!(git_in(path, &["diff", "--cached"]).contains("+line two\r"));
+
+        diff.apply_hunk(path, "crlf.txt", 1, HunkAction::Revert)
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path.join("crlf.txt")).unwrap(),
+            original.replace("line 2\r\n", "line two\r\n")
+        );
+    }
+
+    #[test]
+    fn test_retain_paths_accepts_absolute_and_relative() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        init_repo(
+            path,
+            &[("a.txt", "a\n"), ("b.txt", "b\n"), ("c.txt", "c\n")],
+        );
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(path.join(name), "changed\n").unwrap();
+        }
+
+        let mut diff = WorkspaceDiff::load(path, DiffBase::Head).unwrap();
+        let absolute = path.join("c.txt").to_string_lossy().into_owned();
+        diff.retain_paths(path, &["./a.txt".to_string(), absolute]);
+        let paths: Vec<_> = diff.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.txt", "c.txt"]);
+    }
+}
@@ -0,0 +1,81 @@
+//! Shared helpers for GitHub CLI (`gh`) queries about a workspace's PR
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use serde::Deserialize;
+
+use super::pr::PrManager;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GhError {
+    #[error("GitHub CLI (gh) is not installed")]
+    NotInstalled,
+    #[error("GitHub CLI is not authenticated. Run `gh auth login`")]
+    NotAuthenticated,
+    #[error("No pull request found for this branch")]
+    NoPullRequest,
+    #[error("gh command failed: {0}")]
+    CommandFailed(String),
+    #[error("Failed to parse gh output: {0}")]
+    ParseError(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl GhError {
+    /// Whether the error comes from the user's setup rather than a failure
+    pub fn is_setup_error(&self) -> bool {
+        matches!(
+            self,
+            GhError::NotInstalled | GhError::NotAuthenticated | GhError::NoPullRequest
+        )
+    }
+}
+
+/// Number and URL of the PR for the current branch
+#[derive(Debug, Deserialize)]
+pub(super) struct PrRef {
+    pub number: u32,
+    pub url: String,
+}
+
+/// Fail early when `gh` can't be used
+pub(super) fn require_gh() -> Result<(), GhError> {
+    let gh = PrManager::gh_status();
+    if !gh.installed {
+        return Err(GhError::NotInstalled);
+    }
+    if !gh.authenticated {
+        return Err(GhError::NotAuthenticated);
+    }
+    Ok(())
+}
+
+/// Run `gh` in the working directory, returning its output whatever the exit status
+pub(super) fn run_gh(working_dir: &Path, args: &[&str]) -> Result<Output, GhError> {
+    Ok(Command::new("gh")
+        .args(args)
+        .current_dir(working_dir)
+        .output()?)
+}
+
+/// Run `gh` and return its stdout, failing on a non-zero exit status
+pub(super) fn gh_stdout(working_dir: &Path, args: &[&str]) -> Result<String, GhError> {
+    let output = run_gh(working_dir, args)?;
+    if !output.status.success() {
+        return Err(GhError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The PR for the current branch
+pub(super) fn current_pr(working_dir: &Path) -> Result<PrRef, GhError> {
+    let output = run_gh(working_dir, &["pr", "view", "--json", "number,url"])?;
+    if !output.status.success() {
+        return Err(GhError::NoPullRequest);
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| GhError::ParseError(e.to_string()))
+}
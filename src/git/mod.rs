@@ -1,16 +1,79 @@
 //! Git operations module
 
+mod checkpoint;
+mod ci;
+mod clone;
+mod commit;
+mod diff;
+mod gh;
 mod pr;
+mod review;
 mod status;
+mod sync;
 mod workspace_mode;
 mod workspace_repo;
 mod worktree;
 
+pub use checkpoint::{CheckpointManager, CHECKPOINT_REF_PREFIX};
+pub use ci::{CiFailures, CiManager, FailedCheck};
+pub use clone::{CloneManager, CloneProgress};
+pub use commit::{ChangedFile, CommitManager};
+pub use diff::{
+    parse_unified_diff, DiffBase, DiffHunk, DiffLine, DiffLineKind, FileChangeKind, FileDiff,
+    HunkAction, WorkspaceDiff,
+};
+pub use gh::GhError;
 pub use pr::{
     CheckState, CheckStatus, MergeReadiness, MergeableStatus, PrManager, PrPreflightResult,
     PrState, PrStatus, ReviewDecision,
 };
+pub use review::{PrReviewComments, ReviewComment, ReviewManager};
 pub use status::GitDiffStats;
+pub use sync::{ConflictedFile, SyncConflicts, SyncManager, SyncOutcome, SyncStatus, SyncStrategy};
 pub use workspace_mode::WorkspaceMode;
 pub use workspace_repo::WorkspaceRepoManager;
-pub use worktree::{WorktreeInfo, WorktreeManager};
+pub use worktree::{WorktreeError, WorktreeInfo, WorktreeManager};
+
+use std::path::Path;
+use std::process::Command;
+
+/// Run git in `working_dir` and return its stdout.
+pub(crate) fn git(working_dir: &Path, args: &[&str]) -> Result<String, WorktreeError> {
+    git_with(working_dir, None, &[], args)
+}
+
+/// Run git in `working_dir` with `index` in place of the repository's index
+/// file and extra `env` variables, returning its stdout.
+///
+/// Git never prompts for credentials, so a command that needs them fails
+/// instead of waiting on a terminal nobody is looking at.
+pub(crate) fn git_with(
+    working_dir: &Path,
+    index: Option<&Path>,
+    env: &[(&str, &str)],
+    args: &[&str],
+) -> Result<String, WorktreeError> {
+    let mut command = Command::new("git");
+    command
+        .args(["--no-optional-locks", "-c", "core.quotepath=off"])
+        .args(args)
+        .current_dir(working_dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .envs(env.iter().copied());
+    if let Some(index) = index {
+        command.env("GIT_INDEX_FILE", index);
+    }
+    let output = command.output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        // merge and rebase report conflicts on stdout
+        let message = if stderr.trim().is_empty() {
+            stdout
+        } else {
+            stderr
+        };
+        return Err(WorktreeError::CommandFailed(message.trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
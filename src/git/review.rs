@@ -0,0 +1,324 @@
+//! Pull request review comments
+//!
+//! Fetches the unresolved review threads of a workspace's PR through the
+//! GitHub CLI and turns selected comments into a prompt for the agent.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::gh::{current_pr, gh_stdout, require_gh, GhError};
+
+/// GraphQL query for a PR's review threads. `{owner}` and `{repo}` are
+/// filled in by `gh` from the repository in the working directory.
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100) {
+        nodes {
+          id
+          isResolved
+          isOutdated
+          path
+          line
+          originalLine
+          comments(first: 50) {
+            nodes { id body url author { login } }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// A comment in an unresolved review thread
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewComment {
+    /// GraphQL node ID of the comment
+    pub id: String,
+    /// GraphQL node ID of the thread the comment belongs to
+    pub thread_id: String,
+    /// File the thread is attached to
+    pub path: String,
+    /// Line in the current diff (the original line when outdated)
+    pub line: Option<u32>,
+    /// Login of the author (`ghost` for deleted accounts)
+    pub author: String,
+    pub body: String,
+    pub url: String,
+    /// Whether the code the thread points at has since changed
+    pub outdated: bool,
+}
+
+impl ReviewComment {
+    /// `path:line`, or just the path for file-level comments
+    pub fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.path, line),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// Unresolved review comments of a pull request
+#[derive(Debug, Clone, Default)]
+pub struct PrReviewComments {
+    pub pr_number: u32,
+    pub pr_url: String,
+    /// Comments in thread order, replies after the comment they answer
+    pub comments: Vec<ReviewComment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlResponse {
+    data: Option<GraphqlData>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlData {
+    repository: Option<GraphqlRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlRepository {
+    pull_request: Option<GraphqlPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlPullRequest {
+    review_threads: GraphqlConnection<GraphqlThread>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlConnection<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlThread {
+    id: String,
+    is_resolved: bool,
+    #[serde(default)]
+    is_outdated: bool,
+    path: String,
+    line: Option<u32>,
+    original_line: Option<u32>,
+    comments: GraphqlConnection<GraphqlComment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlComment {
+    id: String,
+    body: String,
+    url: String,
+    author: Option<GraphqlAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlAuthor {
+    login: String,
+}
+
+/// Reads PR review feedback through `gh`
+pub struct ReviewManager;
+
+impl ReviewManager {
+    /// Unresolved review comments on the PR for the current branch
+    pub fn unresolved_comments(working_dir: &Path) -> Result<PrReviewComments, GhError> {
+        require_gh()?;
+        let pr = current_pr(working_dir)?;
+        let number = format!("number={}", pr.number);
+        let query = format!("query={}", REVIEW_THREADS_QUERY);
+        let output = gh_stdout(
+            working_dir,
+            &[
+                "api",
+                "graphql",
+                "-F",
+                "owner={owner}",
+                "-F",
+                "repo={repo}",
+                "-F",
+                &number,
+                "-f",
+                &query,
+            ],
+        )?;
+
+        Ok(PrReviewComments {
+            pr_number: pr.number,
+            pr_url: pr.url,
+            comments: parse_review_threads(&output)?,
+        })
+    }
+
+    /// Prompt asking the agent to address the given review comments
+    pub fn build_prompt(pr_number: u32, comments: &[ReviewComment]) -> String {
+        let mut prompt = format!(
+            "A reviewer left the following comments on PR #{}. Address each one.\n",
+            pr_number
+        );
+        for (index, comment) in comments.iter().enumerate() {
+            let outdated = if comment.outdated {
+                " (outdated: the code has changed since)"
+            } else {
+                ""
+            };
+            prompt.push_str(&format!(
+                "\n## Comment {}\nFile: {}{}\nAuthor: @{}\n\n{}\n",
+                index + 1,
+                comment.location(),
+                outdated,
+                comment.author,
+                comment.body.trim()
+            ));
+        }
+        prompt.push_str(
+            "\nFor each comment, make the requested change or explain why no change is \
+             needed. Do not reply to or resolve the comments on GitHub. Finish with a short \
+             summary listing each comment and what you did about it.",
+        );
+        prompt
+    }
+}
+
+/// Flatten the unresolved threads of a `reviewThreads` GraphQL response
+fn parse_review_threads(json: &str) -> Result<Vec<ReviewComment>, GhError> {
+    let response: GraphqlResponse =
+        serde_json::from_str(json).map_err(|e| GhError::ParseError(e.to_string()))?;
+    if let Some(error) = response.errors.first() {
+        return Err(GhError::CommandFailed(error.message.clone()));
+    }
+    let threads = response
+        .data
+        .and_then(|data| data.repository)
+        .and_then(|repository| repository.pull_request)
+        .ok_or(GhError::NoPullRequest)?
+        .review_threads
+        .nodes;
+
+    Ok(threads
+        .into_iter()
+        .filter(|thread| !thread.is_resolved)
+        .flat_map(|thread| {
+            let line = thread.line.or(thread.original_line);
+            let GraphqlThread {
+                id: thread_id,
+                is_outdated,
+                path,
+                comments,
+                ..
+            } = thread;
+            comments
+                .nodes
+                .into_iter()
+                .map(move |comment| ReviewComment {
+                    id: comment.id,
+                    thread_id: thread_id.clone(),
+                    path: path.clone(),
+                    line,
+                    author: comment
+                        .author
+                        .map(|author| author.login)
+                        .unwrap_or_else(|| "ghost".to_string()),
+                    body: comment.body,
+                    url: comment.url,
+                    outdated: is_outdated,
+                })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"{
+      "data": {
+        "repository": {
+          "pullRequest": {
+            "reviewThreads": {
+              "nodes": [
+                {
+                  "id": "T1", "isResolved": false, "isOutdated": false,
+                  "path": "src/lib.rs", "line": 12, "originalLine": 10,
+                  "comments": { "nodes": [
+                    { "id": "C1", "body": "Handle the error", "url": "https://x/1",
+                      "author": { "login": "alice" } },
+                    { "id": "C2", "body": "+1", "url": "https://x/2", "author": null }
+                  ] }
+                },
+                {
+                  "id": "T2", "isResolved": true, "isOutdated": false,
+                  "path": "src/main.rs", "line": 3, "originalLine": 3,
+                  "comments": { "nodes": [
+                    { "id": "C3", "body": "Done", "url": "https://x/3",
+                      "author": { "login": "bob" } }
+                  ] }
+                },
+                {
+                  "id": "T3", "isResolved": false, "isOutdated": true,
+                  "path": "README.md", "line": null, "originalLine": 7,
+                  "comments": { "nodes": [
+                    { "id": "C4", "body": "Typo", "url": "https://x/4",
+                      "author": { "login": "bob" } }
+                  ] }
+                }
+              ]
+            }
+          }
+        }
+      }
+    }"#;
+
+    #[test]
+    fn test_parse_review_threads_skips_resolved() {
+        let comments = parse_review_threads(RESPONSE).unwrap();
+        let ids: Vec<&str> = comments.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["C1", "C2", "C4"]);
+
+        assert_eq!(comments[0].thread_id, "T1");
+        assert_eq!(comments[0].location(), "src/lib.rs:12");
+        assert_eq!(comments[1].author, "ghost");
+        // Outdated threads fall back to the original line
+        assert!(comments[2].outdated);
+        assert_eq!(comments[2].location(), "README.md:7");
+    }
+
+    #[test]
+    fn test_parse_review_threads_reports_errors() {
+        let json = r#"{"data": null, "errors": [{"message": "Bad credentials"}]}"#;
+        let err = parse_review_threads(json).unwrap_err();
+        assert!(err.to_string().contains("Bad credentials"));
+
+        let json = r#"{"data": {"repository": {"pullRequest": null}}}"#;
+        assert!(matches!(
+            parse_review_threads(json),
+            Err(GhError::NoPullRequest)
+        ));
+    }
+
+    #[test]
+    fn test_build_prompt() {
+        let comments = parse_review_threads(RESPONSE).unwrap();
+        let prompt = ReviewManager::build_prompt(42, &[comments[0].clone(), comments[2].clone()]);
+
+        assert!(prompt.contains("PR #42"));
+        assert!(prompt.contains("## Comment 1\nFile: src/lib.rs:12\nAuthor: @alice"));
+        assert!(prompt.contains("Handle the error"));
+        assert!(prompt.contains("## Comment 2\nFile: README.md:7 (outdated"));
+        assert!(!prompt.contains("+1"));
+    }
+}
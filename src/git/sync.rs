@@ -0,0 +1,497 @@
+//! Syncing a workspace branch with its base branch
+//!
+//! Merges or rebases the base branch (`origin/main` when it exists) into the
+//! workspace. A sync that stops on conflicts is left in progress so the
+//! conflicts can be resolved, then continued or aborted.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::worktree::{WorktreeError, WorktreeManager};
+
+/// Conflict regions kept per file for the agent prompt
+const MAX_CONFLICT_HUNKS: usize = 5;
+/// Lines kept per conflict region
+const MAX_CONFLICT_LINES: usize = 60;
+
+/// How a workspace takes in its base branch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncStrategy {
+    #[default]
+    Merge,
+    Rebase,
+}
+
+impl SyncStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncStrategy::Merge => "merge",
+            SyncStrategy::Rebase => "rebase",
+        }
+    }
+}
+
+impl FromStr for SyncStrategy {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "merge" => Ok(SyncStrategy::Merge),
+            "rebase" => Ok(SyncStrategy::Rebase),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for SyncStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A file left with conflicts by a merge or rebase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConflictedFile {
+    pub path: String,
+    /// Conflict regions, markers included (empty for binary or deleted files)
+    pub hunks: Vec<String>,
+}
+
+/// A merge or rebase stopped on conflicts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncConflicts {
+    /// Ref being synced from, e.g. `origin/main`
+    pub base: String,
+    pub strategy: SyncStrategy,
+    pub files: Vec<ConflictedFile>,
+}
+
+/// Result of starting or continuing a sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// The branch already contains the base branch
+    UpToDate { base: String },
+    /// The base branch was merged in or rebased onto
+    Synced {
+        base: String,
+        strategy: SyncStrategy,
+    },
+    /// Stopped on conflicts; the sync is still in progress
+    Conflicts(SyncConflicts),
+}
+
+/// Sync state of a workspace
+#[derive(Debug, Clone, Default)]
+pub struct SyncStatus {
+    pub base: String,
+    /// Merge or rebase waiting to be continued or aborted
+    pub in_progress: Option<SyncStrategy>,
+    pub conflicts: Vec<ConflictedFile>,
+    pub commits_ahead: usize,
+    pub commits_behind: usize,
+}
+
+/// Merges or rebases workspaces onto their base branch
+pub struct SyncManager;
+
+impl SyncManager {
+    /// Ref the workspace syncs from: `origin/<main>`, or the local main branch
+    pub fn base_ref(working_dir: &Path) -> Result<String, WorktreeError> {
+        let main = WorktreeManager::new().get_main_branch(working_dir)?;
+        let remote = format!("origin/{}", main);
+        if git(working_dir, &["rev-parse", "--verify", "--quiet", &remote]).is_ok() {
+            Ok(remote)
+        } else {
+            Ok(main)
+        }
+    }
+
+    /// Merge or rebase left in progress, if any
+    pub fn in_progress(working_dir: &Path) -> Result<Option<SyncStrategy>, WorktreeError> {
+        if git_path(working_dir, "rebase-merge")?.exists()
+            || git_path(working_dir, "rebase-apply")?.exists()
+        {
+            return Ok(Some(SyncStrategy::Rebase));
+        }
+        if git_path(working_dir, "MERGE_HEAD")?.exists() {
+            return Ok(Some(SyncStrategy::Merge));
+        }
+        Ok(None)
+    }
+
+    /// Files with unresolved conflicts, sorted by path
+    pub fn conflicted_files(working_dir: &Path) -> Result<Vec<ConflictedFile>, WorktreeError> {
+        let output = git(
+            working_dir,
+            &["diff", "--name-only", "-z", "--diff-filter=U"],
+        )?;
+        let mut paths: Vec<&str> = output.split('\0').filter(|p| !p.is_empty()).collect();
+        paths.sort_unstable();
+        paths.dedup();
+        Ok(paths
+            .into_iter()
+            .map(|path| ConflictedFile {
+                path: path.to_string(),
+                hunks: std::fs::read_to_string(working_dir.join(path))
+                    .map(|content| conflict_hunks(&content))
+                    .unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    pub fn status(working_dir: &Path) -> Result<SyncStatus, WorktreeError> {
+        let base = Self::base_ref(working_dir)?;
+        let in_progress = Self::in_progress(working_dir)?;
+        let conflicts = if in_progress.is_some() {
+            Self::conflicted_files(working_dir)?
+        } else {
+            Vec::new()
+        };
+        let (commits_ahead, commits_behind) = ahead_behind(working_dir, &base);
+        Ok(SyncStatus {
+            base,
+            in_progress,
+            conflicts,
+            commits_ahead,
+            commits_behind,
+        })
+    }
+
+    /// Fetch the base branch and merge it in or rebase onto it.
+    ///
+    /// The workspace must be clean. On conflicts the merge or rebase is left
+    /// in progress for [`SyncManager::continue_sync`] or [`SyncManager::abort`].
+    pub fn sync(working_dir: &Path, strategy: SyncStrategy) -> Result<SyncOutcome, WorktreeError> {
+        if let Some(current) = Self::in_progress(working_dir)? {
+            return Err(WorktreeError::CommandFailed(format!(
+                "A {} is already in progress; continue or abort it first",
+                current
+            )));
+        }
+        if let (true, description) = WorktreeManager::new().is_dirty(working_dir)? {
+            return Err(WorktreeError::CommandFailed(format!(
+                "Commit or stash {} before syncing",
+                description.unwrap_or_else(|| "uncommitted changes".to_string())
+            )));
+        }
+
+        let main = WorktreeManager::new().get_main_branch(working_dir)?;
+        // Offline or remote-less workspaces sync from what is already local
+        if let Err(e) = git(working_dir, &["fetch", "-q", "origin", &main]) {
+            tracing::debug!(error = %e, "Failed to fetch base branch before sync");
+        }
+        let base = Self::base_ref(working_dir)?;
+        if ahead_behind(working_dir, &base).1 == 0 {
+            return Ok(SyncOutcome::UpToDate { base });
+        }
+
+        let result = match strategy {
+            SyncStrategy::Merge => git(working_dir, &["merge", "--no-edit", &base]),
+            SyncStrategy::Rebase => git(working_dir, &["rebase", &base]),
+        };
+        Self::finish_step(working_dir, base, strategy, result)
+    }
+
+    /// Stage the resolved files and continue the merge or rebase
+    pub fn continue_sync(working_dir: &Path) -> Result<SyncOutcome, WorktreeError> {
+        let Some(strategy) = Self::in_progress(working_dir)? else {
+            return Err(WorktreeError::CommandFailed(
+                "No merge or rebase in progress".to_string(),
+            ));
+        };
+        let conflicts = Self::conflicted_files(working_dir)?;
+        let unresolved: Vec<&str> = conflicts
+            .iter()
+            .filter(|file| !file.hunks.is_empty())
+            .map(|file| file.path.as_str())
+            .collect();
+        if !unresolved.is_empty() {
+            return Err(WorktreeError::CommandFailed(format!(
+                "Conflict markers remain in {}",
+                unresolved.join(", ")
+            )));
+        }
+        if !conflicts.is_empty() {
+            let mut args = vec!["add", "-A", "--"];
+            args.extend(conflicts.iter().map(|file| file.path.as_str()));
+            git(working_dir, &args)?;
+        }
+
+        let base = Self::base_ref(working_dir)?;
+        let result = match strategy {
+            SyncStrategy::Merge => git(working_dir, &["commit", "--no-edit"]),
+            SyncStrategy::Rebase => git(working_dir, &["rebase", "--continue"]),
+        };
+        Self::finish_step(working_dir, base, strategy, result)
+    }
+
+    /// Abort the merge or rebase in progress, returning which one it was
+    pub fn abort(working_dir: &Path) -> Result<SyncStrategy, WorktreeError> {
+        let Some(strategy) = Self::in_progress(working_dir)? else {
+            return Err(WorktreeError::CommandFailed(
+                "No merge or rebase in progress".to_string(),
+            ));
+        };
+        match strategy {
+            SyncStrategy::Merge => git(working_dir, &["merge", "--abort"])?,
+            SyncStrategy::Rebase => git(working_dir, &["rebase", "--abort"])?,
+        };
+        Ok(strategy)
+    }
+
+    /// Prompt asking the agent to resolve the conflicts of a stopped sync
+    pub fn build_conflict_prompt(conflicts: &SyncConflicts) -> String {
+        let verb = match conflicts.strategy {
+            SyncStrategy::Merge => "Merging",
+            SyncStrategy::Rebase => "Rebasing onto",
+        };
+        let count = conflicts.files.len();
+        let mut prompt = format!(
+            "{} `{}` stopped on conflicts in {} file{}. Resolve them.\n",
+            verb,
+            conflicts.base,
+            count,
+            if count == 1 { "" } else { "s" }
+        );
+        if conflicts.strategy == SyncStrategy::Rebase {
+            prompt.push_str(&format!(
+                "\nThis is a rebase: the `HEAD` side of each conflict is `{}` plus the \
+                 branch commits already replayed, the other side is the branch commit being \
+                 replayed.\n",
+                conflicts.base
+            ));
+        }
+        for file in &conflicts.files {
+            prompt.push_str(&format!("\n## {}\n", file.path));
+            if file.hunks.is_empty() {
+                prompt.push_str(
+                    "No conflict markers (binary, deleted or renamed on one side); decide \
+                     which version to keep.\n",
+                );
+            }
+            for hunk in &file.hunks {
+                prompt.push_str(&format!("```\n{}\n```\n", hunk));
+            }
+        }
+        prompt.push_str(
+            "\nEdit each file so it keeps the intent of both sides and remove every conflict \
+             marker (`<<<<<<<`, `=======`, `>>>>>>>`). For files without markers, delete or \
+             restore them as appropriate. Do not run `git commit`, `git merge --continue` or \
+             `git rebase --continue`; the sync is continued once the conflicts are resolved. \
+             Finish with a short summary of how you resolved each file.",
+        );
+        prompt
+    }
+
+    /// Turn the result of a merge or rebase step into an outcome
+    fn finish_step(
+        working_dir: &Path,
+        base: String,
+        strategy: SyncStrategy,
+        result: Result<String, WorktreeError>,
+    ) -> Result<SyncOutcome, WorktreeError> {
+        let error = match result {
+            Ok(_) if Self::in_progress(working_dir)?.is_none() => {
+                return Ok(SyncOutcome::Synced { base, strategy });
+            }
+            Ok(_) => None,
+            Err(e) => Some(e),
+        };
+        let files = Self::conflicted_files(working_dir)?;
+        if !files.is_empty() {
+            return Ok(SyncOutcome::Conflicts(SyncConflicts {
+                base,
+                strategy,
+                files,
+            }));
+        }
+        Err(error.unwrap_or_else(|| {
+            WorktreeError::CommandFailed(format!("The {} stopped unexpectedly", strategy))
+        }))
+    }
+}
+
+/// Conflict regions of a file, markers included
+fn conflict_hunks(content: &str) -> Vec<String> {
+    let mut hunks = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in content.lines() {
+        if line.starts_with("<<<<<<< ") || line == "<<<<<<<" {
+            current = Some(vec![line]);
+            continue;
+        }
+        let Some(lines) = current.as_mut() else {
+            continue;
+        };
+        lines.push(line);
+        if line.starts_with(">>>>>>> ") || line == ">>>>>>>" {
+            let mut lines = current.take().unwrap_or_default();
+            if lines.len() > MAX_CONFLICT_LINES {
+                let omitted = lines.len() - MAX_CONFLICT_LINES;
+                let end = lines.split_off(lines.len() - MAX_CONFLICT_LINES / 2);
+                lines.truncate(MAX_CONFLICT_LINES / 2);
+                let mut hunk = lines.join("\n");
+                hunk.push_str(&format!("\n... ({} lines omitted) ...\n", omitted));
+                hunk.push_str(&end.join("\n"));
+                hunks.push(hunk);
+            } else {
+                hunks.push(lines.join("\n"));
+            }
+            if hunks.len() == MAX_CONFLICT_HUNKS {
+                break;
+            }
+        }
+    }
+    hunks
+}
+
+/// Commits on HEAD not in `base`, and in `base` not on HEAD
+fn ahead_behind(working_dir: &Path, base: &str) -> (usize, usize) {
+    let range = format!("HEAD...{}", base);
+    git(
+        working_dir,
+        &["rev-list", "--left-right", "--count", &range],
+    )
+    .ok()
+    .and_then(|output| {
+        let mut parts = output.split_whitespace().map(|n| n.parse().ok());
+        Some((parts.next()??, parts.next()??))
+    })
+    .unwrap_or((0, 0))
+}
+
+/// Path of a file in the worktree's git directory
+fn git_path(working_dir: &Path, name: &str) -> Result<PathBuf, WorktreeError> {
+    let path = PathBuf::from(git(working_dir, &["rev-parse", "--git-path", name])?.trim());
+    Ok(if path.is_absolute() {
+        path
+    } else {
+        working_dir.join(path)
+    })
+}
+
+/// Run git without opening an editor for merge and rebase messages.
+fn git(working_dir: &Path, args: &[&str]) -> Result<String, WorktreeError> {
+    super::git_with(working_dir, None, &[("GIT_EDITOR", "true")], args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn git_in(path: &Path, args: &[&str]) -> String {
+        git(path, args).unwrap()
+    }
+
+    /// Repo on `feature` whose `a.txt` conflicts with a newer `main`
+    fn diverged_repo(path: &Path) {
+        git_in(path, &["init", "-q", "-b", "main"]);
+        git_in(path, &["config", "user.email", "test@test.com"]);
+        git_in(path, &["config", "user.name", "Test"]);
+        std::fs::write(path.join("a.txt"), "one\n").unwrap();
+        git_in(path, &["add", "."]);
+        git_in(path, &["commit", "-q", "-m", "Initial commit"]);
+        git_in(path, &["checkout", "-q", "-b", "feature"]);
+        std::fs::write(path.join("a.txt"), "feature\n").unwrap();
+        git_in(path, &["commit", "-q", "-am", "Feature change"]);
+        git_in(path, &["checkout", "-q", "main"]);
+        std::fs::write(path.join("a.txt"), "main\n").unwrap();
+        git_in(path, &["commit", "-q", "-am", "Main change"]);
+        git_in(path, &["checkout", "-q", "feature"]);
+    }
+
+    #[test]
+    fn test_conflict_hunks() {
+        let content = "keep\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> main\nkeep\n";
+        assert_eq!(
+            conflict_hunks(content),
+            vec!["<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> main"]
+        );
+        assert!(conflict_hunks("no conflicts here\n").is_empty());
+    }
+
+    #[test]
+    fn test_merge_conflict_then_continue() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        diverged_repo(path);
+
+        let status = SyncManager::status(path).unwrap();
+        assert_eq!(status.base, "main");
+        assert_eq!((status.commits_ahead, status.commits_behind), (1, 1));
+
+        let SyncOutcome::Conflicts(conflicts) =
+            SyncManager::sync(path, SyncStrategy::Merge).unwrap()
+        else {
+            panic!("expected conflicts");
+        };
+        assert_eq!(conflicts.files.len(), 1);
+        assert_eq!(conflicts.files[0].path, "a.txt");
+        assert!(conflicts.files[0].hunks[0].contains("feature\n=======\nmain"));
+        assert_eq!(
+            SyncManager::in_progress(path).unwrap(),
+            Some(SyncStrategy::Merge)
+        );
+
+        let prompt = SyncManager::build_conflict_prompt(&conflicts);
+        assert!(prompt.starts_with("Merging `main` stopped on conflicts in 1 file."));
+        assert!(prompt.contains("## a.txt\n```\n<<<<<<< HEAD"));
+
+        // Markers left in place block continuing
+        assert!(SyncManager::continue_sync(path).is_err());
+        std::fs::write(path.join("a.txt"), "feature and main\n").unwrap();
+        assert_eq!(
+            SyncManager::continue_sync(path).unwrap(),
+            SyncOutcome::Synced {
+                base: "main".to_string(),
+                strategy: SyncStrategy::Merge,
+            }
+        );
+        assert_eq!(SyncManager::in_progress(path).unwrap(), None);
+        assert_eq!(SyncManager::status(path).unwrap().commits_behind, 0);
+        assert_eq!(
+            SyncManager::sync(path, SyncStrategy::Merge).unwrap(),
+            SyncOutcome::UpToDate {
+                base: "main".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_rebase_conflict_then_abort() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        diverged_repo(path);
+        let head = git_in(path, &["rev-parse", "HEAD"]);
+
+        let outcome = SyncManager::sync(path, SyncStrategy::Rebase).unwrap();
+        assert!(matches!(outcome, SyncOutcome::Conflicts(_)));
+        assert_eq!(
+            SyncManager::in_progress(path).unwrap(),
+            Some(SyncStrategy::Rebase)
+        );
+        // A second sync is refused while one is in progress
+        assert!(SyncManager::sync(path, SyncStrategy::Merge).is_err());
+
+        assert_eq!(SyncManager::abort(path).unwrap(), SyncStrategy::Rebase);
+        assert_eq!(SyncManager::in_progress(path).unwrap(), None);
+        assert_eq!(git_in(path, &["rev-parse", "HEAD"]), head);
+    }
+
+    #[test]
+    fn test_sync_refuses_dirty_workspace() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        diverged_repo(path);
+        std::fs::write(path.join("a.txt"), "edited\n").unwrap();
+
+        let err = SyncManager::sync(path, SyncStrategy::Merge).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Commit or stash 1 uncommitted change"));
+    }
+}
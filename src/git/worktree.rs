@@ -16,6 +16,8 @@ pub enum WorktreeError {
     NotFound(PathBuf),
     #[error("Failed to parse git output: {0}")]
     ParseError(String),
+    #[error("Hunk not found: {0}")]
+    HunkNotFound(String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
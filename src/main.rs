@@ -1,10 +1,11 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use conduit::{
     config::save_tool_path,
+    core::services::ExportFormat,
     ui::terminal_guard,
     util::{self, Tool, ToolAvailability},
-    App, Config,
+    AgentEvent, AgentType, App, Config,
 };
 use std::fs::{self, OpenOptions};
 use std::path::{Path, PathBuf};
@@ -17,6 +18,11 @@ struct Cli {
     #[arg(long, value_name = "PATH")]
     data_dir: Option<PathBuf>,
 
+    /// Run agents in a running `conduit serve` (the local one, or the daemon at
+    /// this WebSocket URL) so they keep going after the TUI exits
+    #[arg(long, value_name = "URL")]
+    attach: Option<Option<String>>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -50,7 +56,191 @@ enum Commands {
         /// Port to listen on
         #[arg(short, long, default_value_t = 3000)]
         port: u16,
+
+        /// Serve HTTPS with this PEM certificate chain
+        #[arg(long, value_name = "PATH", requires = "tls_key")]
+        tls_cert: Option<PathBuf>,
+
+        /// PEM private key for --tls-cert
+        #[arg(long, value_name = "PATH", requires = "tls_cert")]
+        tls_key: Option<PathBuf>,
+
+        /// Also allow API requests from this browser origin (repeatable)
+        #[arg(long = "allow-origin", value_name = "ORIGIN")]
+        allow_origins: Vec<String>,
+
+        /// Development mode: allow requests from any origin (e.g. the Vite dev server)
+        #[arg(long)]
+        dev: bool,
+
+        /// Do not require an API token (only on loopback addresses)
+        #[arg(long)]
+        no_auth: bool,
+
+        /// Keep serving in the background after the terminal closes
+        #[arg(long)]
+        detach: bool,
     },
+
+    /// Manage API tokens for the web server
+    Token {
+        #[command(subcommand)]
+        command: TokenCommand,
+    },
+
+    /// Run an agent without the TUI and stream its events to stdout
+    Run {
+        /// Agent to run (default: configured default agent)
+        #[arg(long, value_enum)]
+        agent: Option<RunAgent>,
+
+        /// Model to use (default: configured default model for the agent)
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Workspace name or ID to run in (default: current directory)
+        #[arg(long, value_name = "WORKSPACE")]
+        workspace: Option<String>,
+
+        /// Continue an existing session instead of creating a new one
+        #[arg(long, value_name = "SESSION_ID")]
+        session: Option<uuid::Uuid>,
+
+        /// Output format for agent events
+        #[arg(long, value_enum, default_value_t = RunFormat::Text)]
+        format: RunFormat,
+
+        /// Prompt for each turn; additional prompts are sent as follow-ups
+        #[arg(value_name = "PROMPT", required = true)]
+        prompts: Vec<String>,
+    },
+
+    /// Export a session transcript as Markdown, HTML, or JSON
+    Export {
+        /// Session ID to export
+        #[arg(value_name = "SESSION_ID")]
+        session_id: uuid::Uuid,
+
+        /// Transcript format
+        #[arg(short, long, value_enum, default_value_t = TranscriptFormat::Markdown)]
+        format: TranscriptFormat,
+
+        /// Output file (default: stdout)
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<PathBuf>,
+    },
+
+    /// Show or export the audit log of tool executions and permission decisions
+    Audit {
+        /// Only entries of this session
+        #[arg(long, value_name = "SESSION_ID")]
+        session: Option<uuid::Uuid>,
+
+        /// Only entries of this workspace (name or ID)
+        #[arg(long, value_name = "WORKSPACE")]
+        workspace: Option<String>,
+
+        /// Only entries of this kind: tool_started, tool_completed, command, file_changed,
+        /// permission
+        #[arg(long, value_name = "KIND")]
+        kind: Option<String>,
+
+        /// Only entries recorded since this date (YYYY-MM-DD) or RFC 3339 timestamp
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+
+        /// Only the most recent N entries
+        #[arg(short = 'n', long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = AuditFormat::Text)]
+        format: AuditFormat,
+
+        /// Output file (default: stdout)
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TokenCommand {
+    /// Generate a token and print it
+    Create {
+        /// Name to recognize the token by, e.g. the device using it
+        #[arg(long, default_value = "default")]
+        name: String,
+    },
+    /// List tokens (without their secrets)
+    List,
+    /// Revoke a token by ID or name
+    Revoke {
+        #[arg(value_name = "ID_OR_NAME")]
+        token: String,
+    },
+}
+
+/// Options of `conduit serve`
+struct ServeOptions {
+    host: String,
+    port: u16,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+    allow_origins: Vec<String>,
+    dev: bool,
+    no_auth: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RunAgent {
+    Claude,
+    Codex,
+    Gemini,
+    Opencode,
+}
+
+impl From<RunAgent> for AgentType {
+    fn from(agent: RunAgent) -> Self {
+        match agent {
+            RunAgent::Claude => AgentType::Claude,
+            RunAgent::Codex => AgentType::Codex,
+            RunAgent::Gemini => AgentType::Gemini,
+            RunAgent::Opencode => AgentType::Opencode,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RunFormat {
+    /// Human-readable transcript
+    Text,
+    /// One JSON-encoded AgentEvent per line
+    Jsonl,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum AuditFormat {
+    /// One line per entry
+    Text,
+    /// One JSON-encoded audit entry per line
+    Jsonl,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TranscriptFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl From<TranscriptFormat> for ExportFormat {
+    fn from(format: TranscriptFormat) -> Self {
+        match format {
+            TranscriptFormat::Markdown => ExportFormat::Markdown,
+            TranscriptFormat::Html => ExportFormat::Html,
+            TranscriptFormat::Json => ExportFormat::Json,
+        }
+    }
 }
 
 #[tokio::main]
@@ -71,11 +261,81 @@ async fn main() -> Result<()> {
         }) => {
             run_migrate_theme(&input, output.as_deref(), palette)?;
         }
-        Some(Commands::Serve { host, port }) => {
-            run_web_server(host, port).await?;
+        Some(Commands::Serve {
+            host,
+            port,
+            tls_cert,
+            tls_key,
+            allow_origins,
+            dev,
+            no_auth,
+            detach,
+        }) => {
+            if detach {
+                return spawn_detached_server();
+            }
+            run_web_server(ServeOptions {
+                host,
+                port,
+                tls_cert,
+                tls_key,
+                allow_origins,
+                dev,
+                no_auth,
+            })
+            .await?;
+        }
+        Some(Commands::Token { command }) => {
+            run_token_command(command)?;
+        }
+        Some(Commands::Run {
+            agent,
+            model,
+            workspace,
+            session,
+            format,
+            prompts,
+        }) => {
+            let params = conduit::core::services::RunParams {
+                agent_type: agent.map(AgentType::from),
+                model,
+                workspace,
+                session_id: session,
+                working_dir: std::env::current_dir()?,
+                prompts,
+            };
+            let success = run_headless(params, format).await?;
+            if !success {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Export {
+            session_id,
+            format,
+            output,
+        }) => {
+            run_export(session_id, format.into(), output.as_deref())?;
+        }
+        Some(Commands::Audit {
+            session,
+            workspace,
+            kind,
+            since,
+            limit,
+            format,
+            output,
+        }) => {
+            let filter = conduit::core::services::AuditFilter {
+                session_id: session,
+                workspace,
+                kind,
+                since,
+                limit,
+            };
+            run_audit(&filter, format, output.as_deref())?;
         }
         None => {
-            run_app().await?;
+            run_app(cli.attach).await?;
         }
     }
 
@@ -83,7 +343,7 @@ async fn main() -> Result<()> {
 }
 
 /// Run the main application
-async fn run_app() -> Result<()> {
+async fn run_app(attach: Option<Option<String>>) -> Result<()> {
     // Install panic hook to restore terminal state before printing panic message
     terminal_guard::install_panic_hook();
 
@@ -163,11 +423,111 @@ async fn run_app() -> Result<()> {
         }
     }
 
+    let daemon = if attach.is_some() || config.daemon.attach {
+        Some(connect_daemon(&config, attach.flatten()).await?)
+    } else {
+        None
+    };
+
     // Create and run app with tool availability
     let mut app = App::new(config, tools);
+    if let Some((client, incoming)) = daemon {
+        app.attach_daemon(client, incoming);
+    }
     app.run().await
 }
 
+/// Connect to the daemon for `conduit --attach`: the given URL, the one in
+/// `[daemon]`, or the local `conduit serve`
+async fn connect_daemon(
+    config: &Config,
+    url: Option<String>,
+) -> Result<(
+    conduit::web::DaemonClient,
+    tokio::sync::mpsc::UnboundedReceiver<conduit::web::DaemonEvent>,
+)> {
+    use conduit::web::{DaemonClient, DaemonInfo};
+
+    let info = match url.or_else(|| config.daemon.url.clone()) {
+        Some(url) => DaemonInfo {
+            url,
+            token: std::env::var("CONDUIT_TOKEN")
+                .ok()
+                .or_else(|| config.daemon.token.clone()),
+            ca_cert: config.daemon.ca_cert.clone(),
+            pid: None,
+        },
+        None => DaemonInfo::load(&util::daemon_info_path())?.ok_or_else(|| {
+            anyhow::anyhow!("No Conduit daemon is running; start one with `conduit serve --detach`")
+        })?,
+    };
+    DaemonClient::connect(&info).await
+}
+
+/// Start `conduit serve` again without `--detach` in its own session, so it
+/// outlives this terminal, and wait until it is reachable
+fn spawn_detached_server() -> Result<()> {
+    use std::process::{Command, Stdio};
+
+    fs::create_dir_all(util::logs_dir())?;
+    let log_path = util::logs_dir().join("daemon.log");
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)?;
+    let info_path = util::daemon_info_path();
+    let previous = fs::metadata(&info_path).and_then(|m| m.modified()).ok();
+
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .args(std::env::args_os().skip(1).filter(|arg| arg != "--detach"))
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: setsid is async-signal-safe and touches no state of this process
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setsid() == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    let mut child = command.spawn()?;
+
+    // The server writes its connection file once it is listening
+    for _ in 0..100 {
+        if let Some(status) = child.try_wait()? {
+            anyhow::bail!(
+                "conduit serve exited ({}); see {}",
+                status,
+                log_path.display()
+            );
+        }
+        let modified = fs::metadata(&info_path).and_then(|m| m.modified()).ok();
+        if modified.is_some() && modified != previous {
+            println!(
+                "Conduit daemon running (pid {}); logs in {}",
+                child.id(),
+                log_path.display()
+            );
+            println!("Attach the TUI with `conduit --attach`.");
+            return Ok(());
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    println!(
+        "Conduit daemon started (pid {}) but is not listening yet; see {}",
+        child.id(),
+        log_path.display()
+    );
+    Ok(())
+}
+
 /// Run a blocking dialog to get a tool path from the user
 ///
 /// This creates a minimal TUI just for the dialog, then returns control.
@@ -337,9 +697,10 @@ fn run_migrate_theme(input: &Path, output: Option<&Path>, extract_palette: bool)
 }
 
 /// Run the web server
-async fn run_web_server(host: String, port: u16) -> Result<()> {
+async fn run_web_server(options: ServeOptions) -> Result<()> {
     use conduit::core::ConduitCore;
-    use conduit::web::{run_server, ServerConfig, WebAppState};
+    use conduit::web::{run_server, DaemonInfo, ServerConfig, TokenStore, WebAppState};
+    use std::sync::Arc;
 
     // Initialize logging to stdout for web server mode
     tracing_subscriber::fmt()
@@ -353,6 +714,62 @@ async fn run_web_server(host: String, port: u16) -> Result<()> {
     // Create config
     let config = Config::load();
 
+    let loopback = is_loopback_host(&options.host);
+    let auth = config.web.auth && !options.no_auth;
+    if !auth && !loopback {
+        anyhow::bail!(
+            "Refusing to serve without authentication on {}; remove --no-auth or bind to 127.0.0.1",
+            options.host
+        );
+    }
+
+    let tls = match (
+        options.tls_cert.or_else(|| config.web.tls_cert.clone()),
+        options.tls_key.or_else(|| config.web.tls_key.clone()),
+    ) {
+        (Some(cert), Some(key)) => Some((cert, key)),
+        (None, None) => None,
+        _ => anyhow::bail!("Both a TLS certificate and a private key are required"),
+    };
+    if tls.is_none() && !loopback {
+        tracing::warn!(
+            host = %options.host,
+            "Serving plain HTTP on a non-loopback address; API tokens are sent unencrypted"
+        );
+    }
+
+    let tokens = if auth {
+        let store = TokenStore::open(util::api_tokens_path())?;
+        if store.is_empty() {
+            let (_, secret) = store.create("default")?;
+            let scheme = if tls.is_some() { "https" } else { "http" };
+            println!("Created an API token (shown only once):\n\n  {}\n", secret);
+            println!(
+                "Log in at {}://{}:{}/#token={}\n",
+                scheme, options.host, options.port, secret
+            );
+            println!("Manage tokens with `conduit token create|list|revoke`.");
+        }
+        Some(Arc::new(store))
+    } else {
+        None
+    };
+
+    // `conduit --attach` authenticates with a token of its own, replaced on every start
+    let daemon_token = match &tokens {
+        Some(store) => Some(store.create_daemon()?),
+        None => None,
+    };
+    let daemon = DaemonInfo::local(
+        &options.host,
+        options.port,
+        tls.as_ref().map(|(cert, _)| cert.clone()),
+        daemon_token,
+    );
+
+    let mut allowed_origins = config.web.allowed_origins.clone();
+    allowed_origins.extend(options.allow_origins);
+
     // Detect tool availability
     let tools = ToolAvailability::detect(&config.tool_paths);
 
@@ -364,17 +781,274 @@ async fn run_web_server(host: String, port: u16) -> Result<()> {
 
     // Configure server
     let server_config = ServerConfig {
-        host,
-        port,
-        cors_permissive: true,
+        host: options.host,
+        port: options.port,
+        cors_permissive: options.dev,
+        allowed_origins,
+        tokens: tokens.clone(),
+        tls,
+        daemon: Some(daemon),
     };
 
     // Run server
-    run_server(state, server_config).await?;
+    let result = run_server(state, server_config).await;
+    if let Some(store) = tokens {
+        store.revoke_daemon()?;
+    }
+    result
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    host == "localhost"
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Create, list or revoke web API tokens
+fn run_token_command(command: TokenCommand) -> Result<()> {
+    use conduit::web::TokenStore;
 
+    let store = TokenStore::open(util::api_tokens_path())?;
+    match command {
+        TokenCommand::Create { name } => {
+            let (token, secret) = store.create(&name)?;
+            println!(
+                "Created token {} ({}):\n\n  {}\n",
+                token.id, token.name, secret
+            );
+            println!("It cannot be shown again.");
+        }
+        TokenCommand::List => {
+            let tokens = store.list();
+            if tokens.is_empty() {
+                println!("No API tokens. Create one with `conduit token create`.");
+            }
+            for token in tokens {
+                println!(
+                    "{}  {}  created {}",
+                    token.id,
+                    token.name,
+                    token.created_at.format("%Y-%m-%d %H:%M")
+                );
+            }
+        }
+        TokenCommand::Revoke { token } => {
+            if !store.revoke(&token)? {
+                anyhow::bail!("No token with ID or name '{}'", token);
+            }
+            println!("Revoked {}", token);
+        }
+    }
     Ok(())
 }
 
+/// Export a stored session's transcript to a file or stdout
+fn run_export(session_id: uuid::Uuid, format: ExportFormat, output: Option<&Path>) -> Result<()> {
+    use conduit::core::services::ExportService;
+    use conduit::core::ConduitCore;
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::builder()
+                .with_default_directive(tracing::Level::WARN.into())
+                .from_env_lossy(),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let config = Config::load();
+    let tools = ToolAvailability::detect(&config.tool_paths);
+    let core = ConduitCore::new(config, tools);
+
+    let (_, content) = ExportService::export_session(&core, session_id, format)?;
+    match output {
+        Some(path) => {
+            fs::write(path, content)?;
+            eprintln!("Transcript written to {}", path.display());
+        }
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+fn run_audit(
+    filter: &conduit::core::services::AuditFilter,
+    format: AuditFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    use conduit::core::services::AuditService;
+    use conduit::core::ConduitCore;
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::builder()
+                .with_default_directive(tracing::Level::WARN.into())
+                .from_env_lossy(),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let config = Config::load();
+    let tools = ToolAvailability::detect(&config.tool_paths);
+    let core = ConduitCore::new(config, tools);
+
+    let entries = AuditService::list(&core, filter)?;
+    let content = match format {
+        AuditFormat::Jsonl => AuditService::to_jsonl(&entries)?,
+        AuditFormat::Text => entries.iter().map(format_audit_entry).collect(),
+    };
+    match output {
+        Some(path) => {
+            fs::write(path, content)?;
+            eprintln!(
+                "{} audit entries written to {}",
+                entries.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// One line of `conduit audit` text output
+fn format_audit_entry(entry: &conduit::data::AuditEntry) -> String {
+    let mut line = format!(
+        "{}  {}  {:<14}",
+        entry
+            .recorded_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        &entry.session_id.to_string()[..8],
+        entry.kind.as_str(),
+    );
+    for field in [&entry.tool, &entry.target].into_iter().flatten() {
+        line.push_str("  ");
+        line.push_str(field);
+    }
+    if let Some(outcome) = &entry.outcome {
+        line.push_str(&format!("  -> {}", outcome));
+    }
+    if let Some(actor) = entry.actor {
+        line.push_str(&format!(" ({})", actor.as_str()));
+    }
+    line.push('\n');
+    line
+}
+
+/// Run agent turns without the TUI
+///
+/// Returns false if any turn failed, so the caller can exit non-zero.
+async fn run_headless(
+    params: conduit::core::services::RunParams,
+    format: RunFormat,
+) -> Result<bool> {
+    use conduit::core::services::RunService;
+    use conduit::core::ConduitCore;
+    use std::io::{stdout, Write};
+
+    // Logs go to stderr so stdout stays machine-readable
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::builder()
+                .with_default_directive(tracing::Level::WARN.into())
+                .from_env_lossy(),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let config = Config::load();
+    let tools = ToolAvailability::detect(&config.tool_paths);
+    let core = ConduitCore::new(config, tools);
+
+    let run = RunService::prepare(&core, params)?;
+    let session_id = run.session.id;
+    let agent_type = run.session.agent_type;
+    if format == RunFormat::Text {
+        eprintln!(
+            "Session {} ({}) in {}",
+            session_id,
+            agent_type.display_name(),
+            run.working_dir.display()
+        );
+    }
+
+    let mut out = stdout().lock();
+    let mut text_state = TextOutputState::default();
+    let outcome = RunService::execute(&core, run, |event| {
+        let written = match format {
+            RunFormat::Jsonl => serde_json::to_string(event)
+                .map_err(std::io::Error::other)
+                .and_then(|line| writeln!(out, "{}", line)),
+            RunFormat::Text => write_text_event(&mut out, &mut text_state, event),
+        };
+        if let Err(err) = written.and_then(|_| out.flush()) {
+            tracing::warn!(error = %err, "Failed to write agent event");
+        }
+    })
+    .await?;
+
+    if let Some(error) = outcome.failure.as_deref() {
+        eprintln!(
+            "Run failed after {} turn(s): {}",
+            outcome.turns_completed, error
+        );
+    } else if format == RunFormat::Text {
+        eprintln!("Completed {} turn(s)", outcome.turns_completed);
+    }
+
+    Ok(outcome.is_success())
+}
+
+#[derive(Default)]
+struct TextOutputState {
+    /// Whether the last write left the cursor mid-line (streamed assistant text)
+    mid_line: bool,
+}
+
+/// Render a single agent event as plain text
+fn write_text_event(
+    out: &mut impl std::io::Write,
+    state: &mut TextOutputState,
+    event: &AgentEvent,
+) -> std::io::Result<()> {
+    let line = match event {
+        AgentEvent::AssistantMessage(msg) => {
+            write!(out, "{}", msg.text)?;
+            state.mid_line = !msg.is_final && !msg.text.ends_with('\n');
+            if msg.is_final && !msg.text.ends_with('\n') {
+                writeln!(out)?;
+            }
+            return Ok(());
+        }
+        AgentEvent::ToolStarted(tool) => format!("[tool] {} {}", tool.tool_name, tool.arguments),
+        AgentEvent::ToolCompleted(tool) if !tool.success => format!(
+            "[tool failed] {}",
+            tool.error.as_deref().unwrap_or("unknown error")
+        ),
+        AgentEvent::CommandOutput(cmd) if !cmd.is_streaming => match cmd.exit_code {
+            Some(code) => format!("[command] {} (exit {})", cmd.command, code),
+            None => format!("[command] {}", cmd.command),
+        },
+        AgentEvent::FileChanged(file) => format!("[file] {:?} {}", file.operation, file.path),
+        AgentEvent::TurnCompleted(done) => format!(
+            "[turn completed] {} input / {} output tokens",
+            done.usage.input_tokens, done.usage.output_tokens
+        ),
+        AgentEvent::TurnFailed(failed) => format!("[turn failed] {}", failed.error),
+        AgentEvent::Error(err) => format!("[error] {}", err.message),
+        _ => return Ok(()),
+    };
+    if state.mid_line {
+        writeln!(out)?;
+        state.mid_line = false;
+    }
+    writeln!(out, "{}", line)
+}
+
 /// Run the keyboard debug mode
 fn run_debug_keys() -> Result<()> {
     use crossterm::{
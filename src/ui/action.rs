@@ -22,8 +22,22 @@ pub enum Action {
     NewWorkspaceUnderCursor,
     /// Open/create pull request
     OpenPr,
+    /// Commit the workspace's changes with a generated message
+    OpenCommitDialog,
+    /// Show unresolved review comments on the workspace's PR
+    OpenReviewComments,
+    /// Send the failing CI checks of the workspace's PR to the agent
+    FixCi,
+    /// Merge in or rebase onto the workspace's base branch
+    SyncWithBase,
+    /// Continue a sync once its conflicts are resolved
+    ContinueSync,
+    /// Abort the sync in progress
+    AbortSync,
     /// Fork current session into a new workspace and tab
     ForkSession,
+    /// Continue the current session with a different agent in a new workspace and tab
+    HandoffSession,
     /// Interrupt current agent processing
     InterruptAgent,
     /// Toggle between Chat and RawEvents view
@@ -34,8 +48,12 @@ pub enum Action {
     ShowThemePicker,
     /// Toggle performance metrics display
     ToggleMetrics,
+    /// Mute or unmute attention notifications
+    ToggleQuietMode,
     /// Dump debug state to file
     DumpDebugState,
+    /// Export the current session transcript (Markdown, HTML, JSON)
+    ExportTranscript,
     /// Suspend the application (Ctrl+Z)
     Suspend,
     /// Copy current workspace path to clipboard
@@ -74,6 +92,12 @@ pub enum Action {
     ScrollPrevUserMessage,
     /// Jump to next user message in chat
     ScrollNextUserMessage,
+    /// Start incremental find in the current chat
+    FindInChat,
+    /// Jump to the next find match
+    FindNext,
+    /// Jump to the previous find match
+    FindPrev,
 
     // ========== Input Box Editing ==========
     /// Insert a newline (for multi-line input)
@@ -213,6 +237,81 @@ pub enum Action {
     /// Cycle session import agent filter
     CycleImportFilter,
 
+    // ========== Session Search ==========
+    /// Open full-text search across all sessions
+    OpenSessionSearch,
+    /// Open the selected search result
+    OpenSearchResult,
+
+    // ========== Usage Stats ==========
+    /// Open the usage statistics dialog
+    ShowUsageStats,
+    /// Show the next usage breakdown (day, agent, model, ...)
+    NextStatsView,
+    /// Show the previous usage breakdown
+    PrevStatsView,
+    /// Cycle the usage statistics time range
+    CycleStatsRange,
+
+    /// Rewind the workspace to before the last agent turn
+    RewindTurn,
+
+    // ========== Diff Viewer ==========
+    /// Open the workspace diff for the active session
+    ShowDiff,
+    /// Show the next changed file
+    DiffNextFile,
+    /// Show the previous changed file
+    DiffPrevFile,
+    /// Toggle unified/side-by-side hunks
+    DiffToggleLayout,
+    /// Toggle comparing against HEAD or the base branch
+    DiffToggleBase,
+    /// Recompute the diff
+    DiffReload,
+    /// Scroll to the next hunk
+    DiffNextHunk,
+    /// Scroll to the previous hunk
+    DiffPrevHunk,
+    /// Stage the current hunk (keep it)
+    DiffStageHunk,
+    /// Discard the current hunk from the working tree
+    DiffRevertHunk,
+    /// Toggle showing all changes or only the last turn's files
+    DiffToggleScope,
+
+    // ========== Race ==========
+    /// Race the prompt in the input box across several agents
+    StartRace,
+    /// Race the given prompt across several agents (`:race <prompt>`)
+    RacePrompt(String),
+    /// Switch to the selected contender's session
+    RaceOpenContender,
+    /// Keep the selected contender and archive the others
+    RacePickWinner,
+    /// Run the race test command for every contender
+    RaceRunTests,
+    /// Refresh the race standings
+    RaceReload,
+
+    // ========== Commit Dialog ==========
+    /// Toggle pushing the branch after committing
+    ToggleCommitPush,
+    /// Discard the commit message and generate a new one
+    RegenerateCommitMessage,
+
+    // ========== Review Comments ==========
+    /// Toggle selection of the highlighted review comment
+    ToggleReviewCommentSelected,
+    /// Select every listed review comment (or clear the selection)
+    SelectAllReviewComments,
+    /// Toggle the addressed mark of the targeted review comments
+    ToggleReviewCommentAddressed,
+    /// Show or hide review comments marked addressed
+    ToggleAddressedReviewComments,
+    /// Fetch the review comments again
+    ReloadReviewComments,
+
     // ========== Command Mode ==========
     /// Show help dialog
     ShowHelp,
@@ -236,13 +335,22 @@ impl Action {
             Action::NewProject => "New project",
             Action::NewWorkspaceUnderCursor => "New workspace (current project)",
             Action::OpenPr => "Open/create PR",
+            Action::OpenCommitDialog => "Commit changes",
+            Action::OpenReviewComments => "PR review comments",
+            Action::FixCi => "Fix failing CI checks",
+            Action::SyncWithBase => "Sync with base branch",
+            Action::ContinueSync => "Continue sync",
+            Action::AbortSync => "Abort sync",
             Action::ForkSession => "Fork session",
+            Action::HandoffSession => "Hand off session to another agent",
             Action::InterruptAgent => "Interrupt agent",
             Action::ToggleViewMode => "Toggle view mode",
             Action::ShowModelSelector => "Select model",
             Action::ShowThemePicker => "Change theme",
             Action::ToggleMetrics => "Toggle metrics",
+            Action::ToggleQuietMode => "Toggle notifications (quiet mode)",
             Action::DumpDebugState => "Dump debug state",
+            Action::ExportTranscript => "Export transcript",
             Action::Suspend => "Suspend",
             Action::CopyWorkspacePath => "Copy workspace path",
             Action::CopySelection => "Copy selection",
@@ -265,6 +373,9 @@ impl Action {
             Action::ScrollToBottom => "Scroll to bottom",
             Action::ScrollPrevUserMessage => "Previous user message",
             Action::ScrollNextUserMessage => "Next user message",
+            Action::FindInChat => "Find in session",
+            Action::FindNext => "Next match",
+            Action::FindPrev => "Previous match",
 
             // Input editing
             Action::InsertNewline => "Insert newline",
@@ -343,6 +454,50 @@ impl Action {
             Action::ImportSession => "Import selected",
             Action::CycleImportFilter => "Cycle filter",
 
+            // Session search
+            Action::OpenSessionSearch => "Search sessions",
+            Action::OpenSearchResult => "Open result",
+
+            // Usage stats
+            Action::ShowUsageStats => "Usage statistics",
+            Action::NextStatsView => "Next breakdown",
+            Action::PrevStatsView => "Previous breakdown",
+            Action::CycleStatsRange => "Cycle time range",
+
+            Action::RewindTurn => "Rewind last turn",
+
+            // Diff viewer
+            Action::ShowDiff => "Show workspace diff",
+            Action::DiffNextFile => "Next changed file",
+            Action::DiffPrevFile => "Previous changed file",
+            Action::DiffToggleLayout => "Toggle unified/side-by-side",
+            Action::DiffToggleBase => "Toggle HEAD/base branch",
+            Action::DiffReload => "Reload diff",
+            Action::DiffNextHunk => "Next hunk",
+            Action::DiffPrevHunk => "Previous hunk",
+            Action::DiffStageHunk => "Stage hunk",
+            Action::DiffRevertHunk => "Revert hunk",
+            Action::DiffToggleScope => "Toggle last turn/all changes",
+
+            // Race
+            Action::StartRace => "Race prompt across agents",
+            Action::RacePrompt(_) => "Race prompt",
+            Action::RaceOpenContender => "Open contender",
+            Action::RacePickWinner => "Pick winner",
+            Action::RaceRunTests => "Run tests",
+            Action::RaceReload => "Reload race",
+
+            // Commit dialog
+            Action::ToggleCommitPush => "Toggle push after commit",
+            Action::RegenerateCommitMessage => "Regenerate commit message",
+
+            // Review comments
+            Action::ToggleReviewCommentSelected => "Select review comment",
+            Action::SelectAllReviewComments => "Select all review comments",
+            Action::ToggleReviewCommentAddressed => "Mark comment addressed",
+            Action::ToggleAddressedReviewComments => "Show/hide addressed comments",
+            Action::ReloadReviewComments => "Reload review comments",
+
             // Command mode
             Action::ShowHelp => "Show help",
             Action::ExecuteCommand => "Execute command",
@@ -362,11 +517,18 @@ impl Action {
                 | Action::ShowThemePicker
                 | Action::OpenQueueEditor
                 | Action::OpenSessionImport
+                | Action::OpenSessionSearch
+                | Action::ShowUsageStats
                 | Action::ShowHelp
                 | Action::AddRepository
                 | Action::OpenSettings
                 | Action::OpenCommandPalette
                 | Action::ForkSession
+                | Action::HandoffSession
+                | Action::RewindTurn
+                | Action::RacePickWinner
+                | Action::OpenCommitDialog
+                | Action::OpenReviewComments
         )
     }
 
@@ -380,13 +542,22 @@ impl Action {
                 | Action::NewProject
                 | Action::NewWorkspaceUnderCursor
                 | Action::OpenPr
+                | Action::OpenCommitDialog
+                | Action::OpenReviewComments
+                | Action::FixCi
+                | Action::SyncWithBase
+                | Action::ContinueSync
+                | Action::AbortSync
                 | Action::ForkSession
+                | Action::HandoffSession
                 | Action::InterruptAgent
                 | Action::ToggleViewMode
                 | Action::ShowModelSelector
                 | Action::ShowThemePicker
                 | Action::ToggleMetrics
+                | Action::ToggleQuietMode
                 | Action::DumpDebugState
+                | Action::ExportTranscript
                 | Action::CopyWorkspacePath
                 | Action::CopySelection
                 // Tab management
@@ -398,6 +569,7 @@ impl Action {
                 | Action::ScrollPageDown
                 | Action::ScrollToTop
                 | Action::ScrollToBottom
+                | Action::FindInChat
                 // Input editing
                 | Action::EditPromptExternal
                 // Sidebar
@@ -409,6 +581,11 @@ impl Action {
                 | Action::ToggleAgentMode
                 | Action::OpenQueueEditor
                 | Action::OpenSessionImport
+                | Action::OpenSessionSearch
+                | Action::ShowUsageStats
+                | Action::ShowDiff
+                | Action::RewindTurn
+                | Action::StartRace
                 | Action::ShowHelp
         )
     }
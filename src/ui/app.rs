@@ -10,7 +10,10 @@ use std::time::{Duration, Instant};
 use anyhow::anyhow;
 use chrono::Utc;
 use crossterm::{
-    event::{EnableMouseCapture, Event, EventStream, KeyCode, KeyModifiers, MouseEventKind},
+    event::{
+        EnableFocusChange, EnableMouseCapture, Event, EventStream, KeyCode, KeyModifiers,
+        MouseEventKind,
+    },
     execute,
     terminal::{enable_raw_mode, EnterAlternateScreen},
 };
@@ -27,20 +30,30 @@ use tokio::sync::mpsc;
 use unicode_width::UnicodeWidthStr;
 use uuid::Uuid;
 
-use crate::agent::events::UserQuestion;
+use crate::agent::events::{ControlRequestEvent, UserQuestion};
 use crate::agent::{
-    load_claude_history_with_debug, load_codex_history_with_debug,
+    load_claude_history_with_debug, load_codex_history_with_debug, load_journal_history,
     load_opencode_history_for_dir_with_debug, load_opencode_history_with_debug, AgentEvent,
     AgentInput, AgentMode, AgentRunner, AgentStartConfig, AgentType, ClaudeCodeRunner,
     CodexCliRunner, GeminiCliRunner, HistoryDebugEntry, MessageDisplay, ModelRegistry,
-    OpencodeRunner, SessionId,
+    OpencodeRunner, PermissionReply, SessionId, TokenUsage,
 };
 use crate::config::{parse_action, parse_key_notation, Config, KeyContext, COMMAND_NAMES};
-use crate::core::resolve_repo_workspace_settings;
+use crate::core::services::search_service::DEFAULT_SEARCH_LIMIT;
+use crate::core::services::{
+    BudgetStatus, CiService, CloneService, CommitService, ConfigService, CostService,
+    CreateRaceParams, ExportFormat, ExportService, RaceService, ReviewService, SearchService,
+    StatsService, SyncService, TranscriptInfo,
+};
 use crate::core::ConduitCore;
+use crate::core::{
+    resolve_repo_workspace_settings, resolve_workspace_hooks, run_workspace_hooks, HookContext,
+    HookEvent, HookReport, WorkspaceHooks,
+};
 use crate::data::{
-    AppStateStore, ForkSeed, ForkSeedStore, QueuedImageAttachment, QueuedMessage,
-    QueuedMessageMode, Repository, RepositoryStore, SessionTab, SessionTabStore, WorkspaceStore,
+    AppStateStore, AuditActor, ForkSeed, ForkSeedStore, QueuedImageAttachment, QueuedMessage,
+    QueuedMessageMode, Repository, RepositoryStore, SearchHit, SearchSource, SessionEventStore,
+    SessionTab, SessionTabStore, UsageStore, Workspace, WorkspaceStore,
 };
 use crate::git::{PrManager, PrStatus, WorkspaceMode, WorkspaceRepoManager};
 use crate::ui::action::Action;
@@ -49,22 +62,28 @@ use crate::ui::app_queue;
 use crate::ui::app_state::{AppState, PendingForkRequest};
 use crate::ui::capabilities::AgentCapabilities;
 use crate::ui::components::{
-    dialog_content_area, AddRepoDialog, AgentSelector, BaseDirDialog, ChatMessage, CommandPalette,
-    ConfirmationContext, ConfirmationDialog, ConfirmationType, DefaultModelSelection, ErrorDialog,
-    EventDirection, GlobalFooter, HelpDialog, InlinePromptState, InlinePromptType, MessageRole,
-    MissingToolDialog, ModelSelector, ProcessingState, ProjectPicker, PromptAnswer, RawEventsClick,
-    SessionHeader, SessionImportPicker, Sidebar, SidebarData, SlashCommand, SlashMenu, TabBar,
-    TabBarHitTarget, ThemePicker, SIDEBAR_HEADER_ROWS,
+    dialog_content_area, AddRepoDialog, AgentSelector, AgentSelectorPurpose, BaseDirDialog,
+    ChatMessage, CommandPalette, CommitDialog, ConfirmationContext, ConfirmationDialog,
+    ConfirmationType, DefaultModelSelection, ErrorDialog, EventDirection, GlobalFooter, HelpDialog,
+    InlinePromptState, InlinePromptType, MessageRole, MissingToolDialog, ModelSelector,
+    ProcessingState, ProjectPicker, PromptAnswer, RawEventsClick, ReviewCommentsDialog,
+    SessionHeader, SessionImportPicker, SessionSearchDialog, Sidebar, SidebarData, SlashCommand,
+    SlashMenu, TabBar, TabBarHitTarget, ThemePicker, UsageStatsDialog, SIDEBAR_HEADER_ROWS,
 };
-use crate::ui::effect::Effect;
+use crate::ui::effect::{Effect, SyncStep};
 use crate::ui::events::{
     AppEvent, ForkWorkspaceCreated, InputMode, RemoveProjectResult, TitleGeneratedResult, ViewMode,
     WorkspaceArchived, WorkspaceCreated,
 };
+use crate::ui::notifications::{Notification, NotificationKind, Notifier};
 use crate::ui::session::AgentSession;
 use crate::ui::terminal_guard::TerminalGuard;
 use crate::util::ToolAvailability;
+use crate::web::{ClientMessage, DaemonClient};
 
+mod app_actions_checkpoint;
+mod app_actions_ci;
+mod app_actions_commit;
 mod app_actions_confirm;
 mod app_actions_confirmation;
 mod app_actions_dialog;
@@ -74,11 +93,15 @@ mod app_actions_list;
 mod app_actions_overlay;
 mod app_actions_pr;
 mod app_actions_queue;
+mod app_actions_race;
 mod app_actions_raw_events;
+mod app_actions_review;
 mod app_actions_scroll;
 mod app_actions_sidebar;
 mod app_actions_submit;
+mod app_actions_sync;
 mod app_actions_tabs;
+mod app_daemon;
 mod app_input;
 mod app_scroll;
 mod app_selection;
@@ -167,6 +190,10 @@ pub struct App {
     event_rx: mpsc::UnboundedReceiver<AppEvent>,
     /// Background git/PR status tracker
     git_tracker: Option<crate::ui::git_tracker::GitTrackerHandle>,
+    /// Daemon running this TUI's agents, when attached to `conduit serve`
+    daemon: Option<DaemonClient>,
+    /// Bell/desktop notifications for agents that need attention
+    notifier: Notifier,
 }
 
 // Convenience accessors for backward compatibility during refactoring
@@ -242,6 +269,30 @@ impl App {
         self.core.fork_seed_store_clone()
     }
 
+    /// Get the session event DAO.
+    #[inline]
+    fn session_event_dao(&self) -> Option<&SessionEventStore> {
+        self.core.session_event_store()
+    }
+
+    /// Get a clone of the session event DAO.
+    #[inline]
+    fn session_event_dao_clone(&self) -> Option<SessionEventStore> {
+        self.core.session_event_store_clone()
+    }
+
+    /// Get the usage DAO.
+    #[inline]
+    fn usage_dao(&self) -> Option<&UsageStore> {
+        self.core.usage_store()
+    }
+
+    /// Get a clone of the usage DAO.
+    #[inline]
+    fn usage_dao_clone(&self) -> Option<UsageStore> {
+        self.core.usage_store_clone()
+    }
+
     /// Get the Claude runner.
     #[inline]
     fn claude_runner(&self) -> &Arc<ClaudeCodeRunner> {
@@ -321,6 +372,53 @@ fn send_app_event(
     }
 }
 
+/// Run a workspace's hooks, streaming their output to the app as it arrives
+fn run_hooks_with_output(
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+    hooks: &WorkspaceHooks,
+    event: HookEvent,
+    context: HookContext<'_>,
+) -> HookReport {
+    let workspace_name = context.workspace_name.to_string();
+    run_workspace_hooks(hooks, event, context, &mut |line| {
+        send_app_event(
+            event_tx,
+            AppEvent::HookOutput {
+                workspace_name: workspace_name.clone(),
+                event,
+                line: line.to_string(),
+            },
+            "hook_output",
+        );
+    })
+}
+
+/// Run a new workspace's `on_create` hooks, returning the report if they failed
+fn run_setup_hooks(
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+    config: &Config,
+    repo: &Repository,
+    workspace: &Workspace,
+    repo_path: &std::path::Path,
+) -> Option<HookReport> {
+    let hooks = resolve_workspace_hooks(config, repo);
+    if !hooks.has(HookEvent::OnCreate) {
+        return None;
+    }
+    let report = run_hooks_with_output(
+        event_tx,
+        &hooks,
+        HookEvent::OnCreate,
+        HookContext {
+            workspace_name: &workspace.name,
+            branch: &workspace.branch,
+            workspace_path: &workspace.path,
+            repo_path,
+        },
+    );
+    (!report.succeeded()).then_some(report)
+}
+
 impl App {
     // When true, selection drag auto-scrolls as soon as the cursor hits the first/last row.
     // When false, auto-scroll only starts after the cursor leaves the chat area.
@@ -354,6 +452,8 @@ impl App {
             event_tx,
             event_rx,
             git_tracker,
+            daemon: None,
+            notifier: Notifier::new(config.notifications.clone()),
         };
 
         // Update agent selector based on available tools
@@ -465,14 +565,20 @@ impl App {
                 }
             }
 
+            // Prefer Conduit's own event journal for chat history
+            let journal_restored =
+                Self::restore_history_from_journal(self.session_event_dao(), &mut session);
+            Self::restore_session_cost(self.usage_dao(), &mut session);
+
             // Set resume session ID if available
             if let Some(ref session_id_str) = tab.agent_session_id {
                 let session_id = SessionId::from_string(session_id_str.clone());
                 session.resume_session_id = Some(session_id.clone());
                 session.agent_session_id = Some(session_id.clone());
 
-                // Load chat history from agent files
+                // Fall back to loading chat history from agent files
                 match tab.agent_type {
+                    _ if journal_restored => {}
                     AgentType::Claude => {
                         if let Ok((msgs, debug_entries, file_path)) =
                             load_claude_history_with_debug(session_id_str)
@@ -526,7 +632,7 @@ impl App {
                         }
                     }
                 }
-            } else if tab.agent_type == AgentType::Opencode {
+            } else if !journal_restored && tab.agent_type == AgentType::Opencode {
                 if let Some(working_dir) = session.working_dir.as_ref() {
                     if let Ok((session_id_str, msgs, debug_entries, file_path)) =
                         load_opencode_history_for_dir_with_debug(working_dir)
@@ -928,7 +1034,12 @@ impl App {
         // Create terminal guard AFTER enabling features - Drop will clean up on any exit path
         let mut guard = TerminalGuard::new(keyboard_enhancement_enabled);
 
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange
+        )?;
 
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
@@ -982,6 +1093,9 @@ impl App {
     }
 
     fn persist_session_state_on_exit(&self) {
+        if let Some(journal) = self.core.session_journal() {
+            journal.flush();
+        }
         let snapshot = self.snapshot_session_state();
         Self::persist_session_state(
             snapshot,
@@ -1212,6 +1326,15 @@ impl App {
             self.state.was_splash_visible = splash_visible;
         }
 
+        if let Some(failure) = self
+            .core
+            .session_journal()
+            .and_then(|journal| journal.take_audit_failure())
+        {
+            self.show_error("Audit Log Write Failed", &failure);
+            state_changed = true;
+        }
+
         // Clear stale double-press state and messages
         let now = Instant::now();
         let timeout = Duration::from_millis(DOUBLE_PRESS_TIMEOUT_MS);
@@ -1278,6 +1401,11 @@ impl App {
         // Tick session import spinner (for loading state)
         self.state.session_import_state.tick();
 
+        // Tick session search spinner (while the index refreshes)
+        self.state.session_search_state.tick();
+        self.state.commit_dialog_state.tick();
+        self.state.review_comments_state.tick();
+
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             session.tick();
         }
@@ -1299,6 +1427,7 @@ impl App {
             session.agent_input_tx = None;
             // Clear any active inline prompt and pending permissions since the agent is gone
             session.inline_prompt = None;
+            session.queued_inline_prompts.clear();
             session.pending_tool_permissions.clear();
             session.pending_tool_permission_responses.clear();
             if session.is_processing {
@@ -1311,6 +1440,9 @@ impl App {
         if let Some(pid) = pid {
             self.spawn_agent_termination(pid, pid_start_time, "interrupt_agent", session_id, true);
         }
+        if let (Some(daemon), Some(session_id)) = (&self.daemon, session_id) {
+            daemon.send(ClientMessage::StopSession { session_id });
+        }
 
         if was_processing {
             if let Some(session_id) = session_id {
@@ -1583,6 +1715,7 @@ impl App {
     fn stop_agent_for_tab(&mut self, tab_index: usize) {
         let mut pid = None;
         let mut pid_start_time = None;
+        let mut session_id = None;
         {
             if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
                 Self::flush_pending_agent_output(session);
@@ -1591,12 +1724,16 @@ impl App {
                 }
                 pid = session.agent_pid.take();
                 pid_start_time = session.agent_pid_start_time.take();
+                session_id = Some(session.id);
             }
         }
 
         if let Some(pid) = pid {
             self.spawn_agent_termination(pid, pid_start_time, "stop_agent_for_tab", None, false);
         }
+        if let (Some(daemon), Some(session_id)) = (&self.daemon, session_id) {
+            daemon.send(ClientMessage::StopSession { session_id });
+        }
     }
 
     /// Handle Ctrl+C press with double-press detection
@@ -1726,6 +1863,7 @@ impl App {
             | Action::NewProject
             | Action::NewWorkspaceUnderCursor
             | Action::ForkSession
+            | Action::HandoffSession
             | Action::InterruptAgent
             | Action::ToggleViewMode
             | Action::ShowModelSelector
@@ -1733,9 +1871,35 @@ impl App {
             | Action::OpenSessionImport
             | Action::ImportSession
             | Action::CycleImportFilter
+            | Action::OpenSessionSearch
+            | Action::OpenSearchResult
+            | Action::ShowUsageStats
+            | Action::NextStatsView
+            | Action::PrevStatsView
+            | Action::CycleStatsRange
+            | Action::RewindTurn
+            | Action::ShowDiff
+            | Action::DiffNextFile
+            | Action::DiffPrevFile
+            | Action::DiffToggleLayout
+            | Action::DiffToggleBase
+            | Action::DiffReload
+            | Action::DiffNextHunk
+            | Action::DiffPrevHunk
+            | Action::DiffStageHunk
+            | Action::DiffRevertHunk
+            | Action::DiffToggleScope
+            | Action::StartRace
+            | Action::RacePrompt(_)
+            | Action::RaceOpenContender
+            | Action::RacePickWinner
+            | Action::RaceRunTests
+            | Action::RaceReload
             | Action::ToggleMetrics
+            | Action::ToggleQuietMode
             | Action::ToggleAgentMode
             | Action::DumpDebugState
+            | Action::ExportTranscript
             | Action::CopyWorkspacePath
             | Action::CopySelection => {
                 self.handle_global_action(action, &mut effects);
@@ -1745,6 +1909,29 @@ impl App {
                     effects.push(effect);
                 }
             }
+            Action::OpenCommitDialog
+            | Action::ToggleCommitPush
+            | Action::RegenerateCommitMessage => {
+                self.handle_commit_action(action, &mut effects);
+            }
+            Action::OpenReviewComments
+            | Action::ToggleReviewCommentSelected
+            | Action::SelectAllReviewComments
+            | Action::ToggleReviewCommentAddressed
+            | Action::ToggleAddressedReviewComments
+            | Action::ReloadReviewComments => {
+                self.handle_review_action(action, &mut effects);
+            }
+            Action::FixCi => {
+                if let Some(effect) = self.fix_ci() {
+                    effects.push(effect);
+                }
+            }
+            Action::SyncWithBase | Action::ContinueSync | Action::AbortSync => {
+                if let Some(effect) = self.handle_sync_action(action) {
+                    effects.push(effect);
+                }
+            }
             Action::Suspend => {
                 if let Err(err) = self.suspend_app(terminal, guard) {
                     tracing::warn!(error = %err, "Suspend failed: {err}");
@@ -1773,7 +1960,10 @@ impl App {
             | Action::ScrollToTop
             | Action::ScrollToBottom
             | Action::ScrollPrevUserMessage
-            | Action::ScrollNextUserMessage => {
+            | Action::ScrollNextUserMessage
+            | Action::FindInChat
+            | Action::FindNext
+            | Action::FindPrev => {
                 self.handle_scroll_action(action);
             }
 
@@ -1871,11 +2061,9 @@ impl App {
                         self.state
                             .model_selector_state
                             .set_default_model(agent_type, model_id.clone());
-                        if let Err(err) = crate::core::services::ConfigService::set_default_model(
-                            &mut self.core,
-                            agent_type,
-                            &model_id,
-                        ) {
+                        if let Err(err) =
+                            ConfigService::set_default_model(&mut self.core, agent_type, &model_id)
+                        {
                             tracing::warn!(error = %err, "Failed to save default model");
                             self.state.set_timed_footer_message(
                                 format!("Failed to save default model: {err}"),
@@ -1977,6 +2165,7 @@ impl App {
                     session_id,
                     agent_type,
                     config,
+                    checkpoint,
                 } => {
                     let runner: Arc<dyn AgentRunner> = match agent_type {
                         AgentType::Claude => self.claude_runner().clone(),
@@ -1988,6 +2177,9 @@ impl App {
                     let event_tx = self.event_tx.clone();
 
                     tokio::spawn(async move {
+                        if let Some(checkpoint) = checkpoint {
+                            checkpoint.record().await;
+                        }
                         match runner.start(config).await {
                             Ok(mut handle) => {
                                 // Send PID (and input channel when available) to main app for interrupt support
@@ -2090,6 +2282,17 @@ impl App {
                         }
                     });
                 }
+                Effect::DaemonPrompt {
+                    session_id,
+                    prompt,
+                    working_dir,
+                    model,
+                    hidden,
+                    images,
+                } => {
+                    self.send_daemon_prompt(session_id, prompt, working_dir, model, hidden, images)
+                        .await;
+                }
                 Effect::PrPreflight {
                     tab_index,
                     working_dir,
@@ -2128,6 +2331,14 @@ impl App {
                         "debug_dumped",
                     );
                 }
+                Effect::ExportTranscript => {
+                    let result = self.export_transcript();
+                    send_app_event(
+                        &self.event_tx,
+                        AppEvent::TranscriptExported { result },
+                        "transcript_exported",
+                    );
+                }
                 Effect::RunShellCommand {
                     session_id,
                     message_index,
@@ -2367,9 +2578,12 @@ impl App {
                                 return Err(format!("Failed to save workspace to database: {}", e));
                             }
 
+                            let setup_failure =
+                                run_setup_hooks(&event_tx, &config, &repo, &workspace, &base_path);
                             Ok(WorkspaceCreated {
                                 repo_id,
                                 workspace_id,
+                                setup_failure,
                             })
                         })();
 
@@ -2476,9 +2690,12 @@ impl App {
                                 return Err(format!("Failed to save workspace to database: {}", e));
                             }
 
+                            let setup_failure =
+                                run_setup_hooks(&event_tx, &config, &repo, &workspace, &base_path);
                             Ok(ForkWorkspaceCreated {
                                 repo_id: parent_workspace.repository_id,
                                 workspace_id,
+                                setup_failure,
                             })
                         })(
                         );
@@ -2493,6 +2710,225 @@ impl App {
                         );
                     });
                 }
+                Effect::StartRace {
+                    workspace_id,
+                    prompt,
+                    contenders,
+                } => {
+                    let race_store = self.core.race_store_clone();
+                    let repo_dao = self.repo_dao_clone();
+                    let workspace_dao = self.workspace_dao_clone();
+                    let worktree_manager = self.worktree_manager().clone();
+                    let config = self.config().clone();
+                    let event_tx = self.event_tx.clone();
+
+                    tokio::task::spawn_blocking(move || {
+                        let result: Result<crate::data::Race, String> = (|| {
+                            let race_store =
+                                race_store.ok_or_else(|| "No race store available".to_string())?;
+                            let repo_dao = repo_dao
+                                .ok_or_else(|| "No repository DAO available".to_string())?;
+                            let workspace_dao = workspace_dao
+                                .ok_or_else(|| "No workspace DAO available".to_string())?;
+                            RaceService::create(
+                                &race_store,
+                                &workspace_dao,
+                                &repo_dao,
+                                &worktree_manager,
+                                &config,
+                                CreateRaceParams {
+                                    workspace_id,
+                                    prompt,
+                                    contenders,
+                                },
+                            )
+                            .map_err(|e| e.to_string())
+                        })(
+                        );
+
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::RaceCreated {
+                                source_workspace_id: workspace_id,
+                                result,
+                            },
+                            "race_created",
+                        );
+                    });
+                }
+                Effect::GenerateCommitMessage {
+                    workspace_id,
+                    working_dir,
+                } => {
+                    let tools = self.tools().clone();
+                    let event_tx = self.event_tx.clone();
+
+                    tokio::spawn(async move {
+                        let result = CommitService::generate_message(&tools, &working_dir)
+                            .await
+                            .map_err(|e| e.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::CommitMessageGenerated {
+                                workspace_id,
+                                result,
+                            },
+                            "commit_message_generated",
+                        );
+                    });
+                }
+                Effect::LoadReviewComments {
+                    workspace_id,
+                    working_dir,
+                } => {
+                    let store = self.core.review_comment_store().cloned();
+                    let event_tx = self.event_tx.clone();
+
+                    tokio::task::spawn_blocking(move || {
+                        let result = ReviewService::pending_review(
+                            store.as_ref(),
+                            workspace_id,
+                            &working_dir,
+                        )
+                        .map_err(|e| e.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::ReviewCommentsLoaded {
+                                workspace_id,
+                                result,
+                            },
+                            "review_comments_loaded",
+                        );
+                    });
+                }
+                Effect::FetchCiFailures {
+                    workspace_id,
+                    working_dir,
+                    auto,
+                } => {
+                    let event_tx = self.event_tx.clone();
+
+                    tokio::task::spawn_blocking(move || {
+                        let result = CiService::failures(&working_dir).map_err(|e| e.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::CiFailuresFetched {
+                                workspace_id,
+                                auto,
+                                result,
+                            },
+                            "ci_failures_fetched",
+                        );
+                    });
+                }
+                Effect::SyncWorkspace {
+                    workspace_id,
+                    working_dir,
+                    step,
+                } => {
+                    let event_tx = self.event_tx.clone();
+
+                    tokio::task::spawn_blocking(move || {
+                        let result = match step {
+                            SyncStep::Start(strategy) => {
+                                SyncService::sync(&working_dir, strategy).map(Some)
+                            }
+                            SyncStep::Continue => {
+                                SyncService::continue_sync(&working_dir).map(Some)
+                            }
+                            SyncStep::Abort => SyncService::abort(&working_dir).map(|_| None),
+                        }
+                        .map_err(|e| e.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::WorkspaceSynced {
+                                workspace_id,
+                                result,
+                            },
+                            "workspace_synced",
+                        );
+                    });
+                }
+                Effect::CommitWorkspace {
+                    workspace_id,
+                    working_dir,
+                    message,
+                    push,
+                } => {
+                    let event_tx = self.event_tx.clone();
+
+                    tokio::task::spawn_blocking(move || {
+                        let result = CommitService::commit(&working_dir, &message, push)
+                            .map_err(|e| e.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::WorkspaceCommitted {
+                                workspace_id,
+                                result,
+                            },
+                            "workspace_committed",
+                        );
+                    });
+                }
+                Effect::RewindTurn {
+                    session_id,
+                    turn,
+                    rewind,
+                    truncate,
+                } => {
+                    let event_tx = self.event_tx.clone();
+
+                    tokio::task::spawn_blocking(move || {
+                        let result = rewind.run(truncate).map(|_| ()).map_err(|e| e.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::TurnRewound {
+                                session_id,
+                                turn,
+                                truncated: truncate,
+                                result,
+                            },
+                            "turn_rewound",
+                        );
+                    });
+                }
+                Effect::CloneRepository {
+                    url,
+                    name,
+                    destination,
+                } => {
+                    let repo_dao = self.repo_dao_clone();
+                    let event_tx = self.event_tx.clone();
+
+                    tokio::task::spawn_blocking(move || {
+                        let result = match repo_dao {
+                            Some(repo_dao) => CloneService::clone_repository(
+                                &repo_dao,
+                                &url,
+                                &name,
+                                &destination,
+                                &mut |progress| {
+                                    send_app_event(
+                                        &event_tx,
+                                        AppEvent::CloneProgress {
+                                            name: name.clone(),
+                                            progress,
+                                        },
+                                        "clone_progress",
+                                    );
+                                },
+                            )
+                            .map(|repo| repo.id)
+                            .map_err(|e| e.to_string()),
+                            None => Err("No repository DAO available".to_string()),
+                        };
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::RepositoryCloned { name, result },
+                            "repository_cloned",
+                        );
+                    });
+                }
                 Effect::ArchiveWorkspace {
                     workspace_id,
                     delete_remote,
@@ -2537,10 +2973,36 @@ impl App {
                             let settings = repo
                                 .as_ref()
                                 .map(|repo| resolve_repo_workspace_settings(&config, repo));
+                            let hooks = repo
+                                .as_ref()
+                                .map(|repo| resolve_workspace_hooks(&config, repo))
+                                .unwrap_or_default();
 
                             let mut warnings = Vec::new();
                             let mut archived_commit_sha = None;
                             if let (Some(base_path), Some(settings)) = (repo_base_path, settings) {
+                                let context = HookContext {
+                                    workspace_name: &workspace.name,
+                                    branch: &workspace.branch,
+                                    workspace_path: &workspace.path,
+                                    repo_path: &base_path,
+                                };
+                                if hooks.has(HookEvent::BeforeArchive) && workspace.path.exists() {
+                                    let report = run_hooks_with_output(
+                                        &event_tx,
+                                        &hooks,
+                                        HookEvent::BeforeArchive,
+                                        context,
+                                    );
+                                    if let Some(summary) = report.failure_summary() {
+                                        return Err(format!(
+                                            "{}; the workspace was not archived.\n\n{}",
+                                            summary,
+                                            report.output.join("\n")
+                                        ));
+                                    }
+                                }
+
                                 match worktree_manager.get_branch_sha(
                                     settings.mode,
                                     &base_path,
@@ -2587,6 +3049,16 @@ impl App {
                                         ));
                                     }
                                 }
+
+                                if hooks.has(HookEvent::AfterArchive) {
+                                    let report = run_hooks_with_output(
+                                        &event_tx,
+                                        &hooks,
+                                        HookEvent::AfterArchive,
+                                        context,
+                                    );
+                                    warnings.extend(report.failure_summary());
+                                }
                             }
 
                             workspace_dao
@@ -2852,6 +3324,33 @@ impl App {
                         });
                     });
                 }
+                Effect::RefreshSearchIndex => {
+                    let event_tx = self.event_tx.clone();
+                    match SearchService::indexer(&self.core) {
+                        Ok(indexer) => {
+                            tokio::task::spawn_blocking(move || {
+                                let result = indexer.refresh().map_err(|e| e.to_string());
+                                send_app_event(
+                                    &event_tx,
+                                    AppEvent::SearchIndexRefreshed { result },
+                                    "search_index_refreshed",
+                                );
+                            });
+                        }
+                        Err(e) => {
+                            send_app_event(
+                                &event_tx,
+                                AppEvent::SearchIndexRefreshed {
+                                    result: Err(e.to_string()),
+                                },
+                                "search_index_refreshed",
+                            );
+                        }
+                    }
+                }
+                Effect::OpenSearchHit(hit) => {
+                    self.open_search_hit(hit).await?;
+                }
                 Effect::ImportSession(session) => {
                     // Create a new tab with the session's agent type and working directory
                     let agent_type = session.agent_type;
@@ -2938,6 +3437,11 @@ impl App {
                     | InputMode::SelectingAgent
                     | InputMode::Confirming
                     | InputMode::ImportingSession
+                    | InputMode::SearchingSessions
+                    | InputMode::FindingInChat
+                    | InputMode::ViewingUsageStats
+                    | InputMode::Committing
+                    | InputMode::ReviewingComments
                     | InputMode::CommandPalette
                     | InputMode::SlashMenu
                     | InputMode::SelectingTheme
@@ -3134,6 +3638,23 @@ impl App {
             }
         }
 
+        // :rewind <turn> picks a specific turn (plain :rewind means the last one)
+        if cmd.eq_ignore_ascii_case("rewind") && !rest.is_empty() {
+            match rest.parse::<u32>() {
+                Ok(turn) => self.prompt_rewind_turn(Some(turn)),
+                Err(_) => self.state.set_timed_footer_message(
+                    "Usage: :rewind [turn]".to_string(),
+                    Duration::from_secs(3),
+                ),
+            }
+            return None;
+        }
+
+        // :race <prompt> races the given prompt (plain :race uses the input box)
+        if cmd.eq_ignore_ascii_case("race") && !rest.is_empty() {
+            return Some(Action::RacePrompt(rest.to_string()));
+        }
+
         let command_lower = command.to_lowercase();
 
         // First check for built-in command aliases
@@ -3221,8 +3742,23 @@ impl App {
     /// Open a workspace (create or switch to tab)
     /// If `close_sidebar` is true, the sidebar will be hidden after opening.
     fn open_workspace_with_options(&mut self, workspace_id: uuid::Uuid, close_sidebar: bool) {
+        self.open_workspace_session(workspace_id, None, close_sidebar);
+    }
+
+    /// Open a workspace in a new tab, restoring the saved session `session_id`
+    /// or, when `None`, the workspace's most recent one (switching to the
+    /// workspace's tab instead if it is already open).
+    fn open_workspace_session(
+        &mut self,
+        workspace_id: uuid::Uuid,
+        session_id: Option<uuid::Uuid>,
+        close_sidebar: bool,
+    ) {
         // Check if there's already a tab with this workspace - switch to it
-        if let Some(existing_index) = self.find_tab_for_workspace(workspace_id) {
+        if let Some(existing_index) = self
+            .find_tab_for_workspace(workspace_id)
+            .filter(|_| session_id.is_none())
+        {
             self.state.tab_manager.switch_to(existing_index);
             self.sync_footer_spinner();
             if close_sidebar {
@@ -3259,9 +3795,13 @@ impl App {
             .map(|repo| repo.name);
 
         // Check if there's a saved session for this workspace (to restore chat history)
-        let saved_tab = self
-            .session_tab_dao()
-            .and_then(|dao| dao.get_by_workspace_id(workspace_id).ok().flatten());
+        let saved_tab = self.session_tab_dao().and_then(|dao| match session_id {
+            Some(session_id) => dao.get_by_id(session_id).ok().flatten(),
+            None => dao.get_by_workspace_id(workspace_id).ok().flatten(),
+        });
+        if session_id.is_some() && saved_tab.is_none() {
+            return;
+        }
 
         // Update last accessed
         if let Err(e) = workspace_dao.update_last_accessed(workspace_id) {
@@ -3350,6 +3890,8 @@ impl App {
         let default_model = self.config().default_model_for(tab_agent_type);
 
         let session_tab_dao = self.session_tab_dao_clone();
+        let session_event_dao = self.session_event_dao_clone();
+        let usage_dao = self.usage_dao_clone();
 
         // Store workspace info in session and restore chat history if available
         if let Some(session) = self.state.tab_manager.active_session_mut() {
@@ -3374,6 +3916,11 @@ impl App {
                 }
                 session.fork_seed_id = saved.fork_seed_id;
 
+                // Prefer Conduit's own event journal for chat history
+                let journal_restored =
+                    Self::restore_history_from_journal(session_event_dao.as_ref(), session);
+                Self::restore_session_cost(usage_dao.as_ref(), session);
+
                 // Restore chat history from agent files
                 if let Some(ref session_id_str) = saved.agent_session_id {
                     let session_id = SessionId::from_string(session_id_str.clone());
@@ -3382,6 +3929,7 @@ impl App {
 
                     // Load chat history
                     match saved.agent_type {
+                        _ if journal_restored => {}
                         AgentType::Claude => {
                             if let Ok((msgs, debug_entries, file_path)) =
                                 load_claude_history_with_debug(session_id_str)
@@ -3435,7 +3983,7 @@ impl App {
                             }
                         }
                     }
-                } else if saved.agent_type == AgentType::Opencode {
+                } else if !journal_restored && saved.agent_type == AgentType::Opencode {
                     if let Some(working_dir) = session.working_dir.as_ref() {
                         if let Ok((session_id_str, msgs, debug_entries, file_path)) =
                             load_opencode_history_for_dir_with_debug(working_dir)
@@ -3636,6 +4184,47 @@ impl App {
         ((chars as f64) / 4.0).ceil() as i64
     }
 
+    /// Restore chat history from Conduit's session event journal.
+    ///
+    /// Returns false when the session has no usable journal, in which case the
+    /// caller falls back to the agent's own history files.
+    fn restore_history_from_journal(
+        store: Option<&SessionEventStore>,
+        session: &mut AgentSession,
+    ) -> bool {
+        let Some(store) = store else {
+            return false;
+        };
+        let Some((msgs, debug_entries)) = load_journal_history(store, session.id) else {
+            return false;
+        };
+        Self::populate_debug_from_history(
+            &mut session.raw_events_view,
+            &debug_entries,
+            &crate::util::database_path(),
+        );
+        for msg in msgs {
+            session.chat_view.push(msg);
+        }
+        true
+    }
+
+    /// Restore the accumulated cost of a saved session
+    fn restore_session_cost(store: Option<&UsageStore>, session: &mut AgentSession) {
+        let Some(store) = store else {
+            return;
+        };
+        match store.totals_for_session(session.id) {
+            Ok(totals) => {
+                session.total_cost = totals.cost_usd;
+                session.update_status();
+            }
+            Err(e) => {
+                tracing::warn!(session_id = %session.id, error = %e, "Failed to load session cost");
+            }
+        }
+    }
+
     /// Populate the debug pane with history loading debug entries
     fn populate_debug_from_history(
         raw_events_view: &mut crate::ui::components::RawEventsView,
@@ -3762,6 +4351,13 @@ impl App {
         if matches!(&ctx, Some(ConfirmationContext::ForkSession { .. })) {
             self.state.pending_fork_request = None;
         }
+        // The sync stays stopped on its conflicts until continued or aborted
+        if matches!(&ctx, Some(ConfirmationContext::ResolveSyncConflicts { .. })) {
+            self.state.set_timed_footer_message(
+                "Resolve the conflicts, then run :sync_continue (or :sync_abort)".to_string(),
+                Duration::from_secs(5),
+            );
+        }
 
         self.state.confirmation_dialog_state.hide();
 
@@ -3771,7 +4367,11 @@ impl App {
             Some(ConfirmationContext::CreatePullRequest { .. })
             | Some(ConfirmationContext::OpenExistingPr { .. })
             | Some(ConfirmationContext::ForkSession { .. })
-            | Some(ConfirmationContext::SteerFallback { .. }) => InputMode::Normal,
+            | Some(ConfirmationContext::SteerFallback { .. })
+            | Some(ConfirmationContext::RevertHunk { .. })
+            | Some(ConfirmationContext::RewindTurn { .. })
+            | Some(ConfirmationContext::PickRaceWinner { .. })
+            | Some(ConfirmationContext::ResolveSyncConflicts { .. }) => InputMode::Normal,
             // Sidebar operations return to sidebar navigation
             Some(ConfirmationContext::ArchiveWorkspace(_))
             | Some(ConfirmationContext::ArchiveWorkspaceRemoteDelete { .. })
@@ -3902,6 +4502,16 @@ impl App {
         self.state.input_mode = InputMode::ShowingError;
     }
 
+    /// Show a failed `on_create` hook with the end of its output
+    fn show_setup_failure(&mut self, report: &HookReport, consequence: &str) {
+        let summary = report.failure_summary().unwrap_or_default();
+        self.show_error_with_details(
+            "Workspace Setup Failed",
+            &format!("{}. {}", summary, consequence),
+            &report.output.join("\n"),
+        );
+    }
+
     /// Execute the archive workspace action after confirmation
     fn execute_archive_workspace(
         &mut self,
@@ -4009,6 +4619,7 @@ impl App {
                 Some(mode),
                 repo.archive_delete_branch,
                 repo.archive_remote_prompt,
+                repo.sync_strategy,
             )
             .map_err(|e| format!("Failed to update repository settings: {}", e))?;
 
@@ -4112,7 +4723,7 @@ impl App {
         self.state.tab_manager.close_tab(index);
     }
 
-    /// Close any tabs that are using the specified workspace
+    /// Close any tabs that are using the specified (archived) workspace
     fn close_tabs_for_workspace(&mut self, workspace_id: uuid::Uuid) {
         // Unregister workspace from git tracker
         if let Some(ref tracker) = self.git_tracker {
@@ -4140,6 +4751,13 @@ impl App {
             self.close_tab_at_index(idx);
         }
 
+        // Archived sessions can't be reopened, so their journals only take space
+        if let Some(dao) = self.session_event_dao() {
+            if let Err(e) = dao.delete_by_workspace(workspace_id) {
+                tracing::warn!(error = %e, "Failed to delete journal of archived workspace");
+            }
+        }
+
         // Switch to sidebar navigation if all tabs are closed
         // But don't override if we're showing an error dialog
         if self.state.tab_manager.is_empty() && self.state.input_mode != InputMode::ShowingError {
@@ -4213,6 +4831,40 @@ impl App {
         Some(repo_id)
     }
 
+    /// Clone the URL entered in the add repository dialog
+    fn start_repository_clone(&mut self) -> Option<Effect> {
+        let url = self.state.add_repo_dialog_state.input().trim().to_string();
+        let name = match CloneService::repo_name(&url, None) {
+            Ok(name) => name,
+            Err(e) => {
+                self.show_error("Clone Failed", &e.to_string());
+                return None;
+            }
+        };
+        let destination = CloneService::destination(self.app_state_dao(), &name);
+        self.state.set_timed_footer_message(
+            format!("Cloning {} into {}", name, destination.display()),
+            Duration::from_secs(5),
+        );
+        Some(Effect::CloneRepository {
+            url,
+            name,
+            destination,
+        })
+    }
+
+    /// Expand, select and focus a newly added repository in the sidebar
+    fn focus_added_repository(&mut self, repo_id: Uuid) {
+        self.state.sidebar_data.expand_repo(repo_id);
+        if let Some(repo_index) = self.state.sidebar_data.find_repo_index(repo_id) {
+            self.state.sidebar_state.tree_state.selected = repo_index + 1;
+        }
+        self.state.sidebar_state.show();
+        self.state.sidebar_state.set_focused(true);
+        self.state.show_first_time_splash = false;
+        self.state.input_mode = InputMode::SidebarNavigation;
+    }
+
     /// Create a new tab with the selected agent type
     fn create_tab_with_agent(&mut self, agent_type: AgentType) {
         self.state.tab_manager.new_tab(agent_type);
@@ -4226,6 +4878,35 @@ impl App {
         self.state.input_mode = InputMode::Normal;
     }
 
+    /// Act on the agent chosen in the agent selector
+    fn confirm_agent_selection(&mut self) {
+        let agent_type = self.state.agent_selector_state.selected_agent();
+        let purpose = self.state.agent_selector_state.purpose;
+        self.state.agent_selector_state.hide();
+        match purpose {
+            AgentSelectorPurpose::NewSession => self.create_tab_with_agent(agent_type),
+            AgentSelectorPurpose::Handoff => {
+                self.state.input_mode = InputMode::Normal;
+                self.initiate_fork_session(agent_type);
+            }
+        }
+    }
+
+    /// Show the agent selector to continue the active session with another agent
+    fn show_handoff_selector(&mut self) {
+        let Some(agent_type) = self
+            .state
+            .tab_manager
+            .active_session()
+            .map(|s| s.agent_type)
+        else {
+            return;
+        };
+        self.state.close_overlays();
+        self.state.agent_selector_state.show_for_handoff(agent_type);
+        self.state.input_mode = InputMode::SelectingAgent;
+    }
+
     /// Replace the active session with a fresh one (same workspace, reset history).
     fn start_new_session_in_place(&mut self) {
         if self.state.tab_manager.is_empty() {
@@ -5067,6 +5748,10 @@ impl App {
             GlobalFooter::sidebar_hints()
         } else if self.state.tab_manager.active_is_file() {
             GlobalFooter::file_viewer_hints()
+        } else if self.state.tab_manager.active_is_diff() {
+            GlobalFooter::diff_viewer_hints()
+        } else if self.state.tab_manager.active_is_race() {
+            GlobalFooter::race_viewer_hints()
         } else {
             match self.state.view_mode {
                 ViewMode::Chat => GlobalFooter::chat_hints(),
@@ -5119,7 +5804,7 @@ impl App {
         } else if self.state.tab_manager.active_is_file() {
             KeyContext::Scrolling
         } else {
-            KeyContext::from_input_mode(self.state.input_mode, self.state.view_mode)
+            self.key_context()
         };
 
         // Look up action in keybinding config
@@ -5173,6 +5858,38 @@ impl App {
                     self.show_error("Export Failed", &err);
                 }
             },
+            AppEvent::SearchIndexRefreshed { result } => {
+                self.state.session_search_state.set_indexing(false);
+                match result {
+                    Ok(stats) => {
+                        tracing::debug!(
+                            indexed = stats.indexed,
+                            unchanged = stats.unchanged,
+                            removed = stats.removed,
+                            "Search index refreshed"
+                        );
+                        if self.state.session_search_state.is_visible() {
+                            self.run_session_search();
+                        }
+                    }
+                    Err(err) => {
+                        tracing::warn!(error = %err, "Failed to refresh search index");
+                        self.state.session_search_state.set_results(Err(err));
+                    }
+                }
+            }
+            AppEvent::TranscriptExported { result } => match result {
+                Ok(paths) => {
+                    self.show_error_with_details(
+                        "Transcript Exported",
+                        "The session transcript has been exported.",
+                        &format!("Files saved to:\n{}", paths),
+                    );
+                }
+                Err(err) => {
+                    self.show_error("Export Failed", &err);
+                }
+            },
             AppEvent::WorkspaceCreated { repo_id, result } => {
                 self.clear_repo_action_busy(repo_id);
                 match result {
@@ -5182,8 +5899,16 @@ impl App {
                         if let Some(index) = self.find_workspace_index(created.workspace_id) {
                             self.state.sidebar_state.tree_state.selected = index;
                         }
-                        // Open workspace, close sidebar, and focus prompt box
-                        self.open_workspace_with_options(created.workspace_id, true);
+                        if let Some(report) = created.setup_failure {
+                            // Leave the workspace closed so no agent starts in a broken checkout
+                            self.show_setup_failure(
+                                &report,
+                                "The workspace was created but not opened.",
+                            );
+                        } else {
+                            // Open workspace, close sidebar, and focus prompt box
+                            self.open_workspace_with_options(created.workspace_id, true);
+                        }
                     }
                     Err(err) => {
                         self.show_error("Workspace Creation Failed", &err);
@@ -5205,6 +5930,12 @@ impl App {
                         match self.finish_fork_session(created.workspace_id) {
                             Ok(mut fork_effects) => {
                                 effects.append(&mut fork_effects);
+                                if let Some(report) = created.setup_failure {
+                                    self.show_setup_failure(
+                                        &report,
+                                        "The forked session may not work as expected.",
+                                    );
+                                }
                             }
                             Err(err) => {
                                 // Clean up fork seed
@@ -5254,6 +5985,94 @@ impl App {
                     }
                 }
             }
+            AppEvent::RaceCreated {
+                source_workspace_id,
+                result,
+            } => {
+                self.clear_workspace_busy(source_workspace_id);
+                match result {
+                    Ok(race) => effects.extend(self.finish_race_start(race)),
+                    Err(err) => self.show_error("Race Failed", &err),
+                }
+            }
+            AppEvent::RaceTestsFinished {
+                race_id,
+                workspace_id,
+                result,
+            } => {
+                self.finish_race_tests(race_id, workspace_id, result);
+            }
+            AppEvent::CommitMessageGenerated {
+                workspace_id,
+                result,
+            } => {
+                self.handle_commit_message_generated(workspace_id, result);
+            }
+            AppEvent::WorkspaceCommitted {
+                workspace_id,
+                result,
+            } => {
+                self.handle_workspace_committed(workspace_id, result);
+            }
+            AppEvent::TurnRewound {
+                session_id,
+                turn,
+                truncated,
+                result,
+            } => {
+                self.handle_turn_rewound(session_id, turn, truncated, result);
+            }
+            AppEvent::ReviewCommentsLoaded {
+                workspace_id,
+                result,
+            } => {
+                self.handle_review_comments_loaded(workspace_id, result);
+            }
+            AppEvent::CiFailuresFetched {
+                workspace_id,
+                auto,
+                result,
+            } => {
+                effects.extend(self.handle_ci_failures_fetched(workspace_id, auto, result)?);
+            }
+            AppEvent::WorkspaceSynced {
+                workspace_id,
+                result,
+            } => {
+                self.handle_workspace_synced(workspace_id, result);
+            }
+            AppEvent::HookOutput {
+                workspace_name,
+                event,
+                line,
+            } => {
+                if !line.trim().is_empty() {
+                    self.state.set_timed_footer_message(
+                        format!("{} {}: {}", workspace_name, event, line),
+                        Duration::from_secs(5),
+                    );
+                }
+            }
+            AppEvent::CloneProgress { name, progress } => {
+                self.state.set_timed_footer_message(
+                    format!("Cloning {}: {}", name, progress),
+                    Duration::from_secs(5),
+                );
+            }
+            AppEvent::RepositoryCloned { name, result } => match result {
+                Ok(repo_id) => {
+                    self.refresh_sidebar_data();
+                    self.state.close_overlays();
+                    self.focus_added_repository(repo_id);
+                    self.state.set_timed_footer_message(
+                        format!("Cloned {}", name),
+                        Duration::from_secs(5),
+                    );
+                }
+                Err(e) => {
+                    self.show_error("Clone Failed", &format!("Could not clone {}: {}", name, e));
+                }
+            },
             AppEvent::WorkspaceArchived {
                 workspace_id,
                 result,
@@ -5270,6 +6089,7 @@ impl App {
                         }
 
                         self.close_tabs_for_workspace(archived.workspace_id);
+                        self.refresh_race_viewers(archived.workspace_id);
 
                         let current_selection = self.state.sidebar_state.tree_state.selected;
                         self.refresh_sidebar_data();
@@ -5384,6 +6204,19 @@ impl App {
                     self.state.stop_footer_spinner();
                 }
             }
+            AppEvent::DaemonSessionAttached {
+                session_id,
+                input_tx,
+                missed_events,
+            } => {
+                self.handle_daemon_session_attached(session_id, input_tx, missed_events);
+            }
+            AppEvent::DaemonDisconnected => {
+                self.handle_daemon_disconnected();
+            }
+            AppEvent::DaemonReconnected => {
+                self.handle_daemon_reconnected();
+            }
             AppEvent::AgentTerminationResult {
                 session_id,
                 pid,
@@ -5471,7 +6304,7 @@ impl App {
                 self.state.session_import_state.set_loading(false);
             }
             AppEvent::GitTracker(update) => {
-                self.handle_git_tracker_update(update);
+                self.handle_git_tracker_update(update, &mut effects);
             }
             AppEvent::ShellCommandCompleted {
                 session_id,
@@ -5609,7 +6442,11 @@ impl App {
     }
 
     /// Handle updates from the background git tracker
-    fn handle_git_tracker_update(&mut self, update: crate::ui::git_tracker::GitTrackerUpdate) {
+    fn handle_git_tracker_update(
+        &mut self,
+        update: crate::ui::git_tracker::GitTrackerUpdate,
+        effects: &mut Vec<Effect>,
+    ) {
         use crate::ui::git_tracker::GitTrackerUpdate;
 
         match update {
@@ -5709,6 +6546,14 @@ impl App {
                 }
                 self.apply_branch_update(workspace_id, branch);
             }
+            GitTrackerUpdate::ChecksFailed {
+                workspace_id,
+                pr_number,
+            } => {
+                if let Some(effect) = self.auto_fix_ci(workspace_id, pr_number) {
+                    effects.push(effect);
+                }
+            }
         }
     }
 
@@ -5743,6 +6588,11 @@ impl App {
             );
             return Ok(());
         };
+        // The attached daemon journals and checkpoints the agents it runs
+        let recorded_here = self.daemon.is_none();
+        if let Some(journal) = self.core.session_journal().filter(|_| recorded_here) {
+            journal.record_event(session_id, &event);
+        }
         // Check if this is a non-active tab receiving content - mark as needing attention
         let is_active_tab = self.state.tab_manager.active_index() == tab_index;
         let is_content_event = matches!(
@@ -5756,17 +6606,41 @@ impl App {
                 | AgentEvent::TurnFailed(_)
         );
 
+        let turn_finished = matches!(
+            event,
+            AgentEvent::TurnCompleted(_) | AgentEvent::TurnFailed(_)
+        );
+
+        let turn_cost = match &event {
+            AgentEvent::TurnCompleted(completed) => {
+                self.record_turn_cost(tab_index, &completed.usage)
+            }
+            _ => 0.0,
+        };
+
         // Track whether we need to stop footer spinner (done after session borrow ends)
         let mut should_stop_footer_spinner = false;
         let mut should_start_footer_spinner = false;
         let mut pending_sidebar_pr_update: Option<(Uuid, PrStatus)> = None;
         let mut pending_model_invalidation = false;
         let mut should_drain_queue = false;
+        let mut pending_notification: Option<Notification> = None;
+        let finished_notification = match &event {
+            AgentEvent::TurnCompleted(_) => Some((NotificationKind::TurnComplete, None)),
+            AgentEvent::TurnFailed(failed) => {
+                Some((NotificationKind::TurnFailed, Some(failed.error.clone())))
+            }
+            _ => None,
+        };
+        let min_turn_secs = self.notifier.min_turn_secs();
 
         {
             let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
                 return Ok(());
             };
+            let awaiting_input_before = session.inline_prompt.is_some();
+            let hidden_turn = session.suppress_next_turn_summary;
+            let turn_elapsed = session.thinking_indicator.elapsed();
 
             // Mark non-active tabs as needing attention when content arrives
             // Exclude suppressed assistant messages (like fork seed ACKs)
@@ -5817,6 +6691,7 @@ impl App {
                     session.update_status();
                 }
                 AgentEvent::TurnCompleted(completed) => {
+                    session.total_cost += turn_cost;
                     session.add_usage(completed.usage);
                     session.stop_processing();
                     if session.inline_prompt.is_none() {
@@ -5995,6 +6870,22 @@ impl App {
                         session.chat_view.push(display.to_chat_message());
                     }
                 }
+                AgentEvent::ControlRequest(ControlRequestEvent {
+                    request_id,
+                    permission: Some(permission),
+                    ..
+                }) => {
+                    // The permission policy wants the user to decide
+                    let prompt = InlinePromptState::new_tool_permission(request_id, permission);
+                    if session.inline_prompt.is_some() {
+                        session.queued_inline_prompts.push_back(prompt);
+                    } else {
+                        session.inline_prompt = Some(prompt);
+                        session.chat_view.scroll_to_bottom();
+                        // Stop footer spinner since we're now awaiting user response
+                        should_stop_footer_spinner = true;
+                    }
+                }
                 AgentEvent::ControlRequest(request) => {
                     if let Some(tool_use_id) = request.tool_use_id.clone() {
                         session
@@ -6176,8 +7067,41 @@ impl App {
                 }
                 _ => {}
             }
+
+            let title = format!("Conduit: {}", session.tab_name());
+            if !awaiting_input_before && session.inline_prompt.is_some() {
+                pending_notification = Some(Notification {
+                    kind: NotificationKind::InputRequired,
+                    session_id,
+                    title,
+                    body: "Waiting for your input".to_string(),
+                });
+            } else if let Some((kind, error)) = finished_notification {
+                // Short turns and internal ones (like fork seeding) are not worth a ping
+                let worth_notifying = kind == NotificationKind::TurnFailed
+                    || (!hidden_turn && turn_elapsed.as_secs() >= min_turn_secs);
+                if worth_notifying {
+                    let body = error.unwrap_or_else(|| {
+                        format!("Turn finished after {}s", turn_elapsed.as_secs())
+                    });
+                    pending_notification = Some(Notification {
+                        kind,
+                        session_id,
+                        title,
+                        body,
+                    });
+                }
+            }
         } // End session borrow scope
 
+        if let Some(notification) = pending_notification {
+            // Nothing to announce when the user is already looking at it
+            let watching = is_active_tab && self.state.terminal_focused == Some(true);
+            if !watching {
+                self.notifier.notify(&notification);
+            }
+        }
+
         if let Some((workspace_id, status)) = pending_sidebar_pr_update {
             self.state
                 .sidebar_data
@@ -6216,6 +7140,10 @@ impl App {
             }
         }
 
+        if turn_finished {
+            self.handle_race_turn_finished(tab_index);
+        }
+
         // Stop footer spinner after session borrow is released
         if should_stop_footer_spinner {
             self.state.stop_footer_spinner();
@@ -6227,6 +7155,37 @@ impl App {
         Ok(())
     }
 
+    /// Persist a completed turn's usage, returning its estimated cost
+    fn record_turn_cost(&self, tab_index: usize, usage: &TokenUsage) -> f64 {
+        let Some(session) = self.state.tab_manager.session(tab_index) else {
+            return 0.0;
+        };
+        if self.daemon.is_some() {
+            // Recorded by the daemon
+            return self.config().calculate_cost(
+                session.agent_type,
+                session.model.as_deref(),
+                usage,
+            );
+        }
+        match CostService::record_turn(
+            &self.core,
+            session.id,
+            session.workspace_id,
+            session.agent_type,
+            session.model.as_deref(),
+            usage,
+            Some(session.thinking_indicator.elapsed()),
+        ) {
+            Ok(cost) => cost,
+            Err(e) => {
+                tracing::warn!(session_id = %session.id, error = %e, "Failed to record turn cost");
+                self.config()
+                    .calculate_cost(session.agent_type, session.model.as_deref(), usage)
+            }
+        }
+    }
+
     fn submit_prompt(
         &mut self,
         prompt: String,
@@ -6315,6 +7274,81 @@ impl App {
         }
     }
 
+    /// Send the user's answer to a tool permission prompt back to the agent.
+    ///
+    /// "Always allow" answers are also saved as a rule for the session's repository.
+    fn answer_tool_permission(
+        &mut self,
+        prompt: InlinePromptState,
+        reply: PermissionReply,
+    ) -> Vec<Effect> {
+        let InlinePromptType::ToolPermission { request } = prompt.prompt_type else {
+            return Vec::new();
+        };
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return Vec::new();
+        };
+        let verb = match reply {
+            PermissionReply::AllowOnce => "Allowed",
+            PermissionReply::AlwaysAllow => "Always allowed",
+            PermissionReply::Deny => "Denied",
+        };
+        let display = MessageDisplay::System {
+            content: format!("{} {}", verb, request.summary()),
+        };
+        session.chat_view.push(display.to_chat_message());
+
+        let Some(input_tx) = session.agent_input_tx.clone() else {
+            let display = MessageDisplay::Error {
+                content: "Cannot answer permission request: the agent is no longer running."
+                    .to_string(),
+            };
+            session.chat_view.push(display.to_chat_message());
+            return Vec::new();
+        };
+        let workspace_id = session.workspace_id;
+        let working_dir = session.working_dir.clone();
+        // When attached, the daemon records the answer and remembers the rule
+        let recorded_here = self.daemon.is_none();
+        if let Some(journal) = self.core.session_journal().filter(|_| recorded_here) {
+            journal.record_permission(
+                session.id,
+                &prompt.tool_id,
+                &request,
+                reply.as_str(),
+                AuditActor::Tui,
+            );
+        }
+        let resume_spinner = session.inline_prompt.is_none() && session.is_processing;
+        if resume_spinner {
+            session.set_processing_state(ProcessingState::Thinking);
+            self.state.start_footer_spinner(None);
+        }
+
+        tokio::spawn(async move {
+            let input = AgentInput::PermissionResponse {
+                request_id: prompt.tool_id,
+                reply,
+            };
+            if let Err(err) = input_tx.send(input).await {
+                tracing::warn!("Failed to send permission response: {}", err);
+            }
+        });
+
+        if recorded_here && reply == PermissionReply::AlwaysAllow {
+            if let Err(err) = ConfigService::remember_permission_rule(
+                &mut self.core,
+                workspace_id,
+                working_dir.as_deref(),
+                &request,
+            ) {
+                self.state
+                    .set_timed_footer_message(err.to_string(), Duration::from_secs(5));
+            }
+        }
+        Vec::new()
+    }
+
     fn send_opencode_question_response(
         &mut self,
         request_id: &str,
@@ -6768,6 +7802,30 @@ impl App {
         }
     }
 
+    /// Submit a prompt to a tab's agent, or queue it as a follow-up when the
+    /// agent is busy. The flag is true when the prompt was queued.
+    fn submit_or_queue_prompt(
+        &mut self,
+        tab_index: usize,
+        prompt: String,
+    ) -> anyhow::Result<(Vec<Effect>, bool)> {
+        if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+            if session.is_processing {
+                session.queue_message(QueuedMessage {
+                    id: Uuid::new_v4(),
+                    mode: QueuedMessageMode::FollowUp,
+                    text: prompt,
+                    images: Vec::new(),
+                    created_at: Utc::now(),
+                });
+                return Ok((Vec::new(), true));
+            }
+        }
+        let effects =
+            self.submit_prompt_for_tab(tab_index, prompt, Vec::new(), Vec::new(), false, None)?;
+        Ok((effects, false))
+    }
+
     fn submit_prompt_for_tab(
         &mut self,
         tab_index: usize,
@@ -6869,6 +7927,30 @@ impl App {
             return Ok(effects);
         }
 
+        // Tool-result resumes continue a turn that already passed the check;
+        // hidden fork/handoff seeds start a new one and must not bypass it.
+        if stdin_payload.is_none() {
+            match CostService::check_budget(&self.core, session_id) {
+                Ok(BudgetStatus::WithinBudget) => {}
+                Ok(BudgetStatus::SoftLimitReached(message)) => {
+                    self.state.set_timed_footer_message(
+                        format!("⚠ {}", message),
+                        Duration::from_secs(10),
+                    );
+                }
+                Ok(BudgetStatus::HardLimitReached(message)) => {
+                    if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+                        let display = MessageDisplay::Error { content: message };
+                        session.chat_view.push(display.to_chat_message());
+                    }
+                    return Ok(effects);
+                }
+                Err(e) => {
+                    tracing::warn!(%session_id, error = %e, "Failed to check spend budget");
+                }
+            }
+        }
+
         // Capture original user message for title generation BEFORE agent-specific transformations
         // (e.g., Codex placeholder stripping, Claude image-path appends)
         let prompt_for_title = display_prompt.clone();
@@ -6939,6 +8021,21 @@ impl App {
             return Ok(effects);
         }
 
+        // An attached daemon records the sessions it runs itself
+        if let Some(journal) = self
+            .core
+            .session_journal()
+            .filter(|_| self.daemon.is_none())
+        {
+            let journal_text = if hidden { "" } else { display_prompt.as_str() };
+            journal.record_prompt(
+                session_id,
+                journal_text,
+                hidden,
+                session_id_to_use.is_some(),
+            );
+        }
+
         if !hidden {
             let mode_prompt = self
                 .state
@@ -6979,6 +8076,29 @@ impl App {
             session.record_raw_event(EventDirection::Sent, "UserPrompt", debug_payload);
         }
 
+        if self.daemon.is_some() {
+            // The daemon builds the agent's input and resumes the conversation itself;
+            // it also titles new sessions, so this skips title generation below
+            let prompt = match stdin_payload {
+                Some(ref payload) if agent_prompt.is_empty() => Self::jsonl_prompt_text(payload),
+                _ => agent_prompt,
+            };
+            if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+                if session.agent_session_id.is_none() {
+                    session.agent_session_id = session.resume_session_id.take();
+                }
+            }
+            effects.push(Effect::DaemonPrompt {
+                session_id,
+                prompt,
+                working_dir,
+                model,
+                hidden,
+                images,
+            });
+            return Ok(effects);
+        }
+
         let mut use_stream_json = false;
         if agent_type == AgentType::Claude {
             use_stream_json = true;
@@ -6991,13 +8111,20 @@ impl App {
             }
         }
 
+        // Snapshot the workspace before the agent sees the prompt
+        let checkpoint = self.turn_checkpoint(tab_index);
+
         if agent_type == AgentType::Claude {
             let is_active_tab = self.state.tab_manager.active_index() == tab_index;
             if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
                 if let Some(ref input_tx) = session.agent_input_tx {
                     if let Some(payload) = stdin_payload.clone() {
                         let input_tx = input_tx.clone();
+                        let checkpoint = checkpoint.clone();
                         tokio::spawn(async move {
+                            if let Some(checkpoint) = checkpoint {
+                                checkpoint.record().await;
+                            }
                             if let Err(err) = input_tx.send(AgentInput::ClaudeJsonl(payload)).await
                             {
                                 tracing::warn!("Failed to send streaming prompt: {}", err);
@@ -7022,7 +8149,11 @@ impl App {
                     let input_tx = input_tx.clone();
                     let prompt_to_send = agent_prompt.clone();
                     let images_to_send = images.clone();
+                    let checkpoint = checkpoint.clone();
                     tokio::spawn(async move {
+                        if let Some(checkpoint) = checkpoint {
+                            checkpoint.record().await;
+                        }
                         let input = AgentInput::CodexPrompt {
                             text: prompt_to_send,
                             images: images_to_send,
@@ -7049,8 +8180,14 @@ impl App {
             agent_prompt.clone()
         };
 
+        let workspace_id = self
+            .state
+            .tab_manager
+            .session(tab_index)
+            .and_then(|session| session.workspace_id);
+        let permissions = ConfigService::permission_policy(&self.core, workspace_id);
         let mut config = AgentStartConfig::new(prompt_for_agent, working_dir)
-            .with_tools(self.config().claude_allowed_tools.clone())
+            .with_permissions(permissions)
             .with_images(images)
             .with_agent_mode(agent_mode);
 
@@ -7083,6 +8220,7 @@ impl App {
             session_id,
             agent_type,
             config,
+            checkpoint,
         });
 
         // Generate title on first user message of a NEW session (no title yet, not already pending)
@@ -7401,7 +8539,12 @@ impl App {
     ) -> anyhow::Result<()> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange
+        )?;
         terminal.clear()?;
         Ok(())
     }
@@ -7518,8 +8661,11 @@ impl App {
         Ok(())
     }
 
-    /// Initiate fork session flow - validate and show confirmation dialog
-    fn initiate_fork_session(&mut self) {
+    /// Initiate fork session flow - validate and show confirmation dialog.
+    ///
+    /// When `target` differs from the session's agent, the transcript is handed off
+    /// to the target agent instead.
+    fn initiate_fork_session(&mut self, target: AgentType) {
         let Some(session) = self.state.tab_manager.active_session() else {
             return;
         };
@@ -7564,13 +8710,35 @@ impl App {
             .get_current_branch(&workspace.path)
             .unwrap_or_else(|_| workspace.branch.clone());
 
-        let seed_prompt = app_prompt::build_fork_seed_prompt(session.chat_view.messages());
+        let is_handoff = target != session.agent_type;
+        let (seed_prompt, model, agent_mode) = if is_handoff {
+            let seed_prompt = app_prompt::build_handoff_seed_prompt(
+                session.chat_view.messages(),
+                session.agent_type,
+                target,
+            );
+            let agent_mode = if target.supports_plan_mode() {
+                session.agent_mode
+            } else {
+                AgentMode::Build
+            };
+            (
+                seed_prompt,
+                Some(self.config().default_model_for(target)),
+                agent_mode,
+            )
+        } else {
+            (
+                app_prompt::build_fork_seed_prompt(session.chat_view.messages()),
+                session.model.clone(),
+                session.agent_mode,
+            )
+        };
 
-        let model_id = session
-            .model
+        let model_id = model
             .clone()
-            .unwrap_or_else(|| ModelRegistry::default_model(session.agent_type));
-        let context_window = ModelRegistry::context_window(session.agent_type, &model_id);
+            .unwrap_or_else(|| ModelRegistry::default_model(target));
+        let context_window = ModelRegistry::context_window(target, &model_id);
         let token_estimate = Self::estimate_tokens(&seed_prompt);
         let usage_pct = if context_window > 0 {
             (token_estimate as f64 / context_window as f64) * 100.0
@@ -7613,18 +8781,21 @@ impl App {
             ConfirmationType::Info
         };
 
+        let action = if is_handoff {
+            format!("Hand off this session to {}", target.display_name())
+        } else {
+            "Fork this session".to_string()
+        };
         let message = format!(
-            "Fork this session into a new workspace based on branch \"{}\".\nSeed size: {} / {} tokens (~{:.0}%).",
-            base_branch,
-            token_estimate,
-            context_window,
-            usage_pct
+            "{} in a new workspace based on branch \"{}\".\nSeed size: {} / {} tokens (~{:.0}%).",
+            action, base_branch, token_estimate, context_window, usage_pct
         );
 
         self.state.pending_fork_request = Some(PendingForkRequest {
-            agent_type: session.agent_type,
-            agent_mode: session.agent_mode,
-            model: session.model.clone(),
+            agent_type: target,
+            parent_agent_type: session.agent_type,
+            agent_mode,
+            model,
             parent_session_id: session
                 .agent_session_id
                 .as_ref()
@@ -7637,12 +8808,17 @@ impl App {
         });
 
         self.state.close_overlays();
+        let (title, confirm_label) = if is_handoff {
+            ("Hand off session?", "Hand off")
+        } else {
+            ("Fork session?", "Fork")
+        };
         self.state.confirmation_dialog_state.show(
-            "Fork session?",
+            title,
             message,
             warnings,
             confirmation_type,
-            "Fork",
+            confirm_label,
             Some(ConfirmationContext::ForkSession {
                 parent_workspace_id,
                 base_branch: base_branch.clone(),
@@ -7686,7 +8862,8 @@ impl App {
             None,
             pending.token_estimate,
             pending.context_window,
-        );
+        )
+        .with_parent_agent_type(pending.parent_agent_type);
 
         if let Err(e) = fork_seed_dao.create(&fork_seed) {
             self.show_error(
@@ -8511,6 +9688,10 @@ impl App {
         // Check if active tab is a file viewer - render it separately
         if self.state.tab_manager.active_is_file() {
             self.render_file_viewer_tab(content_area, footer_area, f);
+        } else if self.state.tab_manager.active_is_diff() {
+            self.render_diff_viewer_tab(content_area, footer_area, f);
+        } else if self.state.tab_manager.active_is_race() {
+            self.render_race_viewer_tab(content_area, footer_area, f);
         } else {
             match self.state.view_mode {
                 ViewMode::Chat => {
@@ -8608,6 +9789,14 @@ impl App {
                         } else if self.state.session_import_state.is_visible() {
                             let picker = SessionImportPicker::new();
                             picker.render(size, f.buffer_mut(), &self.state.session_import_state);
+                        } else if self.state.session_search_state.is_visible() {
+                            self.state.session_search_state.update_viewport(size);
+                            let dialog = SessionSearchDialog::new();
+                            dialog.render(size, f.buffer_mut(), &self.state.session_search_state);
+                        } else if self.state.usage_stats_state.is_visible() {
+                            self.state.usage_stats_state.update_viewport(size);
+                            let dialog = UsageStatsDialog::new();
+                            dialog.render(size, f.buffer_mut(), &self.state.usage_stats_state);
                         } else if self.state.model_selector_state.is_visible() {
                             self.state.model_selector_state.update_viewport(size);
                             let selector = ModelSelector::new();
@@ -8833,6 +10022,7 @@ impl App {
 
                     // Draw active session components
                     let is_command_mode = self.state.input_mode == InputMode::Command;
+                    let is_find_mode = self.state.input_mode == InputMode::FindingInChat;
                     let show_chat_scrollbar = self.config().ui.show_chat_scrollbar;
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         // Use full chat area - prompt is now rendered as part of scrollable content
@@ -8874,7 +10064,22 @@ impl App {
                         let has_inline_prompt = session.inline_prompt.is_some();
 
                         // Render input box (not in command mode, not when inline prompt active)
-                        if !is_command_mode && !has_inline_prompt {
+                        if is_find_mode && !has_inline_prompt {
+                            let query = session.chat_view.find_query().unwrap_or_default();
+                            Self::render_input_prompt(
+                                input_area_inner,
+                                f.buffer_mut(),
+                                "  find › ",
+                                query,
+                            );
+                            let prompt_width = format!("  find › {}", query).width() as u16;
+                            let max_x =
+                                input_area_inner.x + input_area_inner.width.saturating_sub(1);
+                            f.set_cursor_position((
+                                (input_area_inner.x + prompt_width).min(max_x),
+                                input_area_inner.y + 1,
+                            ));
+                        } else if !is_command_mode && !has_inline_prompt {
                             session.input_box.render(input_area_inner, f.buffer_mut());
                         }
                         // Update and render status bar (skip when inline prompt is active)
@@ -8893,6 +10098,12 @@ impl App {
                             session
                                 .status_bar
                                 .set_spinner_frame(self.state.spinner_frame);
+                            session
+                                .status_bar
+                                .set_find_status(session.chat_view.find_status());
+                            session
+                                .status_bar
+                                .set_show_cost(self.core.config().show_cost);
                             session
                                 .status_bar
                                 .render(status_bar_area_inner, f.buffer_mut());
@@ -9036,6 +10247,38 @@ impl App {
             picker.render(size, f.buffer_mut(), &self.state.session_import_state);
         }
 
+        if self.state.session_search_state.is_visible() {
+            self.state.session_search_state.update_viewport(size);
+            let dialog = SessionSearchDialog::new();
+            dialog.render(size, f.buffer_mut(), &self.state.session_search_state);
+        }
+
+        if self.state.usage_stats_state.is_visible() {
+            self.state.usage_stats_state.update_viewport(size);
+            let dialog = UsageStatsDialog::new();
+            dialog.render(size, f.buffer_mut(), &self.state.usage_stats_state);
+        }
+
+        if self.state.commit_dialog_state.is_visible() {
+            let cursor = CommitDialog::new().render(
+                size,
+                f.buffer_mut(),
+                &mut self.state.commit_dialog_state,
+            );
+            if let Some(cursor) = cursor.filter(|_| self.state.input_mode == InputMode::Committing)
+            {
+                f.set_cursor_position(cursor);
+            }
+        }
+
+        if self.state.review_comments_state.is_visible() {
+            ReviewCommentsDialog::new().render(
+                size,
+                f.buffer_mut(),
+                &mut self.state.review_comments_state,
+            );
+        }
+
         // Draw confirmation dialog if open
         if self.state.confirmation_dialog_state.visible {
             use ratatui::widgets::Widget;
@@ -9242,6 +10485,254 @@ impl App {
         footer.render(footer_area, f.buffer_mut());
     }
 
+    /// Render a workspace diff tab
+    fn render_diff_viewer_tab(
+        &mut self,
+        content_area: Rect,
+        footer_area: Rect,
+        f: &mut ratatui::Frame<'_>,
+    ) {
+        use crate::git::DiffBase;
+        use crate::ui::components::{
+            bg_base, diff_add, diff_remove, text_muted, text_primary, DiffView, FooterContext,
+            GlobalFooter,
+        };
+        use crate::ui::diff_viewer::DiffLayout;
+        use ratatui::style::Style;
+        use ratatui::text::{Line, Span};
+        use ratatui::widgets::{Paragraph, Widget};
+        use unicode_width::UnicodeWidthStr;
+
+        let is_command_mode = self.state.input_mode == InputMode::Command;
+
+        // Layout: tab bar, diff header, content (+ optional command prompt)
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(if is_command_mode {
+                vec![
+                    Constraint::Length(1), // Tab bar
+                    Constraint::Length(1), // Diff header
+                    Constraint::Min(3),    // Files + hunks
+                    Constraint::Length(3), // Command prompt
+                ]
+            } else {
+                vec![
+                    Constraint::Length(1), // Tab bar
+                    Constraint::Length(1), // Diff header (base + totals)
+                    Constraint::Min(5),    // Files + hunks
+                ]
+            })
+            .split(content_area);
+
+        let tab_bar_chunk = chunks[0];
+        let header_chunk = chunks[1];
+        let content_chunk = chunks[2];
+        let command_chunk = if is_command_mode {
+            Some(chunks[3])
+        } else {
+            None
+        };
+
+        // Store areas for mouse hit-testing
+        self.state.tab_bar_area = Some(tab_bar_chunk);
+        self.state.chat_area = None;
+        self.state.raw_events_area = None;
+        self.state.input_area = command_chunk;
+        self.state.status_bar_area = None;
+        self.state.footer_area = Some(footer_area);
+
+        // Render tab bar
+        let tabs_focused = self.state.input_mode != InputMode::SidebarNavigation;
+        self.ensure_tab_bar_scroll(tab_bar_chunk.width, tabs_focused);
+        let tab_bar = self.build_tab_bar(tabs_focused);
+        tab_bar.render(tab_bar_chunk, f.buffer_mut());
+
+        if let Some(viewer) = self.state.tab_manager.active_diff_viewer() {
+            let base_label = match (viewer.base, viewer.diff()) {
+                (DiffBase::Head, _) => "HEAD".to_string(),
+                (DiffBase::BaseBranch, Some(diff)) => diff
+                    .base_branch
+                    .clone()
+                    .unwrap_or_else(|| "base branch".to_string()),
+                (DiffBase::BaseBranch, None) => "base branch".to_string(),
+            };
+            let layout_label = match viewer.layout {
+                DiffLayout::Unified => "unified",
+                DiffLayout::SideBySide => "side-by-side",
+            };
+            let mut spans = vec![
+                Span::styled(" Changes vs ", Style::default().fg(text_muted())),
+                Span::styled(base_label, Style::default().fg(text_primary())),
+            ];
+            if let Some(diff) = viewer.diff() {
+                let file_count = diff.files.len();
+                spans.push(Span::styled(
+                    format!(
+                        "  {} file{}  ",
+                        file_count,
+                        if file_count == 1 { "" } else { "s" }
+                    ),
+                    Style::default().fg(text_muted()),
+                ));
+                spans.push(Span::styled(
+                    format!("+{}", diff.additions()),
+                    Style::default().fg(diff_add()),
+                ));
+                spans.push(Span::styled(
+                    format!(" -{}", diff.deletions()),
+                    Style::default().fg(diff_remove()),
+                ));
+            }
+            spans.push(Span::styled(
+                format!("  [{}, {}]", viewer.scope.label(), layout_label),
+                Style::default().fg(text_muted()),
+            ));
+
+            Paragraph::new(Line::from(spans))
+                .style(Style::default().bg(bg_base()))
+                .render(header_chunk, f.buffer_mut());
+
+            DiffView::new(viewer).render(content_chunk, f.buffer_mut());
+        }
+
+        // Render command prompt if in command mode
+        if let Some(cmd_area) = command_chunk {
+            self.render_command_prompt(cmd_area, f.buffer_mut());
+            // Set cursor position for command input
+            let prompt = format!("  cmd › {}", self.state.command_buffer);
+            let prompt_width = UnicodeWidthStr::width(prompt.as_str()) as u16;
+            let max_x = cmd_area.x + cmd_area.width.saturating_sub(1);
+            let cx = (cmd_area.x + prompt_width).min(max_x);
+            let cy = cmd_area.y + 1;
+            f.set_cursor_position((cx, cy));
+        }
+
+        // Render footer (sidebar-aware)
+        let footer_context = if self.state.input_mode == InputMode::SidebarNavigation {
+            FooterContext::Sidebar
+        } else {
+            FooterContext::DiffViewer
+        };
+        let footer = GlobalFooter::for_context(footer_context)
+            .with_spinner(self.state.footer_spinner.as_ref())
+            .with_message(self.state.footer_message.as_deref());
+        footer.render(footer_area, f.buffer_mut());
+    }
+
+    fn render_race_viewer_tab(
+        &mut self,
+        content_area: Rect,
+        footer_area: Rect,
+        f: &mut ratatui::Frame<'_>,
+    ) {
+        use crate::ui::components::{
+            bg_base, text_muted, text_primary, FooterContext, GlobalFooter, RaceView,
+        };
+        use ratatui::style::Style;
+        use ratatui::text::{Line, Span};
+        use ratatui::widgets::{Paragraph, Widget};
+        use std::collections::HashSet;
+        use unicode_width::UnicodeWidthStr;
+
+        let is_command_mode = self.state.input_mode == InputMode::Command;
+
+        // Layout: tab bar, race header, standings (+ optional command prompt)
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(if is_command_mode {
+                vec![
+                    Constraint::Length(1), // Tab bar
+                    Constraint::Length(1), // Race header
+                    Constraint::Min(3),    // Standings
+                    Constraint::Length(3), // Command prompt
+                ]
+            } else {
+                vec![
+                    Constraint::Length(1), // Tab bar
+                    Constraint::Length(1), // Race header (base + contenders)
+                    Constraint::Min(5),    // Standings
+                ]
+            })
+            .split(content_area);
+
+        let tab_bar_chunk = chunks[0];
+        let header_chunk = chunks[1];
+        let content_chunk = chunks[2];
+        let command_chunk = if is_command_mode {
+            Some(chunks[3])
+        } else {
+            None
+        };
+
+        // Store areas for mouse hit-testing
+        self.state.tab_bar_area = Some(tab_bar_chunk);
+        self.state.chat_area = None;
+        self.state.raw_events_area = None;
+        self.state.input_area = command_chunk;
+        self.state.status_bar_area = None;
+        self.state.footer_area = Some(footer_area);
+
+        // Render tab bar
+        let tabs_focused = self.state.input_mode != InputMode::SidebarNavigation;
+        self.ensure_tab_bar_scroll(tab_bar_chunk.width, tabs_focused);
+        let tab_bar = self.build_tab_bar(tabs_focused);
+        tab_bar.render(tab_bar_chunk, f.buffer_mut());
+
+        // Contenders whose agent is mid-turn
+        let running: HashSet<Uuid> = self
+            .state
+            .tab_manager
+            .sessions()
+            .into_iter()
+            .filter(|session| session.is_processing)
+            .filter_map(|session| session.workspace_id)
+            .collect();
+
+        if let Some(viewer) = self.state.tab_manager.active_race_viewer() {
+            let race = &viewer.race;
+            let short_commit: String = race.base_commit.chars().take(7).collect();
+            let spans = vec![
+                Span::styled(" Race from ", Style::default().fg(text_muted())),
+                Span::styled(
+                    race.base_branch.clone(),
+                    Style::default().fg(text_primary()),
+                ),
+                Span::styled(
+                    format!(" @ {}  {} contenders", short_commit, race.entries.len()),
+                    Style::default().fg(text_muted()),
+                ),
+            ];
+            Paragraph::new(Line::from(spans))
+                .style(Style::default().bg(bg_base()))
+                .render(header_chunk, f.buffer_mut());
+
+            RaceView::new(viewer, &running).render(content_chunk, f.buffer_mut());
+        }
+
+        // Render command prompt if in command mode
+        if let Some(cmd_area) = command_chunk {
+            self.render_command_prompt(cmd_area, f.buffer_mut());
+            // Set cursor position for command input
+            let prompt = format!("  cmd › {}", self.state.command_buffer);
+            let prompt_width = UnicodeWidthStr::width(prompt.as_str()) as u16;
+            let max_x = cmd_area.x + cmd_area.width.saturating_sub(1);
+            let cx = (cmd_area.x + prompt_width).min(max_x);
+            let cy = cmd_area.y + 1;
+            f.set_cursor_position((cx, cy));
+        }
+
+        // Render footer (sidebar-aware)
+        let footer_context = if self.state.input_mode == InputMode::SidebarNavigation {
+            FooterContext::Sidebar
+        } else {
+            FooterContext::RaceViewer
+        };
+        let footer = GlobalFooter::for_context(footer_context)
+            .with_spinner(self.state.footer_spinner.as_ref())
+            .with_message(self.state.footer_message.as_deref());
+        footer.render(footer_area, f.buffer_mut());
+    }
+
     fn render_theme_picker(&mut self, size: Rect, buf: &mut ratatui::buffer::Buffer) {
         if !self.state.theme_picker_state.is_visible() {
             return;
@@ -9254,6 +10745,16 @@ impl App {
 
     /// Render command mode prompt
     fn render_command_prompt(&self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        Self::render_input_prompt(area, buf, "  cmd › ", &self.state.command_buffer);
+    }
+
+    /// Render a single-line prompt (command or find mode) in place of the input box.
+    fn render_input_prompt(
+        area: Rect,
+        buf: &mut ratatui::buffer::Buffer,
+        prefix: &str,
+        text: &str,
+    ) {
         use ratatui::style::Style;
         use ratatui::text::{Line, Span};
         use ratatui::widgets::{Clear, Paragraph, Widget};
@@ -9273,9 +10774,8 @@ impl App {
             return;
         }
 
-        let prefix = "  cmd › ";
         let prefix_width = UnicodeWidthStr::width(prefix) as u16;
-        let buffer_width = UnicodeWidthStr::width(self.state.command_buffer.as_str()) as u16;
+        let buffer_width = UnicodeWidthStr::width(text) as u16;
         let total_width = prefix_width + buffer_width;
         let content_width = area.width;
 
@@ -9283,7 +10783,7 @@ impl App {
             // Truncate from the left, showing most recent input
             let mut truncated = String::new();
             let mut width = 0usize;
-            for ch in self.state.command_buffer.chars().rev() {
+            for ch in text.chars().rev() {
                 let w = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(1);
                 if width + w > content_width.saturating_sub(prefix_width + 1) as usize {
                     break;
@@ -9309,7 +10809,7 @@ impl App {
                     Style::default().fg(crate::ui::components::text_muted()),
                 ),
                 Span::styled(
-                    &self.state.command_buffer,
+                    text,
                     Style::default().fg(crate::ui::components::text_primary()),
                 ),
             ])
@@ -9435,6 +10935,113 @@ impl App {
         None
     }
 
+    /// Re-run the session search dialog's query against the index.
+    pub(super) fn run_session_search(&mut self) {
+        let query = self.state.session_search_state.query().trim().to_string();
+        if query.is_empty() {
+            self.state.session_search_state.clear_results();
+            return;
+        }
+        let result = SearchService::search(&self.core, &query, DEFAULT_SEARCH_LIMIT)
+            .map_err(|e| e.to_string());
+        self.state.session_search_state.set_results(result);
+    }
+
+    /// Reload the usage statistics dialog for its current time range.
+    pub(super) fn load_usage_stats(&mut self) {
+        let days = self.state.usage_stats_state.range.days();
+        let result = StatsService::usage_stats(&self.core, days).map_err(|e| e.to_string());
+        self.state.usage_stats_state.set_stats(result);
+    }
+
+    /// Open the session a search hit belongs to and scroll to the matching message.
+    async fn open_search_hit(&mut self, hit: SearchHit) -> anyhow::Result<()> {
+        let document = hit.document;
+        match document.source {
+            SearchSource::Conduit => {
+                let Ok(session_id) = uuid::Uuid::parse_str(&document.source_key) else {
+                    self.show_error("Search", "This search result has an invalid session id.");
+                    return Ok(());
+                };
+                if let Some(index) = self.state.tab_manager.session_index_by_id(session_id) {
+                    self.state.tab_manager.switch_to(index);
+                    self.sync_footer_spinner();
+                } else {
+                    let workspace_id = self
+                        .session_tab_dao()
+                        .and_then(|dao| dao.get_by_id(session_id).ok().flatten())
+                        .and_then(|tab| tab.workspace_id);
+                    let Some(workspace_id) = workspace_id else {
+                        self.show_error(
+                            "Search",
+                            "The session for this result is no longer available.",
+                        );
+                        return Ok(());
+                    };
+                    // Restore the matching session, not the workspace's latest one
+                    self.open_workspace_session(workspace_id, Some(session_id), true);
+                }
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    if session.id == session_id {
+                        session.chat_view.scroll_to_message(hit.message_index);
+                    }
+                }
+            }
+            SearchSource::External => {
+                let Some(file_path) = document.file_path else {
+                    self.show_error("Search", "This search result has no history file.");
+                    return Ok(());
+                };
+                let working_dir = document
+                    .project
+                    .map(std::path::PathBuf::from)
+                    .unwrap_or_else(|| self.config().working_dir.clone());
+                self.create_imported_session_tab(document.agent_type, file_path, working_dir)
+                    .await?;
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.chat_view.scroll_to_message(hit.message_index);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Export the active session's transcript as Markdown, HTML and JSON.
+    fn export_transcript(&self) -> Result<String, String> {
+        let session = self
+            .state
+            .tab_manager
+            .active_session()
+            .ok_or_else(|| "No active session to export".to_string())?;
+        let messages = session.chat_view.messages();
+        if messages.is_empty() {
+            return Err("This session has no messages to export yet".to_string());
+        }
+
+        let info = TranscriptInfo {
+            session_id: session.id,
+            title: session.title.clone(),
+            agent_type: session.agent_type,
+            model: session.model.clone(),
+            exported_at: Utc::now(),
+        };
+
+        let export_dir = crate::util::exports_dir();
+        std::fs::create_dir_all(&export_dir)
+            .map_err(|e| format!("Could not create export directory: {}", e))?;
+
+        let mut paths = Vec::new();
+        for format in ExportFormat::ALL {
+            let content = ExportService::render(&info, messages, format);
+            let path = export_dir.join(info.file_name(format));
+            std::fs::write(&path, content)
+                .map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+            paths.push(path.display().to_string());
+        }
+
+        Ok(paths.join("\n"))
+    }
+
     /// Dump complete app state to a JSON file for debugging.
     fn dump_debug_state(&self) -> Result<String, String> {
         use chrono::Local;
@@ -9762,6 +11369,7 @@ mod tests {
     use crate::agent::AgentType;
     use crate::config::Config;
     use crate::data::{QueuedMessage, QueuedMessageMode};
+    use crate::ui::app_prompt::FORK_SEED_PREFIX;
     use crate::ui::components::MessageRole;
     use crate::ui::session::AgentSession;
     use crate::util::ToolAvailability;
@@ -9809,6 +11417,8 @@ mod tests {
             event_tx,
             event_rx,
             git_tracker: None,
+            daemon: None,
+            notifier: Notifier::new(crate::config::NotificationConfig::default()),
         }
     }
 
@@ -9969,7 +11579,7 @@ mod tests {
         let prompt = app_prompt::build_fork_seed_prompt(&messages);
 
         // Check header and structure
-        assert!(prompt.contains("[CONDUIT_FORK_SEED]"));
+        assert!(prompt.contains(FORK_SEED_PREFIX));
         assert!(prompt.contains("<previous-session-transcript>"));
         assert!(prompt.contains("</previous-session-transcript>"));
         assert!(prompt.contains("[END OF CONTEXT]"));
@@ -10004,6 +11614,54 @@ mod tests {
         assert!(prompt.ends_with("Ready"));
     }
 
+    #[test]
+    fn test_build_handoff_seed_prompt_normalizes_tool_names() {
+        use crate::ui::components::ChatMessage;
+
+        let messages = vec![
+            ChatMessage::user("Fix the build"),
+            ChatMessage::tool_with_exit("exec_command", "cargo build", "error[E0425]", Some(101)),
+            ChatMessage::tool_with_exit("apply_patch", "src/lib.rs", "", Some(0)),
+        ];
+
+        let prompt =
+            app_prompt::build_handoff_seed_prompt(&messages, AgentType::Codex, AgentType::Claude);
+
+        assert!(prompt.starts_with(FORK_SEED_PREFIX));
+        assert!(prompt.contains("PREVIOUS Codex CLI session to seed a NEW Claude Code session"));
+        assert!(prompt.contains("name=\"Bash\" args=\"cargo build\" exit=101"));
+        assert!(prompt.contains("name=\"Edit\""));
+        assert!(!prompt.contains("exec_command"));
+        assert!(prompt.ends_with("Ready"));
+
+        // Plain forks keep the agent's own tool names
+        let fork = app_prompt::build_fork_seed_prompt(&messages);
+        assert!(fork.contains("name=\"exec_command\""));
+    }
+
+    #[test]
+    fn test_hidden_fork_seed_refused_at_hard_limit() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        app.config_mut().budget.daily_hard_limit = Some(0.0);
+        let working_dir = tempfile::tempdir().expect("tempdir");
+        {
+            let session = app.state.tab_manager.session_mut(0).expect("session");
+            session.model = Some("gpt-5.2-codex".to_string());
+            session.working_dir = Some(working_dir.path().to_path_buf());
+        }
+
+        let seed = app_prompt::build_fork_seed_prompt(&[]);
+        let effects = app
+            .submit_prompt_hidden(seed, Vec::new(), Vec::new())
+            .expect("submit");
+
+        assert!(effects.is_empty());
+        let session = app.state.tab_manager.session(0).expect("session");
+        let last = session.chat_view.messages().last().expect("error message");
+        assert!(last.content.contains("new turns are blocked"));
+    }
+
     #[test]
     fn test_strip_image_placeholders_removes_placeholders() {
         let prompt = "Hello [img] world".to_string();
@@ -10626,6 +12284,32 @@ mod tests {
         assert!(effects.is_empty());
     }
 
+    #[test]
+    fn test_handoff_selector_requires_workspace() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+
+        app.show_handoff_selector();
+        assert_eq!(app.state.input_mode, InputMode::SelectingAgent);
+        assert_eq!(
+            app.state.agent_selector_state.purpose,
+            AgentSelectorPurpose::Handoff
+        );
+        assert_ne!(
+            app.state.agent_selector_state.selected_agent(),
+            AgentType::Codex
+        );
+
+        let mut effects = Vec::new();
+        app.handle_confirm_action(&mut effects).unwrap();
+
+        // The session has no workspace, so the handoff is refused without opening a tab
+        assert_eq!(app.state.input_mode, InputMode::ShowingError);
+        assert_eq!(app.state.tab_manager.len(), 1);
+        assert!(app.state.pending_fork_request.is_none());
+        assert!(effects.is_empty());
+    }
+
     #[test]
     fn test_handle_confirm_action_selecting_agent_creates_tab() {
         let mut app = build_test_app_with_sessions(&[]);
@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use chrono::Local;
+use uuid::Uuid;
+
+use crate::core::services::{CheckpointService, TurnCheckpoint};
+use crate::ui::app::App;
+use crate::ui::components::{ConfirmationContext, ConfirmationType};
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+
+impl App {
+    /// Checkpoint to record before a tab's next prompt reaches its agent.
+    ///
+    /// An attached daemon checkpoints the agents it runs itself.
+    pub(super) fn turn_checkpoint(&self, tab_index: usize) -> Option<TurnCheckpoint> {
+        if self.daemon.is_some() {
+            return None;
+        }
+        let session = self.state.tab_manager.session(tab_index)?;
+        TurnCheckpoint::for_session(
+            &self.core,
+            session.id,
+            session.workspace_id,
+            session.working_dir.clone()?,
+        )
+    }
+
+    /// Ask before rewinding the active session to before `turn` (default: the last turn)
+    pub(super) fn prompt_rewind_turn(&mut self, turn: Option<u32>) {
+        let Some(session) = self.state.tab_manager.active_session() else {
+            return;
+        };
+        if session.is_processing {
+            self.state.set_timed_footer_message(
+                "Stop the agent before rewinding".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        }
+        let session_id = session.id;
+        let checkpoint = match self
+            .core
+            .checkpoint_store()
+            .map(|store| store.get(session_id, turn))
+        {
+            Some(Ok(Some(checkpoint))) => checkpoint,
+            Some(Err(e)) => {
+                self.show_error("Unable to Rewind", &e.to_string());
+                return;
+            }
+            _ => {
+                let message = match turn {
+                    Some(turn) => format!("No checkpoint for turn {}", turn),
+                    None => "No checkpoints for this session yet".to_string(),
+                };
+                self.state
+                    .set_timed_footer_message(message, Duration::from_secs(3));
+                return;
+            }
+        };
+
+        let truncate = self.config().checkpoints.truncate_transcript;
+        let mut details = vec![
+            format!(
+                "Changes since {} will be discarded.",
+                checkpoint
+                    .created_at
+                    .with_timezone(&Local)
+                    .format("%H:%M:%S")
+            ),
+            "Commits made since then will be undone.".to_string(),
+        ];
+        if truncate {
+            details.push(format!(
+                "Turn {} and later will be removed from the transcript.",
+                checkpoint.turn
+            ));
+        }
+
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Rewind Workspace",
+            format!("Restore the workspace to before turn {}?", checkpoint.turn),
+            details,
+            ConfirmationType::Danger,
+            "Rewind",
+            Some(ConfirmationContext::RewindTurn {
+                session_id,
+                turn: checkpoint.turn,
+            }),
+        );
+        self.state.input_mode = InputMode::Confirming;
+    }
+
+    /// Rewind a session once the confirmation dialog is accepted
+    pub(super) fn rewind_to_turn(&mut self, session_id: Uuid, turn: u32) -> Option<Effect> {
+        let truncate = self.config().checkpoints.truncate_transcript;
+        match CheckpointService::prepare_rewind(&self.core, session_id, Some(turn)) {
+            Ok(rewind) => Some(Effect::RewindTurn {
+                session_id,
+                turn,
+                rewind,
+                truncate,
+            }),
+            Err(e) => {
+                self.show_error("Unable to Rewind", &e.to_string());
+                None
+            }
+        }
+    }
+
+    pub(super) fn handle_turn_rewound(
+        &mut self,
+        session_id: Uuid,
+        turn: u32,
+        truncated: bool,
+        result: Result<(), String>,
+    ) {
+        if let Err(e) = result {
+            self.show_error("Unable to Rewind", &e);
+            return;
+        }
+
+        if truncated {
+            let store = self.core.session_event_store();
+            if let Some(index) = self.state.tab_manager.session_index_by_id(session_id) {
+                if let Some(session) = self.state.tab_manager.session_mut(index) {
+                    session.chat_view.clear();
+                    session.raw_events_view.clear();
+                    Self::restore_history_from_journal(store, session);
+                }
+            }
+        }
+        self.state.set_timed_footer_message(
+            format!("Rewound workspace to before turn {}", turn),
+            Duration::from_secs(5),
+        );
+    }
+}
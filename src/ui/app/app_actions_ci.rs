@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::core::services::CiService;
+use crate::git::CiFailures;
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+
+impl App {
+    /// Fetch the failing checks of the active workspace's PR for the agent
+    pub(super) fn fix_ci(&mut self) -> Option<Effect> {
+        let session = self.state.tab_manager.active_session();
+        let (Some(workspace_id), Some(working_dir)) = (
+            session.and_then(|s| s.workspace_id),
+            session.and_then(|s| s.working_dir.clone()),
+        ) else {
+            self.state.set_timed_footer_message(
+                "Open a workspace to fix its CI checks".to_string(),
+                Duration::from_secs(3),
+            );
+            return None;
+        };
+        if !self.state.fetching_ci_failures.insert(workspace_id) {
+            return None;
+        }
+
+        self.state.set_timed_footer_message(
+            "Fetching failing CI checks...".to_string(),
+            Duration::from_secs(3),
+        );
+        Some(Effect::FetchCiFailures {
+            workspace_id,
+            working_dir,
+            auto: false,
+        })
+    }
+
+    /// Called by the git tracker when a PR's checks start failing
+    pub(super) fn auto_fix_ci(&mut self, workspace_id: Uuid, pr_number: u32) -> Option<Effect> {
+        let ci = self.config().ci;
+        if !ci.auto_fix || self.state.fetching_ci_failures.contains(&workspace_id) {
+            return None;
+        }
+        let attempts = self
+            .state
+            .ci_auto_fix_attempts
+            .get(&workspace_id)
+            .copied()
+            .unwrap_or(0);
+        if attempts >= ci.auto_fix_limit {
+            tracing::debug!(%workspace_id, pr_number, "CI auto-fix limit reached");
+            return None;
+        }
+        let tab_index = self.find_tab_for_workspace(workspace_id)?;
+        let working_dir = self
+            .state
+            .tab_manager
+            .session(tab_index)
+            .and_then(|session| session.working_dir.clone())?;
+
+        self.state
+            .ci_auto_fix_attempts
+            .insert(workspace_id, attempts + 1);
+        self.state.fetching_ci_failures.insert(workspace_id);
+        tracing::info!(%workspace_id, pr_number, "CI checks started failing, fetching logs");
+        Some(Effect::FetchCiFailures {
+            workspace_id,
+            working_dir,
+            auto: true,
+        })
+    }
+
+    pub(super) fn handle_ci_failures_fetched(
+        &mut self,
+        workspace_id: Uuid,
+        auto: bool,
+        result: Result<CiFailures, String>,
+    ) -> anyhow::Result<Vec<Effect>> {
+        self.state.fetching_ci_failures.remove(&workspace_id);
+
+        let failures = match result {
+            Ok(failures) => failures,
+            Err(e) => {
+                tracing::warn!(%workspace_id, error = %e, "Failed to fetch CI failures");
+                self.state
+                    .set_timed_footer_message(format!("Fix CI: {}", e), Duration::from_secs(5));
+                return Ok(Vec::new());
+            }
+        };
+        let Some(tab_index) = self.find_tab_for_workspace(workspace_id) else {
+            return Ok(Vec::new());
+        };
+        let branch = self
+            .workspace_dao()
+            .and_then(|store| store.get_by_id(workspace_id).ok().flatten())
+            .map(|workspace| workspace.branch);
+        let prompt = match CiService::fix_prompt(&failures, branch.as_deref()) {
+            Ok(prompt) => prompt,
+            Err(e) => {
+                // The checks may have been re-run since they were reported failing
+                if !auto {
+                    self.state
+                        .set_timed_footer_message(e.to_string(), Duration::from_secs(3));
+                }
+                return Ok(Vec::new());
+            }
+        };
+
+        let count = failures.checks.len();
+        let noun = if count == 1 { "check" } else { "checks" };
+        let (effects, queued) = self.submit_or_queue_prompt(tab_index, prompt)?;
+        let verb = if queued { "Queued" } else { "Sent" };
+        let target = if queued { "for" } else { "to" };
+        self.state.set_timed_footer_message(
+            format!(
+                "{} {} failing CI {} on PR #{} {} the agent",
+                verb, count, noun, failures.pr_number, target
+            ),
+            Duration::from_secs(4),
+        );
+        Ok(effects)
+    }
+}
@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use crate::core::services::{CommitOutcome, CommitService};
+use crate::git::PrManager;
+use crate::ui::action::Action;
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+use crate::util::GeneratedCommitMessage;
+
+impl App {
+    pub(super) fn handle_commit_action(&mut self, action: Action, effects: &mut Vec<Effect>) {
+        match action {
+            Action::OpenCommitDialog => {
+                if let Some(effect) = self.open_commit_dialog() {
+                    effects.push(effect);
+                }
+            }
+            Action::ToggleCommitPush => {
+                if self.state.input_mode == InputMode::Committing {
+                    self.state.commit_dialog_state.toggle_push();
+                }
+            }
+            Action::RegenerateCommitMessage => {
+                let dialog = &mut self.state.commit_dialog_state;
+                if self.state.input_mode != InputMode::Committing
+                    || dialog.generating
+                    || dialog.committing
+                {
+                    return;
+                }
+                if let (Some(workspace_id), Some(working_dir)) =
+                    (dialog.workspace_id, dialog.working_dir.clone())
+                {
+                    dialog.start_regenerate();
+                    effects.push(Effect::GenerateCommitMessage {
+                        workspace_id,
+                        working_dir,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the commit dialog for the active workspace and start generating a message
+    fn open_commit_dialog(&mut self) -> Option<Effect> {
+        let session = self.state.tab_manager.active_session();
+        let (Some(workspace_id), Some(working_dir)) = (
+            session.and_then(|s| s.workspace_id),
+            session.and_then(|s| s.working_dir.clone()),
+        ) else {
+            self.state.set_timed_footer_message(
+                "Open a workspace to commit".to_string(),
+                Duration::from_secs(3),
+            );
+            return None;
+        };
+
+        let files = match CommitService::changed_files(&working_dir) {
+            Ok(files) => files,
+            Err(e) => {
+                self.show_error("Unable to Commit", &e.to_string());
+                return None;
+            }
+        };
+        if files.is_empty() {
+            self.state
+                .set_timed_footer_message("Nothing to commit".to_string(), Duration::from_secs(3));
+            return None;
+        }
+
+        let branch = PrManager::get_current_branch(&working_dir);
+        self.state.close_overlays();
+        self.state
+            .commit_dialog_state
+            .show(workspace_id, working_dir.clone(), branch, files);
+        self.state.input_mode = InputMode::Committing;
+
+        Some(Effect::GenerateCommitMessage {
+            workspace_id,
+            working_dir,
+        })
+    }
+
+    /// Commit with the message in the dialog
+    pub(super) fn confirm_commit(&mut self) -> Option<Effect> {
+        let dialog = &mut self.state.commit_dialog_state;
+        if dialog.committing {
+            return None;
+        }
+        let message = dialog.commit_message();
+        if message.is_empty() {
+            dialog.error = Some(if dialog.generating {
+                "Wait for the message to be generated, or type one".to_string()
+            } else {
+                "Commit message cannot be empty".to_string()
+            });
+            return None;
+        }
+        let (Some(workspace_id), Some(working_dir)) =
+            (dialog.workspace_id, dialog.working_dir.clone())
+        else {
+            return None;
+        };
+
+        dialog.committing = true;
+        dialog.error = None;
+        Some(Effect::CommitWorkspace {
+            workspace_id,
+            working_dir,
+            message,
+            push: dialog.push,
+        })
+    }
+
+    pub(super) fn handle_commit_message_generated(
+        &mut self,
+        workspace_id: uuid::Uuid,
+        result: Result<GeneratedCommitMessage, String>,
+    ) {
+        let dialog = &mut self.state.commit_dialog_state;
+        if !dialog.is_visible() || dialog.workspace_id != Some(workspace_id) {
+            return;
+        }
+        dialog.set_generated(result);
+    }
+
+    pub(super) fn handle_workspace_committed(
+        &mut self,
+        workspace_id: uuid::Uuid,
+        result: Result<CommitOutcome, String>,
+    ) {
+        let dialog_open = self.state.commit_dialog_state.is_visible()
+            && self.state.commit_dialog_state.workspace_id == Some(workspace_id);
+
+        let outcome = match result {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                if dialog_open {
+                    // Keep the dialog open so the message isn't lost
+                    self.state.commit_dialog_state.committing = false;
+                    self.state.commit_dialog_state.error = Some(err);
+                } else {
+                    self.show_error("Commit Failed", &err);
+                }
+                return;
+            }
+        };
+
+        if dialog_open {
+            self.state.commit_dialog_state.hide();
+            if self.state.input_mode == InputMode::Committing {
+                self.state.input_mode = InputMode::Normal;
+            }
+        }
+        if let Some(ref tracker) = self.git_tracker {
+            tracker.refresh_now(workspace_id);
+        }
+
+        let short_sha: String = outcome.commit_sha.chars().take(7).collect();
+        let subject = outcome.message.lines().next().unwrap_or_default();
+        if let Some(push_error) = outcome.push_error {
+            self.show_error_with_details(
+                "Push Failed",
+                &format!("Committed {} but the push failed.", short_sha),
+                &push_error,
+            );
+            return;
+        }
+        let pushed = if outcome.pushed { " and pushed" } else { "" };
+        self.state.set_timed_footer_message(
+            format!("Committed {}{}: {}", short_sha, pushed, subject),
+            Duration::from_secs(5),
+        );
+    }
+}
@@ -68,9 +68,7 @@ impl App {
                 effects.extend(self.confirm_theme_picker()?);
             }
             InputMode::SelectingAgent => {
-                let agent_type = self.state.agent_selector_state.selected_agent();
-                self.state.agent_selector_state.hide();
-                self.create_tab_with_agent(agent_type);
+                self.confirm_agent_selection();
             }
             InputMode::PickingProject => {
                 if let Some(project) = self.state.project_picker_state.selected_project() {
@@ -92,19 +90,19 @@ impl App {
             }
             InputMode::AddingRepository => {
                 if self.state.add_repo_dialog_state.is_valid() {
-                    let repo_id = self.add_repository();
-                    self.state.add_repo_dialog_state.hide();
-                    if let Some(id) = repo_id {
-                        self.state.sidebar_data.expand_repo(id);
-                        if let Some(repo_index) = self.state.sidebar_data.find_repo_index(id) {
-                            self.state.sidebar_state.tree_state.selected = repo_index + 1;
-                        }
-                        self.state.sidebar_state.show();
-                        self.state.sidebar_state.set_focused(true);
-                        self.state.show_first_time_splash = false;
-                        self.state.input_mode = InputMode::SidebarNavigation;
-                    } else {
+                    if self.state.add_repo_dialog_state.is_remote() {
+                        let effect = self.start_repository_clone();
+                        self.state.add_repo_dialog_state.hide();
                         self.state.input_mode = InputMode::Normal;
+                        effects.extend(effect);
+                    } else {
+                        let repo_id = self.add_repository();
+                        self.state.add_repo_dialog_state.hide();
+                        if let Some(id) = repo_id {
+                            self.focus_added_repository(id);
+                        } else {
+                            self.state.input_mode = InputMode::Normal;
+                        }
                     }
                 }
             }
@@ -251,6 +249,49 @@ impl App {
                                 return Ok(());
                             }
                         }
+                        ConfirmationContext::RevertHunk {
+                            viewer_id,
+                            path,
+                            hunk_index,
+                            header,
+                        } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                self.revert_diff_hunk(viewer_id, &path, hunk_index, &header);
+                                return Ok(());
+                            }
+                        }
+                        ConfirmationContext::ResolveSyncConflicts {
+                            workspace_id,
+                            prompt,
+                        } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.resolve_sync_conflicts(workspace_id, prompt)?);
+                                return Ok(());
+                            }
+                        }
+                        ConfirmationContext::RewindTurn { session_id, turn } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.rewind_to_turn(session_id, turn));
+                                return Ok(());
+                            }
+                        }
+                        ConfirmationContext::PickRaceWinner {
+                            race_id,
+                            workspace_id,
+                        } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.pick_race_winner(race_id, workspace_id));
+                                return Ok(());
+                            }
+                        }
                         ConfirmationContext::ForkSession {
                             parent_workspace_id,
                             base_branch,
@@ -275,6 +316,14 @@ impl App {
                 self.state.error_dialog_state.hide();
                 self.state.input_mode = InputMode::Normal;
             }
+            InputMode::Committing => {
+                if let Some(effect) = self.confirm_commit() {
+                    effects.push(effect);
+                }
+            }
+            InputMode::ReviewingComments => {
+                effects.extend(self.send_review_comments()?);
+            }
             InputMode::MissingTool => {
                 // Validate and save the path
                 if let Some(result) = self.state.missing_tool_dialog_state.validate() {
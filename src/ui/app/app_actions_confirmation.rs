@@ -110,6 +110,37 @@ impl App {
                                 self.state.input_mode = InputMode::Normal;
                                 effects.extend(self.confirm_steer_fallback(message_id)?);
                             }
+                            ConfirmationContext::RevertHunk {
+                                viewer_id,
+                                path,
+                                hunk_index,
+                                header,
+                            } => {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                self.revert_diff_hunk(viewer_id, &path, hunk_index, &header);
+                            }
+                            ConfirmationContext::ResolveSyncConflicts {
+                                workspace_id,
+                                prompt,
+                            } => {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.resolve_sync_conflicts(workspace_id, prompt)?);
+                            }
+                            ConfirmationContext::RewindTurn { session_id, turn } => {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.rewind_to_turn(session_id, turn));
+                            }
+                            ConfirmationContext::PickRaceWinner {
+                                race_id,
+                                workspace_id,
+                            } => {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.pick_race_winner(race_id, workspace_id));
+                            }
                             ConfirmationContext::ForkSession {
                                 parent_workspace_id,
                                 base_branch,
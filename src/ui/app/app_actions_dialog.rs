@@ -60,6 +60,28 @@ impl App {
                     self.state.session_import_state.hide();
                     self.state.input_mode = InputMode::Normal;
                 }
+                InputMode::SearchingSessions => {
+                    self.state.session_search_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
+                InputMode::ViewingUsageStats => {
+                    self.state.usage_stats_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
+                InputMode::Committing => {
+                    self.state.commit_dialog_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
+                InputMode::ReviewingComments => {
+                    self.state.review_comments_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
+                InputMode::FindingInChat => {
+                    if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        session.chat_view.close_find();
+                    }
+                    self.state.input_mode = InputMode::Normal;
+                }
                 InputMode::CommandPalette => {
                     self.state.command_palette_state.hide();
                     self.state.input_mode = InputMode::Normal;
@@ -81,7 +81,17 @@ impl App {
                 }
             }
             Action::ForkSession => {
-                self.initiate_fork_session();
+                if let Some(agent_type) = self
+                    .state
+                    .tab_manager
+                    .active_session()
+                    .map(|s| s.agent_type)
+                {
+                    self.initiate_fork_session(agent_type);
+                }
+            }
+            Action::HandoffSession => {
+                self.show_handoff_selector();
             }
             Action::InterruptAgent => {
                 self.interrupt_agent();
@@ -125,6 +135,112 @@ impl App {
                     }
                 }
             }
+            Action::OpenSessionSearch => {
+                self.state.close_overlays();
+                self.state.session_search_state.show();
+                self.state.input_mode = InputMode::SearchingSessions;
+                // Bring the index up to date before (re)running the query
+                effects.push(Effect::RefreshSearchIndex);
+            }
+            Action::OpenSearchResult => {
+                if self.state.input_mode == InputMode::SearchingSessions {
+                    if let Some(hit) = self.state.session_search_state.selected_hit().cloned() {
+                        self.state.session_search_state.hide();
+                        self.state.input_mode = InputMode::Normal;
+                        effects.push(Effect::OpenSearchHit(hit));
+                    }
+                }
+            }
+            Action::ShowUsageStats => {
+                self.state.close_overlays();
+                self.state.usage_stats_state.show();
+                self.state.input_mode = InputMode::ViewingUsageStats;
+                self.load_usage_stats();
+            }
+            Action::NextStatsView => {
+                if self.state.input_mode == InputMode::ViewingUsageStats {
+                    self.state.usage_stats_state.next_dimension();
+                }
+            }
+            Action::PrevStatsView => {
+                if self.state.input_mode == InputMode::ViewingUsageStats {
+                    self.state.usage_stats_state.prev_dimension();
+                }
+            }
+            Action::CycleStatsRange => {
+                if self.state.input_mode == InputMode::ViewingUsageStats {
+                    self.state.usage_stats_state.cycle_range();
+                    self.load_usage_stats();
+                }
+            }
+            Action::RewindTurn => {
+                self.prompt_rewind_turn(None);
+            }
+            Action::ShowDiff => {
+                self.handle_show_diff(effects);
+            }
+            Action::DiffNextFile => {
+                if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.select_next_file();
+                }
+            }
+            Action::DiffPrevFile => {
+                if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.select_prev_file();
+                }
+            }
+            Action::DiffToggleLayout => {
+                if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.toggle_layout();
+                }
+            }
+            Action::DiffToggleBase => {
+                if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.toggle_base();
+                }
+            }
+            Action::DiffReload => {
+                self.reload_diff_viewer();
+            }
+            Action::DiffNextHunk => {
+                if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.select_next_hunk();
+                }
+            }
+            Action::DiffPrevHunk => {
+                if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.select_prev_hunk();
+                }
+            }
+            Action::DiffStageHunk => {
+                self.stage_diff_hunk();
+            }
+            Action::DiffRevertHunk => {
+                self.prompt_revert_diff_hunk();
+            }
+            Action::DiffToggleScope => {
+                if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.toggle_scope();
+                }
+            }
+            Action::StartRace => {
+                self.handle_start_race(None, effects);
+            }
+            Action::RacePrompt(prompt) => {
+                self.handle_start_race(Some(prompt), effects);
+            }
+            Action::RaceOpenContender => {
+                self.open_race_contender();
+            }
+            Action::RacePickWinner => {
+                self.prompt_pick_race_winner();
+            }
+            Action::RaceRunTests => {
+                self.run_race_tests();
+            }
+            Action::RaceReload => {
+                self.reload_race_viewer();
+            }
             Action::CycleImportFilter => {
                 if self.state.input_mode == InputMode::ImportingSession {
                     self.state.session_import_state.cycle_filter();
@@ -140,6 +256,15 @@ impl App {
                 //     self.state.stop_footer_spinner();
                 // }
             }
+            Action::ToggleQuietMode => {
+                let message = if self.notifier.toggle_quiet() {
+                    "Notifications muted"
+                } else {
+                    "Notifications on"
+                };
+                self.state
+                    .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+            }
             Action::ToggleAgentMode => {
                 if let Some(session) = self.state.tab_manager.active_session_mut() {
                     // Only toggle when agent supports plan mode
@@ -152,6 +277,9 @@ impl App {
             Action::DumpDebugState => {
                 effects.push(Effect::DumpDebugState);
             }
+            Action::ExportTranscript => {
+                effects.push(Effect::ExportTranscript);
+            }
             Action::CopyWorkspacePath => {
                 if let Some(session) = self.state.tab_manager.active_session() {
                     if let Some(working_dir) = &session.working_dir {
@@ -4,11 +4,16 @@ use crate::ui::events::InputMode;
 
 impl App {
     pub(super) fn handle_input_edit_action(&mut self, action: Action) {
+        if self.state.input_mode == InputMode::Committing {
+            self.state.commit_dialog_state.handle_edit_action(&action);
+            return;
+        }
         match action {
             Action::InsertNewline => {
-                // Don't insert newlines in help dialog, command mode, or sidebar navigation
+                // Don't insert newlines in help dialog, command/find mode, or sidebar navigation
                 if self.state.input_mode != InputMode::ShowingHelp
                     && self.state.input_mode != InputMode::Command
+                    && self.state.input_mode != InputMode::FindingInChat
                     && self.state.input_mode != InputMode::SidebarNavigation
                 {
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
@@ -28,9 +33,18 @@ impl App {
                 InputMode::ShowingHelp => {
                     self.state.help_dialog_state.delete_char();
                 }
+                InputMode::FindingInChat => {
+                    if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        session.chat_view.pop_find_char();
+                    }
+                }
                 InputMode::ImportingSession => {
                     self.state.session_import_state.delete_char();
                 }
+                InputMode::SearchingSessions => {
+                    self.state.session_search_state.delete_char();
+                    self.run_session_search();
+                }
                 InputMode::PickingProject => {
                     self.state.project_picker_state.delete_char();
                 }
@@ -74,6 +88,9 @@ impl App {
                     self.state.base_dir_dialog_state.delete_forward();
                 } else if self.state.input_mode == InputMode::AddingRepository {
                     self.state.add_repo_dialog_state.delete_forward();
+                } else if self.state.input_mode == InputMode::SearchingSessions {
+                    self.state.session_search_state.delete_forward();
+                    self.run_session_search();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.delete();
                 }
@@ -105,6 +122,8 @@ impl App {
                     self.state.add_repo_dialog_state.move_left();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_left();
+                } else if self.state.input_mode == InputMode::SearchingSessions {
+                    self.state.session_search_state.move_cursor_left();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_left();
                 }
@@ -118,6 +137,8 @@ impl App {
                     self.state.add_repo_dialog_state.move_right();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_right();
+                } else if self.state.input_mode == InputMode::SearchingSessions {
+                    self.state.session_search_state.move_cursor_right();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_right();
                 }
@@ -131,6 +152,8 @@ impl App {
                     self.state.add_repo_dialog_state.move_start();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_start();
+                } else if self.state.input_mode == InputMode::SearchingSessions {
+                    self.state.session_search_state.move_cursor_start();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_start();
                 }
@@ -144,6 +167,8 @@ impl App {
                     self.state.add_repo_dialog_state.move_end();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_end();
+                } else if self.state.input_mode == InputMode::SearchingSessions {
+                    self.state.session_search_state.move_cursor_end();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_end();
                 }
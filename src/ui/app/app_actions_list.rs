@@ -28,6 +28,15 @@ impl App {
                 InputMode::ImportingSession => {
                     self.state.session_import_state.select_next();
                 }
+                InputMode::SearchingSessions => {
+                    self.state.session_search_state.select_next();
+                }
+                InputMode::ViewingUsageStats => {
+                    self.state.usage_stats_state.scroll_down(1);
+                }
+                InputMode::ReviewingComments => {
+                    self.state.review_comments_state.select_next();
+                }
                 InputMode::CommandPalette => {
                     self.state.command_palette_state.select_next();
                 }
@@ -64,6 +73,15 @@ impl App {
                 InputMode::ImportingSession => {
                     self.state.session_import_state.select_prev();
                 }
+                InputMode::SearchingSessions => {
+                    self.state.session_search_state.select_prev();
+                }
+                InputMode::ViewingUsageStats => {
+                    self.state.usage_stats_state.scroll_up(1);
+                }
+                InputMode::ReviewingComments => {
+                    self.state.review_comments_state.select_prev();
+                }
                 InputMode::CommandPalette => {
                     self.state.command_palette_state.select_prev();
                 }
@@ -82,6 +100,10 @@ impl App {
                     self.state.project_picker_state.page_down();
                 } else if self.state.input_mode == InputMode::ImportingSession {
                     self.state.session_import_state.page_down();
+                } else if self.state.input_mode == InputMode::SearchingSessions {
+                    self.state.session_search_state.page_down();
+                } else if self.state.input_mode == InputMode::ViewingUsageStats {
+                    self.state.usage_stats_state.page_down();
                 }
             }
             Action::SelectPageUp => {
@@ -89,6 +111,10 @@ impl App {
                     self.state.project_picker_state.page_up();
                 } else if self.state.input_mode == InputMode::ImportingSession {
                     self.state.session_import_state.page_up();
+                } else if self.state.input_mode == InputMode::SearchingSessions {
+                    self.state.session_search_state.page_up();
+                } else if self.state.input_mode == InputMode::ViewingUsageStats {
+                    self.state.usage_stats_state.page_up();
                 }
             }
             _ => {}
@@ -12,9 +12,7 @@ impl App {
             }
             Action::SelectAgent => {
                 if self.state.input_mode == InputMode::SelectingAgent {
-                    let agent_type = self.state.agent_selector_state.selected_agent();
-                    self.state.agent_selector_state.hide();
-                    self.create_tab_with_agent(agent_type);
+                    self.confirm_agent_selection();
                 }
             }
             Action::ShowHelp => {
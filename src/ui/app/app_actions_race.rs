@@ -0,0 +1,443 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::core::services::{RaceService, RaceStanding};
+use crate::data::{Race, RaceTestResult};
+use crate::ui::app::{send_app_event, App};
+use crate::ui::components::{ConfirmationContext, ConfirmationType};
+use crate::ui::effect::Effect;
+use crate::ui::events::{AppEvent, InputMode};
+use crate::ui::race_viewer::RaceViewerSession;
+use crate::ui::session::AgentSession;
+
+impl App {
+    /// Race `prompt` (or the input box) from the active session's workspace
+    pub(super) fn handle_start_race(&mut self, prompt: Option<String>, effects: &mut Vec<Effect>) {
+        let Some(session) = self.state.tab_manager.active_session() else {
+            self.state.set_timed_footer_message(
+                "Open a workspace to start a race".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        };
+        let Some(workspace_id) = session.workspace_id else {
+            self.state.set_timed_footer_message(
+                "Open a workspace to start a race".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        };
+        let from_input = prompt.is_none();
+        let prompt = prompt.unwrap_or_else(|| session.input_box.input().to_string());
+        if prompt.trim().is_empty() {
+            self.state.set_timed_footer_message(
+                "Type a prompt to race".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        }
+
+        let contenders = match RaceService::configured_contenders(&self.core) {
+            Ok(contenders) if contenders.len() >= 2 => contenders,
+            Ok(_) => {
+                self.show_error(
+                    "Unable to Start Race",
+                    "A race needs at least two agents. Install another agent or set [race] contenders.",
+                );
+                return;
+            }
+            Err(e) => {
+                self.show_error("Unable to Start Race", &e.to_string());
+                return;
+            }
+        };
+
+        if from_input {
+            if let Some(session) = self.state.tab_manager.active_session_mut() {
+                session.input_box.clear();
+            }
+        }
+        self.mark_workspace_busy(workspace_id);
+        effects.push(Effect::StartRace {
+            workspace_id,
+            prompt,
+            contenders,
+        });
+    }
+
+    /// Open a tab per contender, send each the prompt and open the race tab
+    pub(super) fn finish_race_start(&mut self, race: Race) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        self.refresh_sidebar_data();
+        self.state.sidebar_data.expand_repo(race.repository_id);
+
+        let project_name = self
+            .repo_dao()
+            .and_then(|dao| dao.get_by_id(race.repository_id).ok().flatten())
+            .map(|repo| repo.name);
+        let workspaces: Vec<_> = match self.workspace_dao() {
+            Some(dao) => race
+                .entries
+                .iter()
+                .filter_map(|entry| {
+                    dao.get_by_id(entry.workspace_id)
+                        .ok()
+                        .flatten()
+                        .map(|workspace| (entry.clone(), workspace))
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut failures = Vec::new();
+        for (entry, workspace) in workspaces {
+            let mut session =
+                AgentSession::with_working_dir(entry.agent_type, workspace.path.clone());
+            session.workspace_id = Some(workspace.id);
+            session.project_name = project_name.clone();
+            session.workspace_name = Some(workspace.name.clone());
+            session.model = entry.model.clone();
+            session.update_status();
+
+            let Some(tab_index) = self.state.tab_manager.add_session(session) else {
+                failures.push(format!(
+                    "{}: maximum number of tabs reached",
+                    workspace.name
+                ));
+                continue;
+            };
+            if let Some(ref tracker) = self.git_tracker {
+                tracker.track_workspace(workspace.id, workspace.path.clone());
+            }
+            match self.submit_prompt_for_tab(
+                tab_index,
+                race.prompt.clone(),
+                vec![],
+                vec![],
+                false,
+                None,
+            ) {
+                Ok(mut submit_effects) => effects.append(&mut submit_effects),
+                Err(e) => failures.push(format!("{}: {}", workspace.name, e)),
+            }
+        }
+
+        if self
+            .state
+            .tab_manager
+            .open_race(RaceViewerSession::new(race))
+            .is_some()
+        {
+            self.reload_race_viewer();
+            self.state.sidebar_state.set_focused(false);
+            self.state.input_mode = InputMode::Normal;
+        } else {
+            failures.push("Race tab: maximum number of tabs reached".to_string());
+        }
+        self.sync_footer_spinner();
+
+        if !failures.is_empty() {
+            self.show_error_with_details(
+                "Race Started with Errors",
+                "Some contenders could not be started",
+                &failures.join("\n"),
+            );
+        }
+        effects.push(Effect::SaveSessionState);
+        effects
+    }
+
+    /// Load a race and its standings from the database
+    fn load_race_standings(&self, race_id: Uuid) -> Result<(Race, Vec<RaceStanding>), String> {
+        let race_store = self
+            .core
+            .race_store()
+            .ok_or_else(|| "Race database unavailable".to_string())?;
+        let workspace_store = self
+            .workspace_dao()
+            .ok_or_else(|| "Workspace database unavailable".to_string())?;
+        let race = RaceService::get(race_store, race_id).map_err(|e| e.to_string())?;
+        let standings = RaceService::standings(workspace_store, self.core.usage_store(), &race)
+            .map_err(|e| e.to_string())?;
+        Ok((race, standings))
+    }
+
+    /// Refresh the active race tab
+    pub(super) fn reload_race_viewer(&mut self) {
+        let Some(race_id) = self
+            .state
+            .tab_manager
+            .active_race_viewer()
+            .map(|viewer| viewer.race.id)
+        else {
+            return;
+        };
+        let loaded = self.load_race_standings(race_id);
+        if let Some(viewer) = self.state.tab_manager.active_race_viewer_mut() {
+            Self::apply_race_standings(viewer, loaded);
+        }
+    }
+
+    /// Refresh every race tab that has `workspace_id` as a contender
+    pub(super) fn refresh_race_viewers(&mut self, workspace_id: Uuid) {
+        let race_ids: Vec<Uuid> = self
+            .state
+            .tab_manager
+            .race_viewers_mut()
+            .filter(|viewer| viewer.has_workspace(workspace_id))
+            .map(|viewer| viewer.race.id)
+            .collect();
+        for race_id in race_ids {
+            let loaded = self.load_race_standings(race_id);
+            if let Some(viewer) = self
+                .state
+                .tab_manager
+                .race_viewers_mut()
+                .find(|viewer| viewer.race.id == race_id)
+            {
+                Self::apply_race_standings(viewer, loaded);
+            }
+        }
+    }
+
+    fn apply_race_standings(
+        viewer: &mut RaceViewerSession,
+        loaded: Result<(Race, Vec<RaceStanding>), String>,
+    ) {
+        match loaded {
+            Ok((race, standings)) => {
+                viewer.race = race;
+                viewer.set_standings(Ok(standings));
+            }
+            Err(e) => viewer.set_standings(Err(e)),
+        }
+    }
+
+    /// Refresh race standings when a contender's turn ends, running the
+    /// configured tests once the agent is idle
+    pub(super) fn handle_race_turn_finished(&mut self, tab_index: usize) {
+        let Some(session) = self.state.tab_manager.session(tab_index) else {
+            return;
+        };
+        let Some(workspace_id) = session.workspace_id else {
+            return;
+        };
+        let idle_dir = (!session.is_processing)
+            .then(|| session.working_dir.clone())
+            .flatten();
+        let Some(race_id) = self
+            .core
+            .race_store()
+            .and_then(|store| store.find_by_workspace(workspace_id).ok().flatten())
+        else {
+            return;
+        };
+
+        self.refresh_race_viewers(workspace_id);
+        if let (Some(command), Some(working_dir)) =
+            (self.config().race.test_command.clone(), idle_dir)
+        {
+            self.spawn_race_tests(race_id, workspace_id, working_dir, command);
+        }
+    }
+
+    /// Run the test command for every live contender of the active race tab
+    pub(super) fn run_race_tests(&mut self) {
+        let Some(viewer) = self.state.tab_manager.active_race_viewer() else {
+            return;
+        };
+        let Some(command) = self.config().race.test_command.clone() else {
+            self.state.set_timed_footer_message(
+                "Set [race] test_command to run tests".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        };
+        let race_id = viewer.race.id;
+        let workspace_ids: Vec<Uuid> = viewer
+            .race
+            .entries
+            .iter()
+            .map(|entry| entry.workspace_id)
+            .filter(|id| !viewer.is_testing(*id))
+            .collect();
+        let Some(workspace_dao) = self.workspace_dao_clone() else {
+            return;
+        };
+        for workspace_id in workspace_ids {
+            let Ok(Some(workspace)) = workspace_dao.get_by_id(workspace_id) else {
+                continue;
+            };
+            if workspace.archived_at.is_none() && workspace.path.exists() {
+                self.spawn_race_tests(race_id, workspace_id, workspace.path, command.clone());
+            }
+        }
+    }
+
+    fn spawn_race_tests(
+        &mut self,
+        race_id: Uuid,
+        workspace_id: Uuid,
+        working_dir: PathBuf,
+        command: String,
+    ) {
+        let Some(race_store) = self.core.race_store_clone() else {
+            return;
+        };
+        for viewer in self.state.tab_manager.race_viewers_mut() {
+            if viewer.race.id == race_id {
+                viewer.set_testing(workspace_id, true);
+            }
+        }
+        let event_tx = self.event_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let result =
+                RaceService::run_tests(&race_store, race_id, workspace_id, &working_dir, &command)
+                    .map_err(|e| e.to_string());
+            send_app_event(
+                &event_tx,
+                AppEvent::RaceTestsFinished {
+                    race_id,
+                    workspace_id,
+                    result,
+                },
+                "race_tests_finished",
+            );
+        });
+    }
+
+    pub(super) fn finish_race_tests(
+        &mut self,
+        race_id: Uuid,
+        workspace_id: Uuid,
+        result: Result<RaceTestResult, String>,
+    ) {
+        for viewer in self.state.tab_manager.race_viewers_mut() {
+            if viewer.race.id == race_id {
+                viewer.set_testing(workspace_id, false);
+            }
+        }
+        if let Err(e) = result {
+            self.state
+                .set_timed_footer_message(format!("Race tests: {}", e), Duration::from_secs(5));
+        }
+        self.refresh_race_viewers(workspace_id);
+    }
+
+    /// Switch to the selected contender's session
+    pub(super) fn open_race_contender(&mut self) {
+        let Some(standing) = self
+            .state
+            .tab_manager
+            .active_race_viewer()
+            .and_then(|viewer| viewer.selected_standing())
+        else {
+            return;
+        };
+        if standing.archived {
+            self.state.set_timed_footer_message(
+                "Contender workspace is archived".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        }
+        let workspace_id = standing.entry.workspace_id;
+        self.open_workspace(workspace_id);
+    }
+
+    /// Ask before keeping the selected contender and archiving the others
+    pub(super) fn prompt_pick_race_winner(&mut self) {
+        let Some(viewer) = self.state.tab_manager.active_race_viewer() else {
+            return;
+        };
+        if viewer.race.winner_workspace_id.is_some() {
+            self.state.set_timed_footer_message(
+                "This race already has a winner".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        }
+        let Some(standing) = viewer.selected_standing() else {
+            return;
+        };
+        let race_id = viewer.race.id;
+        let workspace_id = standing.entry.workspace_id;
+        let contender = match standing.entry.model.as_deref() {
+            Some(model) => format!("{} · {}", standing.entry.agent_type.as_str(), model),
+            None => standing.entry.agent_type.as_str().to_string(),
+        };
+        let losers: Vec<String> = viewer
+            .standings()
+            .iter()
+            .filter(|other| other.entry.workspace_id != workspace_id && !other.archived)
+            .map(|other| other.workspace_name.clone())
+            .collect();
+        let mut details = vec![format!(
+            "Keeps {} ({})",
+            standing.workspace_name, standing.branch
+        )];
+        if !losers.is_empty() {
+            details.push(format!("Archives {}", losers.join(", ")));
+        }
+
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Pick Race Winner",
+            format!("Keep {} and archive the other contenders?", contender),
+            details,
+            ConfirmationType::Danger,
+            "Pick Winner",
+            Some(ConfirmationContext::PickRaceWinner {
+                race_id,
+                workspace_id,
+            }),
+        );
+        self.state.input_mode = InputMode::Confirming;
+    }
+
+    /// Record the winner once the confirmation dialog is accepted and archive the others
+    pub(super) fn pick_race_winner(&mut self, race_id: Uuid, workspace_id: Uuid) -> Vec<Effect> {
+        let result = self
+            .core
+            .race_store()
+            .ok_or_else(|| "Race database unavailable".to_string())
+            .and_then(|store| {
+                let race = RaceService::get(store, race_id).map_err(|e| e.to_string())?;
+                RaceService::pick_winner(store, &race, workspace_id).map_err(|e| e.to_string())
+            });
+        let losers = match result {
+            Ok(losers) => losers,
+            Err(e) => {
+                self.show_error("Unable to Pick Winner", &e);
+                return Vec::new();
+            }
+        };
+
+        let archived: Vec<Uuid> = self
+            .state
+            .tab_manager
+            .active_race_viewer()
+            .map(|viewer| {
+                viewer
+                    .standings()
+                    .iter()
+                    .filter(|standing| standing.archived)
+                    .map(|standing| standing.entry.workspace_id)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let effects: Vec<Effect> = losers
+            .into_iter()
+            .filter(|id| !archived.contains(id))
+            .map(|id| self.execute_archive_workspace(id, false))
+            .collect();
+        self.refresh_race_viewers(workspace_id);
+        self.state.set_timed_footer_message(
+            "Winner picked; archiving the other contenders".to_string(),
+            Duration::from_secs(5),
+        );
+        effects
+    }
+}
@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::core::services::{PendingReview, ReviewService};
+use crate::ui::action::Action;
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+
+impl App {
+    pub(super) fn handle_review_action(&mut self, action: Action, effects: &mut Vec<Effect>) {
+        if action == Action::OpenReviewComments {
+            if let Some(effect) = self.open_review_comments() {
+                effects.push(effect);
+            }
+            return;
+        }
+        if self.state.input_mode != InputMode::ReviewingComments {
+            return;
+        }
+
+        let dialog = &mut self.state.review_comments_state;
+        match action {
+            Action::ToggleReviewCommentSelected => dialog.toggle_selected(),
+            Action::SelectAllReviewComments => dialog.toggle_all(),
+            Action::ToggleAddressedReviewComments => dialog.toggle_show_addressed(),
+            Action::ToggleReviewCommentAddressed => {
+                let ids = dialog.target_ids();
+                let Some(first) = ids.first() else {
+                    return;
+                };
+                // Marking a mix of comments follows the first one's state
+                let addressed = !dialog
+                    .review
+                    .as_ref()
+                    .is_some_and(|review| review.addressed.contains(first));
+                self.set_review_comments_addressed(&ids, addressed);
+            }
+            Action::ReloadReviewComments => {
+                if dialog.loading {
+                    return;
+                }
+                if let (Some(workspace_id), Some(working_dir)) =
+                    (dialog.workspace_id, dialog.working_dir.clone())
+                {
+                    dialog.start_reload();
+                    effects.push(Effect::LoadReviewComments {
+                        workspace_id,
+                        working_dir,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the review panel for the active workspace and fetch its comments
+    fn open_review_comments(&mut self) -> Option<Effect> {
+        let session = self.state.tab_manager.active_session();
+        let (Some(workspace_id), Some(working_dir)) = (
+            session.and_then(|s| s.workspace_id),
+            session.and_then(|s| s.working_dir.clone()),
+        ) else {
+            self.state.set_timed_footer_message(
+                "Open a workspace to see its review comments".to_string(),
+                Duration::from_secs(3),
+            );
+            return None;
+        };
+
+        self.state.close_overlays();
+        self.state
+            .review_comments_state
+            .show(workspace_id, working_dir.clone());
+        self.state.input_mode = InputMode::ReviewingComments;
+
+        Some(Effect::LoadReviewComments {
+            workspace_id,
+            working_dir,
+        })
+    }
+
+    pub(super) fn handle_review_comments_loaded(
+        &mut self,
+        workspace_id: Uuid,
+        result: Result<PendingReview, String>,
+    ) {
+        let dialog = &mut self.state.review_comments_state;
+        if !dialog.is_visible() || dialog.workspace_id != Some(workspace_id) {
+            return;
+        }
+        dialog.set_loaded(result);
+    }
+
+    /// Send the selected comments (or the highlighted one) to the
+    /// workspace's agent and mark them addressed
+    pub(super) fn send_review_comments(&mut self) -> anyhow::Result<Vec<Effect>> {
+        let dialog = &self.state.review_comments_state;
+        let (Some(workspace_id), Some(review)) = (dialog.workspace_id, dialog.review.as_ref())
+        else {
+            return Ok(Vec::new());
+        };
+        let ids = dialog.target_ids();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let prompt = match ReviewService::build_prompt(review, &ids) {
+            Ok(prompt) => prompt,
+            Err(e) => {
+                self.state.review_comments_state.error = Some(e.to_string());
+                return Ok(Vec::new());
+            }
+        };
+
+        let Some(tab_index) = self.find_tab_for_workspace(workspace_id) else {
+            self.state.review_comments_state.error =
+                Some("No open session for this workspace".to_string());
+            return Ok(Vec::new());
+        };
+
+        self.set_review_comments_addressed(&ids, true);
+        self.state.review_comments_state.hide();
+        self.state.input_mode = InputMode::Normal;
+
+        let count = ids.len();
+        let noun = if count == 1 { "comment" } else { "comments" };
+        let (effects, queued) = self.submit_or_queue_prompt(tab_index, prompt)?;
+        let verb = if queued { "Queued" } else { "Sent" };
+        let target = if queued { "for" } else { "to" };
+        self.state.set_timed_footer_message(
+            format!("{} {} review {} {} the agent", verb, count, noun, target),
+            Duration::from_secs(3),
+        );
+        Ok(effects)
+    }
+
+    fn set_review_comments_addressed(&mut self, ids: &[String], addressed: bool) {
+        let Some(workspace_id) = self.state.review_comments_state.workspace_id else {
+            return;
+        };
+        if let Err(e) = ReviewService::set_addressed(
+            self.core.review_comment_store(),
+            workspace_id,
+            ids,
+            addressed,
+        ) {
+            self.state.review_comments_state.error = Some(e.to_string());
+            return;
+        }
+        self.state
+            .review_comments_state
+            .set_addressed(ids, addressed);
+    }
+}
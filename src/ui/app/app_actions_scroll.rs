@@ -1,7 +1,7 @@
 use crate::ui::action::Action;
 use crate::ui::app::App;
 use crate::ui::components::ChatView;
-use crate::ui::events::InputMode;
+use crate::ui::events::{InputMode, ViewMode};
 
 impl App {
     pub(super) fn handle_scroll_action(&mut self, action: Action) {
@@ -10,8 +10,35 @@ impl App {
             self.handle_file_viewer_scroll(&action);
             return;
         }
+        if self.state.tab_manager.active_is_diff() {
+            self.handle_diff_viewer_scroll(&action);
+            return;
+        }
+        if self.state.tab_manager.active_is_race() {
+            self.handle_race_viewer_scroll(&action);
+            return;
+        }
 
         match action {
+            Action::FindInChat => {
+                if self.state.view_mode != ViewMode::Chat {
+                    return;
+                }
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.chat_view.open_find();
+                    self.state.input_mode = InputMode::FindingInChat;
+                }
+            }
+            Action::FindNext => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.chat_view.find_next();
+                }
+            }
+            Action::FindPrev => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.chat_view.find_prev();
+                }
+            }
             Action::ScrollUp(n) => {
                 if self.state.input_mode == InputMode::ShowingHelp {
                     self.state.help_dialog_state.scroll_up(n as usize);
@@ -168,4 +195,31 @@ impl App {
             _ => {}
         }
     }
+
+    /// Handle scroll actions for diff viewer
+    fn handle_diff_viewer_scroll(&mut self, action: &Action) {
+        let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() else {
+            return;
+        };
+        match action {
+            Action::ScrollUp(n) => viewer.scroll_up(*n as usize),
+            Action::ScrollDown(n) => viewer.scroll_down(*n as usize),
+            Action::ScrollPageUp => viewer.page_up(),
+            Action::ScrollPageDown => viewer.page_down(),
+            Action::ScrollToTop => viewer.scroll_to_top(),
+            Action::ScrollToBottom => viewer.scroll_to_bottom(),
+            _ => {}
+        }
+    }
+
+    fn handle_race_viewer_scroll(&mut self, action: &Action) {
+        let Some(viewer) = self.state.tab_manager.active_race_viewer_mut() else {
+            return;
+        };
+        match action {
+            Action::ScrollUp(_) => viewer.select_prev(),
+            Action::ScrollDown(_) => viewer.select_next(),
+            _ => {}
+        }
+    }
 }
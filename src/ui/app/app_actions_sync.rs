@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::core::services::SyncService;
+use crate::git::{SyncOutcome, SyncStrategy};
+use crate::ui::action::Action;
+use crate::ui::app::App;
+use crate::ui::components::{ConfirmationContext, ConfirmationType};
+use crate::ui::effect::{Effect, SyncStep};
+use crate::ui::events::InputMode;
+
+/// Conflicted files listed in the confirmation dialog
+const MAX_LISTED_CONFLICTS: usize = 8;
+
+impl App {
+    /// Start, continue or abort syncing the active workspace with its base
+    pub(super) fn handle_sync_action(&mut self, action: Action) -> Option<Effect> {
+        let session = self.state.tab_manager.active_session();
+        let (Some(workspace_id), Some(working_dir)) = (
+            session.and_then(|s| s.workspace_id),
+            session.and_then(|s| s.working_dir.clone()),
+        ) else {
+            self.state.set_timed_footer_message(
+                "Open a workspace to sync it".to_string(),
+                Duration::from_secs(3),
+            );
+            return None;
+        };
+        if self.state.busy_workspaces.contains(&workspace_id) {
+            self.state
+                .set_timed_footer_message("Workspace is busy".to_string(), Duration::from_secs(3));
+            return None;
+        }
+
+        let step = match action {
+            Action::SyncWithBase => {
+                let strategy = match SyncService::strategy(&self.core, workspace_id) {
+                    Ok(strategy) => strategy,
+                    Err(e) => {
+                        self.show_error("Unable to Sync", &e.to_string());
+                        return None;
+                    }
+                };
+                SyncStep::Start(strategy)
+            }
+            Action::ContinueSync => SyncStep::Continue,
+            Action::AbortSync => SyncStep::Abort,
+            _ => return None,
+        };
+
+        self.mark_workspace_busy(workspace_id);
+        Some(Effect::SyncWorkspace {
+            workspace_id,
+            working_dir,
+            step,
+        })
+    }
+
+    pub(super) fn handle_workspace_synced(
+        &mut self,
+        workspace_id: Uuid,
+        result: Result<Option<SyncOutcome>, String>,
+    ) {
+        self.clear_workspace_busy(workspace_id);
+        if let Some(ref tracker) = self.git_tracker {
+            tracker.refresh_now(workspace_id);
+        }
+
+        let outcome = match result {
+            Ok(Some(outcome)) => outcome,
+            Ok(None) => {
+                self.state.set_timed_footer_message(
+                    "Sync aborted; the branch is back where it was".to_string(),
+                    Duration::from_secs(3),
+                );
+                return;
+            }
+            Err(e) => {
+                self.show_error("Sync Failed", &e);
+                return;
+            }
+        };
+
+        let conflicts = match outcome {
+            SyncOutcome::UpToDate { base } => {
+                self.state.set_timed_footer_message(
+                    format!("Already up to date with {}", base),
+                    Duration::from_secs(3),
+                );
+                return;
+            }
+            SyncOutcome::Synced { base, strategy } => {
+                let message = match strategy {
+                    SyncStrategy::Merge => format!("Merged {}", base),
+                    SyncStrategy::Rebase => format!("Rebased onto {}", base),
+                };
+                self.state
+                    .set_timed_footer_message(message, Duration::from_secs(3));
+                return;
+            }
+            SyncOutcome::Conflicts(conflicts) => conflicts,
+        };
+
+        let verb = match conflicts.strategy {
+            SyncStrategy::Merge => "Merging",
+            SyncStrategy::Rebase => "Rebasing onto",
+        };
+        let count = conflicts.files.len();
+        let mut files: Vec<String> = conflicts
+            .files
+            .iter()
+            .take(MAX_LISTED_CONFLICTS)
+            .map(|file| file.path.clone())
+            .collect();
+        if count > MAX_LISTED_CONFLICTS {
+            files.push(format!("...and {} more", count - MAX_LISTED_CONFLICTS));
+        }
+        let prompt = SyncService::conflict_prompt(&conflicts);
+
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Sync Conflicts",
+            format!(
+                "{} {} stopped on conflicts in {} file{}. Ask the agent to resolve them?",
+                verb,
+                conflicts.base,
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            files,
+            ConfirmationType::Warning,
+            "Resolve with agent",
+            Some(ConfirmationContext::ResolveSyncConflicts {
+                workspace_id,
+                prompt,
+            }),
+        );
+        self.state.input_mode = InputMode::Confirming;
+    }
+
+    /// Send a stopped sync's conflicts to the workspace's agent
+    pub(super) fn resolve_sync_conflicts(
+        &mut self,
+        workspace_id: Uuid,
+        prompt: String,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.find_tab_for_workspace(workspace_id) else {
+            self.show_error(
+                "Unable to Resolve Conflicts",
+                "No open session for this workspace",
+            );
+            return Ok(Vec::new());
+        };
+        let (effects, queued) = self.submit_or_queue_prompt(tab_index, prompt)?;
+        let message = if queued {
+            "Queued the conflicts for the agent; run :sync_continue once resolved"
+        } else {
+            "Sent the conflicts to the agent; run :sync_continue once resolved"
+        };
+        self.state
+            .set_timed_footer_message(message.to_string(), Duration::from_secs(5));
+        Ok(effects)
+    }
+}
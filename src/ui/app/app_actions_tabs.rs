@@ -1,8 +1,14 @@
 use std::path::PathBuf;
 use std::time::Duration;
 
+use uuid::Uuid;
+
+use crate::core::touched_paths;
+use crate::git::HunkAction;
 use crate::ui::action::Action;
 use crate::ui::app::App;
+use crate::ui::components::{ConfirmationContext, ConfirmationType};
+use crate::ui::diff_viewer::DiffViewerSession;
 use crate::ui::effect::Effect;
 use crate::ui::events::InputMode;
 
@@ -28,6 +34,171 @@ impl App {
         }
     }
 
+    /// Open the diff tab for the active session's workspace
+    pub(super) fn handle_show_diff(&mut self, effects: &mut Vec<Effect>) {
+        if self.state.tab_manager.active_is_diff() {
+            self.reload_diff_viewer();
+            return;
+        }
+
+        let Some((session_id, workspace_id, working_dir, workspace_name)) =
+            self.state.tab_manager.active_session().and_then(|session| {
+                session.working_dir.clone().map(|dir| {
+                    (
+                        session.id,
+                        session.workspace_id,
+                        dir,
+                        session.workspace_name.clone(),
+                    )
+                })
+            })
+        else {
+            self.state.set_timed_footer_message(
+                "No workspace to diff".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        };
+
+        let viewer = DiffViewerSession::new(workspace_id, working_dir, workspace_name);
+        if self.state.tab_manager.open_diff(viewer).is_some() {
+            let paths = self.last_turn_paths(session_id);
+            if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                viewer.review_turn(session_id, paths);
+            }
+            self.state.input_mode = InputMode::Normal;
+            self.state.sidebar_state.set_focused(false);
+            effects.push(Effect::SaveSessionState);
+        } else {
+            self.state.set_timed_footer_message(
+                "Maximum tabs reached".to_string(),
+                Duration::from_secs(3),
+            );
+        }
+    }
+
+    /// Files touched during a session's last turn, from the session journal
+    fn last_turn_paths(&self, session_id: Uuid) -> Vec<String> {
+        // Make sure the turn's events have been written
+        if let Some(journal) = self.core.session_journal() {
+            journal.flush();
+        }
+        let Some(dao) = self.session_event_dao() else {
+            return Vec::new();
+        };
+        match dao.get_last_turn(session_id) {
+            Ok(events) => touched_paths(&events),
+            Err(e) => {
+                tracing::warn!(error = %e, %session_id, "Failed to load last turn");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Recompute the active diff tab, refreshing the last turn's files
+    pub(super) fn reload_diff_viewer(&mut self) {
+        let session_id = self
+            .state
+            .tab_manager
+            .active_diff_viewer()
+            .and_then(|viewer| viewer.session_id);
+        let paths = session_id.map(|id| self.last_turn_paths(id));
+        if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+            match paths {
+                Some(paths) => viewer.update_turn_paths(paths),
+                None => viewer.reload(),
+            }
+        }
+    }
+
+    /// Stage the hunk under the cursor in the active diff tab
+    pub(super) fn stage_diff_hunk(&mut self) {
+        let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() else {
+            return;
+        };
+        match viewer.apply_current_hunk(HunkAction::Stage) {
+            Ok(()) => self
+                .state
+                .set_timed_footer_message("Hunk staged".to_string(), Duration::from_secs(3)),
+            Err(e) => self.show_error("Unable to Stage Hunk", &e),
+        }
+    }
+
+    /// Ask before discarding the hunk under the cursor in the active diff tab
+    pub(super) fn prompt_revert_diff_hunk(&mut self) {
+        let Some(viewer) = self.state.tab_manager.active_diff_viewer() else {
+            return;
+        };
+        let (Some(file), Some(hunk_index)) = (viewer.selected_file(), viewer.current_hunk()) else {
+            self.state
+                .set_timed_footer_message("No hunk to revert".to_string(), Duration::from_secs(3));
+            return;
+        };
+        let viewer_id = viewer.id;
+        let path = file.path.clone();
+        let header = file.hunks[hunk_index].header();
+
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Revert Hunk",
+            format!("Discard this change to {}?", path),
+            vec![
+                header.clone(),
+                "The change cannot be recovered.".to_string(),
+            ],
+            ConfirmationType::Danger,
+            "Revert",
+            Some(ConfirmationContext::RevertHunk {
+                viewer_id,
+                path,
+                hunk_index,
+                header,
+            }),
+        );
+        self.state.input_mode = InputMode::Confirming;
+    }
+
+    /// Revert a hunk once the confirmation dialog is accepted.
+    ///
+    /// Nothing happens if the diff changed underneath the dialog.
+    pub(super) fn revert_diff_hunk(
+        &mut self,
+        viewer_id: Uuid,
+        path: &str,
+        hunk_index: usize,
+        header: &str,
+    ) {
+        let Some(viewer) = self
+            .state
+            .tab_manager
+            .active_diff_viewer_mut()
+            .filter(|viewer| viewer.id == viewer_id)
+        else {
+            return;
+        };
+        let unchanged = viewer.selected_file().is_some_and(|file| {
+            file.path == path
+                && file
+                    .hunks
+                    .get(hunk_index)
+                    .is_some_and(|hunk| hunk.header() == header)
+        }) && viewer.current_hunk() == Some(hunk_index);
+        if !unchanged {
+            viewer.reload();
+            self.state.set_timed_footer_message(
+                "Diff changed; hunk not reverted".to_string(),
+                Duration::from_secs(5),
+            );
+            return;
+        }
+        match viewer.apply_current_hunk(HunkAction::Revert) {
+            Ok(()) => self
+                .state
+                .set_timed_footer_message("Hunk reverted".to_string(), Duration::from_secs(3)),
+            Err(e) => self.show_error("Unable to Revert Hunk", &e),
+        }
+    }
+
     pub(super) fn handle_tab_action(&mut self, action: Action, effects: &mut Vec<Effect>) {
         match action {
             Action::CloseTab => {
@@ -0,0 +1,229 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::agent::{AgentInput, MessageDisplay};
+use crate::ui::app::{send_app_event, App};
+use crate::ui::events::{AppEvent, TitleGeneratedResult};
+use crate::web::{DaemonClient, DaemonEvent, ImageAttachment, ServerMessage};
+
+impl App {
+    /// Run agents in a `conduit serve` daemon instead of this process.
+    ///
+    /// Open sessions whose agent is already running there (e.g. started from
+    /// the browser) are followed right away.
+    pub fn attach_daemon(
+        &mut self,
+        client: DaemonClient,
+        mut incoming: mpsc::UnboundedReceiver<DaemonEvent>,
+    ) {
+        let event_tx = self.event_tx.clone();
+        let bridge = client.clone();
+        tokio::spawn(async move {
+            while let Some(daemon_event) = incoming.recv().await {
+                let message = match daemon_event {
+                    DaemonEvent::Message(message) => message,
+                    DaemonEvent::Disconnected => {
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::DaemonDisconnected,
+                            "daemon_disconnected",
+                        );
+                        continue;
+                    }
+                    DaemonEvent::Reconnected => {
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::DaemonReconnected,
+                            "daemon_reconnected",
+                        );
+                        continue;
+                    }
+                };
+                let event = match message {
+                    ServerMessage::SessionStarted { session_id, .. } => {
+                        AppEvent::DaemonSessionAttached {
+                            session_id,
+                            input_tx: bridge.input_sender(session_id),
+                            missed_events: false,
+                        }
+                    }
+                    ServerMessage::Subscribed {
+                        session_id,
+                        resync_required,
+                        ..
+                    } => AppEvent::DaemonSessionAttached {
+                        session_id,
+                        input_tx: bridge.input_sender(session_id),
+                        missed_events: resync_required,
+                    },
+                    ServerMessage::AgentEvent {
+                        session_id, event, ..
+                    } => AppEvent::Agent { session_id, event },
+                    ServerMessage::SessionEnded {
+                        session_id, error, ..
+                    } => {
+                        if let Some(error) = error {
+                            send_app_event(
+                                &event_tx,
+                                AppEvent::AgentStartFailed { session_id, error },
+                                "daemon_session_error",
+                            );
+                        }
+                        AppEvent::AgentStreamEnded { session_id }
+                    }
+                    ServerMessage::SessionMetadata {
+                        session_id,
+                        title: Some(title),
+                        workspace_id,
+                        workspace_branch,
+                    } => AppEvent::TitleGenerated {
+                        session_id,
+                        result: Ok(TitleGeneratedResult {
+                            title,
+                            new_branch: workspace_branch,
+                            workspace_id,
+                            tool_used: None,
+                            used_fallback: false,
+                        }),
+                    },
+                    ServerMessage::Error {
+                        session_id: Some(session_id),
+                        message,
+                    } => AppEvent::AgentStartFailed {
+                        session_id,
+                        error: message,
+                    },
+                    ServerMessage::Error {
+                        session_id: None,
+                        message,
+                    } => {
+                        tracing::warn!(error = %message, "Daemon error");
+                        continue;
+                    }
+                    _ => continue,
+                };
+                if !send_app_event(&event_tx, event, "daemon") {
+                    return;
+                }
+            }
+        });
+
+        for session in self.state.tab_manager.sessions() {
+            client.watch(session.id);
+        }
+        self.daemon = Some(client);
+    }
+
+    /// Save the session so the daemon can load it, then send the prompt
+    pub(super) async fn send_daemon_prompt(
+        &mut self,
+        session_id: Uuid,
+        prompt: String,
+        working_dir: PathBuf,
+        model: Option<String>,
+        hidden: bool,
+        images: Vec<PathBuf>,
+    ) {
+        let snapshot = self.snapshot_session_state();
+        let session_tab_dao = self.session_tab_dao_clone();
+        let app_state_dao = self.app_state_dao_clone();
+        let images = tokio::task::spawn_blocking(move || {
+            App::persist_session_state(snapshot, session_tab_dao, app_state_dao);
+            images
+                .iter()
+                .filter_map(|path| match App::encode_image_to_base64(path) {
+                    Ok((data, media_type)) => Some(ImageAttachment { data, media_type }),
+                    Err(e) => {
+                        tracing::warn!(path = %path.display(), error = %e, "Failed to encode image");
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
+
+        // While reconnecting the daemon's agent may still be running, so the
+        // prompt is refused rather than run in a second agent here
+        let Some(daemon) = self.daemon.as_ref().filter(|daemon| daemon.is_connected()) else {
+            send_app_event(
+                &self.event_tx,
+                AppEvent::AgentStartFailed {
+                    session_id,
+                    error: "Not connected to the Conduit daemon; reconnecting, try again shortly"
+                        .to_string(),
+                },
+                "daemon_prompt",
+            );
+            return;
+        };
+        daemon.send_prompt(session_id, prompt, &working_dir, model, hidden, images);
+    }
+
+    pub(super) fn handle_daemon_session_attached(
+        &mut self,
+        session_id: Uuid,
+        input_tx: mpsc::Sender<AgentInput>,
+        missed_events: bool,
+    ) {
+        if let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) {
+            session.agent_input_tx = Some(input_tx);
+            if missed_events {
+                let display = MessageDisplay::System {
+                    content: "Some agent output was missed while disconnected from the Conduit daemon; reopen the session to see all of it."
+                        .to_string(),
+                };
+                session.chat_view.push(display.to_chat_message());
+            }
+        }
+    }
+
+    /// Keep sessions in daemon mode while the client reconnects; their agents
+    /// may still be running there, so none are started in this terminal
+    pub(super) fn handle_daemon_disconnected(&mut self) {
+        for session in self.state.tab_manager.sessions_mut() {
+            if session.is_processing {
+                let display = MessageDisplay::System {
+                    content: "Lost connection to the Conduit daemon; the agent keeps running there and its output will follow once reconnected."
+                        .to_string(),
+                };
+                session.chat_view.push(display.to_chat_message());
+            }
+        }
+        self.state.set_timed_footer_message(
+            "Disconnected from the Conduit daemon; reconnecting…".to_string(),
+            Duration::from_secs(10),
+        );
+    }
+
+    pub(super) fn handle_daemon_reconnected(&mut self) {
+        self.state.set_timed_footer_message(
+            "Reconnected to the Conduit daemon".to_string(),
+            Duration::from_secs(5),
+        );
+    }
+
+    /// Text of a user message given as Claude stream-json input
+    pub(super) fn jsonl_prompt_text(payload: &str) -> String {
+        payload
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter_map(|value| {
+                let content = value.get("message")?.get("content")?.clone();
+                Some(match content {
+                    serde_json::Value::String(text) => text,
+                    serde_json::Value::Array(blocks) => blocks
+                        .iter()
+                        .filter_map(|block| block.get("text")?.as_str().map(str::to_string))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    _ => String::new(),
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -32,7 +32,14 @@ impl App {
                 terminal.autoresize()?;
                 Ok(Vec::new())
             }
-            Event::FocusGained | Event::FocusLost => Ok(Vec::new()),
+            Event::FocusGained => {
+                self.state.terminal_focused = Some(true);
+                Ok(Vec::new())
+            }
+            Event::FocusLost => {
+                self.state.terminal_focused = Some(false);
+                Ok(Vec::new())
+            }
         }
     }
 
@@ -55,7 +62,7 @@ impl App {
             return Ok(effects);
         }
 
-        // Handle inline prompt input (AskUserQuestion, ExitPlanMode)
+        // Handle inline prompt input (AskUserQuestion, ExitPlanMode, tool permissions)
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             if let Some(ref mut prompt) = session.inline_prompt {
                 use crate::ui::components::{PromptAction, PromptResponse};
@@ -68,8 +75,8 @@ impl App {
                         let pending_request_id = session.pending_tool_permissions.remove(&tool_id);
                         let agent_type = session.agent_type;
 
-                        // Clear the inline prompt
-                        session.inline_prompt = None;
+                        // Clear the inline prompt, showing any queued permission prompt
+                        session.inline_prompt = session.queued_inline_prompts.pop_front();
 
                         // Handle the response - format as natural language for the model
                         let effects = if let (AgentType::Claude, true, Some(request_id)) = (
@@ -217,7 +224,7 @@ impl App {
                         let tool_id = prompt.tool_id.clone();
                         let pending_request_id = session.pending_tool_permissions.remove(&tool_id);
                         let agent_type = session.agent_type;
-                        session.inline_prompt = None;
+                        session.inline_prompt = session.queued_inline_prompts.pop_front();
                         // Send cancellation as clear message
                         let effects = if let (AgentType::Claude, true, Some(request_id)) = (
                             agent_type,
@@ -251,6 +258,11 @@ impl App {
                         };
                         return Ok(effects);
                     }
+                    PromptAction::AnswerPermission(reply) => {
+                        let prompt = prompt.clone();
+                        session.inline_prompt = session.queued_inline_prompts.pop_front();
+                        return Ok(self.answer_tool_permission(prompt, reply));
+                    }
                     PromptAction::Consumed => {
                         // Key was handled but no action yet
                         return Ok(Vec::new());
@@ -420,7 +432,7 @@ impl App {
         }
 
         // Get the current context from input mode and view mode
-        let context = KeyContext::from_input_mode(self.state.input_mode, self.state.view_mode);
+        let context = self.key_context();
 
         // Text input (typing characters) handled specially
         if self.should_handle_as_text_input(&key, context) {
@@ -439,6 +451,23 @@ impl App {
         Ok(Vec::new())
     }
 
+    /// Key context for the current input mode, view mode and active tab
+    pub(super) fn key_context(&self) -> KeyContext {
+        let context = KeyContext::from_input_mode(self.state.input_mode, self.state.view_mode);
+        if !matches!(
+            context,
+            KeyContext::Chat | KeyContext::Scrolling | KeyContext::RawEvents
+        ) {
+            context
+        } else if self.state.tab_manager.active_is_diff() {
+            KeyContext::DiffViewer
+        } else if self.state.tab_manager.active_is_race() {
+            KeyContext::RaceViewer
+        } else {
+            context
+        }
+    }
+
     /// Check if a key event should be handled as text input
     /// Returns true if the key is a printable character without Control/Alt modifiers
     /// and we're in a text-input context
@@ -471,6 +500,7 @@ impl App {
                 | KeyContext::CommandPalette
                 | KeyContext::ThemePicker
                 | KeyContext::ModelSelector
+                | KeyContext::CommitDialog
         )
     }
 
@@ -512,6 +542,12 @@ impl App {
             InputMode::Command => {
                 self.state.command_buffer.push(c);
             }
+            InputMode::FindingInChat => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.chat_view.open_find();
+                    session.chat_view.push_find_char(c);
+                }
+            }
             InputMode::ShowingHelp => {
                 self.state.help_dialog_state.insert_char(c);
             }
@@ -527,6 +563,10 @@ impl App {
             InputMode::ImportingSession => {
                 self.state.session_import_state.insert_char(c);
             }
+            InputMode::SearchingSessions => {
+                self.state.session_search_state.insert_char(c);
+                self.run_session_search();
+            }
             InputMode::CommandPalette => {
                 self.state.command_palette_state.insert_char(c);
             }
@@ -536,6 +576,9 @@ impl App {
             InputMode::MissingTool => {
                 self.state.missing_tool_dialog_state.insert_char(c);
             }
+            InputMode::Committing => {
+                self.state.commit_dialog_state.message.insert_char(c);
+            }
             InputMode::SelectingTheme => {
                 self.state.theme_picker_state.insert_char(c);
             }
@@ -573,6 +616,14 @@ impl App {
                 let sanitized = pasted.replace('\n', " ");
                 self.state.command_buffer.push_str(&sanitized);
             }
+            InputMode::FindingInChat => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.chat_view.open_find();
+                    for ch in pasted.replace('\n', " ").chars() {
+                        session.chat_view.push_find_char(ch);
+                    }
+                }
+            }
             InputMode::ShowingHelp => {
                 let sanitized = pasted.replace('\n', " ");
                 for ch in sanitized.chars() {
@@ -603,6 +654,13 @@ impl App {
                     self.state.session_import_state.insert_char(ch);
                 }
             }
+            InputMode::SearchingSessions => {
+                let sanitized = pasted.replace('\n', " ");
+                for ch in sanitized.chars() {
+                    self.state.session_search_state.insert_char(ch);
+                }
+                self.run_session_search();
+            }
             InputMode::CommandPalette => {
                 let sanitized = pasted.replace('\n', " ");
                 for ch in sanitized.chars() {
@@ -629,6 +687,9 @@ impl App {
                 let sanitized = pasted.replace('\n', " ");
                 self.state.model_selector_state.insert_str(&sanitized);
             }
+            InputMode::Committing => {
+                self.state.commit_dialog_state.message.insert_str(&pasted);
+            }
             _ => {}
         }
     }
@@ -655,12 +716,24 @@ impl App {
                     && self.state.session_import_state.is_visible()
                 {
                     self.state.session_import_state.select_prev();
+                } else if self.state.input_mode == InputMode::SearchingSessions
+                    && self.state.session_search_state.is_visible()
+                {
+                    self.state.session_search_state.select_prev();
+                } else if self.state.input_mode == InputMode::ViewingUsageStats
+                    && self.state.usage_stats_state.is_visible()
+                {
+                    self.state.usage_stats_state.scroll_up(3);
                 } else if self.state.input_mode == InputMode::SelectingTheme
                     && self.state.theme_picker_state.is_visible()
                 {
                     self.state.theme_picker_state.select_prev();
                 } else if self.handle_tab_bar_wheel(x, y, true) {
                     return Ok(Vec::new());
+                } else if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.scroll_up(3);
+                } else if let Some(viewer) = self.state.tab_manager.active_race_viewer_mut() {
+                    viewer.select_prev();
                 } else if self.state.view_mode == ViewMode::RawEvents {
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         if session.raw_events_view.is_detail_visible() {
@@ -688,12 +761,24 @@ impl App {
                     && self.state.session_import_state.is_visible()
                 {
                     self.state.session_import_state.select_next();
+                } else if self.state.input_mode == InputMode::SearchingSessions
+                    && self.state.session_search_state.is_visible()
+                {
+                    self.state.session_search_state.select_next();
+                } else if self.state.input_mode == InputMode::ViewingUsageStats
+                    && self.state.usage_stats_state.is_visible()
+                {
+                    self.state.usage_stats_state.scroll_down(3);
                 } else if self.state.input_mode == InputMode::SelectingTheme
                     && self.state.theme_picker_state.is_visible()
                 {
                     self.state.theme_picker_state.select_next();
                 } else if self.handle_tab_bar_wheel(x, y, false) {
                     return Ok(Vec::new());
+                } else if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.scroll_down(3);
+                } else if let Some(viewer) = self.state.tab_manager.active_race_viewer_mut() {
+                    viewer.select_next();
                 } else if self.state.view_mode == ViewMode::RawEvents {
                     let list_height = self.raw_events_list_visible_height();
                     let detail_height = self.raw_events_detail_visible_height();
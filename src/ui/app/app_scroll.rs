@@ -21,6 +21,10 @@ impl App {
                 && self.state.project_picker_state.is_visible())
             && !(self.state.input_mode == InputMode::ImportingSession
                 && self.state.session_import_state.is_visible())
+            && !(self.state.input_mode == InputMode::SearchingSessions
+                && self.state.session_search_state.is_visible())
+            && !(self.state.input_mode == InputMode::ViewingUsageStats
+                && self.state.usage_stats_state.is_visible())
             && !(self.state.input_mode == InputMode::CommandPalette
                 && self.state.command_palette_state.is_visible())
             && !(self.state.input_mode == InputMode::SlashMenu
@@ -81,6 +85,20 @@ impl App {
             for _ in 0..*pending_down {
                 self.state.session_import_state.select_next();
             }
+        } else if self.state.input_mode == InputMode::SearchingSessions
+            && self.state.session_search_state.is_visible()
+        {
+            for _ in 0..*pending_up {
+                self.state.session_search_state.select_prev();
+            }
+            for _ in 0..*pending_down {
+                self.state.session_search_state.select_next();
+            }
+        } else if self.state.input_mode == InputMode::ViewingUsageStats
+            && self.state.usage_stats_state.is_visible()
+        {
+            self.state.usage_stats_state.scroll_up(*pending_up);
+            self.state.usage_stats_state.scroll_down(*pending_down);
         } else if self.state.input_mode == InputMode::CommandPalette
             && self.state.command_palette_state.is_visible()
         {
@@ -7,9 +7,11 @@ use ratatui::layout::Rect;
 use crate::agent::{AgentMode, AgentType};
 use crate::ui::components::{
     AddRepoDialogState, AgentSelectorState, BaseDirDialogState, CommandPaletteState,
-    ConfirmationDialogState, ErrorDialogState, HelpDialogState, KnightRiderSpinner,
-    LogoShineAnimation, MissingToolDialogState, ModelSelectorState, ProjectPickerState,
-    SessionImportPickerState, SidebarData, SidebarState, SlashMenuState, ThemePickerState,
+    CommitDialogState, ConfirmationDialogState, ErrorDialogState, HelpDialogState,
+    KnightRiderSpinner, LogoShineAnimation, MissingToolDialogState, ModelSelectorState,
+    ProjectPickerState, ReviewCommentsDialogState, SessionImportPickerState,
+    SessionSearchDialogState, SidebarData, SidebarState, SlashMenuState, ThemePickerState,
+    UsageStatsDialogState,
 };
 use crate::ui::events::{InputMode, ViewMode};
 use crate::ui::tab_manager::TabManager;
@@ -169,6 +171,10 @@ pub struct AppState {
     pub base_dir_dialog_state: BaseDirDialogState,
     pub project_picker_state: ProjectPickerState,
     pub session_import_state: SessionImportPickerState,
+    pub session_search_state: SessionSearchDialogState,
+    pub usage_stats_state: UsageStatsDialogState,
+    pub commit_dialog_state: CommitDialogState,
+    pub review_comments_state: ReviewCommentsDialogState,
     pub confirmation_dialog_state: ConfirmationDialogState,
     pub error_dialog_state: ErrorDialogState,
     pub help_dialog_state: HelpDialogState,
@@ -187,6 +193,8 @@ pub struct AppState {
     pub raw_events_area: Option<Rect>,
     pub metrics: PerformanceMetrics,
     pub show_metrics: bool,
+    /// Whether the terminal window has focus, once the terminal has reported it
+    pub terminal_focused: Option<bool>,
     pub spinner_frame: usize,
     pub last_sidebar_click: Option<(Instant, usize)>,
     pub last_raw_events_click: Option<(Instant, usize)>,
@@ -220,12 +228,19 @@ pub struct AppState {
     pub busy_footer_message: Option<String>,
     /// Pending branch updates captured while workspaces are busy
     pub pending_branch_updates: HashMap<Uuid, Option<String>>,
+    /// Workspaces whose failing CI checks are being fetched
+    pub fetching_ci_failures: HashSet<Uuid>,
+    /// Automatic CI fix attempts per workspace this run
+    pub ci_auto_fix_attempts: HashMap<Uuid, u32>,
 }
 
 /// Pending fork request data captured before workspace creation
 #[derive(Clone)]
 pub struct PendingForkRequest {
+    /// Agent type of the new session
     pub agent_type: AgentType,
+    /// Agent type of the session being forked (differs for a handoff)
+    pub parent_agent_type: AgentType,
     pub agent_mode: AgentMode,
     pub model: Option<String>,
     pub parent_session_id: Option<String>,
@@ -242,6 +257,7 @@ impl std::fmt::Debug for PendingForkRequest {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PendingForkRequest")
             .field("agent_type", &self.agent_type)
+            .field("parent_agent_type", &self.parent_agent_type)
             .field("agent_mode", &self.agent_mode)
             .field("model", &self.model)
             .field("parent_session_id", &self.parent_session_id)
@@ -290,6 +306,10 @@ impl AppState {
             base_dir_dialog_state: BaseDirDialogState::new(),
             project_picker_state: ProjectPickerState::new(),
             session_import_state: SessionImportPickerState::new(),
+            session_search_state: SessionSearchDialogState::new(),
+            usage_stats_state: UsageStatsDialogState::new(),
+            commit_dialog_state: CommitDialogState::new(),
+            review_comments_state: ReviewCommentsDialogState::new(),
             confirmation_dialog_state: ConfirmationDialogState::new(),
             error_dialog_state: ErrorDialogState::new(),
             help_dialog_state: HelpDialogState::new(),
@@ -308,6 +328,7 @@ impl AppState {
             raw_events_area: None,
             metrics: PerformanceMetrics::new(),
             show_metrics: false,
+            terminal_focused: None,
             spinner_frame: 0,
             last_sidebar_click: None,
             last_raw_events_click: None,
@@ -327,6 +348,8 @@ impl AppState {
             busy_footer_message_active: false,
             busy_footer_message: None,
             pending_branch_updates: HashMap::new(),
+            fetching_ci_failures: HashSet::new(),
+            ci_auto_fix_attempts: HashMap::new(),
         }
     }
 
@@ -335,6 +358,10 @@ impl AppState {
         self.base_dir_dialog_state.hide();
         self.project_picker_state.hide();
         self.session_import_state.hide();
+        self.session_search_state.hide();
+        self.usage_stats_state.hide();
+        self.commit_dialog_state.hide();
+        self.review_comments_state.hide();
         self.model_selector_state.hide();
         self.theme_picker_state.hide(true); // cancelled=true since we're closing all overlays
         self.agent_selector_state.hide();
@@ -358,6 +385,10 @@ impl AppState {
             || self.help_dialog_state.is_visible()
             || self.missing_tool_dialog_state.is_visible()
             || self.session_import_state.is_visible()
+            || self.session_search_state.is_visible()
+            || self.usage_stats_state.is_visible()
+            || self.commit_dialog_state.is_visible()
+            || self.review_comments_state.is_visible()
             || self.command_palette_state.is_visible()
             || self.slash_menu_state.is_visible()
     }
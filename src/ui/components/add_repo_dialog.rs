@@ -10,6 +10,7 @@ use ratatui::{
 use std::path::PathBuf;
 
 use super::{DialogFrame, PathInputState, StatusLine};
+use crate::git::CloneManager;
 
 /// State for the add repository dialog
 #[derive(Debug, Clone)]
@@ -83,7 +84,7 @@ impl AddRepoDialogState {
         self.path.move_end();
     }
 
-    /// Validate the current input path
+    /// Validate the current input path or URL
     pub fn validate(&mut self) {
         let input = self.path.input();
 
@@ -94,6 +95,18 @@ impl AddRepoDialogState {
             return;
         }
 
+        // Remote URLs are cloned on confirm
+        if CloneManager::is_remote_url(input) {
+            self.repo_name = CloneManager::repo_name(input);
+            if self.repo_name.is_some() {
+                self.path.set_valid();
+            } else {
+                self.path
+                    .set_error("Cannot derive a repository name from URL");
+            }
+            return;
+        }
+
         // Expand ~ to home directory
         let expanded_path = self.path.expanded_path();
 
@@ -129,6 +142,11 @@ impl AddRepoDialogState {
         self.path.set_valid();
     }
 
+    /// Whether the input is a remote URL to clone
+    pub fn is_remote(&self) -> bool {
+        CloneManager::is_remote_url(self.path.input())
+    }
+
     /// Get the expanded path
     pub fn expanded_path(&self) -> PathBuf {
         self.path.expanded_path()
@@ -180,8 +198,8 @@ impl AddRepoDialog {
         .split(inner);
 
         // Render label
-        let label =
-            Paragraph::new("Enter local repository path:").style(Style::default().fg(Color::White));
+        let label = Paragraph::new("Enter local repository path or git URL:")
+            .style(Style::default().fg(Color::White));
         label.render(chunks[0], buf);
 
         // Render input field
@@ -206,15 +224,17 @@ impl AddRepoDialog {
             input_inner,
             buf,
             Style::default().fg(Color::White),
-            "~/path/to/repo",
+            "~/path/to/repo or git@host:org/repo.git",
             Style::default().fg(Color::DarkGray),
         );
 
         // Render status/error using StatusLine component
-        let success_msg = format!(
-            "Valid repository: {}",
-            state.repo_name.as_deref().unwrap_or("repository")
-        );
+        let repo_name = state.repo_name.as_deref().unwrap_or("repository");
+        let success_msg = if state.is_remote() {
+            format!("Remote repository: {} (will be cloned)", repo_name)
+        } else {
+            format!("Valid repository: {}", repo_name)
+        };
         let status = StatusLine::from_result(state.error(), state.is_valid(), &success_msg);
         status.render(chunks[3], buf);
     }
@@ -16,6 +16,16 @@ use super::{
     DialogFrame,
 };
 
+/// What the chosen agent will be used for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AgentSelectorPurpose {
+    /// Open a new tab with the agent
+    #[default]
+    NewSession,
+    /// Continue the active session with the agent in a forked workspace
+    Handoff,
+}
+
 /// State for the agent selector dialog
 #[derive(Debug, Clone)]
 pub struct AgentSelectorState {
@@ -23,6 +33,8 @@ pub struct AgentSelectorState {
     pub visible: bool,
     /// Currently selected index
     pub selected: usize,
+    /// What the selection is for
+    pub purpose: AgentSelectorPurpose,
     /// Available agents
     agents: Vec<AgentOption>,
 }
@@ -45,6 +57,7 @@ impl AgentSelectorState {
         Self {
             visible: false,
             selected: 0,
+            purpose: AgentSelectorPurpose::NewSession,
             agents: vec![
                 AgentOption {
                     agent_type: AgentType::Claude,
@@ -115,6 +128,7 @@ impl AgentSelectorState {
         Self {
             visible: false,
             selected: 0,
+            purpose: AgentSelectorPurpose::NewSession,
             agents,
         }
     }
@@ -174,11 +188,13 @@ impl AgentSelectorState {
     pub fn show(&mut self) {
         self.visible = true;
         self.selected = 0;
+        self.purpose = AgentSelectorPurpose::NewSession;
     }
 
     /// Show the dialog and preselect a preferred agent when available
     pub fn show_with_default(&mut self, agent_type: AgentType) {
         self.visible = true;
+        self.purpose = AgentSelectorPurpose::NewSession;
         self.selected = self
             .agents
             .iter()
@@ -186,6 +202,18 @@ impl AgentSelectorState {
             .unwrap_or(0);
     }
 
+    /// Show the dialog to pick the agent a session is handed off to,
+    /// preselecting the first agent other than `current`
+    pub fn show_for_handoff(&mut self, current: AgentType) {
+        self.visible = true;
+        self.purpose = AgentSelectorPurpose::Handoff;
+        self.selected = self
+            .agents
+            .iter()
+            .position(|agent| agent.agent_type != current)
+            .unwrap_or(0);
+    }
+
     /// Hide the dialog
     pub fn hide(&mut self) {
         self.visible = false;
@@ -231,7 +259,11 @@ impl AgentSelector {
         }
 
         // Render dialog frame (instructions on bottom border)
-        let frame = DialogFrame::new("Select Agent", 44, 12).instructions(vec![
+        let (title, prompt) = match state.purpose {
+            AgentSelectorPurpose::NewSession => ("Select Agent", "Choose an agent:"),
+            AgentSelectorPurpose::Handoff => ("Hand Off Session", "Continue this session in:"),
+        };
+        let frame = DialogFrame::new(title, 44, 12).instructions(vec![
             ("↑↓", "select"),
             ("Enter", "confirm"),
             ("Esc", "cancel"),
@@ -250,7 +282,7 @@ impl AgentSelector {
         .split(inner);
 
         // Render header
-        let header = Paragraph::new("Choose an agent:").style(Style::default().fg(text_primary()));
+        let header = Paragraph::new(prompt).style(Style::default().fg(text_primary()));
         header.render(chunks[0], buf);
 
         // Render agent options
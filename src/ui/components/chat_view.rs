@@ -12,13 +12,17 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use super::{
     render_minimal_scrollbar,
     theme::{
-        accent_error, accent_primary, accent_success, bg_base, bg_highlight, diff_add, diff_remove,
-        markdown_code_bg, theme_revision, tool_block_bg, tool_command, tool_comment, tool_output,
+        accent_error, accent_primary, accent_success, accent_warning, bg_base, bg_highlight,
+        diff_add, diff_remove, markdown_code_bg, theme_revision, tool_block_bg, tool_command,
+        tool_comment, tool_output,
     },
     ChatMessage, MarkdownRenderer, MessageRole, ScrollbarMetrics, TurnSummary,
 };
 
 mod chat_view_cache;
+mod chat_view_find;
+
+pub use self::chat_view_find::FindStatus;
 
 // =============================================================================
 // Tool Block Builder - Opencode-style tool rendering
@@ -217,6 +221,16 @@ impl UserMessageBlockBuilder {
 }
 
 use self::chat_view_cache::LineCache;
+use self::chat_view_find::ChatFind;
+
+/// Scroll target applied on the next render, once line counts are known
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingScroll {
+    /// Put the first line of this message at the top of the view
+    Message(usize),
+    /// Bring this flat line into view (centered if it is off-screen)
+    Line(usize),
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct SelectionPoint {
@@ -472,6 +486,10 @@ pub struct ChatView {
     messages: Vec<ChatMessage>,
     /// Scroll offset (0 = bottom, increases upward)
     scroll_offset: usize,
+    /// Scroll target to resolve on the next render
+    pending_scroll: Option<PendingScroll>,
+    /// Active find session (query, matches, current match)
+    find: Option<ChatFind>,
     /// Currently streaming messages (in arrival order)
     streaming_messages: Vec<StreamingMessage>,
     /// Cached rendered lines per message
@@ -500,6 +518,8 @@ pub struct ChatView {
     theme_revision: u64,
     /// Extra lines appended in the last render (thinking/queue/prompt + spacing)
     last_render_extra_lines: usize,
+    /// Visible height of the last render
+    last_visible_height: usize,
     /// Currently hovered file path (for underline highlighting)
     hovered_file_path: Option<HoveredFilePath>,
     /// Extra lines from last render (prompts, indicators) for hover detection
@@ -526,6 +546,8 @@ impl ChatView {
         Self {
             messages: Vec::new(),
             scroll_offset: 0,
+            pending_scroll: None,
+            find: None,
             streaming_messages: Vec::new(),
             line_cache: LineCache::default(),
             cache_width: None,
@@ -540,6 +562,7 @@ impl ChatView {
             selection_scroll_lock: None,
             theme_revision: theme_revision(),
             last_render_extra_lines: 0,
+            last_visible_height: 0,
             hovered_file_path: None,
             last_extra_lines: Vec::new(),
             last_extra_lines_start: 0,
@@ -708,6 +731,10 @@ impl ChatView {
         self.messages.clear();
         self.streaming_messages.clear();
         self.scroll_offset = 0;
+        self.pending_scroll = None;
+        if let Some(find) = self.find.as_mut() {
+            find.reset();
+        }
         self.clear_selection();
         self.last_render_extra_lines = 0;
         // Clear all caches
@@ -742,6 +769,11 @@ impl ChatView {
         self.scroll_offset = 0;
     }
 
+    /// Scroll so the message at `index` starts at the top of the view on the next render.
+    pub fn scroll_to_message(&mut self, index: usize) {
+        self.pending_scroll = Some(PendingScroll::Message(index));
+    }
+
     /// Jump to previous user message (returns true if moved).
     pub fn scroll_to_prev_user_message(
         &mut self,
@@ -808,12 +840,23 @@ impl ChatView {
     }
 
     fn user_message_line_indices(&self) -> Vec<usize> {
-        let mut indices = Vec::new();
+        self.message_start_lines()
+            .into_iter()
+            .zip(&self.messages)
+            .filter(|(_, msg)| msg.role == MessageRole::User)
+            .filter_map(|(start, _)| start)
+            .collect()
+    }
+
+    /// First flat line of each message (None when the message renders no lines).
+    fn message_start_lines(&self) -> Vec<Option<usize>> {
+        let mut starts = Vec::with_capacity(self.messages.len());
         let mut flat_index = 0usize;
         let mut last_is_blank = false;
 
-        for (msg_idx, msg) in self.messages.iter().enumerate() {
+        for msg_idx in 0..self.messages.len() {
             let Some(Some(cached)) = self.line_cache.entries.get(msg_idx) else {
+                starts.push(None);
                 continue;
             };
             let mut first_included: Option<usize> = None;
@@ -822,20 +865,16 @@ impl ChatView {
                 if is_blank && last_is_blank {
                     continue;
                 }
-                if msg.role == MessageRole::User && first_included.is_none() {
+                if first_included.is_none() {
                     first_included = Some(flat_index);
                 }
                 flat_index = flat_index.saturating_add(1);
                 last_is_blank = is_blank;
             }
-            if msg.role == MessageRole::User {
-                if let Some(idx) = first_included {
-                    indices.push(idx);
-                }
-            }
+            starts.push(first_included);
         }
 
-        indices
+        starts
     }
 
     pub fn set_scroll_from_top(&mut self, offset_from_top: usize, total: usize, visible: usize) {
@@ -1287,6 +1326,20 @@ impl ChatView {
         for (line, line_index) in visible_lines {
             let mut result_line = line.clone();
 
+            // Highlight find matches (current match stands out)
+            if let Some(idx) = line_index {
+                for (m, is_current) in self.find_matches_on_line(idx) {
+                    result_line =
+                        restyle_line_by_cols(&result_line, m.start_col, m.end_col, |style| {
+                            if is_current {
+                                style.bg(accent_warning()).fg(bg_base())
+                            } else {
+                                style.bg(bg_highlight()).fg(accent_warning())
+                            }
+                        });
+                }
+            }
+
             // Apply selection highlight if applicable
             if let (Some((start, end)), Some(idx)) = (selection, line_index) {
                 if idx >= start.line_index && idx <= end.line_index {
@@ -2299,6 +2352,7 @@ impl ChatView {
         self.ensure_flat_cache();
 
         self.ensure_streaming_cache(content.width);
+        self.refresh_find_matches();
 
         let cached_len = self.flat_cache.len();
         let streaming_len = self
@@ -2332,9 +2386,25 @@ impl ChatView {
         self.last_extra_lines_start = cached_len + streaming_len;
         let total_lines = cached_len + streaming_len + extra_len;
         let visible_height = content.height as usize;
+        self.last_visible_height = visible_height;
 
         // Clamp scroll offset (respect selection lock if active)
         let max_scroll = total_lines.saturating_sub(visible_height);
+        match self.pending_scroll.take() {
+            Some(PendingScroll::Message(index)) => {
+                if let Some(Some(target_line)) = self.message_start_lines().get(index).copied() {
+                    self.scroll_offset = max_scroll.saturating_sub(target_line.min(max_scroll));
+                }
+            }
+            Some(PendingScroll::Line(line)) => {
+                let top = max_scroll.saturating_sub(self.scroll_offset.min(max_scroll));
+                if line < top || line >= top + visible_height {
+                    let centered = line.saturating_sub(visible_height / 2).min(max_scroll);
+                    self.scroll_offset = max_scroll - centered;
+                }
+            }
+            None => {}
+        }
         let scroll_from_top = if let Some(lock) = self.selection_scroll_lock {
             let locked = lock.min(max_scroll);
             self.scroll_offset = max_scroll.saturating_sub(locked);
@@ -2624,6 +2694,21 @@ fn highlight_line_by_cols(line: &Line<'static>, start_col: u16, end_col: u16) ->
 
 /// Apply underline styling to characters in the specified column range (for hover highlighting)
 fn underline_line_by_cols(line: &Line<'static>, start_col: u16, end_col: u16) -> Line<'static> {
+    // Add underline and accent color for hovered file paths
+    restyle_line_by_cols(line, start_col, end_col, |style| {
+        style
+            .add_modifier(Modifier::UNDERLINED)
+            .fg(accent_primary())
+    })
+}
+
+/// Restyle characters in the column range `start_col..end_col` (end exclusive)
+fn restyle_line_by_cols(
+    line: &Line<'static>,
+    start_col: u16,
+    end_col: u16,
+    restyle: impl Fn(Style) -> Style,
+) -> Line<'static> {
     let mut spans: Vec<Span<'static>> = Vec::new();
     let mut buffer = String::new();
     let mut current_style: Option<Style> = None;
@@ -2636,10 +2721,7 @@ fn underline_line_by_cols(line: &Line<'static>, start_col: u16, end_col: u16) ->
             let end = col.saturating_add(w.saturating_sub(1));
             let in_range = end >= start_col && col < end_col;
             let style = if in_range {
-                // Add underline and accent color for hovered file paths
-                base_style
-                    .add_modifier(Modifier::UNDERLINED)
-                    .fg(accent_primary())
+                restyle(base_style)
             } else {
                 base_style
             };
@@ -3120,6 +3202,69 @@ mod tests {
         assert!(!view.messages[0].is_collapsed, "Message should be expanded");
     }
 
+    #[test]
+    fn test_find_counts_matches_and_wraps() {
+        let mut view = ChatView::new();
+        view.push(ChatMessage::user("Where is the Needle?"));
+        view.push(ChatMessage::assistant("The needle is here, needle again."));
+        view.ensure_cache(80);
+
+        view.open_find();
+        for c in "needle".chars() {
+            view.push_find_char(c);
+        }
+        let status = view.find_status().unwrap();
+        assert_eq!(status.total, 3, "matching is case-insensitive");
+        assert!(status.current.is_some());
+
+        let first = status.current.unwrap();
+        view.find_next();
+        view.find_next();
+        view.find_next();
+        assert_eq!(view.find_status().unwrap().current, Some(first));
+
+        view.push_find_char('x');
+        assert_eq!(
+            view.find_status(),
+            Some(FindStatus {
+                current: None,
+                total: 0
+            })
+        );
+        assert!(view.pop_find_char());
+        assert_eq!(view.find_status().unwrap().total, 3);
+    }
+
+    #[test]
+    fn test_find_expands_collapsed_tools_until_closed() {
+        let mut view = ChatView::new();
+        let mut tool_msg = ChatMessage::tool(
+            "Bash",
+            r#"{"command": "ls"}"#,
+            "file1.txt\nsecret_config.toml\nfile3.txt",
+        );
+        tool_msg.is_collapsed = true;
+        view.push(tool_msg);
+        view.ensure_cache(80);
+
+        view.open_find();
+        for c in "secret_config".chars() {
+            view.push_find_char(c);
+        }
+        assert!(
+            !view.messages[0].is_collapsed,
+            "matching tool output expands"
+        );
+        assert_eq!(view.find_status().unwrap().total, 1);
+
+        view.close_find();
+        assert!(
+            view.messages[0].is_collapsed,
+            "closing find restores collapse"
+        );
+        assert!(view.find_status().is_none());
+    }
+
     #[test]
     fn test_tool_message_error_exit_code() {
         let mut view = ChatView::new();
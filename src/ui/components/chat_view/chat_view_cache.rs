@@ -185,6 +185,9 @@ impl ChatView {
         }
         self.flat_cache_width = self.cache_width;
         self.flat_cache_dirty = false;
+        if let Some(find) = self.find.as_mut() {
+            find.dirty = true;
+        }
     }
 }
 
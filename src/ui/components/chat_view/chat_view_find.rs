@@ -0,0 +1,312 @@
+//! Incremental find within the chat transcript.
+
+use ratatui::text::Line;
+use unicode_width::UnicodeWidthChar;
+
+use super::{line_to_flat, ChatView, MessageRole, PendingScroll};
+
+/// A single match of the find query in the flattened line cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct FindMatch {
+    /// Line index in the flat cache
+    pub(super) line_index: usize,
+    /// First display column of the match
+    pub(super) start_col: u16,
+    /// Display column just past the match
+    pub(super) end_col: u16,
+}
+
+/// State of an active find in the chat view
+#[derive(Debug, Clone, Default)]
+pub(super) struct ChatFind {
+    pub(super) query: String,
+    pub(super) matches: Vec<FindMatch>,
+    /// Index into `matches` of the current match
+    pub(super) current: Option<usize>,
+    /// Tool messages expanded because their hidden output matched the query
+    auto_expanded: Vec<usize>,
+    /// Matches need recomputing (query or flat cache changed)
+    pub(super) dirty: bool,
+}
+
+/// Match counter shown in the status bar while finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FindStatus {
+    /// 1-based position of the current match (None when nothing matches)
+    pub current: Option<usize>,
+    pub total: usize,
+}
+
+impl ChatFind {
+    /// Drop matches that refer to messages which no longer exist (keeps the query)
+    pub(super) fn reset(&mut self) {
+        self.matches.clear();
+        self.current = None;
+        self.auto_expanded.clear();
+        self.dirty = true;
+    }
+}
+
+impl ChatView {
+    /// Start a find session (keeps the previous query if one is active)
+    pub fn open_find(&mut self) {
+        if self.find.is_none() {
+            self.find = Some(ChatFind::default());
+        }
+    }
+
+    /// End the find session, collapsing tool output that was expanded to show matches
+    pub fn close_find(&mut self) {
+        let Some(find) = self.find.take() else {
+            return;
+        };
+        for index in find.auto_expanded {
+            if let Some(msg) = self.messages.get_mut(index) {
+                if msg.role == MessageRole::Tool && !msg.is_collapsed {
+                    msg.is_collapsed = true;
+                    if let Some(width) = self.cache_width {
+                        self.invalidate_cache_entry(index);
+                        self.update_cache_entry(index, width);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn is_finding(&self) -> bool {
+        self.find.is_some()
+    }
+
+    pub fn find_query(&self) -> Option<&str> {
+        self.find.as_ref().map(|find| find.query.as_str())
+    }
+
+    /// Append a character to the query and jump to the nearest match
+    pub fn push_find_char(&mut self, c: char) {
+        let Some(find) = self.find.as_mut() else {
+            return;
+        };
+        find.query.push(c);
+        find.dirty = true;
+        self.on_find_query_changed();
+    }
+
+    /// Remove the last query character (returns false if the query was already empty)
+    pub fn pop_find_char(&mut self) -> bool {
+        let Some(find) = self.find.as_mut() else {
+            return false;
+        };
+        if find.query.pop().is_none() {
+            return false;
+        }
+        find.dirty = true;
+        self.on_find_query_changed();
+        true
+    }
+
+    /// Move to the next match (wrapping)
+    pub fn find_next(&mut self) {
+        self.step_find(true);
+    }
+
+    /// Move to the previous match (wrapping)
+    pub fn find_prev(&mut self) {
+        self.step_find(false);
+    }
+
+    /// Current match position for display
+    pub fn find_status(&self) -> Option<FindStatus> {
+        let find = self.find.as_ref()?;
+        if find.query.is_empty() {
+            return None;
+        }
+        Some(FindStatus {
+            current: find.current.map(|index| index + 1),
+            total: find.matches.len(),
+        })
+    }
+
+    fn on_find_query_changed(&mut self) {
+        self.expand_tools_matching_find();
+        let anchor = self.view_top_line();
+        self.refresh_find_matches();
+        if let Some(find) = self.find.as_mut() {
+            find.current = if find.matches.is_empty() {
+                None
+            } else {
+                Some(
+                    find.matches
+                        .iter()
+                        .position(|m| m.line_index >= anchor)
+                        .unwrap_or(0),
+                )
+            };
+        }
+        self.scroll_to_current_match();
+    }
+
+    fn step_find(&mut self, forward: bool) {
+        self.refresh_find_matches();
+        let Some(find) = self.find.as_mut() else {
+            return;
+        };
+        let total = find.matches.len();
+        if total == 0 {
+            return;
+        }
+        find.current = Some(match find.current {
+            Some(current) if forward => (current + 1) % total,
+            Some(current) => (current + total - 1) % total,
+            None if forward => 0,
+            None => total - 1,
+        });
+        self.scroll_to_current_match();
+    }
+
+    fn scroll_to_current_match(&mut self) {
+        let line = self
+            .find
+            .as_ref()
+            .and_then(|find| find.current.and_then(|index| find.matches.get(index)))
+            .map(|m| m.line_index);
+        if let Some(line) = line {
+            self.pending_scroll = Some(PendingScroll::Line(line));
+        }
+    }
+
+    /// Expand collapsed tool messages whose hidden output contains the query
+    fn expand_tools_matching_find(&mut self) {
+        let Some(find) = self.find.as_mut() else {
+            return;
+        };
+        let needle = fold_case(&find.query);
+        if needle.is_empty() {
+            return;
+        }
+
+        let mut expanded = Vec::new();
+        for (index, msg) in self.messages.iter_mut().enumerate() {
+            if msg.role == MessageRole::Tool
+                && msg.is_collapsed
+                && fold_case(&msg.content).contains(&needle)
+            {
+                msg.is_collapsed = false;
+                expanded.push(index);
+            }
+        }
+        find.auto_expanded.extend_from_slice(&expanded);
+
+        if let Some(width) = self.cache_width {
+            for index in expanded {
+                self.invalidate_cache_entry(index);
+                self.update_cache_entry(index, width);
+            }
+        }
+    }
+
+    /// Recompute matches against the flat cache if it changed since the last search
+    pub(super) fn refresh_find_matches(&mut self) {
+        if let Some(width) = self.cache_width {
+            self.ensure_cache(width);
+            self.ensure_flat_cache();
+        }
+        let Some(find) = self.find.as_mut() else {
+            return;
+        };
+        if !find.dirty {
+            return;
+        }
+
+        let previous = find
+            .current
+            .and_then(|index| find.matches.get(index).copied());
+        let needle: Vec<char> = fold_case(&find.query).chars().collect();
+        find.matches = if needle.is_empty() {
+            Vec::new()
+        } else {
+            self.flat_cache
+                .iter()
+                .enumerate()
+                .flat_map(|(line_index, line)| find_in_line(line, line_index, &needle))
+                .collect()
+        };
+        find.dirty = false;
+
+        // Keep the current match stable across cache rebuilds
+        find.current = match previous {
+            Some(prev) if !find.matches.is_empty() => Some(
+                find.matches
+                    .iter()
+                    .position(|m| (m.line_index, m.start_col) >= (prev.line_index, prev.start_col))
+                    .unwrap_or(find.matches.len() - 1),
+            ),
+            _ => find.current.filter(|&index| index < find.matches.len()),
+        };
+    }
+
+    /// Flat line index currently at the top of the viewport (from the last render)
+    fn view_top_line(&self) -> usize {
+        let streaming_len = self
+            .streaming_cache
+            .as_ref()
+            .map(|lines| lines.len())
+            .unwrap_or(0);
+        let total = self.flat_cache.len() + streaming_len + self.last_render_extra_lines;
+        let max_scroll = total.saturating_sub(self.last_visible_height);
+        max_scroll.saturating_sub(self.scroll_offset.min(max_scroll))
+    }
+
+    /// Matches on a rendered line, with the current match flagged
+    pub(super) fn find_matches_on_line(&self, line_index: usize) -> Vec<(FindMatch, bool)> {
+        let Some(find) = self.find.as_ref() else {
+            return Vec::new();
+        };
+        let start = find.matches.partition_point(|m| m.line_index < line_index);
+        find.matches[start..]
+            .iter()
+            .enumerate()
+            .take_while(|(_, m)| m.line_index == line_index)
+            .map(|(offset, m)| (*m, find.current == Some(start + offset)))
+            .collect()
+    }
+}
+
+/// Case-insensitive matching: compare characters by their first lowercase form
+fn fold_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn fold_case(s: &str) -> String {
+    s.chars().map(fold_char).collect()
+}
+
+fn find_in_line(line: &Line<'_>, line_index: usize, needle: &[char]) -> Vec<FindMatch> {
+    let flat = line_to_flat(line);
+    let mut chars: Vec<(char, u16)> = Vec::new();
+    let mut col: u16 = 0;
+    for ch in flat.chars() {
+        chars.push((fold_char(ch), col));
+        col = col.saturating_add(UnicodeWidthChar::width(ch).unwrap_or(0) as u16);
+    }
+
+    let mut matches = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= chars.len() {
+        if chars[i..i + needle.len()]
+            .iter()
+            .zip(needle)
+            .all(|((c, _), n)| c == n)
+        {
+            let end = i + needle.len();
+            matches.push(FindMatch {
+                line_index,
+                start_col: chars[i].1,
+                end_col: chars.get(end).map(|(_, col)| *col).unwrap_or(col),
+            });
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    matches
+}
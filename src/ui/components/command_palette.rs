@@ -108,12 +108,21 @@ impl CommandPaletteState {
             Action::NewProject,
             Action::NewWorkspaceUnderCursor,
             Action::OpenPr,
+            Action::OpenCommitDialog,
+            Action::OpenReviewComments,
+            Action::FixCi,
+            Action::SyncWithBase,
+            Action::ContinueSync,
+            Action::AbortSync,
             Action::ForkSession,
+            Action::HandoffSession,
             Action::InterruptAgent,
             Action::ToggleViewMode,
             Action::ShowModelSelector,
             Action::ToggleMetrics,
+            Action::ToggleQuietMode,
             Action::DumpDebugState,
+            Action::ExportTranscript,
             Action::OpenQueueEditor,
             Action::CloseTab,
             Action::NextTab,
@@ -122,12 +131,18 @@ impl CommandPaletteState {
             Action::ScrollPageDown,
             Action::ScrollToTop,
             Action::ScrollToBottom,
+            Action::FindInChat,
             Action::EnterSidebarMode,
             Action::AddRepository,
             Action::OpenSettings,
             Action::ArchiveOrRemove,
             Action::ToggleAgentMode,
             Action::OpenSessionImport,
+            Action::OpenSessionSearch,
+            Action::ShowUsageStats,
+            Action::ShowDiff,
+            Action::RewindTurn,
+            Action::StartRace,
             Action::ShowHelp,
         ];
 
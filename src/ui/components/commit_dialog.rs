@@ -0,0 +1,380 @@
+//! Commit dialog component
+//!
+//! Lists the files a commit would include and holds an editable commit
+//! message, pre-filled by AI generation, plus an option to push afterwards.
+
+use std::path::PathBuf;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use uuid::Uuid;
+
+use super::{
+    accent_error, accent_primary, accent_success, accent_warning, text_muted, text_primary,
+    DialogFrame, InputBox,
+};
+use crate::git::ChangedFile;
+use crate::ui::action::Action;
+use crate::util::GeneratedCommitMessage;
+
+// ============ Dialog Sizing Constants ============
+/// Dialog width as percentage of screen (0-100)
+const DIALOG_WIDTH_PERCENT: u16 = 70;
+/// Minimum dialog width
+const DIALOG_MIN_WIDTH: u16 = 50;
+/// Maximum dialog width
+const DIALOG_MAX_WIDTH: u16 = 100;
+/// Maximum number of file rows shown before summarizing the rest
+const MAX_FILE_ROWS: usize = 8;
+/// Height of the message editor (including its padding rows)
+const MESSAGE_HEIGHT: u16 = 8;
+
+/// State for the commit dialog
+pub struct CommitDialogState {
+    /// Whether the dialog is visible
+    visible: bool,
+    /// Workspace being committed
+    pub workspace_id: Option<Uuid>,
+    /// Working directory of the workspace
+    pub working_dir: Option<PathBuf>,
+    /// Current branch, if known
+    pub branch: Option<String>,
+    /// Changed files, sorted by path
+    pub files: Vec<ChangedFile>,
+    /// Editable commit message
+    pub message: InputBox,
+    /// Push the branch after committing
+    pub push: bool,
+    /// Whether a message is being generated
+    pub generating: bool,
+    /// Whether the commit is running
+    pub committing: bool,
+    /// Tool that generated the current message
+    pub tool_used: Option<String>,
+    /// Error from generation or the last commit attempt
+    pub error: Option<String>,
+    /// Spinner frame for the generating/committing animation
+    spinner_frame: usize,
+}
+
+impl Default for CommitDialogState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommitDialogState {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            workspace_id: None,
+            working_dir: None,
+            branch: None,
+            files: Vec::new(),
+            message: InputBox::new(),
+            push: false,
+            generating: false,
+            committing: false,
+            tool_used: None,
+            error: None,
+            spinner_frame: 0,
+        }
+    }
+
+    /// Show the dialog for a workspace, waiting for a generated message
+    pub fn show(
+        &mut self,
+        workspace_id: Uuid,
+        working_dir: PathBuf,
+        branch: Option<String>,
+        files: Vec<ChangedFile>,
+    ) {
+        *self = Self {
+            visible: true,
+            workspace_id: Some(workspace_id),
+            working_dir: Some(working_dir),
+            branch,
+            files,
+            generating: true,
+            ..Self::new()
+        };
+    }
+
+    /// Hide the dialog
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Check if dialog is visible
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Advance the spinner animation
+    pub fn tick(&mut self) {
+        if self.generating || self.committing {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        }
+    }
+
+    /// Apply a generated message.
+    ///
+    /// A message the user has already started typing is kept.
+    pub fn set_generated(&mut self, result: Result<GeneratedCommitMessage, String>) {
+        self.generating = false;
+        match result {
+            Ok(generated) => {
+                self.error = None;
+                if self.message.is_empty() {
+                    self.message.set_input(generated.message);
+                    self.tool_used = Some(generated.tool_used);
+                }
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    /// Mark a regeneration as started, discarding the current message
+    pub fn start_regenerate(&mut self) {
+        self.message.clear();
+        self.tool_used = None;
+        self.error = None;
+        self.generating = true;
+    }
+
+    pub fn toggle_push(&mut self) {
+        self.push = !self.push;
+    }
+
+    /// Trimmed commit message
+    pub fn commit_message(&self) -> String {
+        self.message.input().trim().to_string()
+    }
+
+    /// Whether only staged changes will be committed
+    pub fn staged_only(&self) -> bool {
+        self.files.iter().any(ChangedFile::is_staged)
+    }
+
+    /// Apply an input-editing action to the message editor
+    pub fn handle_edit_action(&mut self, action: &Action) {
+        let message = &mut self.message;
+        match action {
+            Action::InsertNewline => message.insert_newline(),
+            Action::Backspace => message.backspace(),
+            Action::Delete => message.delete(),
+            Action::DeleteWordBack => message.delete_word_back(),
+            Action::DeleteToStart => message.delete_to_start(),
+            Action::DeleteToEnd => message.delete_to_end(),
+            Action::MoveCursorLeft => message.move_left(),
+            Action::MoveCursorRight => message.move_right(),
+            Action::MoveCursorStart => message.move_start(),
+            Action::MoveCursorEnd => message.move_end(),
+            Action::MoveWordLeft => message.move_word_left(),
+            Action::MoveWordRight => message.move_word_right(),
+            Action::MoveCursorUp => {
+                message.move_up();
+            }
+            Action::MoveCursorDown => {
+                message.move_down();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Commit dialog widget
+pub struct CommitDialog;
+
+impl Default for CommitDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommitDialog {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render the dialog and return the cursor position in the message editor
+    pub fn render(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut CommitDialogState,
+    ) -> Option<(u16, u16)> {
+        if !state.visible {
+            return None;
+        }
+
+        let file_rows = state.files.len().clamp(1, MAX_FILE_ROWS + 1) as u16;
+        // Branch, separator, files, separator, label, editor, push, spacing + frame
+        let height = (1 + 1 + file_rows + 1 + 1 + MESSAGE_HEIGHT + 1 + 1 + 3)
+            .min(area.height.saturating_sub(2));
+        let width = (area.width * DIALOG_WIDTH_PERCENT / 100)
+            .clamp(DIALOG_MIN_WIDTH, DIALOG_MAX_WIDTH)
+            .min(area.width.saturating_sub(4));
+
+        let frame = DialogFrame::new("Commit Changes", width, height).instructions(vec![
+            ("Enter", "Commit"),
+            ("S-Enter", "Newline"),
+            ("Tab", "Push"),
+            ("C-r", "Regenerate"),
+            ("Esc", "Cancel"),
+        ]);
+        let inner = frame.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1),         // Branch and summary
+            Constraint::Length(1),         // Separator
+            Constraint::Length(file_rows), // Files
+            Constraint::Length(1),         // Separator
+            Constraint::Length(1),         // Message label and status
+            Constraint::Min(3),            // Message editor
+            Constraint::Length(1),         // Push option
+            Constraint::Length(1),         // Spacing
+        ])
+        .split(inner);
+
+        self.render_summary(chunks[0], buf, state);
+        let separator = "─".repeat(inner.width as usize);
+        Paragraph::new(separator.clone())
+            .style(Style::default().fg(Color::DarkGray))
+            .render(chunks[1], buf);
+        self.render_files(chunks[2], buf, state);
+        Paragraph::new(separator)
+            .style(Style::default().fg(Color::DarkGray))
+            .render(chunks[3], buf);
+        self.render_status(chunks[4], buf, state);
+
+        let editor_area = chunks[5];
+        state.message.render(editor_area, buf);
+
+        let checkbox = if state.push { "[x]" } else { "[ ]" };
+        let remote = state
+            .branch
+            .as_deref()
+            .map(|branch| format!(" Push {} to origin after committing", branch))
+            .unwrap_or_else(|| " Push to origin after committing".to_string());
+        Paragraph::new(Line::from(vec![
+            Span::styled(checkbox, Style::default().fg(accent_primary())),
+            Span::styled(remote, Style::default().fg(text_primary())),
+        ]))
+        .render(chunks[6], buf);
+
+        if state.committing {
+            return None;
+        }
+        Some(
+            state
+                .message
+                .cursor_position(editor_area, state.message.scroll_offset()),
+        )
+    }
+
+    fn render_summary(&self, area: Rect, buf: &mut Buffer, state: &CommitDialogState) {
+        let count = state.files.len();
+        let scope = if state.staged_only() {
+            "staged changes only"
+        } else {
+            "all changes"
+        };
+        let mut spans = Vec::new();
+        if let Some(branch) = state.branch.as_deref() {
+            spans.push(Span::styled("Branch: ", Style::default().fg(text_muted())));
+            spans.push(Span::styled(
+                format!("{}  ", branch),
+                Style::default().fg(text_primary()),
+            ));
+        }
+        spans.push(Span::styled(
+            format!(
+                "{} {} · {}",
+                count,
+                if count == 1 { "file" } else { "files" },
+                scope
+            ),
+            Style::default().fg(text_muted()),
+        ));
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+
+    fn render_files(&self, area: Rect, buf: &mut Buffer, state: &CommitDialogState) {
+        let staged_only = state.staged_only();
+        let visible = state.files.len().min(MAX_FILE_ROWS);
+        let mut lines: Vec<Line> = state
+            .files
+            .iter()
+            .take(visible)
+            .map(|file| {
+                // Files left out of a staged-only commit are dimmed
+                let included = !staged_only || file.is_staged();
+                let status_color = if !included {
+                    text_muted()
+                } else if file.is_untracked() {
+                    accent_success()
+                } else {
+                    accent_warning()
+                };
+                let path_color = if included {
+                    text_primary()
+                } else {
+                    text_muted()
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {}{}  ", file.index_status, file.worktree_status),
+                        Style::default().fg(status_color),
+                    ),
+                    Span::styled(file.path.clone(), Style::default().fg(path_color)),
+                ])
+            })
+            .collect();
+        if state.files.len() > visible {
+            lines.push(Line::from(Span::styled(
+                format!(" … and {} more", state.files.len() - visible),
+                Style::default().fg(text_muted()),
+            )));
+        }
+        Paragraph::new(lines).render(area, buf);
+    }
+
+    fn render_status(&self, area: Rect, buf: &mut Buffer, state: &CommitDialogState) {
+        const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+        let spinner = SPINNER_FRAMES[state.spinner_frame % SPINNER_FRAMES.len()];
+        let (status, style) = if state.committing {
+            (
+                format!("{} Committing...", spinner),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if state.generating {
+            (
+                format!("{} Generating message...", spinner),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if let Some(error) = state.error.as_deref() {
+            (
+                error.replace('\n', " "),
+                Style::default().fg(accent_error()),
+            )
+        } else if let Some(tool) = state.tool_used.as_deref() {
+            (
+                format!("Generated by {}", tool),
+                Style::default().fg(text_muted()),
+            )
+        } else {
+            (String::new(), Style::default())
+        };
+        Paragraph::new(Line::from(vec![
+            Span::styled("Message  ", Style::default().fg(text_muted())),
+            Span::styled(status, style),
+        ]))
+        .render(area, buf);
+    }
+}
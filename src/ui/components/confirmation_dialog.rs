@@ -56,6 +56,20 @@ pub enum ConfirmationContext {
     SelectWorkspaceMode { repo_id: Uuid },
     /// Confirm whether to delete a remote branch after archive
     ArchiveWorkspaceRemoteDelete { workspace_id: Uuid },
+    /// Rewinding a session's workspace to its checkpoint before a turn
+    RewindTurn { session_id: Uuid, turn: u32 },
+    /// Keeping one race contender and archiving the others
+    PickRaceWinner { race_id: Uuid, workspace_id: Uuid },
+    /// Discarding a hunk in a diff tab
+    RevertHunk {
+        viewer_id: Uuid,
+        path: String,
+        hunk_index: usize,
+        /// Hunk header, to detect the diff changing while the dialog is open
+        header: String,
+    },
+    /// Handing a stopped sync's conflicts to the workspace's agent
+    ResolveSyncConflicts { workspace_id: Uuid, prompt: String },
 }
 
 impl ConfirmationType {
@@ -0,0 +1,633 @@
+//! Diff view component for reviewing workspace changes
+//!
+//! Renders the changed-file list next to the selected file's hunks, either
+//! unified or side-by-side, with light syntax coloring from the active theme.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use super::{
+    accent_error, accent_primary, accent_secondary, accent_success, accent_warning, bg_base,
+    diff_add, diff_remove, interpolate, render_minimal_scrollbar, selected_bg, text_faint,
+    text_muted, text_primary, tool_comment,
+};
+use crate::git::{DiffHunk, DiffLine, DiffLineKind, FileChangeKind, FileDiff};
+use crate::ui::diff_viewer::{DiffLayout, DiffViewerSession};
+
+/// Narrowest content area that still gets a file list
+const MIN_WIDTH_FOR_FILE_LIST: u16 = 60;
+
+/// Renders a workspace diff with a file list and hunks
+pub struct DiffView<'a> {
+    session: &'a DiffViewerSession,
+}
+
+/// One rendered row of the selected file
+enum DiffRow<'a> {
+    Header(usize, &'a DiffHunk),
+    Line(&'a DiffLine),
+    Pair(Option<&'a DiffLine>, Option<&'a DiffLine>),
+}
+
+impl<'a> DiffView<'a> {
+    pub fn new(session: &'a DiffViewerSession) -> Self {
+        Self { session }
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        if let Some(error) = &self.session.error {
+            Self::render_message(area, buf, error, Style::default().fg(accent_error()));
+            return;
+        }
+        if self.session.files().is_empty() {
+            Self::render_message(area, buf, "No changes", Style::default().fg(text_muted()));
+            return;
+        }
+
+        let list_width = if area.width >= MIN_WIDTH_FOR_FILE_LIST {
+            (area.width / 4).clamp(20, 40)
+        } else {
+            0
+        };
+
+        if list_width > 0 {
+            let list_area = Rect {
+                width: list_width - 1,
+                ..area
+            };
+            self.render_file_list(list_area, buf);
+            for y in area.y..area.y + area.height {
+                buf[(area.x + list_width - 1, y)]
+                    .set_symbol("│")
+                    .set_style(Style::default().fg(text_faint()));
+            }
+        }
+
+        let hunks_area = Rect {
+            x: area.x + list_width,
+            width: area.width - list_width,
+            ..area
+        };
+        self.render_hunks(hunks_area, buf);
+    }
+
+    fn render_message(area: Rect, buf: &mut Buffer, message: &str, style: Style) {
+        let line = Line::from(Span::styled(format!(" {}", message), style));
+        Paragraph::new(line).render(area, buf);
+    }
+
+    fn render_file_list(&self, area: Rect, buf: &mut Buffer) {
+        let files = self.session.files();
+        let visible = area.height as usize;
+        let start = self
+            .session
+            .selected_file
+            .saturating_sub(visible.saturating_sub(1));
+
+        for (i, file) in files.iter().enumerate().skip(start).take(visible) {
+            let y = area.y + (i - start) as u16;
+            let selected = i == self.session.selected_file;
+            let counts = if file.binary {
+                " bin".to_string()
+            } else {
+                format!(" +{} -{}", file.additions, file.deletions)
+            };
+            let name_width = (area.width as usize).saturating_sub(3 + counts.len());
+            let spans = vec![
+                Span::styled(
+                    format!(" {} ", file.kind.marker()),
+                    Style::default().fg(kind_color(file.kind)),
+                ),
+                Span::styled(
+                    truncate_left(&file.path, name_width),
+                    Style::default().fg(if selected {
+                        text_primary()
+                    } else {
+                        text_muted()
+                    }),
+                ),
+                Span::styled(counts, Style::default().fg(text_faint())),
+            ];
+            let style = if selected {
+                Style::default().bg(selected_bg())
+            } else {
+                Style::default()
+            };
+            let row = Rect {
+                y,
+                height: 1,
+                ..area
+            };
+            Paragraph::new(Line::from(spans))
+                .style(style)
+                .render(row, buf);
+        }
+    }
+
+    fn render_hunks(&self, area: Rect, buf: &mut Buffer) {
+        let Some(file) = self.session.selected_file() else {
+            return;
+        };
+        if file.binary {
+            Self::render_message(
+                area,
+                buf,
+                "Binary file not shown",
+                Style::default().fg(text_muted()),
+            );
+            return;
+        }
+        if file.hunks.is_empty() {
+            let message = match file.kind {
+                FileChangeKind::Renamed => "Renamed without changes",
+                _ => "No textual changes",
+            };
+            Self::render_message(area, buf, message, Style::default().fg(text_muted()));
+            return;
+        }
+
+        let rows = self.rows(file);
+        let current_hunk = self.session.current_hunk();
+        let visible = area.height as usize;
+        let number_width = line_number_width(file);
+        let language = Language::from_extension(file.extension());
+        let content_area = Rect {
+            width: area.width.saturating_sub(1), // Leave room for scrollbar
+            ..area
+        };
+
+        for (i, row) in rows
+            .iter()
+            .skip(self.session.scroll_offset)
+            .take(visible)
+            .enumerate()
+        {
+            let row_area = Rect {
+                y: area.y + i as u16,
+                height: 1,
+                ..content_area
+            };
+            match row {
+                DiffRow::Header(index, hunk) => {
+                    let is_current = current_hunk == Some(*index);
+                    let mut spans = vec![Span::styled(
+                        format!("{}{}", if is_current { "▸" } else { " " }, hunk.header()),
+                        Style::default().fg(accent_primary()),
+                    )];
+                    if hunk.staged {
+                        spans.push(Span::styled(
+                            "  ✓ staged",
+                            Style::default().fg(accent_success()),
+                        ));
+                    }
+                    let tint = if is_current { 0.16 } else { 0.08 };
+                    Paragraph::new(Line::from(spans))
+                        .style(Style::default().bg(interpolate(bg_base(), accent_primary(), tint)))
+                        .render(row_area, buf);
+                }
+                DiffRow::Line(line) => {
+                    render_diff_line(
+                        row_area,
+                        buf,
+                        line,
+                        &[line.old_line, line.new_line],
+                        number_width,
+                        language,
+                    );
+                }
+                DiffRow::Pair(left, right) => {
+                    let half = row_area.width / 2;
+                    let left_area = Rect {
+                        width: half,
+                        ..row_area
+                    };
+                    let right_area = Rect {
+                        x: row_area.x + half,
+                        width: row_area.width - half,
+                        ..row_area
+                    };
+                    if let Some(line) = left {
+                        render_diff_line(
+                            left_area,
+                            buf,
+                            line,
+                            &[line.old_line],
+                            number_width,
+                            language,
+                        );
+                    }
+                    if let Some(line) = right {
+                        render_diff_line(
+                            right_area,
+                            buf,
+                            line,
+                            &[line.new_line],
+                            number_width,
+                            language,
+                        );
+                    }
+                }
+            }
+        }
+
+        let scrollbar_area = Rect {
+            x: area.x + area.width - 1,
+            width: 1,
+            ..area
+        };
+        render_minimal_scrollbar(
+            scrollbar_area,
+            buf,
+            rows.len(),
+            visible,
+            self.session.scroll_offset,
+        );
+    }
+
+    fn rows(&self, file: &'a FileDiff) -> Vec<DiffRow<'a>> {
+        let mut rows = Vec::new();
+        for (index, hunk) in file.hunks.iter().enumerate() {
+            rows.push(DiffRow::Header(index, hunk));
+            match self.session.layout {
+                DiffLayout::Unified => rows.extend(hunk.lines.iter().map(DiffRow::Line)),
+                DiffLayout::SideBySide => rows.extend(
+                    hunk.side_by_side()
+                        .into_iter()
+                        .map(|(left, right)| DiffRow::Pair(left, right)),
+                ),
+            }
+        }
+        rows
+    }
+}
+
+/// Render one diff line: line number gutter, +/- marker and highlighted content
+fn render_diff_line(
+    area: Rect,
+    buf: &mut Buffer,
+    line: &DiffLine,
+    numbers: &[Option<usize>],
+    number_width: usize,
+    language: Language,
+) {
+    let (marker, marker_color, background) = match line.kind {
+        DiffLineKind::Added => ('+', diff_add(), interpolate(bg_base(), diff_add(), 0.15)),
+        DiffLineKind::Removed => (
+            '-',
+            diff_remove(),
+            interpolate(bg_base(), diff_remove(), 0.15),
+        ),
+        DiffLineKind::Context => (' ', text_muted(), bg_base()),
+    };
+
+    let gutter = numbers
+        .iter()
+        .map(|number| match number {
+            Some(n) => format!("{:>width$}", n, width = number_width),
+            None => " ".repeat(number_width),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut spans = vec![
+        Span::styled(format!(" {} ", gutter), Style::default().fg(text_faint())),
+        Span::styled(format!("{} ", marker), Style::default().fg(marker_color)),
+    ];
+    spans.extend(highlight(
+        &line.content.trim_end_matches('\r').replace('\t', "    "),
+        language,
+    ));
+    if line.no_newline {
+        spans.push(Span::styled(
+            " \\ no newline",
+            Style::default().fg(text_faint()),
+        ));
+    }
+
+    Paragraph::new(Line::from(spans))
+        .style(Style::default().bg(background))
+        .render(area, buf);
+}
+
+fn kind_color(kind: FileChangeKind) -> Color {
+    match kind {
+        FileChangeKind::Added | FileChangeKind::Untracked => diff_add(),
+        FileChangeKind::Deleted => diff_remove(),
+        FileChangeKind::Modified => accent_warning(),
+        FileChangeKind::Renamed => accent_primary(),
+    }
+}
+
+/// Digits needed for the largest line number in the file's hunks
+fn line_number_width(file: &FileDiff) -> usize {
+    let max_line = file
+        .hunks
+        .iter()
+        .map(|h| (h.old_start + h.old_lines).max(h.new_start + h.new_lines))
+        .max()
+        .unwrap_or(1);
+    max_line.to_string().len()
+}
+
+/// Keep the end of a path when it doesn't fit, prefixing "…"
+fn truncate_left(text: &str, max_width: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_width {
+        return format!("{:<width$}", text, width = max_width);
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    let tail: String = text.chars().skip(count - (max_width - 1)).collect();
+    format!("…{}", tail)
+}
+
+// ========== Syntax coloring ==========
+
+/// Languages with keyword and comment rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Rust,
+    CLike,
+    Script,
+    Go,
+    Hash,
+    Plain,
+}
+
+impl Language {
+    fn from_extension(extension: Option<&str>) -> Self {
+        match extension.map(|e| e.to_ascii_lowercase()).as_deref() {
+            Some("rs") => Language::Rust,
+            Some("c" | "h" | "cc" | "cpp" | "hpp" | "java" | "kt" | "swift" | "cs") => {
+                Language::CLike
+            }
+            Some("js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs") => Language::Script,
+            Some("go") => Language::Go,
+            Some("py" | "sh" | "bash" | "zsh" | "rb" | "toml" | "yaml" | "yml") => Language::Hash,
+            _ => Language::Plain,
+        }
+    }
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match",
+                "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct",
+                "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+            ],
+            Language::CLike => &[
+                "break",
+                "case",
+                "class",
+                "const",
+                "continue",
+                "default",
+                "do",
+                "else",
+                "enum",
+                "extends",
+                "false",
+                "final",
+                "for",
+                "if",
+                "import",
+                "new",
+                "null",
+                "private",
+                "protected",
+                "public",
+                "return",
+                "static",
+                "struct",
+                "switch",
+                "this",
+                "true",
+                "void",
+                "while",
+            ],
+            Language::Script => &[
+                "async",
+                "await",
+                "break",
+                "case",
+                "class",
+                "const",
+                "continue",
+                "default",
+                "else",
+                "export",
+                "extends",
+                "false",
+                "for",
+                "from",
+                "function",
+                "if",
+                "import",
+                "interface",
+                "let",
+                "new",
+                "null",
+                "return",
+                "switch",
+                "this",
+                "true",
+                "type",
+                "undefined",
+                "var",
+                "while",
+            ],
+            Language::Go => &[
+                "break",
+                "case",
+                "chan",
+                "const",
+                "continue",
+                "default",
+                "defer",
+                "else",
+                "false",
+                "for",
+                "func",
+                "go",
+                "if",
+                "import",
+                "interface",
+                "map",
+                "nil",
+                "package",
+                "range",
+                "return",
+                "select",
+                "struct",
+                "switch",
+                "true",
+                "type",
+                "var",
+            ],
+            Language::Hash => &[
+                "and", "class", "def", "do", "elif", "else", "end", "false", "False", "fi", "for",
+                "from", "function", "if", "import", "in", "is", "not", "None", "or", "return",
+                "then", "true", "True", "while", "with",
+            ],
+            Language::Plain => &[],
+        }
+    }
+
+    fn comment_prefix(self) -> Option<&'static str> {
+        match self {
+            Language::Rust | Language::CLike | Language::Script | Language::Go => Some("//"),
+            Language::Hash => Some("#"),
+            Language::Plain => None,
+        }
+    }
+
+    fn is_quote(self, c: char) -> bool {
+        match self {
+            Language::Plain => false,
+            // Single quotes are lifetimes in Rust more often than char literals
+            Language::Rust => c == '"',
+            Language::Script | Language::Go => matches!(c, '"' | '\'' | '`'),
+            Language::CLike | Language::Hash => matches!(c, '"' | '\''),
+        }
+    }
+}
+
+/// Split a line into spans colored by token kind
+fn highlight(content: &str, language: Language) -> Vec<Span<'static>> {
+    if language == Language::Plain {
+        return vec![Span::styled(
+            content.to_string(),
+            Style::default().fg(text_primary()),
+        )];
+    }
+
+    let chars: Vec<char> = content.chars().collect();
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(
+                std::mem::take(plain),
+                Style::default().fg(text_primary()),
+            ));
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(prefix) = language.comment_prefix() {
+            if content_starts_with(&chars[i..], prefix) {
+                flush(&mut plain, &mut spans);
+                spans.push(Span::styled(
+                    chars[i..].iter().collect::<String>(),
+                    Style::default()
+                        .fg(tool_comment())
+                        .add_modifier(Modifier::ITALIC),
+                ));
+                return spans;
+            }
+        }
+
+        if language.is_quote(c) {
+            let mut end = i + 1;
+            while end < chars.len() && chars[end] != c {
+                if chars[end] == '\\' {
+                    end += 1;
+                }
+                end += 1;
+            }
+            let end = (end + 1).min(chars.len());
+            flush(&mut plain, &mut spans);
+            spans.push(Span::styled(
+                chars[i..end].iter().collect::<String>(),
+                Style::default().fg(accent_success()),
+            ));
+            i = end;
+            continue;
+        }
+
+        if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let color = if c.is_ascii_digit() {
+                Some(accent_warning())
+            } else if language.keywords().contains(&word.as_str()) {
+                Some(accent_secondary())
+            } else {
+                None
+            };
+            match color {
+                Some(color) => {
+                    flush(&mut plain, &mut spans);
+                    spans.push(Span::styled(word, Style::default().fg(color)));
+                }
+                None => plain.push_str(&word),
+            }
+            continue;
+        }
+
+        plain.push(c);
+        i += 1;
+    }
+
+    flush(&mut plain, &mut spans);
+    spans
+}
+
+fn content_starts_with(chars: &[char], prefix: &str) -> bool {
+    let mut rest = chars.iter();
+    prefix.chars().all(|p| rest.next() == Some(&p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_of(spans: &[Span]) -> Vec<String> {
+        spans.iter().map(|s| s.content.to_string()).collect()
+    }
+
+    #[test]
+    fn test_highlight_splits_tokens() {
+        let spans = highlight(r#"let x = "a\"b" + 42; // note"#, Language::Rust);
+        assert_eq!(
+            text_of(&spans),
+            vec!["let", " x = ", r#""a\"b""#, " + ", "42", "; ", "// note"]
+        );
+        assert_eq!(spans[0].style.fg, Some(accent_secondary()));
+        assert_eq!(spans[2].style.fg, Some(accent_success()));
+        assert_eq!(spans[4].style.fg, Some(accent_warning()));
+        assert_eq!(spans[6].style.fg, Some(tool_comment()));
+
+        // Lifetimes are not string literals
+        let spans = highlight("fn f<'a>(x: &'a str)", Language::Rust);
+        assert_eq!(text_of(&spans).concat(), "fn f<'a>(x: &'a str)");
+        assert!(spans.iter().all(|s| s.style.fg != Some(accent_success())));
+
+        let spans = highlight("plain # text", Language::Plain);
+        assert_eq!(text_of(&spans), vec!["plain # text"]);
+    }
+
+    #[test]
+    fn test_truncate_left() {
+        assert_eq!(truncate_left("src/lib.rs", 12), "src/lib.rs  ");
+        assert_eq!(truncate_left("src/ui/app.rs", 8), "…/app.rs");
+        assert_eq!(truncate_left("abc", 0), "");
+    }
+}
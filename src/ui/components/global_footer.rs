@@ -23,6 +23,10 @@ pub enum FooterContext {
     RawEvents,
     /// File viewer mode
     FileViewer,
+    /// Workspace diff mode
+    DiffViewer,
+    /// Race comparison mode
+    RaceViewer,
 }
 
 impl FooterContext {
@@ -71,6 +75,8 @@ impl<'a> GlobalFooter<'a> {
                 FooterContext::Sidebar => Self::sidebar_hints(),
                 FooterContext::RawEvents => Self::raw_events_hints(),
                 FooterContext::FileViewer => Self::file_viewer_hints(),
+                FooterContext::DiffViewer => Self::diff_viewer_hints(),
+                FooterContext::RaceViewer => Self::race_viewer_hints(),
             },
             spinner: None,
             message: None,
@@ -157,6 +163,32 @@ impl<'a> GlobalFooter<'a> {
         ]
     }
 
+    /// Get hints for diff viewer mode
+    pub fn diff_viewer_hints() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("j/k", "scroll"),
+            ("n/p", "file"),
+            ("[/]", "hunk"),
+            ("s", "stage"),
+            ("x", "revert"),
+            ("t", "scope"),
+            ("v", "layout"),
+            ("b", "base"),
+            ("M-S-w", "close"),
+        ]
+    }
+
+    pub fn race_viewer_hints() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("j/k", "select"),
+            ("Enter", "open"),
+            ("t", "tests"),
+            ("w", "winner"),
+            ("r", "reload"),
+            ("M-S-w", "close"),
+        ]
+    }
+
     pub fn render(&self, area: Rect, buf: &mut Buffer) {
         // Build left side content (spinner + message)
         let mut left_spans: Vec<Span> = Vec::new();
@@ -1,4 +1,4 @@
-//! Inline prompt component for AskUserQuestion and ExitPlanMode tools
+//! Inline prompt component for AskUserQuestion, ExitPlanMode and tool permission requests
 //!
 //! Emulates Claude Code CLI's inline UI patterns for interactive tool responses.
 
@@ -16,6 +16,7 @@ use super::{
     InstructionBar, TextInputState,
 };
 use crate::agent::events::{QuestionOption, UserQuestion};
+use crate::agent::{PermissionReply, PermissionRequest};
 
 // ============================================================================
 // Constants
@@ -42,6 +43,16 @@ const HORIZONTAL_LINE: char = '─';
 /// Dashed line character for plan box
 const DASHED_LINE: char = '╌';
 
+/// Options offered by tool permission prompts, in display order
+const PERMISSION_REPLIES: [(PermissionReply, &str); 3] = [
+    (PermissionReply::AllowOnce, "Yes"),
+    (
+        PermissionReply::AlwaysAllow,
+        "Yes, and don't ask again for this command",
+    ),
+    (PermissionReply::Deny, "No"),
+];
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -56,6 +67,8 @@ pub enum InlinePromptType {
         plan_content: String,
         plan_file_path: String,
     },
+    /// Tool call the permission policy asks the user about
+    ToolPermission { request: PermissionRequest },
 }
 
 /// User's answer to a single question
@@ -76,6 +89,8 @@ pub enum PromptAction {
     Submit(PromptResponse),
     /// User cancelled the prompt
     Cancel,
+    /// User answered a tool permission prompt
+    AnswerPermission(PermissionReply),
     /// Key was handled but no action yet
     Consumed,
     /// Key was not handled
@@ -171,6 +186,19 @@ impl InlinePromptState {
         }
     }
 
+    /// Create a new tool permission prompt for an agent permission request
+    pub fn new_tool_permission(request_id: String, request: PermissionRequest) -> Self {
+        Self {
+            tool_id: request_id,
+            prompt_type: InlinePromptType::ToolPermission { request },
+            current_option: 0,
+            current_question_idx: 0,
+            answers: vec![],
+            input_mode: false,
+            text_input: TextInputState::new(),
+        }
+    }
+
     /// Get the current question (for AskUserQuestion only)
     fn current_question(&self) -> Option<&UserQuestion> {
         match &self.prompt_type {
@@ -236,6 +264,7 @@ impl InlinePromptState {
                     .unwrap_or(0)
             }
             InlinePromptType::ExitPlanMode { .. } => 2, // "Yes, start building" and "Type here..."
+            InlinePromptType::ToolPermission { .. } => PERMISSION_REPLIES.len(),
         }
     }
 
@@ -253,6 +282,7 @@ impl InlinePromptState {
                 }
             }
             InlinePromptType::ExitPlanMode { .. } => self.current_option == 1,
+            InlinePromptType::ToolPermission { .. } => false,
         }
     }
 
@@ -263,6 +293,22 @@ impl InlinePromptState {
             return self.handle_input_mode_key(key);
         }
 
+        if let InlinePromptType::ToolPermission { .. } = self.prompt_type {
+            match key.code {
+                KeyCode::Char('y') => {
+                    return PromptAction::AnswerPermission(PermissionReply::AllowOnce)
+                }
+                KeyCode::Char('a') => {
+                    return PromptAction::AnswerPermission(PermissionReply::AlwaysAllow)
+                }
+                // Dismissing a permission prompt denies the tool call
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    return PromptAction::AnswerPermission(PermissionReply::Deny)
+                }
+                _ => {}
+            }
+        }
+
         match key.code {
             // Navigation
             KeyCode::Up | KeyCode::Char('k') => {
@@ -540,6 +586,10 @@ impl InlinePromptState {
                     PromptAction::Consumed
                 }
             }
+            InlinePromptType::ToolPermission { .. } => PERMISSION_REPLIES
+                .get(self.current_option)
+                .map(|(reply, _)| PromptAction::AnswerPermission(*reply))
+                .unwrap_or(PromptAction::Consumed),
         }
     }
 
@@ -564,6 +614,7 @@ impl InlinePromptState {
             InlinePromptType::ExitPlanMode { .. } => {
                 PromptAction::Submit(PromptResponse::ExitPlanFeedback(text))
             }
+            InlinePromptType::ToolPermission { .. } => PromptAction::NotHandled,
         }
     }
 
@@ -718,6 +769,43 @@ impl InlinePromptState {
                     ]));
                 }
             }
+            InlinePromptType::ToolPermission { request } => {
+                lines.push(self.separator_line(width));
+                lines.push(Line::from(Span::styled(
+                    format!(" Allow {}?", request.tool),
+                    Style::default()
+                        .fg(text_primary())
+                        .add_modifier(Modifier::BOLD),
+                )));
+
+                let detail_style = Style::default().fg(text_secondary());
+                if let Some(command) = &request.command {
+                    for line in command.lines().take(10) {
+                        lines.push(Line::from(Span::styled(
+                            format!("   {}", line),
+                            detail_style,
+                        )));
+                    }
+                }
+                for path in request.paths.iter().take(10) {
+                    lines.push(Line::from(Span::styled(
+                        format!("   {}", path),
+                        detail_style,
+                    )));
+                }
+                lines.push(Line::from("")); // blank line
+
+                for (i, (_, label)) in PERMISSION_REPLIES.iter().enumerate() {
+                    lines.push(self.exit_plan_option_line(i, label, self.current_option == i));
+                }
+                lines.push(Line::from("")); // blank line
+                lines.push(self.instruction_bar_line(&[
+                    ("Enter", "select"),
+                    ("y/a/n", "answer"),
+                    ("↑/↓", "navigate"),
+                    ("Esc", "deny"),
+                ]));
+            }
         }
 
         lines
@@ -1700,6 +1788,11 @@ impl Widget for InlinePrompt<'_> {
                     );
                 }
             }
+
+            InlinePromptType::ToolPermission { .. } => {
+                let lines = self.state.render_as_lines(area.width as usize);
+                Paragraph::new(lines).render(area, buf);
+            }
         }
     }
 }
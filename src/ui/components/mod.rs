@@ -4,8 +4,10 @@ mod base_dir_dialog;
 mod chat_message;
 mod chat_view;
 mod command_palette;
+mod commit_dialog;
 mod confirmation_dialog;
 mod dialog;
+mod diff_view;
 mod error_dialog;
 pub mod file_path_detector;
 mod file_viewer_view;
@@ -21,12 +23,15 @@ mod missing_tool_dialog;
 mod model_selector;
 mod path_input;
 mod project_picker;
+mod race_view;
 mod raw_events_types;
 mod raw_events_view;
+mod review_comments_dialog;
 mod scrollbar;
 mod searchable_list;
 mod session_header;
 mod session_import_picker;
+mod session_search_dialog;
 mod sidebar;
 mod slash_menu;
 mod spinner;
@@ -38,13 +43,15 @@ mod theme_picker;
 mod thinking_indicator;
 mod tree_view;
 mod turn_summary;
+mod usage_stats_dialog;
 
 pub use add_repo_dialog::{AddRepoDialog, AddRepoDialogState};
-pub use agent_selector::{AgentSelector, AgentSelectorState};
+pub use agent_selector::{AgentSelector, AgentSelectorPurpose, AgentSelectorState};
 pub use base_dir_dialog::{BaseDirDialog, BaseDirDialogState};
 pub use chat_message::{ChatMessage, MessageRole};
-pub use chat_view::{ChatView, HoveredFilePath};
+pub use chat_view::{ChatView, FindStatus, HoveredFilePath};
 pub use command_palette::{CommandPalette, CommandPaletteEntry, CommandPaletteState};
+pub use commit_dialog::{CommitDialog, CommitDialogState};
 pub use confirmation_dialog::{
     ConfirmationContext, ConfirmationDialog, ConfirmationDialogState, ConfirmationType,
 };
@@ -52,6 +59,7 @@ pub use dialog::{
     dialog_content_area, DialogFrame, InstructionBar, StatusLine, DIALOG_CONTENT_PADDING_X,
     DIALOG_CONTENT_PADDING_Y,
 };
+pub use diff_view::DiffView;
 pub use error_dialog::{ErrorDialog, ErrorDialogState};
 pub use file_viewer_view::FileViewerView;
 pub use global_footer::{FooterContext, GlobalFooter};
@@ -72,14 +80,17 @@ pub use model_selector::{
 };
 pub use path_input::PathInputState;
 pub use project_picker::{ProjectEntry, ProjectPicker, ProjectPickerState};
+pub use race_view::RaceView;
 pub use raw_events_types::{
     EventDetailState, EventDirection, RawEventEntry, DETAIL_PANEL_BREAKPOINT,
 };
 pub use raw_events_view::{RawEventsClick, RawEventsScrollbarMetrics, RawEventsView};
+pub use review_comments_dialog::{ReviewCommentsDialog, ReviewCommentsDialogState};
 pub use scrollbar::{render_minimal_scrollbar, scrollbar_offset_from_point, ScrollbarMetrics};
 pub use searchable_list::SearchableListState;
 pub use session_header::SessionHeader;
 pub use session_import_picker::{AgentFilter, SessionImportPicker, SessionImportPickerState};
+pub use session_search_dialog::{SessionSearchDialog, SessionSearchDialogState};
 pub use sidebar::{Sidebar, SidebarState, SIDEBAR_HEADER_ROWS};
 pub use slash_menu::{SlashCommand, SlashCommandEntry, SlashMenu, SlashMenuState};
 pub use spinner::Spinner;
@@ -191,3 +202,4 @@ pub use tree_view::{
     SIDEBAR_GIT_DISPLAY,
 };
 pub use turn_summary::{FileChange, TurnSummary};
+pub use usage_stats_dialog::{UsageStatsDialog, UsageStatsDialogState};
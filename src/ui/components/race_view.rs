@@ -0,0 +1,267 @@
+//! Race view component for comparing the contenders of a multi-agent race
+//!
+//! Renders one row per contender (status, diff stats, tests, tokens, cost and
+//! time) followed by the selected contender's test output.
+
+use std::collections::HashSet;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget, Wrap},
+};
+use uuid::Uuid;
+
+use super::{
+    accent_error, accent_primary, accent_success, accent_warning, diff_add, diff_remove,
+    selected_bg, text_faint, text_muted, text_primary,
+};
+use crate::agent::events::ContextWindowState;
+use crate::core::services::RaceStanding;
+use crate::ui::race_viewer::RaceViewerSession;
+
+/// Column widths after the contender name
+const STATUS_WIDTH: usize = 10;
+const FILES_WIDTH: usize = 7;
+const CHANGES_WIDTH: usize = 14;
+const TESTS_WIDTH: usize = 9;
+const TOKENS_WIDTH: usize = 13;
+const COST_WIDTH: usize = 9;
+const TIME_WIDTH: usize = 9;
+
+/// Renders the comparison table of a race
+pub struct RaceView<'a> {
+    session: &'a RaceViewerSession,
+    /// Workspaces whose agent is working on a turn
+    running: &'a HashSet<Uuid>,
+}
+
+impl<'a> RaceView<'a> {
+    pub fn new(session: &'a RaceViewerSession, running: &'a HashSet<Uuid>) -> Self {
+        Self { session, running }
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let name_width = (area.width as usize)
+            .saturating_sub(
+                STATUS_WIDTH
+                    + FILES_WIDTH
+                    + CHANGES_WIDTH
+                    + TESTS_WIDTH
+                    + TOKENS_WIDTH
+                    + COST_WIDTH
+                    + TIME_WIDTH
+                    + 2,
+            )
+            .clamp(12, 40);
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(" Prompt: ", Style::default().fg(text_muted())),
+                Span::styled(
+                    truncate(
+                        &self.session.race.prompt.replace('\n', " "),
+                        (area.width as usize).saturating_sub(10),
+                    ),
+                    Style::default().fg(text_primary()),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    " {:<name$}{:<status$}{:>files$}{:>changes$}{:>tests$}{:>tokens$}{:>cost$}{:>time$}",
+                    "Contender",
+                    "Status",
+                    "Files",
+                    "Changes",
+                    "Tests",
+                    "Tokens",
+                    "Cost",
+                    "Time",
+                    name = name_width,
+                    status = STATUS_WIDTH,
+                    files = FILES_WIDTH,
+                    changes = CHANGES_WIDTH,
+                    tests = TESTS_WIDTH,
+                    tokens = TOKENS_WIDTH,
+                    cost = COST_WIDTH,
+                    time = TIME_WIDTH,
+                ),
+                Style::default()
+                    .fg(text_muted())
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ];
+
+        if let Some(error) = &self.session.error {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", error),
+                Style::default().fg(accent_error()),
+            )));
+        }
+
+        for (index, standing) in self.session.standings().iter().enumerate() {
+            let mut line = self.row(standing, name_width);
+            if index == self.session.selected {
+                line = line.style(Style::default().bg(selected_bg()));
+            }
+            lines.push(line);
+        }
+
+        if let Some(standing) = self.session.selected_standing() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(" {} · {}", standing.workspace_name, standing.branch),
+                Style::default().fg(text_muted()),
+            )));
+            match &standing.entry.test_result {
+                Some(result) if !result.output.is_empty() => {
+                    for output_line in result.output.lines() {
+                        lines.push(Line::from(Span::styled(
+                            format!("   {}", output_line),
+                            Style::default().fg(text_faint()),
+                        )));
+                    }
+                }
+                Some(_) => {}
+                None => lines.push(Line::from(Span::styled(
+                    "   No test results yet",
+                    Style::default().fg(text_faint()),
+                ))),
+            }
+        }
+
+        // Keep the end of the test output visible on short terminals
+        let overflow = lines.len().saturating_sub(area.height as usize);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((overflow as u16, 0))
+            .render(area, buf);
+    }
+
+    fn row(&self, standing: &RaceStanding, name_width: usize) -> Line<'static> {
+        let entry = &standing.entry;
+        let workspace_id = entry.workspace_id;
+        let name = match entry.model.as_deref() {
+            Some(model) => format!("{} · {}", entry.agent_type.as_str(), model),
+            None => entry.agent_type.as_str().to_string(),
+        };
+
+        let (status, status_color) = if self.session.race.winner_workspace_id == Some(workspace_id)
+        {
+            ("★ winner", accent_success())
+        } else if standing.archived {
+            ("archived", text_faint())
+        } else if self.running.contains(&workspace_id) {
+            ("running", accent_primary())
+        } else if self.session.is_testing(workspace_id) {
+            ("testing", accent_warning())
+        } else if standing.usage.turns > 0 {
+            ("done", text_primary())
+        } else {
+            ("waiting", text_muted())
+        };
+
+        let (files, additions, deletions) = match &standing.diff_stats {
+            Some(stats) => (
+                stats.files_changed.to_string(),
+                format!("+{}", stats.additions),
+                format!("-{}", stats.deletions),
+            ),
+            None => ("–".to_string(), String::new(), String::new()),
+        };
+        let changes_pad =
+            CHANGES_WIDTH.saturating_sub(additions.chars().count() + deletions.chars().count() + 1);
+
+        let (tests, tests_color) = match &entry.test_result {
+            Some(result) if result.passed => ("pass", accent_success()),
+            Some(_) => ("fail", accent_error()),
+            None => ("–", text_muted()),
+        };
+
+        let usage = &standing.usage;
+        let tokens = format!(
+            "{}↓ {}↑",
+            ContextWindowState::format_tokens(usage.input_tokens),
+            ContextWindowState::format_tokens(usage.output_tokens)
+        );
+
+        Line::from(vec![
+            Span::styled(
+                format!(
+                    " {:<width$}",
+                    truncate(&name, name_width - 1),
+                    width = name_width
+                ),
+                Style::default().fg(text_primary()),
+            ),
+            Span::styled(
+                format!("{:<width$}", status, width = STATUS_WIDTH),
+                Style::default().fg(status_color),
+            ),
+            Span::styled(
+                format!("{:>width$}", files, width = FILES_WIDTH),
+                Style::default().fg(text_primary()),
+            ),
+            Span::raw(" ".repeat(changes_pad)),
+            Span::styled(additions, Style::default().fg(diff_add())),
+            Span::raw(" "),
+            Span::styled(deletions, Style::default().fg(diff_remove())),
+            Span::styled(
+                format!("{:>width$}", tests, width = TESTS_WIDTH),
+                Style::default().fg(tests_color),
+            ),
+            Span::styled(
+                format!("{:>width$}", tokens, width = TOKENS_WIDTH),
+                Style::default().fg(text_primary()),
+            ),
+            Span::styled(
+                format!(
+                    "{:>width$}",
+                    format_cost(usage.cost_usd),
+                    width = COST_WIDTH
+                ),
+                Style::default().fg(text_primary()),
+            ),
+            Span::styled(
+                format!(
+                    "{:>width$}",
+                    format_duration_ms(usage.duration_ms),
+                    width = TIME_WIDTH
+                ),
+                Style::default().fg(text_primary()),
+            ),
+        ])
+    }
+}
+
+fn format_cost(cost: f64) -> String {
+    if cost > 0.0 && cost < 0.01 {
+        "<$0.01".to_string()
+    } else {
+        format!("${:.2}", cost)
+    }
+}
+
+fn format_duration_ms(ms: i64) -> String {
+    let secs = ms / 1000;
+    if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", truncated)
+}
@@ -0,0 +1,469 @@
+//! Review comments dialog component
+//!
+//! Lists the unresolved review comments on the active workspace's PR so
+//! they can be selected and sent to the session's agent.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use uuid::Uuid;
+
+use super::{
+    accent_error, accent_primary, accent_success, accent_warning, dialog_bg, ensure_contrast_bg,
+    ensure_contrast_fg, selected_bg, text_muted, text_primary, DialogFrame,
+};
+use crate::core::services::PendingReview;
+use crate::git::ReviewComment;
+
+// ============ Dialog Sizing Constants ============
+/// Dialog width as percentage of screen (0-100)
+const DIALOG_WIDTH_PERCENT: u16 = 80;
+/// Dialog height as percentage of screen (0-100)
+const DIALOG_HEIGHT_PERCENT: u16 = 70;
+/// Minimum dialog width
+const DIALOG_MIN_WIDTH: u16 = 60;
+/// Minimum dialog height
+const DIALOG_MIN_HEIGHT: u16 = 12;
+/// Rows per comment: location/author line plus a body preview line
+const ROWS_PER_COMMENT: usize = 2;
+
+/// State for the review comments dialog
+pub struct ReviewCommentsDialogState {
+    /// Whether the dialog is visible
+    visible: bool,
+    /// Workspace whose PR is shown
+    pub workspace_id: Option<Uuid>,
+    /// Working directory of the workspace
+    pub working_dir: Option<PathBuf>,
+    /// Loaded review, if any
+    pub review: Option<PendingReview>,
+    /// IDs of the comments selected for sending
+    selected_ids: HashSet<String>,
+    /// Index of the highlighted comment among the visible ones
+    cursor: usize,
+    /// First visible comment
+    scroll_offset: usize,
+    /// Whether comments already marked addressed are listed
+    pub show_addressed: bool,
+    /// Whether comments are being fetched
+    pub loading: bool,
+    /// Error from the last fetch
+    pub error: Option<String>,
+    /// Spinner frame for the loading animation
+    spinner_frame: usize,
+}
+
+impl Default for ReviewCommentsDialogState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReviewCommentsDialogState {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            workspace_id: None,
+            working_dir: None,
+            review: None,
+            selected_ids: HashSet::new(),
+            cursor: 0,
+            scroll_offset: 0,
+            show_addressed: false,
+            loading: false,
+            error: None,
+            spinner_frame: 0,
+        }
+    }
+
+    /// Show the dialog for a workspace, waiting for its comments to load
+    pub fn show(&mut self, workspace_id: Uuid, working_dir: PathBuf) {
+        *self = Self {
+            visible: true,
+            workspace_id: Some(workspace_id),
+            working_dir: Some(working_dir),
+            loading: true,
+            ..Self::new()
+        };
+    }
+
+    /// Hide the dialog
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Check if dialog is visible
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Advance the spinner animation
+    pub fn tick(&mut self) {
+        if self.loading {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        }
+    }
+
+    /// Mark a reload as started
+    pub fn start_reload(&mut self) {
+        self.loading = true;
+        self.error = None;
+    }
+
+    /// Apply fetched comments, keeping the selection of comments still present
+    pub fn set_loaded(&mut self, result: Result<PendingReview, String>) {
+        self.loading = false;
+        match result {
+            Ok(review) => {
+                self.error = None;
+                self.selected_ids
+                    .retain(|id| review.comments.iter().any(|comment| &comment.id == id));
+                self.review = Some(review);
+                self.clamp_cursor();
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    /// Comments currently listed, in PR order
+    pub fn visible_comments(&self) -> Vec<&ReviewComment> {
+        let Some(review) = self.review.as_ref() else {
+            return Vec::new();
+        };
+        review
+            .comments
+            .iter()
+            .filter(|comment| self.show_addressed || !review.is_addressed(comment))
+            .collect()
+    }
+
+    pub fn is_addressed(&self, comment: &ReviewComment) -> bool {
+        self.review
+            .as_ref()
+            .is_some_and(|review| review.is_addressed(comment))
+    }
+
+    pub fn is_selected(&self, comment: &ReviewComment) -> bool {
+        self.selected_ids.contains(&comment.id)
+    }
+
+    pub fn selected_count(&self) -> usize {
+        self.visible_comments()
+            .iter()
+            .filter(|comment| self.is_selected(comment))
+            .count()
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.visible_comments().len();
+        if count > 0 {
+            self.cursor = (self.cursor + 1).min(count - 1);
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// Toggle selection of the highlighted comment
+    pub fn toggle_selected(&mut self) {
+        let Some(id) = self
+            .visible_comments()
+            .get(self.cursor)
+            .map(|comment| comment.id.clone())
+        else {
+            return;
+        };
+        if !self.selected_ids.remove(&id) {
+            self.selected_ids.insert(id);
+        }
+    }
+
+    /// Select every listed comment, or clear the selection if all are selected
+    pub fn toggle_all(&mut self) {
+        let ids: Vec<String> = self
+            .visible_comments()
+            .iter()
+            .map(|comment| comment.id.clone())
+            .collect();
+        if ids.iter().all(|id| self.selected_ids.contains(id)) {
+            self.selected_ids.clear();
+        } else {
+            self.selected_ids.extend(ids);
+        }
+    }
+
+    pub fn toggle_show_addressed(&mut self) {
+        self.show_addressed = !self.show_addressed;
+        self.clamp_cursor();
+    }
+
+    /// IDs of the comments an action applies to: the selected comments, or
+    /// the highlighted one when nothing is selected
+    pub fn target_ids(&self) -> Vec<String> {
+        let visible = self.visible_comments();
+        let selected: Vec<String> = visible
+            .iter()
+            .filter(|comment| self.is_selected(comment))
+            .map(|comment| comment.id.clone())
+            .collect();
+        if !selected.is_empty() {
+            return selected;
+        }
+        visible
+            .get(self.cursor)
+            .map(|comment| vec![comment.id.clone()])
+            .unwrap_or_default()
+    }
+
+    /// Record comments as addressed (or not) and drop them from the selection
+    pub fn set_addressed(&mut self, comment_ids: &[String], addressed: bool) {
+        let Some(review) = self.review.as_mut() else {
+            return;
+        };
+        for id in comment_ids {
+            self.selected_ids.remove(id);
+            if addressed {
+                review.addressed.insert(id.clone());
+            } else {
+                review.addressed.remove(id);
+            }
+        }
+        self.clamp_cursor();
+    }
+
+    fn clamp_cursor(&mut self) {
+        let count = self.visible_comments().len();
+        self.cursor = self.cursor.min(count.saturating_sub(1));
+    }
+
+    /// Keep the highlighted comment within `visible_count` listed comments
+    fn ensure_cursor_visible(&mut self, visible_count: usize) {
+        if self.cursor < self.scroll_offset {
+            self.scroll_offset = self.cursor;
+        } else if visible_count > 0 && self.cursor >= self.scroll_offset + visible_count {
+            self.scroll_offset = self.cursor + 1 - visible_count;
+        }
+    }
+}
+
+/// Review comments dialog widget
+pub struct ReviewCommentsDialog;
+
+impl Default for ReviewCommentsDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReviewCommentsDialog {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer, state: &mut ReviewCommentsDialogState) {
+        if !state.visible {
+            return;
+        }
+
+        let width = (area.width * DIALOG_WIDTH_PERCENT / 100)
+            .max(DIALOG_MIN_WIDTH)
+            .min(area.width.saturating_sub(4));
+        let height = (area.height * DIALOG_HEIGHT_PERCENT / 100)
+            .max(DIALOG_MIN_HEIGHT)
+            .min(area.height.saturating_sub(2));
+
+        let frame = DialogFrame::new("PR Review Comments", width, height).instructions(vec![
+            ("Enter", "Send"),
+            ("Space", "Select"),
+            ("a", "All"),
+            ("m", "Addressed"),
+            ("h", "Show addressed"),
+            ("r", "Reload"),
+            ("Esc", "Close"),
+        ]);
+        let inner = frame.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Summary
+            Constraint::Length(1), // Separator
+            Constraint::Min(2),    // Comments
+            Constraint::Length(1), // Status
+        ])
+        .split(inner);
+
+        self.render_summary(chunks[0], buf, state);
+        Paragraph::new("─".repeat(inner.width as usize))
+            .style(Style::default().fg(Color::DarkGray))
+            .render(chunks[1], buf);
+        self.render_comments(chunks[2], buf, state);
+        self.render_status(chunks[3], buf, state);
+    }
+
+    fn render_summary(&self, area: Rect, buf: &mut Buffer, state: &ReviewCommentsDialogState) {
+        let Some(review) = state.review.as_ref() else {
+            return;
+        };
+        let addressed = review
+            .comments
+            .iter()
+            .filter(|comment| review.is_addressed(comment))
+            .count();
+        let open = review.comments.len() - addressed;
+        let hidden = if state.show_addressed || addressed == 0 {
+            ""
+        } else {
+            " (hidden)"
+        };
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("PR #{}  ", review.pr_number),
+                Style::default().fg(text_primary()),
+            ),
+            Span::styled(
+                format!("{} unresolved · {} addressed{}", open, addressed, hidden),
+                Style::default().fg(text_muted()),
+            ),
+        ]))
+        .render(area, buf);
+    }
+
+    fn render_comments(&self, area: Rect, buf: &mut Buffer, state: &mut ReviewCommentsDialogState) {
+        let visible_count = area.height as usize / ROWS_PER_COMMENT;
+        state.ensure_cursor_visible(visible_count);
+
+        let comments = state.visible_comments();
+        if comments.is_empty() {
+            let text = if state.review.is_some() && !state.loading && state.error.is_none() {
+                "No unresolved review comments"
+            } else {
+                ""
+            };
+            Paragraph::new(text)
+                .style(Style::default().fg(text_muted()))
+                .render(area, buf);
+            return;
+        }
+
+        let row_selected_bg = ensure_contrast_bg(selected_bg(), dialog_bg(), 3.0);
+        let row_selected_fg = ensure_contrast_fg(text_primary(), row_selected_bg, 4.5);
+        let body_width = (area.width as usize).saturating_sub(6);
+
+        let mut lines = Vec::new();
+        for (i, comment) in comments
+            .iter()
+            .enumerate()
+            .skip(state.scroll_offset)
+            .take(visible_count)
+        {
+            let is_cursor = i == state.cursor;
+            let addressed = state.is_addressed(comment);
+            let primary = if is_cursor {
+                row_selected_fg
+            } else if addressed {
+                text_muted()
+            } else {
+                text_primary()
+            };
+            let muted = if is_cursor {
+                row_selected_fg
+            } else {
+                text_muted()
+            };
+            let row_style = if is_cursor {
+                Style::default().bg(row_selected_bg)
+            } else {
+                Style::default()
+            };
+
+            let checkbox = if state.is_selected(comment) {
+                "[x] "
+            } else {
+                "[ ] "
+            };
+            let mut header = vec![
+                Span::styled(
+                    if is_cursor { "> " } else { "  " },
+                    Style::default().fg(primary),
+                ),
+                Span::styled(checkbox, Style::default().fg(accent_primary())),
+                Span::styled(
+                    comment.location(),
+                    Style::default().fg(primary).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  @{}", comment.author), Style::default().fg(muted)),
+            ];
+            if comment.outdated {
+                header.push(Span::styled(
+                    "  outdated",
+                    Style::default().fg(accent_warning()),
+                ));
+            }
+            if addressed {
+                header.push(Span::styled(
+                    "  ✓ addressed",
+                    Style::default().fg(accent_success()),
+                ));
+            }
+
+            let preview: String = comment
+                .body
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .chars()
+                .take(body_width)
+                .collect();
+            lines.push(Line::from(header).style(row_style));
+            lines.push(
+                Line::from(Span::styled(
+                    format!("      {}", preview),
+                    Style::default().fg(muted),
+                ))
+                .style(row_style),
+            );
+        }
+        Paragraph::new(lines).render(area, buf);
+    }
+
+    fn render_status(&self, area: Rect, buf: &mut Buffer, state: &ReviewCommentsDialogState) {
+        const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+        let spinner = SPINNER_FRAMES[state.spinner_frame % SPINNER_FRAMES.len()];
+        let (status, style) = if state.loading {
+            (
+                format!("{} Fetching review comments...", spinner),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if let Some(error) = state.error.as_deref() {
+            (
+                error.replace('\n', " "),
+                Style::default().fg(accent_error()),
+            )
+        } else {
+            let selected = state.selected_count();
+            if selected == 0 {
+                (
+                    "Enter sends the highlighted comment".to_string(),
+                    Style::default().fg(text_muted()),
+                )
+            } else {
+                (
+                    format!(
+                        "{} {} selected",
+                        selected,
+                        if selected == 1 { "comment" } else { "comments" }
+                    ),
+                    Style::default().fg(text_muted()),
+                )
+            }
+        };
+        Paragraph::new(status).style(style).render(area, buf);
+    }
+}
@@ -0,0 +1,460 @@
+//! Session search dialog component
+//!
+//! Full-text search over prompts, assistant replies and tool output of
+//! Conduit sessions and imported agent histories.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use super::{
+    accent_primary, agent_claude, agent_codex, agent_gemini, agent_opencode, dialog_bg,
+    dialog_content_area, ensure_contrast_bg, ensure_contrast_fg, render_minimal_scrollbar,
+    selected_bg, text_muted, text_primary, DialogFrame, SearchableListState,
+};
+use crate::agent::AgentType;
+use crate::data::{SearchHit, SearchSource, SEARCH_MATCH_END, SEARCH_MATCH_START};
+
+// ============ Dialog Sizing Constants ============
+/// Dialog width as percentage of screen (0-100)
+const DIALOG_WIDTH_PERCENT: u16 = 80;
+/// Dialog height as percentage of screen (0-100)
+const DIALOG_HEIGHT_PERCENT: u16 = 70;
+/// Minimum dialog width
+const DIALOG_MIN_WIDTH: u16 = 60;
+/// Maximum dialog width
+const DIALOG_MAX_WIDTH: u16 = 120;
+/// Minimum dialog height
+const DIALOG_MIN_HEIGHT: u16 = 15;
+/// Maximum dialog height
+const DIALOG_MAX_HEIGHT: u16 = 40;
+/// Maximum width of the session title column
+const TITLE_MAX_WIDTH: usize = 32;
+
+/// Calculate the dialog area for a screen area
+fn calculate_dialog_area(area: Rect) -> Rect {
+    let width = (area.width * DIALOG_WIDTH_PERCENT / 100)
+        .clamp(DIALOG_MIN_WIDTH, DIALOG_MAX_WIDTH)
+        .min(area.width.saturating_sub(4));
+    let height = (area.height * DIALOG_HEIGHT_PERCENT / 100)
+        .clamp(DIALOG_MIN_HEIGHT, DIALOG_MAX_HEIGHT)
+        .min(area.height.saturating_sub(2));
+
+    Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Calculate the list area inside the dialog
+fn calculate_list_area(area: Rect) -> Rect {
+    let inner = dialog_content_area(calculate_dialog_area(area));
+    // List starts after: search (1) + status (1) + separator (1) = 3 rows
+    // Bottom has: spacing (1)
+    Rect {
+        x: inner.x,
+        y: inner.y + 3,
+        width: inner.width,
+        height: inner.height.saturating_sub(4),
+    }
+}
+
+/// State for the session search dialog
+#[derive(Debug, Clone)]
+pub struct SessionSearchDialogState {
+    /// Whether the dialog is visible
+    pub visible: bool,
+    /// Query input and result selection
+    pub list: SearchableListState,
+    /// Results for the current query
+    pub hits: Vec<SearchHit>,
+    /// Whether the index is being refreshed
+    pub indexing: bool,
+    /// Error from the last search or index refresh
+    pub error: Option<String>,
+    /// Spinner frame for the indexing animation
+    pub spinner_frame: usize,
+}
+
+impl Default for SessionSearchDialogState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionSearchDialogState {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            list: SearchableListState::new(10),
+            hits: Vec::new(),
+            indexing: false,
+            error: None,
+            spinner_frame: 0,
+        }
+    }
+
+    /// Advance the spinner animation
+    pub fn tick(&mut self) {
+        if self.indexing {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        }
+    }
+
+    /// Show the dialog (keeps the previous query so it can be refined)
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.error = None;
+        self.indexing = true;
+    }
+
+    /// Hide the dialog
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Check if dialog is visible
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Current query text
+    pub fn query(&self) -> &str {
+        self.list.search.value()
+    }
+
+    /// Mark the index refresh as finished
+    pub fn set_indexing(&mut self, indexing: bool) {
+        self.indexing = indexing;
+    }
+
+    /// Replace the results for the current query
+    pub fn set_results(&mut self, result: Result<Vec<SearchHit>, String>) {
+        match result {
+            Ok(hits) => {
+                self.error = None;
+                self.list.set_filtered((0..hits.len()).collect());
+                self.hits = hits;
+            }
+            Err(error) => {
+                self.error = Some(error);
+                self.hits.clear();
+                self.list.set_filtered(Vec::new());
+            }
+        }
+    }
+
+    /// Clear results (e.g. when the query is emptied)
+    pub fn clear_results(&mut self) {
+        self.set_results(Ok(Vec::new()));
+    }
+
+    /// Keep the page size in sync with the rendered list height
+    pub fn update_viewport(&mut self, area: Rect) {
+        self.list.max_visible = calculate_list_area(area).height.max(1) as usize;
+    }
+
+    // Delegate query input methods (the app re-runs the search afterwards)
+    pub fn insert_char(&mut self, c: char) {
+        self.list.search.insert_char(c);
+    }
+
+    pub fn delete_char(&mut self) {
+        self.list.search.delete_char();
+    }
+
+    pub fn delete_forward(&mut self) {
+        self.list.search.delete_forward();
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        self.list.search.move_left();
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        self.list.search.move_right();
+    }
+
+    pub fn move_cursor_start(&mut self) {
+        self.list.search.move_start();
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        self.list.search.move_end();
+    }
+
+    pub fn clear_search(&mut self) {
+        self.list.search.clear();
+    }
+
+    /// Select previous result
+    pub fn select_prev(&mut self) {
+        self.list.select_prev();
+    }
+
+    /// Select next result
+    pub fn select_next(&mut self) {
+        self.list.select_next();
+    }
+
+    /// Page up
+    pub fn page_up(&mut self) {
+        self.list.page_up();
+    }
+
+    /// Page down
+    pub fn page_down(&mut self) {
+        self.list.page_down();
+    }
+
+    /// Get the currently selected result
+    pub fn selected_hit(&self) -> Option<&SearchHit> {
+        self.list
+            .filtered
+            .get(self.list.selected)
+            .and_then(|&idx| self.hits.get(idx))
+    }
+}
+
+/// Split a snippet into spans, highlighting the matched terms.
+fn snippet_spans(snippet: &str, base: Style, highlight: Style) -> Vec<Span<'static>> {
+    let flattened = snippet.replace(['\n', '\r', '\t'], " ");
+    let mut spans = Vec::new();
+    let mut rest = flattened.as_str();
+    while let Some(start) = rest.find(SEARCH_MATCH_START) {
+        if start > 0 {
+            spans.push(Span::styled(rest[..start].to_string(), base));
+        }
+        rest = &rest[start + SEARCH_MATCH_START.len()..];
+        let end = rest.find(SEARCH_MATCH_END).unwrap_or(rest.len());
+        spans.push(Span::styled(rest[..end].to_string(), highlight));
+        rest = rest.get(end + SEARCH_MATCH_END.len()..).unwrap_or_default();
+    }
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_string(), base));
+    }
+    spans
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", truncated)
+}
+
+/// Session search dialog widget
+pub struct SessionSearchDialog;
+
+impl SessionSearchDialog {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render the dialog
+    pub fn render(&self, area: Rect, buf: &mut Buffer, state: &SessionSearchDialogState) {
+        if !state.visible {
+            return;
+        }
+
+        let dialog_area = calculate_dialog_area(area);
+        let frame = DialogFrame::new("Search Sessions", dialog_area.width, dialog_area.height)
+            .instructions(vec![
+                ("↑↓", "Navigate"),
+                ("Enter", "Open"),
+                ("Esc", "Close"),
+            ]);
+        let inner = frame.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Query
+            Constraint::Length(1), // Status
+            Constraint::Length(1), // Separator
+            Constraint::Min(1),    // Results
+            Constraint::Length(1), // Spacing
+        ])
+        .split(inner);
+
+        // Query with placeholder
+        let query_display = if state.list.search.is_empty() {
+            "Search: (prompts, replies, tool output)".to_string()
+        } else {
+            format!("Search: {}", state.list.search.value())
+        };
+        let query_style = if state.list.search.is_empty() {
+            Style::default().fg(text_muted())
+        } else {
+            Style::default().fg(text_primary())
+        };
+        Paragraph::new(query_display)
+            .style(query_style)
+            .render(chunks[0], buf);
+
+        let cursor_x = chunks[0].x + 8 + state.list.search.cursor as u16;
+        if cursor_x < chunks[0].x + chunks[0].width {
+            buf[(cursor_x, chunks[0].y)]
+                .set_style(Style::default().add_modifier(Modifier::REVERSED));
+        }
+
+        self.render_status(chunks[1], buf, state);
+
+        let separator = "─".repeat(inner.width as usize);
+        Paragraph::new(separator)
+            .style(Style::default().fg(Color::DarkGray))
+            .render(chunks[2], buf);
+
+        let list_area = chunks[3];
+        if let Some(ref error) = state.error {
+            Paragraph::new(error.as_str())
+                .style(Style::default().fg(Color::Red))
+                .alignment(Alignment::Center)
+                .render(list_area, buf);
+        } else if state.hits.is_empty() {
+            let message = if state.list.search.is_empty() {
+                "Type to search all sessions"
+            } else {
+                "No matches"
+            };
+            Paragraph::new(message)
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center)
+                .render(list_area, buf);
+        } else {
+            self.render_results(list_area, buf, state);
+        }
+    }
+
+    fn render_status(&self, area: Rect, buf: &mut Buffer, state: &SessionSearchDialogState) {
+        let (text, style) = if state.indexing {
+            const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+            let spinner = SPINNER_FRAMES[state.spinner_frame % SPINNER_FRAMES.len()];
+            (
+                format!("{} Updating index...", spinner),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if state.list.search.is_empty() {
+            (String::new(), Style::default())
+        } else {
+            let count = state.hits.len();
+            (
+                format!("{} {}", count, if count == 1 { "match" } else { "matches" }),
+                Style::default().fg(text_muted()),
+            )
+        };
+        Paragraph::new(text).style(style).render(area, buf);
+    }
+
+    fn render_results(&self, area: Rect, buf: &mut Buffer, state: &SessionSearchDialogState) {
+        let visible_count = area.height as usize;
+        let row_selected_bg = ensure_contrast_bg(selected_bg(), dialog_bg(), 3.0);
+        let row_selected_fg = ensure_contrast_fg(text_primary(), row_selected_bg, 4.5);
+        let title_width = TITLE_MAX_WIDTH.min(area.width as usize / 3);
+
+        for (i, &hit_idx) in state
+            .list
+            .filtered
+            .iter()
+            .skip(state.list.scroll_offset)
+            .take(visible_count)
+            .enumerate()
+        {
+            let hit = &state.hits[hit_idx];
+            let is_selected = state.list.scroll_offset + i == state.list.selected;
+            let row_style = if is_selected {
+                Style::default().bg(row_selected_bg)
+            } else {
+                Style::default()
+            };
+            let primary = if is_selected {
+                row_selected_fg
+            } else {
+                text_primary()
+            };
+            let muted = if is_selected {
+                row_selected_fg
+            } else {
+                text_muted()
+            };
+
+            let (agent_icon, agent_color) = match hit.document.agent_type {
+                AgentType::Claude => ("C", agent_claude()),
+                AgentType::Codex => ("X", agent_codex()),
+                AgentType::Gemini => ("G", agent_gemini()),
+                AgentType::Opencode => ("O", agent_opencode()),
+            };
+            let source_marker = match hit.document.source {
+                SearchSource::Conduit => " ",
+                SearchSource::External => "↓",
+            };
+            let title = truncate(&hit.document.title, title_width);
+
+            let mut spans = vec![
+                Span::styled(
+                    if is_selected { "> " } else { "  " },
+                    Style::default().fg(primary),
+                ),
+                Span::styled("[", Style::default().fg(muted)),
+                Span::styled(
+                    agent_icon,
+                    Style::default()
+                        .fg(if is_selected { primary } else { agent_color })
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("]", Style::default().fg(muted)),
+                Span::styled(source_marker, Style::default().fg(muted)),
+                Span::styled(
+                    format!("{:<width$}", title, width = title_width),
+                    Style::default().fg(primary),
+                ),
+                Span::styled(format!(" {:<9} ", hit.role), Style::default().fg(muted)),
+            ];
+            spans.extend(snippet_spans(
+                &hit.snippet,
+                Style::default().fg(muted),
+                Style::default()
+                    .fg(if is_selected {
+                        primary
+                    } else {
+                        accent_primary()
+                    })
+                    .add_modifier(Modifier::BOLD),
+            ));
+
+            let row = Rect {
+                x: area.x,
+                y: area.y + i as u16,
+                width: area.width.saturating_sub(1),
+                height: 1,
+            };
+            Paragraph::new(Line::from(spans))
+                .style(row_style)
+                .render(row, buf);
+        }
+
+        render_minimal_scrollbar(
+            Rect {
+                x: area.x + area.width - 1,
+                y: area.y,
+                width: 1,
+                height: area.height,
+            },
+            buf,
+            state.list.filtered.len(),
+            visible_count,
+            state.list.scroll_offset,
+        );
+    }
+}
+
+impl Default for SessionSearchDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
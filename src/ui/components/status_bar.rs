@@ -7,14 +7,12 @@ use ratatui::{
     text::{Line, Span},
 };
 
-use crate::agent::{
-    events::ContextWindowState, AgentMode, AgentType, ModelRegistry, SessionId, TokenUsage,
-};
+use crate::agent::{events::ContextWindowState, AgentMode, AgentType, ModelRegistry, SessionId};
 use crate::git::{CheckState, GitDiffStats, MergeReadiness, MergeableStatus, PrState, PrStatus};
 use crate::ui::components::{
     accent_error, accent_primary, accent_secondary, accent_success, accent_warning, pr_closed_bg,
     pr_draft_bg, pr_merged_bg, pr_open_bg, pr_unknown_bg, status_bar_bg, text_bright, text_faint,
-    text_muted,
+    text_muted, FindStatus,
 };
 use ratatui::style::Color;
 
@@ -28,8 +26,10 @@ pub struct StatusBar {
     model: Option<String>,
     shell_mode: bool,
     session_id: Option<SessionId>,
-    token_usage: TokenUsage,
+    /// Estimated session cost in USD
     estimated_cost: f64,
+    /// Whether to show the estimated cost
+    show_cost: bool,
     /// Whether to show performance metrics
     show_metrics: bool,
     /// Repository name (from git remote or directory)
@@ -66,6 +66,8 @@ pub struct StatusBar {
     supports_plan_mode: bool,
     /// Spinner frame index (shared animation tick)
     spinner_frame: usize,
+    /// Match counter while finding in the chat view
+    find_status: Option<FindStatus>,
 }
 
 impl StatusBar {
@@ -76,8 +78,8 @@ impl StatusBar {
             model: None,
             shell_mode: false,
             session_id: None,
-            token_usage: TokenUsage::default(),
             estimated_cost: 0.0,
+            show_cost: true,
             show_metrics: false,
             repo_name: None,
             branch_name: None,
@@ -96,6 +98,7 @@ impl StatusBar {
             queue_count: 0,
             supports_plan_mode: false,
             spinner_frame: 0,
+            find_status: None,
         }
     }
 
@@ -119,9 +122,12 @@ impl StatusBar {
         self.shell_mode = shell_mode;
     }
 
-    pub fn set_token_usage(&mut self, usage: TokenUsage) {
-        self.token_usage = usage;
-        self.update_cost();
+    pub fn set_estimated_cost(&mut self, cost: f64) {
+        self.estimated_cost = cost;
+    }
+
+    pub fn set_show_cost(&mut self, show: bool) {
+        self.show_cost = show;
     }
 
     pub fn set_context_state(&mut self, state: ContextWindowState) {
@@ -141,6 +147,11 @@ impl StatusBar {
         self.spinner_frame = frame;
     }
 
+    /// Set find match counter (None hides it)
+    pub fn set_find_status(&mut self, status: Option<FindStatus>) {
+        self.find_status = status;
+    }
+
     /// Set PR status for display
     pub fn set_pr_status(&mut self, status: Option<PrStatus>) {
         self.pr_status = status;
@@ -203,11 +214,12 @@ impl StatusBar {
         self.scroll_active = scroll_active;
     }
 
-    fn update_cost(&mut self) {
-        // Claude Sonnet pricing: $3/1M input, $15/1M output
-        let input_cost = (self.token_usage.input_tokens as f64 / 1_000_000.0) * 3.0;
-        let output_cost = (self.token_usage.output_tokens as f64 / 1_000_000.0) * 15.0;
-        self.estimated_cost = input_cost + output_cost;
+    fn format_cost(cost: f64) -> String {
+        if cost < 0.01 {
+            "<$0.01".to_string()
+        } else {
+            format!("${:.2}", cost)
+        }
     }
 
     #[allow(dead_code)]
@@ -301,6 +313,30 @@ impl StatusBar {
         //     }
         // }
 
+        // Estimated session cost
+        if self.show_cost && self.estimated_cost > 0.0 {
+            spans.push(Span::styled(" │ ", Style::default().fg(text_faint())));
+            spans.push(Span::styled(
+                Self::format_cost(self.estimated_cost),
+                Style::default().fg(text_muted()),
+            ));
+        }
+
+        // Find match counter
+        if let Some(find) = self.find_status {
+            spans.push(Span::styled(" │ ", Style::default().fg(text_faint())));
+            match find.current {
+                Some(current) => spans.push(Span::styled(
+                    format!("Find {}/{}", current, find.total),
+                    Style::default().fg(accent_warning()),
+                )),
+                None => spans.push(Span::styled(
+                    "Find: no matches",
+                    Style::default().fg(text_muted()),
+                )),
+            }
+        }
+
         // Note: Old processing spinner removed - now using Knight Rider spinner in footer
 
         // Performance metrics (when enabled)
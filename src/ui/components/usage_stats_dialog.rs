@@ -0,0 +1,508 @@
+//! Usage statistics dialog component
+//!
+//! Recorded tokens, cost, turns and turn durations, broken down per day,
+//! agent, model, repository and workspace with simple bar charts.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use super::{accent_primary, dialog_content_area, text_muted, text_primary, DialogFrame};
+use crate::agent::events::ContextWindowState;
+use crate::core::services::{UsageRow, UsageStats};
+use crate::data::{UsageDimension, UsageTotals};
+
+// ============ Dialog Sizing Constants ============
+/// Dialog width as percentage of screen (0-100)
+const DIALOG_WIDTH_PERCENT: u16 = 80;
+/// Dialog height as percentage of screen (0-100)
+const DIALOG_HEIGHT_PERCENT: u16 = 70;
+/// Minimum dialog width
+const DIALOG_MIN_WIDTH: u16 = 60;
+/// Maximum dialog width
+const DIALOG_MAX_WIDTH: u16 = 120;
+/// Minimum dialog height
+const DIALOG_MIN_HEIGHT: u16 = 15;
+/// Maximum dialog height
+const DIALOG_MAX_HEIGHT: u16 = 40;
+/// Maximum width of the row label column
+const LABEL_MAX_WIDTH: usize = 36;
+/// Width of the numeric columns after the bar
+const VALUES_WIDTH: usize = 38;
+
+/// Calculate the dialog area for a screen area
+fn calculate_dialog_area(area: Rect) -> Rect {
+    let width = (area.width * DIALOG_WIDTH_PERCENT / 100)
+        .clamp(DIALOG_MIN_WIDTH, DIALOG_MAX_WIDTH)
+        .min(area.width.saturating_sub(4));
+    let height = (area.height * DIALOG_HEIGHT_PERCENT / 100)
+        .clamp(DIALOG_MIN_HEIGHT, DIALOG_MAX_HEIGHT)
+        .min(area.height.saturating_sub(2));
+
+    Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Calculate the rows area inside the dialog
+fn calculate_rows_area(area: Rect) -> Rect {
+    let inner = dialog_content_area(calculate_dialog_area(area));
+    // Rows start after: range (1) + totals (1) + spacing (1) + tabs (1) + separator (1) = 5
+    // Bottom has: spacing (1)
+    Rect {
+        x: inner.x,
+        y: inner.y + 5,
+        width: inner.width,
+        height: inner.height.saturating_sub(6),
+    }
+}
+
+/// Time range covered by the dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatsRange {
+    Today,
+    #[default]
+    Week,
+    Month,
+    AllTime,
+}
+
+impl StatsRange {
+    /// Number of local days covered (None = all time)
+    pub fn days(self) -> Option<u32> {
+        match self {
+            StatsRange::Today => Some(1),
+            StatsRange::Week => Some(7),
+            StatsRange::Month => Some(30),
+            StatsRange::AllTime => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StatsRange::Today => "Today",
+            StatsRange::Week => "Last 7 days",
+            StatsRange::Month => "Last 30 days",
+            StatsRange::AllTime => "All time",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            StatsRange::Today => StatsRange::Week,
+            StatsRange::Week => StatsRange::Month,
+            StatsRange::Month => StatsRange::AllTime,
+            StatsRange::AllTime => StatsRange::Today,
+        }
+    }
+}
+
+/// State for the usage statistics dialog
+#[derive(Debug, Clone)]
+pub struct UsageStatsDialogState {
+    /// Whether the dialog is visible
+    pub visible: bool,
+    /// Time range of the loaded stats
+    pub range: StatsRange,
+    /// Breakdown currently shown
+    pub dimension: UsageDimension,
+    /// Stats for the current range
+    pub stats: Option<UsageStats>,
+    /// Error from the last load
+    pub error: Option<String>,
+    /// First visible row
+    pub scroll_offset: usize,
+    /// Number of rows that fit in the dialog
+    pub max_visible: usize,
+}
+
+impl Default for UsageStatsDialogState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UsageStatsDialogState {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            range: StatsRange::default(),
+            dimension: UsageDimension::Day,
+            stats: None,
+            error: None,
+            scroll_offset: 0,
+            max_visible: 10,
+        }
+    }
+
+    /// Show the dialog (keeps the previous range and breakdown)
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.scroll_offset = 0;
+    }
+
+    /// Hide the dialog
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Check if dialog is visible
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Replace the stats for the current range
+    pub fn set_stats(&mut self, result: Result<UsageStats, String>) {
+        match result {
+            Ok(stats) => {
+                self.error = None;
+                self.stats = Some(stats);
+            }
+            Err(error) => {
+                self.error = Some(error);
+                self.stats = None;
+            }
+        }
+        self.clamp_scroll();
+    }
+
+    /// Switch to the next time range (the app reloads the stats afterwards)
+    pub fn cycle_range(&mut self) {
+        self.range = self.range.next();
+        self.scroll_offset = 0;
+    }
+
+    /// Show the next breakdown
+    pub fn next_dimension(&mut self) {
+        let index = self.dimension_index();
+        self.dimension = UsageDimension::ALL[(index + 1) % UsageDimension::ALL.len()];
+        self.scroll_offset = 0;
+    }
+
+    /// Show the previous breakdown
+    pub fn prev_dimension(&mut self) {
+        let len = UsageDimension::ALL.len();
+        let index = self.dimension_index();
+        self.dimension = UsageDimension::ALL[(index + len - 1) % len];
+        self.scroll_offset = 0;
+    }
+
+    fn dimension_index(&self) -> usize {
+        UsageDimension::ALL
+            .iter()
+            .position(|dimension| *dimension == self.dimension)
+            .unwrap_or(0)
+    }
+
+    /// Rows of the current breakdown
+    pub fn rows(&self) -> &[UsageRow] {
+        self.stats
+            .as_ref()
+            .map(|stats| stats.breakdown(self.dimension))
+            .unwrap_or_default()
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(lines);
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_add(lines);
+        self.clamp_scroll();
+    }
+
+    pub fn page_up(&mut self) {
+        self.scroll_up(self.max_visible.max(1));
+    }
+
+    pub fn page_down(&mut self) {
+        self.scroll_down(self.max_visible.max(1));
+    }
+
+    fn clamp_scroll(&mut self) {
+        let max_offset = self.rows().len().saturating_sub(self.max_visible.max(1));
+        self.scroll_offset = self.scroll_offset.min(max_offset);
+    }
+
+    /// Keep the page size in sync with the rendered rows height
+    pub fn update_viewport(&mut self, area: Rect) {
+        self.max_visible = calculate_rows_area(area).height.max(1) as usize;
+        self.clamp_scroll();
+    }
+}
+
+fn format_cost(cost: f64) -> String {
+    if cost > 0.0 && cost < 0.01 {
+        "<$0.01".to_string()
+    } else {
+        format!("${:.2}", cost)
+    }
+}
+
+fn format_duration_ms(ms: i64) -> String {
+    let secs = ms / 1000;
+    if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+fn format_tokens(totals: &UsageTotals) -> String {
+    format!(
+        "{}↓ {}↑",
+        ContextWindowState::format_tokens(totals.input_tokens),
+        ContextWindowState::format_tokens(totals.output_tokens)
+    )
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", truncated)
+}
+
+/// Bar of `width` cells filled in proportion to `value / max`
+fn bar(value: f64, max: f64, width: usize) -> String {
+    if width == 0 || max <= 0.0 {
+        return " ".repeat(width);
+    }
+    let filled = ((value / max) * width as f64).round() as usize;
+    let filled = filled.clamp(usize::from(value > 0.0), width);
+    format!("{}{}", "█".repeat(filled), " ".repeat(width - filled))
+}
+
+/// Usage statistics dialog widget
+pub struct UsageStatsDialog;
+
+impl UsageStatsDialog {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render the dialog
+    pub fn render(&self, area: Rect, buf: &mut Buffer, state: &UsageStatsDialogState) {
+        if !state.visible {
+            return;
+        }
+
+        let dialog_area = calculate_dialog_area(area);
+        let frame = DialogFrame::new("Usage Statistics", dialog_area.width, dialog_area.height)
+            .instructions(vec![
+                ("Tab", "Breakdown"),
+                ("r", "Range"),
+                ("↑↓", "Scroll"),
+                ("Esc", "Close"),
+            ]);
+        let inner = frame.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Range
+            Constraint::Length(1), // Totals
+            Constraint::Length(1), // Spacing
+            Constraint::Length(1), // Breakdown tabs
+            Constraint::Length(1), // Separator
+            Constraint::Min(1),    // Rows
+            Constraint::Length(1), // Spacing
+        ])
+        .split(inner);
+
+        let range = match state.stats.as_ref().and_then(|stats| stats.since) {
+            Some(since) => format!(
+                "{} (since {})",
+                state.range.label(),
+                since.with_timezone(&chrono::Local).format("%Y-%m-%d")
+            ),
+            None => state.range.label().to_string(),
+        };
+        Paragraph::new(Line::from(vec![
+            Span::styled("Range: ", Style::default().fg(text_muted())),
+            Span::styled(range, Style::default().fg(text_primary())),
+        ]))
+        .render(chunks[0], buf);
+
+        if let Some(stats) = &state.stats {
+            Paragraph::new(Self::totals_line(&stats.totals)).render(chunks[1], buf);
+        }
+
+        self.render_tabs(chunks[3], buf, state.dimension);
+
+        let separator = "─".repeat(inner.width as usize);
+        Paragraph::new(separator)
+            .style(Style::default().fg(Color::DarkGray))
+            .render(chunks[4], buf);
+
+        let rows_area = chunks[5];
+        if let Some(ref error) = state.error {
+            Paragraph::new(error.as_str())
+                .style(Style::default().fg(Color::Red))
+                .alignment(Alignment::Center)
+                .render(rows_area, buf);
+        } else if state.rows().is_empty() {
+            Paragraph::new("No usage recorded in this range")
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center)
+                .render(rows_area, buf);
+        } else {
+            self.render_rows(rows_area, buf, state);
+        }
+    }
+
+    fn totals_line(totals: &UsageTotals) -> Line<'static> {
+        let muted = Style::default().fg(text_muted());
+        let value = Style::default()
+            .fg(text_primary())
+            .add_modifier(Modifier::BOLD);
+        let mut spans = vec![
+            Span::styled(format_cost(totals.cost_usd), value),
+            Span::styled(" · ", muted),
+            Span::styled(totals.turns.to_string(), value),
+            Span::styled(if totals.turns == 1 { " turn" } else { " turns" }, muted),
+            Span::styled(" · ", muted),
+            Span::styled(format_tokens(totals), value),
+            Span::styled(" tokens · ", muted),
+            Span::styled(
+                ContextWindowState::format_tokens(totals.cached_tokens),
+                value,
+            ),
+            Span::styled(" cached", muted),
+        ];
+        if let Some(average) = totals.average_duration_ms() {
+            spans.push(Span::styled(" · avg ", muted));
+            spans.push(Span::styled(format_duration_ms(average), value));
+            spans.push(Span::styled("/turn", muted));
+        }
+        Line::from(spans)
+    }
+
+    fn render_tabs(&self, area: Rect, buf: &mut Buffer, active: UsageDimension) {
+        let mut spans = Vec::new();
+        for dimension in UsageDimension::ALL {
+            let style = if dimension == active {
+                Style::default()
+                    .fg(accent_primary())
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                Style::default().fg(text_muted())
+            };
+            spans.push(Span::styled(dimension.label(), style));
+            spans.push(Span::raw("  "));
+        }
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+
+    fn render_rows(&self, area: Rect, buf: &mut Buffer, state: &UsageStatsDialogState) {
+        let rows = state.rows();
+        let label_width = LABEL_MAX_WIDTH.min(area.width as usize / 3);
+        let bar_width = (area.width as usize).saturating_sub(label_width + VALUES_WIDTH + 2);
+
+        // Scale bars by cost, or by turns when no turn had a known price
+        let max_cost = rows
+            .iter()
+            .map(|row| row.totals.cost_usd)
+            .fold(0.0, f64::max);
+        let by_cost = max_cost > 0.0;
+        let max_value = if by_cost {
+            max_cost
+        } else {
+            rows.iter().map(|row| row.totals.turns).max().unwrap_or(0) as f64
+        };
+
+        for (i, row) in rows
+            .iter()
+            .skip(state.scroll_offset)
+            .take(area.height as usize)
+            .enumerate()
+        {
+            let totals = &row.totals;
+            let value = if by_cost {
+                totals.cost_usd
+            } else {
+                totals.turns as f64
+            };
+            let duration = totals
+                .average_duration_ms()
+                .map(format_duration_ms)
+                .unwrap_or_else(|| "-".to_string());
+            let line = Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{:<width$} ",
+                        truncate(&row.label, label_width),
+                        width = label_width
+                    ),
+                    Style::default().fg(text_primary()),
+                ),
+                Span::styled(
+                    bar(value, max_value, bar_width),
+                    Style::default().fg(accent_primary()),
+                ),
+                Span::styled(
+                    format!(
+                        " {:>8} {:>5}t {:>12} {:>8}",
+                        format_cost(totals.cost_usd),
+                        totals.turns,
+                        format_tokens(totals),
+                        duration
+                    ),
+                    Style::default().fg(text_muted()),
+                ),
+            ]);
+            let row_area = Rect {
+                x: area.x,
+                y: area.y + i as u16,
+                width: area.width,
+                height: 1,
+            };
+            Paragraph::new(line).render(row_area, buf);
+        }
+    }
+}
+
+impl Default for UsageStatsDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_scales_to_width() {
+        assert_eq!(bar(10.0, 10.0, 4), "████");
+        assert_eq!(bar(5.0, 10.0, 4), "██  ");
+        // Non-zero values always get at least one cell
+        assert_eq!(bar(0.01, 10.0, 4), "█   ");
+        assert_eq!(bar(0.0, 10.0, 4), "    ");
+        assert_eq!(bar(1.0, 0.0, 3), "   ");
+    }
+
+    #[test]
+    fn test_dimension_and_range_cycling() {
+        let mut state = UsageStatsDialogState::new();
+        assert_eq!(state.dimension, UsageDimension::Day);
+        state.prev_dimension();
+        assert_eq!(state.dimension, UsageDimension::Workspace);
+        state.next_dimension();
+        state.next_dimension();
+        assert_eq!(state.dimension, UsageDimension::Agent);
+
+        assert_eq!(state.range.days(), Some(7));
+        state.cycle_range();
+        state.cycle_range();
+        assert_eq!(state.range.days(), None);
+        state.cycle_range();
+        assert_eq!(state.range, StatsRange::Today);
+    }
+}
@@ -0,0 +1,456 @@
+//! Diff viewer session for reviewing workspace changes in a tab
+//!
+//! This module provides the DiffViewerSession struct which holds the state
+//! for a workspace diff tab: the loaded diff, the selected file and scrolling,
+//! plus per-hunk staging and reverting.
+
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use crate::git::{DiffBase, DiffHunk, FileDiff, HunkAction, WorkspaceDiff};
+
+/// How hunks are laid out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffLayout {
+    #[default]
+    Unified,
+    SideBySide,
+}
+
+impl DiffLayout {
+    pub fn toggle(self) -> Self {
+        match self {
+            DiffLayout::Unified => DiffLayout::SideBySide,
+            DiffLayout::SideBySide => DiffLayout::Unified,
+        }
+    }
+}
+
+/// Which files are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffScope {
+    /// Every changed file in the workspace
+    #[default]
+    All,
+    /// Only files the agent touched during the last turn
+    LastTurn,
+}
+
+impl DiffScope {
+    pub fn toggle(self) -> Self {
+        match self {
+            DiffScope::All => DiffScope::LastTurn,
+            DiffScope::LastTurn => DiffScope::All,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiffScope::All => "all changes",
+            DiffScope::LastTurn => "last turn",
+        }
+    }
+}
+
+/// State for a diff viewer tab
+#[derive(Debug)]
+pub struct DiffViewerSession {
+    /// Unique identifier for this session
+    pub id: Uuid,
+    /// Workspace the diff belongs to
+    pub workspace_id: Option<Uuid>,
+    /// Workspace root the diff is computed in
+    pub working_dir: PathBuf,
+    /// Workspace name (for the tab title)
+    pub workspace_name: Option<String>,
+    /// Session whose last turn is under review
+    pub session_id: Option<Uuid>,
+    /// What the working tree is compared against
+    pub base: DiffBase,
+    /// Unified or side-by-side hunks
+    pub layout: DiffLayout,
+    /// All files or only those touched in the last turn
+    pub scope: DiffScope,
+    /// Files the agent touched during the last turn
+    turn_paths: Vec<String>,
+    /// Last loaded diff
+    diff: Option<WorkspaceDiff>,
+    /// Error from the last load
+    pub error: Option<String>,
+    /// Index of the selected file
+    pub selected_file: usize,
+    /// Current scroll offset (in rows) within the selected file
+    pub scroll_offset: usize,
+}
+
+impl DiffViewerSession {
+    /// Create a diff viewer for a workspace and load its uncommitted changes
+    pub fn new(
+        workspace_id: Option<Uuid>,
+        working_dir: PathBuf,
+        workspace_name: Option<String>,
+    ) -> Self {
+        let mut session = Self {
+            id: Uuid::new_v4(),
+            workspace_id,
+            working_dir,
+            workspace_name,
+            session_id: None,
+            base: DiffBase::Head,
+            layout: DiffLayout::Unified,
+            scope: DiffScope::All,
+            turn_paths: Vec::new(),
+            diff: None,
+            error: None,
+            selected_file: 0,
+            scroll_offset: 0,
+        };
+        session.reload();
+        session
+    }
+
+    /// Get display name for the tab
+    pub fn tab_name(&self) -> String {
+        match &self.workspace_name {
+            Some(name) => format!("Diff: {}", name),
+            None => "Diff".to_string(),
+        }
+    }
+
+    /// Review the files a session touched during its last turn.
+    ///
+    /// Falls back to showing all changes when the turn touched nothing.
+    pub fn review_turn(&mut self, session_id: Uuid, paths: Vec<String>) {
+        self.session_id = Some(session_id);
+        self.scope = if paths.is_empty() {
+            DiffScope::All
+        } else {
+            DiffScope::LastTurn
+        };
+        self.turn_paths = paths;
+        self.selected_file = 0;
+        self.scroll_offset = 0;
+        self.reload();
+    }
+
+    /// Replace the last turn's files, keeping the current scope
+    pub fn update_turn_paths(&mut self, paths: Vec<String>) {
+        self.turn_paths = paths;
+        self.reload();
+    }
+
+    /// Files touched during the last turn
+    pub fn turn_paths(&self) -> &[String] {
+        &self.turn_paths
+    }
+
+    /// Recompute the diff, keeping the selected file where possible
+    pub fn reload(&mut self) {
+        let selected_path = self.selected_file().map(|file| file.path.clone());
+        match WorkspaceDiff::load(&self.working_dir, self.base) {
+            Ok(mut diff) => {
+                if self.scope == DiffScope::LastTurn {
+                    diff.retain_paths(&self.working_dir, &self.turn_paths);
+                }
+                self.selected_file = selected_path
+                    .and_then(|path| diff.files.iter().position(|file| file.path == path))
+                    .unwrap_or(0);
+                self.diff = Some(diff);
+                self.error = None;
+            }
+            Err(e) => {
+                self.diff = None;
+                self.error = Some(e.to_string());
+                self.selected_file = 0;
+            }
+        }
+        self.scroll_offset = self.scroll_offset.min(self.total_rows().saturating_sub(1));
+    }
+
+    /// The loaded diff, if loading succeeded
+    pub fn diff(&self) -> Option<&WorkspaceDiff> {
+        self.diff.as_ref()
+    }
+
+    /// Changed files
+    pub fn files(&self) -> &[FileDiff] {
+        self.diff
+            .as_ref()
+            .map(|diff| diff.files.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The file whose hunks are shown
+    pub fn selected_file(&self) -> Option<&FileDiff> {
+        self.files().get(self.selected_file)
+    }
+
+    /// Switch between uncommitted changes and changes since the base branch
+    pub fn toggle_base(&mut self) {
+        self.base = self.base.toggle();
+        self.selected_file = 0;
+        self.scroll_offset = 0;
+        self.reload();
+    }
+
+    /// Switch between all changes and the files touched in the last turn
+    pub fn toggle_scope(&mut self) {
+        self.scope = self.scope.toggle();
+        self.selected_file = 0;
+        self.scroll_offset = 0;
+        self.reload();
+    }
+
+    /// Switch between unified and side-by-side hunks
+    pub fn toggle_layout(&mut self) {
+        self.layout = self.layout.toggle();
+        self.scroll_offset = 0;
+    }
+
+    /// Select the next file (wrapping)
+    pub fn select_next_file(&mut self) {
+        let count = self.files().len();
+        if count > 0 {
+            self.selected_file = (self.selected_file + 1) % count;
+            self.scroll_offset = 0;
+        }
+    }
+
+    /// Select the previous file (wrapping)
+    pub fn select_prev_file(&mut self) {
+        let count = self.files().len();
+        if count > 0 {
+            self.selected_file = (self.selected_file + count - 1) % count;
+            self.scroll_offset = 0;
+        }
+    }
+
+    /// Number of rendered rows for one hunk (its header included)
+    fn hunk_rows(&self, hunk: &DiffHunk) -> usize {
+        1 + match self.layout {
+            DiffLayout::Unified => hunk.lines.len(),
+            DiffLayout::SideBySide => hunk.side_by_side().len(),
+        }
+    }
+
+    /// Number of rendered rows for the selected file (hunk headers included)
+    pub fn total_rows(&self) -> usize {
+        let Some(file) = self.selected_file() else {
+            return 0;
+        };
+        file.hunks.iter().map(|hunk| self.hunk_rows(hunk)).sum()
+    }
+
+    /// Row of the header of hunk `index` in the selected file
+    fn hunk_row_offset(&self, index: usize) -> usize {
+        let Some(file) = self.selected_file() else {
+            return 0;
+        };
+        file.hunks
+            .iter()
+            .take(index)
+            .map(|hunk| self.hunk_rows(hunk))
+            .sum()
+    }
+
+    /// Index of the hunk containing the top visible row
+    pub fn current_hunk(&self) -> Option<usize> {
+        let file = self.selected_file()?;
+        let mut row = 0;
+        for (index, hunk) in file.hunks.iter().enumerate() {
+            row += self.hunk_rows(hunk);
+            if self.scroll_offset < row {
+                return Some(index);
+            }
+        }
+        file.hunks.len().checked_sub(1)
+    }
+
+    /// Scroll to the next hunk, moving on to the next file after the last one
+    pub fn select_next_hunk(&mut self) {
+        let Some(current) = self.current_hunk() else {
+            self.select_next_file();
+            return;
+        };
+        let count = self.selected_file().map_or(0, |file| file.hunks.len());
+        if current + 1 < count {
+            self.scroll_offset = self.hunk_row_offset(current + 1);
+        } else if self.files().len() > 1 {
+            self.select_next_file();
+        }
+    }
+
+    /// Scroll to the previous hunk, moving back to the previous file's last hunk
+    pub fn select_prev_hunk(&mut self) {
+        match self.current_hunk() {
+            Some(current) if self.scroll_offset > self.hunk_row_offset(current) => {
+                self.scroll_offset = self.hunk_row_offset(current);
+            }
+            Some(current) if current > 0 => {
+                self.scroll_offset = self.hunk_row_offset(current - 1);
+            }
+            _ if self.files().len() > 1 => {
+                self.select_prev_file();
+                let last = self
+                    .selected_file()
+                    .map_or(0, |file| file.hunks.len().saturating_sub(1));
+                self.scroll_offset = self.hunk_row_offset(last);
+            }
+            _ => {}
+        }
+    }
+
+    /// Stage or revert the current hunk and reload the diff
+    pub fn apply_current_hunk(&mut self, action: HunkAction) -> Result<(), String> {
+        let (Some(diff), Some(file), Some(index)) =
+            (self.diff(), self.selected_file(), self.current_hunk())
+        else {
+            return Err("No hunk selected".to_string());
+        };
+        diff.apply_hunk(&self.working_dir, &file.path, index, action)
+            .map_err(|e| e.to_string())?;
+        let offset = self.hunk_row_offset(index);
+        self.reload();
+        // Stay on the same hunk position (a reverted hunk disappears, so
+        // the next one slides into place)
+        self.scroll_offset = offset.min(self.total_rows().saturating_sub(1));
+        Ok(())
+    }
+
+    /// Scroll up by N rows
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(rows);
+    }
+
+    /// Scroll down by N rows, keeping at least one row visible
+    pub fn scroll_down(&mut self, rows: usize) {
+        let max_scroll = self.total_rows().saturating_sub(1);
+        self.scroll_offset = (self.scroll_offset + rows).min(max_scroll);
+    }
+
+    /// Scroll to the first row
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    /// Scroll to the end (uses default visible height approximation)
+    pub fn scroll_to_bottom(&mut self) {
+        let default_visible = 30;
+        self.scroll_offset = self.total_rows().saturating_sub(default_visible);
+    }
+
+    /// Page up (scroll by default page size)
+    pub fn page_up(&mut self) {
+        self.scroll_up(20);
+    }
+
+    /// Page down (scroll by default page size)
+    pub fn page_down(&mut self) {
+        self.scroll_down(20);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn git(path: &std::path::Path, args: &[&str]) {
+        Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_navigation_and_reload() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        git(path, &["init"]);
+        git(path, &["config", "user.email", "test@test.com"]);
+        git(path, &["config", "user.name", "Test"]);
+        std::fs::write(path.join("a.txt"), "a\n").unwrap();
+        git(path, &["add", "."]);
+        git(path, &["commit", "-m", "Initial commit"]);
+
+        std::fs::write(path.join("a.txt"), "a\nb\n").unwrap();
+        std::fs::write(path.join("b.txt"), "new\n").unwrap();
+
+        let mut session = DiffViewerSession::new(None, path.to_path_buf(), Some("ws".into()));
+        assert_eq!(session.tab_name(), "Diff: ws");
+        assert!(session.error.is_none());
+        assert_eq!(session.files().len(), 2);
+        // One hunk header plus two lines
+        assert_eq!(session.total_rows(), 3);
+
+        session.select_next_file();
+        assert_eq!(session.selected_file().unwrap().path, "b.txt");
+        session.select_next_file();
+        assert_eq!(session.selected_file().unwrap().path, "a.txt");
+        session.select_prev_file();
+
+        // The selection follows the file across reloads
+        std::fs::write(path.join("0.txt"), "first\n").unwrap();
+        session.reload();
+        assert_eq!(session.files().len(), 3);
+        assert_eq!(session.selected_file().unwrap().path, "b.txt");
+
+        session.scroll_down(100);
+        assert_eq!(session.scroll_offset, session.total_rows() - 1);
+        session.toggle_layout();
+        assert_eq!(session.layout, DiffLayout::SideBySide);
+        assert_eq!(session.scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_hunk_navigation_scope_and_revert() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        git(path, &["init"]);
+        git(path, &["config", "user.email", "test@test.com"]);
+        git(path, &["config", "user.name", "Test"]);
+        let original: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        std::fs::write(path.join("a.txt"), &original).unwrap();
+        std::fs::write(path.join("b.txt"), "b\n").unwrap();
+        git(path, &["add", "."]);
+        git(path, &["commit", "-m", "Initial commit"]);
+
+        // Two hunks far enough apart not to merge
+        let edited = original
+            .replace("line 2\n", "line two\n")
+            .replace("line 19\n", "line nineteen\n");
+        std::fs::write(path.join("a.txt"), &edited).unwrap();
+        std::fs::write(path.join("b.txt"), "b changed\n").unwrap();
+
+        let mut session = DiffViewerSession::new(None, path.to_path_buf(), None);
+        assert_eq!(session.files().len(), 2);
+        session.review_turn(
+            Uuid::new_v4(),
+            vec![path.join("a.txt").display().to_string()],
+        );
+        assert_eq!(session.scope, DiffScope::LastTurn);
+        assert_eq!(session.files().len(), 1);
+        assert_eq!(session.selected_file().unwrap().hunks.len(), 2);
+
+        assert_eq!(session.current_hunk(), Some(0));
+        session.select_next_hunk();
+        assert_eq!(session.current_hunk(), Some(1));
+        session.select_prev_hunk();
+        assert_eq!(session.current_hunk(), Some(0));
+
+        session.apply_current_hunk(HunkAction::Revert).unwrap();
+        let contents = std::fs::read_to_string(path.join("a.txt")).unwrap();
+        assert!(contents.contains("line 2\n"));
+        assert!(contents.contains("line nineteen\n"));
+        assert_eq!(session.selected_file().unwrap().hunks.len(), 1);
+
+        session.apply_current_hunk(HunkAction::Stage).unwrap();
+        assert!(session.selected_file().unwrap().hunks[0].staged);
+
+        session.toggle_scope();
+        assert_eq!(session.files().len(), 2);
+    }
+}
@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
 use crate::agent::{AgentStartConfig, AgentType};
+use crate::core::services::{RaceContender, Rewind, TurnCheckpoint};
+use crate::data::SearchHit;
+use crate::git::SyncStrategy;
 use crate::session::ExternalSession;
 use uuid::Uuid;
 
@@ -11,6 +14,17 @@ pub enum Effect {
         session_id: Uuid,
         agent_type: AgentType,
         config: AgentStartConfig,
+        /// Recorded before the agent starts on the prompt
+        checkpoint: Option<TurnCheckpoint>,
+    },
+    /// Send a prompt to the session's agent in the attached daemon
+    DaemonPrompt {
+        session_id: Uuid,
+        prompt: String,
+        working_dir: PathBuf,
+        model: Option<String>,
+        hidden: bool,
+        images: Vec<PathBuf>,
     },
     PrPreflight {
         tab_index: usize,
@@ -20,6 +34,8 @@ pub enum Effect {
         working_dir: PathBuf,
     },
     DumpDebugState,
+    /// Export the active session transcript to ~/.conduit/exports
+    ExportTranscript,
     CreateWorkspace {
         repo_id: Uuid,
     },
@@ -31,6 +47,55 @@ pub enum Effect {
         workspace_id: Uuid,
         delete_remote: bool,
     },
+    /// Create one workspace per contender for a race from `workspace_id`'s branch
+    StartRace {
+        workspace_id: Uuid,
+        prompt: String,
+        contenders: Vec<RaceContender>,
+    },
+    /// Generate a commit message from a workspace's pending changes
+    GenerateCommitMessage {
+        workspace_id: Uuid,
+        working_dir: PathBuf,
+    },
+    /// Fetch unresolved review comments on a workspace's PR
+    LoadReviewComments {
+        workspace_id: Uuid,
+        working_dir: PathBuf,
+    },
+    /// Fetch the failing CI checks on a workspace's PR; `auto` when the git
+    /// tracker triggered it rather than the user
+    FetchCiFailures {
+        workspace_id: Uuid,
+        working_dir: PathBuf,
+        auto: bool,
+    },
+    /// Start, continue or abort syncing a workspace with its base branch
+    SyncWorkspace {
+        workspace_id: Uuid,
+        working_dir: PathBuf,
+        step: SyncStep,
+    },
+    /// Commit a workspace's pending changes, optionally pushing afterwards
+    CommitWorkspace {
+        workspace_id: Uuid,
+        working_dir: PathBuf,
+        message: String,
+        push: bool,
+    },
+    /// Restore a session's workspace to a turn checkpoint
+    RewindTurn {
+        session_id: Uuid,
+        turn: u32,
+        rewind: Rewind,
+        truncate: bool,
+    },
+    /// Clone a remote repository and add it as a project
+    CloneRepository {
+        url: String,
+        name: String,
+        destination: PathBuf,
+    },
     RemoveProject {
         repo_id: Uuid,
     },
@@ -39,6 +104,10 @@ pub enum Effect {
     DiscoverSessions,
     /// Import an external session
     ImportSession(ExternalSession),
+    /// Bring the full-text search index up to date
+    RefreshSearchIndex,
+    /// Open a search result in a tab, scrolled to the matching message
+    OpenSearchHit(SearchHit),
     /// Generate session title and branch name from first message
     GenerateTitleAndBranch {
         /// Stable session ID for correlation (avoids stale tab_index after close/reorder)
@@ -56,3 +125,11 @@ pub enum Effect {
         working_dir: Option<PathBuf>,
     },
 }
+
+/// Step of a base branch sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStep {
+    Start(SyncStrategy),
+    Continue,
+    Abort,
+}
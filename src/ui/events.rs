@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use crate::agent::{AgentEvent, AgentInput, AgentType};
+use crate::core::{HookEvent, HookReport};
 use crate::git::PrPreflightResult;
 use crate::ui::git_tracker::GitTrackerUpdate;
 use tokio::sync::mpsc;
@@ -26,6 +27,17 @@ pub enum AppEvent {
     },
     /// Agent failed to start for a specific session
     AgentStartFailed { session_id: Uuid, error: String },
+    /// The attached daemon is running this session's agent
+    DaemonSessionAttached {
+        session_id: Uuid,
+        input_tx: mpsc::Sender<AgentInput>,
+        /// Some of the session's events could not be replayed
+        missed_events: bool,
+    },
+    /// Connection to the attached daemon was lost; the client is reconnecting
+    DaemonDisconnected,
+    /// Connection to the attached daemon is back
+    DaemonReconnected,
     /// Agent termination result (used for async termination feedback)
     AgentTerminationResult {
         session_id: Option<Uuid>,
@@ -86,6 +98,9 @@ pub enum AppEvent {
     /// Debug export completed
     DebugDumped { result: Result<String, String> },
 
+    /// Transcript export completed
+    TranscriptExported { result: Result<String, String> },
+
     /// Workspace creation completed
     WorkspaceCreated {
         repo_id: Uuid,
@@ -97,12 +112,83 @@ pub enum AppEvent {
         result: Result<ForkWorkspaceCreated, String>,
     },
 
+    /// A workspace hook printed a line
+    HookOutput {
+        workspace_name: String,
+        event: HookEvent,
+        line: String,
+    },
+
     /// Workspace archive completed
     WorkspaceArchived {
         workspace_id: Uuid,
         result: Result<WorkspaceArchived, String>,
     },
 
+    /// Race workspaces created
+    RaceCreated {
+        source_workspace_id: Uuid,
+        result: Result<crate::data::Race, String>,
+    },
+
+    /// Race test command finished in a contender's workspace
+    RaceTestsFinished {
+        race_id: Uuid,
+        workspace_id: Uuid,
+        result: Result<crate::data::RaceTestResult, String>,
+    },
+
+    /// Commit message generation finished
+    CommitMessageGenerated {
+        workspace_id: Uuid,
+        result: Result<crate::util::GeneratedCommitMessage, String>,
+    },
+
+    /// Workspace commit (and optional push) finished
+    WorkspaceCommitted {
+        workspace_id: Uuid,
+        result: Result<crate::core::services::CommitOutcome, String>,
+    },
+
+    /// Rewind to a turn checkpoint finished
+    TurnRewound {
+        session_id: Uuid,
+        turn: u32,
+        truncated: bool,
+        result: Result<(), String>,
+    },
+
+    /// PR review comments fetched
+    ReviewCommentsLoaded {
+        workspace_id: Uuid,
+        result: Result<crate::core::services::PendingReview, String>,
+    },
+
+    /// Failing CI checks fetched
+    CiFailuresFetched {
+        workspace_id: Uuid,
+        auto: bool,
+        result: Result<crate::git::CiFailures, String>,
+    },
+
+    /// Base branch sync step finished; `None` when the sync was aborted
+    WorkspaceSynced {
+        workspace_id: Uuid,
+        result: Result<Option<crate::git::SyncOutcome>, String>,
+    },
+
+    /// Progress of a repository clone
+    CloneProgress {
+        name: String,
+        progress: crate::git::CloneProgress,
+    },
+
+    /// Repository clone completed
+    RepositoryCloned {
+        name: String,
+        result: Result<Uuid, String>,
+    },
+
     /// Project removal completed
     ProjectRemoved { result: RemoveProjectResult },
 
@@ -122,6 +208,11 @@ pub enum AppEvent {
     /// Background session discovery complete
     SessionDiscoveryComplete,
 
+    /// Search index refresh completed
+    SearchIndexRefreshed {
+        result: Result<crate::core::services::IndexRefreshStats, String>,
+    },
+
     /// Git tracker update (PR status, git stats, branch changes)
     GitTracker(GitTrackerUpdate),
 
@@ -171,12 +262,16 @@ pub struct ShellCommandResult {
 pub struct WorkspaceCreated {
     pub repo_id: Uuid,
     pub workspace_id: Uuid,
+    /// Set when the `on_create` hooks failed
+    pub setup_failure: Option<HookReport>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ForkWorkspaceCreated {
     pub repo_id: Uuid,
     pub workspace_id: Uuid,
+    /// Set when the `on_create` hooks failed
+    pub setup_failure: Option<HookReport>,
 }
 
 #[derive(Debug, Clone)]
@@ -226,6 +321,12 @@ pub enum InputMode {
     ShowingHelp,
     /// Importing a session from external agent
     ImportingSession,
+    /// Searching across all sessions
+    SearchingSessions,
+    /// Finding text in the current chat transcript
+    FindingInChat,
+    /// Viewing usage statistics
+    ViewingUsageStats,
     /// Command palette is open
     CommandPalette,
     /// Slash command menu is open
@@ -234,6 +335,10 @@ pub enum InputMode {
     MissingTool,
     /// Editing queued messages inline
     QueueEditing,
+    /// Committing workspace changes
+    Committing,
+    /// Reviewing PR review comments
+    ReviewingComments,
 }
 
 /// View mode for the main content area
@@ -9,7 +9,7 @@ use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
-use crate::git::{GitDiffStats, PrManager, PrStatus};
+use crate::git::{CheckState, GitDiffStats, PrManager, PrState, PrStatus};
 
 /// Configuration for the background tracker
 pub struct GitTrackerConfig {
@@ -62,6 +62,9 @@ pub enum GitTrackerUpdate {
         workspace_id: Uuid,
         branch: Option<String>,
     },
+    /// CI checks on the workspace's open PR went from passing or pending to
+    /// failing. Sent after the matching `PrStatusChanged`.
+    ChecksFailed { workspace_id: Uuid, pr_number: u32 },
 }
 
 /// Commands to the background tracker
@@ -113,7 +116,6 @@ impl GitTrackerHandle {
     }
 
     /// Force an immediate refresh for a workspace
-    #[allow(dead_code)]
     pub fn refresh_now(&self, workspace_id: Uuid) {
         if self
             .cmd_tx
@@ -301,6 +303,9 @@ impl GitTracker {
                     (None, None) => false,
                 };
 
+                let checks_failed =
+                    checks_started_failing(state.pr_status.as_ref(), new_pr_status.as_ref());
+
                 if pr_changed {
                     state.pr_status = new_pr_status.clone();
                     state.last_pr_check = Some(Instant::now());
@@ -315,6 +320,16 @@ impl GitTracker {
                         "pr_status_changed",
                     );
                 }
+                if let Some(pr_number) = checks_failed {
+                    send_update(
+                        &update_tx,
+                        GitTrackerUpdate::ChecksFailed {
+                            workspace_id,
+                            pr_number,
+                        },
+                        "checks_failed",
+                    );
+                }
             }
         }
     }
@@ -395,6 +410,22 @@ impl GitTracker {
     }
 }
 
+/// PR number when the same open PR's checks just flipped to failing.
+///
+/// Only transitions between two known statuses count, so the first poll of a
+/// workspace whose PR is already failing doesn't fire.
+fn checks_started_failing(old: Option<&PrStatus>, new: Option<&PrStatus>) -> Option<u32> {
+    let (old, new) = (old?, new?);
+    let number = new.number?;
+    let flipped = old.exists
+        && new.exists
+        && old.number == Some(number)
+        && matches!(new.state, PrState::Open | PrState::Draft)
+        && old.checks.state() != CheckState::Failing
+        && new.checks.state() == CheckState::Failing;
+    flipped.then_some(number)
+}
+
 /// Spawn the git tracker and return a handle
 pub fn spawn_git_tracker(update_tx: mpsc::UnboundedSender<GitTrackerUpdate>) -> GitTrackerHandle {
     GitTracker::spawn(GitTrackerConfig::default(), update_tx)
@@ -418,4 +449,39 @@ mod tests {
         // No updates should be pending (or channel should be dropped)
         assert!(update_rx.try_recv().is_err());
     }
+
+    fn pr_with_checks(number: u32, passed: usize, failed: usize, pending: usize) -> PrStatus {
+        PrStatus {
+            exists: true,
+            number: Some(number),
+            state: PrState::Open,
+            checks: crate::git::CheckStatus {
+                total: passed + failed + pending,
+                passed,
+                failed,
+                pending,
+                skipped: 0,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_checks_started_failing() {
+        let pending = pr_with_checks(7, 1, 0, 1);
+        let failing = pr_with_checks(7, 1, 1, 0);
+
+        assert_eq!(
+            checks_started_failing(Some(&pending), Some(&failing)),
+            Some(7)
+        );
+        // Still failing, or failing on the first poll
+        assert_eq!(checks_started_failing(Some(&failing), Some(&failing)), None);
+        assert_eq!(checks_started_failing(None, Some(&failing)), None);
+        // A different PR for the branch
+        let other = pr_with_checks(8, 0, 1, 0);
+        assert_eq!(checks_started_failing(Some(&pending), Some(&other)), None);
+        // Recovering isn't a failure
+        assert_eq!(checks_started_failing(Some(&failing), Some(&pending)), None);
+    }
 }
@@ -6,10 +6,13 @@ pub mod app_state;
 pub mod capabilities;
 pub mod clipboard_paste;
 pub mod components;
+pub mod diff_viewer;
 pub mod effect;
 pub mod events;
 pub mod file_viewer;
 pub mod git_tracker;
+pub mod notifications;
+pub mod race_viewer;
 pub mod session;
 pub mod tab;
 pub mod tab_manager;
@@ -19,10 +22,12 @@ pub use action::Action;
 pub use app::App;
 pub use app_state::{AppState, PerformanceMetrics};
 pub use capabilities::AgentCapabilities;
+pub use diff_viewer::{DiffLayout, DiffViewerSession};
 pub use effect::Effect;
 pub use events::{AppEvent, InputMode};
 pub use file_viewer::FileViewerSession;
 pub use git_tracker::{GitTrackerHandle, GitTrackerUpdate};
+pub use race_viewer::RaceViewerSession;
 pub use session::AgentSession;
 pub use tab::Tab;
 pub use tab_manager::TabManager;
@@ -0,0 +1,244 @@
+//! Terminal bell, desktop and command notifications for agents that need
+//! attention while the user is looking elsewhere.
+
+use std::io::{self, Write};
+use std::process::Stdio;
+
+use uuid::Uuid;
+
+use crate::config::{DesktopNotification, NotificationConfig};
+
+/// Longest notification body sent to the terminal
+const MAX_BODY_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    TurnComplete,
+    TurnFailed,
+    InputRequired,
+}
+
+impl NotificationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TurnComplete => "turn_complete",
+            Self::TurnFailed => "turn_failed",
+            Self::InputRequired => "input_required",
+        }
+    }
+
+    fn enabled(self, config: &NotificationConfig) -> bool {
+        match self {
+            Self::TurnComplete => config.turn_complete,
+            Self::TurnFailed => config.turn_failed,
+            Self::InputRequired => config.input_required,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub session_id: Uuid,
+    pub title: String,
+    pub body: String,
+}
+
+/// Sends notifications the way `[notifications]` asks, unless muted.
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    config: NotificationConfig,
+    quiet: bool,
+}
+
+impl Notifier {
+    pub fn new(config: NotificationConfig) -> Self {
+        let quiet = config.quiet;
+        Self { config, quiet }
+    }
+
+    pub fn is_quiet(&self) -> bool {
+        self.quiet
+    }
+
+    /// Mute or unmute notifications, returning whether they are now muted.
+    pub fn toggle_quiet(&mut self) -> bool {
+        self.quiet = !self.quiet;
+        self.quiet
+    }
+
+    pub fn min_turn_secs(&self) -> u64 {
+        self.config.min_turn_secs
+    }
+
+    pub fn notify(&self, notification: &Notification) {
+        if self.quiet || !notification.kind.enabled(&self.config) {
+            return;
+        }
+
+        let in_tmux = std::env::var_os("TMUX").is_some();
+        let sequences = terminal_sequences(&self.config, notification, in_tmux);
+        if !sequences.is_empty() {
+            let mut stdout = io::stdout();
+            if let Err(e) = stdout
+                .write_all(sequences.as_bytes())
+                .and_then(|()| stdout.flush())
+            {
+                tracing::debug!(error = %e, "Failed to write terminal notification");
+            }
+        }
+
+        if let Some(command) = &self.config.command {
+            run_command(command, notification);
+        }
+    }
+}
+
+/// Bell and desktop notification escape sequences for a notification.
+fn terminal_sequences(
+    config: &NotificationConfig,
+    notification: &Notification,
+    in_tmux: bool,
+) -> String {
+    let mut sequences = String::new();
+    if config.bell {
+        sequences.push('\x07');
+    }
+    let title = sanitize(&notification.title);
+    let body = sanitize(&notification.body);
+    match config.desktop {
+        DesktopNotification::Off => {}
+        DesktopNotification::Osc9 => {
+            sequences.push_str(&osc(&format!("9;{}: {}", title, body), in_tmux));
+        }
+        DesktopNotification::Osc777 => {
+            // Fields are separated by `;`, so none may contain one
+            let title = title.replace(';', ",");
+            let body = body.replace(';', ",");
+            sequences.push_str(&osc(&format!("777;notify;{};{}", title, body), in_tmux));
+        }
+    }
+    sequences
+}
+
+/// An OSC sequence, wrapped for passthrough when running inside tmux.
+fn osc(payload: &str, in_tmux: bool) -> String {
+    let sequence = format!("\x1b]{}\x07", payload);
+    if in_tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+/// Single line without control characters, which would end the sequence early.
+fn sanitize(text: &str) -> String {
+    let line: String = text
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() > MAX_BODY_CHARS {
+        let mut truncated: String = line.chars().take(MAX_BODY_CHARS - 1).collect();
+        truncated.push('…');
+        truncated
+    } else {
+        line
+    }
+}
+
+/// Run the notification command in the background, without waiting for it.
+fn run_command(command: &str, notification: &Notification) {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let spawned = tokio::process::Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .env("CONDUIT_NOTIFY_EVENT", notification.kind.as_str())
+        .env("CONDUIT_NOTIFY_TITLE", &notification.title)
+        .env("CONDUIT_NOTIFY_BODY", &notification.body)
+        .env("CONDUIT_SESSION_ID", notification.session_id.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        Ok(mut child) => {
+            tokio::spawn(async move {
+                if let Ok(status) = child.wait().await {
+                    if !status.success() {
+                        tracing::debug!(?status, "Notification command failed");
+                    }
+                }
+            });
+        }
+        Err(e) => tracing::warn!(error = %e, "Failed to run notification command"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(kind: NotificationKind) -> Notification {
+        Notification {
+            kind,
+            session_id: Uuid::nil(),
+            title: "Conduit: api (fix-login)".to_string(),
+            body: "Waiting for input; Bash\nrm -rf target".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_terminal_sequences_by_style() {
+        let mut config = NotificationConfig {
+            bell: true,
+            ..NotificationConfig::default()
+        };
+        let input = notification(NotificationKind::InputRequired);
+        assert_eq!(terminal_sequences(&config, &input, false), "\x07");
+
+        config.bell = false;
+        config.desktop = DesktopNotification::Osc9;
+        assert_eq!(
+            terminal_sequences(&config, &input, false),
+            "\x1b]9;Conduit: api (fix-login): Waiting for input; Bash rm -rf target\x07"
+        );
+
+        config.desktop = DesktopNotification::Osc777;
+        assert_eq!(
+            terminal_sequences(&config, &input, false),
+            "\x1b]777;notify;Conduit: api (fix-login);Waiting for input, Bash rm -rf target\x07"
+        );
+    }
+
+    #[test]
+    fn test_osc_wrapped_for_tmux() {
+        assert_eq!(osc("9;hi", true), "\x1bPtmux;\x1b\x1b]9;hi\x07\x1b\\");
+    }
+
+    #[test]
+    fn test_sanitize_truncates_long_text() {
+        let long = "x".repeat(MAX_BODY_CHARS + 10);
+        let sanitized = sanitize(&long);
+        assert_eq!(sanitized.chars().count(), MAX_BODY_CHARS);
+        assert!(sanitized.ends_with('…'));
+        assert_eq!(sanitize("a\x1b]b\x07c"), "a ]b c");
+    }
+
+    #[test]
+    fn test_quiet_and_disabled_kinds_are_skipped() {
+        let mut notifier = Notifier::new(NotificationConfig {
+            turn_complete: false,
+            ..NotificationConfig::default()
+        });
+        assert!(!NotificationKind::TurnComplete.enabled(&notifier.config));
+        assert!(NotificationKind::TurnFailed.enabled(&notifier.config));
+        assert!(!notifier.is_quiet());
+        assert!(notifier.toggle_quiet());
+        assert!(notifier.is_quiet());
+    }
+}
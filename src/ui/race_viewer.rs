@@ -0,0 +1,95 @@
+//! Race viewer session for comparing the contenders of a multi-agent race
+//!
+//! This module provides the RaceViewerSession struct which holds the state
+//! for a race comparison tab: the race, each contender's latest standing and
+//! the selected row.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::core::services::RaceStanding;
+use crate::data::Race;
+
+/// State for a race comparison tab
+#[derive(Debug)]
+pub struct RaceViewerSession {
+    /// Unique identifier for this session
+    pub id: Uuid,
+    /// The race being compared
+    pub race: Race,
+    /// Latest standing of each contender, in race order
+    standings: Vec<RaceStanding>,
+    /// Contenders whose tests are currently running (by workspace)
+    testing: HashSet<Uuid>,
+    /// Error from the last refresh
+    pub error: Option<String>,
+    /// Index of the selected contender
+    pub selected: usize,
+}
+
+impl RaceViewerSession {
+    pub fn new(race: Race) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            race,
+            standings: Vec::new(),
+            testing: HashSet::new(),
+            error: None,
+            selected: 0,
+        }
+    }
+
+    /// Get display name for the tab
+    pub fn tab_name(&self) -> String {
+        format!("Race: {} agents", self.race.entries.len())
+    }
+
+    /// Replace the standings with a fresh load
+    pub fn set_standings(&mut self, standings: Result<Vec<RaceStanding>, String>) {
+        match standings {
+            Ok(standings) => {
+                self.standings = standings;
+                self.error = None;
+            }
+            Err(error) => self.error = Some(error),
+        }
+        self.selected = self.selected.min(self.standings.len().saturating_sub(1));
+    }
+
+    pub fn standings(&self) -> &[RaceStanding] {
+        &self.standings
+    }
+
+    /// Standing of the selected contender
+    pub fn selected_standing(&self) -> Option<&RaceStanding> {
+        self.standings.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.standings.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Whether a workspace is one of this race's contenders
+    pub fn has_workspace(&self, workspace_id: Uuid) -> bool {
+        self.race.entry(workspace_id).is_some()
+    }
+
+    pub fn set_testing(&mut self, workspace_id: Uuid, testing: bool) {
+        if testing {
+            self.testing.insert(workspace_id);
+        } else {
+            self.testing.remove(&workspace_id);
+        }
+    }
+
+    pub fn is_testing(&self, workspace_id: Uuid) -> bool {
+        self.testing.contains(&workspace_id)
+    }
+}
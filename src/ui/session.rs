@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 
 use serde_json::Value;
@@ -62,6 +62,8 @@ pub struct AgentSession {
     pub is_processing: bool,
     /// Accumulated token usage
     pub total_usage: TokenUsage,
+    /// Accumulated estimated cost in USD (including turns from previous runs)
+    pub total_cost: f64,
     /// Turn count
     pub turn_count: u32,
     /// PR number if current branch has an open PR
@@ -102,8 +104,10 @@ pub struct AgentSession {
     pub pending_turn_summary: Option<TurnSummary>,
     /// Number of tools currently in flight for this turn
     pub tools_in_flight: usize,
-    /// Active inline prompt (AskUserQuestion or ExitPlanMode)
+    /// Active inline prompt (AskUserQuestion, ExitPlanMode or tool permission)
     pub inline_prompt: Option<InlinePromptState>,
+    /// Tool permission prompts waiting for the active inline prompt to be answered
+    pub queued_inline_prompts: VecDeque<InlinePromptState>,
     /// Pending permission prompt requests keyed by tool use ID
     pub pending_tool_permissions: HashMap<String, String>,
     /// Pending control responses waiting for a permission request
@@ -142,6 +146,7 @@ impl AgentSession {
             agent_session_id: None,
             is_processing: false,
             total_usage: TokenUsage::default(),
+            total_cost: 0.0,
             turn_count: 0,
             pr_number: None,
             needs_attention: false,
@@ -163,6 +168,7 @@ impl AgentSession {
             pending_turn_summary: None,
             tools_in_flight: 0,
             inline_prompt: None,
+            queued_inline_prompts: VecDeque::new(),
             pending_tool_permissions: HashMap::new(),
             pending_tool_permission_responses: HashMap::new(),
         };
@@ -213,7 +219,7 @@ impl AgentSession {
             .set_shell_mode(self.input_box.is_shell_mode());
         self.status_bar
             .set_session_id(self.agent_session_id.clone());
-        self.status_bar.set_token_usage(self.total_usage.clone());
+        self.status_bar.set_estimated_cost(self.total_cost);
         self.status_bar
             .set_context_state(self.context_state.clone());
         self.status_bar.set_queue_count(self.queued_messages.len());
@@ -271,6 +277,7 @@ impl AgentSession {
         self.total_usage.input_tokens += usage.input_tokens;
         self.total_usage.output_tokens += usage.output_tokens;
         self.total_usage.cached_tokens += usage.cached_tokens;
+        self.total_usage.cache_write_tokens += usage.cache_write_tokens;
         self.total_usage.total_tokens += usage.total_tokens;
         self.turn_count += 1;
         self.update_status();
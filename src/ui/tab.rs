@@ -5,7 +5,9 @@
 
 use uuid::Uuid;
 
+use crate::ui::diff_viewer::DiffViewerSession;
 use crate::ui::file_viewer::FileViewerSession;
+use crate::ui::race_viewer::RaceViewerSession;
 use crate::ui::session::AgentSession;
 
 /// Represents any type of tab in the application
@@ -18,6 +20,10 @@ pub enum Tab {
     Agent(AgentSession),
     /// A file viewer tab
     File(FileViewerSession),
+    /// A workspace diff tab
+    Diff(DiffViewerSession),
+    /// A race comparison tab
+    Race(RaceViewerSession),
 }
 
 impl Tab {
@@ -26,6 +32,8 @@ impl Tab {
         match self {
             Tab::Agent(session) => session.id,
             Tab::File(viewer) => viewer.id,
+            Tab::Diff(viewer) => viewer.id,
+            Tab::Race(viewer) => viewer.id,
         }
     }
 
@@ -34,6 +42,8 @@ impl Tab {
         match self {
             Tab::Agent(session) => session.tab_name(),
             Tab::File(viewer) => viewer.tab_name(),
+            Tab::Diff(viewer) => viewer.tab_name(),
+            Tab::Race(viewer) => viewer.tab_name(),
         }
     }
 
@@ -41,7 +51,7 @@ impl Tab {
     pub fn needs_attention(&self) -> bool {
         match self {
             Tab::Agent(session) => session.needs_attention,
-            Tab::File(_) => false, // Files don't have notifications
+            Tab::File(_) | Tab::Diff(_) | Tab::Race(_) => false, // Viewers don't have notifications
         }
     }
 
@@ -49,7 +59,7 @@ impl Tab {
     pub fn is_processing(&self) -> bool {
         match self {
             Tab::Agent(session) => session.is_processing,
-            Tab::File(_) => false,
+            Tab::File(_) | Tab::Diff(_) | Tab::Race(_) => false,
         }
     }
 
@@ -57,7 +67,7 @@ impl Tab {
     pub fn is_awaiting_response(&self) -> bool {
         match self {
             Tab::Agent(session) => session.inline_prompt.is_some(),
-            Tab::File(_) => false,
+            Tab::File(_) | Tab::Diff(_) | Tab::Race(_) => false,
         }
     }
 
@@ -65,7 +75,7 @@ impl Tab {
     pub fn as_agent(&self) -> Option<&AgentSession> {
         match self {
             Tab::Agent(session) => Some(session),
-            Tab::File(_) => None,
+            Tab::File(_) | Tab::Diff(_) | Tab::Race(_) => None,
         }
     }
 
@@ -73,14 +83,14 @@ impl Tab {
     pub fn as_agent_mut(&mut self) -> Option<&mut AgentSession> {
         match self {
             Tab::Agent(session) => Some(session),
-            Tab::File(_) => None,
+            Tab::File(_) | Tab::Diff(_) | Tab::Race(_) => None,
         }
     }
 
     /// Get the file viewer if this is a file tab
     pub fn as_file(&self) -> Option<&FileViewerSession> {
         match self {
-            Tab::Agent(_) => None,
+            Tab::Agent(_) | Tab::Diff(_) | Tab::Race(_) => None,
             Tab::File(viewer) => Some(viewer),
         }
     }
@@ -88,8 +98,40 @@ impl Tab {
     /// Get the file viewer mutably if this is a file tab
     pub fn as_file_mut(&mut self) -> Option<&mut FileViewerSession> {
         match self {
-            Tab::Agent(_) => None,
+            Tab::Agent(_) | Tab::Diff(_) | Tab::Race(_) => None,
             Tab::File(viewer) => Some(viewer),
         }
     }
+
+    /// Get the diff viewer if this is a diff tab
+    pub fn as_diff(&self) -> Option<&DiffViewerSession> {
+        match self {
+            Tab::Diff(viewer) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Get the diff viewer mutably if this is a diff tab
+    pub fn as_diff_mut(&mut self) -> Option<&mut DiffViewerSession> {
+        match self {
+            Tab::Diff(viewer) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Get the race viewer if this is a race tab
+    pub fn as_race(&self) -> Option<&RaceViewerSession> {
+        match self {
+            Tab::Race(viewer) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Get the race viewer mutably if this is a race tab
+    pub fn as_race_mut(&mut self) -> Option<&mut RaceViewerSession> {
+        match self {
+            Tab::Race(viewer) => Some(viewer),
+            _ => None,
+        }
+    }
 }
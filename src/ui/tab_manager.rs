@@ -7,7 +7,9 @@ use std::path::PathBuf;
 use uuid::Uuid;
 
 use crate::agent::AgentType;
+use crate::ui::diff_viewer::DiffViewerSession;
 use crate::ui::file_viewer::FileViewerSession;
+use crate::ui::race_viewer::RaceViewerSession;
 use crate::ui::session::AgentSession;
 use crate::ui::tab::Tab;
 
@@ -73,6 +75,42 @@ impl TabManager {
         Ok(new_index)
     }
 
+    /// Open a workspace diff in a new tab, or focus the existing one for that directory
+    pub fn open_diff(&mut self, viewer: DiffViewerSession) -> Option<usize> {
+        if let Some(index) = self
+            .tabs
+            .iter()
+            .position(|t| matches!(t, Tab::Diff(d) if d.working_dir == viewer.working_dir))
+        {
+            if let Some(Tab::Diff(existing)) = self.tabs.get_mut(index) {
+                existing.reload();
+            }
+            self.active_tab = index;
+            return Some(index);
+        }
+
+        if self.tabs.len() >= self.max_tabs {
+            return None;
+        }
+
+        self.tabs.push(Tab::Diff(viewer));
+        let new_index = self.tabs.len() - 1;
+        self.active_tab = new_index;
+        Some(new_index)
+    }
+
+    /// Open a race comparison in a new tab
+    pub fn open_race(&mut self, viewer: RaceViewerSession) -> Option<usize> {
+        if self.tabs.len() >= self.max_tabs {
+            return None;
+        }
+
+        self.tabs.push(Tab::Race(viewer));
+        let new_index = self.tabs.len() - 1;
+        self.active_tab = new_index;
+        Some(new_index)
+    }
+
     /// Close a tab by index
     pub fn close_tab(&mut self, index: usize) -> bool {
         if index >= self.tabs.len() {
@@ -192,6 +230,50 @@ impl TabManager {
         }
     }
 
+    /// Get a reference to the active diff viewer
+    /// Returns None if active tab is not a diff viewer
+    pub fn active_diff_viewer(&self) -> Option<&DiffViewerSession> {
+        match self.tabs.get(self.active_tab) {
+            Some(Tab::Diff(viewer)) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable reference to the active diff viewer
+    /// Returns None if active tab is not a diff viewer
+    pub fn active_diff_viewer_mut(&mut self) -> Option<&mut DiffViewerSession> {
+        match self.tabs.get_mut(self.active_tab) {
+            Some(Tab::Diff(viewer)) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Get a reference to the active race viewer
+    /// Returns None if active tab is not a race viewer
+    pub fn active_race_viewer(&self) -> Option<&RaceViewerSession> {
+        match self.tabs.get(self.active_tab) {
+            Some(Tab::Race(viewer)) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable reference to the active race viewer
+    /// Returns None if active tab is not a race viewer
+    pub fn active_race_viewer_mut(&mut self) -> Option<&mut RaceViewerSession> {
+        match self.tabs.get_mut(self.active_tab) {
+            Some(Tab::Race(viewer)) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Iterate over all race viewers mutably
+    pub fn race_viewers_mut(&mut self) -> impl Iterator<Item = &mut RaceViewerSession> {
+        self.tabs.iter_mut().filter_map(|t| match t {
+            Tab::Race(viewer) => Some(viewer),
+            _ => None,
+        })
+    }
+
     /// Get a reference to an agent session by index
     pub fn session(&self, index: usize) -> Option<&AgentSession> {
         match self.tabs.get(index) {
@@ -288,6 +370,16 @@ impl TabManager {
         matches!(self.tabs.get(self.active_tab), Some(Tab::File(_)))
     }
 
+    /// Check if the active tab is a diff viewer
+    pub fn active_is_diff(&self) -> bool {
+        matches!(self.tabs.get(self.active_tab), Some(Tab::Diff(_)))
+    }
+
+    /// Check if the active tab is a race viewer
+    pub fn active_is_race(&self) -> bool {
+        matches!(self.tabs.get(self.active_tab), Some(Tab::Race(_)))
+    }
+
     /// Check if the active tab is an agent session
     pub fn active_is_agent(&self) -> bool {
         matches!(self.tabs.get(self.active_tab), Some(Tab::Agent(_)))
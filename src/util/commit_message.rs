@@ -0,0 +1,105 @@
+//! Commit message generation using AI
+//!
+//! Generates a commit message from a workspace diff with the same
+//! Claude → Codex fallback used for session titles.
+
+use serde::Deserialize;
+use std::path::Path;
+
+use super::title_generator::{
+    extract_json_object, generate_with_fallback, truncate_message, TitleGeneratorError,
+};
+use super::ToolAvailability;
+
+/// Maximum diff characters included in the prompt
+const MAX_DIFF_CHARS: usize = 12_000;
+
+/// Result of commit message generation
+#[derive(Debug, Clone)]
+pub struct GeneratedCommitMessage {
+    /// Subject line, optionally followed by a blank line and a body
+    pub message: String,
+    /// Tool that generated the message
+    pub tool_used: String,
+    /// Whether the result came from a fallback tool
+    pub used_fallback: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitMessageResponse {
+    message: String,
+}
+
+/// Generate a commit message for `diff`
+pub async fn generate_commit_message(
+    tools: &ToolAvailability,
+    diff: &str,
+    working_dir: &Path,
+) -> Result<GeneratedCommitMessage, TitleGeneratorError> {
+    let prompt = format!(
+        r#"Write a git commit message for the following changes.
+Use an imperative subject line (max 72 chars). If the change needs explaining,
+add a blank line and a short body wrapped at 72 chars. Do not mention that the
+message was generated.
+
+Diff:
+{}
+
+Respond ONLY with valid JSON (no markdown, no explanation):
+{{"message": "..."}}"#,
+        truncate_message(diff, MAX_DIFF_CHARS)
+    );
+
+    let (message, tool, used_fallback) =
+        generate_with_fallback(tools, &prompt, working_dir, parse_commit_message).await?;
+    Ok(GeneratedCommitMessage {
+        message,
+        tool_used: tool.display_name().to_string(),
+        used_fallback,
+    })
+}
+
+fn parse_commit_message(response: &str) -> Result<String, TitleGeneratorError> {
+    let json_str = extract_json_object(response)?;
+    let parsed: CommitMessageResponse = serde_json::from_str(json_str)
+        .map_err(|e| TitleGeneratorError::ParseError(format!("Failed to parse JSON: {}", e)))?;
+
+    let message = parsed.message.trim();
+    if message.is_empty() {
+        return Err(TitleGeneratorError::ParseError(
+            "Empty commit message from AI".to_string(),
+        ));
+    }
+    Ok(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commit_message() {
+        let response =
+            "Here you go:\n```json\n{\"message\": \"Fix parser\\n\\nHandle empty input.\"}\n```";
+        let message = parse_commit_message(response).unwrap();
+        assert_eq!(message, "Fix parser\n\nHandle empty input.");
+    }
+
+    #[test]
+    fn test_parse_commit_message_trims_whitespace() {
+        let message = parse_commit_message("{\"message\": \"  Add tests \\n\"}").unwrap();
+        assert_eq!(message, "Add tests");
+    }
+
+    #[test]
+    fn test_parse_commit_message_empty() {
+        let result = parse_commit_message("{\"message\": \"   \"}");
+        assert!(matches!(result, Err(TitleGeneratorError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_parse_commit_message_no_json() {
+        let result = parse_commit_message("I can't do that");
+        assert!(matches!(result, Err(TitleGeneratorError::ParseError(_))));
+    }
+}
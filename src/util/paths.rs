@@ -53,11 +53,37 @@ pub fn log_file_path() -> PathBuf {
     logs_dir().join("conduit.log")
 }
 
+/// Get the transcript exports directory (~/.conduit/exports)
+pub fn exports_dir() -> PathBuf {
+    data_dir().join("exports")
+}
+
 /// Get the workspaces directory (~/.conduit/workspaces)
 pub fn workspaces_dir() -> PathBuf {
     data_dir().join("workspaces")
 }
 
+/// Get the cloned repositories directory (~/.conduit/repos)
+pub fn repos_dir() -> PathBuf {
+    data_dir().join("repos")
+}
+
+/// Get the web API tokens file path (~/.conduit/api_tokens.json)
+pub fn api_tokens_path() -> PathBuf {
+    data_dir().join("api_tokens.json")
+}
+
+/// Get the file audit entries go to when the database rejects them
+/// (~/.conduit/audit_fallback.jsonl)
+pub fn audit_fallback_path() -> PathBuf {
+    data_dir().join("audit_fallback.jsonl")
+}
+
+/// Get the running daemon's connection file path (~/.conduit/daemon.json)
+pub fn daemon_info_path() -> PathBuf {
+    data_dir().join("daemon.json")
+}
+
 /// Migrate old worktrees folder to workspaces folder if needed
 ///
 /// This is a one-time migration for users upgrading from older versions.
@@ -4,7 +4,7 @@
 //! from the first user message in a session using Claude or Codex.
 
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::process::Stdio;
 use std::time::Duration;
 use thiserror::Error;
@@ -51,7 +51,7 @@ pub enum TitleGeneratorError {
 pub async fn generate_title_and_branch(
     tools: &ToolAvailability,
     user_message: &str,
-    working_dir: &PathBuf,
+    working_dir: &Path,
 ) -> Result<GeneratedMetadata, TitleGeneratorError> {
     let prompt = format!(
         r#"Based on this user request, generate:
@@ -65,21 +65,38 @@ Respond ONLY with valid JSON (no markdown, no explanation):
         truncate_message(user_message, 500)
     );
 
+    let (mut metadata, tool, used_fallback) =
+        generate_with_fallback(tools, &prompt, working_dir, parse_json_response).await?;
+    metadata.tool_used = Some(tool.display_name().to_string());
+    metadata.used_fallback = used_fallback;
+    Ok(metadata)
+}
+
+/// Run a one-shot prompt through Claude, falling back to Codex.
+///
+/// Each tool gets [`AI_CALL_TIMEOUT_SECS`]; a response that `parse` rejects
+/// counts as a failure and moves on to the next tool. Returns the parsed
+/// value, the tool that produced it, and whether an earlier tool failed.
+pub(crate) async fn generate_with_fallback<T>(
+    tools: &ToolAvailability,
+    prompt: &str,
+    working_dir: &Path,
+    parse: fn(&str) -> Result<T, TitleGeneratorError>,
+) -> Result<(T, Tool, bool), TitleGeneratorError> {
     let mut failures: Vec<(Tool, TitleGeneratorError)> = Vec::new();
 
     if tools.is_available(Tool::Claude) {
         if let Some(tool_path) = tools.get_path(Tool::Claude) {
             let result = tokio::time::timeout(
                 Duration::from_secs(AI_CALL_TIMEOUT_SECS),
-                call_claude(tool_path, &prompt, working_dir),
+                call_claude(tool_path, prompt, working_dir),
             )
             .await;
             match result {
-                Ok(Ok(mut metadata)) => {
-                    metadata.tool_used = Some(Tool::Claude.display_name().to_string());
-                    metadata.used_fallback = false;
-                    return Ok(metadata);
-                }
+                Ok(Ok(response)) => match parse(&response) {
+                    Ok(value) => return Ok((value, Tool::Claude, false)),
+                    Err(err) => failures.push((Tool::Claude, err)),
+                },
                 Ok(Err(err)) => failures.push((Tool::Claude, err)),
                 Err(_) => failures.push((
                     Tool::Claude,
@@ -98,15 +115,14 @@ Respond ONLY with valid JSON (no markdown, no explanation):
         if let Some(tool_path) = tools.get_path(Tool::Codex) {
             let result = tokio::time::timeout(
                 Duration::from_secs(AI_CALL_TIMEOUT_SECS),
-                call_codex(tool_path.as_path(), &prompt, working_dir.as_path()),
+                call_codex(tool_path.as_path(), prompt, working_dir),
             )
             .await;
             match result {
-                Ok(Ok(mut metadata)) => {
-                    metadata.tool_used = Some(Tool::Codex.display_name().to_string());
-                    metadata.used_fallback = !failures.is_empty();
-                    return Ok(metadata);
-                }
+                Ok(Ok(response)) => match parse(&response) {
+                    Ok(value) => return Ok((value, Tool::Codex, !failures.is_empty())),
+                    Err(err) => failures.push((Tool::Codex, err)),
+                },
                 Ok(Err(err)) => failures.push((Tool::Codex, err)),
                 Err(_) => failures.push((
                     Tool::Codex,
@@ -138,10 +154,10 @@ Respond ONLY with valid JSON (no markdown, no explanation):
 }
 
 async fn call_claude(
-    binary_path: &PathBuf,
+    binary_path: &Path,
     prompt: &str,
-    working_dir: &PathBuf,
-) -> Result<GeneratedMetadata, TitleGeneratorError> {
+    working_dir: &Path,
+) -> Result<String, TitleGeneratorError> {
     let mut cmd = Command::new(binary_path);
     cmd.args(["-p", "--output-format", "text", "--model", "sonnet"]);
     cmd.arg("--").arg(prompt);
@@ -161,15 +177,14 @@ async fn call_claude(
         )));
     }
 
-    let response = String::from_utf8_lossy(&output.stdout);
-    parse_json_response(&response)
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
 async fn call_codex(
     binary_path: &Path,
     prompt: &str,
     working_dir: &Path,
-) -> Result<GeneratedMetadata, TitleGeneratorError> {
+) -> Result<String, TitleGeneratorError> {
     let runner = CodexCliRunner::with_path(binary_path.to_path_buf());
     let config = AgentStartConfig::new(prompt, working_dir.to_path_buf());
     let mut handle = runner
@@ -205,19 +220,28 @@ async fn call_codex(
             "Failed to stop Codex app-server after title generation"
         );
     }
-    parse_json_response(&response)
+    Ok(response)
 }
 
-fn parse_json_response(response: &str) -> Result<GeneratedMetadata, TitleGeneratorError> {
-    // Extract JSON from response (may have markdown or extra text)
+/// Extract the JSON object from a response that may have markdown or extra text
+pub(crate) fn extract_json_object(response: &str) -> Result<&str, TitleGeneratorError> {
     let json_start = response.find('{').ok_or_else(|| {
         TitleGeneratorError::ParseError("No JSON object found in response".into())
     })?;
     let json_end = response.rfind('}').ok_or_else(|| {
         TitleGeneratorError::ParseError("No JSON object found in response".into())
     })?;
+    if json_end < json_start {
+        return Err(TitleGeneratorError::ParseError(
+            "No JSON object found in response".into(),
+        ));
+    }
 
-    let json_str = &response[json_start..=json_end];
+    Ok(&response[json_start..=json_end])
+}
+
+fn parse_json_response(response: &str) -> Result<GeneratedMetadata, TitleGeneratorError> {
+    let json_str = extract_json_object(response)?;
 
     let metadata: GeneratedMetadata = serde_json::from_str(json_str)
         .map_err(|e| TitleGeneratorError::ParseError(format!("Failed to parse JSON: {}", e)))?;
@@ -238,7 +262,7 @@ fn parse_json_response(response: &str) -> Result<GeneratedMetadata, TitleGenerat
 }
 
 /// UTF-8 safe message truncation that respects character boundaries
-fn truncate_message(msg: &str, max_chars: usize) -> String {
+pub(crate) fn truncate_message(msg: &str, max_chars: usize) -> String {
     let char_count = msg.chars().count();
     if char_count <= max_chars {
         msg.to_string()
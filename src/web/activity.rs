@@ -0,0 +1,119 @@
+//! Session and workspace activity for integrations.
+//!
+//! Turn and PR status changes are published here and fanned out to
+//! `GET /api/events/stream` clients and outbound webhooks.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::agent::events::TokenUsage;
+
+use super::PrStatusResponse;
+
+/// Events a slow subscriber may fall behind by before it misses some
+const FEED_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ActivityKind {
+    TurnStarted {
+        session_id: Uuid,
+        workspace_id: Option<Uuid>,
+    },
+    TurnCompleted {
+        session_id: Uuid,
+        workspace_id: Option<Uuid>,
+        usage: TokenUsage,
+    },
+    TurnFailed {
+        session_id: Uuid,
+        workspace_id: Option<Uuid>,
+        error: String,
+    },
+    /// The agent is waiting for the user to answer a tool or input prompt
+    ControlRequestPending {
+        session_id: Uuid,
+        workspace_id: Option<Uuid>,
+        request_id: String,
+        tool_name: String,
+    },
+    /// A workspace's PR was opened or changed state, checks or review status
+    PrStatusChanged {
+        workspace_id: Uuid,
+        pr_status: PrStatusResponse,
+    },
+}
+
+impl ActivityKind {
+    /// Names used for SSE `event:` fields and webhook filters
+    pub const NAMES: &[&str] = &[
+        "turn_started",
+        "turn_completed",
+        "turn_failed",
+        "control_request_pending",
+        "pr_status_changed",
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TurnStarted { .. } => "turn_started",
+            Self::TurnCompleted { .. } => "turn_completed",
+            Self::TurnFailed { .. } => "turn_failed",
+            Self::ControlRequestPending { .. } => "control_request_pending",
+            Self::PrStatusChanged { .. } => "pr_status_changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Activity {
+    /// Increases by one per event for the life of the server; used as the SSE
+    /// `id`, it starts over when the server restarts
+    pub id: u64,
+    /// Unique across restarts, for receivers that deduplicate webhook deliveries
+    pub delivery_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: ActivityKind,
+}
+
+/// Broadcast of activity to every listener.
+#[derive(Debug)]
+pub struct ActivityFeed {
+    tx: broadcast::Sender<Activity>,
+    next_id: AtomicU64,
+}
+
+impl Default for ActivityFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActivityFeed {
+    pub fn new() -> Self {
+        Self {
+            tx: broadcast::channel(FEED_CAPACITY).0,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn publish(&self, kind: ActivityKind) {
+        let activity = Activity {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            delivery_id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            kind,
+        };
+        // Nobody listening is fine
+        let _ = self.tx.send(activity);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Activity> {
+        self.tx.subscribe()
+    }
+}
@@ -0,0 +1,425 @@
+//! Token authentication and origin checks for the web server.
+//!
+//! API tokens are generated with `conduit token create` (or on the first
+//! `conduit serve`) and only their SHA-256 hashes are stored, in
+//! `~/.conduit/api_tokens.json`. REST clients send `Authorization: Bearer
+//! <token>`; browsers cannot set headers on WebSocket upgrades or
+//! `EventSource` requests, so `/ws` and the SSE stream also accept a `token`
+//! query parameter.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::error::WebError;
+
+/// Prefix of generated tokens, so they are recognizable in configs and logs
+const TOKEN_PREFIX: &str = "cdt_";
+
+/// Name of the token `conduit serve` issues to `conduit --attach`
+const DAEMON_TOKEN_NAME: &str = "daemon";
+
+/// Paths reachable without a token
+const PUBLIC_PATHS: &[&str] = &["/api/health", "/api/auth/status"];
+
+/// Paths that take the token as `?token=` for clients that cannot set headers
+const QUERY_TOKEN_PATHS: &[&str] = &["/ws", "/api/events/stream"];
+
+/// A stored API token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    /// Hex SHA-256 of the token
+    hash: String,
+    pub created_at: DateTime<Utc>,
+    /// Issued by `conduit serve` for `conduit --attach`, and replaced by it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub daemon: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TokenFile {
+    tokens: Vec<ApiToken>,
+}
+
+#[derive(Debug, Default)]
+struct Loaded {
+    tokens: Vec<ApiToken>,
+    modified: Option<SystemTime>,
+}
+
+/// API tokens stored in the data directory.
+///
+/// The file is re-read when it changes, so tokens revoked with
+/// `conduit token revoke` stop working without restarting the server.
+#[derive(Debug)]
+pub struct TokenStore {
+    path: PathBuf,
+    loaded: Mutex<Loaded>,
+}
+
+impl TokenStore {
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let store = Self {
+            path,
+            loaded: Mutex::new(Loaded::default()),
+        };
+        store.reload(&mut store.loaded.lock())?;
+        Ok(store)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn list(&self) -> Vec<ApiToken> {
+        let mut loaded = self.loaded.lock();
+        self.refresh(&mut loaded);
+        loaded.tokens.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list().is_empty()
+    }
+
+    /// Generate a token, returning it in plain text; it cannot be shown again.
+    pub fn create(&self, name: &str) -> io::Result<(ApiToken, String)> {
+        self.insert(name, false)
+    }
+
+    /// Replace the daemon's token with a new one, returned in plain text.
+    pub fn create_daemon(&self) -> io::Result<String> {
+        self.revoke_daemon()?;
+        Ok(self.insert(DAEMON_TOKEN_NAME, true)?.1)
+    }
+
+    /// Revoke the daemon's token, leaving user tokens of any name alone.
+    pub fn revoke_daemon(&self) -> io::Result<bool> {
+        self.remove(|token| token.daemon)
+    }
+
+    /// Revoke the token with this ID or name; returns whether one was removed.
+    pub fn revoke(&self, id_or_name: &str) -> io::Result<bool> {
+        self.remove(|token| token.id == id_or_name || token.name == id_or_name)
+    }
+
+    fn insert(&self, name: &str, daemon: bool) -> io::Result<(ApiToken, String)> {
+        let mut bytes = [0u8; 32];
+        rand::rng().fill_bytes(&mut bytes);
+        let secret = format!("{}{}", TOKEN_PREFIX, hex(&bytes));
+        let token = ApiToken {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            name: name.to_string(),
+            hash: hash_token(&secret),
+            created_at: Utc::now(),
+            daemon,
+        };
+
+        let mut loaded = self.loaded.lock();
+        self.reload(&mut loaded)?;
+        loaded.tokens.push(token.clone());
+        self.save(&mut loaded)?;
+        Ok((token, secret))
+    }
+
+    fn remove(&self, matches: impl Fn(&ApiToken) -> bool) -> io::Result<bool> {
+        let mut loaded = self.loaded.lock();
+        self.reload(&mut loaded)?;
+        let before = loaded.tokens.len();
+        loaded.tokens.retain(|token| !matches(token));
+        if loaded.tokens.len() == before {
+            return Ok(false);
+        }
+        self.save(&mut loaded)?;
+        Ok(true)
+    }
+
+    pub fn verify(&self, secret: &str) -> bool {
+        let hash = hash_token(secret);
+        let mut loaded = self.loaded.lock();
+        self.refresh(&mut loaded);
+        loaded.tokens.iter().fold(false, |found, token| {
+            found | constant_time_eq(&token.hash, &hash)
+        })
+    }
+
+    /// Re-read the file if it changed; an unreadable file revokes everything.
+    fn refresh(&self, loaded: &mut Loaded) {
+        let modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+        if modified.is_some() && modified == loaded.modified {
+            return;
+        }
+        if let Err(e) = self.reload(loaded) {
+            tracing::warn!(error = %e, path = %self.path.display(), "Failed to read API tokens");
+            loaded.tokens.clear();
+            loaded.modified = modified;
+        }
+    }
+
+    fn reload(&self, loaded: &mut Loaded) -> io::Result<()> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => {
+                let file: TokenFile = serde_json::from_str(&contents)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                loaded.tokens = file.tokens;
+                loaded.modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+                Ok(())
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                loaded.tokens.clear();
+                loaded.modified = None;
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save(&self, loaded: &mut Loaded) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(&TokenFile {
+            tokens: loaded.tokens.clone(),
+        })
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        write_private_file(&self.path, &contents)?;
+        loaded.modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+        Ok(())
+    }
+}
+
+/// Atomically replace a file only the current user can read.
+pub(super) fn write_private_file(path: &Path, contents: &str) -> io::Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    io::Write::write_all(&mut options.open(&tmp)?, contents.as_bytes())?;
+    fs::rename(&tmp, path)
+}
+
+fn hash_token(secret: &str) -> String {
+    hex(&Sha256::digest(secret.as_bytes()))
+}
+
+pub(super) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+/// Who may call the API, and from which origins.
+#[derive(Debug, Clone)]
+pub struct AccessPolicy {
+    /// None when authentication is disabled
+    tokens: Option<Arc<TokenStore>>,
+    /// Origins allowed besides the server's own
+    allowed_origins: Arc<[String]>,
+    /// Development mode: any origin is allowed
+    any_origin: bool,
+}
+
+impl AccessPolicy {
+    pub fn new(
+        tokens: Option<Arc<TokenStore>>,
+        allowed_origins: Vec<String>,
+        any_origin: bool,
+    ) -> Self {
+        Self {
+            tokens,
+            allowed_origins: allowed_origins
+                .into_iter()
+                .map(|origin| origin.trim_end_matches('/').to_string())
+                .collect(),
+            any_origin,
+        }
+    }
+
+    pub fn auth_required(&self) -> bool {
+        self.tokens.is_some()
+    }
+
+    /// Whether the request carries a valid token (always true without auth)
+    fn authenticated(&self, headers: &HeaderMap, uri: &Uri) -> bool {
+        let Some(tokens) = &self.tokens else {
+            return true;
+        };
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let query = QUERY_TOKEN_PATHS
+            .contains(&uri.path())
+            .then(|| uri.query())
+            .flatten()
+            .and_then(|query| {
+                query
+                    .split('&')
+                    .find_map(|pair| pair.strip_prefix("token="))
+            });
+        bearer
+            .or(query)
+            .is_some_and(|secret| tokens.verify(secret.trim()))
+    }
+
+    /// Requests from browsers must come from the server's own origin or an
+    /// allowed one; clients that send no `Origin` (curl, scripts) pass.
+    fn origin_allowed(&self, headers: &HeaderMap) -> bool {
+        if self.any_origin {
+            return true;
+        }
+        let Some(origin) = headers
+            .get(header::ORIGIN)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return true;
+        };
+        let origin = origin.trim_end_matches('/');
+        if self.allowed_origins.iter().any(|allowed| allowed == origin) {
+            return true;
+        }
+        let host = headers
+            .get(header::HOST)
+            .and_then(|value| value.to_str().ok());
+        let origin_host = origin.split_once("://").map(|(_, host)| host);
+        host.is_some() && origin_host == host
+    }
+}
+
+/// Reject API and WebSocket requests without a valid token or from a
+/// foreign origin. The SPA's static files stay public so it can show the
+/// login page.
+pub async fn enforce_access(
+    State(policy): State<AccessPolicy>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    let protected = (path.starts_with("/api/") || path == "/ws") && !PUBLIC_PATHS.contains(&path);
+    if !protected {
+        return next.run(request).await;
+    }
+    if !policy.origin_allowed(request.headers()) {
+        return WebError::Forbidden("Origin not allowed".to_string()).into_response();
+    }
+    if !policy.authenticated(request.headers(), request.uri()) {
+        return WebError::Unauthorized("A valid API token is required".to_string()).into_response();
+    }
+    next.run(request).await
+}
+
+/// Response for the authentication status.
+#[derive(Debug, Serialize)]
+pub struct AuthStatusResponse {
+    pub auth_required: bool,
+    /// Whether the request carried a valid token
+    pub authenticated: bool,
+}
+
+/// Tell the SPA whether it needs to log in, and whether its token works.
+pub async fn auth_status(
+    State(policy): State<AccessPolicy>,
+    headers: HeaderMap,
+    uri: Uri,
+) -> Json<AuthStatusResponse> {
+    Json(AuthStatusResponse {
+        auth_required: policy.auth_required(),
+        authenticated: policy.authenticated(&headers, &uri),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_create_verify_and_revoke() {
+        let dir = tempdir().unwrap();
+        let store = TokenStore::open(dir.path().join("api_tokens.json")).unwrap();
+        assert!(store.is_empty());
+
+        let (token, secret) = store.create("laptop").unwrap();
+        assert!(secret.starts_with(TOKEN_PREFIX));
+        assert!(store.verify(&secret));
+        assert!(!store.verify("cdt_wrong"));
+
+        // Another process (e.g. `conduit token revoke`) sees the same file
+        let other = TokenStore::open(store.path().to_path_buf()).unwrap();
+        assert!(other.verify(&secret));
+        assert!(other.revoke(&token.id).unwrap());
+        assert!(!other.revoke(&token.id).unwrap());
+        assert!(other.list().is_empty());
+    }
+
+    #[test]
+    fn test_daemon_token_spares_user_tokens() {
+        let dir = tempdir().unwrap();
+        let store = TokenStore::open(dir.path().join("api_tokens.json")).unwrap();
+
+        let (_, user_secret) = store.create(DAEMON_TOKEN_NAME).unwrap();
+        let first = store.create_daemon().unwrap();
+        let second = store.create_daemon().unwrap();
+        assert!(!store.verify(&first));
+        assert!(store.verify(&second));
+
+        assert!(store.revoke_daemon().unwrap());
+        assert!(!store.verify(&second));
+        assert!(store.verify(&user_secret));
+        assert!(!store.revoke_daemon().unwrap());
+    }
+
+    #[test]
+    fn test_origin_allowed() {
+        let policy = AccessPolicy::new(None, vec!["https://laptop:8080/".to_string()], false);
+        let mut headers = HeaderMap::new();
+        assert!(policy.origin_allowed(&headers));
+
+        headers.insert(header::HOST, HeaderValue::from_static("devbox:3000"));
+        headers.insert(
+            header::ORIGIN,
+            HeaderValue::from_static("http://devbox:3000"),
+        );
+        assert!(policy.origin_allowed(&headers));
+
+        headers.insert(
+            header::ORIGIN,
+            HeaderValue::from_static("https://laptop:8080"),
+        );
+        assert!(policy.origin_allowed(&headers));
+
+        headers.insert(
+            header::ORIGIN,
+            HeaderValue::from_static("https://evil.example"),
+        );
+        assert!(!policy.origin_allowed(&headers));
+        assert!(AccessPolicy::new(None, Vec::new(), true).origin_allowed(&headers));
+    }
+}
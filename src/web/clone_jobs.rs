@@ -0,0 +1,92 @@
+//! Repository clones started from the web API.
+//!
+//! Clones run in the background; their progress is kept here so clients can
+//! poll it until the repository has a local checkout.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::git::CloneProgress;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloneStatus {
+    Cloning,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CloneJobResponse {
+    pub status: CloneStatus,
+    pub destination: String,
+    /// Current git step, e.g. "Receiving objects"
+    pub phase: Option<String>,
+    pub percent: Option<u8>,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Latest clone of each repository.
+#[derive(Debug, Default)]
+pub struct CloneJobs {
+    jobs: Mutex<HashMap<Uuid, CloneJobResponse>>,
+}
+
+impl CloneJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new clone; returns false if one is already running.
+    pub fn start(&self, repo_id: Uuid, destination: String) -> bool {
+        let mut jobs = self.jobs.lock();
+        if jobs
+            .get(&repo_id)
+            .is_some_and(|job| job.status == CloneStatus::Cloning)
+        {
+            return false;
+        }
+        jobs.insert(
+            repo_id,
+            CloneJobResponse {
+                status: CloneStatus::Cloning,
+                destination,
+                phase: None,
+                percent: None,
+                error: None,
+                started_at: Utc::now(),
+                finished_at: None,
+            },
+        );
+        true
+    }
+
+    pub fn progress(&self, repo_id: Uuid, progress: CloneProgress) {
+        if let Some(job) = self.jobs.lock().get_mut(&repo_id) {
+            job.phase = Some(progress.phase);
+            job.percent = progress.percent;
+        }
+    }
+
+    pub fn finish(&self, repo_id: Uuid, result: Result<(), String>) {
+        if let Some(job) = self.jobs.lock().get_mut(&repo_id) {
+            job.status = if result.is_ok() {
+                CloneStatus::Succeeded
+            } else {
+                CloneStatus::Failed
+            };
+            job.error = result.err();
+            job.finished_at = Some(Utc::now());
+        }
+    }
+
+    pub fn get(&self, repo_id: Uuid) -> Option<CloneJobResponse> {
+        self.jobs.lock().get(&repo_id).cloned()
+    }
+}
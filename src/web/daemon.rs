@@ -0,0 +1,690 @@
+//! Attaching the TUI to a running `conduit serve`.
+//!
+//! In daemon mode the server's `SessionManager` owns every agent process and
+//! the TUI drives its sessions over the WebSocket protocol, so a turn keeps
+//! running after the terminal closes and the browser sees the same stream.
+//! `conduit serve` records how to reach it in `~/.conduit/daemon.json`.
+//!
+//! When the connection drops the client keeps reconnecting with backoff and
+//! resubscribes from the last event it saw, so sessions stay in daemon mode
+//! instead of starting a second agent in the same workspace.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use futures::stream::SplitSink;
+use futures::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{header, HeaderValue};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{Connector, MaybeTlsStream, WebSocketStream};
+use uuid::Uuid;
+
+use super::auth::write_private_file;
+use super::ws::{ClientMessage, ImageAttachment, ServerMessage};
+use crate::agent::AgentInput;
+
+/// First wait before reconnecting to a daemon that dropped the connection
+const RECONNECT_DELAY_MIN: Duration = Duration::from_secs(1);
+
+/// Longest wait between reconnection attempts
+const RECONNECT_DELAY_MAX: Duration = Duration::from_secs(30);
+
+type DaemonStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// How to reach a running daemon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonInfo {
+    /// WebSocket URL, e.g. `ws://127.0.0.1:3000/ws`
+    pub url: String,
+    /// API token, when the server requires one
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Certificate to trust for a `wss://` server with a self-signed certificate
+    #[serde(default)]
+    pub ca_cert: Option<PathBuf>,
+    /// Process ID of the server
+    #[serde(default)]
+    pub pid: Option<u32>,
+}
+
+impl DaemonInfo {
+    /// Connection details of a server started by this process.
+    pub fn local(host: &str, port: u16, tls_cert: Option<PathBuf>, token: Option<String>) -> Self {
+        // A wildcard address is not something to connect to
+        let host = match host {
+            "0.0.0.0" => "127.0.0.1",
+            "::" => "::1",
+            host => host,
+        };
+        let host = if host.contains(':') {
+            format!("[{}]", host)
+        } else {
+            host.to_string()
+        };
+        let scheme = if tls_cert.is_some() { "wss" } else { "ws" };
+        Self {
+            url: format!("{}://{}:{}/ws", scheme, host, port),
+            token,
+            ca_cert: tls_cert,
+            pid: Some(std::process::id()),
+        }
+    }
+
+    /// Read the connection file; `None` if no daemon is running.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the connection file; it holds a token, so only the owner can read it.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        write_private_file(path, &contents)
+    }
+
+    /// Remove the connection file if it still describes this server.
+    pub fn remove(&self, path: &Path) {
+        if Self::load(path).ok().flatten().as_ref() == Some(self) {
+            if let Err(e) = fs::remove_file(path) {
+                tracing::warn!(error = %e, path = %path.display(), "Failed to remove daemon file");
+            }
+        }
+    }
+}
+
+/// A prompt sent as `StartSession`, kept in case the agent is already running.
+#[derive(Debug)]
+struct PendingPrompt {
+    prompt: String,
+    hidden: bool,
+    images: Vec<ImageAttachment>,
+}
+
+/// Newest event seen in a session, to resume from after reconnecting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct EventPosition {
+    epoch: Option<Uuid>,
+    seq: u64,
+}
+
+#[derive(Debug, Default)]
+struct ClientState {
+    /// Whether the connection is up; messages sent meanwhile wait for it
+    connected: bool,
+    /// Sessions whose agent runs in the daemon
+    running: HashSet<Uuid>,
+    /// Prompts waiting for their session to start
+    prompts: HashMap<Uuid, PendingPrompt>,
+    /// Subscriptions that fail harmlessly when the session is not running
+    watching: HashSet<Uuid>,
+    /// Sessions followed so far, resubscribed after reconnecting
+    positions: HashMap<Uuid, EventPosition>,
+}
+
+impl ClientState {
+    /// Forget what the lost connection told us, keeping where each session was.
+    fn disconnect(&mut self) {
+        self.connected = false;
+        self.running.clear();
+        self.prompts.clear();
+        self.watching.clear();
+    }
+
+    /// Subscriptions picking every followed session up where it left off.
+    fn resubscriptions(&mut self) -> Vec<ClientMessage> {
+        let mut messages = Vec::with_capacity(self.positions.len());
+        for (session_id, position) in &self.positions {
+            self.watching.insert(*session_id);
+            messages.push(ClientMessage::Subscribe {
+                session_id: *session_id,
+                since_seq: Some(position.seq),
+                epoch: position.epoch,
+            });
+        }
+        messages
+    }
+}
+
+/// What a [`DaemonClient`] reports to its owner.
+#[derive(Debug)]
+pub enum DaemonEvent {
+    Message(ServerMessage),
+    /// The connection dropped; the client is reconnecting
+    Disconnected,
+    /// The connection is back and followed sessions are being resubscribed
+    Reconnected,
+}
+
+/// WebSocket client of a daemon.
+///
+/// Replies the client handles itself (falling back to `SendInput` when a
+/// session is already running, failed speculative subscriptions) are not
+/// passed on; everything else arrives on the receiver returned by
+/// [`DaemonClient::connect`], along with connection changes.
+#[derive(Debug, Clone)]
+pub struct DaemonClient {
+    outgoing: mpsc::UnboundedSender<ClientMessage>,
+    state: Arc<Mutex<ClientState>>,
+}
+
+impl DaemonClient {
+    pub async fn connect(
+        info: &DaemonInfo,
+    ) -> anyhow::Result<(Self, mpsc::UnboundedReceiver<DaemonEvent>)> {
+        let stream = open(info).await?;
+        let (outgoing, outgoing_rx) = mpsc::unbounded_channel();
+        let (incoming_tx, incoming) = mpsc::unbounded_channel();
+        let client = Self {
+            outgoing,
+            state: Arc::new(Mutex::new(ClientState {
+                connected: true,
+                ..ClientState::default()
+            })),
+        };
+        tokio::spawn(
+            client
+                .clone()
+                .run(info.clone(), stream, outgoing_rx, incoming_tx),
+        );
+        Ok((client, incoming))
+    }
+
+    /// Relay messages until the receiver is dropped, reconnecting with
+    /// backoff whenever the daemon goes away.
+    async fn run(
+        self,
+        info: DaemonInfo,
+        mut stream: DaemonStream,
+        mut outgoing_rx: mpsc::UnboundedReceiver<ClientMessage>,
+        incoming_tx: mpsc::UnboundedSender<DaemonEvent>,
+    ) {
+        let mut unsent = None;
+        loop {
+            if !self
+                .serve(stream, &mut outgoing_rx, &incoming_tx, &mut unsent)
+                .await
+            {
+                return;
+            }
+            self.state.lock().disconnect();
+            if incoming_tx.send(DaemonEvent::Disconnected).is_err() {
+                return;
+            }
+
+            let mut delay = RECONNECT_DELAY_MIN;
+            stream = loop {
+                tokio::time::sleep(delay).await;
+                if incoming_tx.is_closed() {
+                    return;
+                }
+                match open(&info).await {
+                    Ok(stream) => break stream,
+                    Err(e) => {
+                        tracing::debug!(error = %e, "Daemon still unreachable");
+                        delay = (delay * 2).min(RECONNECT_DELAY_MAX);
+                    }
+                }
+            };
+            tracing::info!(url = %info.url, "Reconnected to daemon");
+            self.state.lock().connected = true;
+            if incoming_tx.send(DaemonEvent::Reconnected).is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Relay messages over one connection, resubscribing followed sessions
+    /// first; returns false once the receiver is dropped.
+    async fn serve(
+        &self,
+        stream: DaemonStream,
+        outgoing_rx: &mut mpsc::UnboundedReceiver<ClientMessage>,
+        incoming_tx: &mpsc::UnboundedSender<DaemonEvent>,
+        unsent: &mut Option<ClientMessage>,
+    ) -> bool {
+        let (mut sink, mut source) = stream.split();
+        let resubscriptions = self.state.lock().resubscriptions();
+        for message in resubscriptions.iter().chain(unsent.as_ref()) {
+            if !write(&mut sink, message).await {
+                return true;
+            }
+        }
+        *unsent = None;
+
+        loop {
+            tokio::select! {
+                message = outgoing_rx.recv() => {
+                    let Some(message) = message else {
+                        return false;
+                    };
+                    if !write(&mut sink, &message).await {
+                        *unsent = Some(message);
+                        return true;
+                    }
+                }
+                result = source.next() => {
+                    let text = match result {
+                        Some(Ok(Message::Text(text))) => text,
+                        Some(Ok(Message::Close(_))) | None => return true,
+                        Some(Ok(_)) => continue,
+                        Some(Err(e)) => {
+                            tracing::warn!(error = %e, "Daemon connection failed");
+                            return true;
+                        }
+                    };
+                    let message: ServerMessage = match serde_json::from_str(text.as_str()) {
+                        Ok(message) => message,
+                        Err(e) => {
+                            tracing::debug!(error = %e, "Ignoring unknown daemon message");
+                            continue;
+                        }
+                    };
+                    if let Some(message) = self.filter(message) {
+                        if incoming_tx.send(DaemonEvent::Message(message)).is_err() {
+                            return false;
+                        }
+                    }
+                }
+                _ = incoming_tx.closed() => return false,
+            }
+        }
+    }
+
+    /// Whether the connection is up; while it is not, prompts are refused
+    /// and other messages wait for it.
+    pub fn is_connected(&self) -> bool {
+        self.state.lock().connected
+    }
+
+    /// Send a prompt: as input when the session's agent is running, otherwise
+    /// by starting it.
+    pub fn send_prompt(
+        &self,
+        session_id: Uuid,
+        prompt: String,
+        working_dir: &Path,
+        model: Option<String>,
+        hidden: bool,
+        images: Vec<ImageAttachment>,
+    ) {
+        let mut state = self.state.lock();
+        if state.running.contains(&session_id) {
+            self.send(ClientMessage::SendInput {
+                session_id,
+                input: prompt,
+                hidden,
+                images,
+            });
+            return;
+        }
+        state.prompts.insert(
+            session_id,
+            PendingPrompt {
+                prompt: prompt.clone(),
+                hidden,
+                images: images.clone(),
+            },
+        );
+        self.send(ClientMessage::StartSession {
+            session_id,
+            prompt,
+            working_dir: working_dir.to_string_lossy().into_owned(),
+            model,
+            hidden,
+            images,
+        });
+    }
+
+    /// Follow a session in case its agent is running, e.g. started from the browser.
+    pub fn watch(&self, session_id: Uuid) {
+        self.state.lock().watching.insert(session_id);
+        self.send(ClientMessage::Subscribe {
+            session_id,
+            since_seq: None,
+            epoch: None,
+        });
+    }
+
+    /// Send a message; returns false once the connection is gone.
+    pub fn send(&self, message: ClientMessage) -> bool {
+        self.outgoing.send(message).is_ok()
+    }
+
+    /// Channel carrying a running session's permission and control answers
+    /// to the daemon, for code that talks to a local agent's input channel.
+    pub fn input_sender(&self, session_id: Uuid) -> mpsc::Sender<AgentInput> {
+        let (tx, mut rx) = mpsc::channel(16);
+        let client = self.clone();
+        tokio::spawn(async move {
+            while let Some(input) = rx.recv().await {
+                let message = match input {
+                    AgentInput::PermissionResponse { request_id, reply } => {
+                        ClientMessage::RespondToPermission {
+                            session_id,
+                            request_id,
+                            reply,
+                        }
+                    }
+                    AgentInput::ClaudeJsonl(payload) => match control_response(&payload) {
+                        Some((request_id, response)) => ClientMessage::RespondToControl {
+                            session_id,
+                            request_id,
+                            response,
+                        },
+                        None => {
+                            tracing::warn!(%session_id, "Raw agent input is not supported by the daemon");
+                            continue;
+                        }
+                    },
+                    AgentInput::CodexPrompt { text, .. } => ClientMessage::SendInput {
+                        session_id,
+                        input: text,
+                        hidden: false,
+                        images: Vec::new(),
+                    },
+                    AgentInput::OpencodeQuestion { .. } => {
+                        tracing::warn!(%session_id, "OpenCode questions are not supported by the daemon");
+                        continue;
+                    }
+                };
+                if !client.send(message) {
+                    break;
+                }
+            }
+        });
+        tx
+    }
+
+    /// Handle replies meant for the client itself; returns the message if
+    /// the caller should see it.
+    fn filter(&self, message: ServerMessage) -> Option<ServerMessage> {
+        let mut state = self.state.lock();
+        match &message {
+            ServerMessage::SessionStarted { session_id, .. } => {
+                state.prompts.remove(session_id);
+                state.running.insert(*session_id);
+                state.positions.entry(*session_id).or_default();
+            }
+            ServerMessage::Subscribed {
+                session_id,
+                epoch,
+                last_seq,
+                ..
+            } => {
+                state.watching.remove(session_id);
+                state.running.insert(*session_id);
+                state.positions.insert(
+                    *session_id,
+                    EventPosition {
+                        epoch: *epoch,
+                        seq: *last_seq,
+                    },
+                );
+            }
+            ServerMessage::AgentEvent {
+                session_id,
+                seq: Some(seq),
+                epoch,
+                ..
+            } => {
+                state.positions.insert(
+                    *session_id,
+                    EventPosition {
+                        epoch: *epoch,
+                        seq: *seq,
+                    },
+                );
+            }
+            ServerMessage::SessionEnded { session_id, .. } => {
+                state.running.remove(session_id);
+            }
+            ServerMessage::Error {
+                session_id: Some(session_id),
+                message: text,
+            } => {
+                if let Some(pending) = state.prompts.remove(session_id) {
+                    if text.contains("already running") {
+                        state.running.insert(*session_id);
+                        self.send(ClientMessage::SendInput {
+                            session_id: *session_id,
+                            input: pending.prompt,
+                            hidden: pending.hidden,
+                            images: pending.images,
+                        });
+                        return None;
+                    }
+                } else if state.watching.remove(session_id) {
+                    // Not running; nothing to follow
+                    return None;
+                }
+            }
+            _ => {}
+        }
+        Some(message)
+    }
+}
+
+/// Send a message over the connection; false once it has failed.
+async fn write(sink: &mut SplitSink<DaemonStream, Message>, message: &ClientMessage) -> bool {
+    let json = match serde_json::to_string(message) {
+        Ok(json) => json,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to serialize daemon message");
+            return true;
+        }
+    };
+    sink.send(Message::Text(json.into())).await.is_ok()
+}
+
+/// Open a WebSocket connection to the daemon.
+async fn open(info: &DaemonInfo) -> anyhow::Result<DaemonStream> {
+    let mut request = info
+        .url
+        .as_str()
+        .into_client_request()
+        .with_context(|| format!("Invalid daemon URL {}", info.url))?;
+    if let Some(token) = &info.token {
+        let value =
+            HeaderValue::from_str(&format!("Bearer {}", token)).context("Invalid API token")?;
+        request.headers_mut().insert(header::AUTHORIZATION, value);
+    }
+    let connector = match &info.ca_cert {
+        Some(path) => Some(Connector::Rustls(trusting(path)?)),
+        None => None,
+    };
+    let (stream, _) =
+        tokio_tungstenite::connect_async_tls_with_config(request, None, false, connector)
+            .await
+            .with_context(|| format!("Failed to connect to {}", info.url))?;
+    Ok(stream)
+}
+
+/// Split a serialized control response back into its request ID and payload.
+fn control_response(payload: &str) -> Option<(String, serde_json::Value)> {
+    let value: serde_json::Value = serde_json::from_str(payload.trim()).ok()?;
+    if value.get("type")?.as_str()? != "control_response" {
+        return None;
+    }
+    let response = value.get("response")?;
+    let request_id = response.get("request_id")?.as_str()?.to_string();
+    Some((request_id, response.get("response")?.clone()))
+}
+
+/// TLS configuration trusting only the certificates in this PEM file.
+fn trusting(ca_cert: &Path) -> anyhow::Result<Arc<ClientConfig>> {
+    let mut roots = RootCertStore::empty();
+    for cert in CertificateDer::pem_file_iter(ca_cert)
+        .with_context(|| format!("Failed to read certificate {}", ca_cert.display()))?
+    {
+        let cert =
+            cert.with_context(|| format!("Failed to read certificate {}", ca_cert.display()))?;
+        roots
+            .add(cert)
+            .with_context(|| format!("Invalid certificate in {}", ca_cert.display()))?;
+    }
+    let config = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::events::AgentEvent;
+    use tempfile::tempdir;
+
+    fn test_client() -> (DaemonClient, mpsc::UnboundedReceiver<ClientMessage>) {
+        let (outgoing, rx) = mpsc::unbounded_channel();
+        let client = DaemonClient {
+            outgoing,
+            state: Arc::default(),
+        };
+        (client, rx)
+    }
+
+    #[test]
+    fn test_daemon_info_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("daemon.json");
+        assert_eq!(DaemonInfo::load(&path).unwrap(), None);
+
+        let info = DaemonInfo::local("0.0.0.0", 3000, None, Some("cdt_secret".to_string()));
+        assert_eq!(info.url, "ws://127.0.0.1:3000/ws");
+        info.save(&path).unwrap();
+        assert_eq!(DaemonInfo::load(&path).unwrap(), Some(info.clone()));
+
+        // A newer server's file is left alone
+        let other = DaemonInfo {
+            pid: Some(1),
+            ..info.clone()
+        };
+        other.remove(&path);
+        assert!(path.exists());
+        info.remove(&path);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_prompt_falls_back_to_input_when_running() {
+        let (client, mut sent) = test_client();
+        let session_id = Uuid::new_v4();
+        client.send_prompt(
+            session_id,
+            "hello".to_string(),
+            Path::new("/tmp"),
+            None,
+            false,
+            Vec::new(),
+        );
+        assert!(matches!(
+            sent.try_recv(),
+            Ok(ClientMessage::StartSession { .. })
+        ));
+
+        let reply = client.filter(ServerMessage::session_error(
+            session_id,
+            format!("Session {} is already running", session_id),
+        ));
+        assert!(reply.is_none());
+        assert!(matches!(
+            sent.try_recv(),
+            Ok(ClientMessage::SendInput { input, .. }) if input == "hello"
+        ));
+
+        // Later prompts go straight to the running agent
+        client.send_prompt(
+            session_id,
+            "again".to_string(),
+            Path::new("/tmp"),
+            None,
+            false,
+            Vec::new(),
+        );
+        assert!(matches!(
+            sent.try_recv(),
+            Ok(ClientMessage::SendInput { input, .. }) if input == "again"
+        ));
+    }
+
+    #[test]
+    fn test_watching_idle_session_is_silent() {
+        let (client, _sent) = test_client();
+        let session_id = Uuid::new_v4();
+        client.watch(session_id);
+        let reply = client.filter(ServerMessage::session_error(
+            session_id,
+            format!("Session {} not found", session_id),
+        ));
+        assert!(reply.is_none());
+
+        // Errors of later requests are passed on
+        let reply = client.filter(ServerMessage::session_error(session_id, "Failed"));
+        assert!(reply.is_some());
+    }
+
+    #[test]
+    fn test_reconnect_resumes_followed_sessions() {
+        let (client, _sent) = test_client();
+        let session_id = Uuid::new_v4();
+        let epoch = Uuid::new_v4();
+        client.filter(ServerMessage::Subscribed {
+            session_id,
+            epoch: Some(epoch),
+            last_seq: 3,
+            resync_required: false,
+        });
+        client.filter(ServerMessage::sequenced_event(
+            session_id,
+            epoch,
+            4,
+            AgentEvent::TurnStarted,
+        ));
+
+        let mut state = client.state.lock();
+        state.disconnect();
+        assert!(!state.running.contains(&session_id));
+        let resubscriptions = state.resubscriptions();
+        assert!(matches!(
+            resubscriptions.as_slice(),
+            [ClientMessage::Subscribe {
+                since_seq: Some(4),
+                epoch: Some(resumed),
+                ..
+            }] if *resumed == epoch
+        ));
+        // A session the new server no longer knows is not reported
+        assert!(state.watching.contains(&session_id));
+    }
+
+    #[test]
+    fn test_control_response() {
+        let payload = r#"{"type":"control_response","response":{"subtype":"success","request_id":"req-1","response":{"behavior":"allow"}}}"#;
+        let (request_id, response) = control_response(payload).unwrap();
+        assert_eq!(request_id, "req-1");
+        assert_eq!(response["behavior"], "allow");
+        assert!(control_response(r#"{"type":"user"}"#).is_none());
+    }
+}
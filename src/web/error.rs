@@ -33,6 +33,14 @@ pub enum WebError {
     /// Conflict error (e.g., resource state mismatch).
     #[error("Conflict: {0}")]
     Conflict(String),
+
+    /// Missing or invalid API token.
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// Request not allowed (e.g., from a foreign origin).
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
 }
 
 /// Error response body.
@@ -71,6 +79,10 @@ impl IntoResponse for WebError {
                 )
             }
             WebError::Conflict(msg) => (StatusCode::CONFLICT, "Conflict", Some(msg.clone())),
+            WebError::Unauthorized(msg) => {
+                (StatusCode::UNAUTHORIZED, "Unauthorized", Some(msg.clone()))
+            }
+            WebError::Forbidden(msg) => (StatusCode::FORBIDDEN, "Forbidden", Some(msg.clone())),
         };
 
         let body = Json(ErrorResponse {
@@ -0,0 +1,69 @@
+//! Server-Sent Events stream of session and PR activity.
+
+use std::convert::Infallible;
+
+use axum::extract::{Query, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::Stream;
+use serde::Deserialize;
+use tokio::sync::broadcast;
+
+use crate::web::state::WebAppState;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ActivityStreamQuery {
+    /// Comma-separated event types to send (default: all)
+    pub events: Option<String>,
+}
+
+/// Stream activity across all sessions as it happens.
+///
+/// Each event's SSE `event` is its type and `id` its activity ID; nothing is
+/// replayed, so a client that reconnects only sees new activity.
+pub async fn stream_activity(
+    State(state): State<WebAppState>,
+    Query(query): Query<ActivityStreamQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events: Vec<String> = query
+        .events
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|event| !event.is_empty())
+        .map(str::to_string)
+        .collect();
+    let activity_rx = state.activity().subscribe();
+
+    let stream = futures::stream::unfold(
+        (activity_rx, events),
+        |(mut activity_rx, events)| async move {
+            loop {
+                let activity = match activity_rx.recv().await {
+                    Ok(activity) => activity,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "Activity stream client lagged");
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                };
+                let name = activity.kind.name();
+                if !events.is_empty() && !events.iter().any(|event| event == name) {
+                    continue;
+                }
+                match Event::default()
+                    .event(name)
+                    .id(activity.id.to_string())
+                    .json_data(&activity)
+                {
+                    Ok(event) => return Some((Ok(event), (activity_rx, events))),
+                    Err(error) => {
+                        tracing::warn!(error = %error, "Failed to serialize activity event");
+                    }
+                }
+            }
+        },
+    );
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,101 @@
+//! Audit log handlers.
+
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::services::{AuditFilter, AuditService, ServiceError};
+use crate::data::AuditEntry;
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+/// Entries returned as JSON when no limit is given; JSONL exports are unlimited.
+const DEFAULT_AUDIT_LIMIT: usize = 500;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct AuditListQuery {
+    pub session_id: Option<Uuid>,
+    /// Workspace name or ID
+    pub workspace: Option<String>,
+    pub kind: Option<String>,
+    /// RFC 3339 timestamp or YYYY-MM-DD
+    pub since: Option<String>,
+    pub limit: Option<usize>,
+    /// "json" (default) or "jsonl"
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditListResponse {
+    pub entries: Vec<AuditEntry>,
+    /// Latest failure to write the audit log; some entries may be missing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_error: Option<String>,
+}
+
+/// List audit entries (oldest first), or export them as JSON Lines with `format=jsonl`.
+pub async fn list_audit(
+    State(state): State<WebAppState>,
+    Query(query): Query<AuditListQuery>,
+) -> Result<Response, WebError> {
+    let jsonl = match query.format.as_deref() {
+        None | Some("json") => false,
+        Some("jsonl") => true,
+        Some(other) => {
+            return Err(WebError::BadRequest(format!(
+                "Unknown audit format '{}' (expected json or jsonl)",
+                other
+            )))
+        }
+    };
+    let filter = AuditFilter {
+        session_id: query.session_id,
+        workspace: query.workspace,
+        kind: query.kind,
+        since: query.since,
+        limit: match (query.limit, jsonl) {
+            (Some(limit), _) => Some(limit),
+            (None, true) => None,
+            (None, false) => Some(DEFAULT_AUDIT_LIMIT),
+        },
+    };
+
+    let core = state.core().await;
+    let entries = AuditService::list(&core, &filter).map_err(map_service_error)?;
+    if !jsonl {
+        let write_error = core
+            .session_journal()
+            .and_then(|journal| journal.audit_failure());
+        return Ok(Json(AuditListResponse {
+            entries,
+            write_error,
+        })
+        .into_response());
+    }
+
+    let content = AuditService::to_jsonl(&entries).map_err(map_service_error)?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"conduit-audit.jsonl\"",
+            ),
+        ],
+        content,
+    )
+        .into_response())
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
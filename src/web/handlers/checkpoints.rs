@@ -0,0 +1,76 @@
+//! Checkpoint handlers for the Conduit web API.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::services::{CheckpointService, ServiceError};
+use crate::data::Checkpoint;
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+#[derive(Debug, Serialize)]
+pub struct ListCheckpointsResponse {
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RewindRequest {
+    /// Rewind to before this turn (default: the latest turn)
+    #[serde(default)]
+    pub turn: Option<u32>,
+    /// Also remove the rewound turns from the transcript (default: from config)
+    #[serde(default)]
+    pub truncate_transcript: Option<bool>,
+}
+
+/// List a session's per-turn checkpoints.
+pub async fn list_checkpoints(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListCheckpointsResponse>, WebError> {
+    let core = state.core().await;
+    let checkpoints = CheckpointService::list(&core, id).map_err(map_service_error)?;
+
+    Ok(Json(ListCheckpointsResponse { checkpoints }))
+}
+
+/// Restore a session's workspace to before a turn.
+pub async fn rewind_session(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<RewindRequest>,
+) -> Result<Json<Checkpoint>, WebError> {
+    if state.session_manager().is_in_turn(id).await {
+        return Err(WebError::Conflict(
+            "Stop the agent before rewinding".to_string(),
+        ));
+    }
+
+    let (rewind, truncate) = {
+        let core = state.core().await;
+        let truncate = req
+            .truncate_transcript
+            .unwrap_or(core.config().checkpoints.truncate_transcript);
+        let rewind =
+            CheckpointService::prepare_rewind(&core, id, req.turn).map_err(map_service_error)?;
+        (rewind, truncate)
+    };
+    let checkpoint = tokio::task::spawn_blocking(move || rewind.run(truncate))
+        .await
+        .map_err(|e| WebError::Internal(format!("Rewind task failed: {}", e)))?
+        .map_err(map_service_error)?;
+
+    Ok(Json(checkpoint))
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
@@ -0,0 +1,87 @@
+//! CI failure handlers for the Conduit web API.
+//!
+//! "Fix CI" collects the failing checks of a workspace's PR with their logs
+//! and returns a prompt, which the client sends to the session's agent over
+//! the WebSocket.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::core::services::{CiService, CommitService, ServiceError};
+use crate::git::FailedCheck;
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+#[derive(Debug, Serialize)]
+pub struct FailedCheckResponse {
+    pub name: String,
+    pub workflow: Option<String>,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub error_lines: Vec<String>,
+    pub has_log: bool,
+}
+
+impl From<FailedCheck> for FailedCheckResponse {
+    fn from(check: FailedCheck) -> Self {
+        Self {
+            has_log: check.log.is_some(),
+            name: check.name,
+            workflow: check.workflow,
+            description: check.description,
+            link: check.link,
+            error_lines: check.error_lines,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CiFixResponse {
+    pub pr_number: u32,
+    pub pr_url: String,
+    pub pending: usize,
+    pub checks: Vec<FailedCheckResponse>,
+    pub prompt: String,
+}
+
+/// Collect the failing checks of the workspace's PR and build a fix prompt.
+pub async fn build_ci_fix_prompt(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CiFixResponse>, WebError> {
+    let (working_dir, branch) = {
+        let core = state.core().await;
+        let working_dir = CommitService::working_dir(&core, id).map_err(map_service_error)?;
+        let branch = core
+            .workspace_store()
+            .and_then(|store| store.get_by_id(id).ok().flatten())
+            .map(|workspace| workspace.branch);
+        (working_dir, branch)
+    };
+
+    let failures = tokio::task::spawn_blocking(move || CiService::failures(&working_dir))
+        .await
+        .map_err(|e| WebError::Internal(format!("CI task failed: {}", e)))?
+        .map_err(map_service_error)?;
+    let prompt = CiService::fix_prompt(&failures, branch.as_deref()).map_err(map_service_error)?;
+
+    Ok(Json(CiFixResponse {
+        pr_number: failures.pr_number,
+        pr_url: failures.pr_url,
+        pending: failures.pending,
+        checks: failures.checks.into_iter().map(Into::into).collect(),
+        prompt,
+    }))
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
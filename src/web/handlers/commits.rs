@@ -0,0 +1,157 @@
+//! Commit handlers for the Conduit web API.
+//!
+//! The web commit dialog lists the workspace's changed files, asks for a
+//! generated commit message, lets the user edit it, then commits (and
+//! optionally pushes).
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::services::{CommitService, ServiceError};
+use crate::git::ChangedFile;
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+#[derive(Debug, Serialize)]
+pub struct ChangedFileResponse {
+    pub path: String,
+    /// Porcelain status code, e.g. `M `, ` M`, `??`
+    pub status: String,
+    pub staged: bool,
+    pub unstaged: bool,
+}
+
+impl From<ChangedFile> for ChangedFileResponse {
+    fn from(file: ChangedFile) -> Self {
+        Self {
+            status: format!("{}{}", file.index_status, file.worktree_status),
+            staged: file.is_staged(),
+            unstaged: file.is_unstaged(),
+            path: file.path,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommitPreviewResponse {
+    pub files: Vec<ChangedFileResponse>,
+    /// Whether only staged changes will be committed
+    pub staged_only: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommitMessageResponse {
+    pub message: String,
+    pub tool_used: String,
+    pub used_fallback: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommitRequest {
+    /// Commit message (generated when omitted or blank)
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Push the branch to `origin` after committing
+    #[serde(default)]
+    pub push: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommitResponse {
+    pub commit_sha: String,
+    pub message: String,
+    pub pushed: bool,
+    pub push_error: Option<String>,
+    /// Tool that generated the message, when it was generated
+    pub tool_used: Option<String>,
+}
+
+/// List the files the next commit would include.
+pub async fn get_commit_preview(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CommitPreviewResponse>, WebError> {
+    let working_dir = {
+        let core = state.core().await;
+        CommitService::working_dir(&core, id).map_err(map_service_error)?
+    };
+    let files = CommitService::changed_files(&working_dir).map_err(map_service_error)?;
+    let staged_only = files.iter().any(ChangedFile::is_staged);
+
+    Ok(Json(CommitPreviewResponse {
+        files: files.into_iter().map(ChangedFileResponse::from).collect(),
+        staged_only,
+    }))
+}
+
+/// Generate a commit message from the workspace's pending changes.
+pub async fn generate_commit_message(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CommitMessageResponse>, WebError> {
+    // Release the core before the (slow) AI call
+    let (working_dir, tools) = {
+        let core = state.core().await;
+        let working_dir = CommitService::working_dir(&core, id).map_err(map_service_error)?;
+        (working_dir, core.tools().clone())
+    };
+    let generated = CommitService::generate_message(&tools, &working_dir)
+        .await
+        .map_err(map_service_error)?;
+
+    Ok(Json(CommitMessageResponse {
+        message: generated.message,
+        tool_used: generated.tool_used,
+        used_fallback: generated.used_fallback,
+    }))
+}
+
+/// Commit the workspace's pending changes, optionally pushing the branch.
+pub async fn commit_workspace(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CommitRequest>,
+) -> Result<Json<CommitResponse>, WebError> {
+    let (working_dir, tools) = {
+        let core = state.core().await;
+        let working_dir = CommitService::working_dir(&core, id).map_err(map_service_error)?;
+        (working_dir, core.tools().clone())
+    };
+
+    let (message, tool_used) = match req.message.filter(|message| !message.trim().is_empty()) {
+        Some(message) => (message, None),
+        None => {
+            let generated = CommitService::generate_message(&tools, &working_dir)
+                .await
+                .map_err(map_service_error)?;
+            (generated.message, Some(generated.tool_used))
+        }
+    };
+
+    let push = req.push;
+    let outcome =
+        tokio::task::spawn_blocking(move || CommitService::commit(&working_dir, &message, push))
+            .await
+            .map_err(|e| WebError::Internal(format!("Commit task failed: {}", e)))?
+            .map_err(map_service_error)?;
+
+    Ok(Json(CommitResponse {
+        commit_sha: outcome.commit_sha,
+        message: outcome.message,
+        pushed: outcome.pushed,
+        push_error: outcome.push_error,
+        tool_used,
+    }))
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
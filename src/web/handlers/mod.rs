@@ -1,12 +1,22 @@
 //! HTTP request handlers for the Conduit web API.
 
+pub mod activity;
+pub mod audit;
 pub mod bootstrap;
+pub mod checkpoints;
+pub mod ci;
+pub mod commits;
 pub mod external_sessions;
 pub mod models;
 pub mod onboarding;
 pub mod queue;
+pub mod races;
 pub mod repositories;
+pub mod reviews;
+pub mod search;
 pub mod sessions;
+pub mod stats;
+pub mod sync;
 pub mod themes;
 pub mod ui_state;
 pub mod workspaces;
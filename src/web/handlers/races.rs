@@ -0,0 +1,292 @@
+//! Race handlers for the Conduit web API.
+//!
+//! A race sends one prompt to several agents, each in its own worktree from
+//! the same base. Creating a race creates one session per contender; clients
+//! start each agent with the race prompt over the WebSocket (`StartSession`).
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::services::{
+    CreateRaceParams, CreateSessionParams, RaceContender, RaceService, ServiceError, SessionService,
+};
+use crate::core::ConduitCore;
+use crate::data::{Race, RaceTestResult, UsageTotals};
+use crate::web::error::WebError;
+use crate::web::handlers::sessions::SessionResponse;
+use crate::web::handlers::workspaces::archive_workspace_inner;
+use crate::web::state::WebAppState;
+use crate::web::status_types::GitDiffStatsResponse;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRaceRequest {
+    /// Workspace whose current branch the contenders start from
+    pub workspace_id: Uuid,
+    pub prompt: String,
+    /// Contenders as `agent[:model]` (default: `[race] contenders` from config)
+    #[serde(default)]
+    pub contenders: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PickWinnerRequest {
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RaceEntryResponse {
+    pub workspace_id: Uuid,
+    pub workspace_name: String,
+    pub branch: String,
+    pub agent_type: String,
+    pub model: Option<String>,
+    /// Session running this contender, if any
+    pub session_id: Option<Uuid>,
+    pub archived: bool,
+    /// Whether the contender's agent is working on a turn
+    pub running: bool,
+    pub diff_stats: Option<GitDiffStatsResponse>,
+    pub usage: UsageTotals,
+    pub test_result: Option<RaceTestResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RaceResponse {
+    pub id: Uuid,
+    pub repository_id: Uuid,
+    pub prompt: String,
+    pub base_branch: String,
+    pub base_commit: String,
+    pub winner_workspace_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub entries: Vec<RaceEntryResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateRaceResponse {
+    pub race: RaceResponse,
+    /// One session per contender, in race order
+    pub sessions: Vec<SessionResponse>,
+}
+
+/// Create a race and one session per contender.
+pub async fn create_race(
+    State(state): State<WebAppState>,
+    Json(req): Json<CreateRaceRequest>,
+) -> Result<(StatusCode, Json<CreateRaceResponse>), WebError> {
+    let core = state.core().await;
+    let contenders = match req.contenders {
+        Some(specs) => specs
+            .iter()
+            .map(|spec| RaceContender::parse(spec))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(map_service_error)?,
+        None => RaceService::configured_contenders(&core).map_err(map_service_error)?,
+    };
+
+    let race_store = core
+        .race_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let workspace_store = core
+        .workspace_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let repo_store = core
+        .repo_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+
+    let race = RaceService::create(
+        race_store,
+        workspace_store,
+        repo_store,
+        core.worktree_manager(),
+        core.config(),
+        CreateRaceParams {
+            workspace_id: req.workspace_id,
+            prompt: req.prompt,
+            contenders,
+        },
+    )
+    .map_err(map_service_error)?;
+
+    let mut sessions = Vec::new();
+    for entry in &race.entries {
+        let session = SessionService::create_session(
+            &core,
+            CreateSessionParams {
+                workspace_id: Some(entry.workspace_id),
+                agent_type: entry.agent_type,
+                model: entry.model.clone(),
+            },
+        )
+        .map_err(map_service_error)?;
+        sessions.push(SessionResponse::from(session));
+    }
+
+    let race = race_response(&state, &core, race).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateRaceResponse { race, sessions }),
+    ))
+}
+
+/// Get a race with each contender's diff stats, usage and test result.
+pub async fn get_race(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RaceResponse>, WebError> {
+    let core = state.core().await;
+    let race_store = core
+        .race_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let race = RaceService::get(race_store, id).map_err(map_service_error)?;
+
+    Ok(Json(race_response(&state, &core, race).await?))
+}
+
+/// Run `[race] test_command` in every contender's workspace.
+pub async fn run_race_tests(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RaceResponse>, WebError> {
+    let core = state.core().await;
+    let command =
+        core.config().race.test_command.clone().ok_or_else(|| {
+            WebError::BadRequest("No [race] test_command is configured".to_string())
+        })?;
+    let race_store = core
+        .race_store_clone()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let workspace_store = core
+        .workspace_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let race = RaceService::get(&race_store, id).map_err(map_service_error)?;
+
+    let mut targets = Vec::new();
+    for entry in &race.entries {
+        let workspace = workspace_store
+            .get_by_id(entry.workspace_id)
+            .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?;
+        if let Some(workspace) = workspace {
+            if workspace.archived_at.is_none() && workspace.path.exists() {
+                targets.push((workspace.id, workspace.path));
+            }
+        }
+    }
+
+    tokio::task::spawn_blocking(move || {
+        for (workspace_id, path) in targets {
+            RaceService::run_tests(&race_store, id, workspace_id, &path, &command)?;
+        }
+        Ok::<_, ServiceError>(())
+    })
+    .await
+    .map_err(|e| WebError::Internal(format!("Test task failed: {}", e)))?
+    .map_err(map_service_error)?;
+
+    let race_store = core
+        .race_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let race = RaceService::get(race_store, id).map_err(map_service_error)?;
+    Ok(Json(race_response(&state, &core, race).await?))
+}
+
+/// Keep one contender and archive the others.
+pub async fn pick_winner(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<PickWinnerRequest>,
+) -> Result<Json<RaceResponse>, WebError> {
+    let core = state.core().await;
+    let race_store = core
+        .race_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let workspace_store = core
+        .workspace_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let race = RaceService::get(race_store, id).map_err(map_service_error)?;
+    if race.winner_workspace_id.is_some() {
+        return Err(WebError::Conflict(
+            "This race already has a winner".to_string(),
+        ));
+    }
+
+    let losers =
+        RaceService::pick_winner(race_store, &race, req.workspace_id).map_err(map_service_error)?;
+    for workspace_id in losers {
+        let archived = workspace_store
+            .get_by_id(workspace_id)
+            .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?
+            .is_none_or(|workspace| workspace.archived_at.is_some());
+        if !archived {
+            archive_workspace_inner(&state, &core, workspace_id, false)?;
+        }
+    }
+
+    let race = RaceService::get(race_store, id).map_err(map_service_error)?;
+    Ok(Json(race_response(&state, &core, race).await?))
+}
+
+async fn race_response(
+    state: &WebAppState,
+    core: &ConduitCore,
+    race: Race,
+) -> Result<RaceResponse, WebError> {
+    let workspace_store = core
+        .workspace_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let session_store = core
+        .session_tab_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let standings = RaceService::standings(workspace_store, core.usage_store(), &race)
+        .map_err(map_service_error)?;
+
+    let mut entries = Vec::with_capacity(standings.len());
+    for standing in standings {
+        let session_id = session_store
+            .get_by_workspace_id(standing.entry.workspace_id)
+            .map_err(|e| WebError::Internal(format!("Failed to get session: {}", e)))?
+            .map(|session| session.id);
+        let running = match session_id {
+            Some(session_id) => state.session_manager().is_in_turn(session_id).await,
+            None => false,
+        };
+        entries.push(RaceEntryResponse {
+            workspace_id: standing.entry.workspace_id,
+            workspace_name: standing.workspace_name,
+            branch: standing.branch,
+            agent_type: standing.entry.agent_type.as_str().to_string(),
+            model: standing.entry.model,
+            session_id,
+            archived: standing.archived,
+            running,
+            diff_stats: standing.diff_stats.map(GitDiffStatsResponse::from),
+            usage: standing.usage,
+            test_result: standing.entry.test_result,
+        });
+    }
+
+    Ok(RaceResponse {
+        id: race.id,
+        repository_id: race.repository_id,
+        prompt: race.prompt,
+        base_branch: race.base_branch,
+        base_commit: race.base_commit,
+        winner_workspace_id: race.winner_workspace_id,
+        created_at: race.created_at,
+        entries,
+    })
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
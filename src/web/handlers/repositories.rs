@@ -10,10 +10,12 @@ use std::path::{Component, PathBuf};
 use uuid::Uuid;
 
 use crate::core::resolve_repo_workspace_settings;
-use crate::data::Repository;
-use crate::git::WorkspaceMode;
+use crate::core::services::{CloneService, ServiceError};
+use crate::data::{Repository, RepositoryStore};
+use crate::git::{SyncStrategy, WorkspaceMode};
 use crate::web::error::WebError;
 use crate::web::state::WebAppState;
+use crate::web::CloneJobResponse;
 
 /// Response for a single repository.
 #[derive(Debug, Serialize)]
@@ -28,6 +30,8 @@ pub struct RepositoryResponse {
     pub archive_delete_branch_effective: bool,
     pub archive_remote_prompt: Option<bool>,
     pub archive_remote_prompt_effective: bool,
+    pub sync_strategy: Option<SyncStrategy>,
+    pub sync_strategy_effective: SyncStrategy,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -46,6 +50,8 @@ impl RepositoryResponse {
             archive_delete_branch_effective: settings.archive_delete_branch,
             archive_remote_prompt: repo.archive_remote_prompt,
             archive_remote_prompt_effective: settings.archive_remote_prompt,
+            sync_strategy: repo.sync_strategy,
+            sync_strategy_effective: settings.sync_strategy,
             created_at: repo.created_at.to_rfc3339(),
             updated_at: repo.updated_at.to_rfc3339(),
         }
@@ -72,6 +78,7 @@ pub struct UpdateRepositorySettingsRequest {
     pub workspace_mode: Option<WorkspaceMode>,
     pub archive_delete_branch: Option<bool>,
     pub archive_remote_prompt: Option<bool>,
+    pub sync_strategy: Option<SyncStrategy>,
 }
 
 /// List all repositories.
@@ -133,26 +140,40 @@ pub async fn create_repository(
         ));
     }
 
-    // Create repository model
+    let core = state.core().await;
+    let store = core
+        .repo_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let config = core.config();
+
+    // Create repository model; URL-only repositories are cloned in the background
+    let mut clone_destination = None;
     let repo = if let Some(path) = req.base_path {
         Repository::from_local_path(&req.name, PathBuf::from(path))
     } else if let Some(url) = req.repository_url {
-        Repository::from_url(&req.name, url)
+        let name = CloneService::repo_name(&url, Some(&req.name)).map_err(map_service_error)?;
+        let destination = CloneService::destination(core.app_state_store(), &name);
+        if destination.exists() {
+            return Err(WebError::BadRequest(format!(
+                "{} already exists; add it as a local repository instead",
+                destination.display()
+            )));
+        }
+        clone_destination = Some(destination);
+        Repository::from_url(&name, url)
     } else {
         unreachable!()
     };
 
     // Save to database
-    let core = state.core().await;
-    let store = core
-        .repo_store()
-        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
-    let config = core.config();
-
     store
         .create(&repo)
         .map_err(|e| WebError::Internal(format!("Failed to create repository: {}", e)))?;
 
+    if let Some(destination) = clone_destination {
+        start_clone(&state, store.clone(), repo.id, destination);
+    }
+
     Ok((
         StatusCode::CREATED,
         Json(RepositoryResponse::from_repo(repo, config)),
@@ -193,6 +214,7 @@ pub async fn update_repository_settings(
     let workspace_mode = req.workspace_mode.or(repo.workspace_mode);
     let archive_delete_branch = req.archive_delete_branch.or(repo.archive_delete_branch);
     let archive_remote_prompt = req.archive_remote_prompt.or(repo.archive_remote_prompt);
+    let sync_strategy = req.sync_strategy.or(repo.sync_strategy);
 
     repo_store
         .update_settings(
@@ -200,6 +222,7 @@ pub async fn update_repository_settings(
             workspace_mode,
             archive_delete_branch,
             archive_remote_prompt,
+            sync_strategy,
         )
         .map_err(|e| WebError::Internal(format!("Failed to update repository: {}", e)))?;
 
@@ -412,6 +435,11 @@ pub async fn remove_repository(
         if let Err(e) = session_store.set_open_by_workspace(ws.id, false) {
             tracing::warn!(error = %e, "Failed to close sessions for archived workspace");
         }
+        if let Some(events) = core.session_event_store() {
+            if let Err(e) = events.delete_by_workspace(ws.id) {
+                tracing::warn!(error = %e, "Failed to delete journal of archived workspace");
+            }
+        }
 
         // Remove from status manager
         state.status_manager().remove_workspace(ws.id);
@@ -476,3 +504,87 @@ pub async fn remove_repository(
         errors,
     }))
 }
+
+/// Get the progress of a repository's clone.
+pub async fn get_clone_status(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CloneJobResponse>, WebError> {
+    state
+        .clone_jobs()
+        .get(id)
+        .map(Json)
+        .ok_or_else(|| WebError::NotFound(format!("No clone for repository {}", id)))
+}
+
+/// Retry cloning a repository that has no local checkout yet.
+pub async fn retry_clone(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<CloneJobResponse>), WebError> {
+    let core = state.core().await;
+    let store = core
+        .repo_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+
+    let repo = store
+        .get_by_id(id)
+        .map_err(|e| WebError::Internal(format!("Failed to get repository: {}", e)))?
+        .ok_or_else(|| WebError::NotFound(format!("Repository {} not found", id)))?;
+    if repo.base_path.is_some() {
+        return Err(WebError::Conflict(
+            "Repository already has a local checkout".to_string(),
+        ));
+    }
+    if repo.repository_url.is_none() {
+        return Err(WebError::BadRequest(
+            "Repository has no URL to clone".to_string(),
+        ));
+    }
+
+    let destination = CloneService::destination(core.app_state_store(), &repo.name);
+    if !start_clone(&state, store.clone(), id, destination) {
+        return Err(WebError::Conflict(
+            "Repository is already being cloned".to_string(),
+        ));
+    }
+
+    let job = state
+        .clone_jobs()
+        .get(id)
+        .ok_or_else(|| WebError::Internal("Clone was not recorded".to_string()))?;
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// Clone a URL-only repository in the background, tracking its progress.
+///
+/// Returns false if a clone of the repository is already running.
+fn start_clone(
+    state: &WebAppState,
+    store: RepositoryStore,
+    repo_id: Uuid,
+    destination: PathBuf,
+) -> bool {
+    let jobs = state.clone_jobs().clone();
+    if !jobs.start(repo_id, destination.display().to_string()) {
+        return false;
+    }
+    tokio::task::spawn_blocking(move || {
+        let result = CloneService::clone_existing(&store, repo_id, &destination, &mut |progress| {
+            jobs.progress(repo_id, progress)
+        });
+        if let Err(error) = &result {
+            tracing::warn!(%repo_id, error = %error, "Repository clone failed");
+        }
+        jobs.finish(repo_id, result.map(|_| ()).map_err(|e| e.to_string()));
+    });
+    true
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
@@ -0,0 +1,153 @@
+//! PR review comment handlers for the Conduit web API.
+//!
+//! The review panel lists the unresolved comments on a workspace's PR. The
+//! client turns the selected comments into a prompt here, sends it to the
+//! session's agent over the WebSocket, and the comments are marked
+//! addressed.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::services::{CommitService, PendingReview, ReviewService, ServiceError};
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+#[derive(Debug, Serialize)]
+pub struct ReviewCommentResponse {
+    pub id: String,
+    pub thread_id: String,
+    pub path: String,
+    pub line: Option<u32>,
+    pub author: String,
+    pub body: String,
+    pub url: String,
+    pub outdated: bool,
+    pub addressed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReviewCommentsResponse {
+    pub pr_number: u32,
+    pub pr_url: String,
+    pub comments: Vec<ReviewCommentResponse>,
+}
+
+impl From<PendingReview> for ReviewCommentsResponse {
+    fn from(review: PendingReview) -> Self {
+        let comments = review
+            .comments
+            .iter()
+            .map(|comment| ReviewCommentResponse {
+                addressed: review.is_addressed(comment),
+                id: comment.id.clone(),
+                thread_id: comment.thread_id.clone(),
+                path: comment.path.clone(),
+                line: comment.line,
+                author: comment.author.clone(),
+                body: comment.body.clone(),
+                url: comment.url.clone(),
+                outdated: comment.outdated,
+            })
+            .collect();
+        Self {
+            pr_number: review.pr_number,
+            pr_url: review.pr_url,
+            comments,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewPromptRequest {
+    pub comment_ids: Vec<String>,
+    /// Mark the comments addressed once the prompt is built
+    #[serde(default = "default_true")]
+    pub mark_addressed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReviewPromptResponse {
+    pub prompt: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetAddressedRequest {
+    pub comment_ids: Vec<String>,
+    pub addressed: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// List the unresolved review comments on the workspace's PR.
+pub async fn list_review_comments(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReviewCommentsResponse>, WebError> {
+    let review = fetch_review(&state, id).await?;
+    Ok(Json(ReviewCommentsResponse::from(review)))
+}
+
+/// Build the agent prompt for the selected review comments.
+pub async fn build_review_prompt(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<ReviewPromptRequest>,
+) -> Result<Json<ReviewPromptResponse>, WebError> {
+    let review = fetch_review(&state, id).await?;
+    let prompt =
+        ReviewService::build_prompt(&review, &req.comment_ids).map_err(map_service_error)?;
+
+    if req.mark_addressed {
+        let core = state.core().await;
+        ReviewService::set_addressed(core.review_comment_store(), id, &req.comment_ids, true)
+            .map_err(map_service_error)?;
+    }
+
+    Ok(Json(ReviewPromptResponse { prompt }))
+}
+
+/// Mark review comments as addressed, or clear the mark.
+pub async fn set_review_comments_addressed(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<SetAddressedRequest>,
+) -> Result<StatusCode, WebError> {
+    let core = state.core().await;
+    ReviewService::set_addressed(
+        core.review_comment_store(),
+        id,
+        &req.comment_ids,
+        req.addressed,
+    )
+    .map_err(map_service_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn fetch_review(state: &WebAppState, id: Uuid) -> Result<PendingReview, WebError> {
+    let (working_dir, store) = {
+        let core = state.core().await;
+        let working_dir = CommitService::working_dir(&core, id).map_err(map_service_error)?;
+        (working_dir, core.review_comment_store().cloned())
+    };
+    tokio::task::spawn_blocking(move || {
+        ReviewService::pending_review(store.as_ref(), id, &working_dir)
+    })
+    .await
+    .map_err(|e| WebError::Internal(format!("Review task failed: {}", e)))?
+    .map_err(map_service_error)
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
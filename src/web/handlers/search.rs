@@ -0,0 +1,126 @@
+//! Full-text search handlers.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::core::services::search_service::DEFAULT_SEARCH_LIMIT;
+use crate::core::services::{SearchService, ServiceError};
+use crate::data::{SearchHit, SearchSource};
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+/// How long a refreshed index is reused before searching refreshes it again.
+const INDEX_MAX_AGE_SECS: i64 = 60;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResultResponse {
+    /// "conduit" or "external"
+    pub source: String,
+    /// Conduit session tab ID (Conduit sessions only)
+    pub session_id: Option<String>,
+    /// Agent session ID to import (external sessions only)
+    pub external_session_id: Option<String>,
+    pub agent_type: String,
+    pub title: String,
+    pub project: Option<String>,
+    pub file_path: Option<String>,
+    pub message_index: usize,
+    pub role: String,
+    /// Excerpt with matched terms wrapped in « and »
+    pub snippet: String,
+    pub indexed_at: String,
+}
+
+impl SearchResultResponse {
+    fn from_hit(hit: SearchHit) -> Self {
+        let document = hit.document;
+        let (session_id, external_session_id) = match document.source {
+            SearchSource::Conduit => (Some(document.source_key.clone()), None),
+            SearchSource::External => (
+                None,
+                document.file_path.as_ref().and_then(|path| {
+                    path.file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .map(|stem| stem.to_string())
+                }),
+            ),
+        };
+        Self {
+            source: document.source.as_str().to_string(),
+            session_id,
+            external_session_id,
+            agent_type: document.agent_type.as_str().to_string(),
+            title: document.title,
+            project: document.project,
+            file_path: document
+                .file_path
+                .map(|path| path.to_string_lossy().to_string()),
+            message_index: hit.message_index,
+            role: hit.role,
+            snippet: hit.snippet,
+            indexed_at: hit.indexed_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub query: String,
+    pub results: Vec<SearchResultResponse>,
+}
+
+/// Search prompts, assistant replies and tool output across all sessions.
+pub async fn search(
+    State(state): State<WebAppState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<SearchResponse>, WebError> {
+    let q = query.q.unwrap_or_default();
+    if q.trim().is_empty() {
+        return Err(WebError::BadRequest(
+            "Query parameter 'q' is required".to_string(),
+        ));
+    }
+
+    let indexer = {
+        let core = state.core().await;
+        SearchService::indexer(&core).map_err(map_service_error)?
+    };
+    let refresh = tokio::task::spawn_blocking(move || {
+        indexer.refresh_if_stale(Duration::seconds(INDEX_MAX_AGE_SECS))
+    })
+    .await
+    .map_err(|e| WebError::Internal(format!("Search index refresh failed: {}", e)))?;
+    if let Err(e) = refresh {
+        tracing::warn!(error = %e, "Failed to refresh search index");
+    }
+
+    let core = state.core().await;
+    let hits = SearchService::search(&core, &q, query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+        .map_err(map_service_error)?;
+
+    Ok(Json(SearchResponse {
+        query: q,
+        results: hits
+            .into_iter()
+            .map(SearchResultResponse::from_hit)
+            .collect(),
+    }))
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
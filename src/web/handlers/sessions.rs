@@ -2,23 +2,25 @@
 
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
+    response::IntoResponse,
     Json,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::agent::{
-    load_claude_history_with_debug, load_codex_history_with_debug,
+    load_claude_history_with_debug, load_codex_history_with_debug, load_journal_history,
     load_opencode_history_with_debug, AgentMode, AgentType, ModelRegistry,
 };
 use crate::core::resolve_repo_workspace_settings;
 use crate::core::services::session_service::CreateForkedSessionParams;
 use crate::core::services::{
-    CreateSessionParams, ServiceError, SessionService, UpdateSessionParams,
+    CreateSessionParams, ExportFormat, ExportService, ServiceError, SessionService,
+    UpdateSessionParams,
 };
 use crate::data::{ForkSeed, SessionTab, Workspace};
-use crate::ui::app_prompt;
+use crate::ui::app_prompt::{self, FORK_SEED_PREFIX};
 use crate::ui::components::{ChatMessage, MessageRole};
 use crate::util::names::{generate_branch_name, generate_workspace_name, get_git_username};
 use crate::web::error::WebError;
@@ -116,19 +118,7 @@ pub async fn create_session(
     State(state): State<WebAppState>,
     Json(req): Json<CreateSessionRequest>,
 ) -> Result<(StatusCode, Json<SessionResponse>), WebError> {
-    // Parse agent type
-    let agent_type = match req.agent_type.to_lowercase().as_str() {
-        "claude" => AgentType::Claude,
-        "codex" => AgentType::Codex,
-        "gemini" => AgentType::Gemini,
-        "opencode" => AgentType::Opencode,
-        _ => {
-            return Err(WebError::BadRequest(format!(
-                "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode",
-                req.agent_type
-            )));
-        }
-    };
+    let agent_type = parse_agent_type(&req.agent_type)?;
 
     let core = state.core().await;
     let session = SessionService::create_session(
@@ -151,6 +141,7 @@ pub async fn close_session(
 ) -> Result<StatusCode, WebError> {
     let core = state.core().await;
     SessionService::close_session(&core, id).map_err(map_service_error)?;
+    state.session_manager().forget_events(id);
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -164,19 +155,8 @@ pub async fn update_session(
     let core = state.core().await;
     let agent_type = req
         .agent_type
-        .as_ref()
-        .map(
-            |agent_type_str| match agent_type_str.to_lowercase().as_str() {
-                "claude" => Ok(AgentType::Claude),
-                "codex" => Ok(AgentType::Codex),
-                "gemini" => Ok(AgentType::Gemini),
-                "opencode" => Ok(AgentType::Opencode),
-                _ => Err(WebError::BadRequest(format!(
-                    "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode",
-                    agent_type_str
-                ))),
-            },
-        )
+        .as_deref()
+        .map(parse_agent_type)
         .transpose()?;
 
     let agent_mode = req
@@ -214,47 +194,17 @@ fn map_service_error(error: ServiceError) -> WebError {
     }
 }
 
-fn load_history_for_session(session: &SessionTab) -> Vec<ChatMessage> {
-    let Some(agent_session_id) = session.agent_session_id.as_deref() else {
-        return Vec::new();
-    };
-
-    let mut messages = match session.agent_type {
-        AgentType::Claude => load_claude_history_with_debug(agent_session_id)
-            .map(|(messages, _, _)| messages)
-            .unwrap_or_else(|e| {
-                tracing::warn!("Failed to load Claude history: {}", e);
-                Vec::new()
-            }),
-        AgentType::Codex => load_codex_history_with_debug(agent_session_id)
-            .map(|(messages, _, _)| messages)
-            .unwrap_or_else(|e| {
-                tracing::warn!("Failed to load Codex history: {}", e);
-                Vec::new()
-            }),
-        AgentType::Gemini => Vec::new(),
-        AgentType::Opencode => load_opencode_history_with_debug(agent_session_id)
-            .map(|(messages, _, _)| messages)
-            .unwrap_or_else(|e| {
-                tracing::warn!("Failed to load OpenCode history: {}", e);
-                Vec::new()
-            }),
-    };
-
-    if let Some(pending) = session.pending_user_message.as_ref() {
-        let already_in_history = messages
-            .iter()
-            .rev()
-            .find(|m| m.role == MessageRole::User)
-            .map(|m| m.content.as_str() == pending.as_str())
-            .unwrap_or(false);
-
-        if !already_in_history {
-            messages.push(ChatMessage::user(pending.clone()));
-        }
+fn parse_agent_type(value: &str) -> Result<AgentType, WebError> {
+    match value.to_lowercase().as_str() {
+        "claude" => Ok(AgentType::Claude),
+        "codex" => Ok(AgentType::Codex),
+        "gemini" => Ok(AgentType::Gemini),
+        "opencode" => Ok(AgentType::Opencode),
+        _ => Err(WebError::BadRequest(format!(
+            "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode",
+            value
+        ))),
     }
-
-    messages
 }
 
 fn estimate_tokens(text: &str) -> i64 {
@@ -326,6 +276,14 @@ pub struct HistoryDebugEntryResponse {
     pub raw: serde_json::Value,
 }
 
+#[derive(Debug, Deserialize, Default)]
+pub struct ForkSessionQuery {
+    /// Hand the session off to this agent (defaults to the session's own agent)
+    pub agent_type: Option<String>,
+    /// Model for the new session (defaults to the parent's, or the target agent's default)
+    pub model: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Default)]
 pub struct SessionEventsQuery {
     pub limit: Option<usize>,
@@ -351,11 +309,19 @@ pub async fn get_session_events(
         .map_err(|e| WebError::Internal(format!("Failed to get session: {}", e)))?
         .ok_or_else(|| WebError::NotFound(format!("Session {} not found", id)))?;
 
-    // Get the agent session ID
-    let agent_session_id = match &session.agent_session_id {
-        Some(id) => id.clone(),
-        None => {
-            // No agent session ID means no history yet
+    // Prefer Conduit's own event journal, falling back to the agent's session files
+    let journal = core
+        .session_event_store()
+        .and_then(|store| load_journal_history(store, id));
+    let mut debug_entries = Vec::new();
+    let mut debug_file: Option<String> = None;
+    let messages = if let Some((msgs, entries)) = journal {
+        debug_entries = entries;
+        debug_file = Some(crate::util::database_path().to_string_lossy().to_string());
+        msgs
+    } else {
+        let Some(agent_session_id) = session.agent_session_id.clone() else {
+            // No journal or agent session ID means no history yet
             return Ok(Json(ListSessionEventsResponse {
                 events: vec![],
                 total: 0,
@@ -364,57 +330,55 @@ pub async fn get_session_events(
                 debug_file: None,
                 debug_entries: vec![],
             }));
-        }
-    };
-
-    // Load history based on agent type
-    let mut debug_entries = Vec::new();
-    let mut debug_file: Option<String> = None;
-    let messages = match session.agent_type {
-        AgentType::Claude => match load_claude_history_with_debug(&agent_session_id) {
-            Ok((msgs, entries, file_path)) => {
-                debug_entries = entries;
-                debug_file = Some(file_path.to_string_lossy().to_string());
-                msgs
-            }
-            Err(e) => {
-                tracing::warn!("Failed to load Claude history: {}", e);
-                vec![]
-            }
-        },
-        AgentType::Codex => match load_codex_history_with_debug(&agent_session_id) {
-            Ok((msgs, entries, file_path)) => {
-                debug_entries = entries;
-                debug_file = Some(file_path.to_string_lossy().to_string());
-                msgs
-            }
-            Err(e) => {
-                tracing::warn!("Failed to load Codex history: {}", e);
+        };
+
+        // Load history based on agent type
+        match session.agent_type {
+            AgentType::Claude => match load_claude_history_with_debug(&agent_session_id) {
+                Ok((msgs, entries, file_path)) => {
+                    debug_entries = entries;
+                    debug_file = Some(file_path.to_string_lossy().to_string());
+                    msgs
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load Claude history: {}", e);
+                    vec![]
+                }
+            },
+            AgentType::Codex => match load_codex_history_with_debug(&agent_session_id) {
+                Ok((msgs, entries, file_path)) => {
+                    debug_entries = entries;
+                    debug_file = Some(file_path.to_string_lossy().to_string());
+                    msgs
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load Codex history: {}", e);
+                    vec![]
+                }
+            },
+            AgentType::Gemini => {
+                // Gemini history loading not supported yet
                 vec![]
             }
-        },
-        AgentType::Gemini => {
-            // Gemini history loading not supported yet
-            vec![]
+            AgentType::Opencode => match load_opencode_history_with_debug(&agent_session_id) {
+                Ok((msgs, entries, file_path)) => {
+                    debug_entries = entries;
+                    debug_file = Some(file_path.to_string_lossy().to_string());
+                    msgs
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load OpenCode history: {}", e);
+                    vec![]
+                }
+            },
         }
-        AgentType::Opencode => match load_opencode_history_with_debug(&agent_session_id) {
-            Ok((msgs, entries, file_path)) => {
-                debug_entries = entries;
-                debug_file = Some(file_path.to_string_lossy().to_string());
-                msgs
-            }
-            Err(e) => {
-                tracing::warn!("Failed to load OpenCode history: {}", e);
-                vec![]
-            }
-        },
     };
 
     let messages: Vec<ChatMessage> = messages
         .into_iter()
         .filter(|msg| {
             !(msg.role == MessageRole::User
-                && msg.content.trim_start().starts_with("[CONDUIT_FORK_SEED]"))
+                && msg.content.trim_start().starts_with(FORK_SEED_PREFIX))
         })
         .collect();
 
@@ -490,6 +454,36 @@ pub async fn get_session_events(
     }))
 }
 
+#[derive(Debug, Deserialize, Default)]
+pub struct SessionExportQuery {
+    pub format: Option<String>,
+}
+
+/// Export a session transcript as Markdown (default), HTML, or JSON.
+pub async fn export_session(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SessionExportQuery>,
+) -> Result<impl IntoResponse, WebError> {
+    let format = match query.format.as_deref() {
+        Some(format) => format.parse::<ExportFormat>().map_err(map_service_error)?,
+        None => ExportFormat::Markdown,
+    };
+
+    let core = state.core().await;
+    let (info, content) =
+        ExportService::export_session(&core, id, format).map_err(map_service_error)?;
+    let disposition = format!("attachment; filename=\"{}\"", info.file_name(format));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        content,
+    ))
+}
+
 /// Get input history for a session.
 pub async fn get_session_history(
     State(state): State<WebAppState>,
@@ -501,13 +495,30 @@ pub async fn get_session_history(
 }
 
 /// Fork a session into a new workspace and return the seed prompt.
+///
+/// With `agent_type`, the session is handed off to that agent instead.
 pub async fn fork_session(
     State(state): State<WebAppState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<ForkSessionQuery>,
 ) -> Result<Json<ForkSessionResponse>, WebError> {
     let core = state.core().await;
 
     let session = SessionService::get_session(&core, id).map_err(map_service_error)?;
+    let target = match query.agent_type.as_deref() {
+        Some(agent_type) => parse_agent_type(agent_type)?,
+        None => session.agent_type,
+    };
+    let is_handoff = target != session.agent_type;
+    // Reject the model before the workspace and worktree are created.
+    if let Some(model_id) = query.model.as_deref() {
+        if ModelRegistry::find_model(target, model_id).is_none() {
+            return Err(map_service_error(ServiceError::InvalidInput(format!(
+                "Invalid model '{}' for agent type {:?}",
+                model_id, target
+            ))));
+        }
+    }
     let workspace_id = session.workspace_id.ok_or_else(|| {
         WebError::BadRequest("Session is not associated with a workspace".to_string())
     })?;
@@ -545,15 +556,39 @@ pub async fn fork_session(
         .get_current_branch(&workspace.path)
         .unwrap_or_else(|_| workspace.branch.clone());
 
-    let history = load_history_for_session(&session);
-    let seed_prompt = app_prompt::build_fork_seed_prompt(&history);
+    let history = SessionService::load_history(&core, &session);
+    let seed_prompt = if is_handoff {
+        app_prompt::build_handoff_seed_prompt(&history, session.agent_type, target)
+    } else {
+        app_prompt::build_fork_seed_prompt(&history)
+    };
     let seed_hash = app_prompt::compute_seed_prompt_hash(&seed_prompt);
 
-    let model_id = session
-        .model
+    let model = match query.model {
+        Some(model) => Some(model),
+        None if is_handoff => Some(core.config().default_model_for(target)),
+        None => session.model.clone(),
+    };
+    let agent_mode = session
+        .agent_mode
+        .as_deref()
+        .and_then(|mode| match mode {
+            "build" => Some(AgentMode::Build),
+            "plan" => Some(AgentMode::Plan),
+            _ => None,
+        })
+        .map(|mode| {
+            if mode == AgentMode::Plan && !target.supports_plan_mode() {
+                AgentMode::Build
+            } else {
+                mode
+            }
+        });
+
+    let model_id = model
         .clone()
-        .unwrap_or_else(|| ModelRegistry::default_model(session.agent_type));
-    let context_window = ModelRegistry::context_window(session.agent_type, &model_id);
+        .unwrap_or_else(|| ModelRegistry::default_model(target));
+    let context_window = ModelRegistry::context_window(target, &model_id);
     let token_estimate = estimate_tokens(&seed_prompt);
     let usage_percent = if context_window > 0 {
         (token_estimate as f64 / context_window as f64) * 100.0
@@ -586,14 +621,15 @@ pub async fn fork_session(
     }
 
     let fork_seed = ForkSeed::new(
-        session.agent_type,
+        target,
         session.agent_session_id.clone(),
         Some(workspace_id),
         seed_hash,
         None,
         token_estimate,
         context_window,
-    );
+    )
+    .with_parent_agent_type(session.agent_type);
     fork_seed_store
         .create(&fork_seed)
         .map_err(|e| WebError::Internal(format!("Failed to save fork metadata: {}", e)))?;
@@ -655,16 +691,9 @@ pub async fn fork_session(
         &core,
         CreateForkedSessionParams {
             workspace_id: new_workspace.id,
-            agent_type: session.agent_type,
-            agent_mode: session
-                .agent_mode
-                .as_ref()
-                .and_then(|mode| match mode.as_str() {
-                    "build" => Some(AgentMode::Build),
-                    "plan" => Some(AgentMode::Plan),
-                    _ => None,
-                }),
-            model: session.model.clone(),
+            agent_type: target,
+            agent_mode,
+            model,
             fork_seed_id: fork_seed.id,
         },
     )
@@ -0,0 +1,36 @@
+//! Usage analytics handlers.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::core::services::{ServiceError, StatsService, UsageStats};
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct StatsQuery {
+    /// Number of local days to cover, including today (omit for all time)
+    pub days: Option<u32>,
+}
+
+/// Token, cost, turn and duration totals broken down per day, agent, model,
+/// repository and workspace.
+pub async fn get_stats(
+    State(state): State<WebAppState>,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<UsageStats>, WebError> {
+    let core = state.core().await;
+    let stats = StatsService::usage_stats(&core, query.days).map_err(map_service_error)?;
+    Ok(Json(stats))
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
@@ -0,0 +1,163 @@
+//! Base branch sync handlers for the Conduit web API.
+//!
+//! Syncing merges the base branch into a workspace or rebases onto it. When
+//! it stops on conflicts the response carries a prompt for the client to send
+//! to the session's agent over the WebSocket; once the conflicts are resolved
+//! the sync is continued (or aborted) here.
+
+use std::path::PathBuf;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::services::{CommitService, ServiceError, SyncService};
+use crate::git::{ConflictedFile, SyncOutcome, SyncStrategy};
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+#[derive(Debug, Serialize)]
+pub struct SyncStatusResponse {
+    pub base: String,
+    /// Strategy a new sync would use
+    pub strategy: SyncStrategy,
+    pub in_progress: Option<SyncStrategy>,
+    pub conflicts: Vec<ConflictedFile>,
+    pub commits_ahead: usize,
+    pub commits_behind: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncResult {
+    UpToDate,
+    Synced,
+    Conflicts,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncOutcomeResponse {
+    pub result: SyncResult,
+    pub base: String,
+    pub strategy: Option<SyncStrategy>,
+    pub conflicts: Vec<ConflictedFile>,
+    /// Prompt asking the agent to resolve the conflicts
+    pub prompt: Option<String>,
+}
+
+impl From<SyncOutcome> for SyncOutcomeResponse {
+    fn from(outcome: SyncOutcome) -> Self {
+        match outcome {
+            SyncOutcome::UpToDate { base } => Self {
+                result: SyncResult::UpToDate,
+                base,
+                strategy: None,
+                conflicts: Vec::new(),
+                prompt: None,
+            },
+            SyncOutcome::Synced { base, strategy } => Self {
+                result: SyncResult::Synced,
+                base,
+                strategy: Some(strategy),
+                conflicts: Vec::new(),
+                prompt: None,
+            },
+            SyncOutcome::Conflicts(conflicts) => Self {
+                result: SyncResult::Conflicts,
+                prompt: Some(SyncService::conflict_prompt(&conflicts)),
+                base: conflicts.base,
+                strategy: Some(conflicts.strategy),
+                conflicts: conflicts.files,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SyncRequest {
+    /// Overrides the repository's strategy for this sync
+    pub strategy: Option<SyncStrategy>,
+}
+
+/// Ahead/behind counts against the base branch and any sync in progress.
+pub async fn get_sync_status(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SyncStatusResponse>, WebError> {
+    let (working_dir, strategy) = workspace_sync_target(&state, id).await?;
+    let status = run_blocking(move || SyncService::status(&working_dir)).await?;
+    Ok(Json(SyncStatusResponse {
+        base: status.base,
+        strategy,
+        in_progress: status.in_progress,
+        conflicts: status.conflicts,
+        commits_ahead: status.commits_ahead,
+        commits_behind: status.commits_behind,
+    }))
+}
+
+/// Merge in or rebase onto the base branch.
+pub async fn sync_workspace(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    body: Option<Json<SyncRequest>>,
+) -> Result<Json<SyncOutcomeResponse>, WebError> {
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+    let (working_dir, strategy) = workspace_sync_target(&state, id).await?;
+    let strategy = req.strategy.unwrap_or(strategy);
+    let outcome = run_blocking(move || SyncService::sync(&working_dir, strategy)).await?;
+    Ok(Json(outcome.into()))
+}
+
+/// Continue a sync whose conflicts have been resolved.
+pub async fn continue_sync(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SyncOutcomeResponse>, WebError> {
+    let (working_dir, _) = workspace_sync_target(&state, id).await?;
+    let outcome = run_blocking(move || SyncService::continue_sync(&working_dir)).await?;
+    Ok(Json(outcome.into()))
+}
+
+/// Abort the sync in progress, restoring the branch.
+pub async fn abort_sync(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, WebError> {
+    let (working_dir, _) = workspace_sync_target(&state, id).await?;
+    run_blocking(move || SyncService::abort(&working_dir)).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn workspace_sync_target(
+    state: &WebAppState,
+    id: Uuid,
+) -> Result<(PathBuf, SyncStrategy), WebError> {
+    let core = state.core().await;
+    let working_dir = CommitService::working_dir(&core, id).map_err(map_service_error)?;
+    let strategy = SyncService::strategy(&core, id).map_err(map_service_error)?;
+    Ok((working_dir, strategy))
+}
+
+async fn run_blocking<T, F>(task: F) -> Result<T, WebError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ServiceError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(task)
+        .await
+        .map_err(|e| WebError::Internal(format!("Sync task failed: {}", e)))?
+        .map_err(map_service_error)
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
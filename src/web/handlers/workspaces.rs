@@ -1,7 +1,7 @@
 //! Workspace handlers for the Conduit web API.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
@@ -9,15 +9,19 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use uuid::Uuid;
 
-use crate::core::resolve_repo_workspace_settings;
 use crate::core::services::{ServiceError, SessionService};
+use crate::core::{
+    resolve_repo_workspace_settings, resolve_workspace_hooks, touched_paths, ConduitCore,
+    HookContext, HookEvent,
+};
 use crate::data::Workspace;
-use crate::git::PrManager;
+use crate::git::{DiffBase, HunkAction, PrManager, WorkspaceDiff, WorktreeError};
 use crate::util::names::{generate_branch_name, generate_workspace_name, get_git_username};
 use crate::web::error::WebError;
 use crate::web::handlers::sessions::SessionResponse;
 use crate::web::state::WebAppState;
 use crate::web::status_types::{PrStatusResponse, WorkspaceStatusResponse};
+use crate::web::HookRunResponse;
 
 /// Response for a single workspace.
 #[derive(Debug, Serialize)]
@@ -366,6 +370,19 @@ pub async fn archive_workspace(
     Json(req): Json<ArchiveWorkspaceRequest>,
 ) -> Result<StatusCode, WebError> {
     let core = state.core().await;
+    archive_workspace_inner(&state, &core, id, req.delete_remote.unwrap_or(false))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Remove a workspace's worktree and mark it archived.
+///
+/// Shared with race winner selection, which archives the losing contenders.
+pub(super) fn archive_workspace_inner(
+    state: &WebAppState,
+    core: &ConduitCore,
+    id: Uuid,
+    delete_remote: bool,
+) -> Result<(), WebError> {
     let workspace_store = core
         .workspace_store()
         .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
@@ -390,13 +407,32 @@ pub async fn archive_workspace(
         })?;
 
     let settings = resolve_repo_workspace_settings(core.config(), &repo);
-    let delete_remote = settings.archive_delete_branch && req.delete_remote.unwrap_or(false);
+    let hooks = resolve_workspace_hooks(core.config(), &repo);
+    let delete_remote = settings.archive_delete_branch && delete_remote;
 
     let worktree_manager = core.worktree_manager();
     let mut warnings = Vec::new();
     let mut archived_commit_sha = None;
 
     if let Some(base_path) = repo.base_path {
+        let context = HookContext {
+            workspace_name: &workspace.name,
+            branch: &workspace.branch,
+            workspace_path: &workspace.path,
+            repo_path: &base_path,
+        };
+        if hooks.has(HookEvent::BeforeArchive) && workspace.path.exists() {
+            let report = state
+                .hook_runs()
+                .run(id, &hooks, HookEvent::BeforeArchive, context);
+            if let Some(summary) = report.failure_summary() {
+                return Err(WebError::Conflict(format!(
+                    "{}; the workspace was not archived",
+                    summary
+                )));
+            }
+        }
+
         match worktree_manager.get_branch_sha(
             settings.mode,
             &base_path,
@@ -439,6 +475,13 @@ pub async fn archive_workspace(
                 ));
             }
         }
+
+        if hooks.has(HookEvent::AfterArchive) {
+            let report = state
+                .hook_runs()
+                .run(id, &hooks, HookEvent::AfterArchive, context);
+            warnings.extend(report.failure_summary());
+        }
     } else {
         warnings.push("Repository has no base path; worktree not removed".to_string());
     }
@@ -451,6 +494,18 @@ pub async fn archive_workspace(
     if let Err(e) = session_store.set_open_by_workspace(id, false) {
         tracing::warn!(error = %e, "Failed to close sessions for archived workspace");
     }
+    match session_store.get_all_including_closed() {
+        Ok(sessions) => sessions
+            .iter()
+            .filter(|session| session.workspace_id == Some(id))
+            .for_each(|session| state.session_manager().forget_events(session.id)),
+        Err(e) => tracing::warn!(error = %e, "Failed to load sessions for archived workspace"),
+    }
+    if let Some(events) = core.session_event_store() {
+        if let Err(e) = events.delete_by_workspace(id) {
+            tracing::warn!(error = %e, "Failed to delete journal of archived workspace");
+        }
+    }
 
     state.status_manager().remove_workspace(id);
 
@@ -462,7 +517,7 @@ pub async fn archive_workspace(
         );
     }
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(())
 }
 
 /// Delete a workspace.
@@ -481,6 +536,14 @@ pub async fn delete_workspace(
         .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?
         .ok_or_else(|| WebError::NotFound(format!("Workspace {} not found", id)))?;
 
+    // Its sessions outlive it, unlinked, so drop their journals while they
+    // can still be found
+    if let Some(events) = core.session_event_store() {
+        if let Err(e) = events.delete_by_workspace(id) {
+            tracing::warn!(error = %e, "Failed to delete journal of deleted workspace");
+        }
+    }
+
     // Delete workspace
     store
         .delete(id)
@@ -576,9 +639,55 @@ pub async fn auto_create_workspace(
         .register_workspace(workspace.id, workspace.path.clone());
     state.status_manager().refresh_workspace(workspace.id);
 
+    // Setup can take minutes; clients poll GET /workspaces/{id}/hooks for its output
+    let hooks = resolve_workspace_hooks(core.config(), &repo);
+    if hooks.has(HookEvent::OnCreate) {
+        let hook_runs = state.hook_runs().clone();
+        let status_manager = state.status_manager().clone();
+        hook_runs.start(workspace.id, HookEvent::OnCreate);
+        tokio::task::spawn_blocking(move || {
+            let context = HookContext {
+                workspace_name: &workspace.name,
+                branch: &workspace.branch,
+                workspace_path: &workspace.path,
+                repo_path: &repo_path,
+            };
+            let report = hook_runs.run(workspace.id, &hooks, HookEvent::OnCreate, context);
+            if let Some(summary) = report.failure_summary() {
+                tracing::warn!(workspace_id = %workspace.id, "{}", summary);
+            }
+            status_manager.refresh_workspace(workspace.id);
+        });
+    }
+
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+/// Lifecycle hook runs of a workspace.
+#[derive(Debug, Serialize)]
+pub struct WorkspaceHooksResponse {
+    pub runs: Vec<HookRunResponse>,
+}
+
+/// Get the output of a workspace's latest hook runs.
+pub async fn get_workspace_hooks(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WorkspaceHooksResponse>, WebError> {
+    let core = state.core().await;
+    let store = core
+        .workspace_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    store
+        .get_by_id(id)
+        .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?
+        .ok_or_else(|| WebError::NotFound(format!("Workspace {} not found", id)))?;
+
+    Ok(Json(WorkspaceHooksResponse {
+        runs: state.hook_runs().get(id),
+    }))
+}
+
 /// Get workspace git status and PR info.
 pub async fn get_workspace_status(
     State(state): State<WebAppState>,
@@ -607,6 +716,170 @@ pub async fn get_workspace_status(
     ))
 }
 
+/// Query parameters for the workspace diff.
+#[derive(Debug, Deserialize, Default)]
+pub struct WorkspaceDiffQuery {
+    /// `head` (uncommitted changes, default) or `base_branch`
+    pub base: Option<String>,
+    /// `all` (default) or `last_turn` (files touched in the session's last turn)
+    pub scope: Option<String>,
+}
+
+/// Get the workspace's changes as structured per-file hunks.
+pub async fn get_workspace_diff(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<WorkspaceDiffQuery>,
+) -> Result<Json<WorkspaceDiff>, WebError> {
+    let base = match query.base.as_deref() {
+        Some(base) => DiffBase::parse(base).ok_or_else(|| {
+            WebError::BadRequest(format!(
+                "Invalid diff base: {}. Must be one of: head, base_branch",
+                base
+            ))
+        })?,
+        None => DiffBase::Head,
+    };
+
+    let last_turn = match query.scope.as_deref() {
+        None | Some("all") => false,
+        Some("last_turn") => true,
+        Some(scope) => {
+            return Err(WebError::BadRequest(format!(
+                "Invalid diff scope: {}. Must be one of: all, last_turn",
+                scope
+            )))
+        }
+    };
+
+    let (working_dir, paths) = {
+        let core = state.core().await;
+        let store = core
+            .workspace_store()
+            .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+
+        let workspace = store
+            .get_by_id(id)
+            .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?
+            .ok_or_else(|| WebError::NotFound(format!("Workspace {} not found", id)))?;
+        let paths = if last_turn {
+            Some(last_turn_paths(&core, id)?)
+        } else {
+            None
+        };
+        (workspace.path, paths)
+    };
+
+    let diff = tokio::task::spawn_blocking(move || {
+        let mut diff = WorkspaceDiff::load(&working_dir, base)?;
+        if let Some(paths) = paths {
+            diff.retain_paths(&working_dir, &paths);
+        }
+        Ok::<_, WorktreeError>(diff)
+    })
+    .await
+    .map_err(|e| WebError::Internal(format!("Diff task failed: {}", e)))?
+    .map_err(|e| WebError::Internal(format!("Failed to load diff: {}", e)))?;
+    Ok(Json(diff))
+}
+
+/// Files touched during the last turn of the workspace's session.
+fn last_turn_paths(core: &ConduitCore, workspace_id: Uuid) -> Result<Vec<String>, WebError> {
+    let Some(session) = core
+        .session_tab_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?
+        .get_by_workspace_id(workspace_id)
+        .map_err(|e| WebError::Internal(format!("Failed to get session: {}", e)))?
+    else {
+        return Ok(Vec::new());
+    };
+    let Some(events) = core.session_event_store() else {
+        return Ok(Vec::new());
+    };
+    let events = events
+        .get_last_turn(session.id)
+        .map_err(|e| WebError::Internal(format!("Failed to load last turn: {}", e)))?;
+    Ok(touched_paths(&events))
+}
+
+/// Request to stage or revert one hunk of the workspace diff.
+#[derive(Debug, Deserialize)]
+pub struct ApplyHunkRequest {
+    /// Workspace-relative path of the file
+    pub path: String,
+    /// Index of the hunk within the file
+    pub hunk_index: usize,
+    /// `stage` (keep) or `revert` (discard)
+    pub action: String,
+    /// `hash` of the hunk the client saw; rejected with 409 if the hunk changed
+    /// since. Required to revert, since that discards the change
+    pub hash: Option<String>,
+}
+
+/// Stage or revert a single hunk of the workspace's uncommitted changes.
+///
+/// Returns the refreshed diff against HEAD.
+pub async fn apply_workspace_hunk(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<ApplyHunkRequest>,
+) -> Result<Json<WorkspaceDiff>, WebError> {
+    let action = HunkAction::parse(&req.action).ok_or_else(|| {
+        WebError::BadRequest(format!(
+            "Invalid hunk action: {}. Must be one of: stage, revert",
+            req.action
+        ))
+    })?;
+    if action == HunkAction::Revert && req.hash.is_none() {
+        return Err(WebError::BadRequest(
+            "Reverting a hunk requires its hash".to_string(),
+        ));
+    }
+
+    let working_dir = {
+        let core = state.core().await;
+        let store = core
+            .workspace_store()
+            .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+
+        store
+            .get_by_id(id)
+            .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?
+            .ok_or_else(|| WebError::NotFound(format!("Workspace {} not found", id)))?
+            .path
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let diff = WorkspaceDiff::load(&working_dir, DiffBase::Head)
+            .map_err(|e| WebError::Internal(format!("Failed to load diff: {}", e)))?;
+        if let Some(expected) = req.hash.as_deref() {
+            let current = diff
+                .file(&req.path)
+                .and_then(|file| file.hunks.get(req.hunk_index))
+                .map(|hunk| hunk.hash.as_str());
+            if current.is_some_and(|hash| hash != expected) {
+                return Err(WebError::Conflict(
+                    "The hunk changed since the diff was loaded".to_string(),
+                ));
+            }
+        }
+
+        diff.apply_hunk(&working_dir, &req.path, req.hunk_index, action)
+            .map_err(|e| match e {
+                WorktreeError::HunkNotFound(hunk) => {
+                    WebError::NotFound(format!("Hunk not found: {}", hunk))
+                }
+                e => WebError::Internal(format!("Failed to apply hunk: {}", e)),
+            })?;
+
+        WorkspaceDiff::load(&working_dir, DiffBase::Head)
+            .map(Json)
+            .map_err(|e| WebError::Internal(format!("Failed to load diff: {}", e)))
+    })
+    .await
+    .map_err(|e| WebError::Internal(format!("Hunk task failed: {}", e)))?
+}
+
 /// Run PR preflight checks for a workspace.
 pub async fn get_workspace_pr_preflight(
     State(state): State<WebAppState>,
@@ -0,0 +1,129 @@
+//! Workspace hook runs for the web UI.
+//!
+//! Hooks run in the background of the request that triggered them; their
+//! output is kept here so clients can poll it while they run and see why
+//! they failed afterwards.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::core::{run_workspace_hooks, HookContext, HookEvent, HookReport, WorkspaceHooks};
+
+/// Output lines kept per run
+const MAX_OUTPUT_LINES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HookRunResponse {
+    pub event: HookEvent,
+    pub status: HookRunStatus,
+    pub output: Vec<String>,
+    /// Which step failed and why
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Latest run of each hook event, per workspace.
+#[derive(Debug, Default)]
+pub struct HookRuns {
+    runs: Mutex<HashMap<Uuid, Vec<HookRunResponse>>>,
+}
+
+impl HookRuns {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that an event's hooks started, replacing its previous run.
+    pub fn start(&self, workspace_id: Uuid, event: HookEvent) {
+        let mut runs = self.runs.lock();
+        let workspace_runs = runs.entry(workspace_id).or_default();
+        workspace_runs.retain(|run| run.event != event);
+        workspace_runs.push(HookRunResponse {
+            event,
+            status: HookRunStatus::Running,
+            output: Vec::new(),
+            error: None,
+            started_at: Utc::now(),
+            finished_at: None,
+        });
+    }
+
+    pub fn push_line(&self, workspace_id: Uuid, event: HookEvent, line: &str) {
+        self.update(workspace_id, event, |run| {
+            if run.output.len() == MAX_OUTPUT_LINES {
+                run.output.remove(0);
+            }
+            run.output.push(line.to_string());
+        });
+    }
+
+    pub fn finish(&self, workspace_id: Uuid, report: &HookReport) {
+        self.update(workspace_id, report.event, |run| {
+            run.status = if report.succeeded() {
+                HookRunStatus::Succeeded
+            } else {
+                HookRunStatus::Failed
+            };
+            run.error = report.failure_summary();
+            run.finished_at = Some(Utc::now());
+        });
+    }
+
+    /// Run an event's hooks, recording their output as it arrives.
+    ///
+    /// Blocks until the hooks finish.
+    pub fn run(
+        &self,
+        workspace_id: Uuid,
+        hooks: &WorkspaceHooks,
+        event: HookEvent,
+        context: HookContext<'_>,
+    ) -> HookReport {
+        if !self.is_running(workspace_id, event) {
+            self.start(workspace_id, event);
+        }
+        let report = run_workspace_hooks(hooks, event, context, &mut |line| {
+            self.push_line(workspace_id, event, line)
+        });
+        self.finish(workspace_id, &report);
+        report
+    }
+
+    pub fn is_running(&self, workspace_id: Uuid, event: HookEvent) -> bool {
+        self.runs.lock().get(&workspace_id).is_some_and(|runs| {
+            runs.iter()
+                .any(|run| run.event == event && run.status == HookRunStatus::Running)
+        })
+    }
+
+    pub fn get(&self, workspace_id: Uuid) -> Vec<HookRunResponse> {
+        self.runs
+            .lock()
+            .get(&workspace_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn update(&self, workspace_id: Uuid, event: HookEvent, f: impl FnOnce(&mut HookRunResponse)) {
+        let mut runs = self.runs.lock();
+        if let Some(run) = runs
+            .get_mut(&workspace_id)
+            .and_then(|runs| runs.iter_mut().find(|run| run.event == event))
+        {
+            f(run);
+        }
+    }
+}
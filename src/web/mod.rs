@@ -5,18 +5,31 @@
 //!
 //! Enable with the `web` feature flag: `cargo build --features web`
 
+mod activity;
+mod auth;
+mod clone_jobs;
+mod daemon;
 mod error;
 pub mod handlers;
+mod hook_runs;
 pub mod routes;
 mod server;
 mod state;
 mod status_manager;
 mod status_types;
+mod tls;
+mod webhooks;
 pub mod ws;
 
+pub use activity::{Activity, ActivityFeed, ActivityKind};
+pub use auth::{AccessPolicy, ApiToken, TokenStore};
+pub use clone_jobs::{CloneJobResponse, CloneJobs, CloneStatus};
+pub use daemon::{DaemonClient, DaemonEvent, DaemonInfo};
 pub use error::WebError;
+pub use hook_runs::{HookRunResponse, HookRunStatus, HookRuns};
 pub use server::{run_server, ServerConfig};
 pub use state::WebAppState;
 pub use status_manager::{StatusManager, StatusManagerConfig};
 pub use status_types::{GitDiffStatsResponse, PrStatusResponse, WorkspaceStatusResponse};
-pub use ws::{ClientMessage, ServerMessage, SessionManager};
+pub use webhooks::{spawn_webhooks, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER};
+pub use ws::{ClientMessage, ImageAttachment, ServerMessage, SessionManager};
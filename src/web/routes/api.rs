@@ -6,8 +6,9 @@ use axum::{
 };
 
 use crate::web::handlers::{
-    bootstrap, external_sessions, models, onboarding, queue, repositories, sessions, themes,
-    ui_state, workspaces,
+    activity, audit, bootstrap, checkpoints, ci, commits, external_sessions, models, onboarding,
+    queue, races, repositories, reviews, search, sessions, stats, sync, themes, ui_state,
+    workspaces,
 };
 use crate::web::state::WebAppState;
 
@@ -36,6 +37,11 @@ pub fn api_routes() -> Router<WebAppState> {
             post(repositories::remove_repository),
         )
         // Repository workspaces routes
+        .route(
+            "/repositories/{id}/clone",
+            get(repositories::get_clone_status),
+        )
+        .route("/repositories/{id}/clone", post(repositories::retry_clone))
         .route(
             "/repositories/{id}/workspaces",
             get(workspaces::list_repository_workspaces),
@@ -60,6 +66,15 @@ pub fn api_routes() -> Router<WebAppState> {
             "/workspaces/{id}/status",
             get(workspaces::get_workspace_status),
         )
+        .route(
+            "/workspaces/{id}/hooks",
+            get(workspaces::get_workspace_hooks),
+        )
+        .route("/workspaces/{id}/diff", get(workspaces::get_workspace_diff))
+        .route(
+            "/workspaces/{id}/diff/hunks",
+            post(workspaces::apply_workspace_hunk),
+        )
         .route(
             "/workspaces/{id}/archive/preflight",
             get(workspaces::get_workspace_archive_preflight),
@@ -69,6 +84,29 @@ pub fn api_routes() -> Router<WebAppState> {
             get(workspaces::get_workspace_pr_preflight),
         )
         .route("/workspaces/{id}/pr", post(workspaces::create_workspace_pr))
+        .route("/workspaces/{id}/commit", get(commits::get_commit_preview))
+        .route("/workspaces/{id}/commit", post(commits::commit_workspace))
+        .route(
+            "/workspaces/{id}/commit/message",
+            post(commits::generate_commit_message),
+        )
+        .route(
+            "/workspaces/{id}/review-comments",
+            get(reviews::list_review_comments),
+        )
+        .route(
+            "/workspaces/{id}/review-comments/prompt",
+            post(reviews::build_review_prompt),
+        )
+        .route(
+            "/workspaces/{id}/review-comments/addressed",
+            post(reviews::set_review_comments_addressed),
+        )
+        .route("/workspaces/{id}/ci/fix", post(ci::build_ci_fix_prompt))
+        .route("/workspaces/{id}/sync", get(sync::get_sync_status))
+        .route("/workspaces/{id}/sync", post(sync::sync_workspace))
+        .route("/workspaces/{id}/sync/continue", post(sync::continue_sync))
+        .route("/workspaces/{id}/sync/abort", post(sync::abort_sync))
         .route(
             "/workspaces/{id}/session",
             post(workspaces::get_or_create_session),
@@ -85,7 +123,13 @@ pub fn api_routes() -> Router<WebAppState> {
         .route("/sessions/{id}", delete(sessions::close_session))
         .route("/sessions/{id}/events", get(sessions::get_session_events))
         .route("/sessions/{id}/history", get(sessions::get_session_history))
+        .route("/sessions/{id}/export", get(sessions::export_session))
         .route("/sessions/{id}/fork", post(sessions::fork_session))
+        .route(
+            "/sessions/{id}/checkpoints",
+            get(checkpoints::list_checkpoints),
+        )
+        .route("/sessions/{id}/rewind", post(checkpoints::rewind_session))
         .route("/sessions/{id}/queue", get(queue::list_queue))
         .route("/sessions/{id}/queue", post(queue::add_queue_message))
         .route(
@@ -96,6 +140,19 @@ pub fn api_routes() -> Router<WebAppState> {
             "/sessions/{id}/queue/{message_id}",
             delete(queue::delete_queue_message),
         )
+        // Race routes
+        .route("/races", post(races::create_race))
+        .route("/races/{id}", get(races::get_race))
+        .route("/races/{id}/tests", post(races::run_race_tests))
+        .route("/races/{id}/winner", post(races::pick_winner))
+        // Search routes
+        .route("/search", get(search::search))
+        // Usage analytics
+        .route("/stats", get(stats::get_stats))
+        // Audit log
+        .route("/audit", get(audit::list_audit))
+        // Activity stream (SSE)
+        .route("/events/stream", get(activity::stream_activity))
         // Onboarding routes
         .route("/onboarding/base-dir", get(onboarding::get_base_dir))
         .route("/onboarding/base-dir", post(onboarding::set_base_dir))
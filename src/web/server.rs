@@ -1,21 +1,27 @@
 //! Axum web server implementation for Conduit.
 
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use axum::{
     extract::{ws::WebSocketUpgrade, State},
-    http::{header, Method},
+    http::{header, HeaderValue, Method},
+    middleware,
     response::IntoResponse,
     routing::get,
     Json, Router,
 };
 use serde::Serialize;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
+use super::auth::{auth_status, enforce_access, AccessPolicy, TokenStore};
+use super::daemon::DaemonInfo;
 use super::routes::api::api_routes;
 use super::routes::static_files::{serve_index, serve_static_file};
 use super::state::WebAppState;
+use super::tls::{load_tls_config, TlsListener};
 use super::ws::handle_websocket;
 
 /// Server configuration options.
@@ -27,6 +33,14 @@ pub struct ServerConfig {
     pub port: u16,
     /// Enable CORS for development (allows any origin).
     pub cors_permissive: bool,
+    /// Origins allowed besides the server's own when not in development mode.
+    pub allowed_origins: Vec<String>,
+    /// API tokens clients must present; `None` disables authentication.
+    pub tokens: Option<Arc<TokenStore>>,
+    /// PEM certificate chain and private key to serve HTTPS.
+    pub tls: Option<(PathBuf, PathBuf)>,
+    /// Connection details published for `conduit --attach` while serving.
+    pub daemon: Option<DaemonInfo>,
 }
 
 impl Default for ServerConfig {
@@ -35,6 +49,10 @@ impl Default for ServerConfig {
             host: "127.0.0.1".to_string(),
             port: 3000,
             cors_permissive: true,
+            allowed_origins: Vec::new(),
+            tokens: None,
+            tls: None,
+            daemon: None,
         }
     }
 }
@@ -108,23 +126,48 @@ async fn ws_handler(ws: WebSocketUpgrade, State(state): State<WebAppState>) -> i
 }
 
 /// Build the Axum router with all routes.
-fn build_router(state: WebAppState, cors_permissive: bool) -> Router {
+fn build_router(state: WebAppState, config: &ServerConfig) -> Router {
+    let methods = [
+        Method::GET,
+        Method::POST,
+        Method::PUT,
+        Method::PATCH,
+        Method::DELETE,
+    ];
+
     // Build CORS layer
-    let cors = if cors_permissive {
+    let cors = if config.cors_permissive {
         CorsLayer::new()
             .allow_origin(Any)
-            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+            .allow_methods(methods)
             .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
     } else {
+        let origins: Vec<HeaderValue> = config
+            .allowed_origins
+            .iter()
+            .filter_map(|origin| HeaderValue::from_str(origin.trim_end_matches('/')).ok())
+            .collect();
         CorsLayer::new()
-            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_methods(methods)
             .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
     };
 
-    // Core API routes (health, agents)
+    let access = AccessPolicy::new(
+        config.tokens.clone(),
+        config.allowed_origins.clone(),
+        config.cors_permissive,
+    );
+
+    // Core API routes (health, agents, auth)
     let core_routes = Router::new()
         .route("/health", get(health))
-        .route("/agents", get(list_agents));
+        .route("/agents", get(list_agents))
+        .merge(
+            Router::new()
+                .route("/auth/status", get(auth_status))
+                .with_state(access.clone()),
+        );
 
     // Build main router combining core routes, REST API routes, and static files
     Router::new()
@@ -135,6 +178,7 @@ fn build_router(state: WebAppState, cors_permissive: bool) -> Router {
         .route("/", get(serve_index))
         // Fallback to index.html for SPA routing
         .fallback(serve_index)
+        .layer(middleware::from_fn_with_state(access, enforce_access))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(state)
@@ -145,17 +189,69 @@ fn build_router(state: WebAppState, cors_permissive: bool) -> Router {
 /// This starts the Axum server and blocks until shutdown.
 pub async fn run_server(state: WebAppState, config: ServerConfig) -> anyhow::Result<()> {
     let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
+    // Fail on a bad certificate before anything else starts
+    let tls = match &config.tls {
+        Some((cert, key)) => Some(load_tls_config(cert, key)?),
+        None => None,
+    };
     state.start_status_manager().await;
-    let app = build_router(state, config.cors_permissive);
-
-    tracing::info!("Starting web server at http://{}", addr);
+    let app = build_router(state, &config);
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    let daemon_path = crate::util::daemon_info_path();
+    if let Some(daemon) = &config.daemon {
+        if let Err(e) = daemon.save(&daemon_path) {
+            tracing::warn!(error = %e, "Failed to write daemon connection file");
+        }
+    }
 
+    let serve = async {
+        match tls {
+            Some(tls) => {
+                tracing::info!("Starting web server at https://{}", addr);
+                axum::serve(TlsListener::new(listener, tls)?, app).await
+            }
+            None => {
+                tracing::info!("Starting web server at http://{}", addr);
+                axum::serve(listener, app).await
+            }
+        }
+    };
+    let result = tokio::select! {
+        result = serve => result,
+        _ = shutdown_signal() => Ok(()),
+    };
+
+    if let Some(daemon) = &config.daemon {
+        daemon.remove(&daemon_path);
+    }
+    result?;
     Ok(())
 }
 
+/// Resolve on Ctrl+C or SIGTERM, so the daemon connection file is cleaned up
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,7 +293,7 @@ mod tests {
     #[tokio::test]
     async fn test_health_endpoint() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         let response = app
             .oneshot(
@@ -215,7 +311,7 @@ mod tests {
     #[tokio::test]
     async fn test_agents_endpoint() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         let response = app
             .oneshot(
@@ -233,7 +329,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_repositories_endpoint() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         let response = app
             .oneshot(
@@ -256,7 +352,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_workspaces_endpoint() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         let response = app
             .oneshot(
@@ -279,7 +375,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_sessions_endpoint() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         let response = app
             .oneshot(
@@ -302,7 +398,7 @@ mod tests {
     #[tokio::test]
     async fn test_create_repository_endpoint() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         let body = serde_json::json!({
             "name": "test-repo",
@@ -333,7 +429,7 @@ mod tests {
     #[tokio::test]
     async fn test_create_repository_validation() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         // Missing both base_path and repository_url
         let body = serde_json::json!({
@@ -358,7 +454,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_repository_not_found() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         let response = app
             .oneshot(
@@ -376,7 +472,7 @@ mod tests {
     #[tokio::test]
     async fn test_create_session_endpoint() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         let body = serde_json::json!({
             "agent_type": "claude",
@@ -410,7 +506,7 @@ mod tests {
     #[tokio::test]
     async fn test_create_session_invalid_agent_type() {
         let state = test_state();
-        let app = build_router(state, true);
+        let app = build_router(state, &ServerConfig::default());
 
         let body = serde_json::json!({
             "agent_type": "invalid"
@@ -430,4 +526,194 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
+
+    /// Config requiring a token; keep the returned dir alive while using it.
+    fn auth_config() -> (ServerConfig, String, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = TokenStore::open(dir.path().join("api_tokens.json")).unwrap();
+        let (_, secret) = store.create("test").unwrap();
+        let config = ServerConfig {
+            cors_permissive: false,
+            tokens: Some(Arc::new(store)),
+            ..ServerConfig::default()
+        };
+        (config, secret, dir)
+    }
+
+    #[tokio::test]
+    async fn test_api_requires_token() {
+        let (config, secret, _dir) = auth_config();
+        let app = build_router(test_state(), &config);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/repositories")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/repositories")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", secret))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Health, auth status and the SPA stay public
+        for uri in ["/api/health", "/api/auth/status", "/"] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+        }
+
+        // The WebSocket takes the token from the query string
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/ws").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/ws?token={}", secret))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_auth_status_endpoint() {
+        let (config, secret, _dir) = auth_config();
+        let app = build_router(test_state(), &config);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/auth/status")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", secret))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["auth_required"], true);
+        assert_eq!(json["authenticated"], true);
+    }
+
+    #[tokio::test]
+    async fn test_foreign_origin_rejected() {
+        let (config, secret, _dir) = auth_config();
+        let app = build_router(test_state(), &config);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/repositories")
+                    .header(header::HOST, "devbox:3000")
+                    .header(header::ORIGIN, "https://evil.example")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", secret))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_daemon_client_attaches_with_token() {
+        use crate::web::{ClientMessage, DaemonClient, DaemonEvent, DaemonInfo, ServerMessage};
+        use std::time::Duration;
+
+        let (config, secret, _dir) = auth_config();
+        let app = build_router(test_state(), &config);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut info = DaemonInfo::local("127.0.0.1", port, None, None);
+        assert!(DaemonClient::connect(&info).await.is_err());
+
+        info.token = Some(secret);
+        let (client, mut incoming) = DaemonClient::connect(&info).await.unwrap();
+        // Following an idle session is not reported
+        client.watch(uuid::Uuid::new_v4());
+        assert!(client.send(ClientMessage::Ping));
+        let reply = tokio::time::timeout(Duration::from_secs(5), incoming.recv())
+            .await
+            .unwrap();
+        assert!(matches!(
+            reply,
+            Some(DaemonEvent::Message(ServerMessage::Pong))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_activity_stream_sends_filtered_events() {
+        use crate::web::ActivityKind;
+        use std::time::Duration;
+
+        let state = test_state();
+        let activity = state.activity().clone();
+        let app = build_router(state, &ServerConfig::default());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/events/stream?events=turn_completed")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/event-stream"
+        );
+
+        let session_id = uuid::Uuid::new_v4();
+        activity.publish(ActivityKind::TurnStarted {
+            session_id,
+            workspace_id: None,
+        });
+        activity.publish(ActivityKind::TurnCompleted {
+            session_id,
+            workspace_id: None,
+            usage: Default::default(),
+        });
+
+        let mut body = response.into_body();
+        let frame = tokio::time::timeout(Duration::from_secs(5), body.frame())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+        assert!(text.starts_with("event: turn_completed\n"), "{}", text);
+        assert!(text.contains("id: 2\n"));
+        assert!(text.contains(&session_id.to_string()));
+    }
 }
@@ -6,7 +6,9 @@ use tokio::sync::RwLock;
 use crate::core::ConduitCore;
 
 use super::ws::SessionManager;
-use super::{StatusManager, StatusManagerConfig};
+use super::{
+    spawn_webhooks, ActivityFeed, CloneJobs, HookRuns, StatusManager, StatusManagerConfig,
+};
 
 /// Shared state for the web application.
 ///
@@ -20,19 +22,30 @@ pub struct WebAppState {
     session_manager: Arc<SessionManager>,
     /// Background workspace status manager.
     status_manager: Arc<StatusManager>,
+    /// Output of workspace lifecycle hooks.
+    hook_runs: Arc<HookRuns>,
+    /// Progress of repository clones.
+    clone_jobs: Arc<CloneJobs>,
+    /// Turn and PR activity for SSE clients and webhooks.
+    activity: Arc<ActivityFeed>,
 }
 
 impl WebAppState {
     /// Create a new web application state from a ConduitCore.
     pub fn new(core: ConduitCore) -> Self {
         let status_config = StatusManagerConfig::from_config(core.config());
+        let activity = Arc::new(ActivityFeed::new());
+        spawn_webhooks(&activity, &core.config().webhooks);
         let inner = Arc::new(RwLock::new(core));
-        let session_manager = Arc::new(SessionManager::new(inner.clone()));
-        let status_manager = Arc::new(StatusManager::new(status_config));
+        let session_manager = Arc::new(SessionManager::new(inner.clone(), activity.clone()));
+        let status_manager = Arc::new(StatusManager::new(status_config, activity.clone()));
         Self {
             inner,
             session_manager,
             status_manager,
+            hook_runs: Arc::new(HookRuns::new()),
+            clone_jobs: Arc::new(CloneJobs::new()),
+            activity,
         }
     }
 
@@ -56,6 +69,21 @@ impl WebAppState {
         &self.status_manager
     }
 
+    /// Get the workspace hook run log.
+    pub fn hook_runs(&self) -> &Arc<HookRuns> {
+        &self.hook_runs
+    }
+
+    /// Get the repository clone progress log.
+    pub fn clone_jobs(&self) -> &Arc<CloneJobs> {
+        &self.clone_jobs
+    }
+
+    /// Get the activity feed.
+    pub fn activity(&self) -> &Arc<ActivityFeed> {
+        &self.activity
+    }
+
     /// Kick the initial status scan for all workspaces.
     pub async fn start_status_manager(&self) {
         let core = self.core().await;
@@ -18,6 +18,7 @@ use crate::config::Config;
 use crate::data::Workspace;
 use crate::git::{GitDiffStats, PrManager};
 use crate::web::status_types::{GitDiffStatsResponse, PrStatusResponse, WorkspaceStatusResponse};
+use crate::web::{ActivityFeed, ActivityKind};
 
 #[derive(Debug, Clone)]
 pub struct StatusManagerConfig {
@@ -86,6 +87,7 @@ struct StatusManagerInner {
     active_workspace: Mutex<Option<Uuid>>,
     semaphore: Arc<Semaphore>,
     initial_scan_started: AtomicBool,
+    activity: Arc<ActivityFeed>,
 }
 
 #[derive(Clone)]
@@ -94,13 +96,14 @@ pub struct StatusManager {
 }
 
 impl StatusManager {
-    pub fn new(config: StatusManagerConfig) -> Self {
+    pub fn new(config: StatusManagerConfig, activity: Arc<ActivityFeed>) -> Self {
         let inner = Arc::new(StatusManagerInner {
             config: config.clone(),
             workspaces: Mutex::new(HashMap::new()),
             active_workspace: Mutex::new(None),
             semaphore: Arc::new(Semaphore::new(config.concurrency)),
             initial_scan_started: AtomicBool::new(false),
+            activity,
         });
 
         Self::spawn_active_refresh_loop(inner.clone());
@@ -307,6 +310,16 @@ impl StatusManager {
                 entry.last_git_at = Some(now);
             }
             if do_pr {
+                // The first lookup is not a change, and a failed one (no PR
+                // found) is more often `gh` having trouble than a deleted PR
+                if let Some(pr) = pr_status.as_ref() {
+                    if entry.last_pr_at.is_some() && entry.status.pr_status.as_ref() != Some(pr) {
+                        inner.activity.publish(ActivityKind::PrStatusChanged {
+                            workspace_id,
+                            pr_status: pr.clone(),
+                        });
+                    }
+                }
                 entry.status.pr_status = pr_status;
                 entry.last_pr_at = Some(now);
             }
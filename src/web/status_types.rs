@@ -24,7 +24,7 @@ impl From<GitDiffStats> for GitDiffStatsResponse {
 }
 
 /// Response for PR status.
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct PrStatusResponse {
     pub number: u32,
     pub state: String,
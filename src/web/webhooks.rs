@@ -0,0 +1,243 @@
+//! Outbound webhooks for server activity.
+//!
+//! Each configured endpoint gets its own delivery task, so a slow receiver
+//! only delays its own events. Bodies are the JSON form of [`Activity`],
+//! signed with HMAC-SHA256 when the endpoint has a secret.
+
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use sha2::Sha256;
+use tokio::sync::broadcast;
+
+use crate::config::WebhookConfig;
+
+use super::activity::{Activity, ActivityFeed, ActivityKind};
+use super::auth::hex;
+
+/// Time a receiver gets to answer each delivery
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Attempts per event before it is dropped; server errors and timeouts are retried
+const DELIVERY_ATTEMPTS: u32 = 3;
+
+/// `sha256=<hex HMAC of the body>`, when the endpoint has a secret
+pub const SIGNATURE_HEADER: &str = "X-Conduit-Signature";
+/// Event type, as in the body's `type`
+pub const EVENT_HEADER: &str = "X-Conduit-Event";
+/// Event's delivery ID, as in the body's `delivery_id`; the same on retries
+pub const DELIVERY_HEADER: &str = "X-Conduit-Delivery";
+
+/// Start delivering activity to the configured webhooks.
+pub fn spawn_webhooks(feed: &ActivityFeed, webhooks: &[WebhookConfig]) {
+    if webhooks.is_empty() {
+        return;
+    }
+    let client = match reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to create webhook client; webhooks disabled");
+            return;
+        }
+    };
+
+    for webhook in webhooks {
+        for event in &webhook.events {
+            if !ActivityKind::NAMES.contains(&event.as_str()) {
+                tracing::warn!(
+                    endpoint = %endpoint_label(&webhook.url),
+                    event = %event,
+                    "Unknown webhook event type"
+                );
+            }
+        }
+        tokio::spawn(deliver_all(
+            client.clone(),
+            webhook.clone(),
+            feed.subscribe(),
+        ));
+    }
+}
+
+async fn deliver_all(
+    client: reqwest::Client,
+    webhook: WebhookConfig,
+    mut activity_rx: broadcast::Receiver<Activity>,
+) {
+    loop {
+        let activity = match activity_rx.recv().await {
+            Ok(activity) => activity,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!(
+                    endpoint = %endpoint_label(&webhook.url),
+                    skipped,
+                    "Webhook deliveries fell behind; events dropped"
+                );
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        let name = activity.kind.name();
+        if !webhook.events.is_empty() && !webhook.events.iter().any(|event| event == name) {
+            continue;
+        }
+        deliver(&client, &webhook, &activity).await;
+    }
+}
+
+async fn deliver(client: &reqwest::Client, webhook: &WebhookConfig, activity: &Activity) {
+    let body = match serde_json::to_vec(activity) {
+        Ok(body) => body,
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to serialize webhook event");
+            return;
+        }
+    };
+    let endpoint = endpoint_label(&webhook.url);
+
+    for attempt in 1..=DELIVERY_ATTEMPTS {
+        let mut request = client
+            .post(&webhook.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, activity.kind.name())
+            .header(DELIVERY_HEADER, activity.delivery_id.to_string());
+        if let Some(secret) = &webhook.secret {
+            request = request.header(SIGNATURE_HEADER, signature(secret, &body));
+        }
+
+        match request.body(body.clone()).send().await {
+            Ok(response) if response.status().is_success() => return,
+            Ok(response) if response.status().is_client_error() => {
+                // The receiver rejected it; sending it again will not help
+                tracing::warn!(
+                    %endpoint,
+                    status = %response.status(),
+                    event = activity.kind.name(),
+                    "Webhook rejected event"
+                );
+                return;
+            }
+            Ok(response) => {
+                let status = response.status();
+                tracing::debug!(%endpoint, attempt, %status, "Webhook delivery failed");
+            }
+            Err(error) => {
+                tracing::debug!(%endpoint, attempt, error = %error, "Webhook delivery failed");
+            }
+        }
+        if attempt < DELIVERY_ATTEMPTS {
+            tokio::time::sleep(Duration::from_secs(1 << (attempt - 1))).await;
+        }
+    }
+    tracing::warn!(
+        %endpoint,
+        event = activity.kind.name(),
+        "Giving up on webhook delivery"
+    );
+}
+
+/// Value of the signature header for a body.
+pub fn signature(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex(&mac.finalize().into_bytes()))
+}
+
+/// Scheme and host of a webhook URL, for logs; chat webhook URLs often carry
+/// their credentials in the path.
+fn endpoint_label(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(url) => format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default()),
+        Err(_) => "<invalid url>".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::extract::State;
+    use axum::http::HeaderMap;
+    use axum::routing::post;
+    use axum::Router;
+    use tokio::sync::mpsc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_signature_matches_hmac_sha256() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            signature("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_endpoint_label_hides_path() {
+        assert_eq!(
+            endpoint_label("https://hooks.example.com/services/T000/B000/secret"),
+            "https://hooks.example.com"
+        );
+        assert_eq!(endpoint_label("not a url"), "<invalid url>");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivers_signed_filtered_events() {
+        let (received_tx, mut received) = mpsc::unbounded_channel::<(HeaderMap, Vec<u8>)>();
+        let receiver = Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State(tx): State<mpsc::UnboundedSender<(HeaderMap, Vec<u8>)>>,
+                     headers: HeaderMap,
+                     body: axum::body::Bytes| async move {
+                        let _ = tx.send((headers, body.to_vec()));
+                    },
+                ),
+            )
+            .with_state(received_tx);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, receiver).await });
+
+        let feed = ActivityFeed::new();
+        spawn_webhooks(
+            &feed,
+            &[WebhookConfig {
+                url: format!("http://127.0.0.1:{}/hook", port),
+                secret: Some("s3cret".to_string()),
+                events: vec!["turn_failed".to_string()],
+            }],
+        );
+
+        let session_id = Uuid::new_v4();
+        feed.publish(ActivityKind::TurnStarted {
+            session_id,
+            workspace_id: None,
+        });
+        feed.publish(ActivityKind::TurnFailed {
+            session_id,
+            workspace_id: None,
+            error: "boom".to_string(),
+        });
+
+        let (headers, body) = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(headers[EVENT_HEADER], "turn_failed");
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let delivery_id: Uuid = headers[DELIVERY_HEADER].to_str().unwrap().parse().unwrap();
+        assert_eq!(json["delivery_id"], delivery_id.to_string());
+        assert_eq!(json["id"], 2);
+        assert_eq!(
+            headers[SIGNATURE_HEADER],
+            signature("s3cret", &body).as_str()
+        );
+        assert_eq!(json["type"], "turn_failed");
+        assert_eq!(json["session_id"], session_id.to_string());
+        assert_eq!(json["error"], "boom");
+    }
+}
@@ -4,40 +4,64 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use axum::extract::ws::{Message, WebSocket};
 use base64::engine::general_purpose;
 use base64::Engine as _;
 use futures::{SinkExt, StreamExt};
+use parking_lot::Mutex;
 use tokio::sync::{broadcast, mpsc, RwLock};
 use uuid::Uuid;
 
-use crate::agent::events::AgentEvent;
+use crate::agent::events::{AgentEvent, ControlRequestEvent, ErrorEvent, TokenUsage};
+use crate::agent::permissions::{PermissionReply, PermissionRequest};
 use crate::agent::runner::{AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
-use crate::core::services::{SessionService, UpdateSessionParams};
+use crate::core::services::{
+    BudgetStatus, ConfigService, CostService, SessionService, TurnCheckpoint, UpdateSessionParams,
+};
 use crate::core::ConduitCore;
+use crate::data::AuditActor;
 use crate::ui::app_prompt;
 use crate::util::{generate_title_and_branch, get_git_username, sanitize_branch_suffix};
+use crate::web::{ActivityFeed, ActivityKind};
 use serde_json::json;
 
 use super::messages::{ClientMessage, ImageAttachment, ServerMessage};
+use super::replay::{EventLog, Replay, SequencedEvent, REPLAY_RETENTION};
 
 /// Active session state tracked by the WebSocket handler.
 struct ActiveSession {
     agent_type: AgentType,
     /// Process ID for stopping the agent
     pid: Option<u32>,
-    /// Sender to broadcast events to all subscribers
-    event_tx: broadcast::Sender<AgentEvent>,
+    /// Sender to broadcast numbered events to all subscribers
+    event_tx: broadcast::Sender<SequencedEvent>,
     /// Input sender for sending follow-up messages
     input_tx: Option<mpsc::Sender<AgentInput>>,
+    /// Permission requests waiting for the user, by request ID
+    pending_permissions: HashMap<String, PermissionRequest>,
+    /// Whether the agent is in the middle of a turn
+    in_turn: bool,
+    /// Recorded before each prompt is sent, when checkpoints are enabled
+    checkpoint: Option<TurnCheckpoint>,
 }
 
 /// Manages active agent sessions and their event streams.
 pub struct SessionManager {
     sessions: Arc<RwLock<HashMap<Uuid, ActiveSession>>>,
+    /// Recent events per session, kept for a while after the agent exits so
+    /// clients can catch up on how it finished
+    event_logs: Arc<Mutex<HashMap<Uuid, EventLog>>>,
     core: Arc<RwLock<ConduitCore>>,
+    activity: Arc<ActivityFeed>,
+}
+
+/// A subscriber's live event stream and the events it missed.
+struct Subscription {
+    events: broadcast::Receiver<SequencedEvent>,
+    replay: Replay,
 }
 
 struct StartSessionArgs {
@@ -96,6 +120,81 @@ async fn append_input_history(
     Ok(())
 }
 
+/// Record a user prompt in the session event journal.
+///
+/// Hidden prompts (e.g. fork seeds) are journaled without their text so replay
+/// can suppress the turn they trigger.
+async fn record_journal_prompt(
+    core: &Arc<RwLock<ConduitCore>>,
+    session_id: Uuid,
+    prompt: &str,
+    hidden: bool,
+    resumed: bool,
+) {
+    let core = core.read().await;
+    if let Some(journal) = core.session_journal() {
+        let text = if hidden { "" } else { prompt };
+        journal.record_prompt(session_id, text, hidden, resumed);
+    }
+}
+
+/// Record the usage and estimated cost of a completed turn.
+async fn record_turn_cost(
+    core: &Arc<RwLock<ConduitCore>>,
+    session_id: Uuid,
+    usage: &TokenUsage,
+    duration: Duration,
+) {
+    let core = core.read().await;
+    let session_tab = match SessionService::get_session(&core, session_id) {
+        Ok(session_tab) => session_tab,
+        Err(error) => {
+            tracing::warn!(%session_id, error = %error, "Failed to load session for turn cost");
+            return;
+        }
+    };
+    if let Err(error) = CostService::record_turn(
+        &core,
+        session_id,
+        session_tab.workspace_id,
+        session_tab.agent_type,
+        session_tab.model.as_deref(),
+        usage,
+        Some(duration),
+    ) {
+        tracing::warn!(%session_id, error = %error, "Failed to record turn cost");
+    }
+}
+
+/// Check spend limits before starting a turn.
+///
+/// Returns the message to send to the client and whether the turn is blocked:
+/// a session error for a hard limit, or a non-fatal error event for a soft limit.
+fn budget_message(core: &ConduitCore, session_id: Uuid) -> Option<(ServerMessage, bool)> {
+    match CostService::check_budget(core, session_id) {
+        Ok(BudgetStatus::WithinBudget) => None,
+        Ok(BudgetStatus::SoftLimitReached(message)) => Some((
+            ServerMessage::agent_event(
+                session_id,
+                AgentEvent::Error(ErrorEvent {
+                    message,
+                    is_fatal: false,
+                    code: Some("budget_soft_limit".to_string()),
+                    details: None,
+                }),
+            ),
+            false,
+        )),
+        Ok(BudgetStatus::HardLimitReached(message)) => {
+            Some((ServerMessage::session_error(session_id, message), true))
+        }
+        Err(error) => {
+            tracing::warn!(%session_id, error = %error, "Failed to check spend budget");
+            None
+        }
+    }
+}
+
 async fn persist_pending_user_message(
     core: &Arc<RwLock<ConduitCore>>,
     session_id: Uuid,
@@ -125,18 +224,60 @@ async fn persist_pending_user_message(
 }
 
 impl SessionManager {
-    pub fn new(core: Arc<RwLock<ConduitCore>>) -> Self {
+    pub fn new(core: Arc<RwLock<ConduitCore>>, activity: Arc<ActivityFeed>) -> Self {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            event_logs: Arc::new(Mutex::new(HashMap::new())),
             core,
+            activity,
         }
     }
 
-    /// Start a new agent session.
-    async fn start_session(
+    /// Subscribe to a session's channel, picking up after `since_seq`.
+    ///
+    /// The event log lock is held while subscribing so no event can be both
+    /// replayed and received live, or neither. The log is created here if
+    /// needed so the epoch handed to the client stays valid.
+    fn attach(
         &self,
-        args: StartSessionArgs,
-    ) -> Result<broadcast::Receiver<AgentEvent>, String> {
+        session_id: Uuid,
+        event_tx: &broadcast::Sender<SequencedEvent>,
+        since_seq: Option<u64>,
+        epoch: Option<Uuid>,
+    ) -> Subscription {
+        let mut logs = self.event_logs.lock();
+        let log = logs.entry(session_id).or_default();
+        let replay = match since_seq {
+            Some(since_seq) => log.since(epoch, since_seq),
+            None => Replay {
+                events: Vec::new(),
+                epoch: log.epoch(),
+                last_seq: log.last_seq(),
+                resync_required: false,
+            },
+        };
+        Subscription {
+            events: event_tx.subscribe(),
+            replay,
+        }
+    }
+
+    /// Drop a session's buffered events, once it is closed or archived.
+    pub fn forget_events(&self, session_id: Uuid) {
+        self.event_logs.lock().remove(&session_id);
+    }
+
+    /// Buffered events after `since_seq`, for subscribers that fell behind.
+    fn events_since(&self, session_id: Uuid, epoch: Uuid, since_seq: u64) -> Replay {
+        let logs = self.event_logs.lock();
+        match logs.get(&session_id) {
+            Some(log) => log.since(Some(epoch), since_seq),
+            None => EventLog::default().since(Some(epoch), since_seq),
+        }
+    }
+
+    /// Start a new agent session.
+    async fn start_session(&self, args: StartSessionArgs) -> Result<Subscription, String> {
         let StartSessionArgs {
             session_id,
             agent_type,
@@ -171,6 +312,12 @@ impl SessionManager {
         if !runner.is_available() {
             return Err(format!("{} is not available", agent_type.display_name()));
         }
+        let journal = core.session_journal().cloned();
+        let workspace_id = SessionService::get_session(&core, session_id)
+            .ok()
+            .and_then(|session_tab| session_tab.workspace_id);
+        let checkpoint =
+            TurnCheckpoint::for_session(&core, session_id, workspace_id, working_dir.clone());
 
         // Build start config
         let mut config = AgentStartConfig::new(prompt, working_dir);
@@ -186,6 +333,7 @@ impl SessionManager {
         if let Some(payload) = stdin_payload {
             config = config.with_stdin_payload(payload);
         }
+        config = config.with_permissions(ConfigService::permission_policy(&core, workspace_id));
 
         if agent_type == AgentType::Opencode {
             match SessionService::get_session(&core, session_id) {
@@ -204,6 +352,11 @@ impl SessionManager {
             }
         }
 
+        // Snapshot the workspace before the agent sees the prompt
+        if let Some(checkpoint) = checkpoint.clone() {
+            checkpoint.record().await;
+        }
+
         // Start the agent
         let mut handle = runner
             .start(config)
@@ -229,7 +382,7 @@ impl SessionManager {
         // Reuse an existing event channel if we already have one (e.g. if the UI subscribed
         // before the session started). This prevents "Session <id> not found" errors when
         // selecting non-running session tabs.
-        let (event_tx, event_rx) = {
+        let event_tx = {
             let mut sessions = self.sessions.write().await;
             if let Some(existing) = sessions.get_mut(&session_id) {
                 // Another start could have raced us.
@@ -239,9 +392,10 @@ impl SessionManager {
                 existing.agent_type = agent_type;
                 existing.pid = Some(pid);
                 existing.input_tx = input_tx;
-                (existing.event_tx.clone(), existing.event_tx.subscribe())
+                existing.checkpoint = checkpoint;
+                existing.event_tx.clone()
             } else {
-                let (event_tx, event_rx) = broadcast::channel(256);
+                let (event_tx, _) = broadcast::channel(256);
                 sessions.insert(
                     session_id,
                     ActiveSession {
@@ -249,17 +403,27 @@ impl SessionManager {
                         pid: Some(pid),
                         event_tx: event_tx.clone(),
                         input_tx,
+                        pending_permissions: HashMap::new(),
+                        in_turn: false,
+                        checkpoint,
                     },
                 );
-                (event_tx, event_rx)
+                event_tx
             }
         };
+        let subscription = self.attach(session_id, &event_tx, None, None);
 
         // Spawn task to forward events from agent to broadcast channel
         let sessions_ref = self.sessions.clone();
+        let event_logs = self.event_logs.clone();
+        let activity = self.activity.clone();
         let core_ref = self.core.clone();
         tokio::spawn(async move {
+            let mut turn_started = Instant::now();
             while let Some(event) = handle.events.recv().await {
+                if let Some(journal) = journal.as_ref() {
+                    journal.record_event(session_id, &event);
+                }
                 if let AgentEvent::SessionInit(init) = &event {
                     if let Err(error) =
                         persist_agent_session_id(&core_ref, session_id, init.session_id.as_str())
@@ -273,6 +437,62 @@ impl SessionManager {
                         );
                     }
                 }
+                match &event {
+                    AgentEvent::ControlRequest(ControlRequestEvent {
+                        request_id,
+                        tool_name,
+                        permission,
+                        ..
+                    }) => {
+                        if let Some(permission) = permission {
+                            let mut sessions = sessions_ref.write().await;
+                            if let Some(session) = sessions.get_mut(&session_id) {
+                                session
+                                    .pending_permissions
+                                    .insert(request_id.clone(), permission.clone());
+                            }
+                        }
+                        activity.publish(ActivityKind::ControlRequestPending {
+                            session_id,
+                            workspace_id,
+                            request_id: request_id.clone(),
+                            tool_name: tool_name.clone(),
+                        });
+                    }
+                    AgentEvent::TurnStarted => {
+                        turn_started = Instant::now();
+                        set_in_turn(&sessions_ref, session_id, true).await;
+                        activity.publish(ActivityKind::TurnStarted {
+                            session_id,
+                            workspace_id,
+                        });
+                    }
+                    AgentEvent::TurnFailed(failed) => {
+                        set_in_turn(&sessions_ref, session_id, false).await;
+                        activity.publish(ActivityKind::TurnFailed {
+                            session_id,
+                            workspace_id,
+                            error: failed.error.clone(),
+                        });
+                    }
+                    AgentEvent::TurnCompleted(completed) => {
+                        set_in_turn(&sessions_ref, session_id, false).await;
+                        activity.publish(ActivityKind::TurnCompleted {
+                            session_id,
+                            workspace_id,
+                            usage: completed.usage.clone(),
+                        });
+                        record_turn_cost(
+                            &core_ref,
+                            session_id,
+                            &completed.usage,
+                            turn_started.elapsed(),
+                        )
+                        .await;
+                        turn_started = Instant::now();
+                    }
+                    _ => {}
+                }
                 if let AgentEvent::Error(err) = &event {
                     if err.code.as_deref() == Some("model_not_found") {
                         let core = core_ref.read().await;
@@ -288,6 +508,9 @@ impl SessionManager {
                     }
                 }
 
+                // Numbered and sent under the log lock; see `attach`
+                let mut logs = event_logs.lock();
+                let event = logs.entry(session_id).or_default().push(event);
                 if let Err(error) = event_tx.send(event) {
                     tracing::debug!(
                         %session_id,
@@ -297,24 +520,41 @@ impl SessionManager {
                 }
             }
             // Session ended, remove from map
-            let mut sessions = sessions_ref.write().await;
-            sessions.remove(&session_id);
+            sessions_ref.write().await.remove(&session_id);
+
+            // Keep the events around for a while so clients can see how it
+            // finished, unless another agent has logged more since
+            let position = |log: &EventLog| (log.epoch(), log.last_seq());
+            let last_position = event_logs.lock().get(&session_id).map(position);
+            tokio::time::sleep(REPLAY_RETENTION).await;
+            let mut logs = event_logs.lock();
+            if logs.get(&session_id).map(position) == last_position {
+                logs.remove(&session_id);
+            }
         });
 
-        Ok(event_rx)
+        Ok(subscription)
     }
 
     /// Subscribe to events for an existing session.
-    pub async fn subscribe(
+    ///
+    /// With `since_seq`, buffered events numbered after it are replayed first,
+    /// provided `epoch` still names the session's event log.
+    async fn subscribe(
         &self,
         session_id: Uuid,
-    ) -> Result<broadcast::Receiver<AgentEvent>, String> {
+        since_seq: Option<u64>,
+        epoch: Option<Uuid>,
+    ) -> Result<Subscription, String> {
         // If the session is running (or already has a channel), subscribe immediately.
-        {
+        let event_tx = {
             let sessions = self.sessions.read().await;
-            if let Some(session) = sessions.get(&session_id) {
-                return Ok(session.event_tx.subscribe());
-            }
+            sessions
+                .get(&session_id)
+                .map(|session| session.event_tx.clone())
+        };
+        if let Some(event_tx) = event_tx {
+            return Ok(self.attach(session_id, &event_tx, since_seq, epoch));
         }
 
         // Otherwise, validate the session exists in the DB and create an idle channel so the UI
@@ -330,24 +570,25 @@ impl SessionManager {
             .map_err(|e| format!("Failed to get session {}: {}", session_id, e))?
             .ok_or_else(|| format!("Session {} not found", session_id))?;
 
-        let (event_tx, event_rx) = broadcast::channel(256);
-        let mut sessions = self.sessions.write().await;
-        // Another subscribe/start could have raced us.
-        if let Some(existing) = sessions.get(&session_id) {
-            return Ok(existing.event_tx.subscribe());
-        }
-
-        sessions.insert(
-            session_id,
-            ActiveSession {
-                agent_type: tab.agent_type,
-                pid: None,
-                event_tx,
-                input_tx: None,
-            },
-        );
+        let event_tx = {
+            let mut sessions = self.sessions.write().await;
+            // Another subscribe/start could have raced us.
+            sessions
+                .entry(session_id)
+                .or_insert_with(|| ActiveSession {
+                    agent_type: tab.agent_type,
+                    pid: None,
+                    event_tx: broadcast::channel(256).0,
+                    input_tx: None,
+                    pending_permissions: HashMap::new(),
+                    in_turn: false,
+                    checkpoint: None,
+                })
+                .event_tx
+                .clone()
+        };
 
-        Ok(event_rx)
+        Ok(self.attach(session_id, &event_tx, since_seq, epoch))
     }
 
     /// Stop a running session.
@@ -420,7 +661,7 @@ impl SessionManager {
         images: Vec<PathBuf>,
         model: Option<String>,
     ) -> Result<(), String> {
-        let (input_tx, agent_type) = {
+        let (input_tx, agent_type, checkpoint) = {
             let sessions = self.sessions.read().await;
             let session = sessions
                 .get(&session_id)
@@ -429,19 +670,24 @@ impl SessionManager {
                 .input_tx
                 .clone()
                 .ok_or_else(|| "Session does not support input".to_string())?;
-            (input_tx, session.agent_type)
+            (input_tx, session.agent_type, session.checkpoint.clone())
         };
 
         // Send as appropriate input type based on agent
         let agent_input = match agent_type {
             AgentType::Claude => AgentInput::ClaudeJsonl(input),
-            AgentType::Codex | AgentType::Gemini | AgentType::Opencode => AgentInput::CodexPrompt {
+            // Gemini's input channel only carries permission answers
+            AgentType::Gemini => return Err("Session does not support input".to_string()),
+            AgentType::Codex | AgentType::Opencode => AgentInput::CodexPrompt {
                 text: input,
                 images,
                 model,
             },
         };
 
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.record().await;
+        }
         input_tx
             .send(agent_input)
             .await
@@ -491,6 +737,70 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Answer a permission request raised by the session's permission policy.
+    ///
+    /// "Always allow" answers are also saved as a rule for the session's repository.
+    pub async fn respond_to_permission(
+        &self,
+        session_id: Uuid,
+        request_id: String,
+        reply: PermissionReply,
+    ) -> Result<(), String> {
+        let (input_tx, request) = {
+            let mut sessions = self.sessions.write().await;
+            let session = sessions
+                .get_mut(&session_id)
+                .ok_or_else(|| format!("Session {} not found", session_id))?;
+            let input_tx = session
+                .input_tx
+                .clone()
+                .ok_or_else(|| "Session does not support permission responses".to_string())?;
+            (input_tx, session.pending_permissions.remove(&request_id))
+        };
+
+        input_tx
+            .send(AgentInput::PermissionResponse {
+                request_id: request_id.clone(),
+                reply,
+            })
+            .await
+            .map_err(|e| format!("Failed to send permission response: {}", e))?;
+
+        let Some(request) = request else {
+            return Ok(());
+        };
+        if let Some(journal) = self.core.read().await.session_journal() {
+            journal.record_permission(
+                session_id,
+                &request_id,
+                &request,
+                reply.as_str(),
+                AuditActor::Web,
+            );
+        }
+        if reply == PermissionReply::AlwaysAllow {
+            let mut core = self.core.write().await;
+            let session_tab = SessionService::get_session(&core, session_id).ok();
+            let workspace_id = session_tab.as_ref().and_then(|tab| tab.workspace_id);
+            let working_dir = session_tab.and_then(|tab| tab.working_dir);
+            ConfigService::remember_permission_rule(
+                &mut core,
+                workspace_id,
+                working_dir.as_deref(),
+                &request,
+            )
+            .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether a session's agent is in the middle of a turn.
+    pub async fn is_in_turn(&self, session_id: Uuid) -> bool {
+        let sessions = self.sessions.read().await;
+        sessions.get(&session_id).is_some_and(|s| s.in_turn)
+    }
+
     /// Get the agent type for a session.
     pub async fn get_agent_type(&self, session_id: Uuid) -> Option<AgentType> {
         let sessions = self.sessions.read().await;
@@ -498,6 +808,16 @@ impl SessionManager {
     }
 }
 
+async fn set_in_turn(
+    sessions: &RwLock<HashMap<Uuid, ActiveSession>>,
+    session_id: Uuid,
+    in_turn: bool,
+) {
+    if let Some(session) = sessions.write().await.get_mut(&session_id) {
+        session.in_turn = in_turn;
+    }
+}
+
 fn should_generate_title(hidden: bool, session: &crate::data::SessionTab) -> bool {
     !hidden && session.title.is_none() && !session.title_generated
 }
@@ -795,6 +1115,81 @@ fn uploads_dir() -> Result<PathBuf, String> {
     Ok(dir)
 }
 
+/// Send a subscriber its missed events, then live ones until the agent exits.
+///
+/// A subscriber that lags behind the broadcast channel is caught up from the
+/// event log, skipping anything it has already been sent.
+async fn forward_events(
+    session_manager: Arc<SessionManager>,
+    session_id: Uuid,
+    subscription: Subscription,
+    tx: mpsc::Sender<ServerMessage>,
+) {
+    let Subscription { mut events, replay } = subscription;
+    let mut epoch = replay.epoch;
+    let mut last_sent = replay.last_seq;
+    for (seq, event) in replay.events {
+        if tx
+            .send(ServerMessage::sequenced_event(
+                session_id, epoch, seq, event,
+            ))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+
+    loop {
+        let batch = match events.recv().await {
+            Ok(event) => vec![event],
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::debug!(%session_id, skipped, "Subscriber lagged");
+                let replay = session_manager.events_since(session_id, epoch, last_sent);
+                if replay.resync_required {
+                    // Too far behind to catch up; have the client reload
+                    epoch = replay.epoch;
+                    last_sent = replay.last_seq;
+                    let resync = ServerMessage::Subscribed {
+                        session_id,
+                        epoch: Some(replay.epoch),
+                        last_seq: replay.last_seq,
+                        resync_required: true,
+                    };
+                    if tx.send(resync).await.is_err() {
+                        return;
+                    }
+                }
+                replay.events
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        for (seq, event) in batch {
+            if seq <= last_sent {
+                continue;
+            }
+            last_sent = seq;
+            if tx
+                .send(ServerMessage::sequenced_event(
+                    session_id, epoch, seq, event,
+                ))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+    // Watchers also learn when the agent exits
+    let _ = tx
+        .send(ServerMessage::SessionEnded {
+            session_id,
+            reason: "completed".to_string(),
+            error: None,
+        })
+        .await;
+}
+
 /// Handle a WebSocket connection.
 pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionManager>) {
     let (mut ws_sender, mut ws_receiver) = socket.split();
@@ -863,29 +1258,31 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                 }
             }
 
-            ClientMessage::Subscribe { session_id } => {
-                match session_manager.subscribe(session_id).await {
-                    Ok(mut event_rx) => {
-                        let tx_clone = tx.clone();
-                        let task = tokio::spawn(async move {
-                            while let Ok(event) = event_rx.recv().await {
-                                if tx_clone
-                                    .send(ServerMessage::agent_event(session_id, event))
-                                    .await
-                                    .is_err()
-                                {
-                                    break;
-                                }
-                            }
-                        });
-
+            ClientMessage::Subscribe {
+                session_id,
+                since_seq,
+                epoch,
+            } => {
+                match session_manager
+                    .subscribe(session_id, since_seq, epoch)
+                    .await
+                {
+                    Ok(subscription) => {
+                        // Stop the previous forwarder first so it cannot
+                        // interleave with the replay
                         let mut subs = subscriptions.write().await;
-                        if let Some(existing) = subs.insert(session_id, task) {
+                        if let Some(existing) = subs.remove(&session_id) {
                             existing.abort();
                         }
 
-                        if let Err(send_err) =
-                            tx.send(ServerMessage::Subscribed { session_id }).await
+                        if let Err(send_err) = tx
+                            .send(ServerMessage::Subscribed {
+                                session_id,
+                                epoch: Some(subscription.replay.epoch),
+                                last_seq: subscription.replay.last_seq,
+                                resync_required: subscription.replay.resync_required,
+                            })
+                            .await
                         {
                             tracing::debug!(
                                 %session_id,
@@ -894,6 +1291,14 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                             );
                             break 'ws_loop;
                         }
+
+                        let task = tokio::spawn(forward_events(
+                            session_manager.clone(),
+                            session_id,
+                            subscription,
+                            tx.clone(),
+                        ));
+                        subs.insert(session_id, task);
                     }
                     Err(e) => {
                         if let Err(send_err) =
@@ -1032,8 +1437,24 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                         continue;
                     }
                 }
+                if !hidden {
+                    if let Some((message, blocked)) = budget_message(&core, session_id) {
+                        if let Err(send_err) = tx.send(message).await {
+                            tracing::debug!(
+                                %session_id,
+                                error = ?send_err,
+                                "Failed to send budget message"
+                            );
+                            break 'ws_loop;
+                        }
+                        if blocked {
+                            continue;
+                        }
+                    }
+                }
                 let agent_type = session_tab.agent_type;
                 let should_generate = should_generate_title(hidden, &session_tab);
+                let resumed = session_tab.agent_session_id.is_some();
                 drop(core);
 
                 let mut input_format: Option<String> = None;
@@ -1150,6 +1571,8 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                 }
 
                 let prompt_for_history = prompt.clone();
+                record_journal_prompt(&session_manager.core, session_id, &prompt, hidden, resumed)
+                    .await;
 
                 match session_manager
                     .start_session(StartSessionArgs {
@@ -1164,7 +1587,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                     })
                     .await
                 {
-                    Ok(mut event_rx) => {
+                    Ok(subscription) => {
                         if !hidden {
                             if let Err(error) = append_input_history(
                                 &session_manager.core,
@@ -1225,26 +1648,12 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                         }
 
                         // Auto-subscribe to the new session
-                        let tx_clone = tx.clone();
-                        let task = tokio::spawn(async move {
-                            while let Ok(event) = event_rx.recv().await {
-                                if tx_clone
-                                    .send(ServerMessage::agent_event(session_id, event))
-                                    .await
-                                    .is_err()
-                                {
-                                    break;
-                                }
-                            }
-                            // Session ended
-                            let _ = tx_clone
-                                .send(ServerMessage::SessionEnded {
-                                    session_id,
-                                    reason: "completed".to_string(),
-                                    error: None,
-                                })
-                                .await;
-                        });
+                        let task = tokio::spawn(forward_events(
+                            session_manager.clone(),
+                            session_id,
+                            subscription,
+                            tx.clone(),
+                        ));
 
                         let mut subs = subscriptions.write().await;
                         if let Some(existing) = subs.insert(session_id, task) {
@@ -1320,7 +1729,23 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                     }
                     continue;
                 }
+                if !hidden {
+                    if let Some((message, blocked)) = budget_message(&core, session_id) {
+                        if let Err(send_err) = tx.send(message).await {
+                            tracing::debug!(
+                                %session_id,
+                                error = ?send_err,
+                                "Failed to send budget message"
+                            );
+                            break 'ws_loop;
+                        }
+                        if blocked {
+                            continue;
+                        }
+                    }
+                }
                 let model = session_tab.model.clone();
+                let resumed = session_tab.agent_session_id.is_some();
                 drop(core);
                 let mut input_payload = input.clone();
                 let image_paths = if images.is_empty() {
@@ -1426,6 +1851,8 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                     }
                 }
 
+                record_journal_prompt(&session_manager.core, session_id, &input, hidden, resumed)
+                    .await;
                 if let Err(e) = session_manager
                     .send_input(session_id, input_payload, image_paths, model)
                     .await
@@ -1485,6 +1912,28 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                 }
             }
 
+            ClientMessage::RespondToPermission {
+                session_id,
+                request_id,
+                reply,
+            } => {
+                if let Err(e) = session_manager
+                    .respond_to_permission(session_id, request_id, reply)
+                    .await
+                {
+                    if let Err(send_err) =
+                        tx.send(ServerMessage::session_error(session_id, e)).await
+                    {
+                        tracing::debug!(
+                            %session_id,
+                            error = ?send_err,
+                            "Failed to send session error"
+                        );
+                        break 'ws_loop;
+                    }
+                }
+            }
+
             ClientMessage::StopSession { session_id } => {
                 // Clean up subscription first
                 {
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::agent::events::AgentEvent;
+use crate::agent::permissions::PermissionReply;
 use crate::agent::runner::AgentType;
 
 /// Image attachment for WebSocket prompts (base64-encoded).
@@ -21,7 +22,16 @@ pub struct ImageAttachment {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ClientMessage {
     /// Subscribe to events for a specific session
-    Subscribe { session_id: Uuid },
+    Subscribe {
+        session_id: Uuid,
+        /// Replay buffered events numbered after this one before live events
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        since_seq: Option<u64>,
+        /// Epoch the client's `since_seq` belongs to, as last reported by the
+        /// server; a mismatch means numbering restarted
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        epoch: Option<Uuid>,
+    },
 
     /// Unsubscribe from a session's events
     Unsubscribe { session_id: Uuid },
@@ -67,6 +77,14 @@ pub enum ClientMessage {
         response: serde_json::Value,
     },
 
+    /// Answer a tool permission request raised by the permission policy
+    RespondToPermission {
+        session_id: Uuid,
+        /// The control request ID to respond to
+        request_id: String,
+        reply: PermissionReply,
+    },
+
     /// Stop a running agent session
     StopSession { session_id: Uuid },
 
@@ -79,7 +97,20 @@ pub enum ClientMessage {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ServerMessage {
     /// Acknowledgment of subscription
-    Subscribed { session_id: Uuid },
+    Subscribed {
+        session_id: Uuid,
+        /// Identifies the session's event log; sequence numbers are only
+        /// comparable within one epoch
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        epoch: Option<Uuid>,
+        /// Sequence number of the session's newest event
+        #[serde(default)]
+        last_seq: u64,
+        /// Events after `since_seq` are no longer buffered; reload the
+        /// session from `/api/sessions/{id}/events`
+        #[serde(default)]
+        resync_required: bool,
+    },
 
     /// Acknowledgment of unsubscription
     Unsubscribed { session_id: Uuid },
@@ -101,7 +132,17 @@ pub enum ServerMessage {
     },
 
     /// Agent event forwarded from a session
-    AgentEvent { session_id: Uuid, event: AgentEvent },
+    AgentEvent {
+        session_id: Uuid,
+        /// Per-session sequence number; absent for events that only this
+        /// client sees (e.g. budget warnings)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seq: Option<u64>,
+        /// Epoch of the event log `seq` belongs to
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        epoch: Option<Uuid>,
+        event: AgentEvent,
+    },
 
     /// Session ended (completed or stopped)
     SessionEnded {
@@ -143,7 +184,22 @@ impl ServerMessage {
 
     /// Create an agent event message.
     pub fn agent_event(session_id: Uuid, event: AgentEvent) -> Self {
-        Self::AgentEvent { session_id, event }
+        Self::AgentEvent {
+            session_id,
+            seq: None,
+            epoch: None,
+            event,
+        }
+    }
+
+    /// Create an agent event message numbered within its session.
+    pub fn sequenced_event(session_id: Uuid, epoch: Uuid, seq: u64, event: AgentEvent) -> Self {
+        Self::AgentEvent {
+            session_id,
+            seq: Some(seq),
+            epoch: Some(epoch),
+            event,
+        }
     }
 
     /// Create a session started message.
@@ -2,9 +2,10 @@
 
 mod handler;
 mod messages;
+mod replay;
 
 #[cfg(test)]
 mod tests;
 
 pub use handler::{handle_websocket, SessionManager};
-pub use messages::{ClientMessage, ServerMessage};
+pub use messages::{ClientMessage, ImageAttachment, ServerMessage};
@@ -0,0 +1,106 @@
+//! Per-session event numbering and replay for reconnecting clients.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::agent::events::AgentEvent;
+
+/// Events kept per session for clients that reconnect with `since_seq`
+pub(super) const REPLAY_BUFFER_EVENTS: usize = 512;
+
+/// How long a session's events are kept once its agent has exited
+pub(super) const REPLAY_RETENTION: Duration = Duration::from_secs(30 * 60);
+
+/// An agent event with its per-session sequence number
+pub(super) type SequencedEvent = (u64, AgentEvent);
+
+/// Events missed by a reconnecting client
+pub(super) struct Replay {
+    pub events: Vec<SequencedEvent>,
+    /// Epoch of the log the sequence numbers belong to
+    pub epoch: Uuid,
+    /// Sequence number of the newest event so far (0 before the first)
+    pub last_seq: u64,
+    /// Some missed events are no longer buffered (or the client's sequence
+    /// number came from another log), so it has to reload the session
+    pub resync_required: bool,
+}
+
+/// Ring buffer of a session's most recent events.
+///
+/// Sequence numbers start at 1 and keep counting across agent restarts, so a
+/// client can resume a session whose agent has exited and started again.
+/// Numbering restarts whenever a log is created (after a server restart or
+/// once the old log was purged), so each log gets a random epoch that clients
+/// send back with `since_seq`.
+pub(super) struct EventLog {
+    epoch: Uuid,
+    last_seq: u64,
+    events: VecDeque<SequencedEvent>,
+    capacity: usize,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::with_capacity(REPLAY_BUFFER_EVENTS)
+    }
+}
+
+impl EventLog {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            epoch: Uuid::new_v4(),
+            last_seq: 0,
+            events: VecDeque::with_capacity(capacity.min(64)),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Number an event and buffer it, dropping the oldest one when full.
+    pub fn push(&mut self, event: AgentEvent) -> SequencedEvent {
+        self.last_seq += 1;
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back((self.last_seq, event.clone()));
+        (self.last_seq, event)
+    }
+
+    pub fn epoch(&self) -> Uuid {
+        self.epoch
+    }
+
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+
+    /// Buffered events numbered after `since_seq` in the log `epoch`.
+    ///
+    /// A sequence number without an epoch, or from another log, cannot be
+    /// trusted and always requires a resync.
+    pub fn since(&self, epoch: Option<Uuid>, since_seq: u64) -> Replay {
+        let oldest = self
+            .events
+            .front()
+            .map_or(self.last_seq + 1, |(seq, _)| *seq);
+        let other_log = since_seq > 0 && epoch != Some(self.epoch);
+        let resync_required = other_log || since_seq > self.last_seq || since_seq + 1 < oldest;
+        let events = if resync_required {
+            Vec::new()
+        } else {
+            self.events
+                .iter()
+                .filter(|(seq, _)| *seq > since_seq)
+                .cloned()
+                .collect()
+        };
+        Replay {
+            events,
+            epoch: self.epoch,
+            last_seq: self.last_seq,
+            resync_required,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Tests for WebSocket message types and serialization.
 
 use super::messages::{ClientMessage, ServerMessage};
+use super::replay::EventLog;
 use crate::agent::events::{AgentEvent, AssistantMessageEvent, SessionInitEvent};
 use crate::agent::session::SessionId;
 use uuid::Uuid;
@@ -18,17 +19,25 @@ fn test_client_message_ping_serialization() {
 #[test]
 fn test_client_message_subscribe_serialization() {
     let session_id = Uuid::nil();
-    let msg = ClientMessage::Subscribe { session_id };
+    let msg = ClientMessage::Subscribe {
+        session_id,
+        since_seq: None,
+        epoch: None,
+    };
     let json = serde_json::to_string(&msg).unwrap();
     assert!(json.contains(r#""type":"subscribe""#));
     assert!(json.contains(&session_id.to_string()));
+    assert!(!json.contains("since_seq"));
 
     let parsed: ClientMessage = serde_json::from_str(&json).unwrap();
     if let ClientMessage::Subscribe {
         session_id: parsed_id,
+        since_seq,
+        ..
     } = parsed
     {
         assert_eq!(parsed_id, session_id);
+        assert_eq!(since_seq, None);
     } else {
         panic!("Expected Subscribe message");
     }
@@ -139,10 +148,13 @@ fn test_server_message_agent_event_serialization() {
     let parsed: ServerMessage = serde_json::from_str(&json).unwrap();
     if let ServerMessage::AgentEvent {
         session_id: sid,
+        seq,
         event: parsed_event,
+        ..
     } = parsed
     {
         assert_eq!(sid, session_id);
+        assert_eq!(seq, None);
         if let AgentEvent::AssistantMessage(msg) = parsed_event {
             assert_eq!(msg.text, "Hello from Claude!");
             assert!(msg.is_final);
@@ -274,3 +286,105 @@ fn test_server_message_session_ended_serialization() {
         panic!("Expected SessionEnded message");
     }
 }
+
+#[test]
+fn test_subscribe_since_seq_round_trip() {
+    let session_id = Uuid::nil();
+    let json = format!(r#"{{"type":"subscribe","session_id":"{session_id}","since_seq":42}}"#);
+    let parsed: ClientMessage = serde_json::from_str(&json).unwrap();
+    assert!(matches!(
+        parsed,
+        ClientMessage::Subscribe {
+            since_seq: Some(42),
+            ..
+        }
+    ));
+
+    let epoch = Uuid::new_v4();
+    let msg = ServerMessage::sequenced_event(session_id, epoch, 7, AgentEvent::TurnStarted);
+    let json = serde_json::to_string(&msg).unwrap();
+    assert!(json.contains(r#""seq":7"#));
+    assert!(json.contains(&format!(r#""epoch":"{epoch}""#)));
+
+    // Older servers acknowledge without sequence numbers
+    let json = format!(r#"{{"type":"subscribed","session_id":"{session_id}"}}"#);
+    let parsed: ServerMessage = serde_json::from_str(&json).unwrap();
+    assert!(matches!(
+        parsed,
+        ServerMessage::Subscribed {
+            epoch: None,
+            last_seq: 0,
+            resync_required: false,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_event_log_replays_missed_events() {
+    let mut log = EventLog::with_capacity(3);
+    let epoch = Some(log.epoch());
+    for _ in 0..2 {
+        log.push(AgentEvent::TurnStarted);
+    }
+    let (seq, _) = log.push(AgentEvent::TurnStarted);
+    assert_eq!(seq, 3);
+
+    let replay = log.since(epoch, 1);
+    assert!(!replay.resync_required);
+    assert_eq!(replay.last_seq, 3);
+    let seqs: Vec<u64> = replay.events.iter().map(|(seq, _)| *seq).collect();
+    assert_eq!(seqs, vec![2, 3]);
+
+    assert!(log.since(epoch, 3).events.is_empty());
+    assert!(!log.since(epoch, 3).resync_required);
+}
+
+#[test]
+fn test_event_log_requires_resync_when_events_dropped() {
+    let mut log = EventLog::with_capacity(2);
+    let epoch = Some(log.epoch());
+    for _ in 0..4 {
+        log.push(AgentEvent::TurnStarted);
+    }
+
+    // Events 1 and 2 fell out of the buffer
+    assert!(log.since(epoch, 0).resync_required);
+    assert!(log.since(epoch, 1).resync_required);
+    assert!(!log.since(epoch, 2).resync_required);
+    assert_eq!(log.since(epoch, 2).events.len(), 2);
+
+    assert!(log.since(epoch, 10).resync_required);
+    assert!(EventLog::default().since(None, 0).events.is_empty());
+    assert!(!EventLog::default().since(None, 0).resync_required);
+}
+
+#[test]
+fn test_event_log_requires_resync_after_numbering_restarts() {
+    let old_log = {
+        let mut log = EventLog::default();
+        for _ in 0..3 {
+            log.push(AgentEvent::TurnStarted);
+        }
+        log
+    };
+    let old_epoch = Some(old_log.epoch());
+
+    // A restarted server (or a purged log) numbers from 1 again and soon
+    // passes the client's old sequence number
+    let mut log = EventLog::default();
+    for _ in 0..5 {
+        log.push(AgentEvent::TurnStarted);
+    }
+    assert_ne!(Some(log.epoch()), old_epoch);
+
+    let replay = log.since(old_epoch, 3);
+    assert!(replay.resync_required);
+    assert!(replay.events.is_empty());
+    assert_eq!(replay.epoch, log.epoch());
+    assert_eq!(replay.last_seq, 5);
+
+    // Clients that don't send an epoch can't be trusted either
+    assert!(log.since(None, 3).resync_required);
+    assert!(!log.since(Some(log.epoch()), 3).resync_required);
+}
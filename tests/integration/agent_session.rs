@@ -9,6 +9,9 @@ use std::time::Duration;
 
 use conduit::agent::events::{AgentEvent, AssistantMessageEvent, SessionInitEvent};
 use conduit::agent::mock::{MockAgentRunner, MockConfig, MockEventBuilder, MockStartError};
+use conduit::agent::permissions::{
+    PermissionDecision, PermissionPolicy, PermissionRequest, PermissionRule,
+};
 use conduit::agent::runner::{AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use conduit::agent::session::SessionId;
 
@@ -136,11 +139,22 @@ async fn test_mock_captures_start_config() {
         PathBuf::from("/workspace/project"),
     )
     .with_model("opus")
-    .with_tools(vec![
-        "Bash".to_string(),
-        "Read".to_string(),
-        "Edit".to_string(),
-    ]);
+    .with_permissions(PermissionPolicy {
+        default: PermissionDecision::Ask,
+        rules: vec![
+            PermissionRule {
+                tool: Some("Read".to_string()),
+                ..PermissionRule::default()
+            },
+            PermissionRule {
+                decision: PermissionDecision::Deny,
+                tool: Some("Bash".to_string()),
+                command: Some("rm *".to_string()),
+                ..PermissionRule::default()
+            },
+        ],
+        ..PermissionPolicy::default()
+    });
 
     runner.start(config).await.unwrap();
 
@@ -151,8 +165,13 @@ async fn test_mock_captures_start_config() {
     assert_eq!(config.prompt, "Test prompt with details");
     assert_eq!(config.working_dir, PathBuf::from("/workspace/project"));
     assert_eq!(config.model, Some("opus".to_string()));
-    assert_eq!(config.allowed_tools.len(), 3);
-    assert!(config.allowed_tools.contains(&"Bash".to_string()));
+    assert_eq!(config.permissions.rules.len(), 2);
+    assert_eq!(
+        config
+            .permissions
+            .evaluate(&PermissionRequest::new("Bash").with_command("rm -rf target")),
+        PermissionDecision::Deny
+    );
 }
 
 /// Test that the mock agent captures inputs sent during the session
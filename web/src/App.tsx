@@ -9,6 +9,7 @@ import {
   AddProjectDialog,
   CreateWorkspaceDialog,
   ConfirmDialog,
+  LoginPage,
 } from './components';
 import { CommandPalette, type CommandPaletteItem } from './components/CommandPalette';
 import { SessionImportDialog } from './components/SessionImportDialog';
@@ -39,10 +40,12 @@ import {
   useWorkspaceActions,
   useUpdateSession,
   useClearUnseenSession,
+  useAuthStatus,
 } from './hooks';
 import type { Repository, Workspace, Session, SessionEvent, AgentEvent, WorkspaceMode } from './types';
-import { supportsPlanMode } from './lib/agentCapabilities';
+import { AGENT_TYPES, agentDisplayName, supportsPlanMode } from './lib/agentCapabilities';
 import { copyText } from './lib/clipboard';
+import { UNAUTHORIZED_EVENT } from './lib/auth';
 import { cn } from './lib/cn';
 
 // Create a client
@@ -736,7 +739,7 @@ function AppContent() {
     }
   };
 
-  const { handleForkSession, handleCreatePr } = useWorkspaceActions({
+  const { handleForkSession, handleHandoffSession, handleCreatePr } = useWorkspaceActions({
     session: activeSession,
     workspace: activeWorkspace ?? null,
     onForkedSession: (session) => handleImportedSession(session),
@@ -802,6 +805,15 @@ function AppContent() {
         disabled: !activeSession,
         onSelect: handleForkSession,
       },
+      ...AGENT_TYPES.filter((agentType) => agentType !== activeSession?.agent_type).map(
+        (agentType): CommandPaletteItem => ({
+          id: `handoff-session-${agentType}`,
+          label: `Hand Off to ${agentDisplayName(agentType)}...`,
+          keywords: 'fork handoff agent continue',
+          disabled: !activeSession,
+          onSelect: () => handleHandoffSession(agentType),
+        })
+      ),
       {
         id: 'toggle-plan-mode',
         label: activeSession?.agent_mode === 'plan' ? 'Switch to Build Mode' : 'Switch to Plan Mode',
@@ -899,6 +911,7 @@ function AppContent() {
       handleCloseSession,
       handleCopyWorkspacePath,
       handleForkSession,
+      handleHandoffSession,
       handleNewSession,
       handleNewWorkspace,
       handleNextTab,
@@ -1156,13 +1169,43 @@ function AppContent() {
   );
 }
 
+// Shows the login page until the server accepts our token (when it requires one)
+function AuthGate({ children }: { children: React.ReactNode }) {
+  const { data: authStatus, isLoading, refetch } = useAuthStatus();
+
+  useEffect(() => {
+    const handleUnauthorized = () => {
+      refetch();
+    };
+    window.addEventListener(UNAUTHORIZED_EVENT, handleUnauthorized);
+    return () => window.removeEventListener(UNAUTHORIZED_EVENT, handleUnauthorized);
+  }, [refetch]);
+
+  if (isLoading) {
+    return null;
+  }
+  if (authStatus?.auth_required && !authStatus.authenticated) {
+    return (
+      <LoginPage
+        onLogin={() => {
+          refetch();
+          queryClient.invalidateQueries();
+        }}
+      />
+    );
+  }
+  return <>{children}</>;
+}
+
 function App() {
   return (
     <QueryClientProvider client={queryClient}>
       <ThemeProvider>
-        <WebSocketProvider>
-          <AppContent />
-        </WebSocketProvider>
+        <AuthGate>
+          <WebSocketProvider>
+            <AppContent />
+          </WebSocketProvider>
+        </AuthGate>
       </ThemeProvider>
     </QueryClientProvider>
   );
@@ -14,6 +14,7 @@ import {
   useWorkspace,
   useWorkspaceStatus,
   useRawSessionEvents,
+  useSessionResync,
   useUpdateSession,
   useSetDefaultModel,
   useSessionQueue,
@@ -197,7 +198,7 @@ export function ChatView({
   const forceScrollToBottom = useRef(false);
   const scrollStateBySession = useRef<Record<string, { top: number; pinned: boolean }>>({});
   const scrollSessionId = useRef<string | null>(null);
-  const { sendPrompt, respondToControl, stopSession } = useWebSocket();
+  const { sendPrompt, respondToControl, respondToPermission, stopSession } = useWebSocket();
   const wsEvents = useSessionEvents(session?.id ?? null);
   const updateSessionMutation = useUpdateSession();
   const setDefaultModelMutation = useSetDefaultModel();
@@ -228,6 +229,7 @@ export function ChatView({
   const [isAwaitingResponse, setIsAwaitingResponse] = useState(false);
   const [hasInitiallyScrolled, setHasInitiallyScrolled] = useState(false);
   const [inlinePrompt, setInlinePrompt] = useState<InlinePromptData | null>(null);
+  const shownPermissionRequestId = useRef<string | null>(null);
   const [pendingControlResponse, setPendingControlResponse] = useState<unknown | null>(null);
   const [showRawEvents, setShowRawEvents] = useState(false);
   const [showModelSelector, setShowModelSelector] = useState(false);
@@ -628,6 +630,14 @@ export function ChatView({
     };
   }, [refreshHistoryTail, session, wsEvents, wsEventCutoff]);
 
+  // Events were lost while disconnected; the reloaded history covers them
+  const wsEventsLengthRef = useRef(wsEvents.length);
+  wsEventsLengthRef.current = wsEvents.length;
+  useSessionResync(session?.id ?? null, () => {
+    const cutoff = wsEventsLengthRef.current;
+    refreshHistoryTail().then(() => setWsEventCutoff(cutoff));
+  });
+
   const draftValue = session ? drafts[session.id] ?? '' : '';
   const optimisticUserMessages = session ? optimisticMessages[session.id] ?? [] : [];
 
@@ -707,6 +717,20 @@ export function ChatView({
       }
     }
 
+    if (lastEvent.type === 'ControlRequest' && lastEvent.permission) {
+      // The event stays last after the prompt is answered; only show each request once
+      if (shownPermissionRequestId.current !== lastEvent.request_id) {
+        shownPermissionRequestId.current = lastEvent.request_id;
+        setInlinePrompt({
+          type: 'tool_permission',
+          toolUseId: lastEvent.tool_use_id ?? lastEvent.request_id,
+          request: lastEvent.permission,
+          requestId: lastEvent.request_id,
+        });
+      }
+      return;
+    }
+
     if (lastEvent.type === 'ControlRequest' && inlinePrompt) {
       if (lastEvent.tool_use_id && lastEvent.tool_use_id === inlinePrompt.toolUseId) {
         setInlinePrompt({ ...inlinePrompt, requestId: lastEvent.request_id });
@@ -953,6 +977,14 @@ export function ChatView({
   const handlePromptSubmit = (response: InlinePromptResponse) => {
     if (!session || !inlinePrompt) return;
 
+    if (inlinePrompt.type === 'tool_permission') {
+      if (response.type === 'tool_permission') {
+        respondToPermission(session.id, inlinePrompt.requestId, response.reply);
+        setInlinePrompt(null);
+      }
+      return;
+    }
+
     let controlResponse: unknown;
 
     if (response.type === 'ask_user' && inlinePrompt.type === 'ask_user') {
@@ -984,6 +1016,11 @@ export function ChatView({
 
   const handlePromptCancel = () => {
     if (!session || !inlinePrompt) return;
+    if (inlinePrompt.type === 'tool_permission') {
+      respondToPermission(session.id, inlinePrompt.requestId, 'deny');
+      setInlinePrompt(null);
+      return;
+    }
     const controlResponse = buildPermissionDenyResponse('User cancelled the prompt.', inlinePrompt.toolUseId);
     if (inlinePrompt.requestId) {
       respondToControl(session.id, inlinePrompt.requestId, controlResponse);
@@ -1006,7 +1043,7 @@ export function ChatView({
   type RenderableEvent = typeof wsEvents[number] | ToolRunEvent;
 
   const visibleWsEvents = useMemo(() => {
-    if (session?.agent_type !== 'opencode' || wsEventCutoff === 0) {
+    if (wsEventCutoff === 0) {
       return wsEvents;
     }
     return wsEvents.filter((_, index) => index >= wsEventCutoff);
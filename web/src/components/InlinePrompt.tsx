@@ -1,6 +1,6 @@
 import { useMemo, useState } from 'react';
-import { CheckCircle2, MessageSquarePlus, XCircle } from 'lucide-react';
-import type { UserQuestion } from '../types';
+import { CheckCircle2, MessageSquarePlus, ShieldAlert, XCircle } from 'lucide-react';
+import type { PermissionReply, PermissionRequest, UserQuestion } from '../types';
 import { cn } from '../lib/cn';
 
 export type InlinePromptData =
@@ -15,6 +15,12 @@ export type InlinePromptData =
       toolUseId: string;
       plan: string;
       requestId: string | null;
+    }
+  | {
+      type: 'tool_permission';
+      toolUseId: string;
+      request: PermissionRequest;
+      requestId: string;
     };
 
 export type InlinePromptResponse =
@@ -26,6 +32,10 @@ export type InlinePromptResponse =
       type: 'exit_plan';
       approved: boolean;
       feedback?: string;
+    }
+  | {
+      type: 'tool_permission';
+      reply: PermissionReply;
     };
 
 interface InlinePromptProps {
@@ -47,6 +57,17 @@ export function InlinePrompt({ prompt, onSubmit, onCancel, isPending }: InlinePr
     );
   }
 
+  if (prompt.type === 'tool_permission') {
+    return (
+      <ToolPermissionPrompt
+        prompt={prompt}
+        onSubmit={onSubmit}
+        onCancel={onCancel}
+        isPending={isPending}
+      />
+    );
+  }
+
   return (
     <ExitPlanPrompt
       prompt={prompt}
@@ -272,3 +293,63 @@ function ExitPlanPrompt({
     </div>
   );
 }
+
+function ToolPermissionPrompt({
+  prompt,
+  onSubmit,
+  onCancel,
+  isPending,
+}: {
+  prompt: Extract<InlinePromptData, { type: 'tool_permission' }>;
+  onSubmit: (response: InlinePromptResponse) => void;
+  onCancel: () => void;
+  isPending?: boolean;
+}) {
+  const { tool, command, paths } = prompt.request;
+
+  return (
+    <div className="rounded-xl border border-border bg-surface p-4">
+      <div className="mb-3 flex items-center gap-2 text-sm font-medium text-text">
+        <ShieldAlert className="h-4 w-4 text-accent" />
+        <span>Allow {tool}?</span>
+      </div>
+
+      {(command || (paths && paths.length > 0)) && (
+        <div className="rounded-lg border border-border bg-surface-elevated p-3">
+          {command && <pre className="whitespace-pre-wrap text-xs text-text">{command}</pre>}
+          {paths?.map((path) => (
+            <div key={path} className="truncate font-mono text-xs text-text-muted">
+              {path}
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="mt-4 flex flex-wrap items-center justify-end gap-2">
+        <button
+          onClick={onCancel}
+          disabled={isPending}
+          className="flex items-center gap-2 rounded-lg border border-border px-3 py-1.5 text-sm text-text-muted hover:bg-surface-elevated disabled:cursor-not-allowed disabled:opacity-60"
+        >
+          <XCircle className="h-4 w-4" />
+          Deny
+        </button>
+        <button
+          onClick={() => onSubmit({ type: 'tool_permission', reply: 'always_allow' })}
+          disabled={isPending}
+          className="rounded-lg border border-border px-3 py-1.5 text-sm text-text hover:bg-surface-elevated disabled:cursor-not-allowed disabled:opacity-60"
+        >
+          Always allow
+        </button>
+        <button
+          onClick={() => onSubmit({ type: 'tool_permission', reply: 'allow_once' })}
+          disabled={isPending}
+          className="flex items-center gap-2 rounded-lg bg-accent px-3 py-1.5 text-sm text-white transition-colors hover:bg-accent-hover disabled:cursor-not-allowed disabled:opacity-60"
+        >
+          <CheckCircle2 className="h-4 w-4" />
+          Allow once
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,70 @@
+import { useState } from 'react';
+import { KeyRound, Loader2 } from 'lucide-react';
+import { Logo } from './Logo';
+import { getAuthStatus } from '../lib/api';
+import { clearAuthToken, setAuthToken } from '../lib/auth';
+
+interface LoginPageProps {
+  onLogin: () => void;
+}
+
+export function LoginPage({ onLogin }: LoginPageProps) {
+  const [token, setToken] = useState('');
+  const [isPending, setIsPending] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!token.trim()) return;
+    setIsPending(true);
+    setError(null);
+    setAuthToken(token.trim());
+    try {
+      const status = await getAuthStatus();
+      if (status.authenticated) {
+        onLogin();
+        return;
+      }
+      clearAuthToken();
+      setError('That token is not valid on this server.');
+    } catch (err) {
+      clearAuthToken();
+      setError(err instanceof Error ? err.message : 'Could not reach the server.');
+    } finally {
+      setIsPending(false);
+    }
+  };
+
+  return (
+    <div className="flex h-screen flex-col items-center justify-center bg-background px-6 text-center text-text-muted">
+      <div className="mb-5 flex size-16 items-center justify-center rounded-2xl bg-accent/15">
+        <Logo className="h-9 w-9" />
+      </div>
+      <h2 className="mb-2 text-2xl font-semibold text-text">Log in to Conduit</h2>
+      <p className="mb-6 max-w-md text-sm">
+        Paste an API token. <code>conduit serve</code> prints one the first time it runs; create
+        more with <code>conduit token create</code>.
+      </p>
+      <form onSubmit={handleSubmit} className="flex w-full max-w-md flex-col gap-3">
+        <input
+          type="password"
+          value={token}
+          onChange={(e) => setToken(e.target.value)}
+          placeholder="cdt_..."
+          autoFocus
+          autoComplete="current-password"
+          className="w-full rounded-lg border border-border bg-surface px-3 py-2 text-sm text-text placeholder:text-text-muted focus:border-accent focus:outline-none"
+        />
+        {error && <p className="text-sm text-error">{error}</p>}
+        <button
+          type="submit"
+          disabled={isPending || !token.trim()}
+          className="flex items-center justify-center gap-2 rounded-lg bg-accent px-5 py-2.5 text-sm font-medium text-white transition-colors hover:bg-accent-hover disabled:opacity-50"
+        >
+          {isPending ? <Loader2 className="h-4 w-4 animate-spin" /> : <KeyRound className="h-4 w-4" />}
+          Log In
+        </button>
+      </form>
+    </div>
+  );
+}
@@ -12,6 +12,7 @@ export * from './CreateWorkspaceDialog';
 export * from './SessionTabs';
 export * from './InlinePrompt';
 export * from './ConfirmDialog';
+export * from './LoginPage';
 export * from './onboarding/OnboardingEmptyState';
 export * from './onboarding/BaseDirDialog';
 export * from './onboarding/ProjectPickerDialog';
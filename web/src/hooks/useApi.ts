@@ -4,6 +4,7 @@ import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import * as api from '../lib/api';
 import type {
   CreateRepositoryRequest,
+  CloneJob,
   UpdateRepositorySettingsRequest,
   CreateWorkspaceRequest,
   CreateSessionRequest,
@@ -15,6 +16,15 @@ import type {
   UpdateQueueMessageRequest,
   OnboardingProjectsResponse,
   AddOnboardingProjectRequest,
+  DiffBase,
+  DiffScope,
+  ApplyHunkRequest,
+  CommitRequest,
+  ReviewPromptRequest,
+  SetReviewCommentsAddressedRequest,
+  SyncRequest,
+  RewindRequest,
+  CreateRaceRequest,
 } from '../types';
 
 // Query keys
@@ -25,12 +35,19 @@ export const queryKeys = {
   repositories: ['repositories'] as const,
   repository: (id: string) => ['repositories', id] as const,
   repositoryRemovePreflight: (id: string) => ['repositories', id, 'remove-preflight'] as const,
+  repositoryClone: (id: string) => ['repositories', id, 'clone'] as const,
   workspaces: ['workspaces'] as const,
   repositoryWorkspaces: (id: string) => ['repositories', id, 'workspaces'] as const,
   workspace: (id: string) => ['workspaces', id] as const,
   workspaceStatus: (id: string) => ['workspaces', id, 'status'] as const,
+  workspaceDiff: (id: string, base: DiffBase, scope: DiffScope = 'all') =>
+    ['workspaces', id, 'diff', base, scope] as const,
   workspaceArchivePreflight: (id: string) => ['workspaces', id, 'archive-preflight'] as const,
   workspacePrPreflight: (id: string) => ['workspaces', id, 'pr-preflight'] as const,
+  workspaceCommitPreview: (id: string) => ['workspaces', id, 'commit-preview'] as const,
+  workspaceReviewComments: (id: string) => ['workspaces', id, 'review-comments'] as const,
+  workspaceSync: (id: string) => ['workspaces', id, 'sync'] as const,
+  workspaceHooks: (id: string) => ['workspaces', id, 'hooks'] as const,
   workspaceSession: (id: string) => ['workspaces', id, 'session'] as const,
   workspaceFileContent: (workspaceId: string, filePath: string) =>
     ['workspaces', workspaceId, 'files', filePath] as const,
@@ -40,12 +57,15 @@ export const queryKeys = {
     ['sessions', id, 'events', query ?? {}] as const,
   sessionHistory: (id: string) => ['sessions', id, 'history'] as const,
   sessionQueue: (id: string) => ['sessions', id, 'queue'] as const,
+  sessionCheckpoints: (id: string) => ['sessions', id, 'checkpoints'] as const,
+  race: (id: string) => ['races', id] as const,
   externalSessions: (agentType?: string | null) =>
     ['external-sessions', agentType ?? 'all'] as const,
   onboardingBaseDir: ['onboarding', 'base-dir'] as const,
   onboardingProjects: ['onboarding', 'projects'] as const,
   uiState: ['ui', 'state'] as const,
   bootstrap: ['bootstrap'] as const,
+  authStatus: ['auth', 'status'] as const,
 };
 
 // Health
@@ -58,6 +78,15 @@ export function useHealth() {
 }
 
 // Bootstrap
+export function useAuthStatus() {
+  return useQuery({
+    queryKey: queryKeys.authStatus,
+    queryFn: api.getAuthStatus,
+    staleTime: Infinity,
+    retry: false,
+  });
+}
+
 export function useBootstrap() {
   const queryClient = useQueryClient();
   return useQuery({
@@ -162,6 +191,36 @@ export function useRemoveRepository() {
   });
 }
 
+export function useCloneStatus(repositoryId: string | null, options?: { enabled?: boolean }) {
+  const queryClient = useQueryClient();
+  return useQuery({
+    queryKey: queryKeys.repositoryClone(repositoryId ?? ''),
+    queryFn: async () => {
+      const key = queryKeys.repositoryClone(repositoryId!);
+      const previous = queryClient.getQueryData<CloneJob>(key);
+      const job = await api.getCloneStatus(repositoryId!);
+      if (previous?.status === 'cloning' && job.status === 'succeeded') {
+        // The repository now has a base path
+        queryClient.invalidateQueries({ queryKey: queryKeys.repositories });
+      }
+      return job;
+    },
+    enabled: (options?.enabled ?? true) && !!repositoryId,
+    retry: false,
+    refetchInterval: (query) => (query.state.data?.status === 'cloning' ? 1000 : false),
+  });
+}
+
+export function useRetryClone() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => api.retryClone(id),
+    onSuccess: (_, id) => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.repositoryClone(id) });
+    },
+  });
+}
+
 // Onboarding
 export function useOnboardingBaseDir(options?: { enabled?: boolean }) {
   return useQuery({
@@ -263,6 +322,17 @@ export function useDeleteWorkspace() {
   });
 }
 
+// Polls while a hook is running so its output streams in
+export function useWorkspaceHooks(workspaceId: string | null, options?: { enabled?: boolean }) {
+  return useQuery({
+    queryKey: queryKeys.workspaceHooks(workspaceId ?? ''),
+    queryFn: () => api.getWorkspaceHooks(workspaceId!),
+    enabled: (options?.enabled ?? true) && !!workspaceId,
+    refetchInterval: (query) =>
+      query.state.data?.runs.some((run) => run.status === 'running') ? 1000 : false,
+  });
+}
+
 export function useWorkspaceStatus(
   workspaceId: string | null,
   options?: { enabled?: boolean; refetchInterval?: number | false; staleTime?: number }
@@ -277,6 +347,31 @@ export function useWorkspaceStatus(
   });
 }
 
+export function useWorkspaceDiff(
+  workspaceId: string | null,
+  base: DiffBase = 'head',
+  scope: DiffScope = 'all'
+) {
+  return useQuery({
+    queryKey: queryKeys.workspaceDiff(workspaceId ?? '', base, scope),
+    queryFn: () => api.getWorkspaceDiff(workspaceId!, base, scope),
+    enabled: !!workspaceId,
+    staleTime: 2000,
+  });
+}
+
+export function useApplyWorkspaceHunk() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ workspaceId, data }: { workspaceId: string; data: ApplyHunkRequest }) =>
+      api.applyWorkspaceHunk(workspaceId, data),
+    onSuccess: (_diff, vars) => {
+      queryClient.invalidateQueries({ queryKey: ['workspaces', vars.workspaceId, 'diff'] });
+      queryClient.invalidateQueries({ queryKey: queryKeys.workspaceStatus(vars.workspaceId) });
+    },
+  });
+}
+
 export function useWorkspaceArchivePreflight(
   workspaceId: string | null,
   options?: { enabled?: boolean }
@@ -298,6 +393,121 @@ export function useWorkspacePrPreflight(workspaceId: string | null, options?: {
   });
 }
 
+export function useCommitPreview(workspaceId: string | null, options?: { enabled?: boolean }) {
+  return useQuery({
+    queryKey: queryKeys.workspaceCommitPreview(workspaceId ?? ''),
+    queryFn: () => api.getCommitPreview(workspaceId!),
+    enabled: (options?.enabled ?? true) && !!workspaceId,
+    staleTime: 0,
+  });
+}
+
+export function useGenerateCommitMessage() {
+  return useMutation({
+    mutationFn: (workspaceId: string) => api.generateCommitMessage(workspaceId),
+  });
+}
+
+export function useCommitWorkspace() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ workspaceId, data }: { workspaceId: string; data: CommitRequest }) =>
+      api.commitWorkspace(workspaceId, data),
+    onSuccess: (_result, vars) => {
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.workspaceCommitPreview(vars.workspaceId),
+      });
+      queryClient.invalidateQueries({ queryKey: ['workspaces', vars.workspaceId, 'diff'] });
+      queryClient.invalidateQueries({ queryKey: queryKeys.workspaceStatus(vars.workspaceId) });
+    },
+  });
+}
+
+export function useReviewComments(workspaceId: string | null, options?: { enabled?: boolean }) {
+  return useQuery({
+    queryKey: queryKeys.workspaceReviewComments(workspaceId ?? ''),
+    queryFn: () => api.getReviewComments(workspaceId!),
+    enabled: (options?.enabled ?? true) && !!workspaceId,
+    staleTime: 0,
+  });
+}
+
+export function useBuildReviewPrompt() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ workspaceId, data }: { workspaceId: string; data: ReviewPromptRequest }) =>
+      api.buildReviewPrompt(workspaceId, data),
+    onSuccess: (_result, vars) => {
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.workspaceReviewComments(vars.workspaceId),
+      });
+    },
+  });
+}
+
+export function useSetReviewCommentsAddressed() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({
+      workspaceId,
+      data,
+    }: {
+      workspaceId: string;
+      data: SetReviewCommentsAddressedRequest;
+    }) => api.setReviewCommentsAddressed(workspaceId, data),
+    onSuccess: (_result, vars) => {
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.workspaceReviewComments(vars.workspaceId),
+      });
+    },
+  });
+}
+
+export function useBuildCiFixPrompt() {
+  return useMutation({
+    mutationFn: (workspaceId: string) => api.buildCiFixPrompt(workspaceId),
+  });
+}
+
+export function useSyncStatus(workspaceId: string | null, options?: { enabled?: boolean }) {
+  return useQuery({
+    queryKey: queryKeys.workspaceSync(workspaceId ?? ''),
+    queryFn: () => api.getSyncStatus(workspaceId!),
+    enabled: (options?.enabled ?? true) && !!workspaceId,
+  });
+}
+
+export function useSyncWorkspace() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ workspaceId, data }: { workspaceId: string; data?: SyncRequest }) =>
+      api.syncWorkspace(workspaceId, data),
+    onSuccess: (_result, vars) => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.workspaceSync(vars.workspaceId) });
+    },
+  });
+}
+
+export function useContinueSync() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (workspaceId: string) => api.continueSync(workspaceId),
+    onSuccess: (_result, workspaceId) => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.workspaceSync(workspaceId) });
+    },
+  });
+}
+
+export function useAbortSync() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (workspaceId: string) => api.abortSync(workspaceId),
+    onSuccess: (_result, workspaceId) => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.workspaceSync(workspaceId) });
+    },
+  });
+}
+
 export function useAutoCreateWorkspace() {
   const queryClient = useQueryClient();
   return useMutation({
@@ -505,7 +715,8 @@ export function useUpdateSession() {
 export function useForkSession() {
   const queryClient = useQueryClient();
   return useMutation({
-    mutationFn: (id: string) => api.forkSession(id),
+    mutationFn: ({ id, agentType }: { id: string; agentType?: Session['agent_type'] }) =>
+      api.forkSession(id, agentType),
     onSuccess: (response) => {
       queryClient.invalidateQueries({ queryKey: queryKeys.sessions });
       queryClient.invalidateQueries({ queryKey: queryKeys.workspaces });
@@ -514,6 +725,73 @@ export function useForkSession() {
   });
 }
 
+export function useSessionCheckpoints(id: string | null) {
+  return useQuery({
+    queryKey: queryKeys.sessionCheckpoints(id ?? ''),
+    queryFn: () => api.getSessionCheckpoints(id!),
+    enabled: !!id,
+  });
+}
+
+export function useRewindSession() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ id, data }: { id: string; data?: RewindRequest }) =>
+      api.rewindSession(id, data),
+    onSuccess: (checkpoint) => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.sessionCheckpoints(checkpoint.session_id) });
+      queryClient.invalidateQueries({ queryKey: ['sessions', checkpoint.session_id, 'events'] });
+      if (checkpoint.workspace_id) {
+        queryClient.invalidateQueries({ queryKey: queryKeys.workspaceStatus(checkpoint.workspace_id) });
+        queryClient.invalidateQueries({ queryKey: ['workspaces', checkpoint.workspace_id, 'diff'] });
+      }
+    },
+  });
+}
+
+export function useRace(id: string | null) {
+  return useQuery({
+    queryKey: queryKeys.race(id ?? ''),
+    queryFn: () => api.getRace(id!),
+    enabled: !!id,
+  });
+}
+
+export function useCreateRace() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (data: CreateRaceRequest) => api.createRace(data),
+    onSuccess: (response) => {
+      queryClient.setQueryData(queryKeys.race(response.race.id), response.race);
+      queryClient.invalidateQueries({ queryKey: queryKeys.sessions });
+      queryClient.invalidateQueries({ queryKey: queryKeys.workspaces });
+    },
+  });
+}
+
+export function useRunRaceTests() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => api.runRaceTests(id),
+    onSuccess: (race) => {
+      queryClient.setQueryData(queryKeys.race(race.id), race);
+    },
+  });
+}
+
+export function usePickRaceWinner() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ id, workspaceId }: { id: string; workspaceId: string }) =>
+      api.pickRaceWinner(id, workspaceId),
+    onSuccess: (race) => {
+      queryClient.setQueryData(queryKeys.race(race.id), race);
+      queryClient.invalidateQueries({ queryKey: queryKeys.sessions });
+      queryClient.invalidateQueries({ queryKey: queryKeys.workspaces });
+    },
+  });
+}
+
 export function useCreateWorkspacePr() {
   return useMutation({
     mutationFn: (workspaceId: string) => api.createWorkspacePr(workspaceId),
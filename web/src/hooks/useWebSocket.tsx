@@ -3,7 +3,7 @@
 import { createContext, useContext, useEffect, useState, useCallback, useRef, type ReactNode } from 'react';
 import { useQueryClient } from '@tanstack/react-query';
 import { getWebSocket, type ConnectionState, type ConduitWebSocket } from '../lib/websocket';
-import type { AgentEvent, ImageAttachment, ServerMessage, Session, Workspace } from '../types';
+import type { AgentEvent, ImageAttachment, PermissionReply, ServerMessage, Session, Workspace } from '../types';
 import { queryKeys } from './useApi';
 
 // WebSocket context
@@ -37,6 +37,7 @@ interface WebSocketContextValue {
   ) => void;
   stopSession: (sessionId: string) => void;
   respondToControl: (sessionId: string, requestId: string, response: unknown) => void;
+  respondToPermission: (sessionId: string, requestId: string, reply: PermissionReply) => void;
 }
 
 const WebSocketContext = createContext<WebSocketContextValue | null>(null);
@@ -259,6 +260,13 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
     [ws]
   );
 
+  const respondToPermission = useCallback(
+    (sessionId: string, requestId: string, reply: PermissionReply) => {
+      ws.respondToPermission(sessionId, requestId, reply);
+    },
+    [ws]
+  );
+
   const clearUnseenSession = useCallback((sessionId: string) => {
     activeSessionIdRef.current = sessionId;
     setUnseenSessionIds((prev) => {
@@ -280,6 +288,7 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
     startSession,
     stopSession,
     respondToControl,
+    respondToPermission,
   };
 
   return <WebSocketContext.Provider value={value}>{children}</WebSocketContext.Provider>;
@@ -419,6 +428,18 @@ export function useRawSessionEvents(sessionId: string | null, enabled = true): A
   return events;
 }
 
+// Hook for reloading a session after missing more events than the server buffers
+export function useSessionResync(sessionId: string | null, onResync: () => void): void {
+  const { ws } = useWebSocket();
+  const onResyncRef = useRef(onResync);
+  onResyncRef.current = onResync;
+
+  useEffect(() => {
+    if (!sessionId) return;
+    return ws.onResync(sessionId, () => onResyncRef.current());
+  }, [sessionId, ws]);
+}
+
 // Hook for managing a session with full controls
 export function useAgentSession(sessionId: string | null) {
   const events = useSessionEvents(sessionId);
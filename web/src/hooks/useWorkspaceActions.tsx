@@ -18,9 +18,9 @@ export function useWorkspaceActions({
   const forkSessionMutation = useForkSession();
   const createPrMutation = useCreateWorkspacePr();
 
-  const handleForkSession = useCallback(() => {
+  const forkSession = useCallback((agentType?: Session['agent_type']) => {
     if (!session) return;
-    forkSessionMutation.mutate(session.id, {
+    forkSessionMutation.mutate({ id: session.id, agentType }, {
       onSuccess: (response) => {
         if (onForkedSession) {
           onForkedSession(response.session, response.workspace);
@@ -39,6 +39,13 @@ export function useWorkspaceActions({
     });
   }, [forkSessionMutation, onForkedSession, sendPrompt, session]);
 
+  const handleForkSession = useCallback(() => forkSession(), [forkSession]);
+
+  const handleHandoffSession = useCallback(
+    (agentType: Session['agent_type']) => forkSession(agentType),
+    [forkSession]
+  );
+
   const handleCreatePr = useCallback(() => {
     if (!session || !workspace) return;
     createPrMutation.mutate(workspace.id, {
@@ -81,6 +88,7 @@ export function useWorkspaceActions({
 
   return {
     handleForkSession,
+    handleHandoffSession,
     handleCreatePr,
     isForking: forkSessionMutation.isPending,
     isCreatingPr: createPrMutation.isPending,
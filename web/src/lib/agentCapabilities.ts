@@ -3,3 +3,18 @@ import type { Session } from '../types';
 export function supportsPlanMode(agentType?: Session['agent_type'] | null): boolean {
   return agentType === 'claude' || agentType === 'codex' || agentType === 'gemini';
 }
+
+export const AGENT_TYPES: Session['agent_type'][] = ['claude', 'codex', 'gemini', 'opencode'];
+
+export function agentDisplayName(agentType: Session['agent_type']): string {
+  switch (agentType) {
+    case 'claude':
+      return 'Claude Code';
+    case 'codex':
+      return 'Codex CLI';
+    case 'gemini':
+      return 'Gemini CLI';
+    case 'opencode':
+      return 'OpenCode';
+  }
+}
@@ -14,12 +14,19 @@ import type {
   ListModelsResponse,
   AgentsResponse,
   CreateRepositoryRequest,
+  AuthStatusResponse,
+  CloneJob,
   UpdateRepositorySettingsRequest,
   CreateWorkspaceRequest,
   CreateSessionRequest,
   UpdateSessionRequest,
   SetDefaultModelRequest,
   WorkspaceStatus,
+  WorkspaceHooksResponse,
+  WorkspaceDiff,
+  DiffBase,
+  DiffScope,
+  ApplyHunkRequest,
   UiState,
   BootstrapResponse,
   SessionEventsQuery,
@@ -32,8 +39,26 @@ import type {
   ListExternalSessionsResponse,
   ImportExternalSessionResponse,
   ForkSessionResponse,
+  Checkpoint,
+  ListCheckpointsResponse,
+  RewindRequest,
+  Race,
+  CreateRaceRequest,
+  CreateRaceResponse,
   PrPreflightResponse,
   PrCreateResponse,
+  CommitPreviewResponse,
+  CommitMessageResponse,
+  CommitRequest,
+  CommitResponse,
+  ReviewCommentsResponse,
+  ReviewPromptRequest,
+  ReviewPromptResponse,
+  SetReviewCommentsAddressedRequest,
+  CiFixResponse,
+  SyncStatusResponse,
+  SyncRequest,
+  SyncOutcomeResponse,
   ArchivePreflightResponse,
   ArchiveWorkspaceRequest,
   RepositoryRemovePreflightResponse,
@@ -43,8 +68,10 @@ import type {
   AddOnboardingProjectRequest,
   AddOnboardingProjectResponse,
   FileContentResponse,
+  UsageStats,
 } from '../types';
 import type { Theme, ThemeListResponse } from './themes';
+import { getAuthToken, clearAuthToken, notifyUnauthorized } from './auth';
 
 const API_BASE = '/api';
 
@@ -59,14 +86,21 @@ export class ApiError extends Error {
 }
 
 async function request<T>(path: string, options?: RequestInit): Promise<T> {
+  const token = getAuthToken();
   const response = await fetch(`${API_BASE}${path}`, {
     ...options,
     headers: {
       'Content-Type': 'application/json',
+      ...(token ? { Authorization: `Bearer ${token}` } : {}),
       ...options?.headers,
     },
   });
 
+  if (response.status === 401) {
+    clearAuthToken();
+    notifyUnauthorized();
+  }
+
   if (!response.ok) {
     const errorText = await response.text();
     let message = errorText;
@@ -101,6 +135,11 @@ export async function getHealth(): Promise<HealthResponse> {
   return request('/health');
 }
 
+// Auth
+export async function getAuthStatus(): Promise<AuthStatusResponse> {
+  return request('/auth/status');
+}
+
 // Bootstrap
 export async function getBootstrap(): Promise<BootstrapResponse> {
   return request('/bootstrap');
@@ -151,6 +190,14 @@ export async function removeRepository(id: string): Promise<RepositoryRemoveResp
   return request(`/repositories/${id}/remove`, { method: 'POST' });
 }
 
+export async function getCloneStatus(id: string): Promise<CloneJob> {
+  return request(`/repositories/${id}/clone`);
+}
+
+export async function retryClone(id: string): Promise<CloneJob> {
+  return request(`/repositories/${id}/clone`, { method: 'POST' });
+}
+
 // Workspaces
 export async function getWorkspaces(): Promise<Workspace[]> {
   const response = await request<ListWorkspacesResponse>('/workspaces');
@@ -298,6 +345,33 @@ export async function getWorkspaceStatus(id: string): Promise<WorkspaceStatus> {
   return request(`/workspaces/${id}/status`);
 }
 
+// Output of the workspace's latest lifecycle hook runs
+export async function getWorkspaceHooks(id: string): Promise<WorkspaceHooksResponse> {
+  return request(`/workspaces/${id}/hooks`);
+}
+
+export async function getWorkspaceDiff(
+  id: string,
+  base?: DiffBase,
+  scope?: DiffScope
+): Promise<WorkspaceDiff> {
+  const params = new URLSearchParams();
+  if (base) params.set('base', base);
+  if (scope) params.set('scope', scope);
+  const query = params.toString();
+  return request(`/workspaces/${id}/diff${query ? `?${query}` : ''}`);
+}
+
+export async function applyWorkspaceHunk(
+  id: string,
+  data: ApplyHunkRequest
+): Promise<WorkspaceDiff> {
+  return request(`/workspaces/${id}/diff/hunks`, {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+}
+
 export async function getWorkspacePrPreflight(id: string): Promise<PrPreflightResponse> {
   return request(`/workspaces/${id}/pr/preflight`);
 }
@@ -306,6 +380,77 @@ export async function createWorkspacePr(id: string): Promise<PrCreateResponse> {
   return request(`/workspaces/${id}/pr`, { method: 'POST' });
 }
 
+export async function getCommitPreview(id: string): Promise<CommitPreviewResponse> {
+  return request(`/workspaces/${id}/commit`);
+}
+
+// Generate a commit message from the workspace's pending changes
+export async function generateCommitMessage(id: string): Promise<CommitMessageResponse> {
+  return request(`/workspaces/${id}/commit/message`, { method: 'POST' });
+}
+
+export async function commitWorkspace(id: string, data: CommitRequest): Promise<CommitResponse> {
+  return request(`/workspaces/${id}/commit`, {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+}
+
+// Unresolved review comments on the workspace's PR
+export async function getReviewComments(id: string): Promise<ReviewCommentsResponse> {
+  return request(`/workspaces/${id}/review-comments`);
+}
+
+// Build the agent prompt for selected review comments (marks them addressed by default)
+export async function buildReviewPrompt(
+  id: string,
+  data: ReviewPromptRequest
+): Promise<ReviewPromptResponse> {
+  return request(`/workspaces/${id}/review-comments/prompt`, {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+}
+
+export async function setReviewCommentsAddressed(
+  id: string,
+  data: SetReviewCommentsAddressedRequest
+): Promise<void> {
+  return request(`/workspaces/${id}/review-comments/addressed`, {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+}
+
+// Failing CI checks on the workspace's PR and a prompt asking the agent to fix them
+export async function buildCiFixPrompt(id: string): Promise<CiFixResponse> {
+  return request(`/workspaces/${id}/ci/fix`, { method: 'POST' });
+}
+
+// Ahead/behind counts against the base branch and any sync in progress
+export async function getSyncStatus(id: string): Promise<SyncStatusResponse> {
+  return request(`/workspaces/${id}/sync`);
+}
+
+// Merge in or rebase onto the base branch; conflicts come back with a prompt for the agent
+export async function syncWorkspace(
+  id: string,
+  data: SyncRequest = {}
+): Promise<SyncOutcomeResponse> {
+  return request(`/workspaces/${id}/sync`, {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+}
+
+export async function continueSync(id: string): Promise<SyncOutcomeResponse> {
+  return request(`/workspaces/${id}/sync/continue`, { method: 'POST' });
+}
+
+export async function abortSync(id: string): Promise<void> {
+  return request(`/workspaces/${id}/sync/abort`, { method: 'POST' });
+}
+
 // Auto-create workspace (generates name/branch automatically)
 export async function autoCreateWorkspace(repositoryId: string): Promise<Workspace> {
   return request(`/repositories/${repositoryId}/workspaces/auto`, {
@@ -334,12 +479,58 @@ export async function importExternalSession(id: string): Promise<ImportExternalS
 }
 
 // Fork session
-export async function forkSession(id: string): Promise<ForkSessionResponse> {
-  return request(`/sessions/${id}/fork`, {
+// Passing an agent type hands the session off to that agent
+export async function forkSession(
+  id: string,
+  agentType?: Session['agent_type']
+): Promise<ForkSessionResponse> {
+  const query = agentType ? `?agent_type=${agentType}` : '';
+  return request(`/sessions/${id}/fork${query}`, {
     method: 'POST',
   });
 }
 
+// Checkpoints
+export async function getSessionCheckpoints(id: string): Promise<Checkpoint[]> {
+  const response = await request<ListCheckpointsResponse>(`/sessions/${id}/checkpoints`);
+  return response.checkpoints;
+}
+
+// Restore the session's workspace to before a turn (default: the latest turn)
+export async function rewindSession(id: string, data: RewindRequest = {}): Promise<Checkpoint> {
+  return request(`/sessions/${id}/rewind`, {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+}
+
+// Races
+export async function createRace(data: CreateRaceRequest): Promise<CreateRaceResponse> {
+  return request('/races', {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+}
+
+export async function getRace(id: string): Promise<Race> {
+  return request(`/races/${id}`);
+}
+
+// Run the configured race test command in every contender's workspace
+export async function runRaceTests(id: string): Promise<Race> {
+  return request(`/races/${id}/tests`, {
+    method: 'POST',
+  });
+}
+
+// Keep one contender and archive the others
+export async function pickRaceWinner(id: string, workspaceId: string): Promise<Race> {
+  return request(`/races/${id}/winner`, {
+    method: 'POST',
+    body: JSON.stringify({ workspace_id: workspaceId }),
+  });
+}
+
 // Themes
 export async function getThemes(): Promise<ThemeListResponse> {
   return request('/themes');
@@ -403,3 +594,9 @@ export async function getFileContent(
     body: JSON.stringify({ path: filePath }),
   });
 }
+
+// Usage analytics
+export async function getUsageStats(days?: number): Promise<UsageStats> {
+  const params = days ? `?days=${days}` : '';
+  return request(`/stats${params}`);
+}
@@ -0,0 +1,31 @@
+// API token storage for servers started with authentication
+
+const TOKEN_KEY = 'conduit.apiToken';
+
+// Dispatched on window when the server rejects the stored token
+export const UNAUTHORIZED_EVENT = 'conduit:unauthorized';
+
+export function getAuthToken(): string | null {
+  return localStorage.getItem(TOKEN_KEY);
+}
+
+export function setAuthToken(token: string): void {
+  localStorage.setItem(TOKEN_KEY, token);
+}
+
+export function clearAuthToken(): void {
+  localStorage.removeItem(TOKEN_KEY);
+}
+
+// `conduit serve` prints a login link ending in `#token=...`; take the token
+// and drop it from the address bar so it does not end up in history.
+export function takeTokenFromUrl(): void {
+  const match = window.location.hash.match(/^#token=([^&]+)/);
+  if (!match) return;
+  setAuthToken(decodeURIComponent(match[1]));
+  window.history.replaceState(null, '', window.location.pathname + window.location.search);
+}
+
+export function notifyUnauthorized(): void {
+  window.dispatchEvent(new Event(UNAUTHORIZED_EVENT));
+}
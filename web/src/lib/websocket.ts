@@ -1,6 +1,7 @@
 // WebSocket client for real-time agent communication
 
-import type { ClientMessage, ServerMessage, AgentEvent, ImageAttachment } from '../types';
+import type { ClientMessage, ServerMessage, AgentEvent, ImageAttachment, PermissionReply } from '../types';
+import { getAuthToken } from './auth';
 
 export type ConnectionState = 'connecting' | 'connected' | 'disconnected' | 'error';
 
@@ -23,6 +24,11 @@ export class ConduitWebSocket {
   private shouldReconnect = true;
   private messageHandlers: Map<string, Set<(event: AgentEvent) => void>> = new Map();
   private activeSubscriptions: Set<string> = new Set();
+  // Newest event seen per session, so a reconnect replays only what was missed
+  private lastSeqs: Map<string, number> = new Map();
+  // Event log each session's sequence numbers belong to
+  private epochs: Map<string, string> = new Map();
+  private resyncHandlers: Map<string, Set<() => void>> = new Map();
 
   constructor(url: string, options: WebSocketOptions = {}) {
     this.url = url;
@@ -50,7 +56,9 @@ export class ConduitWebSocket {
 
     this.shouldReconnect = true;
 
-    this.ws = new WebSocket(this.url);
+    // Browsers cannot set headers on the upgrade request, so the token goes in the query
+    const token = getAuthToken();
+    this.ws = new WebSocket(token ? `${this.url}?token=${encodeURIComponent(token)}` : this.url);
 
     this.ws.onopen = () => {
       this.reconnectAttempts = 0;
@@ -121,6 +129,8 @@ export class ConduitWebSocket {
         handlers.delete(handler);
         if (handlers.size === 0) {
           this.messageHandlers.delete(sessionId);
+          this.lastSeqs.delete(sessionId);
+          this.epochs.delete(sessionId);
           if (this.activeSubscriptions.has(sessionId)) {
             this.activeSubscriptions.delete(sessionId);
             this.send({ type: 'unsubscribe', session_id: sessionId });
@@ -130,6 +140,25 @@ export class ConduitWebSocket {
     };
   }
 
+  // Called when events were missed beyond the server's buffer and the
+  // session has to be reloaded from the API
+  onResync(sessionId: string, handler: () => void): () => void {
+    if (!this.resyncHandlers.has(sessionId)) {
+      this.resyncHandlers.set(sessionId, new Set());
+    }
+    this.resyncHandlers.get(sessionId)!.add(handler);
+
+    return () => {
+      const handlers = this.resyncHandlers.get(sessionId);
+      if (handlers) {
+        handlers.delete(handler);
+        if (handlers.size === 0) {
+          this.resyncHandlers.delete(sessionId);
+        }
+      }
+    };
+  }
+
   // Start a new session
   startSession(
     sessionId: string,
@@ -170,8 +199,34 @@ export class ConduitWebSocket {
     });
   }
 
+  // Answer a tool permission prompt
+  respondToPermission(sessionId: string, requestId: string, reply: PermissionReply): void {
+    this.send({
+      type: 'respond_to_permission',
+      session_id: sessionId,
+      request_id: requestId,
+      reply,
+    });
+  }
+
   private handleMessage(message: ServerMessage): void {
+    if (message.type === 'subscribed') {
+      this.lastSeqs.set(message.session_id, message.last_seq ?? 0);
+      if (message.epoch !== undefined) {
+        this.epochs.set(message.session_id, message.epoch);
+      }
+      if (message.resync_required) {
+        this.resyncHandlers.get(message.session_id)?.forEach((handler) => handler());
+      }
+    }
+
     if (message.type === 'agent_event') {
+      if (message.seq !== undefined) {
+        this.lastSeqs.set(message.session_id, message.seq);
+      }
+      if (message.epoch !== undefined) {
+        this.epochs.set(message.session_id, message.epoch);
+      }
       const handlers = this.messageHandlers.get(message.session_id);
       if (handlers) {
         handlers.forEach((handler) => handler(message.event));
@@ -212,7 +267,12 @@ export class ConduitWebSocket {
     }
     this.activeSubscriptions.clear();
     for (const sessionId of this.messageHandlers.keys()) {
-      this.send({ type: 'subscribe', session_id: sessionId });
+      this.send({
+        type: 'subscribe',
+        session_id: sessionId,
+        since_seq: this.lastSeqs.get(sessionId),
+        epoch: this.epochs.get(sessionId),
+      });
       this.activeSubscriptions.add(sessionId);
     }
   }
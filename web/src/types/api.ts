@@ -2,6 +2,8 @@
 
 export type WorkspaceMode = 'worktree' | 'checkout';
 
+export type SyncStrategy = 'merge' | 'rebase';
+
 export interface Repository {
   id: string;
   name: string;
@@ -13,6 +15,8 @@ export interface Repository {
   archive_delete_branch_effective: boolean;
   archive_remote_prompt: boolean | null;
   archive_remote_prompt_effective: boolean;
+  sync_strategy: SyncStrategy | null;
+  sync_strategy_effective: SyncStrategy;
   created_at: string;
   updated_at: string;
 }
@@ -79,6 +83,12 @@ export interface HealthResponse {
   version: string;
 }
 
+export interface AuthStatusResponse {
+  auth_required: boolean;
+  // Whether the request carried a valid token
+  authenticated: boolean;
+}
+
 export interface ListRepositoriesResponse {
   repositories: Repository[];
 }
@@ -101,10 +111,24 @@ export interface CreateRepositoryRequest {
   repository_url?: string;
 }
 
+export type CloneStatus = 'cloning' | 'succeeded' | 'failed';
+
+export interface CloneJob {
+  status: CloneStatus;
+  destination: string;
+  // Current git step, e.g. "Receiving objects"
+  phase: string | null;
+  percent: number | null;
+  error: string | null;
+  started_at: string;
+  finished_at: string | null;
+}
+
 export interface UpdateRepositorySettingsRequest {
   workspace_mode?: WorkspaceMode;
   archive_delete_branch?: boolean;
   archive_remote_prompt?: boolean;
+  sync_strategy?: SyncStrategy;
 }
 
 export interface CreateWorkspaceRequest {
@@ -206,6 +230,68 @@ export interface ForkSessionResponse {
   seed_prompt: string;
 }
 
+export interface Checkpoint {
+  id: number;
+  session_id: string;
+  workspace_id: string | null;
+  turn: number;
+  commit_sha: string;
+  ref_name: string;
+  created_at: string;
+}
+
+export interface ListCheckpointsResponse {
+  checkpoints: Checkpoint[];
+}
+
+export interface RewindRequest {
+  turn?: number;
+  truncate_transcript?: boolean;
+}
+
+export interface RaceTestResult {
+  passed: boolean;
+  output: string;
+}
+
+export interface RaceEntry {
+  workspace_id: string;
+  workspace_name: string;
+  branch: string;
+  agent_type: string;
+  model: string | null;
+  session_id: string | null;
+  archived: boolean;
+  running: boolean;
+  diff_stats: GitDiffStats | null;
+  usage: UsageTotals;
+  test_result: RaceTestResult | null;
+}
+
+export interface Race {
+  id: string;
+  repository_id: string;
+  prompt: string;
+  base_branch: string;
+  base_commit: string;
+  winner_workspace_id: string | null;
+  created_at: string;
+  entries: RaceEntry[];
+}
+
+export interface CreateRaceRequest {
+  workspace_id: string;
+  prompt: string;
+  // "agent[:model]" specs; defaults to [race] contenders from config
+  contenders?: string[];
+}
+
+export interface CreateRaceResponse {
+  race: Race;
+  // One session per contender, in race order; start each with the race prompt
+  sessions: Session[];
+}
+
 export interface GitDiffStats {
   additions: number;
   deletions: number;
@@ -233,6 +319,75 @@ export interface WorkspaceStatus {
   updated_at?: string;
 }
 
+export type HookEvent = 'on_create' | 'before_archive' | 'after_archive';
+
+export type HookRunStatus = 'running' | 'succeeded' | 'failed';
+
+export interface HookRun {
+  event: HookEvent;
+  status: HookRunStatus;
+  output: string[];
+  // Which step failed and why
+  error: string | null;
+  started_at: string;
+  finished_at: string | null;
+}
+
+export interface WorkspaceHooksResponse {
+  runs: HookRun[];
+}
+
+export type DiffBase = 'head' | 'base_branch';
+
+export type DiffScope = 'all' | 'last_turn';
+
+export type HunkAction = 'stage' | 'revert';
+
+export interface DiffLine {
+  kind: 'context' | 'added' | 'removed';
+  old_line: number | null;
+  new_line: number | null;
+  content: string;
+  no_newline?: boolean;
+}
+
+export interface DiffHunk {
+  old_start: number;
+  old_lines: number;
+  new_start: number;
+  new_lines: number;
+  section: string;
+  lines: DiffLine[];
+  staged: boolean;
+  // Identifies the hunk's exact contents; send it back when applying
+  hash: string;
+}
+
+export interface ApplyHunkRequest {
+  path: string;
+  hunk_index: number;
+  action: HunkAction;
+  // Required for 'revert'
+  hash?: string;
+}
+
+export interface FileDiff {
+  path: string;
+  old_path?: string;
+  kind: 'added' | 'modified' | 'deleted' | 'renamed' | 'untracked';
+  binary: boolean;
+  additions: number;
+  deletions: number;
+  hunks: DiffHunk[];
+}
+
+export interface WorkspaceDiff {
+  base: DiffBase;
+  base_ref: string;
+  base_branch?: string;
+  files: FileDiff[];
+}
+
 export interface PrPreflightResponse {
   gh_installed: boolean;
   gh_authenticated: boolean;
@@ -249,6 +404,124 @@ export interface PrCreateResponse {
   prompt: string;
 }
 
+export interface ChangedFile {
+  path: string;
+  // Porcelain status code, e.g. 'M ', ' M', '??'
+  status: string;
+  staged: boolean;
+  unstaged: boolean;
+}
+
+export interface CommitPreviewResponse {
+  files: ChangedFile[];
+  // Only staged changes are committed when anything is staged
+  staged_only: boolean;
+}
+
+export interface CommitMessageResponse {
+  message: string;
+  tool_used: string;
+  used_fallback: boolean;
+}
+
+export interface CommitRequest {
+  // Generated from the diff when omitted
+  message?: string;
+  push?: boolean;
+}
+
+export interface CommitResponse {
+  commit_sha: string;
+  message: string;
+  pushed: boolean;
+  push_error: string | null;
+  tool_used: string | null;
+}
+
+export interface ReviewComment {
+  id: string;
+  thread_id: string;
+  path: string;
+  line: number | null;
+  author: string;
+  body: string;
+  url: string;
+  // The code the thread points at has changed since
+  outdated: boolean;
+  // Already sent to the agent
+  addressed: boolean;
+}
+
+export interface ReviewCommentsResponse {
+  pr_number: number;
+  pr_url: string;
+  comments: ReviewComment[];
+}
+
+export interface ReviewPromptRequest {
+  comment_ids: string[];
+  // Defaults to true
+  mark_addressed?: boolean;
+}
+
+export interface ReviewPromptResponse {
+  prompt: string;
+}
+
+export interface SetReviewCommentsAddressedRequest {
+  comment_ids: string[];
+  addressed: boolean;
+}
+
+export interface FailedCheck {
+  name: string;
+  workflow: string | null;
+  description: string | null;
+  link: string | null;
+  // Lines of the log that look like errors
+  error_lines: string[];
+  has_log: boolean;
+}
+
+export interface ConflictedFile {
+  path: string;
+  // Conflict regions with their markers (empty for binary or deleted files)
+  hunks: string[];
+}
+
+export interface SyncStatusResponse {
+  base: string;
+  // Strategy a new sync would use
+  strategy: SyncStrategy;
+  in_progress: SyncStrategy | null;
+  conflicts: ConflictedFile[];
+  commits_ahead: number;
+  commits_behind: number;
+}
+
+export interface SyncRequest {
+  // Overrides the repository's strategy
+  strategy?: SyncStrategy;
+}
+
+export interface SyncOutcomeResponse {
+  result: 'up_to_date' | 'synced' | 'conflicts';
+  base: string;
+  strategy: SyncStrategy | null;
+  conflicts: ConflictedFile[];
+  // Prompt asking the agent to resolve the conflicts
+  prompt: string | null;
+}
+
+export interface CiFixResponse {
+  pr_number: number;
+  pr_url: string;
+  // Checks still running
+  pending: number;
+  checks: FailedCheck[];
+  prompt: string;
+}
+
 export interface QueuedImageAttachment {
   path: string;
   placeholder: string;
@@ -358,3 +631,32 @@ export interface FileContentResponse {
   media_type: string;
   exists: boolean;
 }
+
+// Usage analytics types
+export interface UsageTotals {
+  turns: number;
+  input_tokens: number;
+  output_tokens: number;
+  cached_tokens: number;
+  cache_write_tokens: number;
+  cost_usd: number;
+  duration_ms: number;
+  timed_turns: number;
+}
+
+export interface UsageRow {
+  key: string | null;
+  label: string;
+  totals: UsageTotals;
+}
+
+export interface UsageStats {
+  days: number | null;
+  since: string | null;
+  totals: UsageTotals;
+  by_day: UsageRow[];
+  by_agent: UsageRow[];
+  by_model: UsageRow[];
+  by_repository: UsageRow[];
+  by_workspace: UsageRow[];
+}
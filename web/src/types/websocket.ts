@@ -3,7 +3,7 @@
 // Client -> Server messages
 export type ClientMessage =
   | { type: 'ping' }
-  | { type: 'subscribe'; session_id: string }
+  | { type: 'subscribe'; session_id: string; since_seq?: number; epoch?: string }
   | { type: 'unsubscribe'; session_id: string }
   | {
       type: 'start_session';
@@ -16,12 +16,13 @@ export type ClientMessage =
     }
   | { type: 'send_input'; session_id: string; input: string; hidden?: boolean; images?: ImageAttachment[] }
   | { type: 'respond_to_control'; session_id: string; request_id: string; response: unknown }
+  | { type: 'respond_to_permission'; session_id: string; request_id: string; reply: PermissionReply }
   | { type: 'stop_session'; session_id: string };
 
 // Server -> Client messages
 export type ServerMessage =
   | { type: 'pong' }
-  | { type: 'subscribed'; session_id: string }
+  | { type: 'subscribed'; session_id: string; epoch?: string; last_seq: number; resync_required: boolean }
   | { type: 'unsubscribed'; session_id: string }
   | { type: 'session_started'; session_id: string; agent_type: string; agent_session_id: string | null }
   | {
@@ -31,7 +32,7 @@ export type ServerMessage =
       workspace_id: string | null;
       workspace_branch: string | null;
     }
-  | { type: 'agent_event'; session_id: string; event: AgentEvent }
+  | { type: 'agent_event'; session_id: string; seq?: number; epoch?: string; event: AgentEvent }
   | { type: 'session_ended'; session_id: string; reason: string; error: string | null }
   | { type: 'error'; message: string; session_id: string | null };
 
@@ -45,7 +46,15 @@ export type AgentEvent =
   | { type: 'AssistantReasoning'; text: string }
   | { type: 'ToolStarted'; tool_name: string; tool_id: string; arguments: unknown }
   | { type: 'ToolCompleted'; tool_id: string; success: boolean; result: string | null; error: string | null }
-  | { type: 'ControlRequest'; request_id: string; tool_name: string; tool_use_id: string | null; input: unknown }
+  | {
+      type: 'ControlRequest';
+      request_id: string;
+      tool_name: string;
+      tool_use_id: string | null;
+      input: unknown;
+      permission?: PermissionRequest | null;
+    }
+  | { type: 'PermissionDecided'; request_id: string; permission: PermissionRequest; decision: 'allow' | 'deny' }
   | { type: 'FileChanged'; path: string; operation: 'create' | 'update' | 'delete' }
   | { type: 'CommandOutput'; command: string; output: string; exit_code: number | null; is_streaming: boolean }
   | { type: 'TokenUsage'; usage: TokenUsage; context_window: number | null; usage_percent: number | null }
@@ -64,6 +73,14 @@ export interface ImageAttachment {
   media_type: string;
 }
 
+export interface PermissionRequest {
+  tool: string;
+  command?: string;
+  paths?: string[];
+}
+
+export type PermissionReply = 'allow_once' | 'always_allow' | 'deny';
+
 export interface TokenUsage {
   input_tokens: number;
   output_tokens: number;